@@ -1,8 +1,8 @@
 //! BTC Exchange Dashboard - WASM Entry Point
 
 use dash_components::Dashboard;
-use dash_state::provide_app_state;
-use dash_websocket::{use_websocket, WsConfig, ExponentialBackoff};
+use dash_state::{provide_app_state, watchlist_diff, Theme};
+use dash_websocket::{check_protocol, load_depth, load_symbols, use_candle_backfill, use_compare_backfill, use_grid_backfill, use_tab_sync, ExponentialBackoff, WsClient, WsConfig};
 use leptos::prelude::*;
 use wasm_bindgen::JsCast;
 
@@ -12,15 +12,153 @@ fn App() -> impl IntoView {
 
     let ws_config = WsConfig::new(get_ws_url())
         .with_policy(ExponentialBackoff::aggressive())
-        .heartbeat(30000);
+        .idle_timeout(45_000);
 
-    let _ws_handle = use_websocket(state.clone(), Some(ws_config.url.clone()));
+    // Secondary tabs consume the primary tab's feed instead of connecting;
+    // pop-out windows keep their own connection so they survive on their own
+    let popout = state.is_popout();
+    let tab_sync = if popout { None } else { use_tab_sync(state.clone(), true) };
+    let _ws_handle = WsClient::with_config(state.clone(), ws_config)
+        .with_tab_sync(tab_sync)
+        .connect();
+
+    // Flag a server that has moved on to a newer protocol
+    check_protocol(state.clone(), dash_websocket::DEFAULT_API_URL);
+    // Symbol list for the selector (live stats then follow the ticker feed)
+    load_symbols(state.clone(), dash_websocket::DEFAULT_API_URL);
+    // The book to draw until the feed delivers one
+    load_depth(state.clone(), dash_websocket::DEFAULT_API_URL);
+    // Older candles for timeframes the live feed hasn't filled yet
+    use_candle_backfill(state.clone(), dash_websocket::DEFAULT_API_URL);
+    use_compare_backfill(state.clone(), dash_websocket::DEFAULT_API_URL);
+    use_grid_backfill(state.clone(), dash_websocket::DEFAULT_API_URL);
+
+    // Advance the staleness clock so quiet streams get flagged, expire old
+    // trades from the rolling indicators, time out toasts and publish the
+    // status bar telemetry
+    let freshness = state.market.freshness;
+    let indicators = state.market.indicators;
+    let notifications = state.notifications;
+    let telemetry = state.telemetry;
+    set_interval(
+        move || {
+            freshness.tick();
+            let now = freshness.now.get_untracked();
+            indicators.prune(now);
+            notifications.expire(now);
+            telemetry.tick(now);
+        },
+        std::time::Duration::from_secs(1),
+    );
+
+    // Keep server subscriptions in step with the mini ticker watchlist, the
+    // symbol compared on the chart and the symbols in the chart grid
+    let symbols = state.symbols;
+    let compare = state.compare.symbol;
+    let grid_symbols = state.grid.symbols;
+    let rpc = state.rpc.clone();
+    Effect::new(move |previous: Option<Vec<dash_core::Symbol>>| {
+        let mut watchlist = symbols.watchlist();
+        if let Some(symbol) = compare.get().filter(|s| !watchlist.contains(s)) {
+            watchlist.push(symbol);
+        }
+        grid_symbols.with(|grid| {
+            for symbol in grid {
+                if !watchlist.contains(symbol) {
+                    watchlist.push(symbol.clone());
+                }
+            }
+        });
+        let (added, removed) = watchlist_diff(previous.as_deref().unwrap_or_default(), &watchlist);
+        added.iter().for_each(|symbol| rpc.subscribe(symbol));
+        removed.iter().for_each(|symbol| rpc.unsubscribe(symbol));
+        watchlist
+    });
+
+    // Push the active palette onto :root so every var(--...) follows the theme
+    let ui = state.ui;
+    Effect::new(move |_| {
+        let theme = ui.get().theme;
+        apply_theme(theme);
+    });
+    // Canvas and WebGL layers can't read CSS variables, so charts get it too
+    dash_charts::provide_chart_palette(Signal::derive(move || ui.with(|ui| ui.theme.palette())));
+
+    // Ask for the chosen session day start on every connect, since the
+    // server keeps custom ones only until it restarts
+    let connection = state.connection;
+    let rpc = state.rpc.clone();
+    let session_anchor = Memo::new(move |_| ui.with(|ui| ui.session_anchor));
+    Effect::new(move |_| {
+        let anchor = session_anchor.get();
+        if connection.get().is_connected() {
+            rpc.track_session(anchor);
+        }
+    });
+
+    // Keep <html lang> in step so screen readers and fonts pick the language
+    let locale = state.i18n.locale;
+    Effect::new(move |_| {
+        let code = locale.get().code();
+        if let Some(root) = web_sys::window()
+            .and_then(|w| w.document())
+            .and_then(|d| d.document_element())
+        {
+            let _ = root.set_attribute("lang", code);
+        }
+    });
+
+    // Mirror symbol/interval/layout into the URL for bookmarking
+    let url_state = state.clone();
+    Effect::new(move |_| {
+        dash_state::url::write_query(&url_state.url_state().to_query());
+    });
+
+    // Persist theme/layout/favorites/key bindings/sounds/timeframes/overlays whenever they change
+    // (pop-outs only read them, so they never clobber the main window's)
+    if !popout {
+        let settings_state = state.clone();
+        let favorites = state.symbols.favorites;
+        let key_bindings = state.shortcuts.bindings;
+        let sound_prefs = state.sounds.prefs;
+        let chart_intervals = state.chart_intervals;
+        let chart_overlays = state.chart_overlays;
+        let grid_charts = state.grid.charts;
+        Effect::new(move |_| {
+            ui.track();
+            favorites.track();
+            key_bindings.track();
+            sound_prefs.track();
+            chart_intervals.track();
+            chart_overlays.track();
+            grid_charts.track();
+            settings_state.save_settings();
+        });
+
+        let drawings = state.drawings;
+        Effect::new(move |_| {
+            drawings.by_symbol.track();
+            drawings.save();
+        });
+    }
 
     view! {
         <Dashboard />
     }
 }
 
+fn apply_theme(theme: Theme) {
+    let Some(root) = web_sys::window()
+        .and_then(|w| w.document())
+        .and_then(|d| d.document_element())
+    else {
+        return;
+    };
+
+    let _ = root.set_attribute("style", &theme.palette().css_vars());
+    let _ = root.set_attribute("data-theme", theme.css_class());
+}
+
 fn get_ws_url() -> String {
     dash_websocket::DEFAULT_WS_URL.to_string()
 }
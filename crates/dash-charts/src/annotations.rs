@@ -0,0 +1,214 @@
+//! Programmatic markers layered over the candles
+//!
+//! Features that want something on the chart (an alert firing, a
+//! funding time, a maintenance window) add it through a
+//! [`ChartAnnotations`] handle and get an id back to remove it by; the chart
+//! draws whatever the handle holds on its time axis and price scale.
+
+use crate::{
+    chartkit::{BarTimeAxis, PriceScale, Scale},
+    colors,
+};
+use leptos::prelude::*;
+
+/// Marker dot radius (px)
+const MARKER_RADIUS: f64 = 4.0;
+
+/// Region fill opacity
+const REGION_OPACITY: f64 = 0.08;
+
+/// What an annotation marks and where
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum AnnotationKind {
+    /// A point at a time and price
+    Marker { time: i64, price: f64 },
+    /// A vertical line at a time, across the whole plot
+    EventLine { time: i64 },
+    /// A shaded stretch of time (ms), across the whole plot
+    Region { from: i64, to: i64 },
+}
+
+impl AnnotationKind {
+    /// Plot x extent on `time_axis`, clipped to the plot (`None` when off it)
+    pub fn x_span(&self, time_axis: &BarTimeAxis, plot_width: f64) -> Option<(f64, f64)> {
+        let (left, right) = match *self {
+            Self::Marker { time, .. } | Self::EventLine { time } => {
+                let x = time_axis.x_within(time);
+                (x, x)
+            }
+            Self::Region { from, to } => {
+                let (a, b) = (time_axis.x_within(from), time_axis.x_within(to));
+                (a.min(b), a.max(b))
+            }
+        };
+        (right >= 0.0 && left <= plot_width).then(|| (left.max(0.0), right.min(plot_width)))
+    }
+}
+
+/// A labeled annotation on the chart
+#[derive(Debug, Clone, PartialEq)]
+pub struct Annotation {
+    pub id: u64,
+    pub kind: AnnotationKind,
+    pub label: String,
+    pub color: &'static str,
+}
+
+/// Handle to a chart's annotations; cheap to copy into any feature that
+/// marks the chart
+#[derive(Clone, Copy)]
+pub struct ChartAnnotations {
+    items: RwSignal<Vec<Annotation>>,
+    next_id: StoredValue<u64>,
+}
+
+impl ChartAnnotations {
+    pub fn new() -> Self {
+        Self {
+            items: RwSignal::new(Vec::new()),
+            next_id: StoredValue::new(1),
+        }
+    }
+
+    /// Add an annotation; returns its id
+    pub fn add(&self, kind: AnnotationKind, label: impl Into<String>, color: &'static str) -> u64 {
+        let id = self.next_id.get_value();
+        self.next_id.set_value(id + 1);
+        let label = label.into();
+        self.items.update(|items| items.push(Annotation { id, kind, label, color }));
+        id
+    }
+
+    /// Labeled point at `time` and `price`
+    pub fn marker(&self, time: i64, price: f64, label: impl Into<String>) -> u64 {
+        self.add(AnnotationKind::Marker { time, price }, label, colors::css::WARN)
+    }
+
+    /// Labeled vertical line at `time`
+    pub fn event_line(&self, time: i64, label: impl Into<String>) -> u64 {
+        self.add(AnnotationKind::EventLine { time }, label, colors::css::INFO)
+    }
+
+    /// Labeled shading from `from` to `to`
+    pub fn region(&self, from: i64, to: i64, label: impl Into<String>) -> u64 {
+        self.add(AnnotationKind::Region { from, to }, label, colors::css::INFO)
+    }
+
+    /// Move an annotation, keeping its id, label and color
+    pub fn set_kind(&self, id: u64, kind: AnnotationKind) {
+        self.items.update(|items| {
+            if let Some(item) = items.iter_mut().find(|a| a.id == id) {
+                item.kind = kind;
+            }
+        });
+    }
+
+    pub fn remove(&self, id: u64) {
+        self.items.update(|items| items.retain(|a| a.id != id));
+    }
+
+    pub fn clear(&self) {
+        self.items.update(Vec::clear);
+    }
+
+    /// Current annotations, oldest first (tracked)
+    pub fn items(&self) -> Signal<Vec<Annotation>> {
+        self.items.into()
+    }
+}
+
+impl Default for ChartAnnotations {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Annotations drawn over a price plot; those off the plot are skipped
+#[component]
+pub fn AnnotationLayer(
+    annotations: Vec<Annotation>,
+    /// Time across the plot's bars
+    time_axis: BarTimeAxis,
+    /// Price scale of the plot the markers sit on
+    y_scale: PriceScale,
+    plot_width: f64,
+    plot_height: f64,
+) -> impl IntoView {
+    let label = move |x: f64, y: f64, text: String, color: &'static str| {
+        // Labels near the right edge flip to the left of their anchor
+        let flip = x > plot_width - 120.0;
+        view! {
+            <text
+                x={if flip { x - 4.0 } else { x + 4.0 }}
+                y=y
+                text-anchor=if flip { "end" } else { "start" }
+                fill=color
+                font-size="10"
+                font-family="JetBrains Mono, monospace"
+            >
+                {text}
+            </text>
+        }
+    };
+
+    let items = annotations
+        .into_iter()
+        .filter_map(|annotation| {
+            let Annotation { kind, label: text, color, .. } = annotation;
+            let (left, right) = kind.x_span(&time_axis, plot_width)?;
+            Some(match kind {
+                AnnotationKind::Marker { price, .. } => {
+                    let y = y_scale.scale(price);
+                    view! {
+                        <g class="annotation-marker">
+                            <circle cx=left cy=y r=MARKER_RADIUS fill=color stroke=colors::css::BG_VOID stroke-width="1" />
+                            {label(left + MARKER_RADIUS, y - MARKER_RADIUS - 2.0, text, color)}
+                        </g>
+                    }
+                    .into_any()
+                }
+                AnnotationKind::EventLine { .. } => view! {
+                    <g class="annotation-event">
+                        <line x1=left y1="0" x2=left y2=plot_height stroke=color stroke-width="1" stroke-dasharray="4,3" />
+                        {label(left, plot_height - 6.0, text, color)}
+                    </g>
+                }
+                .into_any(),
+                AnnotationKind::Region { .. } => view! {
+                    <g class="annotation-region">
+                        <rect x=left y="0" width={right - left} height=plot_height fill=color fill-opacity=REGION_OPACITY />
+                        {label(left, plot_height - 6.0, text, color)}
+                    </g>
+                }
+                .into_any(),
+            })
+        })
+        .collect_view();
+
+    view! { <g class="chart-annotations" pointer-events="none">{items}</g> }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::chartkit::BandScale;
+
+    #[test]
+    fn test_annotations_by_id_and_on_the_time_axis() {
+        let annotations = ChartAnnotations::new();
+        let alert = annotations.marker(120_000, 100.0, "alert");
+        let funding = annotations.event_line(600_000, "funding");
+        let window = annotations.region(0, 240_000, "window");
+        annotations.remove(alert);
+        let ids: Vec<u64> = annotations.items().get_untracked().iter().map(|a| a.id).collect();
+        assert_eq!(ids, vec![funding, window]);
+
+        // Ten 10px slots from t = 60s, one bar a minute
+        let bands = BandScale::new(10).range(0.0, 100.0).padding(0.0, 0.0);
+        let axis = BarTimeAxis::new(&bands, 60_000, 60_000);
+        assert_eq!(AnnotationKind::EventLine { time: 120_000 }.x_span(&axis, 100.0), Some((10.0, 10.0)));
+        // Regions clip to the plot; wholly off it is skipped
+        assert_eq!(AnnotationKind::Region { from: 0, to: 240_000 }.x_span(&axis, 100.0), Some((0.0, 30.0)));
+        assert_eq!(AnnotationKind::EventLine { time: 2_000_000 }.x_span(&axis, 100.0), None);
+    }
+}
@@ -0,0 +1,492 @@
+//! Rendering backends for the dense, per-bar part of a chart
+//!
+//! Drawing code talks to [`ChartBackend`] in viewBox units. [`SvgBackend`]
+//! turns the calls into SVG nodes; [`CanvasBackend`] paints them onto a 2D
+//! canvas, which stays fast with thousands of bars; [`WebGlBackend`] batches
+//! them into triangles for a single WebGL2 draw, for the heatmap and very
+//! long histories. Axes, labels and the crosshair stay on SVG or 2D layers
+//! either way, so pointer hit-testing is shared.
+
+use crate::ChartDimensions;
+pub use dash_core::RenderBackend;
+use js_sys::Float32Array;
+use leptos::prelude::*;
+use wasm_bindgen::{JsCast, JsValue};
+use web_sys::{
+    CanvasRenderingContext2d, HtmlCanvasElement, WebGl2RenderingContext as Gl, WebGlBuffer, WebGlProgram,
+    WebGlUniformLocation,
+};
+
+/// Drawing primitives every backend supports (coordinates in viewBox units)
+pub trait ChartBackend {
+    /// Filled rectangle
+    fn rect(&mut self, x: f64, y: f64, width: f64, height: f64, fill: &str);
+    /// Straight stroke between two points
+    fn line(&mut self, from: (f64, f64), to: (f64, f64), stroke: &str, width: f64);
+    /// Open stroke through `points`
+    fn polyline(&mut self, points: &[(f64, f64)], stroke: &str, width: f64);
+    /// Region between `points` (ascending x) and the horizontal line at
+    /// `baseline`, filled at `opacity`
+    fn area(&mut self, points: &[(f64, f64)], baseline: f64, fill: &str, opacity: f64);
+}
+
+/// Collects draw calls as SVG elements
+#[derive(Default)]
+pub struct SvgBackend {
+    nodes: Vec<AnyView>,
+}
+
+impl SvgBackend {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn into_view(self) -> AnyView {
+        self.nodes.into_iter().collect_view().into_any()
+    }
+}
+
+fn points_attr(points: &[(f64, f64)]) -> String {
+    points.iter().map(|(x, y)| format!("{:.2},{:.2}", x, y)).collect::<Vec<_>>().join(" ")
+}
+
+/// `points` closed down to `baseline` at both ends
+fn area_outline(points: &[(f64, f64)], baseline: f64) -> Vec<(f64, f64)> {
+    let (Some(&(first_x, _)), Some(&(last_x, _))) = (points.first(), points.last()) else {
+        return Vec::new();
+    };
+    let mut outline = points.to_vec();
+    outline.extend([(last_x, baseline), (first_x, baseline)]);
+    outline
+}
+
+impl ChartBackend for SvgBackend {
+    fn rect(&mut self, x: f64, y: f64, width: f64, height: f64, fill: &str) {
+        let fill = fill.to_string();
+        self.nodes.push(view! { <rect x=x y=y width=width height=height fill=fill /> }.into_any());
+    }
+
+    fn line(&mut self, from: (f64, f64), to: (f64, f64), stroke: &str, width: f64) {
+        let stroke = stroke.to_string();
+        self.nodes.push(
+            view! { <line x1=from.0 y1=from.1 x2=to.0 y2=to.1 stroke=stroke stroke-width=width /> }.into_any(),
+        );
+    }
+
+    fn polyline(&mut self, points: &[(f64, f64)], stroke: &str, width: f64) {
+        let stroke = stroke.to_string();
+        self.nodes.push(
+            view! { <polyline points=points_attr(points) fill="none" stroke=stroke stroke-width=width /> }
+                .into_any(),
+        );
+    }
+
+    fn area(&mut self, points: &[(f64, f64)], baseline: f64, fill: &str, opacity: f64) {
+        let fill = fill.to_string();
+        let outline = points_attr(&area_outline(points, baseline));
+        self.nodes.push(view! { <polygon points=outline fill=fill fill-opacity=opacity stroke="none" /> }.into_any());
+    }
+}
+
+/// Paints draw calls onto a 2D context prepared by [`fit_canvas`]
+pub struct CanvasBackend<'a> {
+    ctx: &'a CanvasRenderingContext2d,
+}
+
+impl<'a> CanvasBackend<'a> {
+    pub fn new(ctx: &'a CanvasRenderingContext2d) -> Self {
+        Self { ctx }
+    }
+
+    fn trace(&self, points: &[(f64, f64)]) {
+        self.ctx.begin_path();
+        for (i, &(x, y)) in points.iter().enumerate() {
+            if i == 0 {
+                self.ctx.move_to(x, y);
+            } else {
+                self.ctx.line_to(x, y);
+            }
+        }
+    }
+}
+
+impl ChartBackend for CanvasBackend<'_> {
+    fn rect(&mut self, x: f64, y: f64, width: f64, height: f64, fill: &str) {
+        self.ctx.set_fill_style_str(fill);
+        self.ctx.fill_rect(x, y, width, height);
+    }
+
+    fn line(&mut self, from: (f64, f64), to: (f64, f64), stroke: &str, width: f64) {
+        self.polyline(&[from, to], stroke, width);
+    }
+
+    fn polyline(&mut self, points: &[(f64, f64)], stroke: &str, width: f64) {
+        self.trace(points);
+        self.ctx.set_stroke_style_str(stroke);
+        self.ctx.set_line_width(width);
+        self.ctx.stroke();
+    }
+
+    fn area(&mut self, points: &[(f64, f64)], baseline: f64, fill: &str, opacity: f64) {
+        self.trace(&area_outline(points, baseline));
+        self.ctx.close_path();
+        self.ctx.set_global_alpha(opacity);
+        self.ctx.set_fill_style_str(fill);
+        self.ctx.fill();
+        self.ctx.set_global_alpha(1.0);
+    }
+}
+
+/// Floats per vertex: x, y, then straight RGBA
+const FLOATS_PER_VERTEX: usize = 6;
+
+/// Batches draw calls into colored triangles for [`GlSurface::draw`]
+#[derive(Debug, Default)]
+pub struct WebGlBackend {
+    vertices: Vec<f32>,
+}
+
+impl WebGlBackend {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn vertex_count(&self) -> usize {
+        self.vertices.len() / FLOATS_PER_VERTEX
+    }
+
+    fn quad(&mut self, corners: [(f64, f64); 4], color: [f32; 4]) {
+        let [a, b, c, d] = corners;
+        for (x, y) in [a, b, c, a, c, d] {
+            self.vertices.extend([x as f32, y as f32]);
+            self.vertices.extend(color);
+        }
+    }
+
+    /// Segment as a quad `width` thick (no joins; fine at chart line widths)
+    fn segment(&mut self, from: (f64, f64), to: (f64, f64), width: f64, color: [f32; 4]) {
+        let (dx, dy) = (to.0 - from.0, to.1 - from.1);
+        let length = dx.hypot(dy);
+        if length == 0.0 {
+            return;
+        }
+        let (nx, ny) = (-dy / length * width / 2.0, dx / length * width / 2.0);
+        self.quad(
+            [(from.0 + nx, from.1 + ny), (to.0 + nx, to.1 + ny), (to.0 - nx, to.1 - ny), (from.0 - nx, from.1 - ny)],
+            color,
+        );
+    }
+}
+
+impl ChartBackend for WebGlBackend {
+    fn rect(&mut self, x: f64, y: f64, width: f64, height: f64, fill: &str) {
+        if let Some(color) = parse_color(fill) {
+            self.quad([(x, y), (x + width, y), (x + width, y + height), (x, y + height)], color);
+        }
+    }
+
+    fn line(&mut self, from: (f64, f64), to: (f64, f64), stroke: &str, width: f64) {
+        if let Some(color) = parse_color(stroke) {
+            self.segment(from, to, width, color);
+        }
+    }
+
+    fn polyline(&mut self, points: &[(f64, f64)], stroke: &str, width: f64) {
+        if let Some(color) = parse_color(stroke) {
+            for pair in points.windows(2) {
+                self.segment(pair[0], pair[1], width, color);
+            }
+        }
+    }
+
+    fn area(&mut self, points: &[(f64, f64)], baseline: f64, fill: &str, opacity: f64) {
+        let Some([r, g, b, a]) = parse_color(fill) else {
+            return;
+        };
+        let color = [r, g, b, a * opacity as f32];
+        for pair in points.windows(2) {
+            let ((x0, y0), (x1, y1)) = (pair[0], pair[1]);
+            self.quad([(x0, y0), (x1, y1), (x1, baseline), (x0, baseline)], color);
+        }
+    }
+}
+
+const VERTEX_SHADER: &str = r#"#version 300 es
+uniform vec4 u_transform;
+in vec2 a_position;
+in vec4 a_color;
+out vec4 v_color;
+void main() {
+    gl_Position = vec4(a_position * u_transform.xy + u_transform.zw, 0.0, 1.0);
+    v_color = a_color;
+}
+"#;
+
+const FRAGMENT_SHADER: &str = r#"#version 300 es
+precision mediump float;
+in vec4 v_color;
+out vec4 color;
+void main() {
+    color = vec4(v_color.rgb * v_color.a, v_color.a);
+}
+"#;
+
+/// WebGL2 context with the flat-color program bound, ready to draw
+/// [`WebGlBackend`] batches
+pub struct GlSurface {
+    gl: Gl,
+    buffer: WebGlBuffer,
+    transform: WebGlUniformLocation,
+}
+
+impl GlSurface {
+    /// `None` when the browser has no WebGL2 (the canvas is then unused)
+    pub fn new(canvas: &HtmlCanvasElement) -> Option<Self> {
+        // Keep the last frame readable so snapshots can copy the canvas
+        let options = js_sys::Object::new();
+        js_sys::Reflect::set(&options, &"preserveDrawingBuffer".into(), &JsValue::TRUE).ok()?;
+        let gl: Gl = canvas.get_context_with_context_options("webgl2", &options).ok()??.dyn_into().ok()?;
+
+        let program = link_program(&gl)?;
+        gl.use_program(Some(&program));
+        let buffer = gl.create_buffer()?;
+        gl.bind_buffer(Gl::ARRAY_BUFFER, Some(&buffer));
+        let stride = (FLOATS_PER_VERTEX * 4) as i32;
+        for (name, size, offset) in [("a_position", 2, 0), ("a_color", 4, 8)] {
+            let location = u32::try_from(gl.get_attrib_location(&program, name)).ok()?;
+            gl.enable_vertex_attrib_array(location);
+            gl.vertex_attrib_pointer_with_i32(location, size, Gl::FLOAT, false, stride, offset);
+        }
+        let transform = gl.get_uniform_location(&program, "u_transform")?;
+        gl.enable(Gl::BLEND);
+        gl.blend_func(Gl::ONE, Gl::ONE_MINUS_SRC_ALPHA);
+        Some(Self { gl, buffer, transform })
+    }
+
+    /// Size the canvas like [`fit_canvas`], clear it to `background` and
+    /// draw `batch` with viewBox units offset by `origin`
+    pub fn draw(
+        &self,
+        canvas: &HtmlCanvasElement,
+        dims: &ChartDimensions,
+        origin: (f64, f64),
+        background: &str,
+        batch: &WebGlBackend,
+    ) {
+        let Some((css_width, css_height, _)) = fit_backing_store(canvas) else {
+            return;
+        };
+        let gl = &self.gl;
+        gl.viewport(0, 0, canvas.width() as i32, canvas.height() as i32);
+        let [r, g, b, a] = parse_color(background).unwrap_or_default();
+        gl.clear_color(r * a, g * a, b * a, a);
+        gl.clear(Gl::COLOR_BUFFER_BIT);
+
+        // viewBox units → CSS pixels (as in `fit_canvas`) → clip space
+        let (scale, offset_x, offset_y) = meet(dims, css_width, css_height);
+        gl.uniform4f(
+            Some(&self.transform),
+            (2.0 * scale / css_width) as f32,
+            (-2.0 * scale / css_height) as f32,
+            (2.0 * (offset_x + origin.0 * scale) / css_width - 1.0) as f32,
+            (1.0 - 2.0 * (offset_y + origin.1 * scale) / css_height) as f32,
+        );
+        gl.bind_buffer(Gl::ARRAY_BUFFER, Some(&self.buffer));
+        gl.buffer_data_with_array_buffer_view(Gl::ARRAY_BUFFER, &Float32Array::from(&batch.vertices[..]), Gl::STREAM_DRAW);
+        gl.draw_arrays(Gl::TRIANGLES, 0, batch.vertex_count() as i32);
+    }
+}
+
+fn link_program(gl: &Gl) -> Option<WebGlProgram> {
+    let compile = |kind: u32, source: &str| {
+        let shader = gl.create_shader(kind)?;
+        gl.shader_source(&shader, source);
+        gl.compile_shader(&shader);
+        if gl.get_shader_parameter(&shader, Gl::COMPILE_STATUS).as_bool() != Some(true) {
+            tracing::warn!("chart shader failed to compile: {:?}", gl.get_shader_info_log(&shader));
+            return None;
+        }
+        Some(shader)
+    };
+    let program = gl.create_program()?;
+    gl.attach_shader(&program, &compile(Gl::VERTEX_SHADER, VERTEX_SHADER)?);
+    gl.attach_shader(&program, &compile(Gl::FRAGMENT_SHADER, FRAGMENT_SHADER)?);
+    gl.link_program(&program);
+    if gl.get_program_parameter(&program, Gl::LINK_STATUS).as_bool() != Some(true) {
+        tracing::warn!("chart shader failed to link: {:?}", gl.get_program_info_log(&program));
+        return None;
+    }
+    Some(program)
+}
+
+/// A chart layer canvas, painted through WebGL2 when that backend is
+/// requested and available, and a 2D context otherwise
+pub enum CanvasSurface {
+    WebGl(GlSurface),
+    TwoD,
+}
+
+impl CanvasSurface {
+    pub fn new(canvas: &HtmlCanvasElement, backend: RenderBackend) -> Self {
+        match backend {
+            RenderBackend::WebGl => GlSurface::new(canvas).map_or(Self::TwoD, Self::WebGl),
+            RenderBackend::Svg | RenderBackend::Canvas => Self::TwoD,
+        }
+    }
+
+    /// Fit the canvas to its box, fill `background`, then hand `paint` a
+    /// backend working in viewBox units offset by `origin`
+    pub fn paint(
+        &self,
+        canvas: &HtmlCanvasElement,
+        dims: &ChartDimensions,
+        origin: (f64, f64),
+        background: &str,
+        paint: impl FnOnce(&mut dyn ChartBackend),
+    ) {
+        match self {
+            Self::WebGl(surface) => {
+                let mut batch = WebGlBackend::new();
+                paint(&mut batch);
+                surface.draw(canvas, dims, origin, background, &batch);
+            }
+            Self::TwoD => {
+                let Some(ctx) = fit_canvas(canvas, dims, origin) else {
+                    return;
+                };
+                ctx.set_fill_style_str(background);
+                ctx.fill_rect(-origin.0, -origin.1, dims.width, dims.height);
+                paint(&mut CanvasBackend::new(&ctx));
+            }
+        }
+    }
+}
+
+/// Match the backing store to the canvas's CSS box at device resolution;
+/// returns the CSS size and pixel ratio, or `None` while it has no size
+fn fit_backing_store(canvas: &HtmlCanvasElement) -> Option<(f64, f64, f64)> {
+    let (css_width, css_height) = (f64::from(canvas.client_width()), f64::from(canvas.client_height()));
+    if css_width <= 0.0 || css_height <= 0.0 {
+        return None;
+    }
+    let ratio = window().device_pixel_ratio().max(1.0);
+    let (pixel_width, pixel_height) = ((css_width * ratio).round() as u32, (css_height * ratio).round() as u32);
+    if canvas.width() != pixel_width || canvas.height() != pixel_height {
+        canvas.set_width(pixel_width);
+        canvas.set_height(pixel_height);
+    }
+    Some((css_width, css_height, ratio))
+}
+
+/// Scale and offset (CSS px) of an `xMidYMid meet` viewBox of `dims`
+fn meet(dims: &ChartDimensions, css_width: f64, css_height: f64) -> (f64, f64, f64) {
+    let scale = (css_width / dims.width).min(css_height / dims.height);
+    (scale, (css_width - dims.width * scale) / 2.0, (css_height - dims.height * scale) / 2.0)
+}
+
+/// Size the canvas backing store to its CSS box at device resolution and
+/// set a transform so viewBox units land exactly where an `xMidYMid meet`
+/// SVG of the same `dims` puts them (plus `origin`, e.g. the plot margins).
+/// Clears the canvas; returns `None` while it has no size.
+pub fn fit_canvas(
+    canvas: &HtmlCanvasElement,
+    dims: &ChartDimensions,
+    origin: (f64, f64),
+) -> Option<CanvasRenderingContext2d> {
+    let (css_width, css_height, ratio) = fit_backing_store(canvas)?;
+    let ctx: CanvasRenderingContext2d = canvas.get_context("2d").ok()??.dyn_into().ok()?;
+
+    let (scale, offset_x, offset_y) = meet(dims, css_width, css_height);
+    let _ = ctx.set_transform(1.0, 0.0, 0.0, 1.0, 0.0, 0.0);
+    ctx.clear_rect(0.0, 0.0, f64::from(canvas.width()), f64::from(canvas.height()));
+    let _ = ctx.set_transform(
+        ratio * scale,
+        0.0,
+        0.0,
+        ratio * scale,
+        ratio * (offset_x + origin.0 * scale),
+        ratio * (offset_y + origin.1 * scale),
+    );
+    Some(ctx)
+}
+
+/// Straight RGBA (0.0 - 1.0) for the CSS colors the charts use: `#rgb`,
+/// `#rrggbb`, `#rrggbbaa`, `rgb()`/`rgba()` and `hsl()`/`hsla()`
+pub fn parse_color(css: &str) -> Option<[f32; 4]> {
+    let css = css.trim();
+    if let Some(hex) = css.strip_prefix('#') {
+        let digits: Vec<u8> = hex
+            .chars()
+            .map(|c| c.to_digit(16).map(|d| d as u8))
+            .collect::<Option<_>>()?;
+        let channels: Vec<f32> = match digits.len() {
+            3 => digits.iter().map(|&d| f32::from(d * 17) / 255.0).collect(),
+            6 | 8 => digits.chunks(2).map(|p| f32::from(p[0] * 16 + p[1]) / 255.0).collect(),
+            _ => return None,
+        };
+        return Some([channels[0], channels[1], channels[2], channels.get(3).copied().unwrap_or(1.0)]);
+    }
+
+    let (function, args) = css.strip_suffix(')')?.split_once('(')?;
+    let args: Vec<&str> = args.split(',').map(str::trim).collect();
+    if args.len() < 3 {
+        return None;
+    }
+    let number = |s: &str| s.trim_end_matches('%').parse::<f32>().ok();
+    let alpha = args.get(3).map_or(Some(1.0), |a| number(a))?.clamp(0.0, 1.0);
+    let [r, g, b] = match function.trim() {
+        "rgb" | "rgba" => [number(args[0])? / 255.0, number(args[1])? / 255.0, number(args[2])? / 255.0],
+        "hsl" | "hsla" => hsl_to_rgb(number(args[0])?, number(args[1])? / 100.0, number(args[2])? / 100.0),
+        _ => return None,
+    };
+    Some([r.clamp(0.0, 1.0), g.clamp(0.0, 1.0), b.clamp(0.0, 1.0), alpha])
+}
+
+fn hsl_to_rgb(hue: f32, saturation: f32, lightness: f32) -> [f32; 3] {
+    let chroma = (1.0 - (2.0 * lightness - 1.0).abs()) * saturation;
+    let h = hue.rem_euclid(360.0) / 60.0;
+    let x = chroma * (1.0 - (h % 2.0 - 1.0).abs());
+    let (r, g, b) = match h as u32 {
+        0 => (chroma, x, 0.0),
+        1 => (x, chroma, 0.0),
+        2 => (0.0, chroma, x),
+        3 => (0.0, x, chroma),
+        4 => (x, 0.0, chroma),
+        _ => (chroma, 0.0, x),
+    };
+    let m = lightness - chroma / 2.0;
+    [r + m, g + m, b + m]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use dash_core::colors;
+
+    fn close(a: [f32; 4], b: [f32; 4]) -> bool {
+        a.iter().zip(&b).all(|(x, y)| (x - y).abs() < 1e-3)
+    }
+
+    #[test]
+    fn test_parse_chart_colors() {
+        assert_eq!(parse_color(colors::BULL), Some([34.0 / 255.0, 197.0 / 255.0, 94.0 / 255.0, 1.0]));
+        assert_eq!(parse_color("#fff"), Some([1.0; 4]));
+        assert!(close(parse_color(&colors::bear_alpha(0.5)).unwrap(), [239.0 / 255.0, 68.0 / 255.0, 68.0 / 255.0, 0.5]));
+        // Heat ramp endpoints: pure-ish blue and yellow
+        assert!(close(parse_color("hsla(240, 100%, 50%, 0.25)").unwrap(), [0.0, 0.0, 1.0, 0.25]));
+        assert!(close(parse_color("hsl(60, 100%, 50%)").unwrap(), [1.0, 1.0, 0.0, 1.0]));
+        assert_eq!(parse_color("var(--accent-bull)"), None);
+        assert_eq!(parse_color("#12345"), None);
+    }
+
+    #[test]
+    fn test_webgl_batches_triangles() {
+        let mut batch = WebGlBackend::new();
+        batch.rect(0.0, 0.0, 2.0, 1.0, colors::BULL);
+        batch.polyline(&[(0.0, 0.0), (1.0, 1.0), (2.0, 0.0)], colors::INFO, 1.0);
+        batch.area(&[(0.0, 0.0), (1.0, 1.0), (2.0, 0.0)], 2.0, colors::INFO, 0.1);
+        // Unparseable colors draw nothing rather than black
+        batch.rect(0.0, 0.0, 1.0, 1.0, "var(--accent-bear)");
+        assert_eq!(batch.vertex_count(), 6 + 2 * 6 + 2 * 6);
+    }
+}
@@ -1,13 +1,45 @@
-//! Candlestick chart component with volume overlay
+//! Candlestick chart component with a volume sub-pane
 //!
-//! Renders OHLCV data as traditional candlestick chart with optional volume bars.
+//! Renders OHLCV data as candlesticks, OHLC bars, a line/area of closes or
+//! Heikin-Ashi candles, with optional volume bars in their own pane
+//! underneath sharing the candles' X scale. The bars go through a
+//! [`ChartBackend`]: SVG by default, or a canvas (2D or WebGL) layer under
+//! the SVG axes for long histories. The price axis can be linear,
+//! logarithmic or percent change from the first visible bar. Dragging the
+//! plot pans back through history, asking for more as it nears the start.
+//! Trendlines, rays, boxes and Fibonacci retracements are placed, picked
+//! and dragged on the same plot, as are working paper orders.
 
 use crate::{
-    chartkit::{BandScale, LinearScale, Scale, format_price},
-    colors, ChartDimensions, ChartMargin,
+    backend::{CanvasSurface, ChartBackend, RenderBackend, SvgBackend},
+    chartkit::{ease_out_cubic, BandScale, BarTimeAxis, LinearScale, PriceScale, Scale, format_time, format_volume, time_gaps, time_ticks},
+    colors, compare_percent, depth::prefers_reduced_motion, cumulative_delta, Annotation, AnnotationLayer, CvdPane, order_at, CompareOverlay, snap_to_ohlc, ChartDimensions, ChartMargin, DrawingEdit, DrawingHandle, DrawingPixels, IndicatorOverlay,
+    OrderLine, OverlaySeries, TradePrint, TradePrintsOverlay, TradingMarkers, TradingOverlay, VolumeProfile, VwapOverlay,
+    use_chart_palette,
+};
+use dash_core::{
+    colors::Palette, heikin_ashi, Anchor, Candle, CandleHistory, ChartOverlay, ChartType, Drawing, DrawingTool, Price, PriceScaleMode, ProfileHistogram,
+    Quantity, TickFormat, TimeZoneMode, VwapPoint,
 };
-use dash_core::{Candle, CandleHistory};
 use leptos::prelude::*;
+use std::ops::Range;
+use wasm_bindgen::{prelude::Closure, JsCast};
+
+/// Space between the price pane and the volume pane below it
+const VOLUME_GAP_PX: f64 = 10.0;
+
+/// Price axis ticks (and gridlines) to aim for
+const PRICE_TICKS: usize = 5;
+
+/// Axis drag sensitivity: 100 px stretches the price range by e×
+const AXIS_DRAG_RATE: f64 = 0.01;
+
+/// Bars of loaded history left of the view that prompt loading another page
+const LOAD_AHEAD_BARS: usize = 20;
+
+/// Crosshair tooltip box size and its gap from the pointer
+const TOOLTIP_SIZE: (f64, f64) = (118.0, 100.0);
+const TOOLTIP_OFFSET: f64 = 14.0;
 
 /// Candlestick chart configuration
 #[derive(Debug, Clone)]
@@ -18,6 +50,19 @@ pub struct CandlestickConfig {
     pub volume_height_ratio: f64,
     pub show_grid: bool,
     pub show_crosshair: bool,
+    /// OHLCV box next to the crosshair
+    pub show_tooltip: bool,
+    /// Bars in view; older ones are reached by dragging the plot
+    pub visible_bars: usize,
+    /// Renderer for the candles and volume bars
+    pub backend: RenderBackend,
+    /// Cumulative volume delta pane along the bottom
+    pub show_cvd: bool,
+    pub cvd_height_ratio: f64,
+    /// Forming candle transition length (ms); 0 jumps straight to each tick
+    pub forming_animation_ms: f64,
+    /// Price precision of the charted symbol
+    pub price_format: TickFormat,
 }
 
 impl Default for CandlestickConfig {
@@ -28,7 +73,14 @@ impl Default for CandlestickConfig {
             show_volume: true,
             volume_height_ratio: 0.2,
             show_grid: true,
-            show_crosshair: false,
+            show_crosshair: true,
+            show_tooltip: true,
+            visible_bars: 120,
+            backend: RenderBackend::Svg,
+            show_cvd: false,
+            cvd_height_ratio: 0.18,
+            forming_animation_ms: 120.0,
+            price_format: TickFormat::default(),
         }
     }
 }
@@ -42,62 +94,248 @@ impl CandlestickConfig {
             volume_height_ratio: 0.0,
             show_grid: false,
             show_crosshair: false,
+            show_tooltip: false,
+            visible_bars: 120,
+            backend: RenderBackend::Svg,
+            show_cvd: false,
+            cvd_height_ratio: 0.0,
+            forming_animation_ms: 0.0,
+            price_format: TickFormat::default(),
         }
     }
 }
 
 /// Internal chart state computed from candle data
-#[derive(Clone)]
+#[derive(Clone, PartialEq)]
 struct ChartState {
+    /// Candles in view
     candles: Vec<Candle>,
-    y_scale: LinearScale,
+    /// Index of the first candle in view within the whole series
+    start: usize,
+    /// The view ends at the newest candle
+    live: bool,
+    /// Time across the bars, for drawings and trade prints
+    time_axis: BarTimeAxis,
+    y_scale: PriceScale,
     vol_scale: LinearScale,
+    vol_max: f64,
     x_scale: BandScale,
     bandwidth: f64,
 }
 
+impl ChartState {
+    /// Indices of the candles in view within the whole series
+    fn window(&self) -> Range<usize> {
+        self.start..self.start + self.candles.len()
+    }
+
+    /// Anchor under a plot point, snapped onto the bar's OHLC when close
+    fn anchor_at(&self, (x, y): (f64, f64)) -> Anchor {
+        let time = self.time_axis.time_at(x);
+        let price = self.y_scale.invert(y);
+        let price = self
+            .candles
+            .binary_search_by_key(&time, |c| c.timestamp)
+            .map_or(price, |i| snap_to_ohlc(&self.candles[i], price, y, |p| self.y_scale.scale(p)));
+        Anchor::new(time, price)
+    }
+
+    fn drawing_pixels(&self, drawing: &Drawing) -> DrawingPixels {
+        let at = |anchor: Anchor| (self.time_axis.x_at(anchor.time), self.y_scale.scale(anchor.price));
+        let levels = if drawing.tool == DrawingTool::Fibonacci {
+            drawing.fib_levels().map(|(_, price)| self.y_scale.scale(price)).collect()
+        } else {
+            Vec::new()
+        };
+        DrawingPixels {
+            tool: drawing.tool,
+            start: at(drawing.start),
+            end: at(drawing.end),
+            levels,
+        }
+    }
+
+    /// `grab`'s drawing with the pointer now at `point`: a handle follows the
+    /// pointer (snapping), the body moves by whole bars and by pixels in price
+    fn dragged(&self, grab: &DrawingDrag, point: (f64, f64)) -> Drawing {
+        let mut drawing = grab.original.clone();
+        match grab.handle {
+            DrawingHandle::Start => drawing.start = self.anchor_at(point),
+            DrawingHandle::End => drawing.end = self.anchor_at(point),
+            DrawingHandle::Body => {
+                let dt = self.time_axis.time_at(point.0) - self.time_axis.time_at(grab.origin.0);
+                let dy = point.1 - grab.origin.1;
+                let shift = |a: Anchor| Anchor::new(a.time + dt, self.y_scale.invert(self.y_scale.scale(a.price) + dy));
+                drawing.start = shift(drawing.start);
+                drawing.end = shift(drawing.end);
+            }
+        }
+        if drawing.tool.is_single_point() {
+            drawing.end = drawing.start;
+        }
+        drawing
+    }
+}
+
+/// A drawing being placed or dragged: what was grabbed, where, and the
+/// drawing as it was
+#[derive(Clone)]
+struct DrawingDrag {
+    handle: DrawingHandle,
+    origin: (f64, f64),
+    original: Drawing,
+}
+
+/// Vertical layout of the plot: price pane height, and the volume pane's
+/// top and height when shown
+#[derive(Clone, Copy)]
+struct Panes {
+    price_height: f64,
+    volume: Option<(f64, f64)>,
+}
+
 /// Candlestick chart component
 #[component]
 pub fn CandlestickChart(
-    candles: RwSignal<CandleHistory>,
+    #[prop(into)] candles: Signal<CandleHistory>,
     #[prop(optional)] config: Option<CandlestickConfig>,
+    /// Called with the candle under the pointer (`None` on leave)
+    #[prop(optional, into)] on_hover: Option<Callback<Option<Candle>>>,
+    /// Volume profile drawn behind the candles on the price scale
+    #[prop(optional, into)] profile: Option<Signal<Option<ProfileHistogram>>>,
+    /// Time left in the forming candle, tagged on the price axis at the last close
+    #[prop(optional, into)] countdown: Option<Signal<String>>,
+    /// Right-click on the price pane with the price under the pointer
+    #[prop(optional, into)] on_context_menu: Option<Callback<(leptos::ev::MouseEvent, f64)>>,
+    /// Moving average / band studies drawn over the candles
+    #[prop(optional, into)] overlays: Option<Signal<Vec<ChartOverlay>>>,
+    /// Session VWAP and σ per candle (empty = hidden)
+    #[prop(optional, into)] vwap: Option<Signal<Vec<Option<VwapPoint>>>>,
+    /// Price drawing style (candles when unset)
+    #[prop(optional, into)] chart_type: Option<Signal<ChartType>>,
+    /// Linear, log or percent price axis (linear when unset)
+    #[prop(optional, into)] price_scale: Option<Signal<PriceScaleMode>>,
+    /// Auto-fit takes in the study bands and VWAP σ bands, not just the candles
+    #[prop(optional, into)] fit_overlays: Option<Signal<bool>>,
+    /// Last trade price and the color of its tick, lined across the price pane
+    #[prop(optional, into)] last_price: Option<Signal<Option<(f64, &'static str)>>>,
+    /// Best bid and ask lined across the price pane (`None` = hidden)
+    #[prop(optional, into)] quotes: Option<Signal<Option<(f64, f64)>>>,
+    /// Panned close to the oldest loaded candle; time to load older ones
+    #[prop(optional, into)] on_scroll_back: Option<Callback<()>>,
+    /// Trendlines, rays, boxes and retracements on this chart
+    #[prop(optional, into)] drawings: Option<Signal<Vec<Drawing>>>,
+    /// Tool the next press places (`None` = select, drag and pan)
+    #[prop(optional, into)] drawing_tool: Option<Signal<Option<DrawingTool>>>,
+    /// Drawing picked by clicking it
+    #[prop(optional)] selected_drawing: Option<RwSignal<Option<u64>>>,
+    /// A drawing was placed or moved
+    #[prop(optional, into)] on_drawing: Option<Callback<DrawingEdit>>,
+    /// Recent trades dotted at their price and time (empty = hidden)
+    #[prop(optional, into)] prints: Option<Signal<Vec<TradePrint>>>,
+    /// Paper orders, fills and the open position on this symbol
+    #[prop(optional, into)] trading: Option<Signal<TradingMarkers>>,
+    /// A working order was dragged to a new price: `(order id, price)`
+    #[prop(optional, into)] on_amend_order: Option<Callback<(String, f64)>>,
+    /// Another symbol's candles at the same interval, drawn as percent change
+    #[prop(optional, into)] compare: Option<Signal<Option<CandleHistory>>>,
+    /// Buy − sell volume per candle, index-aligned with `candles`, for the
+    /// CVD pane (`config.show_cvd`)
+    #[prop(optional, into)] cvd: Option<Signal<Vec<Option<f64>>>>,
+    /// Clock the time axis and crosshair read in (UTC when unset)
+    #[prop(optional, into)] time_zone: Option<Signal<TimeZoneMode>>,
+    /// Markers, event lines and regions added through [`ChartAnnotations`]
+    #[prop(optional, into)] annotations: Option<Signal<Vec<Annotation>>>,
 ) -> impl IntoView {
     let config = config.unwrap_or_default();
+    // A burst of ticks between two frames costs one redraw
+    let candles = frame_batched(candles);
     
     let dims = ChartDimensions::new(config.width, config.height)
         .with_margin(ChartMargin::right_axis());
 
-    let price_height = if config.show_volume {
-        dims.inner_height() * (1.0 - config.volume_height_ratio)
+    // The CVD pane takes its share off the bottom; price and volume panes
+    // split what's left after the gap between them
+    let cvd_height = if config.show_cvd { dims.inner_height() * config.cvd_height_ratio } else { 0.0 };
+    let stacked_height = if config.show_cvd {
+        (dims.inner_height() - cvd_height - VOLUME_GAP_PX).max(0.0)
     } else {
         dims.inner_height()
     };
-
-    let volume_height = dims.inner_height() * config.volume_height_ratio;
-    let volume_y_offset = price_height + 10.0;
+    let (price_height, volume_height) = if config.show_volume {
+        let panes = (stacked_height - VOLUME_GAP_PX).max(0.0);
+        (panes * (1.0 - config.volume_height_ratio), panes * config.volume_height_ratio)
+    } else {
+        (stacked_height, 0.0)
+    };
+    let volume_y_offset = price_height + VOLUME_GAP_PX;
+    let cvd_y_offset = stacked_height + VOLUME_GAP_PX;
 
     let show_volume = config.show_volume;
+    let show_cvd = config.show_cvd;
     let show_grid = config.show_grid;
+    let show_crosshair = config.show_crosshair;
+    let show_tooltip = config.show_tooltip;
+    let visible_bars = config.visible_bars.max(1);
+    let backend = config.backend;
+    let price_format = config.price_format;
+    let panes = Panes {
+        price_height,
+        volume: show_volume.then_some((volume_y_offset, volume_height)),
+    };
+    let series_style = move || chart_type.map_or(ChartType::Candles, |t| t.get());
+    let zone = move || time_zone.map_or(TimeZoneMode::Utc, |z| z.get());
+
+    // Overlay series pick up where the last update left off
+    let overlay_memo = Memo::new(move |previous: Option<&OverlaySeries>| {
+        let mut series = previous.cloned().unwrap_or_default();
+        let overlays = overlays.map(|o| o.get()).unwrap_or_default();
+        candles.with(|history| series.update(&history.candles, &overlays));
+        series
+    });
+
+    // Price range set by dragging the axis (`None` = auto-fit) and the open
+    // time of the newest bar in view once panned back (`None` = follow the
+    // live bar); a new symbol or timeframe resets both. Anchoring the view
+    // on a time keeps it still as older candles are put in front.
+    let manual_range = RwSignal::new(None::<(f64, f64)>);
+    let right_edge = RwSignal::new(None::<i64>);
+    let series_key = Memo::new(move |_| candles.with(|h| (h.symbol.clone(), h.interval)));
+    Effect::new(move |_| {
+        series_key.track();
+        manual_range.set(None);
+        right_edge.set(None);
+    });
 
     // Compute chart state from candle data
-    let chart_state = move || {
+    let chart_memo = Memo::new(move |_| {
         let history = candles.get();
-        let candle_list = &history.candles;
-
-        if candle_list.is_empty() {
+        if history.candles.is_empty() {
             return None;
         }
+        let (start, end) = view_window(&history.candles, right_edge.get(), visible_bars);
+        let candle_list = &history.candles[start..end];
 
-        // Price range with padding
-        let (price_min, price_max) = history.price_range().unwrap_or((0.0, 1.0));
-        let price_padding = (price_max - price_min) * 0.05;
-        
-        let y_scale = LinearScale::new()
-            .domain(price_min - price_padding, price_max + price_padding)
-            .range(price_height, 0.0);
+        // Auto-fit pads the visible range; percent mode measures from the first close
+        let mode = price_scale.map_or(PriceScaleMode::Linear, |m| m.get());
+        let y_scale = match manual_range.get() {
+            Some(range) => PriceScale::new(mode, range, (price_height, 0.0)),
+            None => {
+                let mut range = candle_list
+                    .iter()
+                    .fold((f64::MAX, f64::MIN), |(low, high), c| (low.min(c.low.as_f64()), high.max(c.high.as_f64())));
+                if fit_overlays.is_some_and(|f| f.get()) {
+                    let vwap = vwap.map(|v| v.get()).unwrap_or_default();
+                    overlay_memo.with(|overlays| range = fit_overlay_range(range, overlays, &vwap, start..end));
+                }
+                PriceScale::new(mode, range, (price_height, 0.0)).padding(0.05)
+            }
+        }
+        .base(candle_list[0].close.as_f64())
+        .tick_format(price_format);
 
         // Volume scale
-        let (_, vol_max) = history.volume_range().unwrap_or((0.0, 1.0));
+        let vol_max = candle_list.iter().map(|c| c.volume.as_f64()).fold(0.0, f64::max);
         let vol_scale = LinearScale::new()
             .domain(0.0, vol_max * 1.1)
             .range(volume_height, 0.0);
@@ -110,182 +348,1229 @@ pub fn CandlestickChart(
         let bandwidth = x_scale.bandwidth();
 
         Some(ChartState {
-            candles: candle_list.clone(),
+            candles: candle_list.to_vec(),
+            start,
+            live: end == history.candles.len(),
+            time_axis: BarTimeAxis::new(&x_scale, candle_list[0].timestamp, history.interval.as_millis()),
             y_scale,
             vol_scale,
+            vol_max,
             x_scale,
             bandwidth,
         })
+    });
+    let chart_state = move || chart_memo.get();
+
+    // The live forming candle eases from its last drawn shape to each tick
+    // (wick and body growing, the color turning as the close crosses the
+    // open) rather than jumping; `None` draws it as it stands
+    let forming = RwSignal::new(None::<Candle>);
+    let forming_generation = StoredValue::new(0u64);
+    let forming_ms = config.forming_animation_ms;
+    let animate_forming = forming_ms > 0.0 && !prefers_reduced_motion();
+    Effect::new(move |previous: Option<Option<Candle>>| {
+        let target = chart_memo.with(|s| s.as_ref().filter(|s| s.live).and_then(|s| s.candles.last().cloned()));
+        forming_generation.update_value(|g| *g += 1);
+        let from = forming.get_untracked().or(previous.flatten());
+        match (from, &target) {
+            (Some(from), Some(to)) if animate_forming && from.timestamp == to.timestamp && from != *to => {
+                let id = forming_generation.get_value();
+                tween_forming(forming, (from, to.clone()), js_sys::Date::now(), forming_ms, (forming_generation, id));
+            }
+            _ => forming.set(None),
+        }
+        target
+    });
+
+    // Dragging the price axis stretches the scale about its middle (down
+    // shows more, up less); double-click goes back to auto-fit
+    let axis_drag = StoredValue::new(None::<(f64, PriceScale)>);
+    let start_axis_drag = move |ev: leptos::ev::PointerEvent| {
+        let Some(scale) = chart_memo.with_untracked(|s| s.as_ref().map(|s| s.y_scale.clone())) else {
+            return;
+        };
+        if let Some(target) = ev.current_target().and_then(|t| t.dyn_into::<web_sys::Element>().ok()) {
+            let _ = target.set_pointer_capture(ev.pointer_id());
+        }
+        axis_drag.set_value(Some((f64::from(ev.client_y()), scale)));
+    };
+    let drag_axis = move |ev: leptos::ev::PointerEvent| {
+        axis_drag.with_value(|drag| {
+            if let Some((start_y, scale)) = drag {
+                let factor = ((f64::from(ev.client_y()) - start_y) * AXIS_DRAG_RATE).exp();
+                manual_range.set(Some(scale.stretched_domain(factor)));
+            }
+        });
+    };
+    let end_axis_drag = move |_| axis_drag.set_value(None);
+
+    let context_menu = move |ev: leptos::ev::MouseEvent| {
+        let Some(callback) = on_context_menu else {
+            return;
+        };
+        let Some((_, plot_y)) = plot_point(&ev, dims) else {
+            return;
+        };
+        if !(0.0..=price_height).contains(&plot_y) {
+            return;
+        }
+        if let Some(state) = chart_state() {
+            callback.run((ev, state.y_scale.invert(plot_y)));
+        }
+    };
+
+    // Dragging the plot pans through history, in whole bars from the view
+    // the drag started on (held as the open time of its newest bar)
+    let pan = StoredValue::new(None::<(f64, i64)>);
+    // Pressing the plot places a drawing when a tool is picked, otherwise
+    // grabs the order line or drawing under the pointer, otherwise starts a pan
+    let draft = RwSignal::new(None::<Drawing>);
+    let drawing_drag = StoredValue::new(None::<DrawingDrag>);
+    let order_drag = RwSignal::new(None::<OrderLine>);
+    let press = move |ev: leptos::ev::PointerEvent| {
+        if ev.button() != 0 {
+            return;
+        }
+        let Some((x, y)) = plot_point(&ev, dims) else {
+            return;
+        };
+        if !(0.0..=dims.inner_width()).contains(&x) || !(0.0..=dims.inner_height()).contains(&y) {
+            return;
+        }
+        let Some(anchor) = chart_memo.with_untracked(|s| s.as_ref()?.candles.last().map(|c| c.timestamp)) else {
+            return;
+        };
+        if let Some(target) = ev.current_target().and_then(|t| t.dyn_into::<web_sys::Element>().ok()) {
+            let _ = target.set_pointer_capture(ev.pointer_id());
+        }
+
+        let placing = drawing_tool.is_some_and(|t| t.get_untracked().is_some());
+        let order = on_amend_order.filter(|_| !placing && y <= price_height).and_then(|_| {
+            let y_scale = chart_memo.with_untracked(|s| s.as_ref().map(|s| s.y_scale.clone()))?;
+            trading?.with_untracked(|m| order_at(&m.orders, y, |p| y_scale.scale(p)).cloned())
+        });
+        if order.is_some() {
+            order_drag.set(order);
+            return;
+        }
+
+        let grab = chart_memo.with_untracked(|state| {
+            let state = state.as_ref().filter(|_| y <= price_height)?;
+            if let Some(tool) = drawing_tool.and_then(|t| t.get_untracked()) {
+                let at = state.anchor_at((x, y));
+                return Some(DrawingDrag {
+                    handle: DrawingHandle::End,
+                    origin: (x, y),
+                    original: Drawing::new(0, tool, at, at),
+                });
+            }
+            drawings?.get_untracked().into_iter().rev().find_map(|drawing| {
+                let handle = state.drawing_pixels(&drawing).hit_test((x, y), dims.inner_width())?;
+                Some(DrawingDrag { handle, origin: (x, y), original: drawing })
+            })
+        });
+        let picked = grab.as_ref().map(|g| g.original.id).filter(|&id| id != 0);
+        if let Some(selected) = selected_drawing.filter(|s| s.get_untracked() != picked) {
+            selected.set(picked);
+        }
+        match grab {
+            Some(grab) => {
+                draft.set(Some(grab.original.clone()));
+                drawing_drag.set_value(Some(grab));
+            }
+            None => pan.set_value(Some((x, anchor))),
+        }
     };
+    let drag_order = move |y: f64| {
+        if order_drag.with_untracked(Option::is_none) {
+            return;
+        }
+        if let Some(price) = chart_memo.with_untracked(|s| s.as_ref().map(|s| s.y_scale.invert(y))) {
+            order_drag.update(|o| {
+                if let Some(order) = o {
+                    order.price = price;
+                }
+            });
+        }
+    };
+    let drag_drawing = move |point: (f64, f64)| {
+        let Some(grab) = drawing_drag.get_value() else {
+            return;
+        };
+        if let Some(drawing) = chart_memo.with_untracked(|s| s.as_ref().map(|s| s.dragged(&grab, point))) {
+            draft.set(Some(drawing));
+        }
+    };
+    let pan_view = move |x: f64| {
+        let Some((start_x, anchor)) = pan.get_value() else {
+            return;
+        };
+        let Some(step) = chart_memo.with_untracked(|s| s.as_ref().map(|s| s.x_scale.step())) else {
+            return;
+        };
+        let shift = ((x - start_x) / step).round() as isize;
+        let near_start = candles.with_untracked(|history| {
+            let len = history.candles.len();
+            let (_, anchor_end) = view_window(&history.candles, Some(anchor), visible_bars);
+            let end = anchor_end.saturating_add_signed(-shift).clamp(visible_bars.min(len), len);
+            let edge = (end > 0 && end < len).then(|| history.candles[end - 1].timestamp);
+            if right_edge.get_untracked() != edge {
+                right_edge.set(edge);
+            }
+            end.saturating_sub(visible_bars) < LOAD_AHEAD_BARS
+        });
+        if let Some(cb) = on_scroll_back.filter(|_| near_start && shift > 0) {
+            cb.run(());
+        }
+    };
+    let release = move |_| {
+        pan.set_value(None);
+        if let Some(moved) = order_drag.get_untracked() {
+            order_drag.set(None);
+            let original = trading.and_then(|t| {
+                t.with_untracked(|m| m.orders.iter().find(|o| o.id == moved.id).map(|o| o.price))
+            });
+            if let Some(cb) = on_amend_order.filter(|_| original != Some(moved.price)) {
+                cb.run((moved.id, moved.price));
+            }
+            return;
+        }
+        let Some(grab) = drawing_drag.get_value() else {
+            return;
+        };
+        drawing_drag.set_value(None);
+        let Some(drawing) = draft.get_untracked() else {
+            return;
+        };
+        draft.set(None);
+        let Some(cb) = on_drawing else {
+            return;
+        };
+        if grab.original.id == 0 {
+            if !drawing.is_empty() {
+                cb.run(DrawingEdit::Added(drawing));
+            }
+        } else if drawing != grab.original {
+            cb.run(DrawingEdit::Moved(drawing));
+        }
+    };
+
+    // Crosshair: pointer position in plot coordinates and the candle it
+    // snaps to, reported through `on_hover` as it changes
+    let pointer = RwSignal::new(None::<(f64, f64)>);
+    let snapped = RwSignal::new(None::<usize>);
+    let track_pointer = move |ev: leptos::ev::PointerEvent| {
+        if let Some((x, y)) = plot_point(&ev, dims) {
+            drag_order(y);
+            drag_drawing((x, y));
+            pan_view(x);
+        }
+        let point = plot_point(&ev, dims)
+            .filter(|&(x, y)| (0.0..=dims.inner_width()).contains(&x) && (0.0..=dims.inner_height()).contains(&y));
+        pointer.set(point);
+        let index = point.and_then(|(x, _)| chart_memo.with_untracked(|s| s.as_ref()?.x_scale.index_at(x)));
+        if snapped.get_untracked() != index {
+            snapped.set(index);
+            if let Some(cb) = on_hover {
+                cb.run(index.and_then(|i| chart_memo.with_untracked(|s| s.as_ref()?.candles.get(i).cloned())));
+            }
+        }
+    };
+    let clear_pointer = move |_| {
+        pointer.set(None);
+        if snapped.get_untracked().is_some() {
+            snapped.set(None);
+            if let Some(cb) = on_hover {
+                cb.run(None);
+            }
+        }
+    };
+
+    // Canvas and WebGL backends: repaint on new data, style or element size
+    let canvas_ref = NodeRef::<leptos::html::Canvas>::new();
+    let palette = use_chart_palette();
+    if backend != RenderBackend::Svg {
+        let resized = RwSignal::new(0u32);
+        let surface = StoredValue::new_local(None::<CanvasSurface>);
+        let observer = StoredValue::new_local(None::<(web_sys::ResizeObserver, Closure<dyn FnMut()>)>);
+        Effect::new(move |_| {
+            let Some(canvas) = canvas_ref.get() else {
+                return;
+            };
+            if surface.with_value(Option::is_none) {
+                surface.set_value(Some(CanvasSurface::new(&canvas, backend)));
+            }
+            if observer.with_value(Option::is_none) {
+                let callback = Closure::<dyn FnMut()>::new(move || resized.update(|n| *n += 1));
+                if let Ok(resize) = web_sys::ResizeObserver::new(callback.as_ref().unchecked_ref()) {
+                    resize.observe(&canvas);
+                    observer.set_value(Some((resize, callback)));
+                }
+            }
+            resized.track();
+            let style = series_style();
+            let forming = forming.get();
+            let palette = palette.get();
+            chart_memo.with(|state| {
+                surface.with_value(|surface| {
+                    let Some(surface) = surface else {
+                        return;
+                    };
+                    let origin = (dims.margin.left, dims.margin.top);
+                    surface.paint(&canvas, &dims, origin, palette.bg_panel, |painter| {
+                        if let Some(state) = state {
+                            paint_series(painter, &with_forming(state, forming), style, panes, palette);
+                        }
+                    });
+                })
+            });
+        });
+        on_cleanup(move || {
+            observer.with_value(|o| {
+                if let Some((resize, _)) = o {
+                    resize.disconnect();
+                }
+            })
+        });
+    }
 
     view! {
-        <svg
-            class="candlestick-chart"
-            viewBox=dims.viewbox()
-            preserveAspectRatio="xMidYMid meet"
-            style="width: 100%; height: 100%;"
-        >
-            // Background
-            <rect
-                width=dims.width
-                height=dims.height
-                fill=colors::BG_PANEL
-                rx="4"
-            />
+        <div class="chart-layers">
+            {(backend != RenderBackend::Svg).then(|| view! { <canvas class="chart-canvas" node_ref=canvas_ref /> })}
+            <svg
+                class=move || {
+                    let placing = drawing_tool.is_some_and(|t| t.get().is_some());
+                    if placing { "candlestick-chart placing" } else { "candlestick-chart" }
+                }
+                viewBox=dims.viewbox()
+                preserveAspectRatio="xMidYMid meet"
+                style="width: 100%; height: 100%;"
+                on:contextmenu=context_menu
+                on:pointerdown=press
+                on:pointermove=track_pointer
+                on:pointerup=release
+                on:pointercancel=release
+                on:pointerleave=clear_pointer
+            >
+                // Background (the canvas paints its own)
+                {(backend == RenderBackend::Svg).then(|| view! {
+                    <rect
+                        width=dims.width
+                        height=dims.height
+                        fill=colors::css::BG_PANEL
+                        rx="4"
+                    />
+                })}
 
-            // Chart area
-            <g transform=dims.inner_transform()>
-                // Grid lines
-                {move || {
-                    if show_grid {
+                <defs>
+                    <clipPath id="candle-price-pane">
+                        <rect width=dims.inner_width() height=price_height />
+                    </clipPath>
+                </defs>
+
+                // Chart area
+                <g transform=dims.inner_transform()>
+                    // Grid lines on the price ticks
+                    {move || {
+                        if !show_grid {
+                            return None;
+                        }
+                        let state = chart_state()?;
+                        let rows = state
+                            .y_scale
+                            .nice_ticks(PRICE_TICKS)
+                            .into_iter()
+                            .map(|tick| state.y_scale.scale(tick))
+                            .collect();
                         Some(view! {
                             <ChartGrid
                                 width=dims.inner_width()
                                 height=price_height
-                                h_lines=5
+                                rows=rows
                                 v_lines=0
                             />
                         })
-                    } else {
-                        None
-                    }
-                }}
-
-                // Candlesticks
-                {move || {
-                    chart_state().map(|state| {
-                        state.candles.iter().enumerate().map(|(i, candle)| {
-                            let x = state.x_scale.scale(i);
-                            let x_center = x + state.bandwidth / 2.0;
-
-                            // Wick coordinates
-                            let wick_y1 = state.y_scale.scale(candle.high.as_f64());
-                            let wick_y2 = state.y_scale.scale(candle.low.as_f64());
-
-                            // Body coordinates
-                            let body_top = candle.open.as_f64().max(candle.close.as_f64());
-                            let body_bottom = candle.open.as_f64().min(candle.close.as_f64());
-                            let body_y = state.y_scale.scale(body_top);
-                            let body_h = (state.y_scale.scale(body_bottom) - body_y).max(1.0);
-
-                            let fill = candle.fill_color();
-
-                            view! {
-                                <g class=format!("candle {}", candle.css_class())>
-                                    // Wick
-                                    <line
-                                        x1=x_center
-                                        y1=wick_y1
-                                        x2=x_center
-                                        y2=wick_y2
-                                        stroke=fill
-                                        stroke-width="1"
-                                    />
-                                    // Body
-                                    <rect
-                                        x=x
-                                        y=body_y
-                                        width=state.bandwidth
-                                        height=body_h
-                                        fill=fill
-                                        stroke=fill
-                                        stroke-width="1"
-                                        rx="1"
+                    }}
+
+                    // Time axis: a label per step of wall time, day boundaries
+                    // bold, and holes in the history marked where they close up
+                    {move || {
+                        let zone = zone();
+                        let price_driven = series_style().is_price_driven();
+                        chart_memo.with(|state| {
+                            let state = state.as_ref()?;
+                            let times: Vec<i64> = state.candles.iter().map(|c| c.timestamp).collect();
+                            let interval_ms = state.candles[0].interval.as_millis();
+                            let step = state.x_scale.step();
+                            let gaps = if price_driven { Vec::new() } else { time_gaps(&times, interval_ms) };
+                            let gaps = gaps
+                                .into_iter()
+                                .map(|i| {
+                                    let x = state.x_scale.scale(i) - (step - state.bandwidth) / 2.0;
+                                    view! {
+                                        <line
+                                            class="time-gap"
+                                            x1=x y1="0"
+                                            x2=x y2=dims.inner_height()
+                                            stroke=colors::css::WARN
+                                            stroke-opacity="0.35"
+                                            stroke-dasharray="1,3"
+                                        />
+                                    }
+                                })
+                                .collect_view();
+                            let ticks = time_ticks(&times, interval_ms, step, zone)
+                                .into_iter()
+                                .map(|tick| {
+                                    let x = state.x_scale.scale_center(tick.index);
+                                    view! {
+                                        {show_grid.then(|| view! {
+                                            <line
+                                                x1=x y1="0"
+                                                x2=x y2=dims.inner_height()
+                                                stroke=colors::css::GRID
+                                                stroke-width="1"
+                                                stroke-dasharray=if tick.boundary { "none" } else { "2,2" }
+                                            />
+                                        })}
+                                        <text
+                                            x=x
+                                            y={dims.inner_height() + 11.0}
+                                            dy="0.32em"
+                                            text-anchor="middle"
+                                            fill=if tick.boundary { colors::css::TEXT_PRIMARY } else { colors::css::TEXT_MUTED }
+                                            font-weight=if tick.boundary { "bold" } else { "normal" }
+                                            font-size="10"
+                                            font-family="JetBrains Mono, monospace"
+                                        >
+                                            {tick.label}
+                                        </text>
+                                    }
+                                })
+                                .collect_view();
+                            Some(view! {
+                                <g class="time-axis" pointer-events="none">
+                                    {gaps}
+                                    {ticks}
+                                </g>
+                            })
+                        })
+                    }}
+
+                    // Volume profile
+                    {move || {
+                        let profile = profile.and_then(|p| p.get())?;
+                        chart_state().map(|state| view! {
+                            <VolumeProfile
+                                profile=profile
+                                y_scale=state.y_scale.clone()
+                                plot_width=dims.inner_width()
+                                plot_height=price_height
+                            />
+                        })
+                    }}
+
+                    // Price series and volume bars (painted underneath when on canvas)
+                    {move || {
+                        if backend != RenderBackend::Svg {
+                            return None;
+                        }
+                        let style = series_style();
+                        let forming = forming.get();
+                        let palette = palette.get();
+                        chart_memo.with(|state| {
+                            let mut svg = SvgBackend::new();
+                            paint_series(&mut svg, &with_forming(state.as_ref()?, forming), style, panes, palette);
+                            Some(svg.into_view())
+                        })
+                    }}
+
+                    // Indicator overlays, kept inside the price pane
+                    <g clip-path="url(#candle-price-pane)">
+                        {move || {
+                            let points = vwap.map(|v| v.get()).filter(|points| !points.is_empty())?;
+                            let state = chart_state()?;
+                            let window = state.window();
+                            Some(view! {
+                                <VwapOverlay points=in_window(&points, &window).to_vec() x_scale=state.x_scale y_scale=state.y_scale />
+                            })
+                        }}
+                        {move || {
+                            let state = chart_state()?;
+                            let window = state.window();
+                            Some(overlay_memo.with(|overlays| {
+                                overlays
+                                    .series
+                                    .iter()
+                                    .map(|(overlay, points)| view! {
+                                        <IndicatorOverlay
+                                            points=in_window(points, &window).to_vec()
+                                            color=overlay.color.clone()
+                                            x_scale=state.x_scale.clone()
+                                            y_scale=state.y_scale.clone()
+                                        />
+                                    })
+                                    .collect_view()
+                            }))
+                        }}
+                        {move || {
+                            let other = compare?.get()?;
+                            chart_memo.with(|state| {
+                                let state = state.as_ref()?;
+                                Some(view! {
+                                    <CompareOverlay
+                                        points=compare_percent(&state.candles, &other.candles)
+                                        symbol=other.symbol.to_string()
+                                        x_scale=state.x_scale.clone()
+                                        plot_height=price_height
                                     />
+                                })
+                            })
+                        }}
+                        {move || {
+                            let prints = prints.map(|p| p.get()).filter(|prints| !prints.is_empty())?;
+                            let state = chart_state()?;
+                            Some(view! {
+                                <TradePrintsOverlay
+                                    prints=prints
+                                    time_axis=state.time_axis
+                                    y_scale=state.y_scale
+                                    plot_width=dims.inner_width()
+                                />
+                            })
+                        }}
+                        {move || {
+                            let annotations = annotations.map(|a| a.get()).filter(|a| !a.is_empty())?;
+                            let state = chart_state()?;
+                            Some(view! {
+                                <AnnotationLayer
+                                    annotations=annotations
+                                    time_axis=state.time_axis
+                                    y_scale=state.y_scale
+                                    plot_width=dims.inner_width()
+                                    plot_height=price_height
+                                />
+                            })
+                        }}
+                        // Paper position, fills and working orders (one being dragged at its new price)
+                        {move || {
+                            let mut markers = trading?.get();
+                            if let Some(moved) = order_drag.get()
+                                && let Some(slot) = markers.orders.iter_mut().find(|o| o.id == moved.id)
+                            {
+                                *slot = moved;
+                            }
+                            let state = chart_state()?;
+                            Some(view! {
+                                <TradingOverlay
+                                    markers=markers
+                                    time_axis=state.time_axis
+                                    y_scale=state.y_scale
+                                    plot_width=dims.inner_width()
+                                />
+                            })
+                        }}
+                        // Best bid / ask and the last trade
+                        {move || {
+                            let (bid, ask) = quotes?.get()?;
+                            let y_scale = chart_memo.with(|s| s.as_ref().map(|s| s.y_scale.clone()))?;
+                            Some([(bid, colors::css::BULL), (ask, colors::css::BEAR)]
+                                .into_iter()
+                                .map(|(price, color)| {
+                                    let y = y_scale.scale(price);
+                                    view! {
+                                        <line
+                                            class="quote-line"
+                                            x1="0" y1=y
+                                            x2=dims.inner_width() y2=y
+                                            stroke=color
+                                            stroke-width="1"
+                                            stroke-dasharray="1,3"
+                                            pointer-events="none"
+                                        />
+                                    }
+                                })
+                                .collect_view())
+                        }}
+                        {move || {
+                            let (price, color) = last_price?.get()?;
+                            let y = chart_memo.with(|s| s.as_ref().map(|s| s.y_scale.scale(price)))?;
+                            Some(view! {
+                                <line
+                                    class="last-price-line"
+                                    x1="0" y1=y
+                                    x2=dims.inner_width() y2=y
+                                    stroke=color
+                                    stroke-width="1"
+                                    stroke-dasharray="4,3"
+                                    pointer-events="none"
+                                />
+                            })
+                        }}
+                        // Drawings, with the one being placed or dragged in its new spot
+                        {move || {
+                            let mut list = drawings.map(|d| d.get()).unwrap_or_default();
+                            if let Some(edited) = draft.get() {
+                                match list.iter_mut().find(|d| edited.id != 0 && d.id == edited.id) {
+                                    Some(slot) => *slot = edited,
+                                    None => list.push(edited),
+                                }
+                            }
+                            let selected = selected_drawing.and_then(|s| s.get());
+                            chart_memo.with(|state| {
+                                let state = state.as_ref()?;
+                                Some(
+                                    list.iter()
+                                        .map(|d| drawing_view(state, d, d.id == 0 || selected == Some(d.id), dims.inner_width()))
+                                        .collect_view(),
+                                )
+                            })
+                        }}
+                    </g>
+
+                    // OHLCV legend for the candle under the crosshair (or the last one)
+                    {move || {
+                        let index = snapped.get();
+                        chart_memo.with(|state| {
+                            let candles = &state.as_ref()?.candles;
+                            let candle = index.and_then(|i| candles.get(i)).or_else(|| candles.last())?;
+                            let value = |label: &'static str, price: f64| view! {
+                                <tspan fill=colors::css::TEXT_MUTED>{label}</tspan>
+                                <tspan fill=colors::css::TEXT_PRIMARY>{format!("{}  ", price_format.price(price))}</tspan>
+                            };
+                            Some(view! {
+                                <text
+                                    class="ohlcv-legend"
+                                    x="4"
+                                    y="12"
+                                    font-size="10"
+                                    font-family="JetBrains Mono, monospace"
+                                    pointer-events="none"
+                                    xml:space="preserve"
+                                >
+                                    {value("O ", candle.open.as_f64())}
+                                    {value("H ", candle.high.as_f64())}
+                                    {value("L ", candle.low.as_f64())}
+                                    {value("C ", candle.close.as_f64())}
+                                    <tspan fill=candle.css_color()>{format!("{:+.2}%  ", candle.change_percent())}</tspan>
+                                    <tspan fill=colors::css::TEXT_MUTED>"V "</tspan>
+                                    <tspan fill=colors::css::TEXT_PRIMARY>{format_volume(candle.volume.as_f64())}</tspan>
+                                </text>
+                            })
+                        })
+                    }}
+
+                    // Overlay legend with values at the crosshair (or the last candle)
+                    {move || {
+                        let index = snapped.get();
+                        let window = chart_memo.with(|s| s.as_ref().map(ChartState::window))?;
+                        let at = |len: usize| match index {
+                            Some(i) => Some(window.start + i),
+                            None => window.end.min(len).checked_sub(1),
+                        };
+                        let mut entries: Vec<(String, String)> = Vec::new();
+                        if let Some(points) = vwap.map(|v| v.get()).filter(|points| !points.is_empty()) {
+                            let value = at(points.len())
+                                .and_then(|i| points.get(i).copied().flatten())
+                                .map_or_else(|| "—".to_string(), |p| format!("{} ±{}", price_format.price(p.vwap), price_format.price(p.std_dev)));
+                            entries.push((colors::css::INFO.to_string(), format!("VWAP {}", value)));
+                        }
+                        overlay_memo.with(|overlays| {
+                            for (overlay, points) in &overlays.series {
+                                let value = at(points.len())
+                                    .and_then(|i| points.get(i).copied().flatten())
+                                    .map_or_else(|| "—".to_string(), |p| price_format.price(p.value));
+                                entries.push((overlay.color.clone(), format!("{} {}", overlay.indicator.label(), value)));
+                            }
+                        });
+                        (!entries.is_empty()).then(|| view! {
+                            <text
+                                class="overlay-legend"
+                                x="4"
+                                y="26"
+                                font-size="10"
+                                font-family="JetBrains Mono, monospace"
+                                pointer-events="none"
+                                xml:space="preserve"
+                            >
+                                {entries
+                                    .into_iter()
+                                    .map(|(color, text)| view! { <tspan fill=color>{format!("{}  ", text)}</tspan> })
+                                    .collect_view()}
+                            </text>
+                        })
+                    }}
+
+                    // Y-Axis (right side)
+                    <g transform=format!("translate({}, 0)", dims.inner_width())>
+                        <line
+                            x1="0" y1="0"
+                            x2="0" y2=price_height
+                            stroke=colors::css::BORDER
+                            stroke-width="1"
+                        />
+                        {move || {
+                            chart_state().map(|state| {
+                                let ticks = state.y_scale.nice_ticks(PRICE_TICKS);
+                                ticks.into_iter().map(|tick| {
+                                    let y = state.y_scale.scale(tick);
+                                    let label = state.y_scale.label(tick);
+
+                                    view! {
+                                        <g transform=format!("translate(0, {})", y)>
+                                            <line x1="0" x2="5" stroke=colors::css::BORDER />
+                                            <text
+                                                x="8"
+                                                dy="0.32em"
+                                                fill=colors::css::TEXT_MUTED
+                                                font-size="10"
+                                                font-family="JetBrains Mono, monospace"
+                                            >
+                                                {label}
+                                            </text>
+                                        </g>
+                                    }
+                                }).collect_view()
+                            })
+                        }}
+                        // Last price tag, re-inserted (and so pulsing) on every trade
+                        {move || {
+                            let (price, color) = last_price?.get()?;
+                            let (y, label) = chart_memo.with(|s| {
+                                s.as_ref().map(|s| (s.y_scale.scale(price), s.y_scale.label(price)))
+                            })?;
+                            (0.0..=price_height).contains(&y).then(|| view! {
+                                <g class="last-price-tag" transform=format!("translate(0, {})", y) pointer-events="none">
+                                    <rect x="2" y="-7" width="64" height="14" fill=color rx="2" />
+                                    <text
+                                        x="6"
+                                        dy="0.32em"
+                                        fill=colors::css::BG_VOID
+                                        font-size="10"
+                                        font-family="JetBrains Mono, monospace"
+                                    >
+                                        {label}
+                                    </text>
                                 </g>
+                            })
+                        }}
+                        {move || {
+                            let text = countdown?.get();
+                            let state = chart_state().filter(|state| state.live)?;
+                            let y = state.y_scale.scale(state.candles.last()?.close.as_f64());
+                            Some(view! {
+                                <g class="axis-countdown" transform=format!("translate(0, {})", y + 14.0)>
+                                    <rect x="2" y="-7" width="56" height="14" fill=colors::css::BG_ELEVATED rx="2" />
+                                    <text
+                                        x="6"
+                                        dy="0.32em"
+                                        fill=colors::css::TEXT_PRIMARY
+                                        font-size="10"
+                                        font-family="JetBrains Mono, monospace"
+                                    >
+                                        {text}
+                                    </text>
+                                </g>
+                            })
+                        }}
+                        // Volume pane scale: its baseline and the tallest bar
+                        {move || {
+                            if !show_volume {
+                                return None;
                             }
-                        }).collect_view()
-                    })
-                }}
-
-                // Volume bars
-                {move || {
-                    if show_volume {
-                        chart_state().map(|state| {
-                            view! {
-                                <g transform=format!("translate(0, {})", volume_y_offset)>
-                                    {state.candles.iter().enumerate().map(|(i, candle)| {
-                                        let x = state.x_scale.scale(i);
-                                        let vol = candle.volume.as_f64();
-                                        let bar_y = state.vol_scale.scale(vol);
-                                        let bar_h = (volume_height - bar_y).max(0.0);
-                                        
-                                        let fill = if candle.is_bullish() {
-                                            colors::bull_alpha(0.5)
-                                        } else {
-                                            colors::bear_alpha(0.5)
-                                        };
-
-                                        view! {
-                                            <rect
-                                                x=x
-                                                y=bar_y
-                                                width=state.bandwidth
-                                                height=bar_h
-                                                fill=fill
-                                            />
-                                        }
-                                    }).collect_view()}
+                            let state = chart_state()?;
+                            let y = volume_y_offset + state.vol_scale.scale(state.vol_max);
+                            Some(view! {
+                                <line
+                                    x1="0" y1=volume_y_offset
+                                    x2="0" y2={volume_y_offset + volume_height}
+                                    stroke=colors::css::BORDER
+                                    stroke-width="1"
+                                />
+                                <g transform=format!("translate(0, {})", y)>
+                                    <line x1="0" x2="5" stroke=colors::css::BORDER />
+                                    <text
+                                        x="8"
+                                        dy="0.32em"
+                                        fill=colors::css::TEXT_MUTED
+                                        font-size="10"
+                                        font-family="JetBrains Mono, monospace"
+                                    >
+                                        {format_volume(state.vol_max)}
+                                    </text>
                                 </g>
+                            })
+                        }}
+                        // Cumulative volume delta across the bars in view
+                        {move || {
+                            if !show_cvd {
+                                return None;
                             }
-                        })
-                    } else {
-                        None
-                    }
-                }}
+                            let state = chart_state()?;
+                            let points = cvd?.with(|deltas| cumulative_delta(in_window(deltas, &state.window())));
+                            Some(view! {
+                                <g transform=format!("translate(0, {})", cvd_y_offset)>
+                                    <CvdPane
+                                        points=points
+                                        x_scale=state.x_scale
+                                        plot_width=dims.inner_width()
+                                        height=cvd_height
+                                    />
+                                </g>
+                            })
+                        }}
+                        // Drag handle over the price axis, and the way back to auto-fit
+                        <rect
+                            class="price-axis-handle"
+                            width=dims.margin.right
+                            height=price_height
+                            fill="transparent"
+                            on:pointerdown=start_axis_drag
+                            on:pointermove=drag_axis
+                            on:pointerup=end_axis_drag
+                            on:pointercancel=end_axis_drag
+                            on:dblclick=move |_| manual_range.set(None)
+                        />
+                        {move || {
+                            manual_range.with(Option::is_some).then(|| view! {
+                                <g
+                                    class="price-axis-auto"
+                                    transform=format!("translate(4, {})", price_height - 16.0)
+                                    on:click=move |_| manual_range.set(None)
+                                >
+                                    <title>"Auto-fit the price scale"</title>
+                                    <rect width="32" height="14" fill=colors::css::BG_ELEVATED stroke=colors::css::BORDER rx="2" />
+                                    <text
+                                        x="16"
+                                        y="7"
+                                        dy="0.32em"
+                                        text-anchor="middle"
+                                        fill=colors::css::TEXT_PRIMARY
+                                        font-size="9"
+                                        font-family="JetBrains Mono, monospace"
+                                    >
+                                        "AUTO"
+                                    </text>
+                                </g>
+                            })
+                        }}
+                    </g>
 
-                // Y-Axis (right side)
-                <g transform=format!("translate({}, 0)", dims.inner_width())>
-                    <line
-                        x1="0" y1="0"
-                        x2="0" y2=price_height
-                        stroke=colors::BORDER
-                        stroke-width="1"
-                    />
+                    // Crosshair with price and time tags on the axes
                     {move || {
-                        chart_state().map(|state| {
-                            let ticks = state.y_scale.nice_ticks(5);
-                            ticks.into_iter().map(|tick| {
-                                let y = state.y_scale.scale(tick);
-                                let label = format_price(tick, 2);
-
-                                view! {
-                                    <g transform=format!("translate(0, {})", y)>
-                                        <line x1="0" x2="5" stroke=colors::BORDER />
+                        if !show_crosshair {
+                            return None;
+                        }
+                        let (_, y) = pointer.get()?;
+                        let index = snapped.get()?;
+                        chart_memo.with(|state| {
+                            let state = state.as_ref()?;
+                            let candle = state.candles.get(index)?;
+                            let x = state.x_scale.scale_center(index);
+                            let in_price_pane = y <= price_height;
+                            let in_volume_pane = show_volume && y >= volume_y_offset;
+                            let time = format_time(zone().shift(candle.timestamp), candle.interval.as_secs());
+                            Some(view! {
+                                <g class="crosshair" pointer-events="none">
+                                    <line
+                                        x1=x y1="0"
+                                        x2=x y2=dims.inner_height()
+                                        stroke=colors::css::TEXT_MUTED
+                                        stroke-width="0.5"
+                                        stroke-dasharray="3,3"
+                                    />
+                                    {in_price_pane.then(|| view! {
+                                        <line
+                                            x1="0" y1=y
+                                            x2=dims.inner_width() y2=y
+                                            stroke=colors::css::TEXT_MUTED
+                                            stroke-width="0.5"
+                                            stroke-dasharray="3,3"
+                                        />
+                                        <g transform=format!("translate({}, {})", dims.inner_width(), y)>
+                                            <rect x="2" y="-7" width="64" height="14" fill=colors::css::INFO rx="2" />
+                                            <text
+                                                x="6"
+                                                dy="0.32em"
+                                                fill=colors::css::TEXT_PRIMARY
+                                                font-size="10"
+                                                font-family="JetBrains Mono, monospace"
+                                            >
+                                                {state.y_scale.label(state.y_scale.invert(y))}
+                                            </text>
+                                        </g>
+                                    })}
+                                    {in_volume_pane.then(|| view! {
+                                        <line
+                                            x1="0" y1=y
+                                            x2=dims.inner_width() y2=y
+                                            stroke=colors::css::TEXT_MUTED
+                                            stroke-width="0.5"
+                                            stroke-dasharray="3,3"
+                                        />
+                                        <g transform=format!("translate({}, {})", dims.inner_width(), y)>
+                                            <rect x="2" y="-7" width="64" height="14" fill=colors::css::INFO rx="2" />
+                                            <text
+                                                x="6"
+                                                dy="0.32em"
+                                                fill=colors::css::TEXT_PRIMARY
+                                                font-size="10"
+                                                font-family="JetBrains Mono, monospace"
+                                            >
+                                                {format_volume(candle.volume.as_f64())}
+                                            </text>
+                                        </g>
+                                    })}
+                                    <g transform=format!("translate({}, {})", x, dims.inner_height() + 4.0)>
+                                        <rect x="-30" y="0" width="60" height="14" fill=colors::css::INFO rx="2" />
                                         <text
-                                            x="8"
+                                            y="7"
                                             dy="0.32em"
-                                            fill=colors::TEXT_MUTED
+                                            text-anchor="middle"
+                                            fill=colors::css::TEXT_PRIMARY
                                             font-size="10"
                                             font-family="JetBrains Mono, monospace"
                                         >
-                                            {label}
+                                            {time}
                                         </text>
                                     </g>
-                                }
-                            }).collect_view()
+                                </g>
+                            })
+                        })
+                    }}
+
+                    // OHLCV tooltip beside the pointer, flipped to stay in the plot
+                    {move || {
+                        if !(show_crosshair && show_tooltip) {
+                            return None;
+                        }
+                        let (px, py) = pointer.get()?;
+                        let index = snapped.get()?;
+                        chart_memo.with(|state| {
+                            let candle = state.as_ref()?.candles.get(index)?;
+                            let (width, height) = TOOLTIP_SIZE;
+                            let x = if px + TOOLTIP_OFFSET + width > dims.inner_width() {
+                                px - TOOLTIP_OFFSET - width
+                            } else {
+                                px + TOOLTIP_OFFSET
+                            };
+                            let y = (py + TOOLTIP_OFFSET).min(dims.inner_height() - height).max(0.0);
+                            let time = chrono::DateTime::from_timestamp_millis(candle.timestamp)
+                                .map(|dt| dt.format("%Y-%m-%d %H:%M").to_string())
+                                .unwrap_or_default();
+                            let rows = [
+                                ("Open", price_format.price(candle.open.as_f64()), colors::css::TEXT_PRIMARY),
+                                ("High", price_format.price(candle.high.as_f64()), colors::css::TEXT_PRIMARY),
+                                ("Low", price_format.price(candle.low.as_f64()), colors::css::TEXT_PRIMARY),
+                                ("Close", price_format.price(candle.close.as_f64()), colors::css::TEXT_PRIMARY),
+                                ("Change", format!("{:+.2}%", candle.change_percent()), candle.css_color()),
+                                ("Volume", format_volume(candle.volume.as_f64()), colors::css::TEXT_PRIMARY),
+                            ];
+                            Some(view! {
+                                <g
+                                    class="chart-tooltip"
+                                    transform=format!("translate({}, {})", x, y)
+                                    pointer-events="none"
+                                    font-size="10"
+                                    font-family="JetBrains Mono, monospace"
+                                >
+                                    <rect
+                                        width=width
+                                        height=height
+                                        fill=colors::css::BG_ELEVATED
+                                        stroke=colors::css::BORDER
+                                        rx="3"
+                                    />
+                                    <text x="8" y="15" fill=colors::css::TEXT_MUTED>{time}</text>
+                                    {rows
+                                        .into_iter()
+                                        .enumerate()
+                                        .map(|(i, (label, value, color))| {
+                                            let row_y = 30.0 + i as f64 * 12.5;
+                                            view! {
+                                                <text x="8" y=row_y fill=colors::css::TEXT_MUTED>{label}</text>
+                                                <text x={width - 8.0} y=row_y text-anchor="end" fill=color>{value}</text>
+                                            }
+                                        })
+                                        .collect_view()}
+                                </g>
+                            })
                         })
                     }}
                 </g>
-            </g>
-        </svg>
+            </svg>
+        </div>
     }
 }
 
-/// Grid lines component
+/// `source` passed on at most once per animation frame
+fn frame_batched(source: Signal<CandleHistory>) -> Signal<CandleHistory> {
+    let framed = RwSignal::new(source.get_untracked());
+    let pending = StoredValue::new(false);
+    Effect::new(move |first: Option<()>| {
+        source.track();
+        if first.is_none() || pending.get_value() {
+            return;
+        }
+        pending.set_value(true);
+        request_animation_frame(move || {
+            if framed.is_disposed() {
+                return;
+            }
+            pending.set_value(false);
+            framed.set(source.get_untracked());
+        });
+    });
+    framed.into()
+}
+
+/// Step the drawn forming candle from `from` to `to` once per frame until
+/// the transition ends or a newer tick (another `id`) replaces it
+fn tween_forming(
+    forming: RwSignal<Option<Candle>>,
+    (from, to): (Candle, Candle),
+    start: f64,
+    duration: f64,
+    (generation, id): (StoredValue<u64>, u64),
+) {
+    request_animation_frame(move || {
+        if generation.is_disposed() || generation.get_value() != id {
+            return;
+        }
+        let t = ((js_sys::Date::now() - start) / duration).min(1.0);
+        if t < 1.0 {
+            forming.set(Some(lerp_candle(&from, &to, ease_out_cubic(t))));
+            tween_forming(forming, (from, to), start, duration, (generation, id));
+        } else {
+            forming.set(None);
+        }
+    });
+}
+
+/// `from` moved `t` of the way to `to`: one bar between two of its updates
+fn lerp_candle(from: &Candle, to: &Candle, t: f64) -> Candle {
+    let mix = |a: f64, b: f64| a + (b - a) * t;
+    Candle {
+        open: Price::new(mix(from.open.as_f64(), to.open.as_f64())),
+        high: Price::new(mix(from.high.as_f64(), to.high.as_f64())),
+        low: Price::new(mix(from.low.as_f64(), to.low.as_f64())),
+        close: Price::new(mix(from.close.as_f64(), to.close.as_f64())),
+        volume: Quantity::new(mix(from.volume.as_f64(), to.volume.as_f64())),
+        ..to.clone()
+    }
+}
+
+/// `state` with its newest bar drawn as `forming` while that bar is mid-transition
+fn with_forming(state: &ChartState, forming: Option<Candle>) -> std::borrow::Cow<'_, ChartState> {
+    match forming {
+        Some(candle) if state.live && state.candles.last().is_some_and(|last| last.timestamp == candle.timestamp) => {
+            let mut state = state.clone();
+            if let Some(last) = state.candles.last_mut() {
+                *last = candle;
+            }
+            std::borrow::Cow::Owned(state)
+        }
+        _ => std::borrow::Cow::Borrowed(state),
+    }
+}
+
+/// The per-bar part of the chart: the price series in `style`, then the
+/// volume bars, through whichever backend is rendering
+fn paint_series<B: ChartBackend + ?Sized>(
+    backend: &mut B,
+    state: &ChartState,
+    style: ChartType,
+    panes: Panes,
+    palette: &Palette,
+) {
+    match style {
+        ChartType::Candles | ChartType::Renko | ChartType::RangeBars => {
+            paint_candles(backend, &state.candles, state, palette)
+        }
+        ChartType::HeikinAshi => paint_candles(backend, &heikin_ashi(&state.candles), state, palette),
+        ChartType::Bars => {
+            // High-low bars with the open ticked left and the close ticked right
+            for (i, candle) in state.candles.iter().enumerate() {
+                let x = state.x_scale.scale(i);
+                let x_center = x + state.bandwidth / 2.0;
+                let fill = candle.fill_color(palette);
+                let open_y = state.y_scale.scale(candle.open.as_f64());
+                let close_y = state.y_scale.scale(candle.close.as_f64());
+                backend.line(
+                    (x_center, state.y_scale.scale(candle.high.as_f64())),
+                    (x_center, state.y_scale.scale(candle.low.as_f64())),
+                    fill,
+                    1.5,
+                );
+                backend.line((x, open_y), (x_center, open_y), fill, 1.5);
+                backend.line((x_center, close_y), (x + state.bandwidth, close_y), fill, 1.5);
+            }
+        }
+        ChartType::Line | ChartType::Area => {
+            let points: Vec<(f64, f64)> = state
+                .candles
+                .iter()
+                .enumerate()
+                .map(|(i, candle)| (state.x_scale.scale_center(i), state.y_scale.scale(candle.close.as_f64())))
+                .collect();
+            if style == ChartType::Area {
+                backend.area(&points, panes.price_height, palette.info, 0.12);
+            }
+            backend.polyline(&points, palette.info, 1.5);
+        }
+    }
+
+    if let Some((top, height)) = panes.volume {
+        for (i, candle) in state.candles.iter().enumerate() {
+            let bar_y = state.vol_scale.scale(candle.volume.as_f64());
+            let fill = if candle.is_bullish() {
+                palette.bull_alpha(0.5)
+            } else {
+                palette.bear_alpha(0.5)
+            };
+            backend.rect(state.x_scale.scale(i), top + bar_y, state.bandwidth, (height - bar_y).max(0.0), &fill);
+        }
+    }
+}
+
+/// Wick-and-body candles (the real ones, or a transform of them)
+fn paint_candles<B: ChartBackend + ?Sized>(backend: &mut B, candles: &[Candle], state: &ChartState, palette: &Palette) {
+    for (i, candle) in candles.iter().enumerate() {
+        let x = state.x_scale.scale(i);
+        let x_center = x + state.bandwidth / 2.0;
+        let fill = candle.fill_color(palette);
+
+        backend.line(
+            (x_center, state.y_scale.scale(candle.high.as_f64())),
+            (x_center, state.y_scale.scale(candle.low.as_f64())),
+            fill,
+            1.0,
+        );
+
+        let body_top = candle.open.as_f64().max(candle.close.as_f64());
+        let body_bottom = candle.open.as_f64().min(candle.close.as_f64());
+        let body_y = state.y_scale.scale(body_top);
+        let body_h = (state.y_scale.scale(body_bottom) - body_y).max(1.0);
+        backend.rect(x, body_y, state.bandwidth, body_h, fill);
+    }
+}
+
+/// Widen `range` to take in every study line and band, and the VWAP ±2σ
+fn fit_overlay_range(
+    range: (f64, f64),
+    overlays: &OverlaySeries,
+    vwap: &[Option<VwapPoint>],
+    window: Range<usize>,
+) -> (f64, f64) {
+    let study_extents = overlays
+        .series
+        .iter()
+        .flat_map(|(_, points)| in_window(points, &window).iter().flatten())
+        .map(|p| p.band.map_or((p.value, p.value), |(upper, lower)| (lower, upper)));
+    let vwap_extents = in_window(vwap, &window).iter().flatten().map(|p| (p.band(-2.0), p.band(2.0)));
+    study_extents
+        .chain(vwap_extents)
+        .fold(range, |(low, high), (lower, upper)| (low.min(lower), high.max(upper)))
+}
+
+/// One drawing in plot coordinates; a selected one shows its handles
+fn drawing_view(state: &ChartState, drawing: &Drawing, selected: bool, plot_width: f64) -> AnyView {
+    let px = state.drawing_pixels(drawing);
+    let (a, b) = (px.start, px.end);
+    let color = if selected { colors::css::WARN } else { colors::css::INFO };
+    let (left, right) = (a.0.min(b.0), a.0.max(b.0));
+    let body = match drawing.tool {
+        DrawingTool::Trendline => view! {
+            <line x1={a.0} y1={a.1} x2={b.0} y2={b.1} stroke=color stroke-width="1.5" />
+        }
+        .into_any(),
+        DrawingTool::HorizontalRay => view! {
+            <line x1={a.0} y1={a.1} x2=plot_width y2={a.1} stroke=color stroke-width="1.5" />
+            <text x={plot_width - 4.0} y={a.1 - 3.0} text-anchor="end" fill=color font-size="9">
+                {state.y_scale.label(drawing.start.price)}
+            </text>
+        }
+        .into_any(),
+        DrawingTool::Rectangle => view! {
+            <rect
+                x=left
+                y={a.1.min(b.1)}
+                width={right - left}
+                height={(a.1 - b.1).abs()}
+                fill=color
+                fill-opacity="0.12"
+                stroke=color
+                stroke-width="1"
+            />
+        }
+        .into_any(),
+        DrawingTool::Fibonacci => view! {
+            <line x1={a.0} y1={a.1} x2={b.0} y2={b.1} stroke=color stroke-width="1" stroke-dasharray="3,3" />
+            {drawing
+                .fib_levels()
+                .zip(px.levels.iter().copied())
+                .map(|((ratio, price), y)| view! {
+                    <line x1=left y1=y x2=right y2=y stroke=color stroke-width="1" />
+                    <text x={left + 2.0} y={y - 2.0} fill=color font-size="9">
+                        {format!("{:.1}%  {}", ratio * 100.0, state.y_scale.format().price(price))}
+                    </text>
+                })
+                .collect_view()}
+        }
+        .into_any(),
+    };
+    let handle = |(cx, cy): (f64, f64)| view! {
+        <circle cx=cx cy=cy r="4" fill=colors::css::BG_PANEL stroke=color stroke-width="1.5" />
+    };
+    let handles = selected.then(|| view! {
+        {handle(a)}
+        {(!drawing.tool.is_single_point()).then(|| handle(b))}
+    });
+    view! {
+        <g class="chart-drawing" pointer-events="none">
+            {body}
+            {handles}
+        </g>
+    }
+    .into_any()
+}
+
+/// Candles in view as `(start, end)` indices: `bars` of them ending at the
+/// candle opened at `right_edge`, or at the newest one
+fn view_window(candles: &[Candle], right_edge: Option<i64>, bars: usize) -> (usize, usize) {
+    let len = candles.len();
+    let end = right_edge.map_or(len, |edge| candles.partition_point(|c| c.timestamp <= edge).max(bars.min(len)));
+    (end.saturating_sub(bars), end)
+}
+
+/// Per-candle points (studies, VWAP) for the candles in `window`, which may
+/// run past the end of a series that hasn't caught up yet
+fn in_window<'a, T>(points: &'a [T], window: &Range<usize>) -> &'a [T] {
+    let end = window.end.min(points.len());
+    &points[window.start.min(end)..end]
+}
+
+/// Pointer position in plot coordinates (viewBox units inside the margins)
+pub(crate) fn plot_point(ev: &leptos::ev::MouseEvent, dims: ChartDimensions) -> Option<(f64, f64)> {
+    let rect = ev
+        .current_target()
+        .and_then(|t| t.dyn_into::<web_sys::Element>().ok())?
+        .get_bounding_client_rect();
+    let (x, y) = dims.to_viewbox(
+        (rect.width(), rect.height()),
+        (f64::from(ev.client_x()) - rect.left(), f64::from(ev.client_y()) - rect.top()),
+    );
+    Some((x - dims.margin.left, y - dims.margin.top))
+}
+
+/// Grid lines component: horizontal lines at `rows`, vertical lines evenly spaced
 #[component]
 fn ChartGrid(
     width: f64,
     height: f64,
-    h_lines: usize,
+    rows: Vec<f64>,
     v_lines: usize,
 ) -> impl IntoView {
-    let h_positions: Vec<f64> = if h_lines > 0 {
-        (0..=h_lines).map(|i| i as f64 * height / h_lines as f64).collect()
-    } else {
-        vec![]
-    };
-
     let v_positions: Vec<f64> = if v_lines > 0 {
         (0..=v_lines).map(|i| i as f64 * width / v_lines as f64).collect()
     } else {
@@ -295,12 +1580,12 @@ fn ChartGrid(
     view! {
         <g class="chart-grid">
             // Horizontal lines
-            {h_positions.into_iter().map(|y| {
+            {rows.into_iter().map(|y| {
                 view! {
                     <line
                         x1="0" y1=y
                         x2=width y2=y
-                        stroke=colors::GRID
+                        stroke=colors::css::GRID
                         stroke-width="1"
                         stroke-dasharray="2,2"
                     />
@@ -313,7 +1598,7 @@ fn ChartGrid(
                     <line
                         x1=x y1="0"
                         x2=x y2=height
-                        stroke=colors::GRID
+                        stroke=colors::css::GRID
                         stroke-width="1"
                         stroke-dasharray="2,2"
                     />
@@ -378,7 +1663,7 @@ pub fn CandlestickSparkline(
                         let body_y = y_scale.scale(body_top);
                         let body_h = (y_scale.scale(body_bottom) - body_y).max(1.0);
 
-                        let fill = candle.fill_color();
+                        let fill = candle.css_color();
 
                         view! {
                             <g>
@@ -401,3 +1686,25 @@ pub fn CandlestickSparkline(
         </svg>
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use dash_core::{CandleInterval, Symbol};
+
+    #[test]
+    fn test_forming_candle_turns_as_the_close_crosses_the_open() {
+        let mut from = Candle::new(Symbol::new("BTC-USD"), CandleInterval::M1, 0, 100.0);
+        from.update(101.0, 1.0);
+        let mut to = from.clone();
+        to.update(98.0, 1.0);
+
+        let early = lerp_candle(&from, &to, 0.2);
+        assert!(early.is_bullish());
+        assert!((early.close.as_f64() - 100.4).abs() < 1e-9);
+        let late = lerp_candle(&from, &to, 0.5);
+        assert!(!late.is_bullish());
+        assert!((late.low.as_f64() - 99.0).abs() < 1e-9);
+        assert_eq!(lerp_candle(&from, &to, 1.0), to);
+    }
+}
@@ -3,6 +3,7 @@
 //! Core chart primitives: scales, path builders, axis generators.
 //! Implements Strategy pattern for flexible scale and rendering behaviors.
 
+use dash_core::{utc_day_start, PriceScaleMode, TickFormat, TimeZoneMode};
 use std::fmt::Write;
 
 // ============================================================================
@@ -26,7 +27,7 @@ pub trait Scale: Send + Sync {
 // ============================================================================
 
 /// Linear scale (D3-style continuous scale)
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct LinearScale {
     domain: (f64, f64),
     range: (f64, f64),
@@ -154,6 +155,167 @@ impl Scale for LinearScale {
     }
 }
 
+// ============================================================================
+// PRICE SCALE
+// ============================================================================
+
+/// Price axis in one of the [`PriceScaleMode`]s. Maps through a linear scale
+/// over the mode's axis units (price or ln price), and puts ticks on round
+/// values of what the axis shows: prices, 1-2-5 steps per decade, or percents.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PriceScale {
+    mode: PriceScaleMode,
+    axis: LinearScale,
+    /// Price the percent mode measures change from
+    base: f64,
+    /// Precision of price labels
+    format: TickFormat,
+}
+
+impl PriceScale {
+    /// Log needs positive prices and falls back to linear otherwise
+    pub fn new(mode: PriceScaleMode, domain: (f64, f64), range: (f64, f64)) -> Self {
+        let mode = if mode == PriceScaleMode::Log && domain.0 <= 0.0 { PriceScaleMode::Linear } else { mode };
+        let mut scale = Self {
+            mode,
+            axis: LinearScale::new().range(range.0, range.1),
+            base: domain.0,
+            format: TickFormat::default(),
+        };
+        let axis_domain = (scale.axis_value(domain.0), scale.axis_value(domain.1));
+        scale.axis = scale.axis.domain(axis_domain.0, axis_domain.1);
+        scale
+    }
+
+    /// Widen the domain by `fraction` of its span on each side, in axis units
+    pub fn padding(mut self, fraction: f64) -> Self {
+        let (min, max) = self.axis.domain_bounds();
+        let pad = (max - min) * fraction;
+        self.axis = self.axis.domain(min - pad, max + pad);
+        self
+    }
+
+    /// Price the percent mode reads 0% at
+    pub fn base(mut self, base: f64) -> Self {
+        self.base = base;
+        self
+    }
+
+    /// Label prices at the symbol's tick precision
+    pub fn tick_format(mut self, format: TickFormat) -> Self {
+        self.format = format;
+        self
+    }
+
+    pub fn mode(&self) -> PriceScaleMode {
+        self.mode
+    }
+
+    /// Price range covered
+    pub fn domain_bounds(&self) -> (f64, f64) {
+        let (min, max) = self.axis.domain_bounds();
+        (self.price_at(min), self.price_at(max))
+    }
+
+    fn axis_value(&self, price: f64) -> f64 {
+        match self.mode {
+            PriceScaleMode::Log => price.max(f64::MIN_POSITIVE).ln(),
+            PriceScaleMode::Linear | PriceScaleMode::Percent => price,
+        }
+    }
+
+    fn price_at(&self, value: f64) -> f64 {
+        match self.mode {
+            PriceScaleMode::Log => value.exp(),
+            PriceScaleMode::Linear | PriceScaleMode::Percent => value,
+        }
+    }
+
+    /// Price range after stretching the axis by `factor` about its middle
+    /// (above 1 shows more, below 1 zooms in)
+    pub fn stretched_domain(&self, factor: f64) -> (f64, f64) {
+        let (min, max) = self.axis.domain_bounds();
+        let (middle, half) = ((min + max) / 2.0, (max - min) / 2.0 * factor);
+        (self.price_at(middle - half), self.price_at(middle + half))
+    }
+
+    /// Change of `price` from the base, in percent
+    pub fn percent(&self, price: f64) -> f64 {
+        if self.base == 0.0 {
+            return 0.0;
+        }
+        (price / self.base - 1.0) * 100.0
+    }
+
+    /// Axis and crosshair label for `price`
+    pub fn label(&self, price: f64) -> String {
+        match self.mode {
+            PriceScaleMode::Percent => format!("{:+.2}%", self.percent(price)),
+            PriceScaleMode::Linear | PriceScaleMode::Log => self.format.price(price),
+        }
+    }
+
+    /// Price precision of the labels
+    pub fn format(&self) -> TickFormat {
+        self.format
+    }
+
+    /// Tick prices for roughly `count` gridlines
+    pub fn nice_ticks(&self, count: usize) -> Vec<f64> {
+        let (min, max) = self.domain_bounds();
+        let linear = || LinearScale::new().domain(min, max).nice_ticks(count);
+        match self.mode {
+            PriceScaleMode::Linear => linear(),
+            PriceScaleMode::Percent if self.base != 0.0 => LinearScale::new()
+                .domain(self.percent(min), self.percent(max))
+                .nice_ticks(count)
+                .into_iter()
+                .map(|percent| self.base * (1.0 + percent / 100.0))
+                .collect(),
+            PriceScaleMode::Percent => linear(),
+            PriceScaleMode::Log => {
+                let ticks = decade_ticks(min, max, &[1.0, 2.0, 5.0]);
+                if ticks.len() > count * 2 {
+                    decade_ticks(min, max, &[1.0])
+                } else if ticks.len() < count.div_ceil(2) {
+                    // Narrow range: round prices, unevenly spaced on screen
+                    linear()
+                } else {
+                    ticks
+                }
+            }
+        }
+    }
+}
+
+/// `steps` × each power of ten that falls within `min..=max` (give or take
+/// the rounding from going through ln and back)
+fn decade_ticks(min: f64, max: f64, steps: &[f64]) -> Vec<f64> {
+    let (min, max) = (min * (1.0 - 1e-9), max * (1.0 + 1e-9));
+    let mut ticks = Vec::new();
+    let mut exponent = min.log10().floor() as i32;
+    while 10f64.powi(exponent) <= max {
+        let decade = 10f64.powi(exponent);
+        ticks.extend(steps.iter().map(|step| step * decade).filter(|tick| (min..=max).contains(tick)));
+        exponent += 1;
+    }
+    ticks
+}
+
+impl Scale for PriceScale {
+    fn scale(&self, value: f64) -> f64 {
+        self.axis.scale(self.axis_value(value))
+    }
+
+    fn invert(&self, value: f64) -> f64 {
+        self.price_at(self.axis.invert(value))
+    }
+
+    fn ticks(&self, count: usize) -> Vec<f64> {
+        self.nice_ticks(count)
+    }
+}
+
 // ============================================================================
 // TIME SCALE
 // ============================================================================
@@ -221,7 +383,7 @@ impl Default for TimeScale {
 // ============================================================================
 
 /// Band scale for categorical data (e.g., candlestick x positions)
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct BandScale {
     domain_count: usize,
     range: (f64, f64),
@@ -299,6 +461,18 @@ impl BandScale {
     pub fn scale_center(&self, index: usize) -> f64 {
         self.scale(index) + self.bandwidth() / 2.0
     }
+
+    /// Index of the band whose center is nearest a range position (`None`
+    /// for an empty scale)
+    pub fn index_at(&self, position: f64) -> Option<usize> {
+        if self.domain_count == 0 {
+            return None;
+        }
+        let step = self.step();
+        let first_center = self.scale_center(0);
+        let index = ((position - first_center) / step).round().max(0.0) as usize;
+        Some(index.min(self.domain_count - 1))
+    }
 }
 
 impl Default for BandScale {
@@ -307,6 +481,51 @@ impl Default for BandScale {
     }
 }
 
+// ============================================================================
+// BAR TIME AXIS (time across evenly spaced bars)
+// ============================================================================
+
+/// Places times on a band scale of consecutive bars, carrying on past either
+/// end (for anything positioned by time rather than by bar index)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BarTimeAxis {
+    /// Open time of bar 0 (ms)
+    pub first_open: i64,
+    /// Bar length (ms)
+    pub bar_ms: i64,
+    /// Center of bar 0
+    pub origin: f64,
+    /// Distance between bar centers
+    pub step: f64,
+}
+
+impl BarTimeAxis {
+    pub fn new(bands: &BandScale, first_open: i64, bar_ms: i64) -> Self {
+        Self {
+            first_open,
+            bar_ms: bar_ms.max(1),
+            origin: bands.scale_center(0),
+            step: bands.step(),
+        }
+    }
+
+    /// Center of the bar opening at `time`
+    pub fn x_at(&self, time: i64) -> f64 {
+        self.origin + (time - self.first_open) as f64 / self.bar_ms as f64 * self.step
+    }
+
+    /// An instant inside its bar's slot, from the left edge at the open
+    pub fn x_within(&self, time: i64) -> f64 {
+        self.x_at(time) - self.step / 2.0
+    }
+
+    /// Open time of the bar nearest `x`
+    pub fn time_at(&self, x: f64) -> i64 {
+        let bars = if self.step > 0.0 { ((x - self.origin) / self.step).round() as i64 } else { 0 };
+        self.first_open + bars * self.bar_ms
+    }
+}
+
 // ============================================================================
 // STRATEGY PATTERN: Path Generator Trait
 // ============================================================================
@@ -371,7 +590,7 @@ impl PathGenerator for StepPath {
         write!(path, "M{:.2},{:.2}", x, y).unwrap();
 
         for i in 1..points.len() {
-            let (x0, y0) = points[i - 1];
+            let (x0, _) = points[i - 1];
             let (x1, y1) = points[i];
 
             match self.step_position {
@@ -444,6 +663,7 @@ impl PathBuilder {
         self
     }
 
+    #[allow(clippy::too_many_arguments)]
     pub fn arc_to(
         mut self,
         rx: f64,
@@ -513,21 +733,6 @@ pub fn line_path(points: &[(f64, f64)]) -> String {
 // FORMATTERS
 // ============================================================================
 
-/// Format price for axis labels
-pub fn format_price(price: f64, decimals: usize) -> String {
-    if price >= 1_000_000.0 {
-        format!("{:.1}M", price / 1_000_000.0)
-    } else if price >= 10_000.0 {
-        format!("{:.0}", price)
-    } else if price >= 1_000.0 {
-        format!("{:.1}", price)
-    } else if price >= 1.0 {
-        format!("{:.prec$}", price, prec = decimals)
-    } else {
-        format!("{:.6}", price)
-    }
-}
-
 /// Format volume for axis labels
 pub fn format_volume(volume: f64) -> String {
     if volume >= 1_000_000.0 {
@@ -563,13 +768,163 @@ pub fn format_time(timestamp_ms: i64, interval_secs: i64) -> String {
 
     if interval_secs >= 86400 {
         dt.format("%b %d").to_string()
-    } else if interval_secs >= 3600 {
-        dt.format("%H:%M").to_string()
     } else {
         dt.format("%H:%M").to_string()
     }
 }
 
+// ============================================================================
+// TIME AXIS
+// ============================================================================
+
+const DAY_MS: i64 = 86_400_000;
+
+/// Label spacings the time axis steps through as bars narrow (ms)
+const TIME_STEPS_MS: &[i64] = &[
+    60_000,
+    5 * 60_000,
+    15 * 60_000,
+    30 * 60_000,
+    3_600_000,
+    2 * 3_600_000,
+    4 * 3_600_000,
+    6 * 3_600_000,
+    12 * 3_600_000,
+    DAY_MS,
+    2 * DAY_MS,
+    7 * DAY_MS,
+    14 * DAY_MS,
+];
+
+/// Narrowest gap between time labels (px)
+pub const MIN_TIME_LABEL_GAP_PX: f64 = 64.0;
+
+/// A labelled bar on the time axis
+#[derive(Debug, Clone, PartialEq)]
+pub struct TimeTick {
+    /// Bar index in view
+    pub index: usize,
+    pub label: String,
+    /// First bar of a new day (a new month on daily spacing), drawn bold
+    pub boundary: bool,
+}
+
+/// Labels for bars opening at `times`, on the first bar of each step of
+/// wall time, the step being the finest that keeps labels apart at `bar_px`
+/// per bar. A hole in the history moves a label onto the bar after it.
+pub fn time_ticks(times: &[i64], interval_ms: i64, bar_px: f64, tz: TimeZoneMode) -> Vec<TimeTick> {
+    use chrono::Datelike;
+
+    let min_bars = (MIN_TIME_LABEL_GAP_PX / bar_px.max(f64::EPSILON)).ceil().max(1.0) as usize;
+    let wanted = interval_ms.max(1).saturating_mul(min_bars as i64);
+    let step = TIME_STEPS_MS
+        .iter()
+        .copied()
+        .find(|&step| step >= wanted)
+        .unwrap_or(TIME_STEPS_MS[TIME_STEPS_MS.len() - 1]);
+    let daily = step >= DAY_MS;
+    let month = |wall: i64| chrono::DateTime::from_timestamp_millis(wall).map(|t| (t.year(), t.month()));
+
+    let mut ticks: Vec<TimeTick> = Vec::new();
+    for (index, pair) in times.windows(2).enumerate() {
+        let (before, wall) = (tz.shift(pair[0]), tz.shift(pair[1]));
+        let index = index + 1;
+        let boundary = if daily {
+            month(before) != month(wall)
+        } else {
+            utc_day_start(before) != utc_day_start(wall)
+        };
+        if !boundary && before.div_euclid(step) == wall.div_euclid(step) {
+            continue;
+        }
+        // Too close to the last label: a boundary takes its place
+        if let Some(last) = ticks.last()
+            && index - last.index < min_bars
+        {
+            if !boundary || last.boundary {
+                continue;
+            }
+            ticks.pop();
+        }
+        let format = match (daily, boundary) {
+            (true, true) => "%b",
+            (true, false) => "%d",
+            (false, true) => "%b %d",
+            (false, false) => "%H:%M",
+        };
+        let label = chrono::DateTime::from_timestamp_millis(wall)
+            .map(|t| t.format(format).to_string())
+            .unwrap_or_default();
+        ticks.push(TimeTick { index, label, boundary });
+    }
+    ticks
+}
+
+/// Bars that open more than one interval after the bar before (history
+/// missing in between)
+pub fn time_gaps(times: &[i64], interval_ms: i64) -> Vec<usize> {
+    times
+        .windows(2)
+        .enumerate()
+        .filter(|(_, pair)| pair[1] - pair[0] > interval_ms)
+        .map(|(i, _)| i + 1)
+        .collect()
+}
+
+// ============================================================================
+// TRANSITIONS
+// ============================================================================
+
+/// Decelerating easing curve over `t` in 0..=1
+pub fn ease_out_cubic(t: f64) -> f64 {
+    1.0 - (1.0 - t.clamp(0.0, 1.0)).powi(3)
+}
+
+/// Points `t` of the way from `from` to `to`, matched by index; the shorter
+/// series is padded with its last point so added or dropped points grow out
+/// of (or collapse into) the end of the curve
+pub fn lerp_points(from: &[(f64, f64)], to: &[(f64, f64)], t: f64) -> Vec<(f64, f64)> {
+    let (Some(&from_last), Some(&to_last)) = (from.last(), to.last()) else {
+        return to.to_vec();
+    };
+    (0..from.len().max(to.len()))
+        .map(|i| {
+            let a = from.get(i).copied().unwrap_or(from_last);
+            let b = to.get(i).copied().unwrap_or(to_last);
+            (a.0 + (b.0 - a.0) * t, a.1 + (b.1 - a.1) * t)
+        })
+        .collect()
+}
+
+/// Update rate over the last few updates, for dropping transitions when data
+/// arrives faster than they could play out
+#[derive(Debug, Clone, Default)]
+pub struct UpdateRate {
+    /// Recent update times (ms), oldest first
+    times: std::collections::VecDeque<f64>,
+}
+
+impl UpdateRate {
+    /// Updates the rate is measured over
+    const WINDOW: usize = 8;
+
+    /// Record an update at `now` (ms)
+    pub fn record(&mut self, now: f64) {
+        self.times.push_back(now);
+        if self.times.len() > Self::WINDOW {
+            self.times.pop_front();
+        }
+    }
+
+    /// Updates per second (0 until there are two)
+    pub fn per_sec(&self) -> f64 {
+        match (self.times.front(), self.times.back()) {
+            (Some(first), Some(last)) if last > first => (self.times.len() - 1) as f64 * 1000.0 / (last - first),
+            _ => 0.0,
+        }
+    }
+}
+
 // ============================================================================
 // TESTS
 // ============================================================================
@@ -578,6 +933,20 @@ pub fn format_time(timestamp_ms: i64, interval_secs: i64) -> String {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_bar_time_axis() {
+        let bands = BandScale::new(10).range(0.0, 100.0).padding(0.0, 0.0);
+        let axis = BarTimeAxis::new(&bands, 60_000, 60_000);
+
+        assert_eq!(axis.x_at(60_000), 5.0);
+        assert_eq!(axis.x_at(180_000), 25.0);
+        // Before the first bar and halfway through one
+        assert_eq!(axis.x_at(0), -5.0);
+        assert_eq!(axis.x_within(90_000), 5.0);
+        assert_eq!(axis.time_at(26.0), 180_000);
+        assert_eq!(axis.time_at(-4.0), 0);
+    }
+
     #[test]
     fn test_linear_scale() {
         let scale = LinearScale::new()
@@ -598,6 +967,34 @@ mod tests {
         assert_eq!(scale.invert(250.0), 50.0);
     }
 
+    #[test]
+    fn test_price_scale_modes() {
+        let log = PriceScale::new(PriceScaleMode::Log, (10.0, 1000.0), (100.0, 0.0));
+        assert!((log.scale(100.0) - 50.0).abs() < 1e-9);
+        assert!((log.invert(50.0) - 100.0).abs() < 1e-9);
+        let ticks = log.nice_ticks(5);
+        assert_eq!(ticks.len(), 7);
+        assert!((ticks[1] - 20.0).abs() < 1e-9 && (ticks[6] - 1000.0).abs() < 1e-9);
+        // Too narrow for decades: falls back to round prices
+        let narrow = PriceScale::new(PriceScaleMode::Log, (100.0, 110.0), (100.0, 0.0));
+        let (low, high) = narrow.domain_bounds();
+        assert_eq!(narrow.nice_ticks(5), LinearScale::new().domain(low, high).nice_ticks(5));
+
+        let percent = PriceScale::new(PriceScaleMode::Percent, (95.0, 110.0), (100.0, 0.0)).base(100.0);
+        let ticks = percent.nice_ticks(5);
+        assert_eq!(ticks.len(), 4);
+        assert!(ticks.iter().zip([95.0, 100.0, 105.0, 110.0]).all(|(tick, price)| (tick - price).abs() < 1e-9));
+        assert_eq!(percent.label(105.0), "+5.00%");
+        assert_eq!(percent.scale(95.0), 100.0);
+
+        // Axis drag stretches about the middle in axis units
+        let (low, high) = log.stretched_domain(0.5);
+        assert!((low - 10f64.powf(1.5)).abs() < 1e-6 && (high - 10f64.powf(2.5)).abs() < 1e-6);
+
+        // Log can't show non-positive prices
+        assert_eq!(PriceScale::new(PriceScaleMode::Log, (0.0, 1.0), (1.0, 0.0)).mode(), PriceScaleMode::Linear);
+    }
+
     #[test]
     fn test_band_scale() {
         let scale = BandScale::new(5).range(0.0, 100.0);
@@ -606,6 +1003,16 @@ mod tests {
         assert!(bw < 20.0); // Should be less than 100/5
     }
 
+    #[test]
+    fn test_band_scale_index_at() {
+        let scale = BandScale::new(5).range(0.0, 100.0);
+        assert_eq!(scale.index_at(scale.scale_center(3)), Some(3));
+        assert_eq!(scale.index_at(scale.scale_center(3) + scale.step() * 0.4), Some(3));
+        assert_eq!(scale.index_at(-50.0), Some(0));
+        assert_eq!(scale.index_at(500.0), Some(4));
+        assert_eq!(BandScale::new(0).index_at(10.0), None);
+    }
+
     #[test]
     fn test_path_builder() {
         let path = PathBuilder::new()
@@ -628,6 +1035,43 @@ mod tests {
         assert!(path.contains("L50.00,50.00"));
     }
 
+    #[test]
+    fn test_transitions() {
+        assert_eq!(ease_out_cubic(0.0), 0.0);
+        assert_eq!(ease_out_cubic(1.0), 1.0);
+        assert!(ease_out_cubic(0.5) > 0.5);
+
+        let from = [(0.0, 0.0), (10.0, 10.0)];
+        let to = [(0.0, 10.0), (20.0, 20.0), (30.0, 40.0)];
+        assert_eq!(lerp_points(&from, &to, 0.5), vec![(0.0, 5.0), (15.0, 15.0), (20.0, 25.0)]);
+        assert_eq!(lerp_points(&[], &to, 0.5), to.to_vec());
+
+        let mut rate = UpdateRate::default();
+        rate.record(0.0);
+        assert_eq!(rate.per_sec(), 0.0);
+        (1..=4).for_each(|i| rate.record(i as f64 * 100.0));
+        assert_eq!(rate.per_sec(), 10.0);
+    }
+
+    #[test]
+    fn test_time_ticks() {
+        const HOUR: i64 = 3_600_000;
+        // 15m bars from 22:00 to 02:00 UTC, an hour missing after midnight
+        let times: Vec<i64> = (0..17)
+            .map(|i| 22 * HOUR + i * HOUR / 4)
+            .filter(|&t| !(24 * HOUR + HOUR / 4..25 * HOUR).contains(&t))
+            .collect();
+
+        // 16px bars: hourly labels, four bars apart at least, so 01:00
+        // right after the gap is dropped as too close to midnight's
+        let ticks = time_ticks(&times, HOUR / 4, 16.0, TimeZoneMode::Utc);
+        let labels: Vec<(&str, bool)> = ticks.iter().map(|t| (t.label.as_str(), t.boundary)).collect();
+        assert_eq!(labels, vec![("23:00", false), ("Jan 02", true), ("02:00", false)]);
+        assert_eq!(ticks[2].index, 13);
+
+        assert_eq!(time_gaps(&times, HOUR / 4), vec![9]);
+    }
+
     #[test]
     fn test_format_large_number() {
         assert_eq!(format_large_number(1_500_000.0), "1.50M");
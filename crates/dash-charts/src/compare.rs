@@ -0,0 +1,133 @@
+//! Second symbol drawn over the candles as percent change
+//!
+//! Both series are measured from the first bar in view, so the comparison
+//! line starts on its own zero and runs against a secondary percent axis
+//! down the left edge of the plot, independent of the price axis.
+
+use crate::chartkit::{line_path, BandScale, LinearScale, Scale};
+use dash_core::Candle;
+use leptos::prelude::*;
+
+/// Stroke of the comparison line and its axis
+pub const COMPARE_COLOR: &str = "#e879f9";
+
+/// Percent axis ticks to aim for
+const COMPARE_TICKS: usize = 4;
+
+/// Axis labels stay clear of the legends at the top of the plot
+const LEGEND_CLEARANCE_PX: f64 = 34.0;
+
+/// Percent change of `other`'s close from its first close matched to a bar
+/// of `candles`, per bar (`None` where `other` has no bar at that time)
+pub fn compare_percent(candles: &[Candle], other: &[Candle]) -> Vec<Option<f64>> {
+    let close_at = |time: i64| {
+        other
+            .binary_search_by_key(&time, |c| c.timestamp)
+            .ok()
+            .map(|i| other[i].close.as_f64())
+    };
+    let base = candles
+        .iter()
+        .find_map(|c| close_at(c.timestamp))
+        .filter(|&base| base > 0.0);
+    candles
+        .iter()
+        .map(|c| Some((close_at(c.timestamp)? / base? - 1.0) * 100.0))
+        .collect()
+}
+
+/// Comparison line with its percent axis, scaled to its own range
+#[component]
+pub fn CompareOverlay(
+    /// Percent change per bar in view
+    points: Vec<Option<f64>>,
+    /// Label of the compared symbol
+    symbol: String,
+    /// Bar positions of the plot
+    x_scale: BandScale,
+    plot_height: f64,
+) -> impl IntoView {
+    let placed: Vec<(f64, f64)> = points
+        .iter()
+        .enumerate()
+        .filter_map(|(i, p)| Some((x_scale.scale_center(i), (*p)?)))
+        .collect();
+    let Some(&(last_x, last)) = placed.last() else {
+        return ().into_any();
+    };
+
+    let (low, high) = placed
+        .iter()
+        .fold((0.0_f64, 0.0_f64), |(low, high), &(_, p)| (low.min(p), high.max(p)));
+    let pad = ((high - low) * 0.05).max(0.1);
+    let y_scale = LinearScale::new()
+        .domain(low - pad, high + pad)
+        .range(plot_height, 0.0);
+    let line: Vec<(f64, f64)> = placed.iter().map(|&(x, p)| (x, y_scale.scale(p))).collect();
+    let zero = y_scale.scale(0.0);
+    let last_y = y_scale.scale(last);
+
+    let ticks = y_scale
+        .nice_ticks(COMPARE_TICKS)
+        .into_iter()
+        .map(|tick| (y_scale.scale(tick), tick))
+        .filter(|&(y, _)| y >= LEGEND_CLEARANCE_PX && y <= plot_height - 4.0)
+        .map(|(y, tick)| view! {
+            <text
+                x="4"
+                y=y
+                dy="0.32em"
+                fill=COMPARE_COLOR
+                fill-opacity="0.8"
+                font-size="9"
+                font-family="JetBrains Mono, monospace"
+            >
+                {format!("{:+.1}%", tick)}
+            </text>
+        })
+        .collect_view();
+
+    view! {
+        <g class="compare-overlay" pointer-events="none">
+            <line x1="0" y1=zero x2={last_x} y2=zero stroke=COMPARE_COLOR stroke-opacity="0.3" stroke-dasharray="2,4" />
+            <path d=line_path(&line) fill="none" stroke=COMPARE_COLOR stroke-width="1.5" />
+            {ticks}
+            <text
+                x={last_x - 6.0}
+                y={last_y - 8.0}
+                text-anchor="end"
+                fill=COMPARE_COLOR
+                font-size="10"
+                font-family="JetBrains Mono, monospace"
+            >
+                {format!("{} {:+.2}%", symbol, last)}
+            </text>
+        </g>
+    }
+    .into_any()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use dash_core::{CandleInterval, Symbol};
+
+    fn closes(symbol: &str, minutes: &[i64], close: impl Fn(i64) -> f64) -> Vec<Candle> {
+        minutes
+            .iter()
+            .map(|&m| Candle::new(Symbol::new(symbol), CandleInterval::M1, m * 60_000, close(m)))
+            .collect()
+    }
+
+    #[test]
+    fn test_compare_percent_measures_from_the_first_shared_bar() {
+        let chart = closes("BTC-USD", &[0, 1, 2, 3], |_| 100.0);
+        let other = closes("ETH-USD", &[1, 3], |m| 10.0 + m as f64);
+
+        let percent = compare_percent(&chart, &other);
+        assert_eq!(percent[0], None);
+        assert_eq!(percent[1], Some(0.0));
+        assert_eq!(percent[2], None);
+        assert!((percent[3].unwrap() - 2.0 / 11.0 * 100.0).abs() < 1e-9);
+    }
+}
@@ -0,0 +1,137 @@
+//! Cumulative volume delta (CVD) on the candle time axis
+//!
+//! Buy minus sell volume per bar is summed from the first bar in view, so
+//! the line shows who has been aggressing across the visible stretch. It
+//! runs as a sub-pane under the candlestick chart, or stand-alone with the
+//! same margins and bar layout so it lines up under a live chart.
+
+use crate::{
+    chartkit::{area_path, format_volume, line_path, BandScale, LinearScale, Scale},
+    colors, ChartDimensions, ChartMargin,
+};
+use leptos::prelude::*;
+
+/// Running buy − sell volume per bar, from zero before the first bar with
+/// data (`None` until then)
+pub fn cumulative_delta(deltas: &[Option<f64>]) -> Vec<Option<f64>> {
+    let mut sum = None;
+    deltas
+        .iter()
+        .map(|delta| {
+            if let Some(delta) = delta {
+                sum = Some(sum.unwrap_or(0.0) + delta);
+            }
+            sum
+        })
+        .collect()
+}
+
+fn format_delta(value: f64) -> String {
+    let sign = if value < 0.0 { "-" } else { "+" };
+    format!("{}{}", sign, format_volume(value.abs()))
+}
+
+/// CVD line and zero-split shading for the bars in view
+#[component]
+pub fn CvdPane(
+    /// Cumulative delta per bar in view
+    points: Vec<Option<f64>>,
+    /// Bar positions of the plot above
+    x_scale: BandScale,
+    plot_width: f64,
+    height: f64,
+) -> impl IntoView {
+    let placed: Vec<(f64, f64)> = points
+        .iter()
+        .enumerate()
+        .filter_map(|(i, p)| Some((x_scale.scale_center(i), (*p)?)))
+        .collect();
+
+    let (low, high) = placed
+        .iter()
+        .fold((0.0_f64, 0.0_f64), |(low, high), &(_, p)| (low.min(p), high.max(p)));
+    let pad = if high > low { (high - low) * 0.1 } else { 1.0 };
+    let y_scale = LinearScale::new()
+        .domain(low - pad, high + pad)
+        .range(height, 0.0);
+    let line: Vec<(f64, f64)> = placed.iter().map(|&(x, p)| (x, y_scale.scale(p))).collect();
+    let zero_y = y_scale.scale(0.0);
+    let below = (height - zero_y).max(0.0);
+    let last = placed.last().map(|&(_, p)| p);
+    let stroke = if last >= Some(0.0) { colors::css::BULL } else { colors::css::BEAR };
+    let area = area_path(&line, zero_y);
+
+    view! {
+        <g class="cvd-pane" pointer-events="none">
+            <line x1="0" y1="0" x2=plot_width y2="0" stroke=colors::css::BORDER stroke-width="1" />
+            // Nested viewports clip the one area to either side of zero
+            <svg width=plot_width height=zero_y>
+                <path d=area.clone() fill=colors::css::bull_alpha(0.2) />
+            </svg>
+            <svg y=zero_y width=plot_width height=below viewBox=format!("0 {} {} {}", zero_y, plot_width, below)>
+                <path d=area fill=colors::css::bear_alpha(0.2) />
+            </svg>
+            <line x1="0" y1=zero_y x2=plot_width y2=zero_y stroke=colors::css::BORDER stroke-dasharray="2,4" />
+            <path d=line_path(&line) fill="none" stroke=stroke stroke-width="1.25" />
+            <text
+                x="4"
+                y="10"
+                fill=colors::css::TEXT_MUTED
+                font-size="10"
+                font-family="JetBrains Mono, monospace"
+            >
+                "CVD "
+                <tspan fill=stroke>{last.map_or_else(|| "—".to_string(), format_delta)}</tspan>
+            </text>
+        </g>
+    }
+}
+
+/// Stand-alone CVD chart; with the same width and bar count it sits flush
+/// under a live [`CandlestickChart`](crate::CandlestickChart)
+#[component]
+pub fn CvdChart(
+    /// Buy − sell volume per candle, oldest first
+    #[prop(into)] deltas: Signal<Vec<Option<f64>>>,
+    #[prop(default = 800.0)] width: f64,
+    #[prop(default = 100.0)] height: f64,
+    /// Newest bars shown, as on the chart above
+    #[prop(default = 120)] visible_bars: usize,
+) -> impl IntoView {
+    let dims = ChartDimensions::new(width, height).with_margin(ChartMargin::right_axis());
+    let pane = move || {
+        deltas.with(|deltas| {
+            let start = deltas.len().saturating_sub(visible_bars.max(1));
+            let points = cumulative_delta(&deltas[start..]);
+            if points.is_empty() {
+                return None;
+            }
+            let x_scale = BandScale::new(points.len())
+                .range(0.0, dims.inner_width())
+                .padding(0.2, 0.1);
+            Some(view! {
+                <CvdPane points=points x_scale=x_scale plot_width=dims.inner_width() height=dims.inner_height() />
+            })
+        })
+    };
+
+    view! {
+        <svg class="cvd-chart" width=width height=height viewBox=format!("0 0 {} {}", width, height)>
+            <g transform=format!("translate({}, {})", dims.margin.left, dims.margin.top)>
+                {pane}
+            </g>
+        </svg>
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cumulative_delta_starts_at_the_first_bar_with_data() {
+        let deltas = [None, Some(2.0), Some(-3.0), Some(0.0), Some(1.5)];
+        assert_eq!(cumulative_delta(&deltas), vec![None, Some(2.0), Some(-1.0), Some(-1.0), Some(0.5)]);
+        assert!(cumulative_delta(&[None, None]).iter().all(Option::is_none));
+    }
+}
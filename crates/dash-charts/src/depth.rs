@@ -1,14 +1,30 @@
 //! Market depth chart (bid/ask visualization)
 //!
-//! Renders cumulative order book depth as filled area chart.
+//! Renders cumulative order book depth as filled area chart. Hovering reads
+//! off the depth at the pointer, the wheel zooms the price range about the
+//! mid, and an order size can be swept through both sides to show where a
+//! market order would fill. Between book updates the curves morph over a
+//! short transition, skipped when updates come in too fast for it to play
+//! out or the user prefers reduced motion.
 
 use crate::{
-    chartkit::{area_path, format_large_number, format_price, LinearScale, Scale},
+    candlestick::plot_point,
+    chartkit::{area_path, ease_out_cubic, format_large_number, lerp_points, LinearScale, Scale, UpdateRate},
     colors, ChartDimensions, ChartMargin,
 };
-use dash_core::MarketDepth;
+use dash_core::{MarketDepth, OrderSide, TickFormat, TradeSide};
 use leptos::prelude::*;
 
+/// Wheel zoom bounds (1 = the configured spread multiple)
+const MIN_ZOOM: f64 = 0.25;
+const MAX_ZOOM: f64 = 20.0;
+
+/// Wheel sensitivity: 100 px of scroll zooms by e^0.1
+const WHEEL_ZOOM_RATE: f64 = 0.001;
+
+/// Hover tooltip box size
+const TOOLTIP_SIZE: (f64, f64) = (132.0, 46.0);
+
 /// Depth chart configuration
 #[derive(Debug, Clone)]
 pub struct DepthChartConfig {
@@ -17,6 +33,12 @@ pub struct DepthChartConfig {
     pub spread_multiplier: f64, // How much of the spread to show (e.g., 20x)
     pub show_mid_line: bool,
     pub show_legend: bool,
+    /// Curve transition length (ms); 0 jumps straight to each update
+    pub animation_ms: f64,
+    /// Book updates per second above which transitions are skipped
+    pub max_animated_rate: f64,
+    /// Price precision of the axis and tooltips
+    pub price_format: TickFormat,
 }
 
 impl Default for DepthChartConfig {
@@ -27,12 +49,77 @@ impl Default for DepthChartConfig {
             spread_multiplier: 20.0,
             show_mid_line: true,
             show_legend: true,
+            animation_ms: 180.0,
+            max_animated_rate: 8.0,
+            price_format: TickFormat::default(),
+        }
+    }
+}
+
+/// Depth curves as drawn: `(price, cumulative quantity)` per level, best first
+#[derive(Debug, Clone, Default, PartialEq)]
+struct DepthCurves {
+    bids: Vec<(f64, f64)>,
+    asks: Vec<(f64, f64)>,
+    max_depth: f64,
+}
+
+impl DepthCurves {
+    fn from_depth(depth: &MarketDepth) -> Self {
+        let points = |side: &[dash_core::DepthPoint]| side.iter().map(|p| (p.price, p.cumulative_quantity)).collect();
+        Self {
+            bids: points(&depth.bid_depth),
+            asks: points(&depth.ask_depth),
+            max_depth: depth.max_depth(),
         }
     }
+
+    /// Curves `t` of the way to `to`
+    fn lerp(&self, to: &Self, t: f64) -> Self {
+        Self {
+            bids: lerp_points(&self.bids, &to.bids, t),
+            asks: lerp_points(&self.asks, &to.asks, t),
+            max_depth: self.max_depth + (to.max_depth - self.max_depth) * t,
+        }
+    }
+
+    fn price_range(&self) -> Option<(f64, f64)> {
+        self.bids.iter().chain(&self.asks).map(|p| p.0).fold(None, |range, price| {
+            let (low, high) = range.unwrap_or((price, price));
+            Some((low.min(price), high.max(price)))
+        })
+    }
+}
+
+/// Step the drawn curves from `from` to `to` once per frame until the
+/// transition ends or a newer one (another `id`) replaces it
+fn tween(
+    curves: RwSignal<Option<DepthCurves>>,
+    (from, to): (DepthCurves, DepthCurves),
+    start: f64,
+    duration: f64,
+    (generation, id): (StoredValue<u64>, u64),
+) {
+    request_animation_frame(move || {
+        if generation.is_disposed() || generation.get_value() != id {
+            return;
+        }
+        let t = ((js_sys::Date::now() - start) / duration).min(1.0);
+        curves.set(Some(from.lerp(&to, ease_out_cubic(t))));
+        if t < 1.0 {
+            tween(curves, (from, to), start, duration, (generation, id));
+        }
+    });
+}
+
+pub(crate) fn prefers_reduced_motion() -> bool {
+    web_sys::window()
+        .and_then(|w| w.match_media("(prefers-reduced-motion: reduce)").ok().flatten())
+        .is_some_and(|query| query.matches())
 }
 
 /// Internal depth chart state
-#[derive(Clone)]
+#[derive(Clone, PartialEq)]
 struct DepthState {
     bid_path: String,
     ask_path: String,
@@ -47,6 +134,10 @@ struct DepthState {
 pub fn DepthChart(
     depth: RwSignal<Option<MarketDepth>>,
     #[prop(optional)] config: Option<DepthChartConfig>,
+    /// Price level to mark (e.g. hovered in the order book)
+    #[prop(optional, into)] highlight_price: Option<Signal<Option<f64>>>,
+    /// Market order size (base units) to estimate fills and slippage for
+    #[prop(optional, into)] order_size: Option<Signal<Option<f64>>>,
 ) -> impl IntoView {
     let config = config.unwrap_or_default();
     
@@ -56,46 +147,74 @@ pub fn DepthChart(
     let show_mid = config.show_mid_line;
     let show_legend = config.show_legend;
     let spread_mult = config.spread_multiplier;
+    let price_format = config.price_format;
+    let zoom = RwSignal::new(1.0_f64);
+
+    // Curves as drawn, following the book through a transition when it's
+    // on and updates are slow enough to show it
+    let curves = RwSignal::new(None::<DepthCurves>);
+    let rate = StoredValue::new(UpdateRate::default());
+    let generation = StoredValue::new(0u64);
+    let (animation_ms, max_rate) = (config.animation_ms, config.max_animated_rate);
+    let reduced_motion = prefers_reduced_motion();
+    Effect::new(move |_| {
+        let target = depth.with(|d| d.as_ref().map(DepthCurves::from_depth));
+        let now = js_sys::Date::now();
+        rate.update_value(|r| r.record(now));
+        generation.update_value(|g| *g += 1);
+        let animate = animation_ms > 0.0 && !reduced_motion && rate.with_value(UpdateRate::per_sec) <= max_rate;
+        match (curves.get_untracked(), target) {
+            (Some(from), Some(to)) if animate => {
+                tween(curves, (from, to), now, animation_ms, (generation, generation.get_value()));
+            }
+            (_, target) => curves.set(target),
+        }
+    });
 
     // Compute chart state
-    let chart_state = move || {
-        depth.get().map(|d| {
+    let chart_memo = Memo::new(move |_| {
+        let zoom = zoom.get();
+        curves.get().map(|d| {
             // Get best bid/ask for centering
-            let bid_first = d.bid_depth.first().map(|p| p.price);
-            let ask_first = d.ask_depth.first().map(|p| p.price);
+            let bid_first = d.bids.first().map(|p| p.0);
+            let ask_first = d.asks.first().map(|p| p.0);
 
             // Calculate price range centered on mid price
             let (min_price, max_price) = match (bid_first, ask_first) {
                 (Some(bid), Some(ask)) => {
                     let mid = (bid + ask) / 2.0;
                     let spread = ask - bid;
-                    let range = spread * spread_mult;
+                    let range = spread * spread_mult / zoom;
                     (mid - range / 2.0, mid + range / 2.0)
                 }
-                _ => d.price_range().unwrap_or((0.0, 100.0)),
+                _ => {
+                    let (low, high) = d.price_range().unwrap_or((0.0, 100.0));
+                    let (center, half) = ((low + high) / 2.0, (high - low) / 2.0 / zoom);
+                    (center - half, center + half)
+                }
             };
 
             let x_scale = LinearScale::new()
                 .domain(min_price, max_price)
                 .range(0.0, dims.inner_width());
 
-            let max_depth = d.max_depth();
+            let max_depth = d.max_depth;
             let y_scale = LinearScale::new()
                 .domain(0.0, max_depth * 1.1)
                 .range(dims.inner_height(), 0.0);
 
             // Build bid area points
-            let bid_points: Vec<(f64, f64)> = d.bid_depth
+            let bid_points: Vec<(f64, f64)> = d.bids
                 .iter()
-                .filter(|p| p.price >= min_price && p.price <= max_price)
-                .map(|p| (x_scale.scale(p.price), y_scale.scale(p.cumulative_quantity)))
+                .filter(|p| p.0 >= min_price && p.0 <= max_price)
+                .map(|p| (x_scale.scale(p.0), y_scale.scale(p.1)))
                 .collect();
 
             // Build ask area points
-            let ask_points: Vec<(f64, f64)> = d.ask_depth
+            let ask_points: Vec<(f64, f64)> = d.asks
                 .iter()
-                .filter(|p| p.price >= min_price && p.price <= max_price)
-                .map(|p| (x_scale.scale(p.price), y_scale.scale(p.cumulative_quantity)))
+                .filter(|p| p.0 >= min_price && p.0 <= max_price)
+                .map(|p| (x_scale.scale(p.0), y_scale.scale(p.1)))
                 .collect();
 
             // Generate area paths
@@ -116,6 +235,22 @@ pub fn DepthChart(
                 y_scale,
             }
         })
+    });
+    let chart_state = move || chart_memo.get();
+
+    // The wheel zooms about the mid; double-click goes back to the default span
+    let zoom_wheel = move |ev: leptos::ev::WheelEvent| {
+        ev.prevent_default();
+        let factor = (-ev.delta_y() * WHEEL_ZOOM_RATE).exp();
+        zoom.update(|z| *z = (*z * factor).clamp(MIN_ZOOM, MAX_ZOOM));
+    };
+
+    // Hovered plot point
+    let pointer = RwSignal::new(None::<(f64, f64)>);
+    let track_pointer = move |ev: leptos::ev::PointerEvent| {
+        let point = plot_point(&ev, dims)
+            .filter(|&(x, y)| (0.0..=dims.inner_width()).contains(&x) && (0.0..=dims.inner_height()).contains(&y));
+        pointer.set(point);
     };
 
     view! {
@@ -124,12 +259,16 @@ pub fn DepthChart(
             viewBox=dims.viewbox()
             preserveAspectRatio="xMidYMid meet"
             style="width: 100%; height: 100%;"
+            on:wheel=zoom_wheel
+            on:dblclick=move |_| zoom.set(1.0)
+            on:pointermove=track_pointer
+            on:pointerleave=move |_| pointer.set(None)
         >
             // Background
             <rect
                 width=dims.width
                 height=dims.height
-                fill=colors::BG_PANEL
+                fill=colors::css::BG_PANEL
                 rx="4"
             />
 
@@ -148,16 +287,16 @@ pub fn DepthChart(
                                 // Bid area (green)
                                 <path
                                     d=state.bid_path.clone()
-                                    fill=colors::bull_alpha(0.3)
-                                    stroke=colors::BULL
+                                    fill=colors::css::bull_alpha(0.3)
+                                    stroke=colors::css::BULL
                                     stroke-width="2"
                                 />
 
                                 // Ask area (red)
                                 <path
                                     d=state.ask_path.clone()
-                                    fill=colors::bear_alpha(0.3)
-                                    stroke=colors::BEAR
+                                    fill=colors::css::bear_alpha(0.3)
+                                    stroke=colors::css::BEAR
                                     stroke-width="2"
                                 />
 
@@ -168,7 +307,7 @@ pub fn DepthChart(
                                             <line
                                                 x1=x y1="0"
                                                 x2=x y2=dims.inner_height()
-                                                stroke=colors::WARN
+                                                stroke=colors::css::WARN
                                                 stroke-width="1"
                                                 stroke-dasharray="4,4"
                                             />
@@ -178,6 +317,21 @@ pub fn DepthChart(
                                     None
                                 }}
 
+                                // Highlighted level
+                                {move || {
+                                    let price = highlight_price.and_then(|p| p.get())?;
+                                    let x = state.x_scale.scale(price);
+                                    (0.0..=dims.inner_width()).contains(&x).then(|| view! {
+                                        <line
+                                            class="depth-highlight"
+                                            x1=x y1="0"
+                                            x2=x y2=dims.inner_height()
+                                            stroke=colors::css::INFO
+                                            stroke-width="1"
+                                        />
+                                    })
+                                }}
+
                                 // Mid price label
                                 {state.mid_price.zip(state.mid_x).map(|(price, x)| {
                                     view! {
@@ -185,11 +339,11 @@ pub fn DepthChart(
                                             x=x
                                             y="-5"
                                             text-anchor="middle"
-                                            fill=colors::WARN
+                                            fill=colors::css::WARN
                                             font-size="11"
                                             font-family="JetBrains Mono, monospace"
                                         >
-                                            {format_price(price, 2)}
+                                            {price_format.price(price)}
                                         </text>
                                     }
                                 })}
@@ -198,12 +352,108 @@ pub fn DepthChart(
                     })
                 }}
 
+                // Where a market order of the entered size would reach, per side
+                {move || {
+                    let size = order_size.and_then(|s| s.get())?;
+                    let state = chart_state()?;
+                    let estimates = depth.with(|d| {
+                        let d = d.as_ref()?;
+                        Some([TradeSide::Buy, TradeSide::Sell].map(|side| (side, d.estimate_fill(side, size))))
+                    })?;
+                    Some(estimates
+                        .into_iter()
+                        .enumerate()
+                        .filter_map(|(row, (side, estimate))| {
+                            let estimate = estimate?;
+                            let (label, color) = match side {
+                                TradeSide::Buy => ("Buy", colors::css::BULL),
+                                TradeSide::Sell => ("Sell", colors::css::BEAR),
+                            };
+                            let x = state.x_scale.scale(estimate.worst_price);
+                            let short = if estimate.filled < size { " (book short)" } else { "" };
+                            Some(view! {
+                                <g class="depth-slippage" pointer-events="none">
+                                    {(0.0..=dims.inner_width()).contains(&x).then(|| view! {
+                                        <line
+                                            x1=x y1="0"
+                                            x2=x y2=dims.inner_height()
+                                            stroke=color
+                                            stroke-width="1"
+                                            stroke-dasharray="2,3"
+                                        />
+                                    })}
+                                    <text
+                                        x="6"
+                                        y={12.0 + row as f64 * 13.0}
+                                        fill=color
+                                        font-size="10"
+                                        font-family="JetBrains Mono, monospace"
+                                    >
+                                        {format!(
+                                            "{} {}: avg {} ({:+.3}%){}",
+                                            label,
+                                            size,
+                                            price_format.price(estimate.avg_price),
+                                            estimate.slippage_percent,
+                                            short
+                                        )}
+                                    </text>
+                                </g>
+                            })
+                        })
+                        .collect_view())
+                }}
+
+                // Hover crosshair: price, cumulative size and notional at the pointer
+                {move || {
+                    let (x, _) = pointer.get()?;
+                    let state = chart_state()?;
+                    let price = state.x_scale.invert(x);
+                    let (side, point) = depth.with(|d| d.as_ref()?.depth_at(price))?;
+                    let color = match side {
+                        OrderSide::Bid => colors::css::BULL,
+                        OrderSide::Ask => colors::css::BEAR,
+                    };
+                    let y = state.y_scale.scale(point.cumulative_quantity);
+                    let (w, h) = TOOLTIP_SIZE;
+                    let box_x = if x + 10.0 + w > dims.inner_width() { x - 10.0 - w } else { x + 10.0 };
+                    let box_y = (y - h / 2.0).clamp(0.0, (dims.inner_height() - h).max(0.0));
+                    let row = |i: f64, label: &'static str, value: String| view! {
+                        <text
+                            x={box_x + 6.0}
+                            y={box_y + 12.0 + i * 13.0}
+                            font-size="10"
+                            font-family="JetBrains Mono, monospace"
+                            xml:space="preserve"
+                        >
+                            <tspan fill=colors::css::TEXT_MUTED>{label}</tspan>
+                            <tspan fill=colors::css::TEXT_PRIMARY>{value}</tspan>
+                        </text>
+                    };
+                    Some(view! {
+                        <g class="depth-crosshair" pointer-events="none">
+                            <line
+                                x1=x y1="0"
+                                x2=x y2=dims.inner_height()
+                                stroke=colors::css::TEXT_MUTED
+                                stroke-width="0.5"
+                                stroke-dasharray="3,3"
+                            />
+                            <circle cx=x cy=y r="3" fill=color />
+                            <rect x=box_x y=box_y width=w height=h fill=colors::css::BG_ELEVATED stroke=color rx="3" />
+                            {row(0.0, "Price    ", price_format.price(price))}
+                            {row(1.0, "Size     ", format_large_number(point.cumulative_quantity))}
+                            {row(2.0, "Notional ", format_large_number(point.cumulative_value))}
+                        </g>
+                    })
+                }}
+
                 // X-Axis (price)
                 <g transform=format!("translate(0, {})", dims.inner_height())>
                     <line
                         x1="0" y1="0"
                         x2=dims.inner_width() y2="0"
-                        stroke=colors::BORDER
+                        stroke=colors::css::BORDER
                         stroke-width="1"
                     />
                     {move || {
@@ -211,15 +461,15 @@ pub fn DepthChart(
                             let ticks = state.x_scale.nice_ticks(5);
                             ticks.into_iter().map(|tick| {
                                 let x = state.x_scale.scale(tick);
-                                let label = format_price(tick, 0);
+                                let label = price_format.price(tick);
 
                                 view! {
                                     <g transform=format!("translate({}, 0)", x)>
-                                        <line y1="0" y2="5" stroke=colors::BORDER />
+                                        <line y1="0" y2="5" stroke=colors::css::BORDER />
                                         <text
                                             y="15"
                                             text-anchor="middle"
-                                            fill=colors::TEXT_MUTED
+                                            fill=colors::css::TEXT_MUTED
                                             font-size="9"
                                             font-family="JetBrains Mono, monospace"
                                         >
@@ -237,7 +487,7 @@ pub fn DepthChart(
                     <line
                         x1="0" y1="0"
                         x2="0" y2=dims.inner_height()
-                        stroke=colors::BORDER
+                        stroke=colors::css::BORDER
                         stroke-width="1"
                     />
                     {move || {
@@ -249,12 +499,12 @@ pub fn DepthChart(
 
                                 view! {
                                     <g transform=format!("translate(0, {})", y)>
-                                        <line x1="-5" x2="0" stroke=colors::BORDER />
+                                        <line x1="-5" x2="0" stroke=colors::css::BORDER />
                                         <text
                                             x="-8"
                                             dy="0.32em"
                                             text-anchor="end"
-                                            fill=colors::TEXT_MUTED
+                                            fill=colors::css::TEXT_MUTED
                                             font-size="9"
                                             font-family="JetBrains Mono, monospace"
                                         >
@@ -272,11 +522,11 @@ pub fn DepthChart(
             {if show_legend {
                 Some(view! {
                     <g transform=format!("translate({}, 15)", dims.width - 100.0)>
-                        <rect x="0" y="-4" width="12" height="12" fill=colors::bull_alpha(0.5) />
-                        <text x="16" y="5" fill=colors::TEXT_MUTED font-size="10">"Bids"</text>
+                        <rect x="0" y="-4" width="12" height="12" fill=colors::css::bull_alpha(0.5) />
+                        <text x="16" y="5" fill=colors::css::TEXT_MUTED font-size="10">"Bids"</text>
 
-                        <rect x="50" y="-4" width="12" height="12" fill=colors::bear_alpha(0.5) />
-                        <text x="66" y="5" fill=colors::TEXT_MUTED font-size="10">"Asks"</text>
+                        <rect x="50" y="-4" width="12" height="12" fill=colors::css::bear_alpha(0.5) />
+                        <text x="66" y="5" fill=colors::css::TEXT_MUTED font-size="10">"Asks"</text>
                     </g>
                 })
             } else {
@@ -299,7 +549,7 @@ fn DepthGrid(width: f64, height: f64) -> impl IntoView {
                     <line
                         x1="0" y1=y
                         x2=width y2=y
-                        stroke=colors::GRID
+                        stroke=colors::css::GRID
                         stroke-width="1"
                         opacity="0.5"
                     />
@@ -311,7 +561,7 @@ fn DepthGrid(width: f64, height: f64) -> impl IntoView {
                     <line
                         x1=x y1="0"
                         x2=x y2=height
-                        stroke=colors::GRID
+                        stroke=colors::css::GRID
                         stroke-width="1"
                         opacity="0.5"
                     />
@@ -351,7 +601,7 @@ pub fn DepthBar(
             <rect
                 width=width
                 height=height
-                fill=colors::BG_ELEVATED
+                fill=colors::css::BG_ELEVATED
                 rx="4"
             />
 
@@ -367,7 +617,7 @@ pub fn DepthBar(
                     bid_ratio * width / 2.0
                 }
                 height=height
-                fill=colors::bull_alpha(0.6)
+                fill=colors::css::bull_alpha(0.6)
                 rx="4"
             />
 
@@ -380,7 +630,7 @@ pub fn DepthBar(
                     ask_ratio * width / 2.0
                 }
                 height=height
-                fill=colors::bear_alpha(0.6)
+                fill=colors::css::bear_alpha(0.6)
                 rx="4"
             />
 
@@ -390,7 +640,7 @@ pub fn DepthBar(
                 y1="0"
                 x2=width / 2.0
                 y2=height
-                stroke=colors::BORDER
+                stroke=colors::css::BORDER
                 stroke-width="1"
             />
         </svg>
@@ -417,9 +667,9 @@ pub fn DepthBarVertical(
     };
 
     let fill = if is_bid {
-        colors::bull_alpha(0.3)
+        colors::css::bull_alpha(0.3)
     } else {
-        colors::bear_alpha(0.3)
+        colors::css::bear_alpha(0.3)
     };
 
     view! {
@@ -0,0 +1,184 @@
+//! Drawing tools: hit-testing, OHLC snapping and the tool picker
+//!
+//! Drawings are laid out in plot pixels by the chart they sit on; this
+//! module works on those pixel positions so it doesn't care about the price
+//! scale (a Fibonacci level is tested where it is drawn, log axis or not).
+
+use dash_core::{Candle, Drawing, DrawingTool};
+use leptos::prelude::*;
+
+/// How close (px) the pointer has to be to grab a drawing or a handle
+pub const HIT_TOLERANCE_PX: f64 = 6.0;
+
+/// How close (px) a new anchor has to be to an open, high, low or close to
+/// snap onto it
+pub const SNAP_RADIUS_PX: f64 = 8.0;
+
+/// Part of a drawing the pointer grabbed
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DrawingHandle {
+    Start,
+    End,
+    /// Anywhere else on the drawing: moves it whole
+    Body,
+}
+
+/// Change made on the chart, for the owner to store
+#[derive(Debug, Clone, PartialEq)]
+pub enum DrawingEdit {
+    /// Freshly drawn (id 0 until stored)
+    Added(Drawing),
+    /// Anchors dragged
+    Moved(Drawing),
+}
+
+/// A drawing laid out in plot pixels
+#[derive(Debug, Clone, PartialEq)]
+pub struct DrawingPixels {
+    pub tool: DrawingTool,
+    pub start: (f64, f64),
+    pub end: (f64, f64),
+    /// Fibonacci level rows (`y` per level), empty for other tools
+    pub levels: Vec<f64>,
+}
+
+impl DrawingPixels {
+    /// What `point` grabs, handles first; `plot_width` bounds the ray
+    pub fn hit_test(&self, point: (f64, f64), plot_width: f64) -> Option<DrawingHandle> {
+        if distance(point, self.start) <= HIT_TOLERANCE_PX {
+            return Some(DrawingHandle::Start);
+        }
+        if !self.tool.is_single_point() && distance(point, self.end) <= HIT_TOLERANCE_PX {
+            return Some(DrawingHandle::End);
+        }
+        let (left, right) = (self.start.0.min(self.end.0), self.start.0.max(self.end.0));
+        let within_x = |x: f64| x >= left - HIT_TOLERANCE_PX && x <= right + HIT_TOLERANCE_PX;
+        let on_body = match self.tool {
+            DrawingTool::Trendline => distance_to_segment(point, self.start, self.end) <= HIT_TOLERANCE_PX,
+            DrawingTool::HorizontalRay => {
+                (point.1 - self.start.1).abs() <= HIT_TOLERANCE_PX
+                    && point.0 >= self.start.0 - HIT_TOLERANCE_PX
+                    && point.0 <= plot_width
+            }
+            DrawingTool::Rectangle => {
+                let (top, bottom) = (self.start.1.min(self.end.1), self.start.1.max(self.end.1));
+                within_x(point.0) && point.1 >= top - HIT_TOLERANCE_PX && point.1 <= bottom + HIT_TOLERANCE_PX
+            }
+            DrawingTool::Fibonacci => {
+                within_x(point.0) && self.levels.iter().any(|y| (point.1 - y).abs() <= HIT_TOLERANCE_PX)
+            }
+        };
+        on_body.then_some(DrawingHandle::Body)
+    }
+}
+
+/// The open, high, low or close of `candle` nearest the pointer if it lies
+/// within [`SNAP_RADIUS_PX`] of it (`to_y` maps price to plot y), else `price`
+pub fn snap_to_ohlc(candle: &Candle, price: f64, y: f64, to_y: impl Fn(f64) -> f64) -> f64 {
+    [candle.open, candle.high, candle.low, candle.close]
+        .into_iter()
+        .map(|p| p.as_f64())
+        .map(|p| (p, (to_y(p) - y).abs()))
+        .filter(|&(_, gap)| gap <= SNAP_RADIUS_PX)
+        .min_by(|a, b| a.1.total_cmp(&b.1))
+        .map_or(price, |(p, _)| p)
+}
+
+fn distance(a: (f64, f64), b: (f64, f64)) -> f64 {
+    (a.0 - b.0).hypot(a.1 - b.1)
+}
+
+fn distance_to_segment(point: (f64, f64), a: (f64, f64), b: (f64, f64)) -> f64 {
+    let (dx, dy) = (b.0 - a.0, b.1 - a.1);
+    let length_sq = dx * dx + dy * dy;
+    if length_sq == 0.0 {
+        return distance(point, a);
+    }
+    let t = (((point.0 - a.0) * dx + (point.1 - a.1) * dy) / length_sq).clamp(0.0, 1.0);
+    distance(point, (a.0 + t * dx, a.1 + t * dy))
+}
+
+/// Drawing tool buttons, plus delete-selected and clear-all
+#[component]
+pub fn DrawingToolbar(
+    /// Tool placing the next drawing (`None` = select / pan)
+    tool: RwSignal<Option<DrawingTool>>,
+    /// Drawing picked on the chart
+    #[prop(into)]
+    selected: Signal<Option<u64>>,
+    #[prop(into)] on_delete: Callback<()>,
+    #[prop(into)] on_clear: Callback<()>,
+) -> impl IntoView {
+    view! {
+        <span class="tf-bar drawing-tools" role="group" aria-label="Drawing tools">
+            {DrawingTool::all()
+                .iter()
+                .map(|&kind| {
+                    let active = move || tool.get() == Some(kind);
+                    view! {
+                        <button
+                            class=move || if active() { "sb-preset active" } else { "sb-preset" }
+                            title=kind.name()
+                            aria-pressed=move || active().to_string()
+                            on:click=move |_| tool.update(|t| *t = (*t != Some(kind)).then_some(kind))
+                        >
+                            {kind.label()}
+                        </button>
+                    }
+                })
+                .collect_view()}
+            <button
+                class="sb-preset"
+                title="Delete the selected drawing"
+                disabled=move || selected.get().is_none()
+                on:click=move |_| on_delete.run(())
+            >
+                "✕"
+            </button>
+            <button class="sb-preset" title="Remove every drawing on this chart" on:click=move |_| on_clear.run(())>
+                "Clear"
+            </button>
+        </span>
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use dash_core::{CandleInterval, Symbol};
+
+    fn pixels(tool: DrawingTool, start: (f64, f64), end: (f64, f64)) -> DrawingPixels {
+        DrawingPixels { tool, start, end, levels: Vec::new() }
+    }
+
+    #[test]
+    fn test_hit_test_prefers_handles() {
+        let line = pixels(DrawingTool::Trendline, (10.0, 10.0), (110.0, 110.0));
+        assert_eq!(line.hit_test((12.0, 11.0), 500.0), Some(DrawingHandle::Start));
+        assert_eq!(line.hit_test((108.0, 110.0), 500.0), Some(DrawingHandle::End));
+        assert_eq!(line.hit_test((60.0, 62.0), 500.0), Some(DrawingHandle::Body));
+        assert_eq!(line.hit_test((60.0, 90.0), 500.0), None);
+
+        let ray = pixels(DrawingTool::HorizontalRay, (50.0, 40.0), (50.0, 40.0));
+        assert_eq!(ray.hit_test((400.0, 43.0), 500.0), Some(DrawingHandle::Body));
+        assert_eq!(ray.hit_test((20.0, 40.0), 500.0), None);
+
+        let mut fib = pixels(DrawingTool::Fibonacci, (0.0, 0.0), (100.0, 100.0));
+        fib.levels = vec![0.0, 50.0, 100.0];
+        assert_eq!(fib.hit_test((40.0, 52.0), 500.0), Some(DrawingHandle::Body));
+        assert_eq!(fib.hit_test((40.0, 25.0), 500.0), None);
+    }
+
+    #[test]
+    fn test_snap_to_ohlc() {
+        let mut candle = Candle::new(Symbol::default(), CandleInterval::M1, 0, 100.0);
+        candle.update(110.0, 1.0);
+        candle.update(95.0, 1.0);
+        // One pixel per unit of price, y growing downwards from 200
+        let to_y = |p: f64| 200.0 - p;
+
+        assert_eq!(snap_to_ohlc(&candle, 108.5, to_y(108.5), to_y), 110.0);
+        assert_eq!(snap_to_ohlc(&candle, 103.0, to_y(103.0), to_y), 100.0);
+        assert_eq!(snap_to_ohlc(&candle, 120.0, to_y(120.0), to_y), 120.0);
+    }
+}
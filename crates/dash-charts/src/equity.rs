@@ -0,0 +1,198 @@
+//! Equity curve of the paper account
+//!
+//! An area chart of account equity over time. Wherever equity sits below
+//! its running peak the gap is shaded, so drawdowns read at a glance, and
+//! the deepest one is marked from its peak to its trough with the loss in
+//! percent.
+
+use crate::{
+    chartkit::{area_path, line_path, LinearScale, Scale},
+    colors,
+};
+use dash_core::{max_drawdown, EquitySample};
+use leptos::prelude::*;
+use std::fmt::Write;
+
+/// Deepest drawdown placed on the chart
+#[derive(Debug, Clone, PartialEq)]
+pub struct DrawdownMarker {
+    pub peak_x: f64,
+    pub trough_x: f64,
+    pub peak_y: f64,
+    pub trough_y: f64,
+    /// Loss from the peak in percent
+    pub percent: f64,
+}
+
+/// Plotted equity curve
+#[derive(Debug, Clone, PartialEq)]
+pub struct EquityGeometry {
+    pub line: String,
+    /// Area under the curve down to the bottom edge
+    pub area: String,
+    /// Closed shape between the running peak and the curve
+    pub drawdown: String,
+    pub max_drawdown: Option<DrawdownMarker>,
+}
+
+/// Lay the samples (oldest first) across `width` by time; `None` with
+/// fewer than two
+pub fn equity_geometry(samples: &[EquitySample], width: f64, height: f64) -> Option<EquityGeometry> {
+    let (first, last) = (samples.first()?, samples.last()?);
+    if samples.len() < 2 {
+        return None;
+    }
+    let (low, high) = samples
+        .iter()
+        .fold((f64::INFINITY, f64::NEG_INFINITY), |(low, high), s| (low.min(s.equity), high.max(s.equity)));
+    let pad = if high > low { (high - low) * 0.1 } else { high.abs().max(1.0) * 0.001 };
+    let y_scale = LinearScale::new().domain(low - pad, high + pad).range(height, 0.0);
+    let x_scale = LinearScale::new()
+        .domain(first.timestamp as f64, (last.timestamp.max(first.timestamp + 1)) as f64)
+        .range(0.0, width);
+    let place = |s: &EquitySample| (x_scale.scale(s.timestamp as f64), y_scale.scale(s.equity));
+
+    let points: Vec<(f64, f64)> = samples.iter().map(place).collect();
+
+    // Forward along the running peak, back along the curve
+    let mut drawdown = String::new();
+    let mut peak = f64::NEG_INFINITY;
+    for (i, (s, &(x, _))) in samples.iter().zip(&points).enumerate() {
+        peak = peak.max(s.equity);
+        let command = if i == 0 { 'M' } else { 'L' };
+        write!(drawdown, "{}{:.2},{:.2}", command, x, y_scale.scale(peak)).ok();
+    }
+    for &(x, y) in points.iter().rev() {
+        write!(drawdown, "L{:.2},{:.2}", x, y).ok();
+    }
+    drawdown.push('Z');
+
+    let max_drawdown = max_drawdown(samples).map(|dd| {
+        let ((peak_x, peak_y), (trough_x, trough_y)) = (place(&dd.peak), place(&dd.trough));
+        DrawdownMarker {
+            peak_x,
+            trough_x,
+            peak_y,
+            trough_y,
+            percent: dd.fraction() * 100.0,
+        }
+    });
+
+    Some(EquityGeometry {
+        line: line_path(&points),
+        area: area_path(&points, height),
+        drawdown,
+        max_drawdown,
+    })
+}
+
+/// Equity area chart with drawdowns shaded and the deepest one marked
+#[component]
+pub fn EquityChart(
+    /// Equity samples, oldest first
+    #[prop(into)]
+    samples: Signal<Vec<EquitySample>>,
+    #[prop(default = 320.0)] width: f64,
+    #[prop(default = 120.0)] height: f64,
+) -> impl IntoView {
+    let geometry = move || samples.with(|samples| equity_geometry(samples, width, height));
+    let latest = move || samples.with(|samples| samples.last().map(|s| s.equity));
+
+    view! {
+        <svg class="equity-chart" width="100%" height=height viewBox=format!("0 0 {} {}", width, height)>
+            {move || match geometry() {
+                None => view! {
+                    <text
+                        x=width / 2.0
+                        y=height / 2.0
+                        text-anchor="middle"
+                        fill=colors::css::TEXT_MUTED
+                        font-size="10"
+                        font-family="JetBrains Mono, monospace"
+                    >
+                        "Waiting for equity samples"
+                    </text>
+                }
+                .into_any(),
+                Some(g) => {
+                    let marker = g.max_drawdown.map(|m| {
+                        let label_x = m.trough_x.min(width - 4.0);
+                        let anchor = if m.trough_x > width * 0.75 { "end" } else { "start" };
+                        view! {
+                            <g class="equity-max-drawdown" pointer-events="none">
+                                <path
+                                    d=format!(
+                                        "M{:.2},{:.2}H{:.2}V{:.2}",
+                                        m.peak_x, m.peak_y, m.trough_x, m.trough_y
+                                    )
+                                    fill="none"
+                                    stroke=colors::css::BEAR
+                                    stroke-width="1"
+                                    stroke-dasharray="3,3"
+                                />
+                                <circle cx=m.trough_x cy=m.trough_y r="2.5" fill=colors::css::BEAR />
+                                <text
+                                    x=label_x
+                                    y=(m.trough_y + 12.0).min(height - 2.0)
+                                    text-anchor=anchor
+                                    fill=colors::css::BEAR
+                                    font-size="10"
+                                    font-family="JetBrains Mono, monospace"
+                                >
+                                    {format!("Max DD -{:.2}%", m.percent)}
+                                </text>
+                            </g>
+                        }
+                    });
+                    view! {
+                        <path d=g.area fill=colors::css::bull_alpha(0.12) />
+                        <path d=g.drawdown fill=colors::css::bear_alpha(0.25) />
+                        <path d=g.line fill="none" stroke=colors::css::BULL stroke-width="1.25" stroke-linejoin="round" />
+                        {marker}
+                    }
+                    .into_any()
+                }
+            }}
+            <text x="4" y="12" fill=colors::css::TEXT_MUTED font-size="10" font-family="JetBrains Mono, monospace">
+                "Equity "
+                <tspan fill=colors::css::TEXT_PRIMARY>{move || latest().map_or_else(|| "—".to_string(), |e| format!("{:.2}", e))}</tspan>
+            </text>
+        </svg>
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn curve(values: &[f64]) -> Vec<EquitySample> {
+        values
+            .iter()
+            .enumerate()
+            .map(|(i, &equity)| EquitySample {
+                timestamp: i as i64 * 1_000,
+                equity,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_equity_geometry() {
+        assert_eq!(equity_geometry(&curve(&[100.0]), 100.0, 50.0), None);
+
+        // 100 → 110 → 99 → 105 across 100px: peak at x=33, trough at x=67
+        let g = equity_geometry(&curve(&[100.0, 110.0, 99.0, 105.0]), 100.0, 50.0).unwrap();
+        assert!(g.line.starts_with("M0.00,"));
+        assert!(g.area.contains("100.00,50.00"));
+        assert!(g.drawdown.ends_with('Z'));
+        let m = g.max_drawdown.unwrap();
+        assert!((m.peak_x - 33.33).abs() < 0.01);
+        assert!((m.trough_x - 66.67).abs() < 0.01);
+        assert!(m.trough_y > m.peak_y);
+        assert!((m.percent - 10.0).abs() < 1e-9);
+
+        // A curve that only rises has nothing to mark
+        let rising = equity_geometry(&curve(&[100.0, 100.0, 101.0]), 100.0, 50.0).unwrap();
+        assert_eq!(rising.max_drawdown, None);
+    }
+}
@@ -0,0 +1,270 @@
+//! Order book heatmap (Bookmap-style)
+//!
+//! Paints resting liquidity over time on a canvas: x is time, y is price,
+//! color intensity is resting size. Trade prints are overlaid as bubbles.
+//! The cells can go to a WebGL layer underneath when there are many of them.
+
+use crate::{
+    backend::{fit_canvas, CanvasBackend, CanvasSurface, ChartBackend, RenderBackend},
+    chartkit::{LinearScale, Scale, TimeScale},
+    use_chart_palette, ChartDimensions, ChartMargin,
+};
+use dash_core::{colors::Palette, BookHistory, TickFormat, Trade, TradeSide, BOOK_SAMPLE_MS, MAX_BOOK_COLUMNS};
+use leptos::prelude::*;
+use wasm_bindgen::JsCast;
+use web_sys::{CanvasRenderingContext2d, HtmlCanvasElement};
+
+/// Heatmap configuration
+#[derive(Debug, Clone)]
+pub struct HeatmapConfig {
+    pub width: f64,
+    pub height: f64,
+    /// Height of one price cell in pixels
+    pub cell_height: f64,
+    pub show_trades: bool,
+    /// WebGL moves the liquidity cells to a GPU layer
+    pub backend: RenderBackend,
+    /// Price precision of the axis labels
+    pub price_format: TickFormat,
+}
+
+impl Default for HeatmapConfig {
+    fn default() -> Self {
+        Self {
+            width: 600.0,
+            height: 300.0,
+            cell_height: 3.0,
+            show_trades: true,
+            backend: RenderBackend::Canvas,
+            price_format: TickFormat::default(),
+        }
+    }
+}
+
+/// Heat ramp from cold (dim blue) to hot (yellow-white) for `intensity` 0.0 - 1.0
+pub fn heat_color(intensity: f64) -> String {
+    let t = intensity.clamp(0.0, 1.0);
+    // Hue 230 (blue) → 50 (yellow); lightness rises with size
+    let hue = 230.0 - 180.0 * t;
+    let lightness = 25.0 + 45.0 * t;
+    format!("hsla({:.0}, 90%, {:.0}%, {:.2})", hue, lightness, 0.25 + 0.75 * t)
+}
+
+/// Book heatmap fed by the rolling book history, with trade prints overlaid
+///
+/// With [`RenderBackend::WebGl`] the liquidity cells and best bid/ask traces
+/// go to a WebGL layer and the 2D canvas on top keeps the prints and axis;
+/// any other backend paints everything on the 2D canvas.
+#[component]
+pub fn BookHeatmap(
+    #[prop(into)] history: Signal<BookHistory>,
+    #[prop(into)] trades: Signal<Vec<Trade>>,
+    #[prop(optional)] config: Option<HeatmapConfig>,
+) -> impl IntoView {
+    let config = config.unwrap_or_default();
+    let dims = ChartDimensions::new(config.width, config.height)
+        .with_margin(ChartMargin::right_axis());
+    let canvas = NodeRef::<leptos::html::Canvas>::new();
+    let gl_canvas = NodeRef::<leptos::html::Canvas>::new();
+    let use_gl = config.backend == RenderBackend::WebGl;
+    let surface = StoredValue::new_local(None::<CanvasSurface>);
+    let palette = use_chart_palette();
+
+    Effect::new(move |_| {
+        let Some(canvas) = canvas.get() else {
+            return;
+        };
+        let palette = palette.get();
+        if use_gl {
+            let Some(layer) = gl_canvas.get() else {
+                return;
+            };
+            if surface.with_value(Option::is_none) {
+                surface.set_value(Some(CanvasSurface::new(&layer, RenderBackend::WebGl)));
+            }
+            history.with(|history| {
+                trades.with(|trades| {
+                    surface.with_value(|surface| {
+                        if let Some(surface) = surface {
+                            draw_layered(surface, &layer, &canvas, &dims, &config, palette, history, trades);
+                        }
+                    })
+                })
+            });
+            return;
+        }
+
+        let context = canvas
+            .get_context("2d")
+            .ok()
+            .flatten()
+            .and_then(|ctx| ctx.dyn_into::<CanvasRenderingContext2d>().ok());
+        let Some(ctx) = context else {
+            return;
+        };
+        history.with(|history| {
+            trades.with(|trades| draw(&ctx, &dims, &config, palette, history, trades));
+        });
+    });
+
+    view! {
+        <div class="chart-layers">
+            {use_gl.then(|| view! { <canvas class="chart-canvas" node_ref=gl_canvas /> })}
+            <canvas
+                class="book-heatmap"
+                node_ref=canvas
+                width=dims.width
+                height=dims.height
+                style="width: 100%; height: 100%;"
+            />
+        </div>
+    }
+}
+
+/// Scales shared by the liquidity layer and the prints/axis drawn over it
+struct HeatmapLayout {
+    x_scale: TimeScale,
+    y_scale: LinearScale,
+    column_width: f64,
+    /// Oldest time in the window (ms)
+    since: i64,
+    price_range: (f64, f64),
+    width: f64,
+}
+
+impl HeatmapLayout {
+    fn new(dims: &ChartDimensions, history: &BookHistory) -> Option<Self> {
+        let (Some((low, high)), Some((_, newest))) = (history.price_range(), history.time_range()) else {
+            return None;
+        };
+        let (width, height) = (dims.inner_width(), dims.inner_height());
+
+        // Fixed window ending at the newest column so the map scrolls left
+        let window = MAX_BOOK_COLUMNS as i64 * BOOK_SAMPLE_MS;
+        let x_scale = TimeScale::new()
+            .domain(newest - window, newest + BOOK_SAMPLE_MS)
+            .range(0.0, width);
+        let y_scale = LinearScale::new().domain(low, high).range(height, 0.0);
+        let column_width = width * BOOK_SAMPLE_MS as f64 / (window + BOOK_SAMPLE_MS) as f64;
+        Some(Self { x_scale, y_scale, column_width, since: newest - window, price_range: (low, high), width })
+    }
+}
+
+/// Everything on the 2D canvas
+fn draw(
+    ctx: &CanvasRenderingContext2d,
+    dims: &ChartDimensions,
+    config: &HeatmapConfig,
+    palette: &Palette,
+    history: &BookHistory,
+    trades: &[Trade],
+) {
+    ctx.set_fill_style_str(palette.bg_panel);
+    ctx.fill_rect(0.0, 0.0, dims.width, dims.height);
+
+    let Some(layout) = HeatmapLayout::new(dims, history) else {
+        return;
+    };
+
+    ctx.save();
+    let _ = ctx.translate(dims.margin.left, dims.margin.top);
+    paint_liquidity(&mut CanvasBackend::new(ctx), &layout, config, palette, history);
+    draw_prints_and_axis(ctx, &layout, config, palette, trades);
+    ctx.restore();
+}
+
+/// Liquidity through `surface` on `layer`; prints and axis on the 2D canvas above
+#[allow(clippy::too_many_arguments)]
+fn draw_layered(
+    surface: &CanvasSurface,
+    layer: &HtmlCanvasElement,
+    canvas: &HtmlCanvasElement,
+    dims: &ChartDimensions,
+    config: &HeatmapConfig,
+    palette: &Palette,
+    history: &BookHistory,
+    trades: &[Trade],
+) {
+    let layout = HeatmapLayout::new(dims, history);
+    let origin = (dims.margin.left, dims.margin.top);
+    surface.paint(layer, dims, origin, palette.bg_panel, |painter| {
+        if let Some(layout) = &layout {
+            paint_liquidity(painter, layout, config, palette, history);
+        }
+    });
+    if let (Some(ctx), Some(layout)) = (fit_canvas(canvas, dims, origin), &layout) {
+        draw_prints_and_axis(&ctx, layout, config, palette, trades);
+    }
+}
+
+/// Resting liquidity cells, then the best bid / ask traces
+fn paint_liquidity<B: ChartBackend + ?Sized>(
+    backend: &mut B,
+    layout: &HeatmapLayout,
+    config: &HeatmapConfig,
+    palette: &Palette,
+    history: &BookHistory,
+) {
+    let max_qty = history.max_quantity().max(f64::EPSILON);
+    let cell = config.cell_height;
+    for column in &history.columns {
+        let x = layout.x_scale.scale(column.timestamp);
+        for &(price, qty) in column.bids.iter().chain(&column.asks) {
+            let y = layout.y_scale.scale(price) - cell / 2.0;
+            backend.rect(x, y, layout.column_width + 0.5, cell, &heat_color((qty / max_qty).sqrt()));
+        }
+    }
+
+    let trace = |best: fn(&dash_core::BookColumn) -> Option<f64>| -> Vec<(f64, f64)> {
+        history
+            .columns
+            .iter()
+            .filter_map(|column| {
+                let price = best(column)?;
+                Some((layout.x_scale.scale(column.timestamp) + layout.column_width, layout.y_scale.scale(price)))
+            })
+            .collect()
+    };
+    backend.polyline(&trace(|c| c.bids.first().map(|&(price, _)| price)), palette.bull, 1.0);
+    backend.polyline(&trace(|c| c.asks.first().map(|&(price, _)| price)), palette.bear, 1.0);
+}
+
+/// Trade bubbles and the price axis, in plot coordinates
+fn draw_prints_and_axis(
+    ctx: &CanvasRenderingContext2d,
+    layout: &HeatmapLayout,
+    config: &HeatmapConfig,
+    palette: &Palette,
+    trades: &[Trade],
+) {
+    let (x_scale, y_scale) = (&layout.x_scale, &layout.y_scale);
+
+    // Trade prints sized by quantity
+    if config.show_trades {
+        let (low, high) = layout.price_range;
+        for trade in trades.iter().take_while(|t| t.timestamp.timestamp_millis() >= layout.since) {
+            let price = trade.price.as_f64();
+            if !(low..=high).contains(&price) {
+                continue;
+            }
+            let radius = (1.5 + trade.quantity.as_f64().sqrt() * 2.0).min(8.0);
+            let fill = match trade.side {
+                TradeSide::Buy => palette.bull_alpha(0.7),
+                TradeSide::Sell => palette.bear_alpha(0.7),
+            };
+            ctx.begin_path();
+            let x = x_scale.scale(trade.timestamp.timestamp_millis()).min(layout.width);
+            let _ = ctx.arc(x, y_scale.scale(price), radius, 0.0, std::f64::consts::TAU);
+            ctx.set_fill_style_str(&fill);
+            ctx.fill();
+        }
+    }
+
+    // Price axis on the right
+    ctx.set_fill_style_str(palette.text_muted);
+    ctx.set_font("9px JetBrains Mono, monospace");
+    ctx.set_text_baseline("middle");
+    for tick in y_scale.nice_ticks(6) {
+        let _ = ctx.fill_text(&config.price_format.price(tick), layout.width + 6.0, y_scale.scale(tick));
+    }
+}
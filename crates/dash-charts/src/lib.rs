@@ -13,23 +13,81 @@
 //! ## Modules
 //!
 //! - `chartkit` - Core primitives: scales, paths, axes
+//! - `annotations` - Labeled markers, event lines and shaded regions by time
+//! - `backend` - SVG and canvas renderers behind `ChartBackend`
 //! - `candlestick` - OHLCV candlestick charts
+//! - `compare` - Second symbol as a percent line over the candles
+//! - `cvd` - Cumulative volume delta on the candle time axis
 //! - `depth` - Market depth / order book visualization
+//! - `drawings` - Trendline, ray, box and Fibonacci drawing tools
+//! - `equity` - Paper account equity curve with drawdowns shaded
+//! - `heatmap` - Canvas order book heatmap over time
+//! - `mini` - Axis-less mini candle and depth widgets for lists
+//! - `order_markers` - Paper orders, fills and position entry on the chart
+//! - `overlays` - Moving average and Bollinger Band overlays
 //! - `sparkline` - Compact inline charts
+//! - `spread` - Bid-ask spread micro-chart over a time window
+//! - `trade_prints` - Trade dots sized by notional over the candles
+//! - `volume_profile` - Volume-at-price overlay
 
+pub mod annotations;
+pub mod backend;
 pub mod candlestick;
 pub mod chartkit;
+pub mod compare;
+pub mod cvd;
 pub mod depth;
+pub mod drawings;
+pub mod equity;
+pub mod heatmap;
+pub mod mini;
+pub mod order_markers;
+pub mod overlays;
 pub mod sparkline;
+pub mod spread;
+pub mod trade_prints;
+pub mod volume_profile;
 
+pub use annotations::*;
+pub use backend::*;
 pub use candlestick::*;
 pub use chartkit::*;
+pub use compare::*;
+pub use cvd::*;
 pub use depth::*;
+pub use drawings::*;
+pub use equity::*;
+pub use heatmap::*;
+pub use mini::*;
+pub use order_markers::*;
+pub use overlays::*;
 pub use sparkline::*;
+pub use spread::*;
+pub use trade_prints::*;
+pub use volume_profile::*;
 
 // Re-export colors from dash-core for convenience
 pub use dash_core::colors;
 
+use dash_core::colors::Palette;
+use leptos::prelude::*;
+
+/// Palette of the active theme. SVG charts paint with `colors::css`
+/// references that follow the theme on their own; the canvas and WebGL
+/// backends can't resolve those and take concrete colors from here.
+#[derive(Clone, Copy)]
+pub struct ChartPalette(pub Signal<&'static Palette>);
+
+/// Make the theme's palette available to the charts below
+pub fn provide_chart_palette(palette: Signal<&'static Palette>) {
+    provide_context(ChartPalette(palette));
+}
+
+/// The active palette (the dark one when none was provided)
+pub fn use_chart_palette() -> Signal<&'static Palette> {
+    use_context::<ChartPalette>().map_or_else(|| Signal::stored(&Palette::DARK), |p| p.0)
+}
+
 /// Chart margin configuration
 #[derive(Debug, Clone, Copy)]
 pub struct ChartMargin {
@@ -115,6 +173,18 @@ impl ChartDimensions {
     pub fn viewbox(&self) -> String {
         format!("0 0 {} {}", self.width, self.height)
     }
+
+    /// Map a point inside an element of `size` (px) rendering this viewBox
+    /// with `xMidYMid meet` to viewBox coordinates
+    pub fn to_viewbox(&self, size: (f64, f64), point: (f64, f64)) -> (f64, f64) {
+        let scale = (size.0 / self.width).min(size.1 / self.height);
+        if scale <= 0.0 {
+            return (0.0, 0.0);
+        }
+        let offset_x = (size.0 - self.width * scale) / 2.0;
+        let offset_y = (size.1 - self.height * scale) / 2.0;
+        ((point.0 - offset_x) / scale, (point.1 - offset_y) / scale)
+    }
 }
 
 impl Default for ChartDimensions {
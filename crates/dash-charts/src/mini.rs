@@ -0,0 +1,197 @@
+//! Mini candle and depth widgets for lists and pop-outs
+//!
+//! No axes, labels or interaction, and a fixed small size: each widget is a
+//! handful of `<path>`s however many bars or levels it shows, so a list can
+//! carry dozens of them without the DOM growing with the data.
+
+use crate::chartkit::{area_path, line_path, BandScale, LinearScale, Scale};
+use crate::colors;
+use dash_core::{Candle, MarketDepth};
+use leptos::prelude::*;
+use std::fmt::Write;
+
+/// Default widget size (px)
+pub const MINI_WIDTH: f64 = 64.0;
+pub const MINI_HEIGHT: f64 = 20.0;
+
+/// Ticker trail prices folded into each mini bar
+pub const TRAIL_TICKS_PER_BAR: usize = 5;
+
+/// OHLC of one mini bar
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MiniBar {
+    pub open: f64,
+    pub high: f64,
+    pub low: f64,
+    pub close: f64,
+}
+
+impl MiniBar {
+    pub fn from_candle(candle: &Candle) -> Self {
+        Self {
+            open: candle.open.as_f64(),
+            high: candle.high.as_f64(),
+            low: candle.low.as_f64(),
+            close: candle.close.as_f64(),
+        }
+    }
+
+    fn is_bullish(&self) -> bool {
+        self.close >= self.open
+    }
+}
+
+/// Bars of `per_bar` consecutive prices each (the last may be short), for
+/// symbols that only have a ticker trail
+pub fn trail_bars(prices: &[f64], per_bar: usize) -> Vec<MiniBar> {
+    prices
+        .chunks(per_bar.max(1))
+        .map(|chunk| MiniBar {
+            open: chunk[0],
+            high: chunk.iter().copied().fold(f64::MIN, f64::max),
+            low: chunk.iter().copied().fold(f64::MAX, f64::min),
+            close: chunk[chunk.len() - 1],
+        })
+        .collect()
+}
+
+/// Path data for the mini candles: all wicks, then bull and bear bodies
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct MiniCandlePaths {
+    pub wicks: String,
+    pub bull: String,
+    pub bear: String,
+}
+
+pub fn mini_candle_paths(bars: &[MiniBar], width: f64, height: f64) -> MiniCandlePaths {
+    let mut paths = MiniCandlePaths::default();
+    if bars.is_empty() {
+        return paths;
+    }
+    let (low, high) = bars
+        .iter()
+        .fold((f64::MAX, f64::MIN), |(low, high), b| (low.min(b.low), high.max(b.high)));
+    let pad = if high > low { 0.0 } else { low.abs().max(1.0) * 0.001 };
+    let y_scale = LinearScale::new()
+        .domain(low - pad, high + pad)
+        .range(height - 1.0, 1.0);
+    let x_scale = BandScale::new(bars.len()).range(1.0, width - 1.0).padding(0.25, 0.0);
+    let bandwidth = x_scale.bandwidth();
+
+    for (i, bar) in bars.iter().enumerate() {
+        let x = x_scale.scale(i);
+        let center = x + bandwidth / 2.0;
+        let _ = write!(
+            paths.wicks,
+            "M{:.1},{:.1}V{:.1}",
+            center,
+            y_scale.scale(bar.high),
+            y_scale.scale(bar.low)
+        );
+        let top = y_scale.scale(bar.open.max(bar.close));
+        // Flat bars still show as a sliver
+        let body = (y_scale.scale(bar.open.min(bar.close)) - top).max(0.5);
+        let target = if bar.is_bullish() { &mut paths.bull } else { &mut paths.bear };
+        let _ = write!(target, "M{:.1},{:.1}h{:.1}v{:.1}h{:.1}Z", x, top, bandwidth, body, -bandwidth);
+    }
+    paths
+}
+
+/// Tiny candlestick chart at a fixed size
+#[component]
+pub fn MiniCandles(
+    #[prop(into)] bars: Signal<Vec<MiniBar>>,
+    #[prop(default = MINI_WIDTH)] width: f64,
+    #[prop(default = MINI_HEIGHT)] height: f64,
+) -> impl IntoView {
+    let paths = move || bars.with(|bars| mini_candle_paths(bars, width, height));
+
+    view! {
+        <svg
+            class="mini-candles"
+            width=width
+            height=height
+            viewBox=format!("0 0 {} {}", width, height)
+            aria-hidden="true"
+        >
+            {move || {
+                let MiniCandlePaths { wicks, bull, bear } = paths();
+                view! {
+                    <path d=wicks stroke=colors::css::TEXT_MUTED stroke-width="0.75" fill="none" />
+                    <path d=bull fill=colors::css::BULL />
+                    <path d=bear fill=colors::css::BEAR />
+                }
+            }}
+        </svg>
+    }
+}
+
+/// Tiny depth curve at a fixed size: bids left of the mid, asks right,
+/// both on one depth scale
+#[component]
+pub fn MiniDepth(
+    #[prop(into)] depth: Signal<Option<MarketDepth>>,
+    #[prop(default = MINI_WIDTH)] width: f64,
+    #[prop(default = MINI_HEIGHT)] height: f64,
+) -> impl IntoView {
+    let curves = move || {
+        depth.with(|depth| {
+            let depth = depth.as_ref()?;
+            let (bid, ask) = (depth.bid_depth.last()?, depth.ask_depth.last()?);
+            let max_depth = depth.max_depth();
+            if max_depth <= 0.0 {
+                return None;
+            }
+            let x_scale = LinearScale::new().domain(bid.price, ask.price).range(0.0, width);
+            let y_scale = LinearScale::new().domain(0.0, max_depth).range(height, 1.0);
+            let points = |side: &[dash_core::DepthPoint]| -> Vec<(f64, f64)> {
+                side.iter()
+                    .map(|p| (x_scale.scale(p.price), y_scale.scale(p.cumulative_quantity)))
+                    .collect()
+            };
+            let mut bids = points(&depth.bid_depth);
+            bids.reverse();
+            let asks = points(&depth.ask_depth);
+            Some((line_path(&bids), area_path(&bids, height), line_path(&asks), area_path(&asks, height)))
+        })
+    };
+
+    view! {
+        <svg
+            class="mini-depth"
+            width=width
+            height=height
+            viewBox=format!("0 0 {} {}", width, height)
+            aria-hidden="true"
+        >
+            {move || {
+                curves().map(|(bid_line, bid_area, ask_line, ask_area)| view! {
+                    <path d=bid_area fill=colors::css::bull_alpha(0.2) />
+                    <path d=bid_line stroke=colors::css::BULL stroke-width="1" fill="none" />
+                    <path d=ask_area fill=colors::css::bear_alpha(0.2) />
+                    <path d=ask_line stroke=colors::css::BEAR stroke-width="1" fill="none" />
+                })
+            }}
+        </svg>
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_trail_bars_and_paths() {
+        let bars = trail_bars(&[10.0, 12.0, 9.0, 11.0, 11.0, 8.0, 7.5], 3);
+        assert_eq!(bars.len(), 3);
+        assert_eq!(bars[0], MiniBar { open: 10.0, high: 12.0, low: 9.0, close: 9.0 });
+        assert_eq!(bars[2], MiniBar { open: 7.5, high: 7.5, low: 7.5, close: 7.5 });
+
+        // One subpath per bar however many bars there are
+        let paths = mini_candle_paths(&bars, MINI_WIDTH, MINI_HEIGHT);
+        assert_eq!(paths.wicks.matches('M').count(), 3);
+        assert_eq!(paths.bull.matches('M').count(), 1);
+        assert_eq!(paths.bear.matches('M').count(), 2);
+        assert_eq!(mini_candle_paths(&[], MINI_WIDTH, MINI_HEIGHT), MiniCandlePaths::default());
+    }
+}
@@ -0,0 +1,266 @@
+//! Paper-trading orders, fills and position over the candles
+//!
+//! Working orders are lined across the plot at their limit or trigger price
+//! (the chart lets them be dragged to a new price), fills are arrows at the
+//! bar and price they executed at, and an open position gets its average
+//! entry lined with the running PnL.
+
+use crate::{
+    chartkit::{BarTimeAxis, PriceScale, Scale},
+    colors,
+    drawings::HIT_TOLERANCE_PX,
+};
+use dash_core::{OrderType, PaperOrder, Position, TickFormat, TradeSide};
+use leptos::prelude::*;
+
+/// Order label box width (px), pinned to the plot's right edge
+const ORDER_LABEL_WIDTH: f64 = 132.0;
+
+/// Fill arrow size (px) and its gap from the fill price
+const ARROW_SIZE: f64 = 7.0;
+const ARROW_GAP: f64 = 3.0;
+
+/// A working order at its resting price
+#[derive(Debug, Clone, PartialEq)]
+pub struct OrderLine {
+    pub id: String,
+    pub side: TradeSide,
+    pub order_type: OrderType,
+    pub price: f64,
+    /// Quantity still working
+    pub quantity: f64,
+}
+
+impl OrderLine {
+    /// Line for a working limit or stop order (market orders don't rest)
+    pub fn from_order(order: &PaperOrder) -> Option<Self> {
+        Some(Self {
+            id: order.id.clone(),
+            side: order.side,
+            order_type: order.order_type,
+            price: order.working_price()?,
+            quantity: order.remaining(),
+        })
+    }
+
+    fn label(&self, format: TickFormat) -> String {
+        format!(
+            "{} {} {} @ {}",
+            self.side.label(),
+            self.order_type.label(),
+            format.quantity(self.quantity),
+            format.price(self.price)
+        )
+    }
+}
+
+/// An executed order: where its arrow goes
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FillMarker {
+    /// Execution time (ms)
+    pub time: i64,
+    pub price: f64,
+    pub side: TradeSide,
+}
+
+impl FillMarker {
+    pub fn from_order(order: &PaperOrder) -> Option<Self> {
+        Some(Self {
+            time: order.filled_at?,
+            price: order.fill_price?.as_f64(),
+            side: order.side,
+        })
+    }
+}
+
+/// An open position's average entry and running PnL
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PositionLine {
+    pub entry: f64,
+    /// Signed base quantity
+    pub quantity: f64,
+    pub pnl: f64,
+    pub pnl_percent: f64,
+}
+
+impl PositionLine {
+    pub fn from_position(position: &Position) -> Option<Self> {
+        (!position.is_flat()).then(|| Self {
+            entry: position.entry_price.as_f64(),
+            quantity: position.quantity,
+            pnl: position.unrealized_pnl(),
+            pnl_percent: position.unrealized_pnl_percent(),
+        })
+    }
+
+    fn label(&self, format: TickFormat) -> String {
+        let side = if self.quantity > 0.0 { "LONG" } else { "SHORT" };
+        format!(
+            "{} {} @ {}  {:+.2} ({:+.2}%)",
+            side,
+            format.quantity(self.quantity.abs()),
+            format.price(self.entry),
+            self.pnl,
+            self.pnl_percent
+        )
+    }
+}
+
+/// Everything the account shows on one symbol's chart
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TradingMarkers {
+    pub orders: Vec<OrderLine>,
+    pub fills: Vec<FillMarker>,
+    pub position: Option<PositionLine>,
+}
+
+/// The order line nearest plot row `y`, if within grabbing distance (`to_y`
+/// maps price to plot y)
+pub fn order_at(orders: &[OrderLine], y: f64, to_y: impl Fn(f64) -> f64) -> Option<&OrderLine> {
+    orders
+        .iter()
+        .map(|order| (order, (to_y(order.price) - y).abs()))
+        .filter(|&(_, gap)| gap <= HIT_TOLERANCE_PX)
+        .min_by(|a, b| a.1.total_cmp(&b.1))
+        .map(|(order, _)| order)
+}
+
+fn side_color(side: TradeSide) -> &'static str {
+    match side {
+        TradeSide::Buy => colors::css::BULL,
+        TradeSide::Sell => colors::css::BEAR,
+    }
+}
+
+/// Position entry, working order lines and fill arrows over a price plot
+#[component]
+pub fn TradingOverlay(
+    markers: TradingMarkers,
+    /// Time across the plot's bars
+    time_axis: BarTimeAxis,
+    /// Price scale of the plot the markers sit on
+    y_scale: PriceScale,
+    plot_width: f64,
+) -> impl IntoView {
+    let TradingMarkers { orders, fills, position } = markers;
+
+    let position = position.map(|line| {
+        let y = y_scale.scale(line.entry);
+        let color = if line.pnl >= 0.0 { colors::css::BULL } else { colors::css::BEAR };
+        view! {
+            <g class="position-line" pointer-events="none">
+                <line x1="0" y1=y x2=plot_width y2=y stroke=colors::css::WARN stroke-width="1" />
+                <rect x="4" y={y - 16.0} width="190" height="14" fill=colors::css::BG_ELEVATED stroke=colors::css::WARN rx="2" />
+                <text
+                    x="8"
+                    y={y - 9.0}
+                    dy="0.32em"
+                    fill=color
+                    font-size="10"
+                    font-family="JetBrains Mono, monospace"
+                >
+                    {line.label(y_scale.format())}
+                </text>
+            </g>
+        }
+    });
+
+    let orders = orders
+        .into_iter()
+        .map(|order| {
+            let y = y_scale.scale(order.price);
+            let color = side_color(order.side);
+            let label_x = plot_width - ORDER_LABEL_WIDTH - 4.0;
+            view! {
+                <g class="order-line">
+                    <title>"Drag to move the order"</title>
+                    <line
+                        x1="0" y1=y
+                        x2=plot_width y2=y
+                        stroke=color
+                        stroke-width="1"
+                        stroke-dasharray="6,3"
+                    />
+                    // Wider invisible stroke so the line is easy to grab
+                    <line
+                        x1="0" y1=y
+                        x2=plot_width y2=y
+                        stroke="transparent"
+                        stroke-width={HIT_TOLERANCE_PX * 2.0}
+                    />
+                    <rect x=label_x y={y - 7.0} width=ORDER_LABEL_WIDTH height="14" fill=colors::css::BG_ELEVATED stroke=color rx="2" />
+                    <text
+                        x={label_x + 4.0}
+                        y=y
+                        dy="0.32em"
+                        fill=color
+                        font-size="10"
+                        font-family="JetBrains Mono, monospace"
+                        pointer-events="none"
+                    >
+                        {order.label(y_scale.format())}
+                    </text>
+                </g>
+            }
+        })
+        .collect_view();
+
+    // Buys point up from under the fill price, sells down from above it
+    let fills = fills
+        .into_iter()
+        .filter_map(|fill| {
+            let x = time_axis.x_within(fill.time);
+            if !(0.0..=plot_width).contains(&x) {
+                return None;
+            }
+            let y = y_scale.scale(fill.price);
+            let (tip, base) = match fill.side {
+                TradeSide::Buy => (y + ARROW_GAP, y + ARROW_GAP + ARROW_SIZE),
+                TradeSide::Sell => (y - ARROW_GAP, y - ARROW_GAP - ARROW_SIZE),
+            };
+            let half = ARROW_SIZE / 2.0;
+            Some(view! {
+                <path
+                    class="fill-marker"
+                    d=format!("M{},{}L{},{}L{},{}Z", x, tip, x - half, base, x + half, base)
+                    fill=side_color(fill.side)
+                    stroke=colors::css::BG_VOID
+                    stroke-width="0.5"
+                    pointer-events="none"
+                />
+            })
+        })
+        .collect_view();
+
+    view! {
+        {position}
+        {fills}
+        {orders}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn line(id: &str, price: f64) -> OrderLine {
+        OrderLine {
+            id: id.into(),
+            side: TradeSide::Buy,
+            order_type: OrderType::Limit,
+            price,
+            quantity: 1.0,
+        }
+    }
+
+    #[test]
+    fn test_order_at_picks_the_nearest_line() {
+        let orders = vec![line("a", 100.0), line("b", 104.0)];
+        // One pixel per unit of price, y growing downwards from 200
+        let to_y = |p: f64| 200.0 - p;
+
+        assert_eq!(order_at(&orders, to_y(101.0), to_y).map(|o| o.id.as_str()), Some("a"));
+        assert_eq!(order_at(&orders, to_y(103.0), to_y).map(|o| o.id.as_str()), Some("b"));
+        assert!(order_at(&orders, to_y(120.0), to_y).is_none());
+    }
+}
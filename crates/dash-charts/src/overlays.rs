@@ -0,0 +1,168 @@
+//! Indicator overlays (moving averages, Bollinger Bands, session VWAP) on a
+//! price plot
+//!
+//! Series are cached between candle updates and only recomputed from the
+//! first close that changed, so a ticking candle costs one point per study.
+
+use crate::{
+    chartkit::{line_path, BandScale, PathBuilder, PriceScale, Scale},
+    colors,
+};
+use dash_core::{Candle, ChartOverlay, IndicatorPoint, VwapPoint};
+use leptos::prelude::*;
+
+/// Computed overlay series plus the closes they were built from
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct OverlaySeries {
+    /// Open time of the first candle (a change means the indices shifted)
+    first_open: Option<i64>,
+    closes: Vec<f64>,
+    /// One series per overlay, aligned with the candles
+    pub series: Vec<(ChartOverlay, Vec<Option<IndicatorPoint>>)>,
+}
+
+impl OverlaySeries {
+    /// Catch up with `candles` and the current `overlays`, keeping the points
+    /// that are still valid from the last update
+    pub fn update(&mut self, candles: &[Candle], overlays: &[ChartOverlay]) {
+        let closes: Vec<f64> = candles.iter().map(|c| c.close.as_f64()).collect();
+        let first_open = candles.first().map(|c| c.timestamp);
+        let from = if first_open == self.first_open {
+            self.closes
+                .iter()
+                .zip(&closes)
+                .position(|(old, new)| old != new)
+                .unwrap_or_else(|| self.closes.len().min(closes.len()))
+        } else {
+            0
+        };
+
+        let mut previous = std::mem::take(&mut self.series);
+        self.series = overlays
+            .iter()
+            .map(|overlay| match previous.iter().position(|(o, _)| o == overlay) {
+                Some(i) => {
+                    let (_, mut points) = previous.swap_remove(i);
+                    overlay.indicator.update(&closes, &mut points, from);
+                    (overlay.clone(), points)
+                }
+                None => (overlay.clone(), overlay.indicator.compute(&closes)),
+            })
+            .collect();
+        self.first_open = first_open;
+        self.closes = closes;
+    }
+}
+
+/// One study drawn on the candles' scales: its line, plus the band edges
+/// and a faint fill between them for banded studies
+#[component]
+pub fn IndicatorOverlay(
+    points: Vec<Option<IndicatorPoint>>,
+    color: String,
+    x_scale: BandScale,
+    y_scale: PriceScale,
+) -> impl IntoView {
+    let placed: Vec<(f64, IndicatorPoint)> = points
+        .iter()
+        .enumerate()
+        .filter_map(|(i, point)| Some((x_scale.scale_center(i), (*point)?)))
+        .collect();
+    let line: Vec<(f64, f64)> = placed.iter().map(|&(x, p)| (x, y_scale.scale(p.value))).collect();
+    let upper: Vec<(f64, f64)> = placed
+        .iter()
+        .filter_map(|&(x, p)| Some((x, y_scale.scale(p.band?.0))))
+        .collect();
+    let lower: Vec<(f64, f64)> = placed
+        .iter()
+        .filter_map(|&(x, p)| Some((x, y_scale.scale(p.band?.1))))
+        .collect();
+
+    let band_fill = (!upper.is_empty()).then(|| band_path(&upper, &lower));
+
+    view! {
+        <g class="indicator-overlay" pointer-events="none">
+            {band_fill.map(|d| {
+                let color = color.clone();
+                view! {
+                    <path d=d fill=color.clone() fill-opacity="0.08" stroke="none" />
+                    <path d=line_path(&upper) fill="none" stroke=color.clone() stroke-width="1" stroke-opacity="0.7" />
+                    <path d=line_path(&lower) fill="none" stroke=color stroke-width="1" stroke-opacity="0.7" />
+                }
+            })}
+            <path d=line_path(&line) fill="none" stroke=color stroke-width="1.5" />
+        </g>
+    }
+}
+
+/// Session VWAP with a shaded ±1σ band and dashed ±2σ edges
+#[component]
+pub fn VwapOverlay(
+    points: Vec<Option<VwapPoint>>,
+    x_scale: BandScale,
+    y_scale: PriceScale,
+) -> impl IntoView {
+    let placed: Vec<(f64, VwapPoint)> = points
+        .iter()
+        .enumerate()
+        .filter_map(|(i, point)| Some((x_scale.scale_center(i), (*point)?)))
+        .collect();
+    let band = |k: f64| -> Vec<(f64, f64)> { placed.iter().map(|&(x, p)| (x, y_scale.scale(p.band(k)))).collect() };
+    let inner_fill = (!placed.is_empty()).then(|| band_path(&band(1.0), &band(-1.0)));
+
+    view! {
+        <g class="vwap-overlay" pointer-events="none">
+            {inner_fill.map(|d| view! { <path d=d fill=colors::css::INFO fill-opacity="0.06" stroke="none" /> })}
+            {[2.0, -2.0].map(|k| view! {
+                <path d=line_path(&band(k)) fill="none" stroke=colors::css::INFO stroke-width="1" stroke-opacity="0.35" stroke-dasharray="2,3" />
+            })}
+            {[1.0, -1.0].map(|k| view! {
+                <path d=line_path(&band(k)) fill="none" stroke=colors::css::INFO stroke-width="1" stroke-opacity="0.6" stroke-dasharray="4,3" />
+            })}
+            <path d=line_path(&band(0.0)) fill="none" stroke=colors::css::INFO stroke-width="1.5" />
+        </g>
+    }
+}
+
+/// Closed area between two edges: upper left to right, then back along the lower
+fn band_path(upper: &[(f64, f64)], lower: &[(f64, f64)]) -> String {
+    let mut builder = PathBuilder::new().move_to(upper[0].0, upper[0].1);
+    for &(x, y) in upper[1..].iter().chain(lower.iter().rev()) {
+        builder = builder.line_to(x, y);
+    }
+    builder.close().build()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use dash_core::{CandleInterval, Indicator, Symbol};
+
+    fn candles(closes: &[f64]) -> Vec<Candle> {
+        closes
+            .iter()
+            .enumerate()
+            .map(|(i, &close)| Candle::new(Symbol::new("BTC-USD"), CandleInterval::M1, i as i64 * 60_000, close))
+            .collect()
+    }
+
+    #[test]
+    fn test_incremental_series_match_full_rebuild() {
+        let overlays = [ChartOverlay { indicator: Indicator::Ema(3), color: "#fff".to_string() }];
+        let mut live = candles(&[10.0, 11.0, 12.0, 13.0]);
+        let mut series = OverlaySeries::default();
+        series.update(&live, &overlays);
+
+        // Forming candle ticks, a new one opens, then the oldest is trimmed
+        live[3].update(15.0, 1.0);
+        series.update(&live, &overlays);
+        live.push(Candle::new(Symbol::new("BTC-USD"), CandleInterval::M1, 4 * 60_000, 14.0));
+        series.update(&live, &overlays);
+        live.remove(0);
+        series.update(&live, &overlays);
+
+        let mut fresh = OverlaySeries::default();
+        fresh.update(&live, &overlays);
+        assert_eq!(series, fresh);
+    }
+}
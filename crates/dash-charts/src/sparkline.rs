@@ -8,6 +8,96 @@ use crate::{
 };
 use leptos::prelude::*;
 
+// ============================================================================
+// SHARED GEOMETRY
+// ============================================================================
+
+/// Plot `data` across a `width` × `height` box with a 2px inset and 10%
+/// vertical padding (`None` with fewer than two values)
+pub fn sparkline_points(data: &[f64], width: f64, height: f64) -> Option<Vec<(f64, f64)>> {
+    if data.len() < 2 {
+        return None;
+    }
+
+    let min = data.iter().cloned().fold(f64::MAX, f64::min);
+    let max = data.iter().cloned().fold(f64::MIN, f64::max);
+
+    let range = max - min;
+    let padding = if range > 0.0 { range * 0.1 } else { 1.0 };
+
+    let y_scale = LinearScale::new()
+        .domain(min - padding, max + padding)
+        .range(height - 2.0, 2.0);
+
+    let x_step = (width - 4.0) / (data.len() - 1) as f64;
+
+    Some(
+        data.iter()
+            .enumerate()
+            .map(|(i, &value)| (2.0 + i as f64 * x_step, y_scale.scale(value)))
+            .collect(),
+    )
+}
+
+// ============================================================================
+// SPARKLINE
+// ============================================================================
+
+/// Small trend line over the last `points` values, green when the window
+/// closed at or above where it opened and red otherwise
+#[component]
+pub fn Sparkline(
+    #[prop(into)] values: Signal<Vec<f64>>,
+    /// Values shown (the most recent ones)
+    #[prop(default = 60)]
+    points: usize,
+    /// Shade the area under the line
+    #[prop(default = false)]
+    fill: bool,
+    #[prop(default = 64.0)] width: f64,
+    #[prop(default = 18.0)] height: f64,
+) -> impl IntoView {
+    let chart_data = move || {
+        values.with(|data| {
+            let recent = &data[data.len().saturating_sub(points)..];
+            let plotted = sparkline_points(recent, width, height)?;
+            let rising = recent.last() >= recent.first();
+            let area = fill.then(|| crate::chartkit::area_path(&plotted, height - 2.0));
+            Some((line_path(&plotted), area, rising))
+        })
+    };
+
+    view! {
+        <svg
+            class="sparkline"
+            viewBox=format!("0 0 {} {}", width, height)
+            preserveAspectRatio="none"
+        >
+            {move || {
+                chart_data().map(|(line, area, rising)| {
+                    let (stroke, shade) = if rising {
+                        (colors::css::BULL, colors::css::bull_alpha(0.2))
+                    } else {
+                        (colors::css::BEAR, colors::css::bear_alpha(0.2))
+                    };
+                    view! {
+                        {area.map(|d| view! { <path d=d fill=shade /> })}
+                        <path
+                            d=line
+                            fill="none"
+                            stroke=stroke
+                            stroke-width="1.25"
+                            stroke-linecap="round"
+                            stroke-linejoin="round"
+                            vector-effect="non-scaling-stroke"
+                        />
+                    }
+                })
+            }}
+        </svg>
+    }
+}
+
 // ============================================================================
 // PRICE SPARKLINE
 // ============================================================================
@@ -50,28 +140,7 @@ pub fn PriceSparkline(
 
     let chart_data = move || {
         let data = prices.get();
-        if data.len() < 2 {
-            return None;
-        }
-
-        let min = data.iter().cloned().fold(f64::MAX, f64::min);
-        let max = data.iter().cloned().fold(f64::MIN, f64::max);
-
-        // Add padding
-        let range = max - min;
-        let padding = if range > 0.0 { range * 0.1 } else { 1.0 };
-        
-        let y_scale = LinearScale::new()
-            .domain(min - padding, max + padding)
-            .range(h - 2.0, 2.0);
-
-        let x_step = (w - 4.0) / (data.len() - 1) as f64;
-
-        let points: Vec<(f64, f64)> = data
-            .iter()
-            .enumerate()
-            .map(|(i, &price)| (2.0 + i as f64 * x_step, y_scale.scale(price)))
-            .collect();
+        let points = sparkline_points(&data, w, h)?;
 
         let path = line_path(&points);
 
@@ -79,7 +148,7 @@ pub fn PriceSparkline(
         let is_positive = positive.unwrap_or_else(|| {
             data.last().unwrap_or(&0.0) >= data.first().unwrap_or(&0.0)
         });
-        let color = if is_positive { colors::BULL } else { colors::BEAR };
+        let color = if is_positive { colors::css::BULL } else { colors::css::BEAR };
 
         let last_point = points.last().cloned();
 
@@ -141,7 +210,7 @@ pub fn VolumeSparkline(
     #[prop(default = 24.0)] height: f64,
     #[prop(optional)] color: Option<&'static str>,
 ) -> impl IntoView {
-    let bar_color = color.unwrap_or(colors::BULL);
+    let bar_color = color.unwrap_or(colors::css::BULL);
 
     let chart_data = move || {
         let data = volumes.get();
@@ -186,7 +255,8 @@ pub fn VolumeSparkline(
                                 y=y
                                 width=bar_width
                                 height=h
-                                fill=colors::bull_alpha(0.4)
+                                fill=bar_color
+                                fill-opacity="0.4"
                                 rx="1"
                             />
                         }
@@ -269,7 +339,7 @@ pub fn TradeFlowSparkline(
                 y1=mid_y
                 x2=width
                 y2=mid_y
-                stroke=colors::BORDER
+                stroke=colors::css::BORDER
                 stroke-width="0.5"
             />
 
@@ -284,7 +354,7 @@ pub fn TradeFlowSparkline(
                                         x=x y=y
                                         width=bar_width
                                         height=h.max(0.5)
-                                        fill=colors::BULL
+                                        fill=colors::css::BULL
                                         rx="1"
                                     />
                                 }
@@ -297,7 +367,7 @@ pub fn TradeFlowSparkline(
                                         x=x y=y
                                         width=bar_width
                                         height=h.max(0.5)
-                                        fill=colors::BEAR
+                                        fill=colors::css::BEAR
                                         rx="1"
                                     />
                                 }
@@ -310,6 +380,91 @@ pub fn TradeFlowSparkline(
     }
 }
 
+// ============================================================================
+// DELTA SPARKLINE
+// ============================================================================
+
+/// Signed series around a zero line, shaded green above it and red below
+/// (cumulative volume delta and the like)
+#[component]
+pub fn DeltaSparkline(
+    #[prop(into)] values: Signal<Vec<f64>>,
+    #[prop(default = 64.0)] width: f64,
+    #[prop(default = 18.0)] height: f64,
+) -> impl IntoView {
+    let chart_data = move || {
+        values.with(|data| {
+            if data.len() < 2 {
+                return None;
+            }
+            // Zero is always in view so the shading reads as above/below
+            let min = data.iter().cloned().fold(0.0_f64, f64::min);
+            let max = data.iter().cloned().fold(0.0_f64, f64::max);
+            let padding = if max > min { (max - min) * 0.1 } else { 1.0 };
+            let y_scale = LinearScale::new()
+                .domain(min - padding, max + padding)
+                .range(height - 2.0, 2.0);
+            let x_step = (width - 4.0) / (data.len() - 1) as f64;
+
+            let points: Vec<(f64, f64)> = data
+                .iter()
+                .enumerate()
+                .map(|(i, &value)| (2.0 + i as f64 * x_step, y_scale.scale(value)))
+                .collect();
+            let zero_y = y_scale.scale(0.0);
+            let area = crate::chartkit::area_path(&points, zero_y);
+            let stroke = if data.last() >= Some(&0.0) { colors::css::BULL } else { colors::css::BEAR };
+            Some((line_path(&points), area, zero_y, stroke))
+        })
+    };
+
+    view! {
+        <svg
+            class="sparkline delta-sparkline"
+            viewBox=format!("0 0 {} {}", width, height)
+            preserveAspectRatio="none"
+        >
+            {move || {
+                chart_data().map(|(line, area, zero_y, stroke)| {
+                    // Nested viewports clip the one area to either side of zero
+                    let below = (height - zero_y).max(0.0);
+                    view! {
+                        <svg width=width height=zero_y>
+                            <path d=area.clone() fill=colors::css::bull_alpha(0.25) />
+                        </svg>
+                        <svg
+                            y=zero_y
+                            width=width
+                            height=below
+                            viewBox=format!("0 {} {} {}", zero_y, width, below)
+                        >
+                            <path d=area fill=colors::css::bear_alpha(0.25) />
+                        </svg>
+                        <line
+                            x1="0"
+                            y1=zero_y
+                            x2=width
+                            y2=zero_y
+                            stroke=colors::css::BORDER
+                            stroke-width="0.5"
+                            vector-effect="non-scaling-stroke"
+                        />
+                        <path
+                            d=line
+                            fill="none"
+                            stroke=stroke
+                            stroke-width="1.25"
+                            stroke-linecap="round"
+                            stroke-linejoin="round"
+                            vector-effect="non-scaling-stroke"
+                        />
+                    }
+                })
+            }}
+        </svg>
+    }
+}
+
 // ============================================================================
 // PERCENT BAR
 // ============================================================================
@@ -321,8 +476,8 @@ pub fn PercentBar(
     #[prop(into)] value: Signal<f64>,
     #[prop(default = 100.0)] width: f64,
     #[prop(default = 6.0)] height: f64,
-    #[prop(default = colors::BULL)] positive_color: &'static str,
-    #[prop(default = colors::BEAR)] negative_color: &'static str,
+    #[prop(default = colors::css::BULL)] positive_color: &'static str,
+    #[prop(default = colors::css::BEAR)] negative_color: &'static str,
 ) -> impl IntoView {
     let center = width / 2.0;
 
@@ -349,7 +504,7 @@ pub fn PercentBar(
             <rect
                 width=width
                 height=height
-                fill=colors::BG_ELEVATED
+                fill=colors::css::BG_ELEVATED
                 rx="3"
             />
 
@@ -369,7 +524,7 @@ pub fn PercentBar(
                 y1="0"
                 x2=center
                 y2=height
-                stroke=colors::BORDER
+                stroke=colors::css::BORDER
                 stroke-width="1"
             />
         </svg>
@@ -388,31 +543,11 @@ pub fn AreaSparkline(
     #[prop(default = 32.0)] height: f64,
     #[prop(optional)] color: Option<&'static str>,
 ) -> impl IntoView {
-    let stroke_color = color.unwrap_or(colors::BULL);
+    let stroke_color = color.unwrap_or(colors::css::BULL);
 
     let chart_data = move || {
         let data = values.get();
-        if data.len() < 2 {
-            return None;
-        }
-
-        let min = data.iter().cloned().fold(f64::MAX, f64::min);
-        let max = data.iter().cloned().fold(f64::MIN, f64::max);
-
-        let range = max - min;
-        let padding = if range > 0.0 { range * 0.1 } else { 1.0 };
-
-        let y_scale = LinearScale::new()
-            .domain(min - padding, max + padding)
-            .range(height - 2.0, 2.0);
-
-        let x_step = (width - 4.0) / (data.len() - 1) as f64;
-
-        let points: Vec<(f64, f64)> = data
-            .iter()
-            .enumerate()
-            .map(|(i, &val)| (2.0 + i as f64 * x_step, y_scale.scale(val)))
-            .collect();
+        let points = sparkline_points(&data, width, height)?;
 
         // Build area path
         let baseline = height - 2.0;
@@ -435,7 +570,7 @@ pub fn AreaSparkline(
                             // Filled area
                             <path
                                 d=area
-                                fill=colors::bull_alpha(0.2)
+                                fill=colors::css::bull_alpha(0.2)
                             />
                             // Line
                             <path
@@ -0,0 +1,126 @@
+//! Spread micro-chart: bid-ask spread over the last few minutes
+//!
+//! A step line of the spread in basis points on a fixed time window, with
+//! the window's average dashed across it. The line turns amber while the
+//! spread runs well above that average, the usual sign of liquidity
+//! pulling back before a fast move.
+
+use crate::{
+    chartkit::{LinearScale, Scale},
+    colors,
+};
+use leptos::prelude::*;
+use std::fmt::Write;
+
+/// Latest spread over the window average that reads as widening
+pub const SPREAD_WIDENING_RATIO: f64 = 2.0;
+
+/// Plotted spread: step path, average line height, widening flag
+#[derive(Debug, Clone, PartialEq)]
+pub struct SpreadGeometry {
+    pub path: String,
+    pub average_y: f64,
+    pub widening: bool,
+}
+
+/// Lay `(time, bps)` points across `window_ms` ending at the newest one
+/// (`None` with fewer than two points)
+pub fn spread_geometry(points: &[(i64, f64)], window_ms: i64, width: f64, height: f64) -> Option<SpreadGeometry> {
+    let (&(_, first), &(end, latest)) = (points.first()?, points.last()?);
+    if points.len() < 2 {
+        return None;
+    }
+    let max = points.iter().map(|&(_, bps)| bps).fold(first, f64::max);
+    let average = points.iter().map(|&(_, bps)| bps).sum::<f64>() / points.len() as f64;
+    // Spreads start at zero so the line's height reads as the spread itself
+    let y_scale = LinearScale::new()
+        .domain(0.0, if max > 0.0 { max * 1.1 } else { 1.0 })
+        .range(height - 1.0, 1.0);
+    let x_scale = LinearScale::new()
+        .domain((end - window_ms) as f64, end as f64)
+        .range(0.0, width);
+
+    let mut path = String::new();
+    for (i, &(time, bps)) in points.iter().enumerate() {
+        let (x, y) = (x_scale.scale(time as f64).max(0.0), y_scale.scale(bps));
+        if i == 0 {
+            write!(path, "M{:.1},{:.1}", x, y).ok();
+        } else {
+            write!(path, "H{:.1}V{:.1}", x, y).ok();
+        }
+    }
+    write!(path, "H{:.1}", width).ok();
+
+    Some(SpreadGeometry {
+        path,
+        average_y: y_scale.scale(average),
+        widening: latest > average * SPREAD_WIDENING_RATIO,
+    })
+}
+
+/// Spread step line over the last `window_ms`
+#[component]
+pub fn SpreadChart(
+    /// `(time, spread in bps)`, oldest first
+    #[prop(into)]
+    points: Signal<Vec<(i64, f64)>>,
+    #[prop(default = 5 * 60_000)] window_ms: i64,
+    #[prop(default = 96.0)] width: f64,
+    #[prop(default = 24.0)] height: f64,
+) -> impl IntoView {
+    let geometry = move || points.with(|points| spread_geometry(points, window_ms, width, height));
+
+    view! {
+        <svg
+            class="sparkline spread-chart"
+            viewBox=format!("0 0 {} {}", width, height)
+            preserveAspectRatio="none"
+        >
+            {move || {
+                geometry().map(|g| {
+                    let stroke = if g.widening { colors::css::WARN } else { colors::css::INFO };
+                    view! {
+                        <line
+                            x1="0"
+                            y1=g.average_y
+                            x2=width
+                            y2=g.average_y
+                            stroke=colors::css::TEXT_MUTED
+                            stroke-width="0.75"
+                            stroke-dasharray="2,2"
+                            vector-effect="non-scaling-stroke"
+                        />
+                        <path
+                            d=g.path
+                            fill="none"
+                            stroke=stroke
+                            stroke-width="1.25"
+                            stroke-linejoin="round"
+                            vector-effect="non-scaling-stroke"
+                        />
+                    }
+                })
+            }}
+        </svg>
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_spread_steps_across_the_window() {
+        assert_eq!(spread_geometry(&[(0, 1.0)], 1_000, 100.0, 12.0), None);
+
+        // Half the window covered; flat at 1bps then a jump to 10bps
+        let points = [(500, 1.0), (750, 1.0), (1_000, 10.0)];
+        let g = spread_geometry(&points, 1_000, 100.0, 12.0).unwrap();
+        assert!(g.path.starts_with("M50.0,"));
+        assert!(g.path.ends_with("H100.0V1.9H100.0"));
+        assert!(g.widening);
+
+        let calm = spread_geometry(&[(0, 2.0), (1_000, 2.0)], 1_000, 100.0, 12.0).unwrap();
+        assert!(!calm.widening);
+    }
+}
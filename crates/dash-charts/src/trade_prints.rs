@@ -0,0 +1,102 @@
+//! Trade prints plotted over the candles
+//!
+//! Each recent trade becomes a dot at its price and time, sized by notional
+//! against the same thresholds the tape classifies with, so the prints the
+//! tape flags as whales are the ones ringed here.
+
+use crate::{
+    chartkit::{BarTimeAxis, PriceScale, Scale},
+    colors,
+};
+use dash_core::{Trade, TradeClassification, TradeClassifier, TradeSide, ValueThresholdClassifier};
+use leptos::prelude::*;
+
+/// Newest trades worth plotting; older ones pile up behind the live bar
+pub const MAX_TRADE_PRINTS: usize = 500;
+
+/// Dot radius bounds (px)
+const MIN_RADIUS_PX: f64 = 1.5;
+const MAX_RADIUS_PX: f64 = 12.0;
+
+/// A trade as drawn on the chart
+#[derive(Debug, Clone, PartialEq)]
+pub struct TradePrint {
+    /// Execution time (ms)
+    pub time: i64,
+    pub price: f64,
+    pub side: TradeSide,
+    /// Dot radius (px)
+    pub radius: f64,
+    pub whale: bool,
+}
+
+impl TradePrint {
+    pub fn from_trade(trade: &Trade, classifier: &ValueThresholdClassifier) -> Self {
+        Self {
+            time: trade.timestamp.timestamp_millis(),
+            price: trade.price.as_f64(),
+            side: trade.side,
+            radius: print_radius(trade.value(), classifier),
+            whale: classifier.classify(trade) == TradeClassification::Whale,
+        }
+    }
+}
+
+/// Dot radius for a notional: area grows with size, a whale-threshold print
+/// gets two thirds of the largest dot
+pub fn print_radius(notional: f64, classifier: &ValueThresholdClassifier) -> f64 {
+    let share = (notional / classifier.whale_threshold.max(f64::EPSILON)).max(0.0);
+    (MAX_RADIUS_PX * 2.0 / 3.0 * share.sqrt()).clamp(MIN_RADIUS_PX, MAX_RADIUS_PX)
+}
+
+/// Trade dots (buys green, sells red, whales ringed) over a price plot
+#[component]
+pub fn TradePrintsOverlay(
+    prints: Vec<TradePrint>,
+    /// Time across the plot's bars
+    time_axis: BarTimeAxis,
+    /// Price scale of the plot the prints sit on
+    y_scale: PriceScale,
+    /// Plot width; prints off either side are skipped
+    plot_width: f64,
+) -> impl IntoView {
+    prints
+        .into_iter()
+        .filter_map(|print| {
+            let x = time_axis.x_within(print.time);
+            if !(0.0..=plot_width).contains(&x) {
+                return None;
+            }
+            let fill = match print.side {
+                TradeSide::Buy => colors::css::bull_alpha(0.45),
+                TradeSide::Sell => colors::css::bear_alpha(0.45),
+            };
+            Some(view! {
+                <circle
+                    class=if print.whale { "trade-print whale" } else { "trade-print" }
+                    cx=x
+                    cy=y_scale.scale(print.price)
+                    r=print.radius
+                    fill=fill
+                    stroke=if print.whale { colors::css::WARN } else { "none" }
+                    stroke-width="1.5"
+                />
+            })
+        })
+        .collect_view()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_print_radius_tracks_the_classifier() {
+        let classifier = ValueThresholdClassifier::default();
+
+        assert_eq!(print_radius(0.0, &classifier), MIN_RADIUS_PX);
+        assert_eq!(print_radius(classifier.whale_threshold, &classifier), 8.0);
+        assert_eq!(print_radius(classifier.whale_threshold * 100.0, &classifier), MAX_RADIUS_PX);
+        assert!(print_radius(classifier.large_threshold, &classifier) < print_radius(classifier.whale_threshold, &classifier));
+    }
+}
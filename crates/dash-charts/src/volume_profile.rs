@@ -0,0 +1,117 @@
+//! Volume profile (volume-at-price) overlay
+//!
+//! Horizontal histogram anchored to the right edge of a price plot, sharing
+//! its y scale so bins line up with the candles.
+
+use crate::{
+    chartkit::{PriceScale, Scale},
+    colors,
+};
+use dash_core::ProfileHistogram;
+use leptos::prelude::*;
+
+/// Volume profile bars plus POC and value-area lines
+#[component]
+pub fn VolumeProfile(
+    profile: ProfileHistogram,
+    /// Price scale of the plot the profile sits on
+    y_scale: PriceScale,
+    /// Plot width; bars grow leftward from its right edge
+    plot_width: f64,
+    /// Plot height; bins outside it are skipped
+    plot_height: f64,
+    /// Longest bar as a share of the plot width
+    #[prop(default = 0.25)]
+    max_share: f64,
+) -> impl IntoView {
+    let max_volume = profile.max_volume().max(f64::EPSILON);
+    let max_width = plot_width * max_share;
+    let on_plot = move |y: f64| (0.0..=plot_height).contains(&y);
+
+    let bars = profile
+        .bins
+        .iter()
+        .enumerate()
+        .filter_map(|(i, bin)| {
+            let (top, bottom) = (y_scale.scale(bin.high), y_scale.scale(bin.low));
+            if !on_plot(top) || !on_plot(bottom) || bin.volume() <= 0.0 {
+                return None;
+            }
+            let height = (bottom - top - 1.0).max(1.0);
+            let buy_width = bin.buy_volume / max_volume * max_width;
+            let sell_width = bin.sell_volume / max_volume * max_width;
+            // Value-area bins stand out; the rest fade back
+            let alpha = if profile.in_value_area(i) { 0.45 } else { 0.2 };
+            let class = if i == profile.poc { "vp-bin poc" } else { "vp-bin" };
+
+            Some(view! {
+                <g class=class>
+                    <rect
+                        x=plot_width - buy_width - sell_width
+                        y=top
+                        width=sell_width
+                        height=height
+                        fill=colors::css::bear_alpha(alpha)
+                    />
+                    <rect
+                        x=plot_width - buy_width
+                        y=top
+                        width=buy_width
+                        height=height
+                        fill=colors::css::bull_alpha(alpha)
+                    />
+                </g>
+            })
+        })
+        .collect_view();
+
+    let poc_line = profile.poc_price().and_then(|price| {
+        let y = y_scale.scale(price);
+        on_plot(y).then(|| view! {
+            <g class="vp-poc">
+                <line
+                    x1="0" y1=y
+                    x2=plot_width y2=y
+                    stroke=colors::css::WARN
+                    stroke-width="1"
+                    stroke-dasharray="6,3"
+                />
+                <text
+                    x="4"
+                    y=y - 3.0
+                    fill=colors::css::WARN
+                    font-size="9"
+                    font-family="JetBrains Mono, monospace"
+                >
+                    {format!("POC {}", y_scale.format().price(price))}
+                </text>
+            </g>
+        })
+    });
+
+    let value_area_lines = profile.value_area_prices().map(|(low, high)| {
+        [low, high]
+            .into_iter()
+            .map(|price| y_scale.scale(price))
+            .filter(|&y| on_plot(y))
+            .map(|y| view! {
+                <line
+                    class="vp-value-area"
+                    x1="0" y1=y
+                    x2=plot_width y2=y
+                    stroke=colors::css::warn_alpha(0.35)
+                    stroke-width="1"
+                    stroke-dasharray="2,4"
+                />
+            })
+            .collect_view()
+    });
+
+    view! {
+        <g class="volume-profile">
+            {bars}
+            {value_area_lines}
+            {poc_line}
+        </g>
+    }
+}
@@ -0,0 +1,81 @@
+//! Mean-reversion bot on the paper account
+//!
+//! Buys the minimum quantity when the last trade is 0.05% below the average
+//! of the last 50 prints and sells it back once the price is above the
+//! average again. Run the server first, then:
+//!
+//! ```shell
+//! cargo run -p dash-client --example mean_reversion -- ws://127.0.0.1:3001/ws BTC-USD
+//! ```
+
+use std::collections::VecDeque;
+
+use dash_client::{market_order, DashClient};
+use dash_core::{BookFeed, Symbol, TradeSide, WsMessage};
+
+const WINDOW: usize = 50;
+const ENTRY_DISCOUNT: f64 = 0.0005;
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    tracing_subscriber::fmt::init();
+    let mut args = std::env::args().skip(1);
+    let url = args.next().unwrap_or_else(|| "ws://127.0.0.1:3001/ws".to_string());
+    let symbol = Symbol::new(args.next().as_deref().unwrap_or("BTC-USD"));
+
+    let mut client = DashClient::connect(&url).await?;
+    // Trades drive the strategy; the full book isn't needed
+    client.set_book_feed(BookFeed::Bbo)?;
+    client.list_symbols()?;
+
+    let mut quantity = None;
+    let mut prices: VecDeque<f64> = VecDeque::with_capacity(WINDOW);
+    let mut position = 0.0;
+    let mut pending: Option<String> = None;
+
+    while let Some(msg) = client.recv().await {
+        match msg {
+            WsMessage::Symbols(symbols) => {
+                quantity = symbols.iter().find(|s| s.symbol == symbol).map(|s| s.min_quantity);
+                if quantity.is_none() {
+                    return Err(format!("{} isn't traded here", symbol).into());
+                }
+            }
+            WsMessage::Position(p) if p.symbol == symbol => position = p.quantity,
+            WsMessage::OrderUpdate(order) if pending.as_ref() == Some(&order.id) && !order.status.is_open() => {
+                println!("{} {} {:?}", order.side.label(), order.quantity.as_f64(), order.status);
+                pending = None;
+            }
+            WsMessage::OrderRejected { client_id, reason } if pending.as_ref() == Some(&client_id) => {
+                println!("Rejected: {:?}", reason);
+                pending = None;
+            }
+            WsMessage::Trade(trade) if trade.symbol == symbol => {
+                let price = trade.price.as_f64();
+                if prices.len() == WINDOW {
+                    prices.pop_front();
+                }
+                prices.push_back(price);
+                let (Some(quantity), None, WINDOW) = (quantity, &pending, prices.len()) else {
+                    continue;
+                };
+                let mean = prices.iter().sum::<f64>() / WINDOW as f64;
+                let side = if position <= 0.0 && price < mean * (1.0 - ENTRY_DISCOUNT) {
+                    TradeSide::Buy
+                } else if position > 0.0 && price > mean {
+                    TradeSide::Sell
+                } else {
+                    continue;
+                };
+                let size = if side == TradeSide::Sell { position } else { quantity };
+                pending = Some(client.place_order(market_order(symbol.clone(), side, size))?);
+            }
+            _ => {}
+        }
+    }
+
+    if let Some(reason) = client.close_reason() {
+        println!("Server closed the connection: {}", reason.message());
+    }
+    Ok(())
+}
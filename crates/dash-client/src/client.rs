@@ -0,0 +1,266 @@
+//! WebSocket connection to the dashboard server
+//!
+//! A background task owns the socket: it decodes server messages into a
+//! queue for [`DashClient::recv`], answers the server's idle pings (or the
+//! server drops the connection) and writes queued commands. The queue is
+//! unbounded, so a bot should keep reading, or ask for less with
+//! [`DashClient::set_conflation`] and [`DashClient::set_book_feed`].
+
+use std::fmt;
+
+use futures::{SinkExt, StreamExt};
+use tokio::net::TcpStream;
+use tokio::sync::{mpsc, watch};
+use tokio_tungstenite::tungstenite::{self, Message};
+use tokio_tungstenite::{MaybeTlsStream, WebSocketStream};
+
+use dash_core::{BookFeed, ClientCommand, CloseReason, OrderRequest, Symbol, WsMessage};
+
+type Socket = WebSocketStream<MaybeTlsStream<TcpStream>>;
+
+/// Why a client call failed
+#[derive(Debug)]
+pub enum ClientError {
+    /// The WebSocket handshake failed
+    Connect(Box<tungstenite::Error>),
+    /// The connection is gone; see [`DashClient::close_reason`]
+    Disconnected,
+}
+
+impl fmt::Display for ClientError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Connect(e) => write!(f, "failed to connect: {}", e),
+            Self::Disconnected => write!(f, "disconnected from the server"),
+        }
+    }
+}
+
+impl std::error::Error for ClientError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Connect(e) => Some(e.as_ref()),
+            Self::Disconnected => None,
+        }
+    }
+}
+
+/// Connection to the server's `/ws` endpoint
+pub struct DashClient {
+    commands: mpsc::UnboundedSender<ClientCommand>,
+    messages: mpsc::UnboundedReceiver<WsMessage>,
+    closed: watch::Receiver<Option<CloseReason>>,
+    connection_id: Option<String>,
+}
+
+impl DashClient {
+    /// Connect to `url` (e.g. `ws://127.0.0.1:3001/ws`); must be called
+    /// within a Tokio runtime
+    pub async fn connect(url: &str) -> Result<Self, ClientError> {
+        let (socket, _) = tokio_tungstenite::connect_async(url).await.map_err(|e| ClientError::Connect(Box::new(e)))?;
+        let (commands, commands_rx) = mpsc::unbounded_channel();
+        let (messages_tx, messages) = mpsc::unbounded_channel();
+        let (closed_tx, closed) = watch::channel(None);
+        tokio::spawn(run_socket(socket, commands_rx, messages_tx, closed_tx));
+        Ok(Self {
+            commands,
+            messages,
+            closed,
+            connection_id: None,
+        })
+    }
+
+    /// Next message from the server, or `None` once the connection is gone
+    /// and everything received has been read
+    pub async fn recv(&mut self) -> Option<WsMessage> {
+        let msg = self.messages.recv().await?;
+        if let WsMessage::Hello { connection_id } = &msg {
+            self.connection_id = Some(connection_id.clone());
+        }
+        Some(msg)
+    }
+
+    /// Id the server tags its logs for this connection with, once `hello`
+    /// has been read
+    pub fn connection_id(&self) -> Option<&str> {
+        self.connection_id.as_deref()
+    }
+
+    /// Why the server closed the connection, if it said
+    pub fn close_reason(&self) -> Option<CloseReason> {
+        *self.closed.borrow()
+    }
+
+    /// Queue any command for the server
+    pub fn send(&self, command: ClientCommand) -> Result<(), ClientError> {
+        self.commands.send(command).map_err(|_| ClientError::Disconnected)
+    }
+
+    /// Submit a paper order and return its id; the answer is an
+    /// `order_update` or `order_rejected` carrying that id
+    pub fn place_order(&self, order: OrderRequest) -> Result<String, ClientError> {
+        let id = order.client_id.clone();
+        self.send(ClientCommand::PlaceOrder { order })?;
+        Ok(id)
+    }
+
+    pub fn cancel_order(&self, order_id: impl Into<String>) -> Result<(), ClientError> {
+        self.send(ClientCommand::CancelOrder {
+            order_id: order_id.into(),
+        })
+    }
+
+    /// Move a resting order to a new limit (or, for stops, trigger) price
+    pub fn amend_order(&self, order_id: impl Into<String>, price: f64) -> Result<(), ClientError> {
+        self.send(ClientCommand::AmendOrder {
+            order_id: order_id.into(),
+            price,
+        })
+    }
+
+    /// Ask for the tradable symbols; answered by `symbols`
+    pub fn list_symbols(&self) -> Result<(), ClientError> {
+        self.send(ClientCommand::ListSymbols)
+    }
+
+    /// Ask for `symbol`'s latest ticker, book and candle; answered by
+    /// `snapshot`
+    pub fn request_snapshot(&self, symbol: Symbol) -> Result<(), ClientError> {
+        self.send(ClientCommand::RequestSnapshot { symbol })
+    }
+
+    /// At most one book, depth and BBO update per symbol every
+    /// `interval_ms` (`None` streams every update)
+    pub fn set_conflation(&self, interval_ms: Option<u32>) -> Result<(), ClientError> {
+        self.send(ClientCommand::SetConflation { interval_ms })
+    }
+
+    /// Full book and depth updates, or just the BBO
+    pub fn set_book_feed(&self, feed: BookFeed) -> Result<(), ClientError> {
+        self.send(ClientCommand::SetBookFeed { feed })
+    }
+}
+
+/// Reply the client owes the server for `msg`
+fn reply_to(msg: &WsMessage) -> Option<ClientCommand> {
+    match msg {
+        WsMessage::Ping { nonce, .. } => Some(ClientCommand::Pong { nonce: *nonce }),
+        _ => None,
+    }
+}
+
+async fn write(socket: &mut Socket, command: &ClientCommand) -> Result<(), tungstenite::Error> {
+    match serde_json::to_string(command) {
+        Ok(json) => socket.send(Message::Text(json)).await,
+        Err(e) => {
+            tracing::error!("Failed to serialize {}: {}", command.kind(), e);
+            Ok(())
+        }
+    }
+}
+
+/// Pump the socket until either side closes it or the client is dropped
+async fn run_socket(
+    mut socket: Socket,
+    mut commands: mpsc::UnboundedReceiver<ClientCommand>,
+    messages: mpsc::UnboundedSender<WsMessage>,
+    closed: watch::Sender<Option<CloseReason>>,
+) {
+    loop {
+        tokio::select! {
+            frame = socket.next() => match frame {
+                Some(Ok(Message::Text(text))) => match serde_json::from_str::<WsMessage>(&text) {
+                    Ok(msg) => {
+                        if let Some(reply) = reply_to(&msg)
+                            && let Err(e) = write(&mut socket, &reply).await
+                        {
+                            tracing::warn!("Failed to answer ping: {}", e);
+                            break;
+                        }
+                        if messages.send(msg).is_err() {
+                            break;
+                        }
+                    }
+                    // Newer servers may send messages this build doesn't know
+                    Err(e) => tracing::debug!("Skipping unreadable message: {}", e),
+                },
+                Some(Ok(Message::Close(frame))) => {
+                    let reason = frame.and_then(|f| CloseReason::from_code(f.code.into()));
+                    tracing::info!("Server closed the connection: {:?}", reason);
+                    closed.send_replace(reason);
+                    break;
+                }
+                Some(Ok(_)) => {}
+                Some(Err(e)) => {
+                    tracing::warn!("Connection lost: {}", e);
+                    break;
+                }
+                None => break,
+            },
+            command = commands.recv() => match command {
+                Some(command) => {
+                    if let Err(e) = write(&mut socket, &command).await {
+                        tracing::warn!("Failed to send {}: {}", command.kind(), e);
+                        break;
+                    }
+                }
+                None => {
+                    let _ = socket.close(None).await;
+                    break;
+                }
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::net::TcpListener;
+    use tokio_tungstenite::tungstenite::protocol::{frame::coding::CloseCode, CloseFrame};
+
+    #[test]
+    fn test_reply_to_ping() {
+        let ping = WsMessage::Ping { nonce: 3, timestamp: 0 };
+        assert!(matches!(reply_to(&ping), Some(ClientCommand::Pong { nonce: 3 })));
+        assert!(reply_to(&WsMessage::Heartbeat { timestamp: 0 }).is_none());
+    }
+
+    #[tokio::test]
+    async fn test_client_session() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("ws://{}", listener.local_addr().unwrap());
+        let server = tokio::spawn(async move {
+            let (stream, _) = listener.accept().await.unwrap();
+            let mut socket = tokio_tungstenite::accept_async(stream).await.unwrap();
+            let send = |msg: WsMessage| Message::Text(serde_json::to_string(&msg).unwrap());
+            socket.send(send(WsMessage::Hello { connection_id: "c1".into() })).await.unwrap();
+            socket.send(Message::Text(r#"{"type":"from_the_future","data":{}}"#.into())).await.unwrap();
+            socket.send(send(WsMessage::Ping { nonce: 9, timestamp: 0 })).await.unwrap();
+
+            let mut received = Vec::new();
+            while received.len() < 2 {
+                if let Some(Ok(Message::Text(text))) = socket.next().await {
+                    received.push(serde_json::from_str::<ClientCommand>(&text).unwrap().kind());
+                }
+            }
+            let frame = CloseFrame {
+                code: CloseCode::from(CloseReason::SlowConsumer.code()),
+                reason: "".into(),
+            };
+            socket.send(Message::Close(Some(frame))).await.unwrap();
+            received
+        });
+
+        let mut client = DashClient::connect(&url).await.unwrap();
+        assert!(matches!(client.recv().await, Some(WsMessage::Hello { .. })));
+        assert_eq!(client.connection_id(), Some("c1"));
+        assert!(matches!(client.recv().await, Some(WsMessage::Ping { nonce: 9, .. })));
+        client.list_symbols().unwrap();
+
+        assert!(client.recv().await.is_none());
+        assert_eq!(client.close_reason(), Some(CloseReason::SlowConsumer));
+        assert_eq!(server.await.unwrap(), ["pong", "list_symbols"]);
+        assert!(matches!(client.list_symbols(), Err(ClientError::Disconnected)));
+    }
+}
@@ -0,0 +1,33 @@
+//! # dash-client
+//!
+//! Rust client for bots that trade the dashboard server's paper account.
+//! It speaks the same WebSocket protocol as the dashboard: [`WsMessage`]s
+//! in, [`ClientCommand`]s out, both described by `GET /api/schema`.
+//!
+//! - `client`: connection, server pings answered in the background
+//! - `orders`: paper order requests with fresh client ids
+//!
+//! ```no_run
+//! # async fn run() -> Result<(), dash_client::ClientError> {
+//! use dash_client::{market_order, DashClient};
+//! use dash_core::{Symbol, TradeSide, WsMessage};
+//!
+//! let mut client = DashClient::connect("ws://127.0.0.1:3001/ws").await?;
+//! client.place_order(market_order(Symbol::new("BTC-USD"), TradeSide::Buy, 0.001))?;
+//! while let Some(msg) = client.recv().await {
+//!     if let WsMessage::OrderUpdate(order) = msg {
+//!         println!("{} {:?}", order.id, order.status);
+//!     }
+//! }
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! [`WsMessage`]: dash_core::WsMessage
+//! [`ClientCommand`]: dash_core::ClientCommand
+
+pub mod client;
+pub mod orders;
+
+pub use client::*;
+pub use orders::*;
@@ -0,0 +1,70 @@
+//! Paper order requests
+//!
+//! Each request gets a fresh `bot-` prefixed client id, which the engine
+//! reuses as the order id, so fills and rejections can be matched up.
+
+use dash_core::{OrderRequest, OrderType, Symbol, TradeSide};
+use uuid::Uuid;
+
+/// Prefix of the client ids given to bot orders
+pub const CLIENT_ID_PREFIX: &str = "bot-";
+
+fn client_id() -> String {
+    format!("{}{}", CLIENT_ID_PREFIX, Uuid::new_v4())
+}
+
+fn order(symbol: Symbol, side: TradeSide, order_type: OrderType, quantity: f64) -> OrderRequest {
+    OrderRequest {
+        client_id: client_id(),
+        symbol,
+        side,
+        order_type,
+        price: None,
+        stop_price: None,
+        quantity,
+    }
+}
+
+/// Order filled at the next trade price
+pub fn market_order(symbol: Symbol, side: TradeSide, quantity: f64) -> OrderRequest {
+    order(symbol, side, OrderType::Market, quantity)
+}
+
+/// Order resting at `price` until the market trades through it
+pub fn limit_order(symbol: Symbol, side: TradeSide, quantity: f64, price: f64) -> OrderRequest {
+    OrderRequest {
+        price: Some(price),
+        ..order(symbol, side, OrderType::Limit, quantity)
+    }
+}
+
+/// Market order armed once the last price crosses `stop_price`
+pub fn stop_order(symbol: Symbol, side: TradeSide, quantity: f64, stop_price: f64) -> OrderRequest {
+    OrderRequest {
+        stop_price: Some(stop_price),
+        ..order(symbol, side, OrderType::Stop, quantity)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use dash_core::SymbolInfo;
+
+    #[test]
+    fn test_orders_validate() {
+        let info = SymbolInfo::new(Symbol::new("BTC-USD"), 0.01, 0.001);
+        let symbol = info.symbol.clone();
+
+        let market = market_order(symbol.clone(), TradeSide::Buy, 0.002);
+        let limit = limit_order(symbol.clone(), TradeSide::Sell, 0.002, 50_000.25);
+        let stop = stop_order(symbol, TradeSide::Sell, 0.002, 49_000.0);
+        for order in [&market, &limit, &stop] {
+            assert!(order.client_id.starts_with(CLIENT_ID_PREFIX));
+            assert_eq!(order.validate(&info), Ok(()));
+        }
+        assert_ne!(market.client_id, limit.client_id);
+        assert_eq!(limit.price, Some(50_000.25));
+        assert_eq!(stop.stop_price, Some(49_000.0));
+    }
+}
@@ -0,0 +1,104 @@
+//! Keyboard row navigation and throttled screen reader price announcements
+
+use dash_state::MarketState;
+use leptos::ev::{FocusEvent, KeyboardEvent};
+use leptos::prelude::*;
+use leptos::web_sys::{Element, HtmlElement, Node};
+use wasm_bindgen::JsCast;
+
+/// Minimum gap between spoken price updates
+pub const ANNOUNCE_INTERVAL_MS: u64 = 5_000;
+
+/// Arrow/Home/End keys move focus between the focusable `[role=row]`s of the
+/// enclosing `[role=table]`; Enter/Space activates the focused row
+pub fn row_keydown(ev: &KeyboardEvent) {
+    let target: Element = event_target(ev);
+    let Some(table) = target.closest("[role=table]").ok().flatten() else {
+        return;
+    };
+    let Ok(list) = table.query_selector_all("[role=row][tabindex]") else {
+        return;
+    };
+    let rows: Vec<HtmlElement> = (0..list.length())
+        .filter_map(|i| list.item(i)?.dyn_into().ok())
+        .collect();
+    let current = rows.iter().position(|row| row.is_same_node(Some(&target)));
+
+    let next = match (ev.key().as_str(), current) {
+        ("ArrowDown", Some(i)) => i + 1,
+        ("ArrowDown", None) | ("Home", _) => 0,
+        ("ArrowUp", Some(i)) if i > 0 => i - 1,
+        ("End", _) => rows.len().saturating_sub(1),
+        ("Enter" | " ", Some(i)) => {
+            ev.prevent_default();
+            rows[i].click();
+            return;
+        }
+        _ => return,
+    };
+    if let Some(row) = rows.get(next) {
+        ev.prevent_default();
+        let _ = row.focus();
+    }
+}
+
+/// Index of the tab to select for an arrow/Home/End key press in a
+/// horizontal tablist (wraps around)
+pub fn tab_step(key: &str, current: usize, len: usize) -> Option<usize> {
+    match key {
+        "ArrowRight" => Some((current + 1) % len),
+        "ArrowLeft" => Some((current + len - 1) % len),
+        "Home" => Some(0),
+        "End" => Some(len - 1),
+        _ => None,
+    }
+}
+
+/// Did focus move out of `container` (rather than between its children)?
+pub fn focus_left(ev: &FocusEvent, container: &Element) -> bool {
+    let next = ev.related_target().and_then(|t| t.dyn_into::<Node>().ok());
+    !container.contains(next.as_ref())
+}
+
+/// Visually hidden live region reading out the last price at most every
+/// [`ANNOUNCE_INTERVAL_MS`], and only when it changed
+#[component]
+pub fn PriceAnnouncer(#[prop(into)] market: MarketState) -> impl IntoView {
+    let ticker = market.ticker;
+    let message = RwSignal::new(String::new());
+
+    let handle = set_interval_with_handle(
+        move || {
+            let Some(text) = ticker.with_untracked(|t| {
+                t.as_ref().map(|t| {
+                    let direction = if t.change_percent_24h >= 0.0 { "up" } else { "down" };
+                    format!(
+                        "{} {:.2}, {} {:.2}% on the day",
+                        t.symbol,
+                        t.last_price.as_f64(),
+                        direction,
+                        t.change_percent_24h.abs()
+                    )
+                })
+            }) else {
+                return;
+            };
+            if message.with_untracked(|m| *m != text) {
+                message.set(text);
+            }
+        },
+        std::time::Duration::from_millis(ANNOUNCE_INTERVAL_MS),
+    )
+    .ok();
+    on_cleanup(move || {
+        if let Some(handle) = handle {
+            handle.clear();
+        }
+    });
+
+    view! {
+        <div class="sr-only" role="status" aria-live="polite" aria-atomic="true">
+            {move || message.get()}
+        </div>
+    }
+}
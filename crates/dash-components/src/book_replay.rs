@@ -0,0 +1,123 @@
+//! Book replay: step back through the sampled book history
+//!
+//! Each step is one column of the heatmap's history (half a second apart,
+//! five minutes deep), shown as a short ladder with the levels that were
+//! added, pulled, grown or shrunk since the step before highlighted. Useful
+//! for seeing what the book did around a sudden wick.
+
+use dash_core::{format_time_ms, BookHistory, LevelChange, LevelDiff, BOOK_SAMPLE_MS};
+use dash_state::use_i18n;
+use leptos::prelude::*;
+
+/// Levels shown per side
+const REPLAY_DEPTH: usize = 10;
+
+/// Step controls over the book history with the diffed ladder below
+#[component]
+pub fn BookReplay(#[prop(into)] history: Signal<BookHistory>) -> impl IntoView {
+    let i18n = use_i18n();
+    // Timestamp of the column on screen; `None` follows the newest one
+    let pinned = RwSignal::new(None::<i64>);
+
+    let position = Memo::new(move |_| {
+        history.with(|h| {
+            let last = h.columns.len().checked_sub(1)?;
+            let index = match pinned.get() {
+                Some(time) => h.index_at(time)?,
+                None => last,
+            };
+            Some((index, last))
+        })
+    });
+    let diff = Memo::new(move |_| {
+        let (index, _) = position.get()?;
+        history.with(|h| h.diff_at(index, REPLAY_DEPTH))
+    });
+
+    let step = move |by: isize| {
+        let Some((index, last)) = position.get_untracked() else {
+            return;
+        };
+        let target = index.saturating_add_signed(by).min(last);
+        if target == last && by > 0 {
+            pinned.set(None);
+        } else {
+            pinned.set(history.with_untracked(|h| h.columns.get(target).map(|c| c.timestamp)));
+        }
+    };
+    let oldest = move |_| pinned.set(history.with_untracked(|h| h.columns.front().map(|c| c.timestamp)));
+
+    let status = move || {
+        let (index, last) = position.get()?;
+        let timestamp = diff.with(|d| d.as_ref().map(|d| d.timestamp))?;
+        let newest = history.with(|h| h.columns.back().map(|c| c.timestamp))?;
+        let behind = (newest - timestamp) as f64 / 1_000.0;
+        Some(if pinned.with(Option::is_none) {
+            format!("Live · {}", format_time_ms(timestamp))
+        } else {
+            format!("−{:.1}s · {} · {}/{}", behind, format_time_ms(timestamp), index + 1, last + 1)
+        })
+    };
+
+    let row = move |level: LevelDiff, side: &'static str| {
+        let delta = (level.change != LevelChange::Unchanged).then(|| i18n.signed(level.delta(), 4));
+        let class = format!("replay-row {} {}", side, level.change.css_class());
+        view! {
+            <div class=class role="row">
+                <span class="replay-price" role="cell">{i18n.number(level.price, 2)}</span>
+                <span class="replay-size" role="cell">{i18n.number(level.quantity, 4)}</span>
+                <span class="replay-delta" role="cell">{delta}</span>
+            </div>
+        }
+    };
+
+    view! {
+        <div
+            class="book-replay"
+            tabindex="0"
+            aria-label="Book replay"
+            on:keydown=move |ev| match ev.key().as_str() {
+                "ArrowLeft" => {
+                    ev.prevent_default();
+                    step(-1);
+                }
+                "ArrowRight" => {
+                    ev.prevent_default();
+                    step(1);
+                }
+                "End" => pinned.set(None),
+                _ => {}
+            }
+        >
+            <div class="panel-toolbar replay-toolbar">
+                <button class="sb-preset" title="Oldest snapshot" on:click=oldest>"⏮"</button>
+                <button class="sb-preset" title="Step back 5 seconds" on:click=move |_| step(-(5_000 / BOOK_SAMPLE_MS) as isize)>"−5s"</button>
+                <button class="sb-preset" title="Step back (←)" on:click=move |_| step(-1)>"◀"</button>
+                <button class="sb-preset" title="Step forward (→)" on:click=move |_| step(1)>"▶"</button>
+                <button
+                    class=move || if pinned.with(Option::is_none) { "sb-preset active" } else { "sb-preset" }
+                    title="Follow the live book (End)"
+                    on:click=move |_| pinned.set(None)
+                >
+                    "Live"
+                </button>
+                <span class="replay-status">{status}</span>
+            </div>
+            <div class="replay-table" role="table" aria-label="Book at the selected step">
+                {move || {
+                    diff.get().map(|diff| {
+                        view! {
+                            <div class="replay-asks" role="rowgroup" aria-label="Asks">
+                                {diff.asks.into_iter().rev().map(|l| row(l, "ask")).collect_view()}
+                            </div>
+                            <div class="replay-mid" role="separator" />
+                            <div class="replay-bids" role="rowgroup" aria-label="Bids">
+                                {diff.bids.into_iter().map(|l| row(l, "bid")).collect_view()}
+                            </div>
+                        }
+                    })
+                }}
+            </div>
+        </div>
+    }
+}
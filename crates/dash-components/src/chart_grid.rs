@@ -0,0 +1,184 @@
+//! Multi-chart grid: several symbols or timeframes side by side
+//!
+//! Each cell has its own symbol and timeframe picker; chart type, price
+//! scale and time zone follow the main chart's settings.
+
+use dash_charts::{CandlestickChart, CandlestickConfig};
+use dash_core::{CandleHistory, CandleInterval, Symbol, TickFormat};
+use dash_state::{use_app_state, GridChart, GridSize, Msg};
+use leptos::prelude::*;
+
+use crate::EmptyState;
+
+/// Bars shown per grid chart (fewer than the single chart, they're narrower)
+const GRID_VISIBLE_BARS: usize = 80;
+
+/// Grid size buttons plus the way back to the single chart
+#[component]
+pub fn ChartGridToggle() -> impl IntoView {
+    let state = use_app_state();
+    let ui = state.ui;
+    let current = move || ui.with(|ui| ui.chart_grid);
+
+    view! {
+        <span class="tf-bar" role="group" aria-label="Chart grid">
+            {
+                let state = state.clone();
+                view! {
+                    <button
+                        class=move || if current().is_none() { "sb-preset active" } else { "sb-preset" }
+                        title="Single chart"
+                        aria-pressed=move || current().is_none().to_string()
+                        on:click=move |_| state.set_chart_grid(None)
+                    >
+                        "1"
+                    </button>
+                }
+            }
+            {GridSize::all()
+                .iter()
+                .map(|&size| {
+                    let state = state.clone();
+                    view! {
+                        <button
+                            class=move || if current() == Some(size) { "sb-preset active" } else { "sb-preset" }
+                            title=format!("{} chart grid", size.label())
+                            aria-pressed=move || (current() == Some(size)).to_string()
+                            on:click=move |_| state.set_chart_grid(Some(size))
+                        >
+                            {size.label()}
+                        </button>
+                    }
+                })
+                .collect_view()}
+        </span>
+    }
+}
+
+/// Grid of candlestick charts in the chart panel
+#[component]
+pub fn ChartGrid(size: GridSize) -> impl IntoView {
+    view! {
+        <div
+            class="chart-grid"
+            style=format!(
+                "grid-template-columns: repeat({}, minmax(0, 1fr)); grid-template-rows: repeat({}, minmax(0, 1fr));",
+                size.columns(),
+                size.rows()
+            )
+        >
+            {(0..size.cells()).map(|index| view! { <GridChartCell index=index /> }).collect_view()}
+        </div>
+    }
+}
+
+/// One grid chart with its symbol and timeframe toolbar
+#[component]
+fn GridChartCell(index: usize) -> impl IntoView {
+    let state = use_app_state();
+    let grid = state.grid;
+    let ui = state.ui;
+    let directory = state.symbols;
+
+    let chart = Memo::new(move |_| grid.charts.with(|charts| charts.get(index).cloned()));
+    let candles = Memo::new(move |_| match chart.get() {
+        Some(chart) => grid.candles(&chart.symbol, chart.interval),
+        None => CandleHistory::new(Symbol::default(), CandleInterval::default()),
+    });
+    let chart_type = Signal::derive(move || ui.with(|ui| ui.chart_type));
+    let price_scale = Signal::derive(move || ui.with(|ui| ui.price_scale));
+    let time_zone = Signal::derive(move || ui.with(|ui| ui.time_zone));
+    let chart_backend = Memo::new(move |_| ui.with(|ui| ui.chart_backend));
+    let price_format = Memo::new(move |_| {
+        chart.with(|c| c.as_ref().map_or_else(TickFormat::default, |c| directory.tick_format(&c.symbol)))
+    });
+
+    let pick_state = state.clone();
+    let pick_symbol = move |ev: leptos::ev::Event| {
+        let Some(current) = chart.get_untracked() else {
+            return;
+        };
+        let symbol = Symbol::new(event_target_value(&ev));
+        pick_state.set_grid_chart(index, GridChart { symbol, ..current });
+    };
+    let interval_state = state.clone();
+    let pick_interval = move |ev: leptos::ev::Event| {
+        let Some(current) = chart.get_untracked() else {
+            return;
+        };
+        let value = event_target_value(&ev);
+        if let Some(&interval) = CandleInterval::all().iter().find(|i| i.label() == value) {
+            interval_state.set_grid_chart(index, GridChart { interval, ..current });
+        }
+    };
+    let focus_state = state.clone();
+
+    view! {
+        <div class="grid-chart">
+            <div class="grid-chart-toolbar">
+                <select
+                    class="ct-select"
+                    aria-label="Grid chart symbol"
+                    prop:value=move || chart.get().map(|c| c.symbol.to_string()).unwrap_or_default()
+                    on:change=pick_symbol
+                >
+                    {move || {
+                        let current = chart.get().map(|c| c.symbol);
+                        let mut symbols: Vec<Symbol> = directory.symbols.with(|list| list.iter().map(|i| i.symbol.clone()).collect());
+                        // Keep a saved symbol selectable before the directory loads
+                        if let Some(current) = current.filter(|c| !symbols.contains(c)) {
+                            symbols.insert(0, current);
+                        }
+                        symbols
+                            .into_iter()
+                            .map(|symbol| view! { <option value=symbol.to_string()>{symbol.to_string()}</option> })
+                            .collect_view()
+                    }}
+                </select>
+                <select
+                    class="ct-select"
+                    aria-label="Grid chart timeframe"
+                    prop:value=move || chart.get().map(|c| c.interval.label()).unwrap_or_default()
+                    on:change=pick_interval
+                >
+                    {CandleInterval::all()
+                        .iter()
+                        .map(|interval| view! { <option value=interval.label()>{interval.label()}</option> })
+                        .collect_view()}
+                </select>
+                <button
+                    class="sb-preset"
+                    title="Open on the single chart"
+                    on:click=move |_| focus_state.focus_grid_chart(index)
+                >
+                    "⤢"
+                </button>
+            </div>
+            <Show
+                when=move || candles.with(|c| !c.is_empty())
+                fallback=|| view! { <EmptyState message=Msg::WaitingCandles rows=4 /> }
+            >
+                {move || {
+                    let config = CandlestickConfig {
+                        width: 400.0,
+                        height: 240.0,
+                        show_tooltip: false,
+                        visible_bars: GRID_VISIBLE_BARS,
+                        backend: chart_backend.get(),
+                        price_format: price_format.get(),
+                        ..Default::default()
+                    };
+                    view! {
+                        <CandlestickChart
+                            candles=candles
+                            config=config
+                            chart_type=chart_type
+                            price_scale=price_scale
+                            time_zone=time_zone
+                        />
+                    }
+                }}
+            </Show>
+        </div>
+    }
+}
@@ -0,0 +1,210 @@
+//! Right-click context menus for tape rows, book levels and charts
+//!
+//! A single menu lives at the dashboard root. Rows and charts grab the
+//! [`ContextMenu`] handle from context and open it with the actions for
+//! whatever was clicked; clicking elsewhere, Escape, scrolling or resizing
+//! dismisses it.
+
+use dash_core::{TickFormat, Trade, TradeSide};
+use dash_state::{use_app_state, AppState};
+use leptos::{ev, prelude::*};
+
+/// Menu width in px (must match `.context-menu`)
+const MENU_WIDTH: f64 = 200.0;
+/// Item height in px (must match `.cm-item`)
+const ITEM_HEIGHT: f64 = 28.0;
+/// Title row plus padding in px
+const MENU_CHROME: f64 = 36.0;
+
+/// One clickable menu entry
+#[derive(Clone)]
+pub struct MenuItem {
+    pub label: String,
+    pub action: Callback<()>,
+}
+
+impl MenuItem {
+    pub fn new(label: impl Into<String>, action: impl Fn() + Send + Sync + 'static) -> Self {
+        Self {
+            label: label.into(),
+            action: Callback::new(move |()| action()),
+        }
+    }
+}
+
+#[derive(Clone)]
+struct OpenMenu {
+    x: f64,
+    y: f64,
+    title: String,
+    items: Vec<MenuItem>,
+}
+
+/// Handle to the dashboard's context menu
+#[derive(Clone, Copy)]
+pub struct ContextMenu {
+    open: RwSignal<Option<OpenMenu>>,
+    state: StoredValue<AppState>,
+}
+
+impl ContextMenu {
+    /// Open `items` at the pointer, replacing the browser menu
+    pub fn show(&self, ev: &ev::MouseEvent, title: impl Into<String>, items: Vec<MenuItem>) {
+        ev.prevent_default();
+        ev.stop_propagation();
+        let (x, y) = menu_position(f64::from(ev.client_x()), f64::from(ev.client_y()), items.len());
+        self.open.set(Some(OpenMenu {
+            x,
+            y,
+            title: title.into(),
+            items,
+        }));
+    }
+
+    pub fn close(&self) {
+        if self.open.with_untracked(Option::is_some) {
+            self.open.set(None);
+        }
+    }
+
+    /// Actions for a price: copy, alert, and a limit ticket on either side
+    pub fn show_price(&self, ev: &ev::MouseEvent, price: f64) {
+        let items = self.price_items(price);
+        self.show(ev, self.format_price(price), items);
+    }
+
+    /// Price actions plus tape filtering by the trade's size
+    pub fn show_trade(&self, ev: &ev::MouseEvent, trade: &Trade) {
+        let price = trade.price.as_f64();
+        let size = trade.quantity.as_f64();
+        let state = self.state.get_value();
+        let mut items = self.price_items(price);
+
+        let tape = state.tape_min_size;
+        let size_text = self.tick_format().quantity(size);
+        items.push(MenuItem::new(format!("Filter tape to ≥ {}", size_text), move || tape.set(Some(size))));
+        if tape.with_untracked(Option::is_some) {
+            items.push(MenuItem::new("Clear tape filter", move || tape.set(None)));
+        }
+        let title = format!("{} {} @ {}", trade.side.label(), size_text, self.format_price(price));
+        self.show(ev, title, items);
+    }
+
+    fn price_items(&self, price: f64) -> Vec<MenuItem> {
+        let text = self.format_price(price);
+        let (alert_state, buy_state, sell_state) = self.state.with_value(|s| (s.clone(), s.clone(), s.clone()));
+        let copied = text.clone();
+        vec![
+            MenuItem::new("Copy price", move || copy_to_clipboard(&copied)),
+            MenuItem::new(format!("Set alert at {}", text), move || alert_state.add_price_alert(price)),
+            MenuItem::new(format!("Buy limit at {}", text), move || {
+                buy_state.fill_ticket(TradeSide::Buy, price, false)
+            }),
+            MenuItem::new(format!("Sell limit at {}", text), move || {
+                sell_state.fill_ticket(TradeSide::Sell, price, false)
+            }),
+        ]
+    }
+
+    /// Precision of the active symbol
+    fn tick_format(&self) -> TickFormat {
+        self.state.with_value(|s| s.market.symbol.with_untracked(|symbol| s.symbols.tick_format(symbol)))
+    }
+
+    /// Price at the active symbol's tick precision
+    fn format_price(&self, price: f64) -> String {
+        self.tick_format().price(price)
+    }
+}
+
+/// Create the dashboard's context menu and make it available to children
+pub fn provide_context_menu() -> ContextMenu {
+    let menu = ContextMenu {
+        open: RwSignal::new(None),
+        state: StoredValue::new(use_app_state()),
+    };
+    provide_context(menu);
+    menu
+}
+
+/// The dashboard's context menu (`None` outside the dashboard)
+pub fn use_context_menu() -> Option<ContextMenu> {
+    use_context::<ContextMenu>()
+}
+
+/// Top-left corner for a menu opened at (`x`, `y`), flipped so it stays
+/// inside the viewport
+fn menu_position(x: f64, y: f64, item_count: usize) -> (f64, f64) {
+    let (width, height) = web_sys::window()
+        .map(|w| {
+            let size = |v: Result<wasm_bindgen::JsValue, _>| v.ok().and_then(|v| v.as_f64()).unwrap_or(f64::MAX);
+            (size(w.inner_width()), size(w.inner_height()))
+        })
+        .unwrap_or((f64::MAX, f64::MAX));
+    let menu_height = MENU_CHROME + item_count as f64 * ITEM_HEIGHT;
+    let x = if x + MENU_WIDTH > width { x - MENU_WIDTH } else { x };
+    let y = if y + menu_height > height { y - menu_height } else { y };
+    (x.max(0.0), y.max(0.0))
+}
+
+fn copy_to_clipboard(text: &str) {
+    if let Some(window) = web_sys::window() {
+        // Fire and forget; the promise only rejects without clipboard permission
+        let _ = window.navigator().clipboard().write_text(text);
+    }
+}
+
+/// Renders the open menu and dismisses it on outside clicks, Escape,
+/// scrolling, resizing or losing focus
+#[component]
+pub fn ContextMenuHost() -> impl IntoView {
+    let menu = use_context_menu()?;
+
+    let handles = vec![
+        window_event_listener(ev::mousedown, move |_| menu.close()),
+        window_event_listener(ev::wheel, move |_| menu.close()),
+        window_event_listener(ev::resize, move |_| menu.close()),
+        window_event_listener(ev::blur, move |_| menu.close()),
+        window_event_listener(ev::keydown, move |ev| {
+            if ev.key() == "Escape" {
+                menu.close();
+            }
+        }),
+    ];
+    on_cleanup(move || handles.into_iter().for_each(|h| h.remove()));
+
+    Some(move || {
+        menu.open.get().map(|open| {
+            view! {
+                <div
+                    class="context-menu"
+                    role="menu"
+                    style=format!("left: {}px; top: {}px", open.x, open.y)
+                    on:mousedown=|ev| ev.stop_propagation()
+                    on:contextmenu=|ev| ev.prevent_default()
+                >
+                    <div class="cm-title">{open.title}</div>
+                    {open
+                        .items
+                        .into_iter()
+                        .map(|item| {
+                            let action = item.action;
+                            view! {
+                                <button
+                                    class="cm-item"
+                                    role="menuitem"
+                                    on:click=move |_| {
+                                        menu.close();
+                                        action.run(());
+                                    }
+                                >
+                                    {item.label}
+                                </button>
+                            }
+                        })
+                        .collect_view()}
+                </div>
+            }
+        })
+    })
+}
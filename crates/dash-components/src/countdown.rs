@@ -0,0 +1,28 @@
+//! Time left in the forming candle
+
+use dash_core::format_countdown;
+use dash_state::use_app_state;
+use leptos::prelude::*;
+
+/// Remaining time in the forming candle of the selected interval, ticking
+/// with the freshness clock
+pub fn use_candle_countdown() -> Signal<String> {
+    let market = use_app_state().market;
+    let now = market.freshness.now;
+    let interval = market.interval;
+    Signal::derive(move || format_countdown(interval.get().remaining_ms(now.get())))
+}
+
+/// Interval label plus countdown to the candle close
+#[component]
+pub fn CandleCountdown() -> impl IntoView {
+    let interval = use_app_state().market.interval;
+    let countdown = use_candle_countdown();
+
+    view! {
+        <span class="candle-countdown" title="Time until the current candle closes">
+            <span class="cc-interval">{move || interval.get().label()}</span>
+            <span class="cc-time">{countdown}</span>
+        </span>
+    }
+}
@@ -1,10 +1,37 @@
 //! Main dashboard layout component
 
-use dash_charts::{CandlestickChart, DepthChart};
-use dash_state::use_app_state;
+use dash_charts::{AnnotationKind, BookHeatmap, CandlestickChart, CandlestickConfig, ChartAnnotations, DepthChart, DepthChartConfig, DrawingEdit, DrawingToolbar, FillMarker, HeatmapConfig, OrderLine, PositionLine, TradePrint,
+    TradingMarkers, MAX_TRADE_PRINTS};
+use dash_core::{colors, export_candles, export_cvd, export_depth, format_time_ms, Candle, DrawingTool, ExportFormat, FUNDING_INTERVAL_MS, ProfileRange, SourceState, TradeSide, ValueThresholdClassifier, TAPE_GROUP_WINDOWS};
+use dash_state::{
+    grid_cell, use_app_state, use_tick_format, CompactTab, DashboardLayout, DragMode, Msg, NotificationSource, Panel, PanelDrag, PanelLayout,
+    Severity, ShortcutAction, StreamChannel, UiState,
+};
 use leptos::prelude::*;
+use wasm_bindgen::JsCast;
 
-use crate::{OrderBook, TickerBar, TradeHistory};
+use crate::{
+    provide_context_menu, tab_step, BookReplay, use_candle_countdown, use_keyboard_shortcuts, use_responsive_layout, use_sound_alerts, AccountPanel,
+    CandleCountdown, ChartGrid, ChartGridToggle, ChartTypeSelect, CompareSelect, ContextMenuHost, DerivativesBar, EmptyState, ExportButtons, GuidedTour, LiquidationsFeed, MiniTickerStrip, MuteToggle, OrderBook,
+    OrderEntry, OverlayMenu, PositionsPanel, PriceAnnouncer, PriceLadder, PriceScaleToggle, SettingsDialog, ShareButton, ShareTarget, ShortcutCheatSheet, TelemetryReadout, TickerBar, TimeframeBar, TimeZoneToggle, ToastHost, TradeFlowStats, TradeHistory,
+    WhaleAlertStream, WhaleToggle,
+};
+
+/// What the depth panel is showing
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum DepthView {
+    Curve,
+    Heatmap,
+    Replay,
+}
+
+/// What the trades panel is showing
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum TapeView {
+    Trades,
+    Liquidations,
+    Whales,
+}
 
 #[component]
 pub fn Dashboard() -> impl IntoView {
@@ -13,94 +40,996 @@ pub fn Dashboard() -> impl IntoView {
     // Extract signals for charts
     let candles = state.market.candles;
     let depth = state.market.depth;
+    let orderbook = state.market.orderbook;
+    let book_history = state.market.book_history;
+    let trades = state.market.trades;
     let connection = state.connection;
+    let ui = state.ui;
+    let cursor = state.cursor;
+    let indicators = state.market.indicators;
+    let (book_market, trades_market) = (state.market.clone(), state.market.clone());
+    let liquidations_market = state.market.clone();
+    let (positions_portfolio, account_portfolio) = (state.portfolio.clone(), state.portfolio.clone());
+
+    // Panel being dragged/resized; geometry is only committed on release
+    let drag = RwSignal::new(None::<PanelDrag>);
+    let grid = NodeRef::<leptos::html::Main>::new();
+
+    let move_handle = window_event_listener(leptos::ev::pointermove, move |ev| {
+        if drag.with_untracked(Option::is_none) {
+            return;
+        }
+        if let Some(cell) = pointer_cell(grid, ev.client_x(), ev.client_y())
+            && drag.with_untracked(|d| d.is_some_and(|d| d.current != cell))
+        {
+            drag.update(|d| {
+                if let Some(d) = d {
+                    d.current = cell;
+                }
+            });
+        }
+    });
+    let drop_state = state.clone();
+    let up_handle = window_event_listener(leptos::ev::pointerup, move |_| {
+        if let Some(finished) = drag.get_untracked() {
+            drop_state.apply_drag(&finished);
+            drag.set(None);
+        }
+    });
+    on_cleanup(move || {
+        move_handle.remove();
+        up_handle.remove();
+    });
+
+    use_keyboard_shortcuts();
+    use_sound_alerts();
+    use_responsive_layout();
+    let context_menu = provide_context_menu();
+    let tape_min_size = state.tape_min_size;
+    let tape_group = state.tape_group_window;
+    let popout = state.popout;
+
+    // Dim panels whose stream has gone quiet instead of showing old numbers silently
+    let freshness = state.market.freshness;
+    let ticker_stale = freshness.is_stale(StreamChannel::Ticker);
+    let tab_state = state.clone();
+    let keys_state = state.clone();
+    let select_tab = Callback::new(move |tab: CompactTab| keys_state.set_compact_tab(tab));
+    let tabs_ref = NodeRef::<leptos::html::Nav>::new();
+    let announcer_market = state.market.clone();
+    let ticket_state = state.clone();
+    let price_click = Callback::new(move |(side, price, modifier): (TradeSide, f64, bool)| {
+        ticket_state.fill_ticket(side, price, modifier)
+    });
+    // Order book panel shows the stacked book or the DOM ladder
+    let ladder_view = RwSignal::new(false);
+    // Depth panel shows the depth curve, the book heatmap or the book replay
+    let depth_view = RwSignal::new(DepthView::Curve);
+    let toggle_depth_view = move |view: DepthView| {
+        depth_view.update(|v| *v = if *v == view { DepthView::Curve } else { view });
+    };
+    // Market order size swept through the depth curve for a slippage estimate
+    let depth_order_size = RwSignal::new(None::<f64>);
+    // Trades panel shows the tape, the liquidation feed or whale alerts
+    let tape_view = RwSignal::new(TapeView::Trades);
+    let toggle_tape_view = move |view: TapeView| {
+        tape_view.update(|v| *v = if *v == view { TapeView::Trades } else { view });
+    };
+    let whales = state.market.whales;
+    let directory = state.symbols;
+    let watchlist = Signal::derive(move || directory.watchlist_tickers());
+    // Volume profile drawn on the chart (`None` = hidden)
+    let profile_range = RwSignal::new(None::<ProfileRange>);
+    let profile_market = state.market.clone();
+    let countdown = use_candle_countdown();
+    let profile = Memo::new(move |_| profile_range.get().and_then(|range| profile_market.volume_profile(range)));
+    let chart_overlays = state.chart_overlays;
+    let show_volume = Memo::new(move |_| !ui.with(|ui| ui.hide_volume));
+    let volume_state = state.clone();
+    let chart_type = Memo::new(move |_| ui.with(|ui| ui.chart_type));
+    // Renko and range bars stand in for the candles; the studies lined up
+    // with candle times (VWAP, CVD) sit those out
+    let price_driven = Memo::new(move |_| chart_type.get().is_price_driven());
+    let bricks_market = state.market.clone();
+    let chart_candles = Memo::new(move |_| {
+        if price_driven.get() {
+            bricks_market.brick_series(chart_type.get(), ui.with(|ui| ui.brick_size))
+        } else {
+            candles.get()
+        }
+    });
+    let chart_backend = Memo::new(move |_| ui.with(|ui| ui.chart_backend));
+    let tick_format = use_tick_format();
+    let chart_grid = Memo::new(move |_| ui.with(|ui| ui.chart_grid));
+    let price_scale = Memo::new(move |_| ui.with(|ui| ui.price_scale));
+    let time_zone = Memo::new(move |_| ui.with(|ui| ui.time_zone));
+    let fit_indicators = Memo::new(move |_| ui.with(|ui| ui.fit_indicators));
+    let show_vwap = Memo::new(move |_| ui.with(|ui| ui.show_vwap));
+    let vwap_market = state.market.clone();
+    let vwap = Memo::new(move |_| if show_vwap.get() && !price_driven.get() { vwap_market.vwap_series() } else { Vec::new() });
+    let vwap_state = state.clone();
+    let tick_market = state.market.clone();
+    let last_tick = Memo::new(move |_| tick_market.last_tick().map(|(price, direction)| (price, direction.color())));
+    let show_quotes = Memo::new(move |_| ui.with(|ui| ui.show_quotes));
+    let show_tooltip = Memo::new(move |_| !ui.with(|ui| ui.hide_tooltip));
+    let quotes_market = state.market.clone();
+    let quotes = Memo::new(move |_| if show_quotes.get() { quotes_market.best_quotes() } else { None });
+    let quotes_state = state.clone();
+    // Trade dots use the tape's size classes so whales match across panels
+    let show_prints = Memo::new(move |_| ui.with(|ui| ui.show_prints));
+    let prints_trades = state.market.trades;
+    let prints = Memo::new(move |_| {
+        if !show_prints.get() {
+            return Vec::new();
+        }
+        let classifier = ValueThresholdClassifier::default();
+        prints_trades.with(|trades| {
+            trades
+                .iter()
+                .take(MAX_TRADE_PRINTS)
+                .map(|trade| TradePrint::from_trade(trade, &classifier))
+                .collect()
+        })
+    });
+    let prints_state = state.clone();
+    // Per-candle delta from the trade flow for the CVD pane
+    let show_cvd = Memo::new(move |_| ui.with(|ui| ui.show_cvd));
+    let cvd_indicators = state.market.indicators;
+    let cvd_pane = Memo::new(move |_| show_cvd.get() && !price_driven.get());
+    let cvd = Memo::new(move |_| {
+        if !cvd_pane.get() {
+            return Vec::new();
+        }
+        candles.with(|history| cvd_indicators.candle_deltas(&history.candles))
+    });
+    let cvd_state = state.clone();
+    // Data behind the chart and depth panels for download
+    let candles_export = Callback::new(move |format: ExportFormat| {
+        chart_candles.with_untracked(|history| (!history.is_empty()).then(|| export_candles(&history.candles, format)))
+    });
+    let cvd_export = Callback::new(move |format: ExportFormat| {
+        let deltas = cvd.get_untracked();
+        (!deltas.is_empty()).then(|| chart_candles.with_untracked(|history| export_cvd(&history.candles, &deltas, format)))
+    });
+    let depth_export = Callback::new(move |format: ExportFormat| {
+        depth.with_untracked(|depth| depth.as_ref().map(|depth| export_depth(depth, format)))
+    });
+    // Paper orders, fills and position in the charted symbol; dragging an
+    // order line amends it
+    let portfolio = state.portfolio.clone();
+    let trading_symbol = state.market.symbol;
+    let trading = Memo::new(move |_| {
+        let symbol = trading_symbol.get();
+        TradingMarkers {
+            orders: portfolio.orders.with(|orders| {
+                orders.iter().filter(|o| o.symbol == symbol).filter_map(OrderLine::from_order).collect()
+            }),
+            fills: portfolio.fills.with(|fills| {
+                fills.iter().filter(|o| o.symbol == symbol).filter_map(FillMarker::from_order).collect()
+            }),
+            position: portfolio.positions.with(|positions| {
+                positions.iter().find(|p| p.symbol == symbol).and_then(PositionLine::from_position)
+            }),
+        }
+    });
+    let amend_state = state.clone();
+    let amend_order = Callback::new(move |(order_id, price): (String, f64)| {
+        if let Err(reason) = amend_state.amend_order(&order_id, price) {
+            amend_state.notify(Severity::Warning, NotificationSource::System, format!("Order not moved: {}", reason));
+        }
+    });
+    // Second symbol overlaid on the chart, hidden while it's the one charted
+    let compare = state.compare;
+    let (compare_symbol, compare_interval) = (state.market.symbol, state.market.interval);
+    let compare_candles = Memo::new(move |_| {
+        let other = compare.symbol.get().filter(|s| *s != compare_symbol.get())?;
+        let history = compare.candles(compare_interval.get());
+        (history.symbol == other).then_some(history)
+    });
+    // Annotations: where the charted symbol's alerts fired and its last
+    // funding time
+    let annotations = ChartAnnotations::new();
+    let (fired_alerts, annotated_symbol) = (state.fired_alerts, state.market.symbol);
+    let alert_marks = StoredValue::new(Vec::<u64>::new());
+    Effect::new(move |_| {
+        let symbol = annotated_symbol.get();
+        alert_marks.get_value().into_iter().for_each(|id| annotations.remove(id));
+        let marks = fired_alerts.with(|fired| {
+            fired
+                .iter()
+                .filter(|f| f.alert.symbol == symbol)
+                .map(|f| annotations.marker(f.time, f.price, format!("Alert {}", f.alert.label())))
+                .collect()
+        });
+        alert_marks.set_value(marks);
+    });
+    let derivatives = state.market.derivatives;
+    let toxicity = state.market.toxicity;
+    let funding_mark = StoredValue::new(None::<(u64, i64)>);
+    Effect::new(move |_| {
+        let last_funding = derivatives.with(|d| d.as_ref().map(|d| d.next_funding_time - FUNDING_INTERVAL_MS));
+        match (last_funding, funding_mark.get_value()) {
+            (Some(time), Some((_, marked))) if time == marked => {}
+            (Some(time), Some((id, _))) => {
+                annotations.set_kind(id, AnnotationKind::EventLine { time });
+                funding_mark.set_value(Some((id, time)));
+            }
+            (Some(time), None) => funding_mark.set_value(Some((annotations.event_line(time, "Funding"), time))),
+            (None, Some((id, _))) => {
+                annotations.remove(id);
+                funding_mark.set_value(None);
+            }
+            (None, None) => {}
+        }
+    });
+    let history_market = state.market.clone();
+    let load_history = Callback::new(move |_: ()| history_market.extend_history());
+    // Drawing tool picked in the chart header and the drawing selected on
+    // the chart (cleared with the symbol)
+    let drawing_tool = RwSignal::new(None::<DrawingTool>);
+    let selected_drawing = RwSignal::new(None::<u64>);
+    let drawing_store = state.drawings;
+    let drawing_symbol = state.market.symbol;
+    let chart_drawings = Memo::new(move |_| drawing_store.for_symbol(&drawing_symbol.get()));
+    Effect::new(move |_| {
+        drawing_symbol.track();
+        selected_drawing.set(None);
+    });
+    let drawing_state = state.clone();
+    let on_drawing = Callback::new(move |edit: DrawingEdit| match edit {
+        DrawingEdit::Added(drawing) => {
+            selected_drawing.set(Some(drawing_state.add_drawing(drawing)));
+            drawing_tool.set(None);
+        }
+        DrawingEdit::Moved(drawing) => drawing_state.update_drawing(drawing),
+    });
+    let delete_state = state.clone();
+    let delete_drawing = Callback::new(move |_: ()| {
+        if let Some(id) = selected_drawing.get_untracked() {
+            delete_state.remove_drawing(id);
+            selected_drawing.set(None);
+        }
+    });
+    let clear_state = state.clone();
+    let clear_drawings = Callback::new(move |_: ()| {
+        clear_state.clear_drawings();
+        selected_drawing.set(None);
+    });
 
     view! {
-        <div class="dashboard">
-            <header class="dash-header">
+        <div class=move || {
+            let dragging = if drag.with(Option::is_some) { " dragging" } else { "" };
+            let compact = if ui.with(UiState::is_compact) { " compact" } else { "" };
+            let popout = if popout.with(Option::is_some) { " popout" } else { "" };
+            let a11y = if ui.with(|ui| ui.accessible_mode) { " a11y" } else { "" };
+            let ui = ui.get();
+            format!(
+                "dashboard {} {}{}{}{}{}",
+                ui.theme.css_class(),
+                ui.breakpoint.css_class(),
+                compact,
+                dragging,
+                popout,
+                a11y
+            )
+        }>
+            <MiniTickerStrip tickers=watchlist />
+            <header class=move || {
+                if ticker_stale.get() { "dash-header stale" } else { "dash-header" }
+            }>
                 <TickerBar
                     market=state.market.clone()
                     connection=connection
                 />
+                <DerivativesBar />
+                <FeedBanner />
             </header>
 
-            <main class="dash-main">
-                <aside class="dash-sidebar left">
-                    <div class="panel">
-                        <div class="panel-header">
-                            <span class="panel-title">"Order Book"</span>
-                        </div>
-                        <div class="panel-content">
-                            <OrderBook market=state.market.clone() />
-                        </div>
-                    </div>
-                </aside>
-
-                <section class="dash-center">
-                    <div class="panel chart-container">
-                        <div class="panel-header">
-                            <span class="panel-title">"Chart"</span>
-                        </div>
-                        <div class="panel-content">
-                            <CandlestickChart candles=candles />
-                        </div>
-                    </div>
+            <Show when=move || ui.with(|ui| ui.accessible_mode)>
+                <PriceAnnouncer market=announcer_market.clone() />
+            </Show>
 
-                    <div class="panel depth-container">
-                        <div class="panel-header">
-                            <span class="panel-title">"Market Depth"</span>
-                        </div>
-                        <div class="panel-content">
-                            <DepthChart depth=depth />
-                        </div>
-                    </div>
-                </section>
-
-                <aside class="dash-sidebar right">
-                    <div class="panel">
-                        <div class="panel-header">
-                            <span class="panel-title">"Recent Trades"</span>
-                        </div>
-                        <div class="panel-content">
-                            <TradeHistory market=state.market.clone() />
-                        </div>
-                    </div>
-                </aside>
+            <Show when=move || ui.with(UiState::is_compact)>
+                <nav
+                    class="tab-headers compact-tabs"
+                    role="tablist"
+                    aria-label="Panels"
+                    node_ref=tabs_ref
+                    on:keydown=move |ev| {
+                        let tabs = CompactTab::all();
+                        let current = ui.with_untracked(|ui| tabs.iter().position(|&t| t == ui.compact_tab)).unwrap_or(0);
+                        let Some(next) = tab_step(&ev.key(), current, tabs.len()) else {
+                            return;
+                        };
+                        ev.prevent_default();
+                        select_tab.run(tabs[next]);
+                        // Roving focus follows the selection
+                        if let Some(nav) = tabs_ref.get_untracked()
+                            && let Ok(buttons) = nav.query_selector_all("[role=tab]")
+                            && let Some(button) = buttons.item(next as u32)
+                            && let Ok(button) = button.dyn_into::<leptos::web_sys::HtmlElement>()
+                        {
+                            let _ = button.focus();
+                        }
+                    }
+                >
+                    {CompactTab::all()
+                        .iter()
+                        .enumerate()
+                        .map(|(i, &tab)| {
+                            let state = tab_state.clone();
+                            let selected = move || ui.with(|ui| ui.compact_tab) == tab;
+                            view! {
+                                <button
+                                    class=move || if selected() { "tab-header active" } else { "tab-header" }
+                                    role="tab"
+                                    aria-selected=move || selected().to_string()
+                                    tabindex=move || if selected() { "0" } else { "-1" }
+                                    on:click=move |_| state.set_compact_tab(tab)
+                                >
+                                    <kbd class="tab-key">{i + 1}</kbd>
+                                    {tab.label()}
+                                </button>
+                            }
+                        })
+                        .collect_view()}
+                </nav>
+            </Show>
+
+            <main class="dash-main" node_ref=grid>
+                <GridPanel
+                    panel=Panel::OrderBook
+                    class="orderbook-container"
+                    title=Msg::OrderBook
+                    drag=drag
+                    grid=grid
+                    meta=move || view! {
+                        <ShareButton target=ShareTarget::OrderBook />
+                        <button
+                            class=move || if ladder_view.get() { "sb-preset active" } else { "sb-preset" }
+                            title="Switch between the stacked book and the DOM ladder"
+                            on:click=move |_| ladder_view.update(|v| *v = !*v)
+                        >
+                            "Ladder"
+                        </button>
+                    }
+                >
+                    <Show
+                        when=move || orderbook.with(Option::is_some)
+                        fallback=|| view! { <EmptyState message=Msg::WaitingBook /> }
+                    >
+                        {
+                            let market = book_market.clone();
+                            view! {
+                                <Show
+                                    when=move || ladder_view.get()
+                                    fallback=move || view! {
+                                        <OrderBook market=market.clone() cursor=cursor on_price_click=price_click />
+                                    }
+                                >
+                                    <PriceLadder on_price_click=price_click />
+                                </Show>
+                            }
+                        }
+                    </Show>
+                </GridPanel>
+
+                <GridPanel
+                    panel=Panel::CandleChart
+                    class="chart-container"
+                    title=Msg::Chart
+                    drag=drag
+                    grid=grid
+                    meta=move || view! {
+                        <ChartGridToggle />
+                        <TimeframeBar />
+                        <ChartTypeSelect />
+                        <CompareSelect />
+                        <PriceScaleToggle />
+                        <TimeZoneToggle />
+                        <DrawingToolbar
+                            tool=drawing_tool
+                            selected=selected_drawing
+                            on_delete=delete_drawing
+                            on_clear=clear_drawings
+                        />
+                        <OverlayMenu />
+                        <CandleCountdown />
+                        <ShareButton target=ShareTarget::Chart />
+                        <ExportButtons kind="candles" render=candles_export />
+                        {move || cvd_pane.get().then(|| view! { <ExportButtons kind="cvd" render=cvd_export /> })}
+                        <button
+                            class=move || if show_volume.get() { "sb-preset active" } else { "sb-preset" }
+                            title="Show volume bars under the candles"
+                            on:click={
+                                let volume_state = volume_state.clone();
+                                move |_| volume_state.toggle_volume_pane()
+                            }
+                        >
+                            "Vol"
+                        </button>
+                        <button
+                            class=move || if show_vwap.get() { "sb-preset active" } else { "sb-preset" }
+                            title="Session VWAP with ±1σ and ±2σ bands"
+                            on:click={
+                                let vwap_state = vwap_state.clone();
+                                move |_| vwap_state.toggle_vwap()
+                            }
+                        >
+                            "VWAP"
+                        </button>
+                        <button
+                            class=move || if show_quotes.get() { "sb-preset active" } else { "sb-preset" }
+                            title="Best bid and ask lines"
+                            on:click={
+                                let quotes_state = quotes_state.clone();
+                                move |_| quotes_state.toggle_quotes()
+                            }
+                        >
+                            "B/A"
+                        </button>
+                        <button
+                            class=move || if show_prints.get() { "sb-preset active" } else { "sb-preset" }
+                            title="Recent trades as dots sized by notional, whales ringed"
+                            on:click={
+                                let prints_state = prints_state.clone();
+                                move |_| prints_state.toggle_prints()
+                            }
+                        >
+                            "Prints"
+                        </button>
+                        <button
+                            class=move || if show_cvd.get() { "sb-preset active" } else { "sb-preset" }
+                            title="Cumulative volume delta pane under the chart"
+                            on:click={
+                                let cvd_state = cvd_state.clone();
+                                move |_| cvd_state.toggle_cvd()
+                            }
+                        >
+                            "CVD"
+                        </button>
+                        <span class="vp-modes" title="Volume profile">
+                            "VP"
+                            {ProfileRange::all()
+                                .iter()
+                                .map(|&range| view! {
+                                    <button
+                                        class=move || {
+                                            if profile_range.get() == Some(range) { "sb-preset active" } else { "sb-preset" }
+                                        }
+                                        on:click=move |_| profile_range.update(|current| {
+                                            *current = (*current != Some(range)).then_some(range);
+                                        })
+                                    >
+                                        {range.label()}
+                                    </button>
+                                })
+                                .collect_view()}
+                        </span>
+                    }
+                >
+                    <Show
+                        when=move || chart_grid.get().is_none()
+                        fallback=move || chart_grid.get().map(|size| view! { <ChartGrid size=size /> })
+                    >
+                        <Show
+                            when=move || candles.with(|c| !c.is_empty())
+                            fallback=|| view! { <EmptyState message=Msg::WaitingCandles rows=10 /> }
+                        >
+                            // Panes and renderer are fixed per chart, so changing either rebuilds it
+                            {move || {
+                                let config = CandlestickConfig {
+                                    show_volume: show_volume.get(),
+                                    backend: chart_backend.get(),
+                                    show_tooltip: show_tooltip.get(),
+                                    show_cvd: cvd_pane.get(),
+                                    price_format: tick_format.get(),
+                                    ..Default::default()
+                                };
+                                view! {
+                                    <CandlestickChart
+                                        candles=chart_candles
+                                        config=config
+                                        on_hover=Callback::new(move |candle: Option<Candle>| {
+                                            cursor.hover_candle(candle.as_ref())
+                                        })
+                                        profile=profile
+                                        countdown=countdown
+                                        overlays=chart_overlays
+                                        vwap=vwap
+                                        chart_type=chart_type
+                                        price_scale=price_scale
+                                        fit_overlays=fit_indicators
+                                        last_price=last_tick
+                                        quotes=quotes
+                                        on_scroll_back=load_history
+                                        drawings=chart_drawings
+                                        drawing_tool=drawing_tool
+                                        selected_drawing=selected_drawing
+                                        on_drawing=on_drawing
+                                        prints=prints
+                                        trading=trading
+                                        on_amend_order=amend_order
+                                        compare=compare_candles
+                                        cvd=cvd
+                                        time_zone=time_zone
+                                        annotations=annotations.items()
+                                        on_context_menu=Callback::new(move |(ev, price): (leptos::ev::MouseEvent, f64)| {
+                                            context_menu.show_price(&ev, price)
+                                        })
+                                    />
+                                }
+                            }}
+                        </Show>
+                    </Show>
+                </GridPanel>
+
+                <GridPanel
+                    panel=Panel::DepthChart
+                    class="depth-container"
+                    title=Msg::MarketDepth
+                    drag=drag
+                    grid=grid
+                    meta=move || view! {
+                        <button
+                            class=move || if depth_view.get() == DepthView::Heatmap { "sb-preset active" } else { "sb-preset" }
+                            title="Switch between the depth curve and the book heatmap"
+                            on:click=move |_| toggle_depth_view(DepthView::Heatmap)
+                        >
+                            "Heatmap"
+                        </button>
+                        <button
+                            class=move || if depth_view.get() == DepthView::Replay { "sb-preset active" } else { "sb-preset" }
+                            title="Step back through recent book snapshots"
+                            on:click=move |_| toggle_depth_view(DepthView::Replay)
+                        >
+                            "Replay"
+                        </button>
+                        <input
+                            class="ct-select depth-size-input"
+                            type="number"
+                            min="0"
+                            step="any"
+                            placeholder="Size"
+                            title="Order size to estimate market fills and slippage for"
+                            aria-label="Slippage order size"
+                            on:input=move |ev| {
+                                let size = event_target_value(&ev).parse::<f64>().ok().filter(|s| *s > 0.0);
+                                depth_order_size.set(size);
+                            }
+                        />
+                        <ShareButton target=ShareTarget::Depth />
+                        <ExportButtons kind="depth" render=depth_export />
+                    }
+                >
+                    <Show
+                        when=move || depth.with(Option::is_some)
+                        fallback=|| view! { <EmptyState message=Msg::WaitingDepth /> }
+                    >
+                        {move || match depth_view.get() {
+                            DepthView::Curve => view! {
+                                <DepthChart
+                                    depth=depth
+                                    config=DepthChartConfig { price_format: tick_format.get(), ..Default::default() }
+                                    highlight_price=cursor.hovered_price
+                                    order_size=depth_order_size
+                                />
+                            }
+                            .into_any(),
+                            DepthView::Heatmap => {
+                                let config = HeatmapConfig {
+                                    backend: chart_backend.get(),
+                                    price_format: tick_format.get(),
+                                    ..Default::default()
+                                };
+                                view! { <BookHeatmap history=book_history trades=trades config=config /> }.into_any()
+                            }
+                            DepthView::Replay => view! { <BookReplay history=book_history /> }.into_any(),
+                        }}
+                    </Show>
+                </GridPanel>
+
+                <GridPanel
+                    panel=Panel::Trades
+                    class="trades-container"
+                    title=Msg::RecentTrades
+                    drag=drag
+                    grid=grid
+                    meta=move || view! {
+                        <TradeFlowStats indicators=indicators toxicity=toxicity />
+                        <button
+                            class=move || if tape_view.get() == TapeView::Liquidations { "sb-preset active" } else { "sb-preset" }
+                            title="Switch between the trade tape and the liquidation feed"
+                            on:click=move |_| toggle_tape_view(TapeView::Liquidations)
+                        >
+                            "Liqs"
+                        </button>
+                        <button
+                            class=move || if tape_group.with(Option::is_some) { "sb-preset active" } else { "sb-preset" }
+                            title="Merge consecutive trades at the same price and side"
+                            on:click=move |_| tape_group.update(|window| *window = next_group_window(*window))
+                        >
+                            {move || match tape_group.get() {
+                                Some(ms) if ms >= 1_000 => format!("Group {}s", ms / 1_000),
+                                Some(ms) => format!("Group {}ms", ms),
+                                None => "Group".to_string(),
+                            }}
+                        </button>
+                        <WhaleToggle
+                            whales=whales
+                            active=Signal::derive(move || tape_view.get() == TapeView::Whales)
+                            on_toggle=Callback::new(move |_| toggle_tape_view(TapeView::Whales))
+                        />
+                    }
+                >
+                    {move || match tape_view.get() {
+                        TapeView::Trades => {
+                            let market = trades_market.clone();
+                            view! {
+                                <Show
+                                    when=move || trades.with(|t| !t.is_empty())
+                                    fallback=|| view! { <EmptyState message=Msg::WaitingTrades /> }
+                                >
+                                    <TradeHistory
+                                        market=market.clone()
+                                        cursor=cursor
+                                        min_size=tape_min_size
+                                        group_window=tape_group
+                                    />
+                                </Show>
+                            }
+                            .into_any()
+                        }
+                        TapeView::Liquidations => view! { <LiquidationsFeed market=liquidations_market.clone() /> }.into_any(),
+                        TapeView::Whales => view! { <WhaleAlertStream whales=whales /> }.into_any(),
+                    }}
+                </GridPanel>
+
+                <GridPanel panel=Panel::OrderEntry class="order-entry-container" title=Msg::OrderEntry drag=drag grid=grid>
+                    <OrderEntry />
+                </GridPanel>
+
+                <GridPanel panel=Panel::Positions class="positions-container" title=Msg::Positions drag=drag grid=grid>
+                    <PositionsPanel portfolio=positions_portfolio />
+                </GridPanel>
+
+                <GridPanel panel=Panel::Account class="account-container" title=Msg::Account drag=drag grid=grid>
+                    <AccountPanel portfolio=account_portfolio />
+                </GridPanel>
             </main>
 
+            <ToastHost />
+            <ContextMenuHost />
+            <ShortcutCheatSheet />
+            <SettingsDialog />
+            <GuidedTour />
+
             <footer class="dash-footer">
                 <StatusBar />
+                {debug_tools()}
             </footer>
         </div>
     }
 }
 
+/// Next tape grouping window: off, then each preset in turn, then off again
+fn next_group_window(current: Option<i64>) -> Option<i64> {
+    match current {
+        None => TAPE_GROUP_WINDOWS.first().copied(),
+        Some(ms) => TAPE_GROUP_WINDOWS.iter().copied().find(|&w| w > ms),
+    }
+}
+
+/// Grid cell under a viewport point
+fn pointer_cell(grid: NodeRef<leptos::html::Main>, x: i32, y: i32) -> Option<(u16, u16)> {
+    let rect = grid.get_untracked()?.get_bounding_client_rect();
+    Some(grid_cell(
+        f64::from(x) - rect.left(),
+        f64::from(y) - rect.top(),
+        rect.width(),
+        rect.height(),
+    ))
+}
+
+/// Panel chrome placed on the layout grid: drag by the header to move (drop
+/// onto another panel to swap slots, or to reorder a column of stacked
+/// panels), drag the corner to resize
+#[component]
+fn GridPanel(
+    panel: Panel,
+    class: &'static str,
+    title: Msg,
+    drag: RwSignal<Option<PanelDrag>>,
+    grid: NodeRef<leptos::html::Main>,
+    /// Extra header content (right-aligned)
+    #[prop(optional, into)]
+    meta: ViewFn,
+    children: Children,
+) -> impl IntoView {
+    let state = use_app_state();
+    let ui = state.ui;
+    let i18n = state.i18n;
+    let stale = panel.stream().map(|channel| state.market.freshness.is_stale(channel));
+
+    let popout = state.popout;
+    let shown_state = state.clone();
+    let style = move || {
+        // A pop-out window shows its panel across the whole grid
+        if let Some(only) = popout.get() {
+            return if only == panel { "grid-area: 1 / 1 / -1 / -1".to_string() } else { "display: none".to_string() };
+        }
+        // Compact mode stacks the active tab's panels in a single column and
+        // the tablet layout flows the visible ones through two
+        if !ui.with(UiState::uses_grid) {
+            return if shown_state.panel_shown(panel) { String::new() } else { "display: none".to_string() };
+        }
+        let layout = *ui.get().layout.panel(panel);
+        match drag.get() {
+            Some(d) if d.panel == panel => PanelLayout { rect: d.preview(), ..layout }.style(),
+            _ => layout.style(),
+        }
+    };
+
+    let class_name = move || {
+        let mut name = format!("panel {}", class);
+        if stale.is_some_and(|s| s.get()) {
+            name.push_str(" stale");
+        }
+        if let Some(d) = drag.get() {
+            if d.panel == panel {
+                name.push_str(" drag-source");
+            } else if ui.with(|ui| d.dock_target(&ui.layout)) == Some(panel) {
+                name.push_str(" dock-target");
+            }
+        }
+        name
+    };
+
+    let start_drag = move |ev: leptos::ev::PointerEvent, mode: DragMode| {
+        // Left button only, and leave header controls clickable
+        if ev.button() != 0 || !ui.with_untracked(UiState::uses_grid) || popout.with_untracked(Option::is_some) {
+            return;
+        }
+        let Some(cell) = pointer_cell(grid, ev.client_x(), ev.client_y()) else {
+            return;
+        };
+        ev.prevent_default();
+        let origin = ui.with_untracked(|ui| ui.layout.panel(panel).rect);
+        drag.set(Some(PanelDrag::new(panel, mode, origin, cell)));
+    };
+
+    let panel_ref = NodeRef::<leptos::html::Div>::new();
+    let toggle_fullscreen = move |_| {
+        let Some(el) = panel_ref.get_untracked() else {
+            return;
+        };
+        let document = document();
+        if document.fullscreen_element().is_some() {
+            document.exit_fullscreen();
+        } else if el.request_fullscreen().is_err() {
+            tracing::warn!("Fullscreen request for {} was refused", panel.label());
+        }
+    };
+    let popout_state = state.clone();
+    let pop_out = move |_| {
+        let window = window();
+        let path = window.location().pathname().unwrap_or_default();
+        let url = format!("{}{}", path, popout_state.popout_query(panel));
+        let target = format!("dash-popout-{}", panel.code());
+        if window
+            .open_with_url_and_target_and_features(&url, &target, "popup,width=960,height=640")
+            .ok()
+            .flatten()
+            .is_none()
+        {
+            tracing::warn!("Pop-out window for {} was blocked", panel.label());
+        }
+    };
+
+    view! {
+        <div class=class_name style=style node_ref=panel_ref>
+            <div class="panel-header" on:pointerdown=move |ev| start_drag(ev, DragMode::Move)>
+                <span class="panel-title">{move || i18n.t(title)}</span>
+                <div class="panel-meta-slot" on:pointerdown=|ev| ev.stop_propagation()>
+                    {meta.run()}
+                    <span class="panel-controls">
+                        <button class="panel-control" title="Fullscreen" on:click=toggle_fullscreen>"⛶"</button>
+                        <Show when=move || popout.with(Option::is_none)>
+                            <button class="panel-control" title="Pop out into a new window" on:click=pop_out.clone()>
+                                "⧉"
+                            </button>
+                        </Show>
+                    </span>
+                </div>
+            </div>
+            <div class="panel-content">
+                {children()}
+            </div>
+            <div
+                class="panel-resize"
+                title="Resize"
+                on:pointerdown=move |ev| {
+                    ev.stop_propagation();
+                    start_drag(ev, DragMode::Resize);
+                }
+            />
+        </div>
+    }
+}
+
+/// Debug-only footer controls
+#[cfg(feature = "time-travel")]
+fn debug_tools() -> impl IntoView {
+    view! { <crate::TimeTravelScrubber /> }
+}
+
+#[cfg(not(feature = "time-travel"))]
+fn debug_tools() -> impl IntoView {}
+
+/// Warning strip while the server streams a backup feed instead of the
+/// exchange
+#[component]
+fn FeedBanner() -> impl IntoView {
+    let feed = use_app_state().feed;
+    move || {
+        let status = feed.get().filter(|f| f.is_degraded())?;
+        Some(view! {
+            <div class="feed-banner" role="status">
+                <strong>"Backup feed"</strong>
+                {format!(
+                    " — the exchange feed is down; prices come from the {} source since {} UTC and may not reflect the market",
+                    status.name,
+                    format_time_ms(status.since)
+                )}
+            </div>
+        })
+    }
+}
+
+/// Upstream feed health from the server's supervisor, so an exchange
+/// outage reads differently from a dropped connection
+#[component]
+fn SourceReadout() -> impl IntoView {
+    let sources = use_app_state().sources;
+    move || {
+        sources
+            .get()
+            .into_values()
+            .map(|source| {
+                let color = match source.state {
+                    SourceState::Running => colors::css::BULL,
+                    SourceState::Stalled => colors::css::WARN,
+                    SourceState::Restarting => colors::css::BEAR,
+                };
+                let age = source
+                    .message_age_ms
+                    .map_or_else(|| "no data yet".to_string(), |ms| format!("last message {:.1}s ago", ms as f64 / 1_000.0));
+                let title = format!(
+                    "Exchange feed {}: {}, {} errors, {} restarts",
+                    source.name, age, source.errors, source.restarts
+                );
+                view! {
+                    <div class="sb-item" title=title>
+                        <span class="sb-label">{source.name}</span>
+                        <span class="sb-value" style=format!("color: {}", color)>{source.state.label()}</span>
+                    </div>
+                }
+            })
+            .collect_view()
+    }
+}
+
+/// Outcome of the server's latest check of the active symbol's bars
+/// against its trades
+#[component]
+fn CandleCheckReadout() -> impl IntoView {
+    let state = use_app_state();
+    let symbol = state.market.symbol;
+    let checks = state.candle_checks;
+    move || {
+        let report = checks.with(|c| c.get(&symbol.get()).cloned())?;
+        let (text, color) = match report.discrepancies.len() {
+            0 => ("ok".to_string(), colors::css::BULL),
+            n => (format!("{} off", n), colors::css::WARN),
+        };
+        let title = match report.discrepancies.first() {
+            None => format!("Last {} bars match the trades", report.checked),
+            Some(first) => format!(
+                "{} of the last {} bars disagree with the trades (first: {})",
+                report.discrepancies.len(),
+                report.checked,
+                first.fields.join(", ")
+            ),
+        };
+        Some(view! {
+            <div class="sb-item" title=title>
+                <span class="sb-label">"Bars"</span>
+                <span class="sb-value" style=format!("color: {}", color)>{text}</span>
+            </div>
+        })
+    }
+}
+
 #[component]
 fn StatusBar() -> impl IntoView {
     let state = use_app_state();
     let connection = state.connection;
-    let error = state.error;
+    let notifications = state.notifications;
+    let ui = state.ui;
+    let shortcuts = state.shortcuts;
+    let settings_open = state.settings_open;
+    let i18n = state.i18n;
 
     view! {
         <div class="status-bar">
             <div class="sb-connection">
-                <span class="sb-label">"Status:"</span>
+                <span class="sb-label">{move || i18n.t(Msg::Status)}</span>
                 <span class=move || format!("sb-value {}", connection.get().css_class())>
                     {move || connection.get().label()}
                 </span>
             </div>
 
-            {move || {
-                error.get().map(|e| {
+            <div class="sb-layout">
+                <span class="sb-label">{move || i18n.t(Msg::Layout)}</span>
+                {DashboardLayout::presets()
+                    .iter()
+                    .map(|&preset| {
+                        let state = state.clone();
+                        view! {
+                            <button
+                                class=move || {
+                                    if ui.with(|ui| ui.layout.preset) == preset { "sb-preset active" } else { "sb-preset" }
+                                }
+                                on:click=move |_| state.apply_layout(preset)
+                            >
+                                {preset.label()}
+                            </button>
+                        }
+                    })
+                    .collect_view()}
+                <Show when=move || ui.with(|ui| ui.layout.preset) == DashboardLayout::Custom>
+                    <span class="sb-preset active">{DashboardLayout::Custom.label()}</span>
+                </Show>
+                {
+                    let state = state.clone();
                     view! {
-                        <div class="sb-error">
-                            <span class="error-icon">"⚠"</span>
-                            <span class="error-msg">{e}</span>
-                        </div>
+                        <button
+                            class=move || if ui.with(|ui| ui.compact_mode) { "sb-preset active" } else { "sb-preset" }
+                            on:click=move |_| state.toggle_compact_mode()
+                        >
+                            {move || i18n.t(Msg::Compact)}
+                        </button>
                     }
+                }
+            </div>
+
+            <button
+                class="sb-preset"
+                title="Keyboard shortcuts"
+                on:click=move |_| shortcuts.cheat_sheet.set(true)
+            >
+                {move || shortcuts.bindings.with(|b| b.key_for(ShortcutAction::CheatSheet).to_string())}
+            </button>
+
+            <MuteToggle />
+
+            <button class="sb-preset" title=move || i18n.t(Msg::Settings) on:click=move |_| settings_open.set(true)>
+                "⚙"
+            </button>
+
+            {move || {
+                let unread = notifications.unread.get();
+                (unread > 0).then(|| view! {
+                    <div class="sb-unread" title="Unread notifications">{unread}</div>
                 })
             }}
 
+            <SourceReadout />
+
+            <CandleCheckReadout />
+
+            <TelemetryReadout />
+
             <div class="sb-version">
                 <span>"v0.1.0"</span>
             </div>
@@ -0,0 +1,64 @@
+//! Time-travel scrubber for replaying recorded messages (`time-travel` feature)
+
+use dash_state::use_app_state;
+use leptos::prelude::*;
+
+/// Rewind/replay control bar for the message recorder
+#[component]
+pub fn TimeTravelScrubber() -> impl IntoView {
+    let state = use_app_state();
+    let recorder = state.recorder;
+
+    let len = move || recorder.len();
+    let position = move || recorder.position.get().unwrap_or(len().saturating_sub(1));
+    let is_live = move || recorder.position.get().is_none();
+
+    let current_label = move || {
+        recorder.current().map_or_else(
+            || "no messages".to_string(),
+            |entry| {
+                let time = chrono::DateTime::from_timestamp_millis(entry.received_at)
+                    .map(|t| t.format("%H:%M:%S%.3f").to_string())
+                    .unwrap_or_default();
+                format!("#{} {} @ {}", position(), entry.message.kind(), time)
+            },
+        )
+    };
+
+    let seek_state = state.clone();
+    let back_state = state.clone();
+    let fwd_state = state.clone();
+    let live_state = state.clone();
+
+    view! {
+        <div class=move || if is_live() { "time-travel" } else { "time-travel scrubbing" }>
+            <span class="tt-label">"⏱"</span>
+            <button class="tt-btn" title="Step back" on:click=move |_| back_state.step_replay(-1)>
+                "◀"
+            </button>
+            <input
+                class="tt-range"
+                type="range"
+                min="0"
+                max=move || len().saturating_sub(1).to_string()
+                prop:value=move || position().to_string()
+                on:input=move |ev| {
+                    if let Ok(index) = event_target_value(&ev).parse::<usize>() {
+                        seek_state.rewind_to(index);
+                    }
+                }
+            />
+            <button class="tt-btn" title="Step forward" on:click=move |_| fwd_state.step_replay(1)>
+                "▶"
+            </button>
+            <button
+                class="tt-btn tt-live"
+                disabled=is_live
+                on:click=move |_| live_state.go_live()
+            >
+                "Live"
+            </button>
+            <span class="tt-current">{current_label}</span>
+        </div>
+    }
+}
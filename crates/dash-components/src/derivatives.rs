@@ -0,0 +1,80 @@
+//! Funding rate, open interest and basis strip for perpetual markets
+
+use dash_core::{colors, format_countdown, CompactNumberFormatter, LargeNumberFormatter};
+use dash_state::{use_app_state, use_i18n, use_tick_format, Msg};
+use leptos::prelude::*;
+
+/// Derivatives stats shown under the ticker bar; renders nothing until the
+/// feed delivers derivatives data for the active symbol
+#[component]
+pub fn DerivativesBar() -> impl IntoView {
+    let market = use_app_state().market;
+    let derivatives = market.derivatives;
+    let now = market.freshness.now;
+    let i18n = use_i18n();
+    let format = use_tick_format();
+
+    view! {
+        <Show when=move || derivatives.with(Option::is_some)>
+            <div class="derivatives-bar">
+                {move || {
+                    let d = derivatives.get()?;
+                    let color = if d.is_funding_positive() { colors::css::BULL } else { colors::css::BEAR };
+                    Some(view! {
+                        <div class="tb-stat" title=format!("{:.2}% annualized", d.funding_annualized())>
+                            <span class="stat-label">{move || i18n.t(Msg::Funding)}</span>
+                            <span class="stat-value" style=format!("color: {}", color)>{d.funding_str()}</span>
+                        </div>
+                        <div class="tb-stat">
+                            <span class="stat-label">{move || i18n.t(Msg::NextFunding)}</span>
+                            <span class="stat-value">
+                                {move || {
+                                    format_countdown(d.funding_remaining_ms(now.get()))
+                                }}
+                            </span>
+                        </div>
+                    })
+                }}
+
+                {move || {
+                    let d = derivatives.get()?;
+                    let change = d.open_interest_change_24h;
+                    let color = if change >= 0.0 { colors::css::BULL } else { colors::css::BEAR };
+                    let sign = if change >= 0.0 { "+" } else { "" };
+                    Some(view! {
+                        <div class="tb-stat">
+                            <span class="stat-label">{move || i18n.t(Msg::OpenInterest)}</span>
+                            <span class="stat-value">
+                                {CompactNumberFormatter.format(d.open_interest.as_f64())}
+                                " "
+                                <span class="dv-delta" style=format!("color: {}", color)>
+                                    {format!("{}{:.2}%", sign, d.open_interest_change_percent())}
+                                </span>
+                            </span>
+                        </div>
+                    })
+                }}
+
+                {move || {
+                    let d = derivatives.get()?;
+                    Some(view! {
+                        <div class="tb-stat">
+                            <span class="stat-label">{move || i18n.t(Msg::Mark)}</span>
+                            <span class="stat-value">{i18n.price(d.mark_price.as_f64(), format.get())}</span>
+                        </div>
+                        <div class="tb-stat">
+                            <span class="stat-label">{move || i18n.t(Msg::Index)}</span>
+                            <span class="stat-value">{i18n.price(d.index_price.as_f64(), format.get())}</span>
+                        </div>
+                        <div class="tb-stat">
+                            <span class="stat-label">{move || i18n.t(Msg::Basis)}</span>
+                            <span class="stat-value" style=format!("color: {}", colors::css::WARN)>
+                                {format!("{} ({}%)", i18n.price(d.basis(), format.get()), i18n.number(d.basis_percent(), 3))}
+                            </span>
+                        </div>
+                    })
+                }}
+            </div>
+        </Show>
+    }
+}
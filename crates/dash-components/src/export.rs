@@ -0,0 +1,82 @@
+//! Export buttons that download panel data as CSV/JSON files
+
+use dash_core::{export_filename, ExportFormat};
+use dash_state::use_app_state;
+use leptos::prelude::*;
+
+/// Save `contents` as a file through a temporary Blob URL (no-op outside wasm32)
+pub fn download(filename: &str, mime_type: &str, contents: &str) {
+    #[cfg(target_arch = "wasm32")]
+    {
+        use wasm_bindgen::JsValue;
+
+        let result = (|| -> Result<(), JsValue> {
+            let parts = js_sys::Array::of1(&JsValue::from_str(contents));
+            let options = web_sys::BlobPropertyBag::new();
+            options.set_type(mime_type);
+            let blob = web_sys::Blob::new_with_str_sequence_and_options(&parts, &options)?;
+            save_blob(filename, &blob)
+        })();
+
+        if let Err(e) = result {
+            tracing::warn!("Export of {} failed: {:?}", filename, e);
+        }
+    }
+    #[cfg(not(target_arch = "wasm32"))]
+    {
+        let _ = (filename, mime_type, contents);
+    }
+}
+
+/// Save a Blob as a file by clicking a temporary object URL link
+pub fn save_blob(filename: &str, blob: &web_sys::Blob) -> Result<(), wasm_bindgen::JsValue> {
+    use wasm_bindgen::{JsCast, JsValue};
+
+    let url = web_sys::Url::create_object_url_with_blob(blob)?;
+    let document = web_sys::window()
+        .and_then(|w| w.document())
+        .ok_or_else(|| JsValue::from_str("no document"))?;
+    let anchor: web_sys::HtmlAnchorElement = document.create_element("a")?.dyn_into()?;
+    anchor.set_href(&url);
+    anchor.set_download(filename);
+    anchor.click();
+
+    web_sys::Url::revoke_object_url(&url)
+}
+
+/// One button per format; `render` serializes the panel's current data
+/// (`None` when there is nothing to export)
+#[component]
+pub fn ExportButtons(
+    /// File name prefix, e.g. `trades`
+    kind: &'static str,
+    #[prop(into)] render: Callback<ExportFormat, Option<String>>,
+) -> impl IntoView {
+    let symbol = use_app_state().market.symbol;
+
+    let export = move |format: ExportFormat| {
+        let Some(contents) = render.run(format) else {
+            return;
+        };
+        let now = chrono::Utc::now().timestamp_millis();
+        let filename = export_filename(kind, &symbol.get_untracked(), now, format);
+        download(&filename, format.mime_type(), &contents);
+    };
+
+    view! {
+        <span class="export-buttons">
+            {ExportFormat::all()
+                .iter()
+                .map(|&format| view! {
+                    <button
+                        class="export-btn"
+                        title=format!("Download as {}", format.label())
+                        on:click=move |_| export(format)
+                    >
+                        {format.label()}
+                    </button>
+                })
+                .collect_view()}
+        </span>
+    }
+}
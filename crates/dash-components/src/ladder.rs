@@ -0,0 +1,254 @@
+//! DOM-style price ladder: a fixed price axis with bids left and asks right
+
+use dash_core::{colors, ladder_index, LadderRung, SymbolInfo, TradeSide};
+use dash_state::{use_app_state, Msg};
+
+use crate::use_context_menu;
+use leptos::prelude::*;
+use std::collections::HashMap;
+
+/// Rungs shown above and below the center
+const HALF_RUNGS: usize = 12;
+/// Book levels per side the automatic step tries to fit on screen
+const STEP_DEPTH: usize = 10;
+/// Rungs moved per wheel notch
+const SCROLL_RUNGS: f64 = 3.0;
+
+/// Working paper-order size on one rung
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+struct RungOrders {
+    buy: f64,
+    sell: f64,
+}
+
+/// Possible-iceberg tooltips on one rung, per side
+#[derive(Debug, Clone, Default, PartialEq)]
+struct RungIcebergs {
+    bid: Option<String>,
+    ask: Option<String>,
+}
+
+/// Fixed price axis centered on the mid (scroll to move, "Recenter" to snap
+/// back), with working paper orders and possible icebergs marked on their rungs. Click a bid cell
+/// to buy or an ask cell to sell at that price.
+#[component]
+pub fn PriceLadder(
+    /// Clicked cell: ticket side, rung price, and whether shift was held
+    #[prop(optional, into)]
+    on_price_click: Option<Callback<(TradeSide, f64, bool)>>,
+) -> impl IntoView {
+    let state = use_app_state();
+    let i18n = state.i18n;
+    let directory = state.symbols;
+    let symbol = state.market.symbol;
+    let orderbook = state.market.orderbook;
+    let orders = state.portfolio.orders;
+    let icebergs = state.market.icebergs;
+
+    // Step is picked once per symbol so rungs don't jump on every snapshot
+    let step = RwSignal::new(None::<f64>);
+    let center = RwSignal::new(None::<f64>);
+    let auto_center = RwSignal::new(true);
+
+    let info = Memo::new(move |_| {
+        let symbol = symbol.get();
+        directory
+            .info(&symbol)
+            .unwrap_or_else(|| SymbolInfo { symbol, ..SymbolInfo::default() })
+    });
+
+    Effect::new(move |_| {
+        symbol.track();
+        step.set(None);
+        center.set(None);
+        auto_center.set(true);
+    });
+
+    Effect::new(move |_| {
+        orderbook.with(|book| {
+            let Some(book) = book else {
+                return;
+            };
+            let tick = info.with(|i| i.tick_size);
+            if step.get_untracked().is_none() && tick > 0.0 {
+                step.set(Some(book.ladder_step(tick, 2 * HALF_RUNGS + 1, STEP_DEPTH)));
+            }
+            if auto_center.get()
+                && let Some(mid) = book.mid_price()
+            {
+                center.set(Some(mid));
+            }
+        });
+    });
+
+    let rungs = Memo::new(move |_| {
+        let (Some(step), Some(center)) = (step.get(), center.get()) else {
+            return Vec::new();
+        };
+        orderbook.with(|book| {
+            book.as_ref()
+                .map_or_else(Vec::new, |book| book.ladder(center, step, HALF_RUNGS))
+        })
+    });
+
+    let working = Memo::new(move |_| {
+        let Some(step) = step.get() else {
+            return HashMap::new();
+        };
+        let symbol = symbol.get();
+        orders.with(|orders| {
+            let mut working: HashMap<i64, RungOrders> = HashMap::new();
+            for order in orders.iter().filter(|o| o.symbol == symbol) {
+                let Some(price) = order.price.or(order.stop_price) else {
+                    continue;
+                };
+                let entry = working.entry(ladder_index(price.as_f64(), step)).or_default();
+                match order.side {
+                    TradeSide::Buy => entry.buy += order.remaining(),
+                    TradeSide::Sell => entry.sell += order.remaining(),
+                }
+            }
+            working
+        })
+    });
+
+    let iceberg_rungs = Memo::new(move |_| {
+        let Some(step) = step.get() else {
+            return HashMap::new();
+        };
+        icebergs.with(|flagged| {
+            let mut rungs: HashMap<i64, RungIcebergs> = HashMap::new();
+            for level in flagged {
+                let entry = rungs.entry(ladder_index(level.price, step)).or_default();
+                match level.side {
+                    TradeSide::Buy => entry.bid = Some(level.describe()),
+                    TradeSide::Sell => entry.ask = Some(level.describe()),
+                }
+            }
+            rungs
+        })
+    });
+
+    let max_size = Memo::new(move |_| {
+        rungs.with(|rungs| rungs.iter().map(|r| r.bid.max(r.ask)).fold(0.001_f64, f64::max))
+    });
+
+    let on_wheel = move |ev: leptos::ev::WheelEvent| {
+        let (Some(step), Some(current)) = (step.get_untracked(), center.get_untracked()) else {
+            return;
+        };
+        ev.prevent_default();
+        auto_center.set(false);
+        let direction = if ev.delta_y() > 0.0 { -1.0 } else { 1.0 };
+        center.set(Some(current + direction * SCROLL_RUNGS * step));
+    };
+
+    let click = move |side: TradeSide, price: f64, ev: leptos::ev::MouseEvent| {
+        if let Some(on_click) = on_price_click {
+            on_click.run((side, price, ev.shift_key()));
+        }
+    };
+
+    let menu = use_context_menu();
+    let row = move |rung: LadderRung| {
+        let decimals = info.with_untracked(SymbolInfo::price_decimals);
+        let qty_decimals = info.with_untracked(SymbolInfo::quantity_decimals).max(2);
+        let marks = step
+            .get_untracked()
+            .and_then(|step| working.with(|w| w.get(&ladder_index(rung.price, step)).copied()))
+            .unwrap_or_default();
+        let RungIcebergs { bid: bid_iceberg, ask: ask_iceberg } = step
+            .get_untracked()
+            .and_then(|step| iceberg_rungs.with(|r| r.get(&ladder_index(rung.price, step)).cloned()))
+            .unwrap_or_default();
+        let max = max_size.get();
+        let price = rung.price;
+
+        let size_cell = move |size: f64, side: TradeSide| {
+            if size <= 0.0 {
+                return (String::new(), String::new());
+            }
+            let pct = (size / max * 100.0).min(100.0);
+            let (color, toward) = match side {
+                TradeSide::Buy => (colors::css::bull_alpha(0.2), "left"),
+                TradeSide::Sell => (colors::css::bear_alpha(0.2), "right"),
+            };
+            (
+                format!("{:.*}", qty_decimals, size),
+                format!("background: linear-gradient(to {}, {} {}%, transparent {}%)", toward, color, pct, pct),
+            )
+        };
+        let (bid_text, bid_style) = size_cell(rung.bid, TradeSide::Buy);
+        let (ask_text, ask_style) = size_cell(rung.ask, TradeSide::Sell);
+        let mark = |qty: f64| (qty > 0.0).then(|| format!("{:.*}", qty_decimals, qty));
+        let iceberg_mark = |level: &Option<String>| {
+            level.as_ref().map(|_| view! { <span class="iceberg-mark" aria-hidden="true">"🧊"</span> })
+        };
+        let (bid_mark, ask_mark) = (iceberg_mark(&bid_iceberg), iceberg_mark(&ask_iceberg));
+        let bid_class = if bid_iceberg.is_some() { "ladder-size bid iceberg" } else { "ladder-size bid" };
+        let ask_class = if ask_iceberg.is_some() { "ladder-size ask iceberg" } else { "ladder-size ask" };
+
+        view! {
+            <div
+                class="ladder-row"
+                on:contextmenu=move |ev| {
+                    if let Some(menu) = menu {
+                        menu.show_price(&ev, price);
+                    }
+                }
+            >
+                <span class="ladder-order buy">{mark(marks.buy)}</span>
+                <span
+                    class=bid_class
+                    style=bid_style
+                    title=bid_iceberg
+                    on:click=move |ev| click(TradeSide::Buy, price, ev)
+                >
+                    {bid_mark}
+                    {bid_text}
+                </span>
+                <span class="ladder-price">{format!("{:.*}", decimals, price)}</span>
+                <span
+                    class=ask_class
+                    style=ask_style
+                    title=ask_iceberg
+                    on:click=move |ev| click(TradeSide::Sell, price, ev)
+                >
+                    {ask_text}
+                    {ask_mark}
+                </span>
+                <span class="ladder-order sell">{mark(marks.sell)}</span>
+            </div>
+        }
+    };
+
+    view! {
+        <div class="price-ladder">
+            <div class="panel-toolbar">
+                <span class="ladder-step">
+                    {move || {
+                        let decimals = info.with(SymbolInfo::price_decimals);
+                        step.get().map(|s| format!("Step {:.*}", decimals, s))
+                    }}
+                </span>
+                <button
+                    class="ladder-recenter"
+                    disabled=move || auto_center.get()
+                    on:click=move |_| auto_center.set(true)
+                >
+                    "Recenter"
+                </button>
+            </div>
+            <div class="ladder-header">
+                <span>{move || i18n.t(Msg::Orders)}</span>
+                <span>{move || i18n.t(Msg::Bid)}</span>
+                <span>{move || i18n.t(Msg::Price)}</span>
+                <span>{move || i18n.t(Msg::Ask)}</span>
+                <span>{move || i18n.t(Msg::Orders)}</span>
+            </div>
+            <div class="ladder-rungs" on:wheel=on_wheel>
+                {move || rungs.get().into_iter().map(row).collect_view()}
+            </div>
+        </div>
+    }
+}
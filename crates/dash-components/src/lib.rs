@@ -4,17 +4,94 @@
 //!
 //! ## Components
 //!
+//! - `a11y` - Keyboard row navigation and screen reader announcements
+//! - `book_replay` - Step back through recent book snapshots with changes highlighted
+//! - `chart_grid` - Multi-chart grid of symbols and timeframes
+//! - `context_menu` - Right-click actions for rows and charts
+//! - `countdown` - Time left in the forming candle
+//! - `derivatives` - Funding, open interest and basis strip
+//! - `export` - CSV/JSON download buttons
+//! - `ladder` - DOM-style fixed-axis price ladder
+//! - `liquidations` - Liquidation tape with 1h long/short summary
+//! - `mini_ticker` - Scrolling watchlist ticker strip
+//! - `onboarding` - Panel empty states and the first-run guided tour
 //! - `order` - Order book ladder display
+//! - `overlays` - Chart indicator overlay menu
 //! - `trade_history` - Recent trades tape
+//! - `order_entry` - Paper-trading order ticket
+//! - `portfolio` - Paper-trading positions and account summary
+//! - `responsive` - Viewport breakpoints driving the grid/tablet/compact layouts
+//! - `settings` - Settings dialog
+//! - `share` - PNG snapshots of the chart and order book panels
+//! - `shortcuts` - Global keyboard shortcuts and cheat sheet
+//! - `sounds` - Synthesized alert sounds with per-event volume
+//! - `symbol_selector` - Searchable symbol dropdown with favorites
+//! - `telemetry` - Status bar throughput, frame time and memory readout
+//! - `timeframe` - Chart timeframe toolbar
+//! - `toast` - Notification toasts with auto-dismiss and actions
 //! - `ticker_bar` - Header ticker with price/stats
+//! - `whales` - Whale trade alert cards with unread badge
 //! - `dashboard` - Main dashboard layout
+//! - `debug` - Time-travel message scrubber (`time-travel` feature)
 
+pub mod a11y;
+pub mod book_replay;
+pub mod chart_grid;
+pub mod context_menu;
+pub mod countdown;
 pub mod dashboard;
+#[cfg(feature = "time-travel")]
+pub mod debug;
+pub mod derivatives;
+pub mod export;
+pub mod ladder;
+pub mod liquidations;
+pub mod mini_ticker;
+pub mod onboarding;
 pub mod order;
+pub mod order_entry;
+pub mod overlays;
+pub mod portfolio;
+pub mod responsive;
+pub mod settings;
+pub mod share;
+pub mod shortcuts;
+pub mod sounds;
+pub mod symbol_selector;
+pub mod telemetry;
+pub mod timeframe;
 pub mod ticker_bar;
+pub mod toast;
 pub mod trade_history;
+pub mod whales;
 
+pub use a11y::*;
+pub use book_replay::*;
+pub use chart_grid::*;
+pub use context_menu::*;
+pub use countdown::*;
 pub use dashboard::*;
+#[cfg(feature = "time-travel")]
+pub use debug::*;
+pub use derivatives::*;
+pub use export::*;
+pub use ladder::*;
+pub use liquidations::*;
+pub use mini_ticker::*;
+pub use onboarding::*;
 pub use order::*;
+pub use order_entry::*;
+pub use overlays::*;
+pub use portfolio::*;
+pub use responsive::*;
+pub use settings::*;
+pub use share::*;
+pub use shortcuts::*;
+pub use sounds::*;
+pub use symbol_selector::*;
+pub use telemetry::*;
+pub use timeframe::*;
 pub use ticker_bar::*;
+pub use toast::*;
 pub use trade_history::*;
+pub use whales::*;
@@ -0,0 +1,100 @@
+//! Liquidation feed (tape of forced closes)
+
+use dash_core::{
+    colors, CompactNumberFormatter, LargeNumberFormatter, Liquidation, LiquidationSummary, LIQUIDATION_WINDOW_MS,
+};
+use dash_state::{use_app_state, use_i18n, MarketState, Msg};
+use leptos::prelude::*;
+
+/// Notional at which a row is fully highlighted
+const FULL_HIGHLIGHT_VALUE: f64 = 100_000.0;
+/// Rows shown in the feed
+const MAX_VISIBLE: usize = 100;
+
+/// Liquidation tape with a rolling 1h longs-vs-shorts summary
+#[component]
+pub fn LiquidationsFeed(#[prop(into)] market: MarketState) -> impl IntoView {
+    let liquidations = market.liquidations;
+    let now = market.freshness.now;
+    let i18n = use_i18n();
+
+    let summary = Memo::new(move |_| {
+        let since = now.get() - LIQUIDATION_WINDOW_MS;
+        liquidations.with(|l| LiquidationSummary::since(l, since))
+    });
+
+    let visible = move || liquidations.with(|l| l.iter().take(MAX_VISIBLE).cloned().collect::<Vec<_>>());
+
+    view! {
+        <div class="trade-history liquidations">
+            <div class="liq-summary" title="Liquidated notional over the last hour">
+                <span class="stat-label">"1h"</span>
+                <span style=format!("color: {}", colors::css::BEAR)>
+                    {move || summary.with(|s| {
+                        format!("Longs {} ({})", CompactNumberFormatter.format(s.long_value), s.long_count)
+                    })}
+                </span>
+                <div class="liq-split">
+                    <div
+                        class="liq-split-longs"
+                        style=move || format!("width: {:.1}%", summary.get().long_share() * 100.0)
+                    />
+                </div>
+                <span style=format!("color: {}", colors::css::BULL)>
+                    {move || summary.with(|s| {
+                        format!("Shorts {} ({})", CompactNumberFormatter.format(s.short_value), s.short_count)
+                    })}
+                </span>
+            </div>
+            <div class="th-header">
+                <span class="th-col time">{move || i18n.t(Msg::Time)}</span>
+                <span class="th-col side">{move || i18n.t(Msg::PositionSide)}</span>
+                <span class="th-col price">{move || i18n.t(Msg::Price)}</span>
+                <span class="th-col size">{move || i18n.t(Msg::Size)}</span>
+                <span class="th-col value">{move || i18n.t(Msg::Value)}</span>
+            </div>
+            <div class="th-list">
+                <Show
+                    when=move || liquidations.with(|l| !l.is_empty())
+                    fallback=move || view! { <div class="liq-empty">{move || i18n.t(Msg::NoLiquidations)}</div> }
+                >
+                    <For
+                        each=visible
+                        key=|liq| liq.id.clone()
+                        children=|liq| view! { <LiquidationRow liquidation=liq /> }
+                    />
+                </Show>
+            </div>
+        </div>
+    }
+}
+
+#[component]
+fn LiquidationRow(liquidation: Liquidation) -> impl IntoView {
+    let value = liquidation.value();
+    // Square root keeps mid-sized prints visible next to the largest ones
+    let intensity = (value / FULL_HIGHLIGHT_VALUE).sqrt().min(1.0);
+    let alpha = 0.06 + 0.34 * intensity;
+    // A liquidated long is a forced sell, so it takes the bear color
+    let (color, background) = if liquidation.is_long() {
+        (colors::css::BEAR, colors::css::bear_alpha(alpha))
+    } else {
+        (colors::css::BULL, colors::css::bull_alpha(alpha))
+    };
+    let class = if intensity >= 1.0 { "th-row liq-row big" } else { "th-row liq-row" };
+    let state = use_app_state();
+    let (i18n, directory) = (state.i18n, state.symbols);
+    let symbol = liquidation.symbol.clone();
+    let format = Signal::derive(move || directory.tick_format(&symbol));
+    let (price, quantity) = (liquidation.price.as_f64(), liquidation.quantity.as_f64());
+
+    view! {
+        <div class=class style=format!("background: {}", background)>
+            <span class="th-col time">{liquidation.time_str()}</span>
+            <span class="th-col side" style=format!("color: {}", color)>{liquidation.position_label()}</span>
+            <span class="th-col price">{move || i18n.price(price, format.get())}</span>
+            <span class="th-col size">{move || i18n.quantity(quantity, format.get())}</span>
+            <span class="th-col value">{CompactNumberFormatter.format(value)}</span>
+        </div>
+    }
+}
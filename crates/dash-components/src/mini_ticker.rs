@@ -0,0 +1,66 @@
+//! Scrolling multi-symbol ticker strip
+
+use dash_charts::{trail_bars, MiniCandles, TRAIL_TICKS_PER_BAR};
+use dash_core::{Symbol, Ticker};
+use dash_state::use_app_state;
+use leptos::prelude::*;
+
+/// Seconds each symbol adds to one marquee loop
+const SECONDS_PER_SYMBOL: usize = 4;
+
+/// Marquee of watchlist tickers; hovering pauses it and clicking a symbol
+/// switches the dashboard to it
+#[component]
+pub fn MiniTickerStrip(#[prop(into)] tickers: Signal<Vec<Ticker>>) -> impl IntoView {
+    let state = use_app_state();
+    let (i18n, directory) = (state.i18n, state.symbols);
+    let switch = Callback::new(move |symbol: Symbol| state.switch_symbol(symbol));
+
+    let item = move |ticker: Ticker| {
+        let symbol = ticker.symbol.clone();
+        let trail_symbol = ticker.symbol.clone();
+        let bbo_symbol = ticker.symbol.clone();
+        let format_symbol = ticker.symbol.clone();
+        let format = Signal::derive(move || directory.tick_format(&format_symbol));
+        let last = ticker.last_price.as_f64();
+        view! {
+            <button class="mts-item" on:click=move |_| switch.run(symbol.clone())>
+                <span class="mts-symbol">{ticker.symbol.to_string()}</span>
+                <MiniCandles
+                    bars=Signal::derive(move || trail_bars(&directory.trail(&trail_symbol), TRAIL_TICKS_PER_BAR))
+                    width=40.0
+                    height=14.0
+                />
+                <span>{move || i18n.price(last, format.get())}</span>
+                // Touch from the BBO channel, live between ticker updates
+                <span class="mts-bbo" title="Best bid / ask">
+                    {move || {
+                        directory.bbo(&bbo_symbol).map(|bbo| {
+                            let format = format.get();
+                            format!("{}/{}", i18n.price(bbo.bid.as_f64(), format), i18n.price(bbo.ask.as_f64(), format))
+                        })
+                    }}
+                </span>
+                <span style=format!("color: {}", ticker.css_color())>{ticker.change_percent_str()}</span>
+            </button>
+        }
+    };
+
+    view! {
+        <Show when=move || tickers.with(|t| !t.is_empty())>
+            <div class="mini-ticker-strip">
+                {move || {
+                    let list = tickers.get();
+                    let duration = list.len().max(1) * SECONDS_PER_SYMBOL;
+                    // Two copies so the -50% keyframe lands on an identical frame
+                    let copies = list.iter().chain(&list).cloned().map(&item).collect_view();
+                    view! {
+                        <div class="mts-track" style=format!("animation-duration: {}s", duration)>
+                            {copies}
+                        </div>
+                    }
+                }}
+            </div>
+        </Show>
+    }
+}
@@ -0,0 +1,146 @@
+//! First-run helpers: skeleton empty states for panels still waiting on data
+//! and the guided tour overlay
+
+use dash_state::{use_app_state, use_i18n, Msg};
+use leptos::prelude::*;
+
+// ============================================================================
+// EMPTY STATE
+// ============================================================================
+
+/// Skeleton rows with a waiting message, shown until a panel's first data
+#[component]
+pub fn EmptyState(
+    message: Msg,
+    /// Placeholder rows drawn behind the message
+    #[prop(default = 6)]
+    rows: usize,
+) -> impl IntoView {
+    let i18n = use_i18n();
+
+    view! {
+        <div class="empty-state" role="status" aria-busy="true">
+            <div class="skeleton-rows" aria-hidden="true">
+                {(0..rows)
+                    .map(|i| {
+                        // Staggered widths so it reads as data rather than a block
+                        let width = 55 + (i * 37) % 40;
+                        view! { <div class="skeleton-row" style=format!("width: {}%", width) /> }
+                    })
+                    .collect_view()}
+            </div>
+            <span class="empty-message">{move || i18n.t(message)}</span>
+        </div>
+    }
+}
+
+// ============================================================================
+// GUIDED TOUR
+// ============================================================================
+
+/// One stop on the tour: the element it points at (CSS selector) and what
+/// it says
+struct TourStep {
+    target: &'static str,
+    title: Msg,
+    body: Msg,
+}
+
+const TOUR_STEPS: &[TourStep] = &[
+    TourStep { target: ".symbol-selector", title: Msg::TourSymbolTitle, body: Msg::TourSymbolBody },
+    TourStep { target: ".order-entry-container", title: Msg::TourOrderTitle, body: Msg::TourOrderBody },
+    TourStep { target: ".chart-container", title: Msg::TourAlertsTitle, body: Msg::TourAlertsBody },
+];
+
+/// Space between the highlighted element and the card
+const CARD_GAP_PX: f64 = 12.0;
+
+/// Viewport rect of the step's target (`None` when it isn't on screen, e.g.
+/// on another compact tab)
+fn target_rect(selector: &str) -> Option<(f64, f64, f64, f64)> {
+    let element = document().query_selector(selector).ok()??;
+    let rect = element.get_bounding_client_rect();
+    (rect.width() > 0.0 && rect.height() > 0.0).then(|| (rect.left(), rect.top(), rect.width(), rect.height()))
+}
+
+/// Overlay walking first-time users through the symbol selector, order
+/// entry and alerts; shown until finished or skipped
+#[component]
+pub fn GuidedTour() -> impl IntoView {
+    let state = use_app_state();
+    let ui = state.ui;
+    let i18n = state.i18n;
+    let step = RwSignal::new(0usize);
+    // Bumped on resize so the highlight follows its target
+    let layout_tick = RwSignal::new(0u32);
+
+    let handle = window_event_listener(leptos::ev::resize, move |_| layout_tick.update(|t| *t += 1));
+    // Targets aren't in the document until the dashboard has mounted
+    request_animation_frame(move || layout_tick.update(|t| *t += 1));
+    on_cleanup(move || handle.remove());
+
+    let finish_state = state.clone();
+    let finish = Callback::new(move |_: ()| {
+        finish_state.finish_tour();
+        step.set(0);
+    });
+    let next = move |_| {
+        if step.get_untracked() + 1 < TOUR_STEPS.len() {
+            step.update(|s| *s += 1);
+        } else {
+            finish.run(());
+        }
+    };
+
+    let rect = move || {
+        layout_tick.track();
+        TOUR_STEPS.get(step.get()).and_then(|s| target_rect(s.target))
+    };
+    let spotlight_style = move || match rect() {
+        Some((left, top, width, height)) => format!(
+            "left: {}px; top: {}px; width: {}px; height: {}px",
+            left - 4.0,
+            top - 4.0,
+            width + 8.0,
+            height + 8.0
+        ),
+        None => "display: none".to_string(),
+    };
+    // Below the target when there's room, otherwise above; centred without one
+    let card_style = move || match rect() {
+        Some((left, top, _, height)) => {
+            let viewport = window().inner_height().ok().and_then(|h| h.as_f64()).unwrap_or(0.0);
+            let below = top + height + CARD_GAP_PX;
+            if below + 160.0 < viewport {
+                format!("left: {}px; top: {}px", left.max(CARD_GAP_PX), below)
+            } else {
+                format!("left: {}px; bottom: {}px", left.max(CARD_GAP_PX), viewport - top + CARD_GAP_PX)
+            }
+        }
+        None => "left: 50%; top: 40%; transform: translate(-50%, -50%)".to_string(),
+    };
+
+    view! {
+        <Show when=move || !ui.with(|ui| ui.has_seen_tour) && !state.is_popout()>
+            <div class="tour-spotlight" style=spotlight_style />
+            <div class="tour-card" role="dialog" aria-modal="false" style=card_style>
+                {move || {
+                    let current = &TOUR_STEPS[step.get().min(TOUR_STEPS.len() - 1)];
+                    view! {
+                        <span class="tour-title">{i18n.t(current.title)}</span>
+                        <p class="tour-body">{i18n.t(current.body)}</p>
+                    }
+                }}
+                <div class="tour-footer">
+                    <span class="tour-progress">{move || format!("{} / {}", step.get() + 1, TOUR_STEPS.len())}</span>
+                    <button class="sb-preset" on:click=move |_| finish.run(())>{move || i18n.t(Msg::TourSkip)}</button>
+                    <button class="sb-preset active" on:click=next>
+                        {move || {
+                            if step.get() + 1 < TOUR_STEPS.len() { i18n.t(Msg::TourNext) } else { i18n.t(Msg::TourDone) }
+                        }}
+                    </button>
+                </div>
+            </div>
+        </Show>
+    }
+}
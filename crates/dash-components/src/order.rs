@@ -1,9 +1,37 @@
 //! Order book ladder display component
 
-use dash_core::{colors, OrderBookLevel, OrderSide};
-use dash_state::MarketState;
+use dash_core::{colors, export_order_book, ExportFormat, IcebergLevel, OrderBookLevel, OrderSide, TickFormat, TradeSide};
+use dash_state::{use_i18n, use_tick_format, CursorState, MarketState, Msg};
 use leptos::prelude::*;
 
+use crate::{focus_left, row_keydown, use_context_menu, ExportButtons};
+
+/// What the background depth bars are scaled by
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum BarScale {
+    /// Each level's own size against the largest level
+    #[default]
+    Level,
+    /// Running depth from the touch, like a standard exchange ladder
+    Cumulative,
+}
+
+impl BarScale {
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Level => "Size",
+            Self::Cumulative => "Cumulative",
+        }
+    }
+
+    pub fn toggled(&self) -> Self {
+        match self {
+            Self::Level => Self::Cumulative,
+            Self::Cumulative => Self::Level,
+        }
+    }
+}
+
 /// Order book configuration
 #[derive(Debug, Clone)]
 pub struct OrderBookConfig {
@@ -11,6 +39,8 @@ pub struct OrderBookConfig {
     pub show_spread: bool,
     pub show_totals: bool,
     pub compact: bool,
+    /// Initial bar scaling (switchable from the panel toolbar)
+    pub bar_scale: BarScale,
 }
 
 impl Default for OrderBookConfig {
@@ -20,6 +50,7 @@ impl Default for OrderBookConfig {
             show_spread: true,
             show_totals: true,
             compact: false,
+            bar_scale: BarScale::default(),
         }
     }
 }
@@ -31,45 +62,126 @@ impl OrderBookConfig {
             show_spread: true,
             show_totals: false,
             compact: true,
+            bar_scale: BarScale::default(),
         }
     }
 }
 
+/// Ladder row with its running depth and bar width (percent)
+#[derive(Debug, Clone, PartialEq)]
+struct LadderRow {
+    /// Price in ticks of the symbol, the row's identity across updates
+    tick: i64,
+    level: OrderBookLevel,
+    cumulative: f64,
+    bar_pct: f64,
+    /// Tooltip when the level is flagged as a possible iceberg
+    iceberg: Option<String>,
+}
+
 /// Main order book component
 #[component]
 pub fn OrderBook(
     #[prop(into)] market: MarketState,
     #[prop(optional)] config: Option<OrderBookConfig>,
+    /// Shared hover state (highlights the level on other panels)
+    #[prop(optional)] cursor: Option<CursorState>,
+    /// Level clicked: ticket side (bids buy, asks sell), price, and whether
+    /// the shift modifier was held
+    #[prop(optional, into)] on_price_click: Option<Callback<(TradeSide, f64, bool)>>,
 ) -> impl IntoView {
     let config = config.unwrap_or_default();
+    let i18n = use_i18n();
+    let tick_format = use_tick_format();
     let depth = config.depth;
     let show_spread = config.show_spread;
     let show_totals = config.show_totals;
+    let bar_scale = RwSignal::new(config.bar_scale);
 
     let orderbook = market.orderbook;
+    let icebergs = market.icebergs;
 
-    let max_qty = move || {
-        orderbook.get().map_or(1.0, |book| book.max_quantity().max(0.001))
-    };
+    // Both sides share one scale so bar lengths are comparable across the spread
+    let ladder = Memo::new(move |_| {
+        orderbook.with(|book| {
+            let Some(book) = book else {
+                return (Vec::new(), Vec::new());
+            };
+            let asks = book.cumulative_levels(OrderSide::Ask, depth);
+            let bids = book.cumulative_levels(OrderSide::Bid, depth);
 
-    let asks = move || {
-        orderbook.get().map_or(vec![], |book| {
-            let mut a: Vec<_> = book.asks.iter().take(depth).cloned().collect();
-            a.reverse();
-            a
+            let format = tick_format.get();
+            let scale = bar_scale.get();
+            let measure = move |(level, cumulative): &(OrderBookLevel, f64)| match scale {
+                BarScale::Level => level.quantity.as_f64(),
+                BarScale::Cumulative => *cumulative,
+            };
+            let max = asks.iter().chain(&bids).map(measure).fold(0.001_f64, f64::max);
+
+            let rows = |side: Vec<(OrderBookLevel, f64)>, book_side: TradeSide| -> Vec<LadderRow> {
+                side.into_iter()
+                    .map(|entry| LadderRow {
+                        tick: format.ticks(entry.0.price.as_f64()),
+                        bar_pct: (measure(&entry) / max * 100.0).min(100.0),
+                        iceberg: icebergs.with(|flagged| {
+                            flagged
+                                .iter()
+                                .find(|i| i.side == book_side && i.price == entry.0.price.as_f64())
+                                .map(IcebergLevel::describe)
+                        }),
+                        level: entry.0,
+                        cumulative: entry.1,
+                    })
+                    .collect()
+            };
+            (rows(asks, TradeSide::Sell), rows(bids, TradeSide::Buy))
         })
+    });
+
+    // Keyboard focus inside the book freezes the rows so they hold still
+    // while being read
+    let table_ref = NodeRef::<leptos::html::Div>::new();
+    let focused = RwSignal::new(false);
+    let snapshot = RwSignal::new(None::<(Vec<LadderRow>, Vec<LadderRow>)>);
+    Effect::new(move |_| {
+        let focus = focused.get();
+        if focus && snapshot.with_untracked(Option::is_none) {
+            snapshot.set(Some(ladder.get_untracked()));
+        } else if !focus {
+            snapshot.set(None);
+        }
+    });
+    let shown = Memo::new(move |_| snapshot.get().unwrap_or_else(|| ladder.get()));
+
+    // Asks are listed best-last so the spread sits in the middle
+    let asks = move || {
+        let mut a = shown.with(|(asks, _)| asks.clone());
+        a.reverse();
+        a
     };
 
-    let bids = move || {
-        orderbook.get().map_or(vec![], |book| {
-            book.bids.iter().take(depth).cloned().collect()
-        })
+    let bids = move || shown.with(|(_, bids)| bids.clone());
+
+    // Rows stay mounted while their price is in the book and follow it
+    // through a memo, so only levels that actually changed touch the DOM
+    let book_row = move |row: LadderRow, side: OrderSide| {
+        let tick = row.tick;
+        let live = Memo::new(move |prev: Option<&LadderRow>| {
+            shown
+                .with(|(asks, bids)| {
+                    let rows = if side == OrderSide::Ask { asks } else { bids };
+                    rows.iter().find(|r| r.tick == tick).cloned()
+                })
+                .or_else(|| prev.cloned())
+                .unwrap_or_else(|| row.clone())
+        });
+        view! { <OrderBookRow row=live side=side format=tick_format cursor=cursor on_click=on_price_click /> }
     };
 
     let spread_info = move || {
         orderbook.get().and_then(|book| {
             book.spread().zip(book.spread_percent()).map(|(s, pct)| {
-                (format!("{:.2}", s), format!("{:.3}%", pct))
+                (i18n.price(s, tick_format.get()), format!("{}%", i18n.number(pct, 3)))
             })
         })
     };
@@ -80,50 +192,76 @@ pub fn OrderBook(
         })
     };
 
+    let export = Callback::new(move |format: ExportFormat| {
+        orderbook.with_untracked(|book| book.as_ref().map(|book| export_order_book(book, format)))
+    });
+
     view! {
         <div class="orderbook">
-            <div class="ob-header">
-                <span class="ob-col price">"Price"</span>
-                <span class="ob-col size">"Size"</span>
-                <span class="ob-col total">"Total"</span>
+            <div class="panel-toolbar">
+                <button
+                    class="ob-scale"
+                    title="Scale depth bars by level size or cumulative depth"
+                    on:click=move |_| bar_scale.update(|s| *s = s.toggled())
+                >
+                    {move || format!("Bars: {}", bar_scale.get().label())}
+                </button>
+                <ExportButtons kind="orderbook" render=export />
             </div>
-
-            <div class="ob-asks">
-                <For
-                    each=asks
-                    key=|level| format!("{:.8}", level.price.as_f64())
-                    children=move |level| {
-                        let mq = max_qty();
-                        view! { <OrderBookRow level=level side=OrderSide::Ask max_qty=mq /> }
+            <div
+                class=move || if focused.get() { "ob-table paused" } else { "ob-table" }
+                role="table"
+                aria-label="Order book"
+                tabindex="0"
+                node_ref=table_ref
+                on:keydown=move |ev| row_keydown(&ev)
+                on:focusin=move |_| focused.set(true)
+                on:focusout=move |ev| {
+                    if let Some(table) = table_ref.get_untracked()
+                        && focus_left(&ev, &table)
+                    {
+                        focused.set(false);
                     }
-                />
-            </div>
-
-            {move || {
-                if show_spread {
-                    spread_info().map(|(spread, pct)| {
-                        view! {
-                            <div class="ob-spread">
-                                <span class="spread-label">"Spread"</span>
-                                <span class="spread-value">{spread}</span>
-                                <span class="spread-pct">{pct}</span>
-                            </div>
-                        }
-                    })
-                } else {
-                    None
                 }
-            }}
+            >
+                <div class="ob-header" role="row">
+                    <span class="ob-col price" role="columnheader">{move || i18n.t(Msg::Price)}</span>
+                    <span class="ob-col size" role="columnheader">{move || i18n.t(Msg::Size)}</span>
+                    <span class="ob-col sum" role="columnheader">{move || i18n.t(Msg::Sum)}</span>
+                    <span class="ob-col total" role="columnheader">{move || i18n.t(Msg::Total)}</span>
+                </div>
+
+                <div class="ob-asks" role="rowgroup" aria-label="Asks">
+                    <For
+                        each=asks
+                        key=|row| row.tick
+                        children=move |row| book_row(row, OrderSide::Ask)
+                    />
+                </div>
 
-            <div class="ob-bids">
-                <For
-                    each=bids
-                    key=|level| format!("{:.8}", level.price.as_f64())
-                    children=move |level| {
-                        let mq = max_qty();
-                        view! { <OrderBookRow level=level side=OrderSide::Bid max_qty=mq /> }
+                {move || {
+                    if show_spread {
+                        spread_info().map(|(spread, pct)| {
+                            view! {
+                                <div class="ob-spread" role="row">
+                                    <span class="spread-label" role="cell">{i18n.t(Msg::Spread)}</span>
+                                    <span class="spread-value" role="cell">{spread}</span>
+                                    <span class="spread-pct" role="cell">{pct}</span>
+                                </div>
+                            }
+                        })
+                    } else {
+                        None
                     }
-                />
+                }}
+
+                <div class="ob-bids" role="rowgroup" aria-label="Bids">
+                    <For
+                        each=bids
+                        key=|row| row.tick
+                        children=move |row| book_row(row, OrderSide::Bid)
+                    />
+                </div>
             </div>
 
             {move || {
@@ -132,15 +270,15 @@ pub fn OrderBook(
                         view! {
                             <div class="ob-totals">
                                 <div class="total-bid">
-                                    <span class="label">"Bid Total:"</span>
-                                    <span class="value" style=format!("color: {}", colors::BULL)>
-                                        {format!("{:.4}", bid_total)}
+                                    <span class="label">{i18n.t(Msg::BidTotal)}</span>
+                                    <span class="value" style=format!("color: {}", colors::css::BULL)>
+                                        {i18n.quantity(bid_total, tick_format.get())}
                                     </span>
                                 </div>
                                 <div class="total-ask">
-                                    <span class="label">"Ask Total:"</span>
-                                    <span class="value" style=format!("color: {}", colors::BEAR)>
-                                        {format!("{:.4}", ask_total)}
+                                    <span class="label">{i18n.t(Msg::AskTotal)}</span>
+                                    <span class="value" style=format!("color: {}", colors::css::BEAR)>
+                                        {i18n.quantity(ask_total, tick_format.get())}
                                     </span>
                                 </div>
                             </div>
@@ -156,40 +294,91 @@ pub fn OrderBook(
 
 #[component]
 fn OrderBookRow(
-    level: OrderBookLevel,
+    #[prop(into)] row: Signal<LadderRow>,
     side: OrderSide,
-    max_qty: f64,
+    /// Precision of the book's symbol
+    #[prop(into)]
+    format: Signal<TickFormat>,
+    cursor: Option<CursorState>,
+    on_click: Option<Callback<(TradeSide, f64, bool)>>,
 ) -> impl IntoView {
-    let price = level.price.as_f64();
-    let qty = level.quantity.as_f64();
-    let bar_pct = (qty / max_qty * 100.0).min(100.0);
-
-    let price_str = if price >= 1000.0 {
-        format!("{:.2}", price)
-    } else {
-        format!("{:.4}", price)
-    };
+    // The price is the row's key; everything else follows the level
+    let price = row.with_untracked(|r| r.level.price.as_f64());
 
-    let qty_str = format!("{:.4}", qty);
-    let value = price * qty;
-    let value_str = format!("{:.2}", value);
+    let i18n = use_i18n();
+    let price_str = move || i18n.price(price, format.get());
+    let qty_str = move || row.with(|r| i18n.quantity(r.level.quantity.as_f64(), format.get()));
+    let sum_str = move || row.with(|r| i18n.quantity(r.cumulative, format.get()));
+    let value_str = move || row.with(|r| i18n.price(price * r.level.quantity.as_f64(), format.get()));
 
     let (bar_color, text_color) = match side {
-        OrderSide::Bid => (colors::bull_alpha(0.2), colors::BULL),
-        OrderSide::Ask => (colors::bear_alpha(0.2), colors::BEAR),
+        OrderSide::Bid => (colors::css::bull_alpha(0.2), colors::css::BULL),
+        OrderSide::Ask => (colors::css::bear_alpha(0.2), colors::css::BEAR),
     };
 
-    let bg_style = format!(
-        "background: linear-gradient(to {}, {} {}%, transparent {}%)",
-        if side == OrderSide::Bid { "left" } else { "right" },
-        bar_color, bar_pct, bar_pct
-    );
+    let bg_style = move || {
+        let bar_pct = row.with(|r| r.bar_pct);
+        format!(
+            "background: linear-gradient(to {}, {} {}%, transparent {}%)",
+            if side == OrderSide::Bid { "left" } else { "right" },
+            bar_color, bar_pct, bar_pct
+        )
+    };
+
+    let row_class = move || {
+        let hovered = cursor.is_some_and(|c| c.is_price_hovered(price));
+        let class = match (hovered, on_click.is_some()) {
+            (true, true) => "ob-row clickable hovered",
+            (true, false) => "ob-row hovered",
+            (false, true) => "ob-row clickable",
+            (false, false) => "ob-row",
+        };
+        if row.with(|r| r.iceberg.is_some()) { format!("{} iceberg", class) } else { class.to_string() }
+    };
+    let iceberg = move || {
+        row.with(|r| r.iceberg.clone())
+            .map(|tip| view! { <span class="iceberg-mark" title=tip.clone() aria-label=tip>"🧊"</span> })
+    };
+    let ticket_side = match side {
+        OrderSide::Bid => TradeSide::Buy,
+        OrderSide::Ask => TradeSide::Sell,
+    };
+    let menu = use_context_menu();
 
     view! {
-        <div class="ob-row" style=bg_style>
-            <span class="ob-col price" style=format!("color: {}", text_color)>{price_str}</span>
-            <span class="ob-col size">{qty_str}</span>
-            <span class="ob-col total">{value_str}</span>
+        <div
+            class=row_class
+            style=bg_style
+            role="row"
+            tabindex="-1"
+            on:mouseenter=move |_| {
+                if let Some(c) = cursor {
+                    c.hover_price(Some(price));
+                }
+            }
+            on:mouseleave=move |_| {
+                if let Some(c) = cursor {
+                    c.hover_price(None);
+                }
+            }
+            on:click=move |ev| {
+                if let Some(on_click) = on_click {
+                    on_click.run((ticket_side, price, ev.shift_key()));
+                }
+            }
+            on:contextmenu=move |ev| {
+                if let Some(menu) = menu {
+                    menu.show_price(&ev, price);
+                }
+            }
+        >
+            <span class="ob-col price" role="cell" style=format!("color: {}", text_color)>
+                {iceberg}
+                {price_str}
+            </span>
+            <span class="ob-col size" role="cell">{qty_str}</span>
+            <span class="ob-col sum" role="cell">{sum_str}</span>
+            <span class="ob-col total" role="cell">{value_str}</span>
         </div>
     }
 }
\ No newline at end of file
@@ -0,0 +1,297 @@
+//! Paper-trading order entry form
+
+use dash_core::{colors, OrderRejection, OrderRequest, OrderType, SymbolInfo, TradeSide};
+use dash_state::{use_app_state, Msg, ShortcutAction};
+use leptos::prelude::*;
+
+use crate::use_shortcut;
+
+/// Parse a numeric input (empty or malformed → `None`)
+fn parse_field(text: &str) -> Option<f64> {
+    text.trim().parse::<f64>().ok().filter(|v| v.is_finite())
+}
+
+/// Order ticket: side, type, price/quantity with tick/lot validation,
+/// notional preview and balance check. Orders go to the paper engine over
+/// the WS command channel; engine rejections show inline. Prices clicked in
+/// the order book land here as limit orders.
+#[component]
+pub fn OrderEntry() -> impl IntoView {
+    let state = use_app_state();
+    let directory = state.symbols;
+    let symbol = state.market.symbol;
+    let ticker = state.market.ticker;
+    let portfolio = state.portfolio.clone();
+    let rpc = state.rpc.clone();
+    let i18n = state.i18n;
+
+    let side = RwSignal::new(TradeSide::Buy);
+    let order_type = RwSignal::new(OrderType::Limit);
+    let price = RwSignal::new(String::new());
+    let stop_price = RwSignal::new(String::new());
+    let quantity = RwSignal::new(String::new());
+
+    // Last submitted order and any error raised before it left the client
+    let submitted = RwSignal::new(None::<String>);
+    let local_error = RwSignal::new(None::<OrderRejection>);
+    let sequence = StoredValue::new(0u64);
+    let quantity_ref = NodeRef::<leptos::html::Input>::new();
+
+    // `b` / `s`: pick the side and jump to the quantity field
+    let focus_side = move |picked: TradeSide| {
+        side.set(picked);
+        if let Some(input) = quantity_ref.get_untracked() {
+            let _ = input.focus();
+        }
+    };
+    use_shortcut(ShortcutAction::FocusBuy, move || focus_side(TradeSide::Buy));
+    use_shortcut(ShortcutAction::FocusSell, move || focus_side(TradeSide::Sell));
+
+    let info = Memo::new(move |_| {
+        let symbol = symbol.get();
+        directory
+            .info(&symbol)
+            .unwrap_or_else(|| SymbolInfo { symbol, ..SymbolInfo::default() })
+    });
+    let reference = Memo::new(move |_| {
+        ticker
+            .with(|t| t.as_ref().map(|t| t.last_price.as_f64()))
+            .unwrap_or_else(|| info.with(|i| i.last_price))
+    });
+    let available = Memo::new(move |_| portfolio.available_quote());
+
+    // Prices from another market are meaningless: clear them on switch
+    Effect::new(move |_| {
+        symbol.track();
+        price.set(String::new());
+        stop_price.set(String::new());
+        submitted.set(None);
+        local_error.set(None);
+    });
+
+    let draft = move || OrderRequest {
+        client_id: String::new(),
+        symbol: symbol.get(),
+        side: side.get(),
+        order_type: order_type.get(),
+        price: parse_field(&price.get()),
+        stop_price: parse_field(&stop_price.get()),
+        quantity: parse_field(&quantity.get()).unwrap_or(0.0),
+    };
+
+    let notional = move || {
+        let order = draft();
+        (order.quantity > 0.0).then(|| order.notional(reference.get()))
+    };
+
+    // Live validation, shown once a quantity has been typed
+    let validation = move || {
+        if quantity.with(|q| q.trim().is_empty()) {
+            return None;
+        }
+        let order = draft();
+        info.with(|info| order.validate(info))
+            .and_then(|_| order.check_balance(reference.get(), available.get()))
+            .err()
+    };
+
+    let pending = Memo::new(move |_| {
+        submitted.with(|id| id.as_deref().is_some_and(|id| rpc.is_pending(id)))
+    });
+
+    let error = {
+        let rpc = state.rpc.clone();
+        move || {
+            local_error.get().or_else(|| {
+                submitted.with(|id| id.as_deref().and_then(|id| rpc.rejection(id)))
+            })
+        }
+    };
+
+    let ui = state.ui;
+    let submit_state = state.clone();
+    let submit = move || {
+        let n = sequence.get_value() + 1;
+        sequence.set_value(n);
+        let client_id = format!("paper-{}-{}", chrono::Utc::now().timestamp_millis(), n);
+
+        let order = OrderRequest {
+            client_id: client_id.clone(),
+            ..draft()
+        };
+        match submit_state.place_order(order) {
+            Ok(()) => {
+                local_error.set(None);
+                submitted.set(Some(client_id));
+            }
+            Err(reason) => {
+                local_error.set(Some(reason));
+                submitted.set(None);
+            }
+        }
+    };
+    let on_submit = {
+        let submit = submit.clone();
+        move |ev: leptos::ev::SubmitEvent| {
+            ev.prevent_default();
+            submit();
+        }
+    };
+
+    // Click-to-trade: take the picked level as a limit price
+    let ticket = state.ticket;
+    let since = ticket.with_untracked(|t| t.map_or(0, |t| t.seq));
+    Effect::new(move |_| {
+        let Some(fill) = ticket.get().filter(|fill| fill.seq > since) else {
+            return;
+        };
+        untrack(|| {
+            let decimals = info.with(SymbolInfo::price_decimals);
+            side.set(fill.side);
+            order_type.set(OrderType::Limit);
+            price.set(format!("{:.*}", decimals, fill.price));
+            if fill.submit {
+                submit();
+            } else if let Some(input) = quantity_ref.get_untracked() {
+                let _ = input.focus();
+            }
+        });
+    });
+
+    let fast_state = state.clone();
+
+    let use_last = move |_| {
+        let decimals = info.with(SymbolInfo::price_decimals);
+        let text = format!("{:.*}", decimals, reference.get());
+        match order_type.get() {
+            OrderType::Stop => stop_price.set(text),
+            _ => price.set(text),
+        }
+    };
+
+    let side_button = move |button_side: TradeSide| {
+        view! {
+            <button
+                type="button"
+                class=move || {
+                    if side.get() == button_side {
+                        format!("oe-side active {}", button_side.css_class())
+                    } else {
+                        "oe-side".to_string()
+                    }
+                }
+                on:click=move |_| side.set(button_side)
+            >
+                {move || i18n.side(button_side)}
+            </button>
+        }
+    };
+
+    view! {
+        <form class="order-entry" on:submit=on_submit>
+            <div class="oe-sides">
+                {side_button(TradeSide::Buy)}
+                {side_button(TradeSide::Sell)}
+            </div>
+
+            <div class="oe-types">
+                {OrderType::all()
+                    .iter()
+                    .map(|&ty| view! {
+                        <button
+                            type="button"
+                            class=move || if order_type.get() == ty { "oe-type active" } else { "oe-type" }
+                            on:click=move |_| order_type.set(ty)
+                        >
+                            {ty.label()}
+                        </button>
+                    })
+                    .collect_view()}
+            </div>
+
+            <Show when=move || order_type.get() == OrderType::Limit>
+                <label class="oe-field">
+                    <span class="oe-label">{move || i18n.t(Msg::Price)}</span>
+                    <input
+                        type="number"
+                        min="0"
+                        step=move || info.with(|i| i.tick_size.to_string())
+                        prop:value=move || price.get()
+                        on:input=move |ev| price.set(event_target_value(&ev))
+                    />
+                    <button type="button" class="oe-last" on:click=use_last>{move || i18n.t(Msg::Last)}</button>
+                </label>
+            </Show>
+
+            <Show when=move || order_type.get() == OrderType::Stop>
+                <label class="oe-field">
+                    <span class="oe-label">{move || i18n.t(Msg::Stop)}</span>
+                    <input
+                        type="number"
+                        min="0"
+                        step=move || info.with(|i| i.tick_size.to_string())
+                        prop:value=move || stop_price.get()
+                        on:input=move |ev| stop_price.set(event_target_value(&ev))
+                    />
+                    <button type="button" class="oe-last" on:click=use_last>{move || i18n.t(Msg::Last)}</button>
+                </label>
+            </Show>
+
+            <label class="oe-field">
+                <span class="oe-label">{move || i18n.t(Msg::Quantity)}</span>
+                <input
+                    type="number"
+                    node_ref=quantity_ref
+                    min=move || info.with(|i| i.min_quantity.to_string())
+                    step=move || info.with(|i| i.lot_size.to_string())
+                    prop:value=move || quantity.get()
+                    on:input=move |ev| quantity.set(event_target_value(&ev))
+                />
+            </label>
+
+            <div class="oe-summary">
+                <div class="oe-row">
+                    <span class="oe-label">{move || i18n.t(Msg::Notional)}</span>
+                    <span class="oe-value">
+                        {move || notional().map_or_else(|| "—".to_string(), |n| i18n.number(n, 2))}
+                    </span>
+                </div>
+                <div class="oe-row">
+                    <span class="oe-label">{move || i18n.t(Msg::Available)}</span>
+                    <span class="oe-value">{move || i18n.number(available.get(), 2)}</span>
+                </div>
+            </div>
+
+            {move || {
+                validation().or_else(error.clone()).map(|reason| view! {
+                    <div class="oe-error" style=format!("color: {}", colors::css::BEAR)>
+                        {reason.to_string()}
+                    </div>
+                })
+            }}
+
+            <label class="oe-fast" title="Shift-click a book price to send a limit order at once">
+                <input
+                    type="checkbox"
+                    prop:checked=move || ui.with(|ui| ui.fast_trading)
+                    on:change=move |_| fast_state.toggle_fast_trading()
+                />
+                {move || i18n.t(Msg::FastTrading)}
+            </label>
+
+            <button
+                type="submit"
+                class=move || format!("oe-submit {}", side.get().css_class())
+                disabled=move || pending.get() || validation().is_some()
+            >
+                {move || {
+                    if pending.get() {
+                        i18n.t(Msg::Sending).to_string()
+                    } else {
+                        format!("{} {}", i18n.side(side.get()), symbol.get())
+                    }
+                }}
+            </button>
+        </form>
+    }
+}
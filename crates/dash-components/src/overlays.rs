@@ -0,0 +1,103 @@
+//! Chart header menu for adding and tuning indicator overlays
+
+use dash_core::{ChartOverlay, Indicator};
+use dash_state::use_app_state;
+use leptos::prelude::*;
+
+/// "Studies" dropdown: one row per overlay (period, color, remove) plus a
+/// button for each preset
+#[component]
+pub fn OverlayMenu() -> impl IntoView {
+    let state = use_app_state();
+    let overlays = state.chart_overlays;
+    let open = RwSignal::new(false);
+
+    // Rows rebuild on every edit, so inputs commit on change rather than on
+    // each keystroke to keep focus and the color picker open
+    let rows_state = state.clone();
+    let rows = move || {
+        overlays
+            .get()
+            .into_iter()
+            .enumerate()
+            .map(|(index, overlay)| {
+                let (period_state, color_state, remove_state) =
+                    (rows_state.clone(), rows_state.clone(), rows_state.clone());
+                let std_dev = match overlay.indicator {
+                    Indicator::Bollinger { std_dev, .. } => Some(std_dev),
+                    _ => None,
+                };
+                view! {
+                    <div class="om-row">
+                        <span class="om-name" style=format!("color: {}", overlay.color)>
+                            {overlay.indicator.name()}
+                        </span>
+                        <input
+                            class="om-period"
+                            type="number"
+                            min="1"
+                            max=dash_core::MAX_INDICATOR_PERIOD
+                            aria-label="Period"
+                            prop:value=overlay.indicator.period().to_string()
+                            on:change=move |ev| {
+                                if let Ok(period) = event_target_value(&ev).parse::<usize>() {
+                                    period_state.edit_overlay(index, |o| o.indicator = o.indicator.with_period(period));
+                                }
+                            }
+                        />
+                        {std_dev.map(|k| view! { <span class="om-stddev">{format!("±{}σ", k)}</span> })}
+                        <input
+                            class="om-color"
+                            type="color"
+                            aria-label="Color"
+                            prop:value=overlay.color.clone()
+                            on:change=move |ev| {
+                                let color = event_target_value(&ev);
+                                color_state.edit_overlay(index, |o| o.color = color);
+                            }
+                        />
+                        <button class="om-remove" title="Remove" on:click=move |_| remove_state.remove_overlay(index)>
+                            "✕"
+                        </button>
+                    </div>
+                }
+            })
+            .collect_view()
+    };
+
+    view! {
+        <div class="overlay-menu">
+            <button
+                class=move || if overlays.with(|o| o.is_empty()) { "sb-preset" } else { "sb-preset active" }
+                title="Moving averages and Bollinger Bands"
+                aria-expanded=move || open.get().to_string()
+                on:click=move |_| open.update(|o| *o = !*o)
+            >
+                {move || match overlays.with(Vec::len) {
+                    0 => "Studies".to_string(),
+                    n => format!("Studies ({})", n),
+                }}
+            </button>
+            <Show when=move || open.get()>
+                <div class="ss-backdrop" on:click=move |_| open.set(false) />
+                <div class="om-dropdown">
+                    {rows.clone()}
+                    <div class="om-add">
+                        {ChartOverlay::presets()
+                            .into_iter()
+                            .map(|preset| {
+                                let state = state.clone();
+                                let label = format!("+ {}", preset.indicator.name());
+                                view! {
+                                    <button class="sb-preset" on:click=move |_| state.add_overlay(preset.clone())>
+                                        {label}
+                                    </button>
+                                }
+                            })
+                            .collect_view()}
+                    </div>
+                </div>
+            </Show>
+        </div>
+    }
+}
@@ -0,0 +1,168 @@
+//! Paper-trading Positions and Account panels
+
+use dash_charts::EquityChart;
+use dash_core::{colors, Position};
+use dash_state::{use_i18n, Msg, PortfolioState, QUOTE_ASSET};
+use leptos::prelude::*;
+
+// ============================================================================
+// POSITIONS
+// ============================================================================
+
+/// Open positions table with live PnL
+#[component]
+pub fn PositionsPanel(#[prop(into)] portfolio: PortfolioState) -> impl IntoView {
+    let positions = portfolio.positions;
+    let orders = portfolio.orders;
+    let i18n = use_i18n();
+
+    view! {
+        <div class="positions">
+            <div class="pos-header">
+                <span class="pos-col symbol">{move || i18n.t(Msg::Symbol)}</span>
+                <span class="pos-col side">{move || i18n.t(Msg::Side)}</span>
+                <span class="pos-col size">{move || i18n.t(Msg::Size)}</span>
+                <span class="pos-col entry">{move || i18n.t(Msg::Entry)}</span>
+                <span class="pos-col mark">{move || i18n.t(Msg::Mark)}</span>
+                <span class="pos-col pnl">{move || i18n.t(Msg::UnrealizedPnl)}</span>
+            </div>
+
+            <div class="pos-list">
+                <Show
+                    when=move || !positions.with(|p| p.is_empty())
+                    fallback=move || view! { <div class="pos-empty">{move || i18n.t(Msg::NoPositions)}</div> }
+                >
+                    <For
+                        each=move || positions.get()
+                        // Re-render a row whenever its size or mark moves
+                        key=|pos| format!("{}:{}:{}", pos.symbol, pos.quantity, pos.mark_price.as_f64())
+                        children=|pos| view! { <PositionRow position=pos /> }
+                    />
+                </Show>
+            </div>
+
+            <div class="pos-footer">
+                <span class="pos-label">{move || i18n.t(Msg::WorkingOrders)}</span>
+                <span class="pos-value">{move || orders.with(|o| o.len())}</span>
+            </div>
+        </div>
+    }
+}
+
+#[component]
+fn PositionRow(position: Position) -> impl IntoView {
+    let side_color = position
+        .side()
+        .map_or(colors::css::NEUTRAL, |side| side.css_color());
+    let pnl = position.unrealized_pnl();
+    let i18n = use_i18n();
+
+    view! {
+        <div class="pos-row">
+            <span class="pos-col symbol">{position.symbol.to_string()}</span>
+            <span class="pos-col side" style=format!("color: {}", side_color)>
+                {position.side_label()}
+            </span>
+            <span class="pos-col size">{i18n.number(position.quantity.abs(), 4)}</span>
+            <span class="pos-col entry">{i18n.number(position.entry_price.as_f64(), 2)}</span>
+            <span class="pos-col mark">{i18n.number(position.mark_price.as_f64(), 2)}</span>
+            <span class="pos-col pnl" style=format!("color: {}", position.pnl_color())>
+                {format!("{} ({}%)", i18n.signed(pnl, 2), i18n.signed(position.unrealized_pnl_percent(), 2))}
+            </span>
+        </div>
+    }
+}
+
+// ============================================================================
+// ACCOUNT
+// ============================================================================
+
+/// Account summary: equity in the quote asset, PnL, fees, exposure, equity
+/// curve and balances per asset
+#[component]
+pub fn AccountPanel(#[prop(into)] portfolio: PortfolioState) -> impl IntoView {
+    let equity = portfolio.account_equity;
+    let total_pnl = portfolio.total_pnl;
+    let exposure = portfolio.exposure;
+    let balances = portfolio.balances;
+    let equity_curve = portfolio.equity;
+    let account = portfolio.account;
+    let i18n = use_i18n();
+
+    let realized_today = move || account.get().map(|a| a.realized_pnl_today);
+    let realized_color = move || match realized_today() {
+        Some(pnl) if pnl < 0.0 => colors::css::BEAR,
+        Some(pnl) if pnl > 0.0 => colors::css::BULL,
+        _ => colors::css::TEXT_PRIMARY,
+    };
+    let fees_paid = move || account.get().map(|a| a.fees_paid);
+
+    let pnl_color = move || {
+        if total_pnl.get() >= 0.0 {
+            colors::css::BULL
+        } else {
+            colors::css::BEAR
+        }
+    };
+
+    // Gross exposure as a multiple of equity
+    let leverage = move || {
+        let eq = equity.get();
+        if eq > 0.0 { exposure.get() / eq } else { 0.0 }
+    };
+
+    view! {
+        <div class="account">
+            <div class="acct-stats">
+                <div class="acct-stat">
+                    <span class="acct-label">{move || format!("{} ({})", i18n.t(Msg::Equity), QUOTE_ASSET)}</span>
+                    <span class="acct-value">{move || i18n.number(equity.get(), 2)}</span>
+                </div>
+                <div class="acct-stat">
+                    <span class="acct-label">{move || i18n.t(Msg::RealizedToday)}</span>
+                    <span class="acct-value" style=move || format!("color: {}", realized_color())>
+                        {move || realized_today().map_or_else(|| "—".to_string(), |pnl| i18n.signed(pnl, 2))}
+                    </span>
+                </div>
+                <div class="acct-stat">
+                    <span class="acct-label">{move || i18n.t(Msg::FeesPaid)}</span>
+                    <span class="acct-value">
+                        {move || fees_paid().map_or_else(|| "—".to_string(), |fees| i18n.number(fees, 2))}
+                    </span>
+                </div>
+                <div class="acct-stat">
+                    <span class="acct-label">{move || i18n.t(Msg::TotalPnl)}</span>
+                    <span class="acct-value" style=move || format!("color: {}", pnl_color())>
+                        {move || i18n.signed(total_pnl.get(), 2)}
+                    </span>
+                </div>
+                <div class="acct-stat">
+                    <span class="acct-label">{move || i18n.t(Msg::Exposure)}</span>
+                    <span class="acct-value">
+                        {move || format!("{} ({}x)", i18n.number(exposure.get(), 2), i18n.number(leverage(), 2))}
+                    </span>
+                </div>
+            </div>
+
+            <div class="acct-equity-curve">
+                <EquityChart samples=equity_curve />
+            </div>
+
+            <div class="acct-balances">
+                <For
+                    each=move || balances.get()
+                    key=|b| format!("{}:{}:{}", b.asset, b.free, b.locked)
+                    children=|b| {
+                        view! {
+                            <div class="acct-balance">
+                                <span class="acct-asset">{b.asset.clone()}</span>
+                                <span class="acct-free">{format!("{:.4}", b.free)}</span>
+                                <span class="acct-locked">{format!("{:.4}", b.locked)}</span>
+                            </div>
+                        }
+                    }
+                />
+            </div>
+        </div>
+    }
+}
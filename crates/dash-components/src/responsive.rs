@@ -0,0 +1,67 @@
+//! Responsive layout controller: picks the desktop grid, the two-column
+//! tablet stack or the compact tabs from the viewport width
+
+use dash_state::{use_app_state, Breakpoint};
+use leptos::prelude::*;
+use std::cell::RefCell;
+use std::rc::Rc;
+use wasm_bindgen::JsCast;
+
+/// Track the viewport width and switch layouts as it crosses a breakpoint.
+/// Pop-out windows keep their single-panel layout.
+pub fn use_responsive_layout() {
+    let state = use_app_state();
+    if state.is_popout() {
+        return;
+    }
+
+    // Scroll offsets of elements hidden by an earlier switch, so they come
+    // back where they were when a later switch shows them again
+    let remembered = Rc::new(RefCell::new(Vec::<(web_sys::Element, i32)>::new()));
+
+    let update = move || {
+        let Some(width) = window().inner_width().ok().and_then(|w| w.as_f64()) else {
+            return;
+        };
+        let breakpoint = Breakpoint::from_width(width);
+        if state.ui.with_untracked(|ui| ui.breakpoint) == breakpoint {
+            return;
+        }
+        let offsets = scroll_offsets(remembered.take());
+        state.set_breakpoint(breakpoint);
+        // Re-apply once the new layout has been laid out
+        let restore = offsets.clone();
+        request_animation_frame(move || {
+            for (element, top) in restore {
+                element.set_scroll_top(top);
+            }
+        });
+        remembered.replace(offsets);
+    };
+
+    update.clone()();
+    let handle = window_event_listener(leptos::ev::resize, move |_| update());
+    on_cleanup(move || handle.remove());
+}
+
+/// Scrolled elements in the panel grid with their offsets; elements that are
+/// currently hidden keep the offset they had in `previous`
+fn scroll_offsets(previous: Vec<(web_sys::Element, i32)>) -> Vec<(web_sys::Element, i32)> {
+    let Ok(nodes) = document().query_selector_all(".dash-main *") else {
+        return previous;
+    };
+    let mut offsets: Vec<(web_sys::Element, i32)> = previous
+        .into_iter()
+        .filter(|(element, _)| element.is_connected() && element.client_height() == 0)
+        .collect();
+    for i in 0..nodes.length() {
+        let Some(element) = nodes.item(i).and_then(|n| n.dyn_into::<web_sys::Element>().ok()) else {
+            continue;
+        };
+        let top = element.scroll_top();
+        if top > 0 {
+            offsets.push((element, top));
+        }
+    }
+    offsets
+}
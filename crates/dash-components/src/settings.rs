@@ -0,0 +1,116 @@
+//! Settings dialog for preferences that don't fit in the status bar
+
+use dash_core::RenderBackend;
+use dash_state::{use_app_state, Locale, Msg};
+use leptos::prelude::*;
+
+use crate::SoundSettings;
+
+#[component]
+pub fn SettingsDialog() -> impl IntoView {
+    let state = use_app_state();
+    let toggle_state = state.clone();
+    let open = state.settings_open;
+    let ui = state.ui;
+    let i18n = state.i18n;
+    let pick_locale = Callback::new(move |code: String| {
+        state.set_locale(Locale::all().iter().copied().find(|l| l.code() == code))
+    });
+    let tour_state = toggle_state.clone();
+    let backend_state = toggle_state.clone();
+    let fit_state = toggle_state.clone();
+    let toggle_fit = Callback::new(move |_: ()| fit_state.toggle_fit_indicators());
+    let tooltip_state = toggle_state.clone();
+    let toggle_tooltip = Callback::new(move |_: ()| tooltip_state.toggle_tooltip());
+    let pick_backend = Callback::new(move |label: String| {
+        if let Some(&backend) = RenderBackend::all().iter().find(|b| b.label() == label) {
+            backend_state.set_chart_backend(backend);
+        }
+    });
+    let toggle_accessible = Callback::new(move |_: ()| toggle_state.toggle_accessible_mode());
+    let show_tour = Callback::new(move |_: ()| {
+        tour_state.restart_tour();
+        open.set(false);
+    });
+
+    let handle = window_event_listener(leptos::ev::keydown, move |ev| {
+        if open.get_untracked() && ev.key() == "Escape" {
+            ev.prevent_default();
+            open.set(false);
+        }
+    });
+    on_cleanup(move || handle.remove());
+
+    view! {
+        <Show when=move || open.get()>
+            <div class="shortcut-backdrop" on:click=move |_| open.set(false) />
+            <div class="shortcut-sheet" role="dialog" aria-label=move || i18n.t(Msg::Settings)>
+                <div class="shortcut-header">
+                    <span class="panel-title">{move || i18n.t(Msg::Settings)}</span>
+                    <button class="shortcut-close" title="Close" on:click=move |_| open.set(false)>"×"</button>
+                </div>
+                <div class="settings-section">
+                    <span class="settings-heading">{move || i18n.t(Msg::Language)}</span>
+                    <select
+                        class="settings-select"
+                        aria-label=move || i18n.t(Msg::Language)
+                        prop:value=move || ui.with(|ui| ui.locale.map_or("", |l| l.code()))
+                        on:change=move |ev| pick_locale.run(event_target_value(&ev))
+                    >
+                        <option value="">{move || i18n.t(Msg::AutoLanguage)}</option>
+                        {Locale::all()
+                            .iter()
+                            .map(|locale| view! { <option value=locale.code()>{locale.native_name()}</option> })
+                            .collect_view()}
+                    </select>
+                </div>
+                <div class="settings-section">
+                    <span class="settings-heading">{move || i18n.t(Msg::Accessibility)}</span>
+                    <label class="settings-option">
+                        <input
+                            type="checkbox"
+                            prop:checked=move || ui.with(|ui| ui.accessible_mode)
+                            on:change=move |_| toggle_accessible.run(())
+                        />
+                        {move || i18n.t(Msg::AccessibleMode)}
+                    </label>
+                    <button class="sb-preset" on:click=move |_| show_tour.run(())>{move || i18n.t(Msg::ShowTour)}</button>
+                </div>
+                <div class="settings-section">
+                    <span class="settings-heading">{move || i18n.t(Msg::Chart)}</span>
+                    <select
+                        class="settings-select"
+                        aria-label=move || i18n.t(Msg::ChartRenderer)
+                        prop:value=move || ui.with(|ui| ui.chart_backend.label())
+                        on:change=move |ev| pick_backend.run(event_target_value(&ev))
+                    >
+                        {RenderBackend::all()
+                            .iter()
+                            .map(|backend| view! { <option value=backend.label()>{backend.label()}</option> })
+                            .collect_view()}
+                    </select>
+                    <label class="settings-option">
+                        <input
+                            type="checkbox"
+                            prop:checked=move || ui.with(|ui| ui.fit_indicators)
+                            on:change=move |_| toggle_fit.run(())
+                        />
+                        {move || i18n.t(Msg::FitIndicators)}
+                    </label>
+                    <label class="settings-option">
+                        <input
+                            type="checkbox"
+                            prop:checked=move || ui.with(|ui| !ui.hide_tooltip)
+                            on:change=move |_| toggle_tooltip.run(())
+                        />
+                        {move || i18n.t(Msg::CandleTooltip)}
+                    </label>
+                </div>
+                <div class="settings-section">
+                    <span class="settings-heading">{move || i18n.t(Msg::SoundAlerts)}</span>
+                    <SoundSettings />
+                </div>
+            </div>
+        </Show>
+    }
+}
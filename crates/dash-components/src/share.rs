@@ -0,0 +1,333 @@
+//! Panel snapshots: render the chart, depth chart or order book to a PNG
+//! stamped with the symbol and time, then copy it to the clipboard (or
+//! download it)
+
+use dash_core::{colors::Palette, snapshot_filename, snapshot_watermark, OrderBookLevel, OrderBookSnapshot, TickFormat};
+use dash_state::{use_app_state, I18n, NotificationSource, Severity};
+use js_sys::{Array, Object, Promise, Reflect};
+use leptos::prelude::*;
+use wasm_bindgen::{JsCast, JsValue};
+use wasm_bindgen_futures::{spawn_local, JsFuture};
+use web_sys::{Blob, CanvasRenderingContext2d, Element, HtmlCanvasElement, HtmlImageElement};
+
+use crate::save_blob;
+
+/// Panels with a share action
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ShareTarget {
+    Chart,
+    /// Depth curve or book heatmap, whichever is showing
+    Depth,
+    OrderBook,
+}
+
+impl ShareTarget {
+    /// File name prefix
+    fn kind(&self) -> &'static str {
+        match self {
+            Self::Chart => "chart",
+            Self::Depth => "depth",
+            Self::OrderBook => "book",
+        }
+    }
+
+    /// The panel's SVG layer (drawn over any canvas layers)
+    fn svg_selector(&self) -> Option<&'static str> {
+        match self {
+            Self::Chart => Some("svg.candlestick-chart"),
+            Self::Depth => Some("svg.depth-chart"),
+            Self::OrderBook => None,
+        }
+    }
+
+    fn label(&self) -> &'static str {
+        match self {
+            Self::Chart => "Chart",
+            Self::Depth => "Depth chart",
+            Self::OrderBook => "Order book",
+        }
+    }
+}
+
+/// Book levels drawn on each side
+const BOOK_ROWS: usize = 15;
+const BOOK_ROW_PX: f64 = 20.0;
+const BOOK_WIDTH_PX: f64 = 360.0;
+/// Strip under the snapshot carrying the watermark
+const WATERMARK_PX: f64 = 28.0;
+
+/// Where a snapshot ended up
+enum Shared {
+    Copied,
+    Downloaded(String),
+}
+
+/// Header button that snapshots its panel
+#[component]
+pub fn ShareButton(target: ShareTarget) -> impl IntoView {
+    let state = use_app_state();
+    let busy = RwSignal::new(false);
+
+    let share = move |ev: leptos::ev::MouseEvent| {
+        if busy.get_untracked() {
+            return;
+        }
+        let panel = ev
+            .target()
+            .and_then(|t| t.dyn_into::<Element>().ok())
+            .and_then(|el| el.closest(".panel").ok().flatten());
+        let book = state.market.orderbook.get_untracked();
+        let symbol = state.market.symbol.get_untracked();
+        let text = BookText { i18n: state.i18n, format: state.symbols.tick_format(&symbol) };
+        let palette = state.ui.with_untracked(|ui| ui.theme.palette());
+        let notifications = state.notifications;
+
+        busy.set(true);
+        spawn_local(async move {
+            let now = chrono::Utc::now().timestamp_millis();
+            let watermark = snapshot_watermark(&symbol, now);
+            let filename = snapshot_filename(target.kind(), &symbol, now);
+            let result = match render(target, panel, book, text, palette, &watermark).await {
+                Ok(blob) => deliver(&blob, filename).await,
+                Err(e) => Err(e),
+            };
+            busy.set(false);
+            match result {
+                Ok(Shared::Copied) => notifications.notify(
+                    Severity::Success,
+                    NotificationSource::System,
+                    format!("{} snapshot copied to the clipboard", target.label()),
+                ),
+                Ok(Shared::Downloaded(name)) => notifications.notify(
+                    Severity::Info,
+                    NotificationSource::System,
+                    format!("{} snapshot saved as {}", target.label(), name),
+                ),
+                Err(e) => {
+                    tracing::warn!("{} snapshot failed: {:?}", target.label(), e);
+                    notifications.notify(
+                        Severity::Warning,
+                        NotificationSource::System,
+                        format!("Couldn't capture the {}", target.label().to_lowercase()),
+                    )
+                }
+            };
+        });
+    };
+
+    view! {
+        <button
+            class="panel-control"
+            title="Copy a PNG snapshot (downloads it if the clipboard is unavailable)"
+            disabled=move || busy.get()
+            on:click=share
+        >
+            "📷"
+        </button>
+    }
+}
+
+/// Draw the panel onto a canvas with the watermark strip underneath
+async fn render(
+    target: ShareTarget,
+    panel: Option<Element>,
+    book: Option<OrderBookSnapshot>,
+    text: BookText,
+    palette: &Palette,
+    watermark: &str,
+) -> Result<Blob, JsValue> {
+    // Charts paint onto canvas layers under their SVG (the heatmap is all
+    // canvas); everything is composed in stacking order
+    let layers: Vec<HtmlCanvasElement> = match (target.svg_selector(), &panel) {
+        (Some(_), Some(panel)) => {
+            let found = panel.query_selector_all(".chart-layers canvas")?;
+            (0..found.length())
+                .filter_map(|i| found.item(i)?.dyn_into().ok())
+                .collect()
+        }
+        _ => Vec::new(),
+    };
+    let svg = target
+        .svg_selector()
+        .and_then(|selector| panel.as_ref()?.query_selector(selector).ok().flatten());
+    let frame: Option<&Element> = svg.as_ref().or_else(|| layers.first().map(|c| c.as_ref()));
+    let (width, height) = match (target, frame) {
+        (ShareTarget::OrderBook, _) => (BOOK_WIDTH_PX, (2 * BOOK_ROWS + 1) as f64 * BOOK_ROW_PX),
+        (_, Some(frame)) => {
+            let rect = frame.get_bounding_client_rect();
+            (rect.width(), rect.height())
+        }
+        (_, None) => return Err(JsValue::from_str("chart not mounted")),
+    };
+    if width <= 0.0 || height <= 0.0 {
+        return Err(JsValue::from_str("panel is hidden"));
+    }
+
+    // Full device resolution so the snapshot is as sharp as the screen
+    let scale = window().device_pixel_ratio().max(1.0);
+    let canvas: HtmlCanvasElement = document().create_element("canvas")?.dyn_into()?;
+    canvas.set_width((width * scale).round() as u32);
+    canvas.set_height(((height + WATERMARK_PX) * scale).round() as u32);
+    let ctx: CanvasRenderingContext2d = canvas
+        .get_context("2d")?
+        .ok_or_else(|| JsValue::from_str("no 2d context"))?
+        .dyn_into()?;
+    ctx.scale(scale, scale)?;
+    ctx.set_fill_style_str(palette.bg_panel);
+    ctx.fill_rect(0.0, 0.0, width, height + WATERMARK_PX);
+
+    if target == ShareTarget::OrderBook {
+        let book = book.ok_or_else(|| JsValue::from_str("no order book yet"))?;
+        draw_book(&ctx, &book, text, palette, width)?;
+    } else {
+        for layer in &layers {
+            ctx.draw_image_with_html_canvas_element_and_dw_and_dh(layer, 0.0, 0.0, width, height)?;
+        }
+        if let Some(svg) = &svg {
+            let image = load_svg(svg, palette, width, height).await?;
+            ctx.draw_image_with_html_image_element_and_dw_and_dh(&image, 0.0, 0.0, width, height)?;
+        }
+    }
+
+    ctx.set_fill_style_str(palette.border);
+    ctx.fill_rect(0.0, height, width, 1.0);
+    ctx.set_font("12px sans-serif");
+    ctx.set_text_align("left");
+    ctx.set_fill_style_str(palette.text_muted);
+    ctx.fill_text(watermark, 8.0, height + WATERMARK_PX / 2.0 + 4.0)?;
+
+    canvas_png(&canvas).await
+}
+
+/// Rasterize an SVG element through an `<img>` loaded from its markup
+async fn load_svg(svg: &Element, palette: &Palette, width: f64, height: f64) -> Result<HtmlImageElement, JsValue> {
+    // The serialized copy has no layout to size against, so pin it, and no
+    // :root to resolve var(--...) colors from, so carry the theme along
+    let copy: Element = svg.clone_node_with_deep(true)?.dyn_into()?;
+    copy.set_attribute("style", &palette.css_vars())?;
+    copy.set_attribute("width", &width.to_string())?;
+    copy.set_attribute("height", &height.to_string())?;
+    let markup = web_sys::XmlSerializer::new()?.serialize_to_string(&copy)?;
+
+    let options = web_sys::BlobPropertyBag::new();
+    options.set_type("image/svg+xml");
+    let blob = Blob::new_with_str_sequence_and_options(&Array::of1(&JsValue::from_str(&markup)), &options)?;
+    let url = web_sys::Url::create_object_url_with_blob(&blob)?;
+
+    let image = HtmlImageElement::new()?;
+    let loaded = Promise::new(&mut |resolve, reject| {
+        image.set_onload(Some(&resolve));
+        image.set_onerror(Some(&reject));
+    });
+    image.set_src(&url);
+    let result = JsFuture::from(loaded).await;
+    web_sys::Url::revoke_object_url(&url)?;
+    result?;
+    Ok(image)
+}
+
+/// Localized book numbers at the symbol's precision
+#[derive(Clone, Copy)]
+struct BookText {
+    i18n: I18n,
+    format: TickFormat,
+}
+
+impl BookText {
+    fn price(&self, price: f64) -> String {
+        self.i18n.price(price, self.format)
+    }
+
+    fn quantity(&self, qty: f64) -> String {
+        self.i18n.quantity(qty, self.format)
+    }
+}
+
+/// Asks above the spread, bids below, with depth bars scaled to the largest
+/// level shown
+fn draw_book(
+    ctx: &CanvasRenderingContext2d,
+    book: &OrderBookSnapshot,
+    text: BookText,
+    palette: &Palette,
+    width: f64,
+) -> Result<(), JsValue> {
+    let asks = &book.asks[..book.asks.len().min(BOOK_ROWS)];
+    let bids = &book.bids[..book.bids.len().min(BOOK_ROWS)];
+    let max_qty = asks.iter().chain(bids).map(|l| l.quantity.as_f64()).fold(0.0, f64::max);
+
+    ctx.set_font("12px monospace");
+    // Best ask sits just above the spread row
+    for (i, level) in asks.iter().enumerate() {
+        let row = BOOK_ROWS - 1 - i;
+        draw_level(ctx, level, text, row as f64 * BOOK_ROW_PX, width, max_qty, palette.bear, palette)?;
+    }
+    let spread_y = BOOK_ROWS as f64 * BOOK_ROW_PX;
+    if let Some(spread) = book.spread() {
+        ctx.set_text_align("center");
+        ctx.set_fill_style_str(palette.text_muted);
+        ctx.fill_text(&format!("Spread {}", text.price(spread)), width / 2.0, spread_y + 14.0)?;
+    }
+    for (i, level) in bids.iter().enumerate() {
+        let y = spread_y + (i + 1) as f64 * BOOK_ROW_PX;
+        draw_level(ctx, level, text, y, width, max_qty, palette.bull, palette)?;
+    }
+    Ok(())
+}
+
+#[allow(clippy::too_many_arguments)]
+fn draw_level(
+    ctx: &CanvasRenderingContext2d,
+    level: &OrderBookLevel,
+    text: BookText,
+    y: f64,
+    width: f64,
+    max_qty: f64,
+    color: &str,
+    palette: &Palette,
+) -> Result<(), JsValue> {
+    let qty = level.quantity.as_f64();
+    if max_qty > 0.0 {
+        let bar = qty / max_qty * width;
+        ctx.set_global_alpha(0.15);
+        ctx.set_fill_style_str(color);
+        ctx.fill_rect(width - bar, y + 1.0, bar, BOOK_ROW_PX - 2.0);
+        ctx.set_global_alpha(1.0);
+    }
+    ctx.set_text_align("left");
+    ctx.set_fill_style_str(color);
+    ctx.fill_text(&text.price(level.price.as_f64()), 8.0, y + 14.0)?;
+    ctx.set_text_align("right");
+    ctx.set_fill_style_str(palette.text_primary);
+    ctx.fill_text(&text.quantity(qty), width - 8.0, y + 14.0)
+}
+
+async fn canvas_png(canvas: &HtmlCanvasElement) -> Result<Blob, JsValue> {
+    let encoded = Promise::new(&mut |resolve, reject| {
+        if let Err(e) = canvas.to_blob(&resolve) {
+            let _ = reject.call1(&JsValue::NULL, &e);
+        }
+    });
+    JsFuture::from(encoded).await?.dyn_into()
+}
+
+/// Copy the PNG to the clipboard, falling back to a download where images
+/// can't be written (no `ClipboardItem`, or permission denied)
+async fn deliver(blob: &Blob, filename: String) -> Result<Shared, JsValue> {
+    match copy_png(blob).await {
+        Ok(()) => Ok(Shared::Copied),
+        Err(e) => {
+            tracing::debug!("Clipboard write refused, downloading instead: {:?}", e);
+            save_blob(&filename, blob)?;
+            Ok(Shared::Downloaded(filename))
+        }
+    }
+}
+
+async fn copy_png(blob: &Blob) -> Result<(), JsValue> {
+    let items = Object::new();
+    Reflect::set(&items, &JsValue::from_str("image/png"), blob)?;
+    let item = web_sys::ClipboardItem::new_with_record_from_str_to_blob_promise(&items)?;
+    JsFuture::from(window().navigator().clipboard().write(&Array::of1(&item))).await?;
+    Ok(())
+}
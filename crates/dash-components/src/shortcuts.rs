@@ -0,0 +1,159 @@
+//! Global keyboard shortcuts and the remappable cheat-sheet overlay
+
+use dash_state::{use_app_state, AppState, CompactTab, Msg, ShortcutAction};
+use leptos::prelude::*;
+
+/// Is the key press meant for a text field rather than the dashboard?
+fn typing_into_field(ev: &leptos::ev::KeyboardEvent) -> bool {
+    let target: leptos::web_sys::Element = event_target(ev);
+    matches!(target.tag_name().as_str(), "INPUT" | "TEXTAREA" | "SELECT")
+}
+
+fn run_action(state: &AppState, action: ShortcutAction) {
+    let compact_tab = match action {
+        ShortcutAction::TabChart => Some(CompactTab::Chart),
+        ShortcutAction::TabBook => Some(CompactTab::Book),
+        ShortcutAction::TabTrades => Some(CompactTab::Trades),
+        // The order ticket lives on the account tab
+        ShortcutAction::TabAccount | ShortcutAction::FocusBuy | ShortcutAction::FocusSell => {
+            Some(CompactTab::Account)
+        }
+        ShortcutAction::SymbolSearch | ShortcutAction::CheatSheet => None,
+    };
+    if let Some(tab) = compact_tab {
+        state.set_compact_tab(tab);
+    }
+    if action == ShortcutAction::CheatSheet {
+        state.shortcuts.cheat_sheet.set(true);
+    }
+    state.shortcuts.fire(action);
+}
+
+/// Install the window-level key listener that maps bound keys to actions.
+/// Keys typed into inputs and modified chords are left alone.
+pub fn use_keyboard_shortcuts() {
+    let state = use_app_state();
+    let shortcuts = state.shortcuts;
+
+    let handle = window_event_listener(leptos::ev::keydown, move |ev| {
+        // The cheat sheet owns the keyboard while it's open
+        if ev.default_prevented()
+            || ev.ctrl_key()
+            || ev.meta_key()
+            || ev.alt_key()
+            || shortcuts.cheat_sheet.get_untracked()
+            || typing_into_field(&ev)
+        {
+            return;
+        }
+        let key = ev.key();
+        if let Some(action) = shortcuts.bindings.with_untracked(|b| b.action_for(&key)) {
+            ev.prevent_default();
+            run_action(&state, action);
+        }
+    });
+    on_cleanup(move || handle.remove());
+}
+
+/// Run `handler` each time `action` fires after this call
+pub fn use_shortcut(action: ShortcutAction, handler: impl Fn() + 'static) {
+    let fired = use_app_state().shortcuts.fired;
+    let since = fired.with_untracked(|f| f.map_or(0, |(seq, _)| seq));
+
+    Effect::new(move |_| {
+        if let Some((seq, fired_action)) = fired.get()
+            && seq > since
+            && fired_action == action
+        {
+            untrack(&handler);
+        }
+    });
+}
+
+/// Overlay listing every shortcut; click a key to rebind it
+#[component]
+pub fn ShortcutCheatSheet() -> impl IntoView {
+    let state = use_app_state();
+    let (shortcuts, i18n) = (state.shortcuts, state.i18n);
+    let open = shortcuts.cheat_sheet;
+    let bindings = shortcuts.bindings;
+    let capturing = RwSignal::new(None::<ShortcutAction>);
+
+    let close = move || {
+        capturing.set(None);
+        open.set(false);
+    };
+
+    let handle = window_event_listener(leptos::ev::keydown, move |ev| {
+        // Skip the press that opened the sheet
+        if !open.get_untracked() || ev.default_prevented() {
+            return;
+        }
+        let key = ev.key();
+        ev.prevent_default();
+
+        match capturing.get_untracked() {
+            Some(_) if key == "Escape" => capturing.set(None),
+            // Bare modifiers aren't bindable on their own
+            Some(_) if matches!(key.as_str(), "Shift" | "Control" | "Alt" | "Meta") => {}
+            Some(action) => {
+                shortcuts.rebind(action, key);
+                capturing.set(None);
+            }
+            None => {
+                let toggles = bindings.with_untracked(|b| b.key_for(ShortcutAction::CheatSheet) == key);
+                if key == "Escape" || toggles {
+                    close();
+                }
+            }
+        }
+    });
+    on_cleanup(move || handle.remove());
+
+    view! {
+        <Show when=move || open.get()>
+            <div class="shortcut-backdrop" on:click=move |_| close() />
+            <div class="shortcut-sheet" role="dialog" aria-label=move || i18n.t(Msg::KeyboardShortcuts)>
+                <div class="shortcut-header">
+                    <span class="panel-title">{move || i18n.t(Msg::KeyboardShortcuts)}</span>
+                    <button class="shortcut-close" title="Close" on:click=move |_| close()>"×"</button>
+                </div>
+                <ul class="shortcut-list">
+                    {ShortcutAction::all()
+                        .iter()
+                        .map(|&action| view! {
+                            <li class="shortcut-row">
+                                <span class="shortcut-label">{action.label()}</span>
+                                <button
+                                    class=move || {
+                                        if capturing.get() == Some(action) { "shortcut-key capturing" } else { "shortcut-key" }
+                                    }
+                                    title="Click, then press a key"
+                                    on:click=move |_| capturing.set(Some(action))
+                                >
+                                    {move || {
+                                        if capturing.get() == Some(action) {
+                                            i18n.t(Msg::PressKey).to_string()
+                                        } else {
+                                            bindings.with(|b| b.key_for(action).to_string())
+                                        }
+                                    }}
+                                </button>
+                            </li>
+                        })
+                        .collect_view()}
+                </ul>
+                <div class="shortcut-footer">
+                    <span class="shortcut-hint">{move || i18n.t(Msg::ShortcutTabsHint)}</span>
+                    <button
+                        class="shortcut-reset"
+                        disabled=move || !bindings.with(|b| b.is_customized())
+                        on:click=move |_| shortcuts.reset_bindings()
+                    >
+                        {move || i18n.t(Msg::ResetDefaults)}
+                    </button>
+                </div>
+            </div>
+        </Show>
+    }
+}
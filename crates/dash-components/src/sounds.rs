@@ -0,0 +1,174 @@
+//! Synthesized alert sounds (Web Audio) and the sound settings section
+
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use dash_state::{use_app_state, SoundCue, SoundEvent, SoundState};
+use leptos::prelude::*;
+use leptos::web_sys::{AudioContext, OscillatorType};
+
+/// One note: frequency (Hz), start offset and length (seconds)
+type Tone = (f32, f64, f64);
+
+/// Waveform and notes for each event; kept short and distinct by contour
+fn voice(event: SoundEvent) -> (OscillatorType, &'static [Tone]) {
+    match event {
+        // Low double thump
+        SoundEvent::WhaleTrade => (OscillatorType::Triangle, &[(196.0, 0.0, 0.12), (147.0, 0.14, 0.18)]),
+        // Rising triple beep
+        SoundEvent::AlertTriggered => (
+            OscillatorType::Square,
+            &[(660.0, 0.0, 0.08), (880.0, 0.11, 0.08), (1100.0, 0.22, 0.12)],
+        ),
+        // Bright two-note chime
+        SoundEvent::OrderFilled => (OscillatorType::Sine, &[(1046.5, 0.0, 0.1), (1568.0, 0.09, 0.22)]),
+        // Falling sweep
+        SoundEvent::Disconnected => (OscillatorType::Sawtooth, &[(523.0, 0.0, 0.15), (392.0, 0.16, 0.15), (262.0, 0.32, 0.3)]),
+    }
+}
+
+/// Plays alert sounds through a lazily created `AudioContext` (browsers only
+/// allow audio after a user gesture, so creating it up front would stay
+/// suspended)
+#[derive(Clone, Default)]
+pub struct SoundAlerts {
+    context: Rc<RefCell<Option<AudioContext>>>,
+}
+
+impl SoundAlerts {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn context(&self) -> Option<AudioContext> {
+        let mut slot = self.context.borrow_mut();
+        if slot.is_none() {
+            *slot = AudioContext::new().ok();
+        }
+        let context = slot.clone()?;
+        let _ = context.resume();
+        Some(context)
+    }
+
+    /// Play `event` at `volume` (0.0–1.0)
+    pub fn play(&self, event: SoundEvent, volume: f64) {
+        let Some(context) = self.context() else {
+            return;
+        };
+        let (wave, tones) = voice(event);
+        let now = context.current_time();
+        // Square and sawtooth are much louder than sine at the same gain
+        let level = volume as f32 * if matches!(wave, OscillatorType::Sine | OscillatorType::Triangle) { 0.4 } else { 0.15 };
+
+        for &(frequency, offset, length) in tones {
+            let (Ok(oscillator), Ok(gain)) = (context.create_oscillator(), context.create_gain()) else {
+                return;
+            };
+            let start = now + offset;
+            let end = start + length;
+            oscillator.set_type(wave);
+            oscillator.frequency().set_value(frequency);
+            // Short attack and exponential tail so notes don't click
+            let envelope = gain.gain();
+            let _ = envelope.set_value_at_time(0.0001, start);
+            let _ = envelope.exponential_ramp_to_value_at_time(level.max(0.0001), start + 0.01);
+            let _ = envelope.exponential_ramp_to_value_at_time(0.0001, end);
+
+            let _ = oscillator.connect_with_audio_node(&gain);
+            let _ = gain.connect_with_audio_node(&context.destination());
+            let _ = oscillator.start_with_when(start);
+            let _ = oscillator.stop_with_when(end + 0.02);
+        }
+    }
+}
+
+/// Play each cue raised on the app state, honouring the volume and mute
+/// preferences. Pop-out windows stay silent so alerts don't double up.
+pub fn use_sound_alerts() {
+    let state = use_app_state();
+    if state.is_popout() {
+        return;
+    }
+    let sounds = state.sounds;
+    let player = SoundAlerts::new();
+    let since = sounds.cue.with_untracked(|c| c.map_or(0, |c| c.seq));
+
+    Effect::new(move |_| {
+        let Some(SoundCue { seq, event, preview }) = sounds.cue.get() else {
+            return;
+        };
+        if seq <= since {
+            return;
+        }
+        let volume = sounds.prefs.with_untracked(|p| {
+            if preview { Some(p.channel(event).volume) } else { p.volume_for(event) }
+        });
+        if let Some(volume) = volume {
+            player.play(event, volume);
+        }
+    });
+}
+
+/// Per-event mute, volume and preview rows for the settings dialog
+#[component]
+pub fn SoundSettings() -> impl IntoView {
+    let sounds: SoundState = use_app_state().sounds;
+    let prefs = sounds.prefs;
+
+    view! {
+        <ul class="shortcut-list">
+            {SoundEvent::all()
+                .iter()
+                .map(|&event| {
+                    let channel = move || prefs.with(|p| p.channel(event));
+                    view! {
+                        <li class="shortcut-row sound-row">
+                            <label class="shortcut-label sound-label">
+                                <input
+                                    type="checkbox"
+                                    prop:checked=move || !channel().muted
+                                    on:change=move |_| prefs.update(|p| p.toggle_event(event))
+                                />
+                                {event.label()}
+                            </label>
+                            <input
+                                class="sound-volume"
+                                type="range"
+                                min="0"
+                                max="100"
+                                aria-label=format!("{} volume", event.label())
+                                prop:value=move || (channel().volume * 100.0).round().to_string()
+                                prop:disabled=move || channel().muted
+                                on:input=move |ev| {
+                                    if let Ok(percent) = event_target_value(&ev).parse::<f64>() {
+                                        prefs.update(|p| p.set_volume(event, percent / 100.0));
+                                    }
+                                }
+                            />
+                            <button class="shortcut-key" title="Preview" on:click=move |_| sounds.preview(event)>
+                                "▶"
+                            </button>
+                        </li>
+                    }
+                })
+                .collect_view()}
+        </ul>
+    }
+}
+
+/// Status bar button toggling every alert sound
+#[component]
+pub fn MuteToggle() -> impl IntoView {
+    let sounds = use_app_state().sounds;
+
+    view! {
+        <button
+            class=move || if sounds.is_muted() { "sb-preset sb-mute active" } else { "sb-preset sb-mute" }
+            title=move || if sounds.is_muted() { "Unmute alert sounds" } else { "Mute alert sounds" }
+            aria-pressed=move || sounds.is_muted().to_string()
+            on:click=move |_| sounds.toggle_mute()
+        >
+            {move || if sounds.is_muted() { "🔇" } else { "🔊" }}
+        </button>
+    }
+}
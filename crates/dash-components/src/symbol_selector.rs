@@ -0,0 +1,156 @@
+//! Symbol selector dropdown with fuzzy search, favorites and 24h change badges
+
+use dash_charts::{trail_bars, MiniCandles, TRAIL_TICKS_PER_BAR};
+use dash_core::{colors, Symbol, SymbolInfo};
+use dash_state::{use_app_state, ShortcutAction};
+use leptos::prelude::*;
+
+use crate::use_shortcut;
+
+#[component]
+pub fn SymbolSelector() -> impl IntoView {
+    let state = use_app_state();
+    let directory = state.symbols;
+    let active = state.market.symbol;
+
+    let open = RwSignal::new(false);
+    let query = RwSignal::new(String::new());
+    let highlighted = RwSignal::new(0usize);
+    let input_ref = NodeRef::<leptos::html::Input>::new();
+
+    let matches = Memo::new(move |_| query.with(|q| directory.search(q)));
+
+    let close = move || {
+        open.set(false);
+        query.set(String::new());
+        highlighted.set(0);
+    };
+
+    let select = Callback::new(move |symbol: Symbol| {
+        state.switch_symbol(symbol);
+        close();
+    });
+
+    use_shortcut(ShortcutAction::SymbolSearch, move || open.set(true));
+
+    // Focus the search box as soon as the dropdown mounts
+    Effect::new(move |_| {
+        if let Some(input) = input_ref.get() {
+            let _ = input.focus();
+        }
+    });
+
+    let on_keydown = move |ev: leptos::ev::KeyboardEvent| {
+        let count = matches.with_untracked(Vec::len);
+        match ev.key().as_str() {
+            "ArrowDown" if count > 0 => {
+                ev.prevent_default();
+                highlighted.update(|h| *h = (*h + 1) % count);
+            }
+            "ArrowUp" if count > 0 => {
+                ev.prevent_default();
+                highlighted.update(|h| *h = (*h + count - 1) % count);
+            }
+            "Enter" => {
+                let index = highlighted.get_untracked();
+                if let Some(entry) = matches.with_untracked(|m| m.get(index).cloned()) {
+                    select.run(entry.symbol);
+                }
+            }
+            "Escape" => close(),
+            _ => {}
+        }
+    };
+
+    view! {
+        <div class="symbol-selector">
+            <button class="ss-trigger" on:click=move |_| open.update(|o| *o = !*o)>
+                <span class="symbol-name">{move || active.get().to_string()}</span>
+                <span class="ss-caret">"▾"</span>
+            </button>
+
+            <Show when=move || open.get()>
+                <div class="ss-backdrop" on:click=move |_| close() />
+                <div class="ss-dropdown">
+                    <input
+                        class="ss-search"
+                        type="text"
+                        placeholder="Search symbols"
+                        node_ref=input_ref
+                        prop:value=move || query.get()
+                        on:input=move |ev| {
+                            query.set(event_target_value(&ev));
+                            highlighted.set(0);
+                        }
+                        on:keydown=on_keydown
+                    />
+                    <ul class="ss-list">
+                        {move || {
+                            matches
+                                .get()
+                                .into_iter()
+                                .enumerate()
+                                .map(|(index, entry)| view! {
+                                    <SymbolRow
+                                        entry=entry
+                                        index=index
+                                        highlighted=highlighted
+                                        on_select=select
+                                    />
+                                })
+                                .collect_view()
+                        }}
+                    </ul>
+                </div>
+            </Show>
+        </div>
+    }
+}
+
+#[component]
+fn SymbolRow(
+    entry: SymbolInfo,
+    index: usize,
+    highlighted: RwSignal<usize>,
+    on_select: Callback<Symbol>,
+) -> impl IntoView {
+    let directory = use_app_state().symbols;
+    let symbol = entry.symbol.clone();
+    let star_symbol = entry.symbol.clone();
+    let trail_symbol = entry.symbol.clone();
+    let is_favorite = directory.is_favorite(&entry.symbol);
+
+    let price_str = if entry.last_price > 0.0 {
+        format!("{:.*}", entry.price_decimals(), entry.last_price)
+    } else {
+        "—".to_string()
+    };
+    let badge_color = if entry.is_up() { colors::css::BULL } else { colors::css::BEAR };
+
+    view! {
+        <li
+            class=move || if highlighted.get() == index { "ss-item highlighted" } else { "ss-item" }
+            on:mouseenter=move |_| highlighted.set(index)
+            on:click=move |_| on_select.run(symbol.clone())
+        >
+            <button
+                class=if is_favorite { "ss-star pinned" } else { "ss-star" }
+                title=if is_favorite { "Unpin" } else { "Pin to top" }
+                on:click=move |ev| {
+                    ev.stop_propagation();
+                    directory.toggle_favorite(&star_symbol);
+                }
+            >
+                {if is_favorite { "★" } else { "☆" }}
+            </button>
+            <span class="ss-name">{entry.symbol.to_string()}</span>
+            <span class="ss-spark">
+                <MiniCandles bars=Signal::derive(move || trail_bars(&directory.trail(&trail_symbol), TRAIL_TICKS_PER_BAR)) height=18.0 />
+            </span>
+            <span class="ss-price">{price_str}</span>
+            <span class="ss-badge" style=format!("color: {}", badge_color)>
+                {format!("{:+.2}%", entry.change_percent_24h)}
+            </span>
+        </li>
+    }
+}
@@ -0,0 +1,122 @@
+//! Status bar performance readout: messages/sec, frame time, buffer sizes,
+//! heartbeat age and the server's id for this connection
+
+use dash_core::colors;
+use dash_state::{use_app_state, Telemetry};
+use leptos::prelude::*;
+
+/// Frame time above which the readout turns amber (below ~30 fps)
+const SLOW_FRAME_MS: f64 = 33.0;
+/// Heartbeat age past which the readout turns amber (the feed heartbeats
+/// every 30s)
+const LATE_HEARTBEAT_MS: i64 = 35_000;
+
+/// Sample every animation frame into `telemetry` for as long as the calling
+/// component is mounted
+pub fn use_frame_sampler(telemetry: Telemetry) {
+    // Disposed with the owner, which ends the loop on its next frame
+    let alive = StoredValue::new(());
+    fn schedule(telemetry: Telemetry, alive: StoredValue<()>) {
+        request_animation_frame(move || {
+            if alive.is_disposed() {
+                return;
+            }
+            if let Some(now) = web_sys::window().and_then(|w| w.performance()).map(|p| p.now()) {
+                telemetry.record_frame(now);
+            }
+            schedule(telemetry, alive);
+        });
+    }
+    schedule(telemetry, alive);
+}
+
+#[component]
+pub fn TelemetryReadout() -> impl IntoView {
+    let state = use_app_state();
+    let telemetry = state.telemetry;
+    let market = state.market.clone();
+    use_frame_sampler(telemetry);
+
+    // Buffers change on every trade, so re-read them on the telemetry tick
+    // rather than subscribing to them
+    let footprint = move || {
+        telemetry.msgs_per_sec.track();
+        market.footprint()
+    };
+    let footprint_title = footprint.clone();
+    let connection_id = state.connection_id;
+    // Clicking copies the full id for a bug report
+    let copy_id = move |_| {
+        if let Some(id) = connection_id.get_untracked() {
+            let _ = window().navigator().clipboard().write_text(&id);
+        }
+    };
+
+    view! {
+        <div class="sb-updates">
+            <div class="sb-item" title="Incoming messages per second">
+                <span class="sb-label">"msg/s"</span>
+                <span class="sb-value">{move || format!("{:.0}", telemetry.msgs_per_sec.get())}</span>
+            </div>
+            <div
+                class="sb-item"
+                title=move || {
+                    telemetry
+                        .worst_frame_ms
+                        .get()
+                        .map_or_else(|| "Render frame time".to_string(), |w| format!("Render frame time (worst {:.1} ms)", w))
+                }
+            >
+                <span class="sb-label">"frame"</span>
+                <span
+                    class="sb-value"
+                    style=move || {
+                        let slow = telemetry.frame_ms.get().is_some_and(|ms| ms > SLOW_FRAME_MS);
+                        if slow { format!("color: {}", colors::css::WARN) } else { String::new() }
+                    }
+                >
+                    {move || telemetry.frame_ms.get().map_or_else(|| "—".to_string(), |ms| format!("{:.1} ms", ms))}
+                </span>
+            </div>
+            <div
+                class="sb-item"
+                title=move || {
+                    let f = footprint_title();
+                    format!(
+                        "{} trades, {} candles, {} book levels, {} liquidations",
+                        f.trades, f.candles, f.book_levels, f.liquidations
+                    )
+                }
+            >
+                <span class="sb-label">"mem"</span>
+                <span class="sb-value">{move || format!("~{}", footprint().approx_label())}</span>
+            </div>
+            {move || {
+                telemetry.heartbeat_age_ms.get().map(|age| {
+                    let style = if age > LATE_HEARTBEAT_MS { format!("color: {}", colors::css::WARN) } else { String::new() };
+                    view! {
+                        <div class="sb-item" title="Time since the server last sent a heartbeat or ping">
+                            <span class="sb-label">"hb"</span>
+                            <span class="sb-value" style=style>{format!("{}s", age / 1_000)}</span>
+                        </div>
+                    }
+                })
+            }}
+            {move || {
+                connection_id.get().map(|id| {
+                    let short = id.chars().take(8).collect::<String>();
+                    view! {
+                        <div
+                            class="sb-item sb-conn"
+                            title=format!("Connection {} (click to copy; quote it when reporting a problem)", id)
+                            on:click=copy_id
+                        >
+                            <span class="sb-label">"conn"</span>
+                            <span class="sb-value">{short}</span>
+                        </div>
+                    }
+                })
+            }}
+        </div>
+    }
+}
@@ -1,14 +1,19 @@
 //! Ticker bar component for dashboard header
 
-use dash_core::{colors, ConnectionState, Ticker};
-use dash_state::MarketState;
+use dash_charts::{MiniDepth, Sparkline, SpreadChart};
+use dash_core::{colors, format_time_ms, ConnectionState, SessionAnchor, SessionStats};
+use dash_state::{use_app_state, use_i18n, use_tick_format, MarketState, Msg, EMA_PERIOD, SPREAD_WINDOW_MS};
 use leptos::prelude::*;
 
+use crate::SymbolSelector;
+
 #[derive(Debug, Clone)]
 pub struct TickerBarConfig {
     pub show_volume: bool,
     pub show_high_low: bool,
     pub show_spread: bool,
+    /// Session open, VWAP distance and range markers (analytics channel)
+    pub show_session: bool,
     pub compact: bool,
 }
 
@@ -18,6 +23,7 @@ impl Default for TickerBarConfig {
             show_volume: true,
             show_high_low: true,
             show_spread: true,
+            show_session: true,
             compact: false,
         }
     }
@@ -33,25 +39,36 @@ pub fn TickerBar(
     let show_volume = config.show_volume;
     let show_high_low = config.show_high_low;
     let show_spread = config.show_spread;
+    let show_session = config.show_session;
 
     let ticker = market.ticker;
+    let session = market.session;
+    let last_price = Signal::derive(move || ticker.with(|t| t.as_ref().map(|t| t.last_price.as_f64())));
+    let ema = market.indicators.ema;
     let symbol = market.symbol;
+    let directory = use_app_state().symbols;
+    let i18n = use_i18n();
+    let format = use_tick_format();
+    let trail = Signal::derive(move || symbol.with(|s| directory.trail(s)));
+    let depth = market.depth;
+    let spread_history = market.spread_history;
+    let spread_points = Signal::derive(move || spread_history.with(|h| h.points()));
 
     view! {
         <div class="ticker-bar">
             <div class="tb-symbol">
-                <span class="symbol-name">{move || symbol.get().to_string()}</span>
+                <SymbolSelector />
                 <ConnectionIndicator state=connection />
             </div>
 
             <div class="tb-price">
                 {move || {
                     ticker.get().map(|t| {
-                        let color = t.color();
+                        let color = t.css_color();
                         let arrow = t.arrow();
                         view! {
                             <span class="price-value" style=format!("color: {}", color)>
-                                {format!("{:.2}", t.last_price.as_f64())}
+                                {i18n.price(t.last_price.as_f64(), format.get())}
                             </span>
                             <span class="price-change" style=format!("color: {}", color)>
                                 {arrow} " " {t.change_percent_str()}
@@ -59,33 +76,64 @@ pub fn TickerBar(
                         }
                     })
                 }}
+                <span class="tb-spark" title="Recent price trend">
+                    <Sparkline values=trail points=120 fill=true width=96.0 height=24.0 />
+                </span>
             </div>
 
             <div class="tb-stats">
                 {move || {
                     let t = ticker.get()?;
-                    let color = t.color();
+                    let color = t.css_color();
                     Some(view! {
                         <div class="tb-stat">
-                            <span class="stat-label">"24h Change"</span>
-                            <span class="stat-value" style=format!("color: {}", color)>{t.change_str()}</span>
+                            <span class="stat-label">{i18n.t(Msg::Change24h)}</span>
+                            <span class="stat-value" style=format!("color: {}", color)>
+                                {i18n.signed(t.change_24h, format.get().price_decimals)}
+                            </span>
                         </div>
                     })
                 }}
 
+                {move || {
+                    if !show_session {
+                        return None;
+                    }
+                    let s = session.get()?;
+                    let price = last_price.get()?;
+                    let distance = s.vwap_distance_percent(price);
+                    let color = if distance >= 0.0 { colors::css::BULL } else { colors::css::BEAR };
+                    Some(view! {
+                        <div class="tb-stat" title=format!("{} session from {} UTC", s.anchor.label(), format_time_ms(s.session_start))>
+                            <span class="stat-label">{i18n.t(Msg::SessionOpen)}</span>
+                            <span class="stat-value">{i18n.price(s.open.as_f64(), format.get())}</span>
+                        </div>
+                        <div class="tb-stat" title=format!("Session VWAP {}", format.get().price(s.vwap))>
+                            <span class="stat-label">{i18n.t(Msg::VsVwap)}</span>
+                            <span class="stat-value" style=format!("color: {}", color)>
+                                {format!("{}%", i18n.signed(distance, 2))}
+                            </span>
+                        </div>
+                    })
+                }}
+
+                {move || show_session.then(|| view! { <RangeIndicator session=session price=last_price /> })}
+
+                {move || show_session.then(|| view! { <SessionAnchorSelect /> })}
+
                 {move || {
                     if show_high_low {
                         ticker.get().map(|t| view! {
                             <div class="tb-stat">
-                                <span class="stat-label">"24h High"</span>
-                                <span class="stat-value" style=format!("color: {}", colors::BULL)>
-                                    {format!("{:.2}", t.high_24h.as_f64())}
+                                <span class="stat-label">{i18n.t(Msg::High24h)}</span>
+                                <span class="stat-value" style=format!("color: {}", colors::css::BULL)>
+                                    {i18n.price(t.high_24h.as_f64(), format.get())}
                                 </span>
                             </div>
                             <div class="tb-stat">
-                                <span class="stat-label">"24h Low"</span>
-                                <span class="stat-value" style=format!("color: {}", colors::BEAR)>
-                                    {format!("{:.2}", t.low_24h.as_f64())}
+                                <span class="stat-label">{i18n.t(Msg::Low24h)}</span>
+                                <span class="stat-value" style=format!("color: {}", colors::css::BEAR)>
+                                    {i18n.price(t.low_24h.as_f64(), format.get())}
                                 </span>
                             </div>
                         })
@@ -99,15 +147,15 @@ pub fn TickerBar(
                         ticker.get().map(|t| {
                             let vol = t.volume_24h.as_f64();
                             let vol_str = if vol >= 1_000_000.0 {
-                                format!("{:.2}M", vol / 1_000_000.0)
+                                format!("{}M", i18n.number(vol / 1_000_000.0, 2))
                             } else if vol >= 1_000.0 {
-                                format!("{:.2}K", vol / 1_000.0)
+                                format!("{}K", i18n.number(vol / 1_000.0, 2))
                             } else {
-                                format!("{:.4}", vol)
+                                i18n.quantity(vol, format.get())
                             };
                             view! {
                                 <div class="tb-stat">
-                                    <span class="stat-label">"24h Volume"</span>
+                                    <span class="stat-label">{i18n.t(Msg::Volume24h)}</span>
                                     <span class="stat-value">{vol_str}</span>
                                 </div>
                             }
@@ -117,13 +165,29 @@ pub fn TickerBar(
                     }
                 }}
 
+                {move || {
+                    ema.get().map(|ema| view! {
+                        <div class="tb-stat">
+                            <span class="stat-label">{format!("EMA({})", EMA_PERIOD)}</span>
+                            <span class="stat-value">{i18n.price(ema, format.get())}</span>
+                        </div>
+                    })
+                }}
+
                 {move || {
                     if show_spread {
                         ticker.get().map(|t| view! {
-                            <div class="tb-stat">
-                                <span class="stat-label">"Spread"</span>
-                                <span class="stat-value" style=format!("color: {}", colors::WARN)>
-                                    {format!("{:.2} ({:.3}%)", t.spread(), t.spread_percent())}
+                            <div class="tb-stat tb-has-popout">
+                                <span class="stat-label">{i18n.t(Msg::Spread)}</span>
+                                <span class="stat-value" style=format!("color: {}", colors::css::WARN)>
+                                    {format!("{} ({}%)", i18n.price(t.spread(), format.get()), i18n.number(t.spread_percent(), 3))}
+                                </span>
+                                <span class="tb-spread-chart" title="Spread over the last 5 minutes (bps, dashed: average)">
+                                    <SpreadChart points=spread_points window_ms=SPREAD_WINDOW_MS width=64.0 height=18.0 />
+                                </span>
+                                // Book shape at a glance on hover
+                                <span class="tb-popout">
+                                    <MiniDepth depth=depth width=120.0 height=40.0 />
                                 </span>
                             </div>
                         })
@@ -136,6 +200,122 @@ pub fn TickerBar(
     }
 }
 
+/// Session low-high bar with markers for the open and the last price; the
+/// ends carry the time each extreme was set
+#[component]
+pub fn RangeIndicator(
+    #[prop(into)] session: Signal<Option<SessionStats>>,
+    #[prop(into)] price: Signal<Option<f64>>,
+) -> impl IntoView {
+    let i18n = use_i18n();
+    let format = use_tick_format();
+    move || {
+        let s = session.get()?;
+        let format = format.get();
+        let price = price.get()?;
+        let marker = |position: f64| format!("left: {:.1}%", position * 100.0);
+        Some(view! {
+            <div class="tb-stat range-indicator">
+                <span class="stat-label">{i18n.t(Msg::SessionRange)}</span>
+                <div class="ri-body">
+                    <span
+                        class="ri-end"
+                        style=format!("color: {}", colors::css::BEAR)
+                        title=format!("Low {} set at {} UTC", format.price(s.low.as_f64()), format_time_ms(s.low_time))
+                    >
+                        {i18n.price(s.low.as_f64(), format)}
+                    </span>
+                    <div class="ri-track">
+                        <span
+                            class="ri-open"
+                            style=marker(s.range_position(s.open.as_f64()))
+                            title=format!("Open {}", format.price(s.open.as_f64()))
+                        />
+                        <span
+                            class="ri-vwap"
+                            style=marker(s.range_position(s.vwap))
+                            title=format!("VWAP {}", format.price(s.vwap))
+                        />
+                        <span class="ri-price" style=marker(s.range_position(price)) />
+                    </div>
+                    <span
+                        class="ri-end"
+                        style=format!("color: {}", colors::css::BULL)
+                        title=format!("High {} set at {} UTC", format.price(s.high.as_f64()), format_time_ms(s.high_time))
+                    >
+                        {i18n.price(s.high.as_f64(), format)}
+                    </span>
+                </div>
+            </div>
+        })
+    }
+}
+
+/// Day start the session stats follow: a preset or a custom UTC time
+#[component]
+pub fn SessionAnchorSelect() -> impl IntoView {
+    let state = use_app_state();
+    let ui = state.ui;
+    let anchor = Memo::new(move |_| ui.with(|ui| ui.session_anchor));
+    let time_state = state.clone();
+
+    let pick = move |ev: leptos::ev::Event| {
+        let current = anchor.get_untracked();
+        let next = match event_target_value(&ev).as_str() {
+            "exchange" => SessionAnchor::ExchangeDay,
+            // Start from the current day start so switching keeps the time
+            "custom" => SessionAnchor::Custom { start_minute: current.start_minute() },
+            _ => SessionAnchor::UtcMidnight,
+        };
+        state.set_session_anchor(next);
+    };
+    let key = move || match anchor.get() {
+        SessionAnchor::UtcMidnight => "utc",
+        SessionAnchor::ExchangeDay => "exchange",
+        SessionAnchor::Custom { .. } => "custom",
+    };
+
+    view! {
+        <div class="tb-stat tb-session">
+            <span class="stat-label">"Session"</span>
+            <select
+                class="ct-select"
+                aria-label="Session day start"
+                title=move || anchor.get().label()
+                prop:value=key
+                on:change=pick
+            >
+                <option value="utc">"UTC day"</option>
+                <option value="exchange">"Exchange day"</option>
+                <option value="custom">"Custom"</option>
+            </select>
+            {move || {
+                let time_state = time_state.clone();
+                matches!(anchor.get(), SessionAnchor::Custom { .. }).then(|| view! {
+                    <input
+                        type="time"
+                        class="tb-session-time"
+                        aria-label="Session start (UTC)"
+                        prop:value=move || {
+                            let minute = anchor.get().start_minute();
+                            format!("{:02}:{:02}", minute / 60, minute % 60)
+                        }
+                        on:change=move |ev| {
+                            let value = event_target_value(&ev);
+                            let parsed = value
+                                .split_once(':')
+                                .and_then(|(h, m)| Some((h.parse::<u16>().ok()?, m.parse::<u16>().ok()?)));
+                            if let Some((hour, minute)) = parsed.filter(|&(h, m)| h < 24 && m < 60) {
+                                time_state.set_session_anchor(SessionAnchor::Custom { start_minute: hour * 60 + minute });
+                            }
+                        }
+                    />
+                })
+            }}
+        </div>
+    }
+}
+
 #[component]
 pub fn ConnectionIndicator(
     #[prop(into)] state: Signal<ConnectionState>,
@@ -143,9 +323,9 @@ pub fn ConnectionIndicator(
     let indicator_style = move || {
         let s = state.get();
         let color = match s {
-            ConnectionState::Connected => colors::BULL,
-            ConnectionState::Connecting | ConnectionState::Reconnecting => colors::WARN,
-            ConnectionState::Disconnected => colors::BEAR,
+            ConnectionState::Connected => colors::css::BULL,
+            ConnectionState::Connecting | ConnectionState::Reconnecting => colors::css::WARN,
+            ConnectionState::Disconnected | ConnectionState::Closed(_) => colors::css::BEAR,
         };
         format!("background-color: {}", color)
     };
@@ -0,0 +1,205 @@
+//! Chart timeframe toolbar, chart type picker, compare picker, price
+//! scale toggle and time zone toggle
+
+use dash_core::{BrickSize, CandleInterval, ChartType, PriceScaleMode, Symbol, TimeZoneMode};
+use dash_state::{use_app_state, BackfillStatus};
+use leptos::prelude::*;
+
+/// Timeframes offered in the chart header
+pub const CHART_TIMEFRAMES: &[CandleInterval] = &[
+    CandleInterval::M1,
+    CandleInterval::M5,
+    CandleInterval::M15,
+    CandleInterval::H1,
+    CandleInterval::H4,
+    CandleInterval::D1,
+];
+
+/// One button per timeframe; the pick is remembered per symbol
+#[component]
+pub fn TimeframeBar() -> impl IntoView {
+    let state = use_app_state();
+    let interval = state.market.interval;
+    let backfill = state.market.backfill;
+
+    view! {
+        <span class="tf-bar" role="group" aria-label="Timeframe">
+            {CHART_TIMEFRAMES
+                .iter()
+                .map(|&timeframe| {
+                    let state = state.clone();
+                    view! {
+                        <button
+                            class=move || if interval.get() == timeframe { "sb-preset active" } else { "sb-preset" }
+                            aria-pressed=move || (interval.get() == timeframe).to_string()
+                            on:click=move |_| state.select_interval(timeframe)
+                        >
+                            {timeframe.label()}
+                        </button>
+                    }
+                })
+                .collect_view()}
+            {move || {
+                (backfill.get() == BackfillStatus::Loading)
+                    .then(|| view! { <span class="tf-loading" title="Loading older candles">"…"</span> })
+            }}
+        </span>
+    }
+}
+
+/// Drop-down switching how the chart draws price; renko and range bars
+/// replace the candles with price-driven bars, sized by a second drop-down
+#[component]
+pub fn ChartTypeSelect() -> impl IntoView {
+    let state = use_app_state();
+    let ui = state.ui;
+    let size_state = state.clone();
+
+    let pick = move |ev: leptos::ev::Event| {
+        let label = event_target_value(&ev);
+        if let Some(&chart_type) = ChartType::all().iter().find(|t| t.label() == label) {
+            state.set_chart_type(chart_type);
+        }
+    };
+
+    view! {
+        <select
+            class="ct-select"
+            aria-label="Chart type"
+            prop:value=move || ui.with(|ui| ui.chart_type.label())
+            on:change=pick
+        >
+            {ChartType::all()
+                .iter()
+                .map(|chart_type| view! { <option value=chart_type.label()>{chart_type.label()}</option> })
+                .collect_view()}
+        </select>
+        {move || {
+            let size_state = size_state.clone();
+            ui.with(|ui| ui.chart_type.is_price_driven()).then(|| view! {
+                <select
+                    class="ct-select"
+                    aria-label="Brick size"
+                    title="Brick or bar size as a share of price"
+                    prop:value=move || ui.with(|ui| ui.brick_size.0.to_string())
+                    on:change=move |ev| {
+                        if let Ok(bps) = event_target_value(&ev).parse() {
+                            size_state.set_brick_size(BrickSize(bps));
+                        }
+                    }
+                >
+                    {BrickSize::presets()
+                        .iter()
+                        .map(|size| view! { <option value=size.0.to_string()>{size.label()}</option> })
+                        .collect_view()}
+                </select>
+            })
+        }}
+    }
+}
+
+/// Drop-down picking a second symbol to overlay on the chart as percent
+/// change (any symbol but the charted one)
+#[component]
+pub fn CompareSelect() -> impl IntoView {
+    let state = use_app_state();
+    let compare = state.compare;
+    let directory = state.symbols;
+    let charted = state.market.symbol;
+
+    let pick = move |ev: leptos::ev::Event| {
+        let value = event_target_value(&ev);
+        compare.set_symbol((!value.is_empty()).then(|| Symbol::new(value)));
+    };
+
+    view! {
+        <select
+            class=move || if compare.symbol.with(Option::is_some) { "ct-select compare-select active" } else { "ct-select compare-select" }
+            aria-label="Compare with"
+            title="Overlay another symbol as percent change"
+            prop:value=move || compare.symbol.get().map(|s| s.to_string()).unwrap_or_default()
+            on:change=pick
+        >
+            <option value="">"Compare…"</option>
+            {move || {
+                let charted = charted.get();
+                directory
+                    .symbols
+                    .get()
+                    .into_iter()
+                    .filter(|info| info.symbol != charted)
+                    .map(|info| {
+                        let symbol = info.symbol.to_string();
+                        view! { <option value=symbol.clone()>{info.symbol.to_string()}</option> }
+                    })
+                    .collect_view()
+            }}
+        </select>
+    }
+}
+
+/// Linear / log / percent price axis buttons
+#[component]
+pub fn PriceScaleToggle() -> impl IntoView {
+    let state = use_app_state();
+    let ui = state.ui;
+    let current = move || ui.with(|ui| ui.price_scale);
+
+    view! {
+        <span class="tf-bar" role="group" aria-label="Price scale">
+            {PriceScaleMode::all()
+                .iter()
+                .map(|&mode| {
+                    let state = state.clone();
+                    let title = match mode {
+                        PriceScaleMode::Linear => "Linear price scale",
+                        PriceScaleMode::Log => "Logarithmic price scale",
+                        PriceScaleMode::Percent => "Percent change from the first visible bar",
+                    };
+                    view! {
+                        <button
+                            class=move || if current() == mode { "sb-preset active" } else { "sb-preset" }
+                            title=title
+                            aria-pressed=move || (current() == mode).to_string()
+                            on:click=move |_| state.set_price_scale(mode)
+                        >
+                            {mode.label()}
+                        </button>
+                    }
+                })
+                .collect_view()}
+        </span>
+    }
+}
+
+/// UTC / local time switch for the chart's time axis
+#[component]
+pub fn TimeZoneToggle() -> impl IntoView {
+    let state = use_app_state();
+    let ui = state.ui;
+    let current = move || ui.with(|ui| ui.time_zone);
+
+    view! {
+        <span class="tf-bar" role="group" aria-label="Time zone">
+            {TimeZoneMode::all()
+                .iter()
+                .map(|&zone| {
+                    let state = state.clone();
+                    view! {
+                        <button
+                            class=move || if current() == zone { "sb-preset active" } else { "sb-preset" }
+                            title=match zone {
+                                TimeZoneMode::Utc => "Chart times in UTC",
+                                TimeZoneMode::Local => "Chart times in your time zone",
+                            }
+                            aria-pressed=move || (current() == zone).to_string()
+                            on:click=move |_| state.set_time_zone(zone)
+                        >
+                            {zone.label()}
+                        </button>
+                    }
+                })
+                .collect_view()}
+        </span>
+    }
+}
@@ -0,0 +1,75 @@
+//! Toast stack for the notification center
+
+use dash_state::{use_app_state, Notification, NotificationAction};
+use leptos::prelude::*;
+
+fn now_ms() -> i64 {
+    chrono::Utc::now().timestamp_millis()
+}
+
+/// Active notifications, oldest at the top. Expiry is driven by
+/// `NotificationsState::expire`; hovering a toast pauses its clock.
+#[component]
+pub fn ToastHost() -> impl IntoView {
+    let notifications = use_app_state().notifications;
+
+    view! {
+        <div class="toast-host" role="status" aria-live="polite">
+            <For
+                each=move || notifications.toasts()
+                // Re-render when a repeat is folded in
+                key=|n| (n.id, n.count)
+                children=|n| view! { <Toast notification=n /> }
+            />
+        </div>
+    }
+}
+
+#[component]
+fn Toast(notification: Notification) -> impl IntoView {
+    let state = use_app_state();
+    let notifications = state.notifications;
+    let id = notification.id;
+
+    let message = if notification.count > 1 {
+        format!("{} (×{})", notification.message, notification.count)
+    } else {
+        notification.message.clone()
+    };
+
+    let run_action = move |action: NotificationAction| match action {
+        NotificationAction::Reconnect => state.request_reconnect(),
+    };
+
+    view! {
+        <div
+            class=format!("toast {}", notification.severity.css_class())
+            on:mouseenter=move |_| notifications.pause(id, now_ms())
+            on:mouseleave=move |_| notifications.resume(id, now_ms())
+        >
+            <span class="toast-icon">{notification.severity.icon()}</span>
+            <div class="toast-body">
+                <span class="toast-source">{notification.source.label()}</span>
+                <span class="toast-msg">{message}</span>
+            </div>
+            {notification.action.map(|action| view! {
+                <button
+                    class="toast-action"
+                    on:click=move |_| {
+                        run_action(action);
+                        notifications.dismiss(id);
+                    }
+                >
+                    {action.label()}
+                </button>
+            })}
+            <button
+                class="toast-close"
+                title="Dismiss"
+                on:click=move |_| notifications.dismiss(id)
+            >
+                "×"
+            </button>
+        </div>
+    }
+}
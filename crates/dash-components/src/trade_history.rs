@@ -1,9 +1,87 @@
 //! Trade history (tape) component
 
-use dash_core::{colors, Trade, TradeSide, TradeClassification, ValueThresholdClassifier, TradeClassifier};
-use dash_state::MarketState;
+use dash_charts::DeltaSparkline;
+use dash_core::{
+    colors, export_trades, group_trades, ExportFormat, FlowToxicity, Trade, TradeClassification, TradeClassifier, TradeGroup,
+    ValueThresholdClassifier,
+};
+use dash_state::{use_i18n, use_tick_format, CursorState, Indicators, MarketState, Msg, MAX_TRADES};
 use leptos::prelude::*;
 
+use crate::{focus_left, row_keydown, use_context_menu, ExportButtons};
+
+/// Fixed row height in px (must match `.th-row` in the stylesheet)
+pub const TRADE_ROW_HEIGHT: f64 = 22.0;
+/// Extra rows mounted above and below the viewport
+const OVERSCAN_ROWS: usize = 8;
+/// Viewport height assumed until the list has been measured
+const DEFAULT_VIEWPORT_PX: f64 = 500.0;
+
+/// Rows to mount for a scroll position: `(first index, count)`
+fn visible_window(scroll_top: f64, viewport: f64, total: usize) -> (usize, usize) {
+    let first = ((scroll_top / TRADE_ROW_HEIGHT).floor().max(0.0) as usize).saturating_sub(OVERSCAN_ROWS);
+    let count = (viewport / TRADE_ROW_HEIGHT).ceil() as usize + 2 * OVERSCAN_ROWS;
+    let first = first.min(total);
+    (first, count.min(total - first))
+}
+
+/// Rows the tape draws from: the live trades, or a filtered copy while a
+/// size filter is set, merged into groups while grouping is on
+#[derive(Clone, Copy)]
+struct Tape {
+    trades: RwSignal<Vec<Trade>>,
+    filtered: Memo<Option<Vec<Trade>>>,
+    grouped: Memo<Option<Vec<TradeGroup>>>,
+}
+
+impl Tape {
+    fn new(
+        trades: RwSignal<Vec<Trade>>,
+        min_size: Option<RwSignal<Option<f64>>>,
+        group_window: Option<RwSignal<Option<i64>>>,
+    ) -> Self {
+        let filtered = Memo::new(move |_| -> Option<Vec<Trade>> {
+            let min = min_size.and_then(|m| m.get())?;
+            Some(trades.with(|t| t.iter().filter(|t| t.quantity.as_f64() >= min).cloned().collect()))
+        });
+        let grouped = Memo::new(move |_| {
+            let window = group_window.and_then(|w| w.get())?;
+            Some(filtered.with(|filtered| match filtered {
+                Some(rows) => group_trades(rows, window),
+                None => trades.with(|t| group_trades(t, window)),
+            }))
+        });
+        Self { trades, filtered, grouped }
+    }
+
+    /// Number of rows
+    fn len(&self) -> usize {
+        self.grouped.with(|grouped| match grouped {
+            Some(groups) => groups.len(),
+            None => self.with(<[Trade]>::len),
+        })
+    }
+
+    /// `count` rows from `first`
+    fn rows(&self, first: usize, count: usize) -> Vec<TradeGroup> {
+        self.grouped.with(|grouped| match grouped {
+            Some(groups) => groups.iter().skip(first).take(count).cloned().collect(),
+            None => self.with(|t| t.iter().skip(first).take(count).cloned().map(TradeGroup::single).collect()),
+        })
+    }
+
+    fn rows_untracked(&self, first: usize, count: usize) -> Vec<TradeGroup> {
+        untrack(|| self.rows(first, count))
+    }
+
+    fn with<R>(&self, f: impl FnOnce(&[Trade]) -> R) -> R {
+        self.filtered.with(|filtered| match filtered {
+            Some(rows) => f(rows),
+            None => self.trades.with(|t| f(t)),
+        })
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct TradeHistoryConfig {
     pub max_visible: usize,
@@ -15,7 +93,7 @@ pub struct TradeHistoryConfig {
 impl Default for TradeHistoryConfig {
     fn default() -> Self {
         Self {
-            max_visible: 50,
+            max_visible: MAX_TRADES,
             show_value: true,
             highlight_whales: true,
             compact: false,
@@ -38,98 +116,382 @@ impl TradeHistoryConfig {
 pub fn TradeHistory(
     #[prop(into)] market: MarketState,
     #[prop(optional)] config: Option<TradeHistoryConfig>,
+    /// Shared hover/selection state (highlights trades in the hovered candle)
+    #[prop(optional)] cursor: Option<CursorState>,
+    /// Hide trades smaller than this size (`None` = show all)
+    #[prop(optional)] min_size: Option<RwSignal<Option<f64>>>,
+    /// Merge consecutive same-price, same-side trades within this many ms
+    /// into one row (`None` = one row per trade)
+    #[prop(optional)] group_window: Option<RwSignal<Option<i64>>>,
 ) -> impl IntoView {
     let config = config.unwrap_or_default();
+    let i18n = use_i18n();
+    let tick_format = use_tick_format();
     let max_visible = config.max_visible;
     let show_value = config.show_value;
     let highlight_whales = config.highlight_whales;
     let compact = config.compact;
 
-    let trades = market.trades;
+    let trades = Tape::new(market.trades, min_size, group_window);
     let classifier = ValueThresholdClassifier::default();
 
+    // Virtualized list: only rows inside the scroll viewport (plus overscan)
+    // are mounted; a spacer keeps the scrollbar sized for the whole tape
+    let list_ref = NodeRef::<leptos::html::Div>::new();
+    let table_ref = NodeRef::<leptos::html::Div>::new();
+    let scroll_top = RwSignal::new(0.0);
+    let viewport = RwSignal::new(DEFAULT_VIEWPORT_PX);
+
+    let measure = move || {
+        if let Some(el) = list_ref.get_untracked() {
+            scroll_top.set(f64::from(el.scroll_top()));
+            let height = f64::from(el.client_height());
+            if height > 0.0 {
+                viewport.set(height);
+            }
+        }
+    };
+
+    Effect::new(move |_| {
+        if list_ref.get().is_some() {
+            measure();
+        }
+    });
+    let resize_handle = window_event_listener(leptos::ev::resize, move |_| measure());
+    on_cleanup(move || resize_handle.remove());
+
+    // Pause while the pointer is over the tape, a row has keyboard focus or
+    // it's scrolled into history: rows render from a frozen snapshot and new
+    // trades only bump a counter
+    let hovered = RwSignal::new(false);
+    let focused = RwSignal::new(false);
+    let frozen = RwSignal::new(None::<Vec<TradeGroup>>);
+    let paused = Memo::new(move |_| hovered.get() || focused.get() || scroll_top.get() >= TRADE_ROW_HEIGHT / 2.0);
+
+    Effect::new(move |_| {
+        let pause = paused.get();
+        let is_frozen = frozen.with_untracked(Option::is_some);
+        if pause && !is_frozen {
+            frozen.set(Some(trades.rows_untracked(0, max_visible)));
+        } else if !pause && is_frozen {
+            frozen.set(None);
+        }
+    });
+
+    // Trades that arrived since the snapshot (all retained ones if it scrolled out)
+    let buffered = Memo::new(move |_| {
+        frozen.with(|f| {
+            let head = f.as_ref()?.first().map(|g| g.trade.id.clone());
+            Some(trades.with(|t| match head {
+                Some(id) => t.iter().position(|trade| trade.id == id).unwrap_or(t.len()),
+                None => t.len(),
+            }))
+        })
+    });
+
+    let resume = move |_| {
+        if let Some(el) = list_ref.get_untracked() {
+            el.set_scroll_top(0);
+        }
+        scroll_top.set(0.0);
+        hovered.set(false);
+    };
+
+    let total = Memo::new(move |_| match frozen.with(|f| f.as_ref().map(Vec::len)) {
+        Some(len) => len,
+        None => trades.len().min(max_visible),
+    });
+
+    let window = Memo::new(move |_| visible_window(scroll_top.get(), viewport.get(), total.get()));
+
     let visible_trades = move || {
-        trades.get().into_iter().take(max_visible).collect::<Vec<_>>()
+        let (first, count) = window.get();
+        frozen.with(|f| match f {
+            Some(rows) => rows.iter().skip(first).take(count).cloned().collect(),
+            None => trades.rows(first, count),
+        })
+    };
+
+    // Exports what the tape shows: the paused snapshot or the live rows
+    let export = Callback::new(move |format: ExportFormat| {
+        let rows: Vec<Trade> = frozen
+            .with_untracked(|f| f.clone())
+            .unwrap_or_else(|| trades.rows_untracked(0, max_visible))
+            .into_iter()
+            .map(|g| g.trade)
+            .collect();
+        (!rows.is_empty()).then(|| export_trades(&rows, format))
+    });
+
+    let spacer_style = move || {
+        let (first, _) = window.get();
+        format!(
+            "height: {}px; padding-top: {}px",
+            total.get() as f64 * TRADE_ROW_HEIGHT,
+            first as f64 * TRADE_ROW_HEIGHT
+        )
     };
 
     view! {
         <div class="trade-history">
-            <div class="th-header">
-                <span class="th-col time">"Time"</span>
-                <span class="th-col side">"Side"</span>
-                <span class="th-col price">"Price"</span>
-                <span class="th-col size">"Size"</span>
-                {if show_value {
-                    Some(view! { <span class="th-col value">"Value"</span> })
-                } else {
-                    None
+            <div class="panel-toolbar">
+                {move || {
+                    let filter = min_size?;
+                    let min = filter.get()?;
+                    Some(view! {
+                        <button class="th-filter" title="Clear the size filter" on:click=move |_| filter.set(None)>
+                            {format!("Size ≥ {} ✕", tick_format.get().quantity(min))}
+                        </button>
+                    })
                 }}
+                <ExportButtons kind="trades" render=export />
             </div>
+            {move || {
+                buffered.get().filter(|&n| n > 0).map(|n| view! {
+                    <button class="th-resume" on:click=resume>
+                        {format!("{} new trade{} ↑", n, if n == 1 { "" } else { "s" })}
+                    </button>
+                })
+            }}
 
-            <div class="th-list">
-                <For
-                    each=visible_trades
-                    key=|trade| trade.id.clone()
-                    children=move |trade| {
-                        let classification = if highlight_whales {
-                            Some(classifier.classify(&trade))
-                        } else {
-                            None
-                        };
-                        view! {
-                            <TradeRow
-                                trade=trade
-                                show_value=show_value
-                                classification=classification
-                                compact=compact
-                            />
-                        }
+            <div
+                class="th-table"
+                role="table"
+                aria-label="Recent trades"
+                tabindex="0"
+                node_ref=table_ref
+                on:keydown=move |ev| row_keydown(&ev)
+                on:focusin=move |_| focused.set(true)
+                on:focusout=move |ev| {
+                    if let Some(table) = table_ref.get_untracked()
+                        && focus_left(&ev, &table)
+                    {
+                        focused.set(false);
                     }
-                />
+                }
+            >
+                <div class="th-header" role="row">
+                    <span class="th-col time" role="columnheader">{move || i18n.t(Msg::Time)}</span>
+                    <span class="th-col side" role="columnheader">{move || i18n.t(Msg::Side)}</span>
+                    <span class="th-col price" role="columnheader">{move || i18n.t(Msg::Price)}</span>
+                    <span class="th-col size" role="columnheader">{move || i18n.t(Msg::Size)}</span>
+                    {if show_value {
+                        Some(view! { <span class="th-col value" role="columnheader">{move || i18n.t(Msg::Value)}</span> })
+                    } else {
+                        None
+                    }}
+                </div>
+
+                <div
+                    class=move || if paused.get() { "th-list paused" } else { "th-list" }
+                    role="rowgroup"
+                    node_ref=list_ref
+                    on:scroll=move |_| measure()
+                    on:mouseenter=move |_| hovered.set(true)
+                    on:mouseleave=move |_| hovered.set(false)
+                >
+                    <div class="th-spacer" style=spacer_style>
+                        <For
+                            each=visible_trades
+                            key=TradeGroup::key
+                            children=move |group| {
+                                let classification = if highlight_whales {
+                                    Some(classifier.classify(&group.trade))
+                                } else {
+                                    None
+                                };
+                                view! {
+                                    <TradeRow
+                                        trade=group.trade
+                                        count=group.count
+                                        show_value=show_value
+                                        classification=classification
+                                        compact=compact
+                                        cursor=cursor
+                                    />
+                                }
+                            }
+                        />
+                    </div>
+                </div>
             </div>
         </div>
     }
 }
 
+/// One-minute trade count and buy share for the panel header, or the
+/// five-minute cumulative volume delta when toggled to CVD mode; the
+/// toxicity gauge sits alongside when the server sends it
+#[component]
+pub fn TradeFlowStats(
+    indicators: Indicators,
+    #[prop(optional, into)] toxicity: Option<Signal<Option<FlowToxicity>>>,
+) -> impl IntoView {
+    let count = indicators.trade_count_1m;
+    let buy_ratio = indicators.buy_ratio;
+    let cvd = indicators.cvd;
+    let cvd_mode = RwSignal::new(false);
+
+    let summary = move || {
+        view! {
+            <span>{move || format!("{}/1m", count.get())}</span>
+            <span style=move || {
+                let color = if buy_ratio.get() >= 0.5 { colors::css::BULL } else { colors::css::BEAR };
+                format!("color: {}", color)
+            }>
+                {move || format!("{:.0}% buy", buy_ratio.get() * 100.0)}
+            </span>
+        }
+    };
+
+    let delta = move || {
+        let last = move || cvd.with(|series| series.last().copied().unwrap_or(0.0));
+        view! {
+            <span class="tf-cvd-chart">
+                <DeltaSparkline values=cvd />
+            </span>
+            <span style=move || {
+                let color = if last() >= 0.0 { colors::css::BULL } else { colors::css::BEAR };
+                format!("color: {}", color)
+            }>
+                {move || format!("{:+.2}", last())}
+            </span>
+        }
+    };
+
+    view! {
+        <span
+            class="panel-meta"
+            title=move || {
+                if cvd_mode.get() {
+                    "Cumulative buy − sell volume, last 5 minutes"
+                } else {
+                    "Rolling 1-minute trade flow"
+                }
+            }
+        >
+            <Show when=move || cvd_mode.get() fallback=summary>
+                {delta}
+            </Show>
+            {toxicity.map(|toxicity| view! { <ToxicityGauge toxicity=toxicity /> })}
+            <button
+                class=move || if cvd_mode.get() { "sb-preset active" } else { "sb-preset" }
+                title="Toggle cumulative volume delta"
+                on:click=move |_| cvd_mode.update(|on| *on = !*on)
+            >
+                "CVD"
+            </button>
+        </span>
+    }
+}
+
+/// VPIN bar: how one-sided the recent volume buckets have been
+#[component]
+pub fn ToxicityGauge(#[prop(into)] toxicity: Signal<Option<FlowToxicity>>) -> impl IntoView {
+    move || {
+        toxicity.get().map(|flow| {
+            let title = format!(
+                "Flow toxicity (VPIN) over {} buckets of {:.2}: {}",
+                flow.buckets,
+                flow.bucket_volume,
+                flow.label()
+            );
+            let fill = format!(
+                "width: {:.0}%; background: {}",
+                flow.vpin.clamp(0.0, 1.0) * 100.0,
+                flow.css_color()
+            );
+            view! {
+                <span class="vpin-gauge" title=title role="meter" aria-label="Flow toxicity" aria-valuenow=format!("{:.2}", flow.vpin) aria-valuemin="0" aria-valuemax="1">
+                    <span class="vpin-track">
+                        <span class="vpin-fill" style=fill />
+                    </span>
+                    <span style=format!("color: {}", flow.css_color())>{format!("VPIN {:.2}", flow.vpin)}</span>
+                </span>
+            }
+        })
+    }
+}
+
 #[component]
 fn TradeRow(
     trade: Trade,
+    /// Trades merged into this row
+    count: usize,
     show_value: bool,
     classification: Option<TradeClassification>,
     compact: bool,
+    cursor: Option<CursorState>,
 ) -> impl IntoView {
     let time_str = if compact { trade.time_short() } else { trade.time_str() };
     let price = trade.price.as_f64();
     let qty = trade.quantity.as_f64();
     let value = trade.value();
 
-    let price_str = if price >= 1000.0 { format!("{:.2}", price) } else { format!("{:.4}", price) };
-    let qty_str = format!("{:.4}", qty);
-    let value_str = if value >= 1_000_000.0 {
-        format!("{:.2}M", value / 1_000_000.0)
-    } else if value >= 1_000.0 {
-        format!("{:.2}K", value / 1_000.0)
-    } else {
-        format!("{:.2}", value)
+    let i18n = use_i18n();
+    let format = use_tick_format();
+    let value_str = move || {
+        if value >= 1_000_000.0 {
+            format!("{}M", i18n.number(value / 1_000_000.0, 2))
+        } else if value >= 1_000.0 {
+            format!("{}K", i18n.number(value / 1_000.0, 2))
+        } else {
+            i18n.price(value, format.get())
+        }
     };
 
-    let side_color = trade.side.color();
+    let side_color = trade.side.css_color();
     let side_arrow = trade.side.arrow();
+    let side = trade.side;
 
-    let row_class = match classification {
+    let base_class = match classification {
         Some(TradeClassification::Whale) => "th-row whale",
         Some(TradeClassification::Large) => "th-row large",
         _ => "th-row",
     };
 
+    let id = trade.id.clone();
+    let menu = use_context_menu();
+    let menu_trade = trade.clone();
+    let row_class = move || {
+        let Some(c) = cursor else {
+            return base_class.to_string();
+        };
+        let mut class = base_class.to_string();
+        if c.is_in_hovered_period(&trade) {
+            class.push_str(" in-period");
+        }
+        if c.is_trade_selected(&trade.id) {
+            class.push_str(" selected");
+        }
+        class
+    };
+
     view! {
-        <div class=row_class>
-            <span class="th-col time">{time_str}</span>
-            <span class="th-col side" style=format!("color: {}", side_color)>{side_arrow}</span>
-            <span class="th-col price" style=format!("color: {}", side_color)>{price_str}</span>
-            <span class="th-col size">{qty_str}</span>
+        <div
+            class=row_class
+            role="row"
+            tabindex="-1"
+            on:click=move |_| {
+                if let Some(c) = cursor {
+                    c.toggle_trade(&id);
+                }
+            }
+            on:contextmenu=move |ev| {
+                if let Some(menu) = menu {
+                    menu.show_trade(&ev, &menu_trade);
+                }
+            }
+        >
+            <span class="th-col time" role="cell">{time_str}</span>
+            <span class="th-col side" role="cell" aria-label=move || i18n.side(side) style=format!("color: {}", side_color)>{side_arrow}</span>
+            <span class="th-col price" role="cell" style=format!("color: {}", side_color)>{move || i18n.price(price, format.get())}</span>
+            <span class="th-col size" role="cell">
+                {(count > 1).then(|| view! { <span class="th-count" title="Trades merged into this row">{format!("×{}", count)}</span> })}
+                {move || i18n.quantity(qty, format.get())}
+            </span>
             {if show_value {
-                Some(view! { <span class="th-col value">{value_str}</span> })
+                Some(view! { <span class="th-col value" role="cell">{value_str}</span> })
             } else {
                 None
             }}
@@ -0,0 +1,121 @@
+//! Whale alert stream: dismissible cards for whale prints
+
+use dash_core::{colors, CompactNumberFormatter, LargeNumberFormatter};
+use dash_state::{use_app_state, Msg, WhaleAlert, WhaleAlerts, IMPACT_WINDOW_MS};
+use leptos::prelude::*;
+
+/// Whale prints newest first, each with its value, side and the price move
+/// over the following seconds. Opening the stream acknowledges everything.
+#[component]
+pub fn WhaleAlertStream(whales: WhaleAlerts) -> impl IntoView {
+    let state = use_app_state();
+    let (ui, i18n) = (state.ui, state.i18n);
+    let feed = whales.feed;
+
+    // Anything arriving while the stream is on screen counts as seen
+    Effect::new(move |_| {
+        whales.unread.track();
+        whales.mark_all_read();
+    });
+
+    let alerts = move || feed.with(|f| f.alerts().cloned().collect::<Vec<_>>());
+
+    view! {
+        <div class="whale-stream">
+            <div class="liq-summary">
+                <span class="stat-label">{move || format!("{} alerts", feed.with(|f| f.alerts().count()))}</span>
+                <label class="whale-notify" title="Also raise a toast for each whale print">
+                    <input
+                        type="checkbox"
+                        prop:checked=move || ui.with(|ui| ui.whale_notifications)
+                        on:change=move |_| state.toggle_whale_notifications()
+                    />
+                    "Notify"
+                </label>
+                <button class="sb-preset" on:click=move |_| whales.clear()>{move || i18n.t(Msg::Clear)}</button>
+            </div>
+            <div class="whale-list">
+                <Show
+                    when=move || feed.with(|f| !f.is_empty())
+                    fallback=move || view! { <div class="liq-empty">{move || i18n.t(Msg::NoWhalePrints)}</div> }
+                >
+                    <For
+                        each=alerts
+                        // Re-key once the impact settles so the card re-renders
+                        key=|alert| (alert.id.clone(), alert.impact.is_some())
+                        children=move |alert| view! { <WhaleCard alert=alert whales=whales /> }
+                    />
+                </Show>
+            </div>
+        </div>
+    }
+}
+
+#[component]
+fn WhaleCard(alert: WhaleAlert, whales: WhaleAlerts) -> impl IntoView {
+    let state = use_app_state();
+    let (i18n, directory) = (state.i18n, state.symbols);
+    let side_color = alert.side.css_color();
+    let id = alert.id.clone();
+    let time = chrono::DateTime::from_timestamp_millis(alert.timestamp)
+        .map(|t| t.format("%H:%M:%S").to_string())
+        .unwrap_or_default();
+
+    let symbol = alert.symbol.clone();
+    let fill = move || {
+        let format = directory.tick_format(&symbol);
+        format!("{} @ {}", i18n.quantity(alert.quantity, format), i18n.price(alert.price, format))
+    };
+
+    let impact = match alert.impact {
+        Some(pct) => {
+            let color = if pct >= 0.0 { colors::css::BULL } else { colors::css::BEAR };
+            view! {
+                <span class="whale-impact" style=format!("color: {}", color)>
+                    {format!("{:+.3}% in {}s", pct, IMPACT_WINDOW_MS / 1000)}
+                </span>
+            }
+            .into_any()
+        }
+        None => view! { <span class="whale-impact pending">"measuring…"</span> }.into_any(),
+    };
+
+    view! {
+        <div class="whale-card" style=format!("border-left-color: {}", side_color)>
+            <div class="whale-card-body">
+                <div class="whale-card-head">
+                    <span style=format!("color: {}", side_color)>
+                        {format!("🐋 {} {}", alert.side.label(), alert.symbol)}
+                    </span>
+                    <span class="whale-value">{CompactNumberFormatter.format(alert.value)}</span>
+                </div>
+                <div class="whale-card-meta">
+                    <span>{time}</span>
+                    <span>{fill}</span>
+                    {impact}
+                </div>
+            </div>
+            <button class="toast-close" title="Dismiss" on:click=move |_| whales.dismiss(&id)>"×"</button>
+        </div>
+    }
+}
+
+/// Panel header toggle for the whale stream with an unread badge
+#[component]
+pub fn WhaleToggle(whales: WhaleAlerts, #[prop(into)] active: Signal<bool>, on_toggle: Callback<()>) -> impl IntoView {
+    let unread = whales.unread;
+
+    view! {
+        <button
+            class=move || if active.get() { "sb-preset active" } else { "sb-preset" }
+            title="Whale trade alerts"
+            on:click=move |_| on_toggle.run(())
+        >
+            "🐋"
+            {move || {
+                let unread = unread.get();
+                (unread > 0).then(|| view! { <span class="whale-badge">{unread.min(99)}</span> })
+            }}
+        </button>
+    }
+}
@@ -0,0 +1,261 @@
+//! Rolling order book history sampled for the heatmap and book replay
+
+use crate::{OrderBookLevel, OrderBookSnapshot};
+use std::collections::VecDeque;
+
+/// Book columns kept (at [`BOOK_SAMPLE_MS`] apart: five minutes)
+pub const MAX_BOOK_COLUMNS: usize = 600;
+/// Minimum spacing between stored columns; faster snapshots overwrite the
+/// newest column instead of appending
+pub const BOOK_SAMPLE_MS: i64 = 500;
+
+/// Resting sizes at one moment, as `(price, quantity)` pairs
+#[derive(Debug, Clone, PartialEq)]
+pub struct BookColumn {
+    /// Unix timestamp in milliseconds
+    pub timestamp: i64,
+    pub bids: Vec<(f64, f64)>,
+    pub asks: Vec<(f64, f64)>,
+}
+
+impl BookColumn {
+    pub fn from_snapshot(book: &OrderBookSnapshot) -> Self {
+        let levels = |side: &[OrderBookLevel]| {
+            side.iter()
+                .map(|l| (l.price.as_f64(), l.quantity.as_f64()))
+                .collect()
+        };
+        Self {
+            timestamp: book.timestamp,
+            bids: levels(&book.bids),
+            asks: levels(&book.asks),
+        }
+    }
+
+    fn max_quantity(&self) -> f64 {
+        self.bids
+            .iter()
+            .chain(&self.asks)
+            .map(|&(_, qty)| qty)
+            .fold(0.0, f64::max)
+    }
+}
+
+/// Time-ordered book columns (oldest first)
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct BookHistory {
+    pub columns: VecDeque<BookColumn>,
+}
+
+impl BookHistory {
+    /// Record a snapshot, coalescing ones that arrive within [`BOOK_SAMPLE_MS`]
+    pub fn push(&mut self, book: &OrderBookSnapshot) {
+        let column = BookColumn::from_snapshot(book);
+        match self.columns.back_mut() {
+            Some(last) if column.timestamp < last.timestamp => return,
+            Some(last) if column.timestamp - last.timestamp < BOOK_SAMPLE_MS => {
+                // Keep the column's slot time so sampling stays regular
+                let timestamp = last.timestamp;
+                *last = BookColumn { timestamp, ..column };
+                return;
+            }
+            _ => {}
+        }
+        self.columns.push_back(column);
+        while self.columns.len() > MAX_BOOK_COLUMNS {
+            self.columns.pop_front();
+        }
+    }
+
+    pub fn clear(&mut self) {
+        self.columns.clear();
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.columns.is_empty()
+    }
+
+    /// Largest resting size anywhere in the history (for color scaling)
+    pub fn max_quantity(&self) -> f64 {
+        self.columns.iter().map(BookColumn::max_quantity).fold(0.0, f64::max)
+    }
+
+    /// Lowest bid and highest ask seen across the history
+    pub fn price_range(&self) -> Option<(f64, f64)> {
+        let prices = self
+            .columns
+            .iter()
+            .flat_map(|c| c.bids.iter().chain(&c.asks).map(|&(price, _)| price));
+        prices.fold(None, |range, price| match range {
+            None => Some((price, price)),
+            Some((low, high)) => Some((f64::min(low, price), f64::max(high, price))),
+        })
+    }
+
+    /// Time span covered, `(oldest, newest)`
+    pub fn time_range(&self) -> Option<(i64, i64)> {
+        Some((self.columns.front()?.timestamp, self.columns.back()?.timestamp))
+    }
+}
+
+/// How a level changed from one book column to the next
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LevelChange {
+    Unchanged,
+    Added,
+    Removed,
+    Grew,
+    Shrank,
+}
+
+impl LevelChange {
+    pub fn css_class(&self) -> &'static str {
+        match self {
+            Self::Unchanged => "",
+            Self::Added => "added",
+            Self::Removed => "removed",
+            Self::Grew => "grew",
+            Self::Shrank => "shrank",
+        }
+    }
+}
+
+/// A level of one replay step against the step before it
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LevelDiff {
+    pub price: f64,
+    /// Size now (0 once removed)
+    pub quantity: f64,
+    /// Size in the step before (0 if new)
+    pub previous: f64,
+    pub change: LevelChange,
+}
+
+impl LevelDiff {
+    pub fn delta(&self) -> f64 {
+        self.quantity - self.previous
+    }
+}
+
+/// The best levels of one column with what changed since the column before
+#[derive(Debug, Clone, PartialEq)]
+pub struct BookDiff {
+    pub timestamp: i64,
+    /// Best first
+    pub bids: Vec<LevelDiff>,
+    /// Best first
+    pub asks: Vec<LevelDiff>,
+}
+
+impl BookHistory {
+    /// The `depth` best levels per side of column `index`, diffed against the
+    /// column before it (the oldest column diffs as all unchanged)
+    pub fn diff_at(&self, index: usize, depth: usize) -> Option<BookDiff> {
+        let current = self.columns.get(index)?;
+        let previous = index.checked_sub(1).and_then(|i| self.columns.get(i)).unwrap_or(current);
+        Some(BookDiff {
+            timestamp: current.timestamp,
+            bids: diff_side(&previous.bids, &current.bids, depth, true),
+            asks: diff_side(&previous.asks, &current.asks, depth, false),
+        })
+    }
+
+    /// Index of the newest column at or before `timestamp` (the oldest one
+    /// if it has aged out)
+    pub fn index_at(&self, timestamp: i64) -> Option<usize> {
+        if self.columns.is_empty() {
+            return None;
+        }
+        Some(self.columns.partition_point(|c| c.timestamp <= timestamp).saturating_sub(1))
+    }
+}
+
+/// Diff one side's best `depth` levels; levels gone from the top of the book
+/// are kept as removed unless they just moved deeper
+fn diff_side(previous: &[(f64, f64)], current: &[(f64, f64)], depth: usize, descending: bool) -> Vec<LevelDiff> {
+    let find = |levels: &[(f64, f64)], price: f64| levels.iter().find(|&&(p, _)| p == price).map(|&(_, qty)| qty);
+    let top = &current[..current.len().min(depth)];
+
+    let mut diffs: Vec<LevelDiff> = top
+        .iter()
+        .map(|&(price, quantity)| {
+            let (previous, change) = match find(previous, price) {
+                None => (0.0, LevelChange::Added),
+                Some(before) if quantity > before => (before, LevelChange::Grew),
+                Some(before) if quantity < before => (before, LevelChange::Shrank),
+                Some(before) => (before, LevelChange::Unchanged),
+            };
+            LevelDiff { price, quantity, previous, change }
+        })
+        .collect();
+    diffs.extend(
+        previous[..previous.len().min(depth)]
+            .iter()
+            .filter(|&&(price, _)| find(current, price).is_none())
+            .map(|&(price, before)| LevelDiff { price, quantity: 0.0, previous: before, change: LevelChange::Removed }),
+    );
+    if descending {
+        diffs.sort_by(|a, b| b.price.total_cmp(&a.price));
+    } else {
+        diffs.sort_by(|a, b| a.price.total_cmp(&b.price));
+    }
+    diffs
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Symbol;
+
+    fn book(timestamp: i64, bid_qty: f64) -> OrderBookSnapshot {
+        let mut book = OrderBookSnapshot::new(Symbol::new("BTC-USD"));
+        book.timestamp = timestamp;
+        book.bids.push(OrderBookLevel::new(99.0, bid_qty, 1));
+        book.asks.push(OrderBookLevel::new(101.0, 1.0, 1));
+        book
+    }
+
+    #[test]
+    fn test_push_coalesces_and_caps() {
+        let mut history = BookHistory::default();
+        history.push(&book(1_000, 1.0));
+        history.push(&book(1_200, 3.0));
+        assert_eq!(history.columns.len(), 1);
+        assert_eq!(history.columns[0].timestamp, 1_000);
+        assert_eq!(history.max_quantity(), 3.0);
+
+        // Out-of-order snapshots are dropped
+        history.push(&book(900, 9.0));
+        assert_eq!(history.max_quantity(), 3.0);
+
+        for i in 0..(MAX_BOOK_COLUMNS as i64 + 5) {
+            history.push(&book(2_000 + i * BOOK_SAMPLE_MS, 1.0));
+        }
+        assert_eq!(history.columns.len(), MAX_BOOK_COLUMNS);
+        assert_eq!(history.price_range(), Some((99.0, 101.0)));
+        assert!(history.time_range().is_some_and(|(old, new)| old < new));
+    }
+
+    #[test]
+    fn test_diff_at_marks_level_changes() {
+        let mut history = BookHistory::default();
+        let mut first = book(1_000, 2.0);
+        first.bids.push(OrderBookLevel::new(98.0, 1.0, 1));
+        history.push(&first);
+        let mut second = book(2_000, 3.0);
+        second.bids.push(OrderBookLevel::new(97.0, 4.0, 1));
+        second.asks[0] = OrderBookLevel::new(100.5, 1.0, 1);
+        history.push(&second);
+
+        let diff = history.diff_at(1, 10).unwrap();
+        let bids: Vec<(f64, LevelChange)> = diff.bids.iter().map(|l| (l.price, l.change)).collect();
+        assert_eq!(bids, vec![(99.0, LevelChange::Grew), (98.0, LevelChange::Removed), (97.0, LevelChange::Added)]);
+        assert_eq!(diff.bids[0].delta(), 1.0);
+        let asks: Vec<(f64, LevelChange)> = diff.asks.iter().map(|l| (l.price, l.change)).collect();
+        assert_eq!(asks, vec![(100.5, LevelChange::Added), (101.0, LevelChange::Removed)]);
+
+        // The oldest column has nothing to diff against
+        assert!(history.diff_at(0, 10).unwrap().bids.iter().all(|l| l.change == LevelChange::Unchanged));
+        assert_eq!((history.index_at(1_500), history.index_at(500), history.index_at(9_000)), (Some(0), Some(0), Some(1)));
+    }
+}
@@ -1,7 +1,8 @@
 //! Candlestick (OHLCV) types for charting
 
-use crate::{colors, Price, Quantity, Symbol};
+use crate::{colors::{self, Palette}, Price, Quantity, Symbol, Trade};
 use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
 
 // ============================================================================
 // STRATEGY PATTERN: Candle Pattern Detection
@@ -104,8 +105,10 @@ impl CandlePatternDetector for BasicPatternDetector {
 // ============================================================================
 
 /// Time interval for candlesticks
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub enum CandleInterval {
+    #[default]
     #[serde(rename = "1m")]
     M1,
     #[serde(rename = "5m")]
@@ -144,6 +147,21 @@ impl CandleInterval {
         self.as_secs() * 1000
     }
 
+    /// Open time of the bucket containing `timestamp_ms` (weeks open Monday 00:00 UTC)
+    pub fn bucket_start(&self, timestamp_ms: i64) -> i64 {
+        // 1970-01-01 was a Thursday
+        const MONDAY_OFFSET_MS: i64 = 4 * 86_400_000;
+
+        let len = self.as_millis();
+        let offset = if *self == Self::W1 { MONDAY_OFFSET_MS } else { 0 };
+        (timestamp_ms - offset).div_euclid(len) * len + offset
+    }
+
+    /// Milliseconds until the bucket containing `timestamp_ms` closes
+    pub fn remaining_ms(&self, timestamp_ms: i64) -> i64 {
+        self.bucket_start(timestamp_ms) + self.as_millis() - timestamp_ms
+    }
+
     /// Display label
     pub fn label(&self) -> &'static str {
         match self {
@@ -158,6 +176,28 @@ impl CandleInterval {
         }
     }
 
+    /// Wire/URL code (matches the serde representation)
+    pub fn code(&self) -> &'static str {
+        match self {
+            Self::M1 => "1m",
+            Self::M5 => "5m",
+            Self::M15 => "15m",
+            Self::M30 => "30m",
+            Self::H1 => "1h",
+            Self::H4 => "4h",
+            Self::D1 => "1d",
+            Self::W1 => "1w",
+        }
+    }
+
+    /// Parse a wire code or display label (case-insensitive, e.g. "1h" or "1H")
+    pub fn from_code(code: &str) -> Option<Self> {
+        Self::all()
+            .iter()
+            .copied()
+            .find(|i| i.code().eq_ignore_ascii_case(code))
+    }
+
     /// All intervals
     pub fn all() -> &'static [Self] {
         &[
@@ -167,9 +207,16 @@ impl CandleInterval {
     }
 }
 
-impl Default for CandleInterval {
-    fn default() -> Self {
-        Self::M1
+/// Countdown text: `mm:ss`, `h:mm:ss`, or `Nd hh:mm:ss` (seconds round up)
+pub fn format_countdown(remaining_ms: i64) -> String {
+    let secs = (remaining_ms.max(0) + 999) / 1000;
+    let (days, hours, mins, secs) = (secs / 86_400, secs / 3600 % 24, secs / 60 % 60, secs % 60);
+    if days > 0 {
+        format!("{}d {:02}:{:02}:{:02}", days, hours, mins, secs)
+    } else if hours > 0 {
+        format!("{}:{:02}:{:02}", hours, mins, secs)
+    } else {
+        format!("{:02}:{:02}", mins, secs)
     }
 }
 
@@ -180,7 +227,8 @@ impl std::fmt::Display for CandleInterval {
 }
 
 /// Single OHLCV candlestick
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct Candle {
     pub symbol: Symbol,
     pub interval: CandleInterval,
@@ -231,6 +279,20 @@ impl Candle {
         self.trade_count += 1;
     }
 
+    /// Fold a later, finer-grained candle into this one
+    pub fn merge(&mut self, other: &Candle) {
+        if other.high.as_f64() > self.high.as_f64() {
+            self.high = other.high;
+        }
+        if other.low.as_f64() < self.low.as_f64() {
+            self.low = other.low;
+        }
+        self.close = other.close;
+        self.volume = Quantity::new(self.volume.as_f64() + other.volume.as_f64());
+        self.quote_volume += other.quote_volume;
+        self.trade_count += other.trade_count;
+    }
+
     /// Close the candle
     pub fn close_candle(&mut self) {
         self.is_closed = true;
@@ -292,13 +354,14 @@ impl Candle {
         self.open.as_f64().min(self.close.as_f64())
     }
 
-    /// Fill color for rendering
-    pub fn fill_color(&self) -> &'static str {
-        if self.is_bullish() {
-            colors::BULL
-        } else {
-            colors::BEAR
-        }
+    /// Fill color in `palette` (for canvas and WebGL painting)
+    pub fn fill_color(&self, palette: &Palette) -> &'static str {
+        palette.direction(self.is_bullish())
+    }
+
+    /// Themed fill color (CSS custom property reference)
+    pub fn css_color(&self) -> &'static str {
+        colors::css::direction(self.is_bullish())
     }
 
     /// CSS class
@@ -312,12 +375,13 @@ impl Candle {
 
     /// Detect patterns using given strategy
     pub fn detect_patterns_with<D: CandlePatternDetector>(&self, detector: &D) -> Vec<CandlePattern> {
-        detector.detect(&[self.clone()])
+        detector.detect(std::slice::from_ref(self))
     }
 }
 
 /// Collection of candles for charting
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct CandleHistory {
     pub symbol: Symbol,
     pub interval: CandleInterval,
@@ -416,12 +480,531 @@ impl CandleHistory {
     pub fn detect_patterns_with<D: CandlePatternDetector>(&self, detector: &D) -> Vec<CandlePattern> {
         detector.detect(&self.candles)
     }
+
+    /// Resample into a coarser interval (no-op clone if not coarser)
+    pub fn resample(&self, target: CandleInterval) -> CandleHistory {
+        if target.as_millis() <= self.interval.as_millis() {
+            return self.clone();
+        }
+
+        CandleHistory {
+            symbol: self.symbol.clone(),
+            interval: target,
+            candles: resample_candles(&self.candles, target),
+        }
+    }
+}
+
+// ============================================================================
+// RESAMPLING
+// ============================================================================
+
+/// Aggregate time-ordered candles into `target` buckets.
+///
+/// A bucket is closed once its final source candle is closed; a gap at the end
+/// of the bucket leaves it open (still forming).
+pub fn resample_candles(candles: &[Candle], target: CandleInterval) -> Vec<Candle> {
+    let bucket_len = target.as_millis();
+    let mut out: Vec<Candle> = Vec::new();
+
+    for candle in candles {
+        let bucket = target.bucket_start(candle.timestamp);
+
+        match out.last_mut() {
+            Some(current) if current.timestamp == bucket => current.merge(candle),
+            _ => {
+                let mut opened = candle.clone();
+                opened.interval = target;
+                opened.timestamp = bucket;
+                out.push(opened);
+            }
+        }
+
+        if let Some(current) = out.last_mut() {
+            let source_end = candle.timestamp + candle.interval.as_millis();
+            current.is_closed = candle.is_closed && source_end >= bucket + bucket_len;
+        }
+    }
+
+    out
+}
+
+// ============================================================================
+// CONSISTENCY
+// ============================================================================
+
+/// Relative difference tolerated between volumes summed in a different
+/// order
+const VOLUME_TOLERANCE: f64 = 1e-9;
+
+/// Closed candles built from time-ordered trades, one per bucket that saw a
+/// print
+pub fn candles_from_trades(trades: &[Trade], interval: CandleInterval) -> Vec<Candle> {
+    let mut out: Vec<Candle> = Vec::new();
+    for trade in trades {
+        let (price, quantity) = (trade.price.as_f64(), trade.quantity.as_f64());
+        let bucket = interval.bucket_start(trade.timestamp.timestamp_millis());
+        match out.last_mut() {
+            Some(current) if current.timestamp == bucket => current.update(price, quantity),
+            _ => {
+                let mut candle = Candle::new(trade.symbol.clone(), interval, bucket, price);
+                candle.update(price, quantity);
+                candle.close_candle();
+                out.push(candle);
+            }
+        }
+    }
+    out
+}
+
+/// Fields on which two candles for the same bucket disagree: prices must
+/// match exactly (both come from the same prints), volumes within rounding
+pub fn candle_mismatches(official: &Candle, local: &Candle) -> Vec<String> {
+    let volume_differs = |a: f64, b: f64| (a - b).abs() > VOLUME_TOLERANCE * a.abs().max(b.abs()).max(1.0);
+    [
+        ("open", official.open != local.open),
+        ("high", official.high != local.high),
+        ("low", official.low != local.low),
+        ("close", official.close != local.close),
+        ("volume", volume_differs(official.volume.as_f64(), local.volume.as_f64())),
+        ("trades", official.trade_count != local.trade_count),
+    ]
+    .into_iter()
+    .filter(|&(_, differs)| differs)
+    .map(|(field, _)| field.to_string())
+    .collect()
+}
+
+/// A bucket where the official bar and the trades seen for it disagree
+/// (either side may be missing)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct CandleDiscrepancy {
+    /// Bucket open time in milliseconds
+    pub timestamp: i64,
+    pub official: Option<Candle>,
+    pub from_trades: Option<Candle>,
+    /// Disagreeing fields, or `bar` when one side has no candle
+    pub fields: Vec<String>,
+}
+
+/// One cross-check of a symbol's recent bars against its trades
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct CandleCheckReport {
+    pub symbol: Symbol,
+    /// Buckets checked, `from <= open time < to` (ms)
+    pub from: i64,
+    pub to: i64,
+    pub checked: usize,
+    pub discrepancies: Vec<CandleDiscrepancy>,
+    /// Timestamp in milliseconds
+    pub timestamp: i64,
+}
+
+impl CandleCheckReport {
+    /// Compare the `official` 1m bars opening in `from..to` with bars
+    /// rebuilt from the `trades` over the same span
+    pub fn check(symbol: Symbol, official: &[Candle], trades: &[Trade], from: i64, to: i64, now: i64) -> Self {
+        let in_span = |c: &&Candle| c.interval == CandleInterval::M1 && (from..to).contains(&c.timestamp);
+        let official: BTreeMap<i64, &Candle> = official.iter().filter(in_span).map(|c| (c.timestamp, c)).collect();
+        let rebuilt = candles_from_trades(trades, CandleInterval::M1);
+        let rebuilt: BTreeMap<i64, &Candle> = rebuilt.iter().filter(in_span).map(|c| (c.timestamp, c)).collect();
+
+        let buckets: BTreeSet<i64> = official.keys().chain(rebuilt.keys()).copied().collect();
+        let discrepancies = buckets
+            .iter()
+            .filter_map(|bucket| {
+                let (official, local) = (official.get(bucket).copied(), rebuilt.get(bucket).copied());
+                let fields = match (official, local) {
+                    (Some(official), Some(local)) => candle_mismatches(official, local),
+                    _ => vec!["bar".to_string()],
+                };
+                (!fields.is_empty()).then(|| CandleDiscrepancy {
+                    timestamp: *bucket,
+                    official: official.cloned(),
+                    from_trades: local.cloned(),
+                    fields,
+                })
+            })
+            .collect();
+
+        Self {
+            symbol,
+            from,
+            to,
+            checked: buckets.len(),
+            discrepancies,
+            timestamp: now,
+        }
+    }
+
+    pub fn is_consistent(&self) -> bool {
+        self.discrepancies.is_empty()
+    }
+
+    /// Official bars to replace the divergent ones with
+    pub fn repairs(&self) -> impl Iterator<Item = &Candle> {
+        self.discrepancies.iter().filter_map(|d| d.official.as_ref())
+    }
+}
+
+// ============================================================================
+// CHART TYPES
+// ============================================================================
+
+/// How the price pane draws the candles
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub enum ChartType {
+    #[default]
+    Candles,
+    /// OHLC bars (open tick left, close tick right)
+    Bars,
+    /// Closes joined by a line
+    Line,
+    /// Line with the area under it filled
+    Area,
+    /// Candles of the [`heikin_ashi`] transform
+    HeikinAshi,
+    /// Fixed-size [`renko`] bricks, independent of time
+    Renko,
+    /// [`range_bars`] spanning a fixed price range each
+    RangeBars,
+}
+
+impl ChartType {
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Candles => "Candles",
+            Self::Bars => "Bars",
+            Self::Line => "Line",
+            Self::Area => "Area",
+            Self::HeikinAshi => "Heikin-Ashi",
+            Self::Renko => "Renko",
+            Self::RangeBars => "Range",
+        }
+    }
+
+    pub fn all() -> &'static [Self] {
+        &[Self::Candles, Self::Bars, Self::Line, Self::Area, Self::HeikinAshi, Self::Renko, Self::RangeBars]
+    }
+
+    /// Built from the price path at a [`BrickSize`] rather than one bar per
+    /// interval
+    pub fn is_price_driven(&self) -> bool {
+        matches!(self, Self::Renko | Self::RangeBars)
+    }
+}
+
+/// Renko brick or range bar height, in basis points of price
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct BrickSize(pub u32);
+
+impl BrickSize {
+    pub fn presets() -> &'static [Self] {
+        &[Self(5), Self(10), Self(25), Self(50), Self(100)]
+    }
+
+    pub fn label(&self) -> String {
+        format!("{}%", self.0 as f64 / 100.0)
+    }
+
+    /// Absolute size at `price`, rounded to two significant figures
+    pub fn at(&self, price: f64) -> f64 {
+        let raw = price.abs() * self.0 as f64 / 10_000.0;
+        if raw <= 0.0 {
+            return 0.0;
+        }
+        let unit = 10_f64.powf(raw.log10().floor() - 1.0);
+        (raw / unit).round() * unit
+    }
+}
+
+impl Default for BrickSize {
+    fn default() -> Self {
+        Self(10)
+    }
+}
+
+/// Renderer a chart draws its series with
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub enum RenderBackend {
+    /// One DOM node per shape
+    #[default]
+    Svg,
+    /// Immediate-mode 2D canvas at device resolution
+    Canvas,
+    /// WebGL2 triangle batches, for very dense series (falls back to canvas)
+    WebGl,
+}
+
+impl RenderBackend {
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Svg => "SVG",
+            Self::Canvas => "Canvas",
+            Self::WebGl => "WebGL",
+        }
+    }
+
+    pub fn all() -> &'static [Self] {
+        &[Self::Svg, Self::Canvas, Self::WebGl]
+    }
+}
+
+/// How the price axis maps prices to height
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub enum PriceScaleMode {
+    #[default]
+    Linear,
+    /// Equal ratios take equal height
+    Log,
+    /// Linear, labelled as change from the first visible close
+    Percent,
+}
+
+impl PriceScaleMode {
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Linear => "Lin",
+            Self::Log => "Log",
+            Self::Percent => "%",
+        }
+    }
+
+    pub fn all() -> &'static [Self] {
+        &[Self::Linear, Self::Log, Self::Percent]
+    }
+}
+
+/// Clock the chart's time axis reads in
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub enum TimeZoneMode {
+    #[default]
+    Utc,
+    /// The browser's time zone
+    Local,
+}
+
+impl TimeZoneMode {
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Utc => "UTC",
+            Self::Local => "Local",
+        }
+    }
+
+    pub fn all() -> &'static [Self] {
+        &[Self::Utc, Self::Local]
+    }
+
+    /// `timestamp_ms` moved onto this clock, so UTC formatting and day
+    /// arithmetic read as wall time here
+    pub fn shift(&self, timestamp_ms: i64) -> i64 {
+        match self {
+            Self::Utc => timestamp_ms,
+            Self::Local => {
+                use chrono::{Local, Offset, TimeZone};
+                let offset = chrono::DateTime::from_timestamp_millis(timestamp_ms)
+                    .map_or(0, |t| Local.offset_from_utc_datetime(&t.naive_utc()).fix().local_minus_utc());
+                timestamp_ms + offset as i64 * 1000
+            }
+        }
+    }
+}
+
+/// Heikin-Ashi candles: close is the OHLC average, open the midpoint of the
+/// previous Heikin-Ashi body, and the wicks stretch to cover both.
+/// Time, volume and closed state carry over unchanged.
+pub fn heikin_ashi(candles: &[Candle]) -> Vec<Candle> {
+    let mut out: Vec<Candle> = Vec::with_capacity(candles.len());
+    for candle in candles {
+        let (open, high, low, close) = (
+            candle.open.as_f64(),
+            candle.high.as_f64(),
+            candle.low.as_f64(),
+            candle.close.as_f64(),
+        );
+        let ha_close = (open + high + low + close) / 4.0;
+        let ha_open = match out.last() {
+            Some(prev) => (prev.open.as_f64() + prev.close.as_f64()) / 2.0,
+            None => (open + close) / 2.0,
+        };
+        let mut ha = candle.clone();
+        ha.open = Price::new(ha_open);
+        ha.close = Price::new(ha_close);
+        ha.high = Price::new(high.max(ha_open).max(ha_close));
+        ha.low = Price::new(low.min(ha_open).min(ha_close));
+        out.push(ha);
+    }
+    out
+}
+
+/// One step along the traded price path
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PriceSample {
+    /// Unix timestamp in milliseconds
+    pub time: i64,
+    pub price: f64,
+    /// Volume traded at this step
+    pub volume: f64,
+}
+
+/// Candles walked open → nearer extreme → farther extreme → close, the
+/// usual stand-in for the trades inside them; volume lands on the close
+pub fn candle_path(candles: &[Candle]) -> impl Iterator<Item = PriceSample> + '_ {
+    candles.iter().flat_map(|c| {
+        let (first, second) = if c.is_bullish() { (c.low, c.high) } else { (c.high, c.low) };
+        let step = |price: Price, volume: f64| PriceSample { time: c.timestamp, price: price.as_f64(), volume };
+        [step(c.open, 0.0), step(first, 0.0), step(second, 0.0), step(c.close, c.volume.as_f64())]
+    })
+}
+
+/// Renko bricks of height `size`: a brick is laid each time price moves a
+/// full size past the last brick's top or bottom, so reversing takes two.
+/// Bricks have no wicks, are stamped with the time they completed and carry
+/// the volume traded since the brick before.
+pub fn renko(symbol: &Symbol, samples: impl IntoIterator<Item = PriceSample>, size: f64) -> Vec<Candle> {
+    let mut bricks = Vec::new();
+    if size <= 0.0 {
+        return bricks;
+    }
+    let brick = |time: i64, open: f64, close: f64, volume: &mut f64| {
+        let mut candle = Candle::new(symbol.clone(), CandleInterval::M1, time, open);
+        candle.update(close, std::mem::take(volume));
+        candle.is_closed = true;
+        candle
+    };
+
+    // (bottom, top) of the last brick
+    let mut last: Option<(f64, f64)> = None;
+    let mut volume = 0.0;
+    for sample in samples {
+        volume += sample.volume;
+        let (mut bottom, mut top) = *last.get_or_insert_with(|| {
+            let base = (sample.price / size).floor() * size;
+            (base, base)
+        });
+        while sample.price >= top + size {
+            bricks.push(brick(sample.time, top, top + size, &mut volume));
+            (bottom, top) = (top, top + size);
+        }
+        while sample.price <= bottom - size {
+            bricks.push(brick(sample.time, bottom, bottom - size, &mut volume));
+            (bottom, top) = (bottom - size, bottom);
+        }
+        last = Some((bottom, top));
+    }
+    bricks
+}
+
+/// Range bars spanning `size` from high to low: a bar closes on the edge
+/// price crossed and the next opens there, whatever the time taken. The
+/// last bar is still forming.
+pub fn range_bars(symbol: &Symbol, samples: impl IntoIterator<Item = PriceSample>, size: f64) -> Vec<Candle> {
+    let mut bars: Vec<Candle> = Vec::new();
+    if size <= 0.0 {
+        return bars;
+    }
+    for sample in samples {
+        let mut forming = match bars.pop() {
+            Some(bar) if !bar.is_closed => bar,
+            closed => {
+                bars.extend(closed);
+                Candle::new(symbol.clone(), CandleInterval::M1, sample.time, sample.price)
+            }
+        };
+        loop {
+            let (low, high) = (forming.low.as_f64(), forming.high.as_f64());
+            let edge = if sample.price > low + size {
+                low + size
+            } else if sample.price < high - size {
+                high - size
+            } else {
+                break;
+            };
+            forming.update(edge, 0.0);
+            forming.is_closed = true;
+            bars.push(forming);
+            forming = Candle::new(symbol.clone(), CandleInterval::M1, sample.time, edge);
+        }
+        forming.update(sample.price, sample.volume);
+        bars.push(forming);
+    }
+    bars
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    fn path(prices: &[f64]) -> Vec<PriceSample> {
+        prices
+            .iter()
+            .enumerate()
+            .map(|(i, &price)| PriceSample { time: i as i64, price, volume: 1.0 })
+            .collect()
+    }
+
+    #[test]
+    fn test_renko_needs_two_bricks_to_reverse() {
+        let bricks = renko(&Symbol::default(), path(&[100.0, 101.0, 103.5, 102.5, 101.0, 99.0]), 1.0);
+        let moves: Vec<(f64, f64)> = bricks.iter().map(|b| (b.open.as_f64(), b.close.as_f64())).collect();
+
+        // Up to 103, back down past 102 (two below the top) to 99
+        assert_eq!(moves, vec![(100.0, 101.0), (101.0, 102.0), (102.0, 103.0), (102.0, 101.0), (101.0, 100.0), (100.0, 99.0)]);
+        assert_eq!(bricks[1].timestamp, 2);
+        // The first brick has the opening print's volume too
+        assert_eq!(bricks[0].volume.as_f64(), 2.0);
+        assert!(bricks.iter().all(|b| b.high.as_f64() == b.open.as_f64().max(b.close.as_f64()) && b.is_closed));
+    }
+
+    #[test]
+    fn test_range_bars_close_on_the_edge() {
+        let bars = range_bars(&Symbol::default(), path(&[100.0, 101.0, 99.5, 103.0, 104.0]), 2.0);
+        let ranges: Vec<(f64, f64, f64, f64)> = bars
+            .iter()
+            .map(|b| (b.open.as_f64(), b.high.as_f64(), b.low.as_f64(), b.close.as_f64()))
+            .collect();
+
+        // 99.5 + 2 closes the first bar; the second tops out at 101.5 + 2
+        assert_eq!(ranges, vec![(100.0, 101.5, 99.5, 101.5), (101.5, 103.5, 101.5, 103.5), (103.5, 104.0, 103.5, 104.0)]);
+        assert!(bars[0].is_closed && bars[1].is_closed && !bars[2].is_closed);
+    }
+
+    #[test]
+    fn test_brick_size() {
+        assert_eq!(BrickSize(10).at(64_321.0), 64.0);
+        assert_eq!(BrickSize(25).at(2_345.0), 5.9);
+        assert_eq!(BrickSize(10).label(), "0.1%");
+        assert_eq!(BrickSize::default().at(0.0), 0.0);
+    }
+
+    #[test]
+    fn test_heikin_ashi() {
+        let mut first = Candle::new(Symbol::default(), CandleInterval::M1, 0, 10.0);
+        first.update(14.0, 1.0);
+        first.update(8.0, 1.0);
+        first.update(12.0, 1.0);
+        let mut second = Candle::new(Symbol::default(), CandleInterval::M1, 60_000, 12.0);
+        second.update(13.0, 1.0);
+
+        let ha = heikin_ashi(&[first, second.clone()]);
+
+        // (10 + 14 + 8 + 12) / 4 and (10 + 12) / 2
+        assert_eq!((ha[0].open.as_f64(), ha[0].close.as_f64()), (11.0, 11.0));
+        // Opens at the previous body's midpoint; wicks cover the real range
+        assert_eq!(ha[1].open.as_f64(), 11.0);
+        assert_eq!(ha[1].close.as_f64(), 12.5);
+        assert_eq!((ha[1].high.as_f64(), ha[1].low.as_f64()), (13.0, 11.0));
+        assert_eq!(ha[1].volume, second.volume);
+    }
+
     #[test]
     fn test_candle_update() {
         let mut candle = Candle::new(Symbol::default(), CandleInterval::M1, 1700000000000, 50000.0);
@@ -448,6 +1031,100 @@ mod tests {
         assert!(bearish.is_bearish());
     }
 
+    #[test]
+    fn test_interval_codes() {
+        assert_eq!(CandleInterval::from_code("4h"), Some(CandleInterval::H4));
+        assert_eq!(CandleInterval::from_code("1D"), Some(CandleInterval::D1));
+        assert_eq!(CandleInterval::from_code("2h"), None);
+        for interval in CandleInterval::all() {
+            let json = serde_json::to_string(interval).unwrap();
+            assert_eq!(json, format!("\"{}\"", interval.code()));
+        }
+    }
+
+    #[test]
+    fn test_bucket_start() {
+        // 2024-01-03 (Wednesday) 10:17 UTC
+        let ts = 1_704_277_020_000;
+        assert_eq!(CandleInterval::M5.bucket_start(ts), 1_704_276_900_000);
+        assert_eq!(CandleInterval::D1.bucket_start(ts), 1_704_240_000_000);
+        // Monday 2024-01-01 00:00 UTC
+        assert_eq!(CandleInterval::W1.bucket_start(ts), 1_704_067_200_000);
+    }
+
+    #[test]
+    fn test_countdown() {
+        let ts = 1_704_277_020_000; // 10:17:00 UTC
+        assert_eq!(CandleInterval::M5.remaining_ms(ts), 3 * 60_000);
+        // Partial seconds round up
+        assert_eq!(format_countdown(CandleInterval::M5.remaining_ms(ts + 500)), "03:00");
+        assert_eq!(format_countdown(CandleInterval::H4.remaining_ms(ts)), "1:43:00");
+        assert_eq!(format_countdown(CandleInterval::W1.remaining_ms(ts)), "4d 13:43:00");
+        assert_eq!(format_countdown(0), "00:00");
+    }
+
+    #[test]
+    fn test_resample_to_5m() {
+        let minute = CandleInterval::M1.as_millis();
+        let candles: Vec<Candle> = (0..7)
+            .map(|i| {
+                let mut c = Candle::new(Symbol::default(), CandleInterval::M1, i * minute, 100.0 + i as f64);
+                c.update(101.0 + i as f64, 1.0);
+                c.is_closed = i < 6;
+                c
+            })
+            .collect();
+
+        let bars = resample_candles(&candles, CandleInterval::M5);
+        assert_eq!(bars.len(), 2);
+
+        let first = &bars[0];
+        assert_eq!(first.interval, CandleInterval::M5);
+        assert_eq!(first.open.as_f64(), 100.0);
+        assert_eq!(first.high.as_f64(), 105.0);
+        assert_eq!(first.close.as_f64(), 105.0);
+        assert_eq!(first.volume.as_f64(), 5.0);
+        assert!(first.is_closed);
+
+        let second = &bars[1];
+        assert_eq!(second.timestamp, 5 * minute);
+        assert_eq!(second.trade_count, 2);
+        assert!(!second.is_closed);
+    }
+
+    #[test]
+    fn test_candle_check_against_trades() {
+        let minute = CandleInterval::M1.as_millis();
+        let symbol = Symbol::new("BTC-USD");
+        let trade = |ms: i64, price: f64| {
+            let mut trade = Trade::new(symbol.clone(), price, 1.0, crate::TradeSide::Buy);
+            trade.timestamp = chrono::DateTime::from_timestamp_millis(ms).unwrap();
+            trade
+        };
+        let trades = vec![trade(5_000, 100.0), trade(30_000, 102.0), trade(minute + 1_000, 101.0)];
+
+        let mut official = candles_from_trades(&trades, CandleInterval::M1);
+        assert_eq!(official.len(), 2);
+        assert_eq!(official[0].high.as_f64(), 102.0);
+        assert_eq!(official[0].trade_count, 2);
+        let report = CandleCheckReport::check(symbol.clone(), &official, &trades, 0, 2 * minute, 0);
+        assert!(report.is_consistent());
+        assert_eq!(report.checked, 2);
+
+        // A print the feed never delivered shows up in the official bar only
+        official[1].update(99.0, 0.5);
+        let report = CandleCheckReport::check(symbol.clone(), &official, &trades, 0, 2 * minute, 0);
+        assert_eq!(report.discrepancies.len(), 1);
+        assert_eq!(report.discrepancies[0].fields, ["low", "close", "volume", "trades"]);
+        assert_eq!(report.repairs().next().map(|c| c.timestamp), Some(minute));
+
+        // Buckets outside the span are ignored; a bar with no trades is flagged
+        official.push(Candle::new(symbol.clone(), CandleInterval::M1, 2 * minute, 100.0));
+        let report = CandleCheckReport::check(symbol, &official, &trades, minute, 3 * minute, 0);
+        assert_eq!(report.checked, 2);
+        assert_eq!(report.discrepancies.last().map(|d| d.fields.clone()), Some(vec!["bar".to_string()]));
+    }
+
     #[test]
     fn test_doji_detection() {
         let detector = BasicPatternDetector::new();
@@ -0,0 +1,121 @@
+//! Chart drawings (trendlines, rays, boxes, Fibonacci retracements)
+//!
+//! Anchors are stored in time and price rather than pixels, so a drawing
+//! stays put as the chart pans, rescales or takes in new candles.
+
+use serde::{Deserialize, Serialize};
+
+/// Kind of drawing
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub enum DrawingTool {
+    /// Segment between two points
+    Trendline,
+    /// Level running from a point to the right edge
+    HorizontalRay,
+    /// Box spanned by two corners
+    Rectangle,
+    /// Retracement levels between a swing's start and end
+    Fibonacci,
+}
+
+impl DrawingTool {
+    /// Short toolbar label
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Trendline => "Trend",
+            Self::HorizontalRay => "Ray",
+            Self::Rectangle => "Box",
+            Self::Fibonacci => "Fib",
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Trendline => "Trendline",
+            Self::HorizontalRay => "Horizontal ray",
+            Self::Rectangle => "Rectangle",
+            Self::Fibonacci => "Fibonacci retracement",
+        }
+    }
+
+    /// Placed with a single click rather than a drag
+    pub fn is_single_point(&self) -> bool {
+        matches!(self, Self::HorizontalRay)
+    }
+
+    pub fn all() -> &'static [Self] {
+        &[Self::Trendline, Self::HorizontalRay, Self::Rectangle, Self::Fibonacci]
+    }
+}
+
+/// Point on the chart: candle open time (ms) and price
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct Anchor {
+    pub time: i64,
+    pub price: f64,
+}
+
+impl Anchor {
+    pub fn new(time: i64, price: f64) -> Self {
+        Self { time, price }
+    }
+}
+
+/// Retracement ratios drawn by the Fibonacci tool
+pub const FIB_LEVELS: [f64; 7] = [0.0, 0.236, 0.382, 0.5, 0.618, 0.786, 1.0];
+
+/// A drawing on one symbol's chart
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct Drawing {
+    /// Unique per symbol; 0 until the drawing is stored
+    pub id: u64,
+    pub tool: DrawingTool,
+    pub start: Anchor,
+    /// Same as `start` for single-point tools
+    pub end: Anchor,
+}
+
+impl Drawing {
+    pub fn new(id: u64, tool: DrawingTool, start: Anchor, end: Anchor) -> Self {
+        Self { id, tool, start, end }
+    }
+
+    /// Two-point drawing whose anchors still coincide (nothing to keep)
+    pub fn is_empty(&self) -> bool {
+        !self.tool.is_single_point() && self.start == self.end
+    }
+
+    /// Fibonacci levels as `(ratio, price)`: 0 at the end of the swing, 1 back
+    /// at its start
+    pub fn fib_levels(&self) -> impl Iterator<Item = (f64, f64)> + '_ {
+        FIB_LEVELS
+            .iter()
+            .map(|&ratio| (ratio, self.end.price + (self.start.price - self.end.price) * ratio))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fib_levels_retrace_the_swing() {
+        let swing = Drawing::new(1, DrawingTool::Fibonacci, Anchor::new(0, 100.0), Anchor::new(60_000, 200.0));
+        let levels: Vec<(f64, f64)> = swing.fib_levels().collect();
+
+        assert_eq!(levels.first(), Some(&(0.0, 200.0)));
+        assert_eq!(levels.last(), Some(&(1.0, 100.0)));
+        let (_, golden) = levels[4];
+        assert!((golden - 138.2).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_empty_drawings() {
+        let point = Anchor::new(0, 100.0);
+        assert!(Drawing::new(0, DrawingTool::Trendline, point, point).is_empty());
+        assert!(!Drawing::new(0, DrawingTool::HorizontalRay, point, point).is_empty());
+    }
+}
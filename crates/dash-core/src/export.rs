@@ -0,0 +1,267 @@
+//! CSV/JSON export of trades, order book snapshots and chart series
+
+use crate::{Candle, MarketDepth, OrderBookSnapshot, Symbol, Trade};
+use serde::Serialize;
+
+/// File format for exports
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExportFormat {
+    Csv,
+    Json,
+}
+
+impl ExportFormat {
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Csv => "CSV",
+            Self::Json => "JSON",
+        }
+    }
+
+    pub fn extension(&self) -> &'static str {
+        match self {
+            Self::Csv => "csv",
+            Self::Json => "json",
+        }
+    }
+
+    pub fn mime_type(&self) -> &'static str {
+        match self {
+            Self::Csv => "text/csv;charset=utf-8",
+            Self::Json => "application/json",
+        }
+    }
+
+    pub fn all() -> &'static [Self] {
+        &[Self::Csv, Self::Json]
+    }
+}
+
+/// Quote a CSV field if it contains separators, quotes or line breaks
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+fn to_json<T: Serialize + ?Sized>(value: &T) -> String {
+    serde_json::to_string_pretty(value).unwrap_or_default()
+}
+
+/// Trades in tape order
+pub fn export_trades(trades: &[Trade], format: ExportFormat) -> String {
+    match format {
+        ExportFormat::Json => to_json(trades),
+        ExportFormat::Csv => {
+            let mut out = String::from("id,timestamp,symbol,side,price,quantity,value\n");
+            for trade in trades {
+                out.push_str(&format!(
+                    "{},{},{},{},{},{},{}\n",
+                    csv_field(&trade.id),
+                    trade.timestamp.to_rfc3339(),
+                    csv_field(trade.symbol.as_str()),
+                    trade.side.label(),
+                    trade.price.as_f64(),
+                    trade.quantity.as_f64(),
+                    trade.value()
+                ));
+            }
+            out
+        }
+    }
+}
+
+/// Book snapshot; CSV rows list asks (best first) then bids (best first)
+pub fn export_order_book(book: &OrderBookSnapshot, format: ExportFormat) -> String {
+    match format {
+        ExportFormat::Json => to_json(book),
+        ExportFormat::Csv => {
+            let mut out = String::from("side,price,quantity,orders\n");
+            let levels = book
+                .asks
+                .iter()
+                .map(|l| ("ask", l))
+                .chain(book.bids.iter().map(|l| ("bid", l)));
+            for (side, level) in levels {
+                out.push_str(&format!(
+                    "{},{},{},{}\n",
+                    side,
+                    level.price.as_f64(),
+                    level.quantity.as_f64(),
+                    level.order_count
+                ));
+            }
+            out
+        }
+    }
+}
+
+fn rfc3339(timestamp_ms: i64) -> String {
+    chrono::DateTime::from_timestamp_millis(timestamp_ms)
+        .map(|t| t.to_rfc3339())
+        .unwrap_or_default()
+}
+
+/// Chart candles, oldest first
+pub fn export_candles(candles: &[Candle], format: ExportFormat) -> String {
+    match format {
+        ExportFormat::Json => to_json(candles),
+        ExportFormat::Csv => {
+            let mut out = String::from("timestamp,open,high,low,close,volume,trades\n");
+            for candle in candles {
+                out.push_str(&format!(
+                    "{},{},{},{},{},{},{}\n",
+                    rfc3339(candle.timestamp),
+                    candle.open.as_f64(),
+                    candle.high.as_f64(),
+                    candle.low.as_f64(),
+                    candle.close.as_f64(),
+                    candle.volume.as_f64(),
+                    candle.trade_count
+                ));
+            }
+            out
+        }
+    }
+}
+
+/// Depth curve points; CSV rows list bids (best first) then asks (best first)
+pub fn export_depth(depth: &MarketDepth, format: ExportFormat) -> String {
+    match format {
+        ExportFormat::Json => to_json(depth),
+        ExportFormat::Csv => {
+            let mut out = String::from("side,price,cumulative_quantity,cumulative_value\n");
+            let points = depth
+                .bid_depth
+                .iter()
+                .map(|p| ("bid", p))
+                .chain(depth.ask_depth.iter().map(|p| ("ask", p)));
+            for (side, point) in points {
+                out.push_str(&format!(
+                    "{},{},{},{}\n",
+                    side, point.price, point.cumulative_quantity, point.cumulative_value
+                ));
+            }
+            out
+        }
+    }
+}
+
+/// One bar of a cumulative volume delta export
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct CvdRow {
+    /// Candle open time (ms)
+    pub timestamp: i64,
+    /// Buy − sell volume inside the candle
+    pub delta: f64,
+    /// Running total from the first exported candle
+    pub cvd: f64,
+}
+
+/// Per-candle delta (`deltas` index-aligned with `candles`, `None` where
+/// nothing was recorded) with its running total
+pub fn export_cvd(candles: &[Candle], deltas: &[Option<f64>], format: ExportFormat) -> String {
+    let rows: Vec<CvdRow> = candles
+        .iter()
+        .zip(deltas)
+        .filter_map(|(candle, delta)| Some((candle.timestamp, (*delta)?)))
+        .scan(0.0, |cvd, (timestamp, delta)| {
+            *cvd += delta;
+            Some(CvdRow { timestamp, delta, cvd: *cvd })
+        })
+        .collect();
+    match format {
+        ExportFormat::Json => to_json(&rows),
+        ExportFormat::Csv => {
+            let mut out = String::from("timestamp,delta,cvd\n");
+            for row in &rows {
+                out.push_str(&format!("{},{},{}\n", rfc3339(row.timestamp), row.delta, row.cvd));
+            }
+            out
+        }
+    }
+}
+
+fn file_stamp(timestamp_ms: i64) -> String {
+    chrono::DateTime::from_timestamp_millis(timestamp_ms)
+        .map(|t| t.format("%Y%m%d-%H%M%S").to_string())
+        .unwrap_or_default()
+}
+
+/// Download name such as `trades-BTC-USD-20240101-120000.csv`
+pub fn export_filename(kind: &str, symbol: &Symbol, timestamp_ms: i64, format: ExportFormat) -> String {
+    format!("{}-{}-{}.{}", kind, symbol, file_stamp(timestamp_ms), format.extension())
+}
+
+/// PNG name for a panel snapshot, e.g. `chart-BTC-USD-20240101-120000.png`
+pub fn snapshot_filename(kind: &str, symbol: &Symbol, timestamp_ms: i64) -> String {
+    format!("{}-{}-{}.png", kind, symbol, file_stamp(timestamp_ms))
+}
+
+/// Watermark stamped on panel snapshots: `BTC-USD · 2024-01-01 12:00:00 UTC`
+pub fn snapshot_watermark(symbol: &Symbol, timestamp_ms: i64) -> String {
+    let time = chrono::DateTime::from_timestamp_millis(timestamp_ms)
+        .map(|t| t.format("%Y-%m-%d %H:%M:%S UTC").to_string())
+        .unwrap_or_default();
+    format!("{} · {}", symbol, time)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{CandleInterval, OrderBookLevel, TradeSide};
+
+    #[test]
+    fn test_trades_csv() {
+        let mut trade = Trade::new(Symbol::new("BTC-USD"), 100.0, 0.5, TradeSide::Buy);
+        trade.id = "a,\"b\"".to_string();
+
+        let csv = export_trades(&[trade], ExportFormat::Csv);
+        let row = csv.lines().nth(1).unwrap();
+        assert!(row.starts_with("\"a,\"\"b\"\"\","));
+        assert!(row.ends_with(",BTC-USD,BUY,100,0.5,50"));
+    }
+
+    #[test]
+    fn test_chart_series_csv() {
+        let symbol = Symbol::new("BTC-USD");
+        let mut candle = Candle::new(symbol.clone(), CandleInterval::M1, 0, 100.0);
+        candle.update(101.0, 2.0);
+        let next = Candle::new(symbol, CandleInterval::M1, 60_000, 101.0);
+
+        let csv = export_candles(std::slice::from_ref(&candle), ExportFormat::Csv);
+        assert_eq!(csv.lines().nth(1), Some("1970-01-01T00:00:00+00:00,100,101,100,101,2,1"));
+
+        let csv = export_cvd(&[candle, next], &[Some(2.0), Some(-0.5)], ExportFormat::Csv);
+        assert_eq!(csv.lines().last(), Some("1970-01-01T00:01:00+00:00,-0.5,1.5"));
+
+        let mut book = OrderBookSnapshot::new(Symbol::new("BTC-USD"));
+        book.bids.push(OrderBookLevel::new(99.0, 2.0, 3));
+        let csv = export_depth(&MarketDepth::from_orderbook(&book), ExportFormat::Csv);
+        assert_eq!(csv.lines().nth(1), Some("bid,99,2,198"));
+    }
+
+    #[test]
+    fn test_order_book_export() {
+        let mut book = OrderBookSnapshot::new(Symbol::new("BTC-USD"));
+        book.bids.push(OrderBookLevel::new(99.0, 2.0, 3));
+        book.asks.push(OrderBookLevel::new(101.0, 1.0, 1));
+
+        let csv = export_order_book(&book, ExportFormat::Csv);
+        assert_eq!(csv, "side,price,quantity,orders\nask,101,1,1\nbid,99,2,3\n");
+
+        let json = export_order_book(&book, ExportFormat::Json);
+        let parsed: OrderBookSnapshot = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed.bids.len(), 1);
+
+        assert_eq!(snapshot_filename("chart", &book.symbol, 0), "chart-BTC-USD-19700101-000000.png");
+        assert_eq!(snapshot_watermark(&book.symbol, 0), "BTC-USD · 1970-01-01 00:00:00 UTC");
+        assert_eq!(
+            export_filename("book", &book.symbol, 0, ExportFormat::Json),
+            "book-BTC-USD-19700101-000000.json"
+        );
+    }
+}
@@ -0,0 +1,170 @@
+//! Iceberg heuristic: book levels that keep refilling after being traded
+//!
+//! Trades are summed per resting level between book updates. When the next
+//! book still shows clearly more at that level than was displayed minus what
+//! traded there, the size was topped back up: a refill. A level that refills
+//! [`ICEBERG_MIN_REFILLS`] times within [`ICEBERG_WINDOW_MS`] is flagged as
+//! a possible iceberg. It's a guess from public data, not an exchange flag.
+
+use crate::{OrderBookLevel, OrderBookSnapshot, Trade, TradeSide};
+use std::collections::HashMap;
+
+/// Refills before a level is flagged
+pub const ICEBERG_MIN_REFILLS: u32 = 3;
+/// Share of the traded size the level must regain to count as a refill
+pub const ICEBERG_REFILL_RATIO: f64 = 0.5;
+/// Refills older than this are forgotten
+pub const ICEBERG_WINDOW_MS: i64 = 60_000;
+
+/// A level's side and price, as a map key
+type LevelKey = (TradeSide, u64);
+
+fn key(side: TradeSide, price: f64) -> LevelKey {
+    (side, price.to_bits())
+}
+
+/// A level that has refilled after trading
+#[derive(Debug, Clone, PartialEq)]
+pub struct IcebergLevel {
+    /// Bid (`Buy`) or ask (`Sell`) side of the book
+    pub side: TradeSide,
+    pub price: f64,
+    /// Refills within the window
+    pub refills: u32,
+    /// Size traded at the level across those refills
+    pub traded: f64,
+    /// Time of the latest refill (ms)
+    pub last_refill: i64,
+}
+
+impl IcebergLevel {
+    /// Flagged as a possible iceberg
+    pub fn is_flagged(&self) -> bool {
+        self.refills >= ICEBERG_MIN_REFILLS
+    }
+
+    /// Tooltip text: "Possible iceberg: refilled 4× (12.5000 traded)"
+    pub fn describe(&self) -> String {
+        format!("Possible iceberg: refilled {}× ({:.4} traded)", self.refills, self.traded)
+    }
+}
+
+/// Running refill counts for one symbol's book
+#[derive(Debug, Clone, Default)]
+pub struct IcebergDetector {
+    /// Size shown per level in the last book
+    displayed: HashMap<LevelKey, f64>,
+    /// Size traded per level since the last book
+    traded: HashMap<LevelKey, f64>,
+    levels: HashMap<LevelKey, IcebergLevel>,
+}
+
+impl IcebergDetector {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Count a trade against the level it executed on: buys lift asks,
+    /// sells hit bids
+    pub fn on_trade(&mut self, trade: &Trade) {
+        let side = trade.side.opposite();
+        *self.traded.entry(key(side, trade.price.as_f64())).or_default() += trade.quantity.as_f64();
+    }
+
+    /// Compare a new book with the last one for refilled levels
+    pub fn on_book(&mut self, book: &OrderBookSnapshot) {
+        let now = book.timestamp;
+        let traded = std::mem::take(&mut self.traded);
+        for (side, levels) in [(TradeSide::Buy, &book.bids), (TradeSide::Sell, &book.asks)] {
+            for level in levels {
+                self.check_refill(side, level, &traded, now);
+            }
+        }
+
+        self.displayed = book
+            .bids
+            .iter()
+            .map(|l| (key(TradeSide::Buy, l.price.as_f64()), l.quantity.as_f64()))
+            .chain(book.asks.iter().map(|l| (key(TradeSide::Sell, l.price.as_f64()), l.quantity.as_f64())))
+            .collect();
+        self.levels.retain(|_, level| now - level.last_refill <= ICEBERG_WINDOW_MS);
+    }
+
+    fn check_refill(&mut self, side: TradeSide, level: &OrderBookLevel, traded: &HashMap<LevelKey, f64>, now: i64) {
+        let price = level.price.as_f64();
+        let key = key(side, price);
+        let (Some(&sold), Some(&shown)) = (traded.get(&key), self.displayed.get(&key)) else {
+            return;
+        };
+        let left = (shown - sold).max(0.0);
+        if sold <= 0.0 || level.quantity.as_f64() < left + sold * ICEBERG_REFILL_RATIO {
+            return;
+        }
+        let entry = self.levels.entry(key).or_insert(IcebergLevel {
+            side,
+            price,
+            refills: 0,
+            traded: 0.0,
+            last_refill: now,
+        });
+        entry.refills += 1;
+        entry.traded += sold;
+        entry.last_refill = now;
+    }
+
+    /// Flagged level at `price` on `side`, if any
+    pub fn flagged_at(&self, side: TradeSide, price: f64) -> Option<&IcebergLevel> {
+        self.levels.get(&key(side, price)).filter(|level| level.is_flagged())
+    }
+
+    /// Every flagged level, highest price first
+    pub fn flagged(&self) -> Vec<IcebergLevel> {
+        let mut flagged: Vec<IcebergLevel> = self.levels.values().filter(|l| l.is_flagged()).cloned().collect();
+        flagged.sort_by(|a, b| b.price.total_cmp(&a.price));
+        flagged
+    }
+
+    pub fn clear(&mut self) {
+        *self = Self::default();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Symbol;
+
+    fn book(timestamp: i64, bid: (f64, f64), ask: (f64, f64)) -> OrderBookSnapshot {
+        let mut book = OrderBookSnapshot::new(Symbol::new("BTC-USD"));
+        book.timestamp = timestamp;
+        book.bids = vec![OrderBookLevel::new(bid.0, bid.1, 1)];
+        book.asks = vec![OrderBookLevel::new(ask.0, ask.1, 1)];
+        book
+    }
+
+    #[test]
+    fn test_refilling_level_is_flagged() {
+        let btc = Symbol::new("BTC-USD");
+        let mut detector = IcebergDetector::new();
+        detector.on_book(&book(0, (99.0, 5.0), (101.0, 2.0)));
+
+        // Sellers take 4 off the 5-lot bid three times; it shows 5 again each time
+        for i in 1..=3 {
+            detector.on_trade(&Trade::new(btc.clone(), 99.0, 4.0, TradeSide::Sell));
+            assert!(detector.flagged_at(TradeSide::Buy, 99.0).is_none());
+            detector.on_book(&book(i * 1_000, (99.0, 5.0), (101.0, 2.0)));
+        }
+        let flagged = detector.flagged_at(TradeSide::Buy, 99.0).unwrap();
+        assert_eq!((flagged.refills, flagged.traded), (3, 12.0));
+
+        // The ask only shrinks as it trades: no refill
+        detector.on_trade(&Trade::new(btc, 101.0, 1.0, TradeSide::Buy));
+        detector.on_book(&book(4_000, (99.0, 5.0), (101.0, 1.0)));
+        assert!(detector.flagged_at(TradeSide::Sell, 101.0).is_none());
+        assert_eq!(detector.flagged().len(), 1);
+
+        // Forgotten once the refills age out
+        detector.on_book(&book(4_000 + ICEBERG_WINDOW_MS, (99.0, 5.0), (101.0, 1.0)));
+        assert!(detector.flagged().is_empty());
+    }
+}
@@ -0,0 +1,293 @@
+//! Price overlays: moving averages and Bollinger Bands from candle closes,
+//! and the session VWAP with its standard deviation bands
+
+use crate::{Candle, SessionStats, Trade};
+use serde::{Deserialize, Serialize};
+
+/// Longest period accepted for an overlay
+pub const MAX_INDICATOR_PERIOD: usize = 500;
+
+/// Overlay study and its parameters
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub enum Indicator {
+    /// Simple moving average over `period` closes
+    Sma(usize),
+    /// Exponential moving average, seeded with the SMA of its first `period` closes
+    Ema(usize),
+    /// SMA middle line with bands `std_dev` standard deviations either side
+    Bollinger { period: usize, std_dev: f64 },
+}
+
+impl Indicator {
+    pub fn period(&self) -> usize {
+        match *self {
+            Self::Sma(period) | Self::Ema(period) | Self::Bollinger { period, .. } => period,
+        }
+    }
+
+    /// Same study with another period (clamped to 1..=[`MAX_INDICATOR_PERIOD`])
+    pub fn with_period(self, period: usize) -> Self {
+        let period = period.clamp(1, MAX_INDICATOR_PERIOD);
+        match self {
+            Self::Sma(_) => Self::Sma(period),
+            Self::Ema(_) => Self::Ema(period),
+            Self::Bollinger { std_dev, .. } => Self::Bollinger { period, std_dev },
+        }
+    }
+
+    /// Short study name
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Sma(_) => "SMA",
+            Self::Ema(_) => "EMA",
+            Self::Bollinger { .. } => "BB",
+        }
+    }
+
+    /// Legend label, e.g. "EMA 50" or "BB 20 2"
+    pub fn label(&self) -> String {
+        match *self {
+            Self::Bollinger { period, std_dev } => format!("BB {} {}", period, std_dev),
+            _ => format!("{} {}", self.name(), self.period()),
+        }
+    }
+
+    /// Series for every close (`None` until the period has filled)
+    pub fn compute(&self, closes: &[f64]) -> Vec<Option<IndicatorPoint>> {
+        let mut points = Vec::with_capacity(closes.len());
+        self.update(closes, &mut points, 0);
+        points
+    }
+
+    /// Bring `points` up to date with `closes`, keeping the points before
+    /// `from` (the first close that changed) and recomputing the rest
+    pub fn update(&self, closes: &[f64], points: &mut Vec<Option<IndicatorPoint>>, from: usize) {
+        points.truncate(from.min(closes.len()));
+        let period = self.period().max(1);
+        for i in points.len()..closes.len() {
+            let point = if i + 1 < period {
+                None
+            } else {
+                let window = &closes[i + 1 - period..=i];
+                Some(match *self {
+                    Self::Sma(_) => IndicatorPoint::line(mean(window)),
+                    Self::Ema(_) => {
+                        let alpha = 2.0 / (period as f64 + 1.0);
+                        let value = match points.last().copied().flatten() {
+                            Some(prev) => prev.value + alpha * (closes[i] - prev.value),
+                            None => mean(window),
+                        };
+                        IndicatorPoint::line(value)
+                    }
+                    Self::Bollinger { std_dev, .. } => {
+                        let middle = mean(window);
+                        let variance = window.iter().map(|c| (c - middle).powi(2)).sum::<f64>() / period as f64;
+                        let offset = std_dev * variance.sqrt();
+                        IndicatorPoint { value: middle, band: Some((middle + offset, middle - offset)) }
+                    }
+                })
+            };
+            points.push(point);
+        }
+    }
+}
+
+fn mean(values: &[f64]) -> f64 {
+    values.iter().sum::<f64>() / values.len() as f64
+}
+
+/// One computed value: the line, plus (upper, lower) for banded studies
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct IndicatorPoint {
+    pub value: f64,
+    pub band: Option<(f64, f64)>,
+}
+
+impl IndicatorPoint {
+    fn line(value: f64) -> Self {
+        Self { value, band: None }
+    }
+}
+
+/// An overlay the user has added to the chart
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct ChartOverlay {
+    pub indicator: Indicator,
+    /// Stroke color (`#rrggbb`)
+    pub color: String,
+}
+
+impl ChartOverlay {
+    /// Studies offered in the overlay menu, with their starting colors
+    pub fn presets() -> [Self; 3] {
+        [
+            Self { indicator: Indicator::Sma(20), color: "#f0b90b".to_string() },
+            Self { indicator: Indicator::Ema(50), color: "#2196f3".to_string() },
+            Self { indicator: Indicator::Bollinger { period: 20, std_dev: 2.0 }, color: "#ab47bc".to_string() },
+        ]
+    }
+}
+
+// ============================================================================
+// VWAP
+// ============================================================================
+
+/// Session VWAP and the volume-weighted standard deviation of price around it
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct VwapPoint {
+    pub vwap: f64,
+    pub std_dev: f64,
+}
+
+impl VwapPoint {
+    /// Price `k` standard deviations from the VWAP (negative `k` for below)
+    pub fn band(&self, k: f64) -> f64 {
+        self.vwap + k * self.std_dev
+    }
+}
+
+impl From<&SessionStats> for VwapPoint {
+    fn from(session: &SessionStats) -> Self {
+        Self { vwap: session.vwap, std_dev: session.vwap_std_dev() }
+    }
+}
+
+/// Running VWAP and variance over prints fed oldest first
+#[derive(Debug, Clone, Copy, Default)]
+struct VwapAccumulator {
+    volume: f64,
+    vwap: f64,
+    variance: f64,
+}
+
+impl VwapAccumulator {
+    fn add(&mut self, price: f64, quantity: f64) {
+        let volume = self.volume + quantity;
+        if volume <= 0.0 {
+            return;
+        }
+        let vwap = self.vwap + quantity / volume * (price - self.vwap);
+        self.variance = (self.variance * self.volume + quantity * (price - self.vwap) * (price - vwap)) / volume;
+        self.vwap = vwap;
+        self.volume = volume;
+    }
+
+    fn point(&self) -> Option<VwapPoint> {
+        (self.volume > 0.0).then(|| VwapPoint { vwap: self.vwap, std_dev: self.variance.max(0.0).sqrt() })
+    }
+}
+
+/// Session-anchored VWAP at the close of each candle
+///
+/// `trail` holds server snapshots (time ms, point) in ascending order; a
+/// candle with a snapshot inside it takes the latest one. The rest fall back
+/// to folding `trades` (newest first, as buffered) from `session_start`.
+/// Candles before the session, or before the first buffered trade, get `None`.
+pub fn vwap_series(
+    candles: &[Candle],
+    trades: &[Trade],
+    session_start: i64,
+    trail: &[(i64, VwapPoint)],
+) -> Vec<Option<VwapPoint>> {
+    let mut local = VwapAccumulator::default();
+    let mut prints = trades
+        .iter()
+        .rev()
+        .filter(|t| t.timestamp.timestamp_millis() >= session_start)
+        .peekable();
+    let mut snapshots = trail.iter().filter(|(ts, _)| *ts >= session_start).peekable();
+
+    candles
+        .iter()
+        .map(|candle| {
+            let end = candle.timestamp + candle.interval.as_millis();
+            while let Some(trade) = prints.next_if(|t| t.timestamp.timestamp_millis() < end) {
+                local.add(trade.price.as_f64(), trade.quantity.as_f64());
+            }
+            let mut server = None;
+            while let Some(&(ts, point)) = snapshots.next_if(|(ts, _)| *ts < end) {
+                if ts >= candle.timestamp {
+                    server = Some(point);
+                }
+            }
+            if end <= session_start {
+                return None;
+            }
+            server.or_else(|| local.point())
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn values(points: &[Option<IndicatorPoint>]) -> Vec<Option<f64>> {
+        points.iter().map(|p| p.map(|p| p.value)).collect()
+    }
+
+    #[test]
+    fn test_moving_averages() {
+        let closes = [1.0, 2.0, 3.0, 4.0, 5.0];
+        assert_eq!(
+            values(&Indicator::Sma(3).compute(&closes)),
+            vec![None, None, Some(2.0), Some(3.0), Some(4.0)]
+        );
+        // Seeded with SMA(3) = 2, then alpha = 0.5
+        assert_eq!(
+            values(&Indicator::Ema(3).compute(&closes)),
+            vec![None, None, Some(2.0), Some(3.0), Some(4.0)]
+        );
+
+        let bands = Indicator::Bollinger { period: 2, std_dev: 2.0 }.compute(&[1.0, 3.0]);
+        assert_eq!(bands[1], Some(IndicatorPoint { value: 2.0, band: Some((4.0, 0.0)) }));
+    }
+
+    #[test]
+    fn test_vwap_series_prefers_server_snapshots() {
+        use crate::{CandleInterval, Symbol, TradeSide};
+        use chrono::TimeZone;
+
+        let symbol = Symbol::new("BTC-USD");
+        let candles: Vec<Candle> = (0..3)
+            .map(|i| Candle::new(symbol.clone(), CandleInterval::M1, i * 60_000, 100.0))
+            .collect();
+        let trade = |ms: i64, price: f64, qty: f64| Trade {
+            timestamp: chrono::Utc.timestamp_millis_opt(ms).unwrap(),
+            ..Trade::new(symbol.clone(), price, qty, TradeSide::Buy)
+        };
+        // Newest first, like the trade buffer
+        let trades = [trade(130_000, 110.0, 1.0), trade(70_000, 90.0, 2.0), trade(10_000, 100.0, 1.0)];
+        let server = VwapPoint { vwap: 99.0, std_dev: 3.0 };
+
+        let series = vwap_series(&candles, &trades, 0, &[(60_000, server)]);
+
+        assert_eq!(series[0], Some(VwapPoint { vwap: 100.0, std_dev: 0.0 }));
+        assert_eq!(series[1], Some(server));
+        // Back to the trade buffer: 100×1, 90×2, 110×1
+        let last = series[2].unwrap();
+        assert!((last.vwap - 97.5).abs() < 1e-9);
+        assert!((last.std_dev - 68.75f64.sqrt()).abs() < 1e-9);
+        assert_eq!(last.band(-2.0), last.vwap - 2.0 * last.std_dev);
+
+        // Nothing before the session anchor
+        assert_eq!(vwap_series(&candles, &trades, 120_000, &[])[..2], [None, None]);
+    }
+
+    #[test]
+    fn test_incremental_update_matches_full() {
+        let mut closes = vec![10.0, 11.0, 12.5, 12.0, 13.0, 12.8];
+        let ema = Indicator::Ema(3);
+        let mut points = ema.compute(&closes);
+
+        // Forming candle ticks, then a new one opens
+        *closes.last_mut().unwrap() = 14.0;
+        ema.update(&closes, &mut points, closes.len() - 1);
+        closes.push(13.5);
+        ema.update(&closes, &mut points, closes.len() - 1);
+
+        assert_eq!(points, ema.compute(&closes));
+    }
+}
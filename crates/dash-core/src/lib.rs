@@ -3,13 +3,27 @@
 //! Core domain types for the BTC Exchange Dashboard.
 //! Implements Strategy pattern for formatting and validation.
 
+pub mod book_history;
 pub mod candle;
+pub mod drawing;
+pub mod export;
+pub mod iceberg;
+pub mod indicators;
 pub mod order;
+pub mod portfolio;
+pub mod profile;
 pub mod ticker;
 pub mod trade;
 
+pub use book_history::*;
 pub use candle::*;
+pub use drawing::*;
+pub use export::*;
+pub use iceberg::*;
+pub use indicators::*;
 pub use order::*;
+pub use portfolio::*;
+pub use profile::*;
 pub use ticker::*;
 pub use trade::*;
 
@@ -105,12 +119,55 @@ impl QuantityFormatter for CryptoQuantityFormatter {
     }
 }
 
+/// Price and quantity precision from a symbol's tick and lot sizes, so a
+/// 0.0001-tick pair isn't cut to cents and a 0.5-tick one isn't padded
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TickFormat {
+    pub price_decimals: usize,
+    pub quantity_decimals: usize,
+}
+
+impl TickFormat {
+    pub fn price(&self, price: f64) -> String {
+        format!("{:.*}", self.price_decimals, price)
+    }
+
+    pub fn quantity(&self, qty: f64) -> String {
+        format!("{:.*}", self.quantity_decimals, qty)
+    }
+
+    /// Price as a whole number of its last shown decimal, a stable integer
+    /// key for a book level
+    pub fn ticks(&self, price: f64) -> i64 {
+        (price * 10f64.powi(self.price_decimals as i32)).round() as i64
+    }
+}
+
+impl Default for TickFormat {
+    fn default() -> Self {
+        SymbolInfo::default().tick_format()
+    }
+}
+
+impl PriceFormatter for TickFormat {
+    fn format(&self, price: f64) -> String {
+        self.price(price)
+    }
+}
+
+impl QuantityFormatter for TickFormat {
+    fn format(&self, qty: f64) -> String {
+        self.quantity(qty)
+    }
+}
+
 // ============================================================================
 // CORE VALUE TYPES
 // ============================================================================
 
 /// Trading pair identifier (e.g., "BTC-USD", "ETH-BTC")
-#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct Symbol(pub String);
 
 impl Symbol {
@@ -151,8 +208,116 @@ impl From<&str> for Symbol {
     }
 }
 
+/// Trading rules and latest 24h stats for a symbol (`GET /api/symbols`)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct SymbolInfo {
+    pub symbol: Symbol,
+    /// Minimum price increment
+    pub tick_size: f64,
+    /// Minimum quantity increment
+    pub lot_size: f64,
+    /// Smallest accepted order quantity
+    pub min_quantity: f64,
+    pub last_price: f64,
+    pub change_percent_24h: f64,
+}
+
+impl SymbolInfo {
+    /// Entry with no price yet; minimum quantity is one lot
+    pub fn new(symbol: Symbol, tick_size: f64, lot_size: f64) -> Self {
+        Self {
+            symbol,
+            tick_size,
+            lot_size,
+            min_quantity: lot_size,
+            last_price: 0.0,
+            change_percent_24h: 0.0,
+        }
+    }
+
+    pub fn is_up(&self) -> bool {
+        self.change_percent_24h >= 0.0
+    }
+
+    /// Refresh the 24h stats from a ticker
+    pub fn update_from(&mut self, ticker: &Ticker) {
+        self.last_price = ticker.last_price.as_f64();
+        self.change_percent_24h = ticker.change_percent_24h;
+    }
+
+    /// Is `price` a whole number of ticks?
+    pub fn is_valid_price(&self, price: f64) -> bool {
+        is_step_multiple(price, self.tick_size)
+    }
+
+    /// `price` rounded to the nearest tick
+    pub fn round_price(&self, price: f64) -> f64 {
+        if self.tick_size <= 0.0 {
+            return price;
+        }
+        (price / self.tick_size).round() * self.tick_size
+    }
+
+    /// Is `quantity` a whole number of lots?
+    pub fn is_valid_quantity(&self, quantity: f64) -> bool {
+        is_step_multiple(quantity, self.lot_size)
+    }
+
+    /// Decimals needed to show one tick
+    pub fn price_decimals(&self) -> usize {
+        step_decimals(self.tick_size)
+    }
+
+    /// Decimals needed to show one lot
+    pub fn quantity_decimals(&self) -> usize {
+        step_decimals(self.lot_size)
+    }
+
+    /// Formatter at this symbol's tick and lot precision
+    pub fn tick_format(&self) -> TickFormat {
+        TickFormat {
+            price_decimals: self.price_decimals(),
+            quantity_decimals: self.quantity_decimals(),
+        }
+    }
+}
+
+impl Default for SymbolInfo {
+    fn default() -> Self {
+        Self::new(Symbol::default(), 0.01, 0.0001)
+    }
+}
+
+/// Is `value` an integer multiple of `step` (within float tolerance)?
+fn is_step_multiple(value: f64, step: f64) -> bool {
+    if step <= 0.0 {
+        return true;
+    }
+    let steps = value / step;
+    (steps - steps.round()).abs() < 1e-6
+}
+
+/// Most decimals a tick or lot step is shown with
+const MAX_STEP_DECIMALS: i32 = 12;
+
+/// Fewest decimals that show every multiple of `step` exactly (0.25 → 2,
+/// 0.0025 → 4), rather than the power of ten nearest to it
+fn step_decimals(step: f64) -> usize {
+    if step <= 0.0 || !step.is_finite() {
+        return 0;
+    }
+    (0..MAX_STEP_DECIMALS)
+        .find(|&d| {
+            let scaled = step * 10f64.powi(d);
+            (scaled - scaled.round()).abs() < 1e-9 * scaled.max(1.0)
+        })
+        .unwrap_or(MAX_STEP_DECIMALS) as usize
+}
+
 /// Decimal price representation
 #[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct Price(pub f64);
 
 impl Price {
@@ -197,6 +362,7 @@ impl std::ops::Sub for Price {
 
 /// Quantity representation
 #[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct Quantity(pub f64);
 
 impl Quantity {
@@ -236,8 +402,24 @@ impl std::ops::Add for Quantity {
 // WEBSOCKET MESSAGE ENVELOPE
 // ============================================================================
 
+/// Version of the WebSocket protocol; bumped on breaking wire changes so a
+/// stale frontend can tell it's talking to a newer server
+pub const PROTOCOL_VERSION: u32 = 1;
+
+/// JSON Schema for both directions of the WebSocket protocol, as served at
+/// `/api/schema`
+#[cfg(feature = "schema")]
+pub fn protocol_schema() -> serde_json::Value {
+    serde_json::json!({
+        "protocol_version": PROTOCOL_VERSION,
+        "server_messages": schemars::schema_for!(WsMessage),
+        "client_commands": schemars::schema_for!(ClientCommand),
+    })
+}
+
 /// WebSocket message envelope with discriminated union
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 #[serde(tag = "type", content = "data")]
 pub enum WsMessage {
     #[serde(rename = "trade")]
@@ -246,12 +428,346 @@ pub enum WsMessage {
     OrderBook(OrderBookSnapshot),
     #[serde(rename = "ticker")]
     Ticker(Ticker),
+    /// Top of book, sent whenever the touch moves
+    #[serde(rename = "bbo")]
+    Bbo(Bbo),
     #[serde(rename = "candle")]
     Candle(Candle),
     #[serde(rename = "depth")]
     Depth(MarketDepth),
+    /// Funding, open interest and mark/index for perpetual markets
+    #[serde(rename = "derivatives")]
+    Derivatives(DerivativesTicker),
+    /// Session analytics (open, extremes, VWAP)
+    #[serde(rename = "session")]
+    Session(SessionStats),
+    /// VPIN-style flow toxicity (analytics channel)
+    #[serde(rename = "toxicity")]
+    Toxicity(FlowToxicity),
+    #[serde(rename = "liquidation")]
+    Liquidation(Liquidation),
     #[serde(rename = "heartbeat")]
     Heartbeat { timestamp: i64 },
+    /// Server checking an idle connection; answer with a `pong` command
+    /// echoing the nonce or be dropped
+    #[serde(rename = "ping")]
+    Ping { nonce: u64, timestamp: i64 },
+    /// First message on a connection; the id tags the server's logs for it
+    #[serde(rename = "hello")]
+    Hello { connection_id: String },
+    /// Source of the market data, sent on connect and on every failover
+    #[serde(rename = "feed_status")]
+    FeedStatus(FeedStatus),
+    /// Health of one upstream source, sent every second by the supervisor
+    #[serde(rename = "source_status")]
+    SourceStatus(SourceStatus),
+    /// Outcome of cross-checking a symbol's recent bars against its trades
+    #[serde(rename = "candle_check")]
+    CandleCheck(CandleCheckReport),
+    #[serde(rename = "portfolio")]
+    Portfolio(PortfolioSnapshot),
+    #[serde(rename = "order_update")]
+    OrderUpdate(PaperOrder),
+    /// Order refused by the paper-trading engine
+    #[serde(rename = "order_rejected")]
+    OrderRejected {
+        client_id: String,
+        reason: OrderRejection,
+    },
+    #[serde(rename = "position")]
+    Position(Position),
+    #[serde(rename = "balance")]
+    Balance(Balance),
+    #[serde(rename = "equity")]
+    Equity(EquitySample),
+    #[serde(rename = "account")]
+    Account(AccountSummary),
+    /// Answer to `request_snapshot`
+    #[serde(rename = "snapshot")]
+    Snapshot(MarketSnapshot),
+    /// Answer to `request_candle_history`: 1m candles, oldest first
+    #[serde(rename = "candle_history")]
+    CandleHistory {
+        symbol: Symbol,
+        before: Option<i64>,
+        limit: usize,
+        candles: Vec<Candle>,
+    },
+    /// Answer to `list_symbols`
+    #[serde(rename = "symbols")]
+    Symbols(Vec<SymbolInfo>),
+}
+
+impl WsMessage {
+    /// Wire tag (matches the serde `type` field)
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Self::Trade(_) => "trade",
+            Self::OrderBook(_) => "orderbook",
+            Self::Ticker(_) => "ticker",
+            Self::Bbo(_) => "bbo",
+            Self::Candle(_) => "candle",
+            Self::Depth(_) => "depth",
+            Self::Derivatives(_) => "derivatives",
+            Self::Session(_) => "session",
+            Self::Toxicity(_) => "toxicity",
+            Self::Liquidation(_) => "liquidation",
+            Self::Heartbeat { .. } => "heartbeat",
+            Self::Ping { .. } => "ping",
+            Self::Hello { .. } => "hello",
+            Self::FeedStatus(_) => "feed_status",
+            Self::SourceStatus(_) => "source_status",
+            Self::CandleCheck(_) => "candle_check",
+            Self::Portfolio(_) => "portfolio",
+            Self::OrderUpdate(_) => "order_update",
+            Self::OrderRejected { .. } => "order_rejected",
+            Self::Position(_) => "position",
+            Self::Balance(_) => "balance",
+            Self::Equity(_) => "equity",
+            Self::Account(_) => "account",
+            Self::Snapshot(_) => "snapshot",
+            Self::CandleHistory { .. } => "candle_history",
+            Self::Symbols(_) => "symbols",
+        }
+    }
+}
+
+/// Latest state of one symbol, so a client can draw it before the next update
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct MarketSnapshot {
+    pub symbol: Symbol,
+    pub ticker: Option<Ticker>,
+    pub orderbook: Option<OrderBookSnapshot>,
+    /// Forming 1m candle
+    pub candle: Option<Candle>,
+}
+
+impl MarketSnapshot {
+    /// Nothing seen yet for `symbol`
+    pub fn new(symbol: Symbol) -> Self {
+        Self {
+            symbol,
+            ticker: None,
+            orderbook: None,
+            candle: None,
+        }
+    }
+}
+
+/// One page of `GET /api/candles`, oldest first, with cursors for the pages
+/// either side
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct CandlePage {
+    pub candles: Vec<Candle>,
+    /// Pass as `before` for the previous (older) page; `None` at the start
+    pub older: Option<i64>,
+    /// Pass as `after` for the next (newer) page; `None` at the end
+    pub newer: Option<i64>,
+}
+
+/// Client → server command (the WS RPC channel)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ClientCommand {
+    Subscribe { symbol: String },
+    Unsubscribe { symbol: String },
+    Ping,
+    /// Answer to a server `ping`
+    Pong { nonce: u64 },
+    /// Submit a paper order; answered by `order_update` or `order_rejected`
+    PlaceOrder { order: OrderRequest },
+    CancelOrder { order_id: String },
+    /// Move a resting order to a new limit (or, for stops, trigger) price
+    AmendOrder { order_id: String, price: f64 },
+    /// Have the analytics channel also send sessions following `anchor`
+    TrackSession { anchor: SessionAnchor },
+    /// Latest ticker, book and candle for `symbol`; answered by `snapshot`
+    RequestSnapshot { symbol: Symbol },
+    /// 1m candles opening before `before` (latest when `None`); answered
+    /// by `candle_history`
+    RequestCandleHistory {
+        symbol: Symbol,
+        before: Option<i64>,
+        limit: usize,
+    },
+    /// Tradable symbols; answered by `symbols`
+    ListSymbols,
+    /// Send at most one book, depth and BBO update per symbol every
+    /// `interval_ms` (`None` streams every update)
+    SetConflation { interval_ms: Option<u32> },
+    /// Choose between full book and depth updates or just the BBO
+    SetBookFeed { feed: BookFeed },
+}
+
+impl ClientCommand {
+    /// Wire tag (matches the serde `type` field)
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Self::Subscribe { .. } => "subscribe",
+            Self::Unsubscribe { .. } => "unsubscribe",
+            Self::Ping => "ping",
+            Self::Pong { .. } => "pong",
+            Self::PlaceOrder { .. } => "place_order",
+            Self::CancelOrder { .. } => "cancel_order",
+            Self::AmendOrder { .. } => "amend_order",
+            Self::TrackSession { .. } => "track_session",
+            Self::RequestSnapshot { .. } => "request_snapshot",
+            Self::RequestCandleHistory { .. } => "request_candle_history",
+            Self::ListSymbols => "list_symbols",
+            Self::SetConflation { .. } => "set_conflation",
+            Self::SetBookFeed { .. } => "set_book_feed",
+        }
+    }
+}
+
+/// Order book detail a connection receives
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum BookFeed {
+    /// Full book and depth updates as well as the BBO
+    #[default]
+    Full,
+    /// BBO only, for bandwidth-constrained views
+    Bbo,
+}
+
+/// Why the server closed a connection, sent as an application close code
+/// (4000–4999, left to applications by RFC 6455)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum CloseReason {
+    /// Server is going down; reconnect later
+    ServerShutdown,
+    /// Connection not permitted (e.g. origin outside the allow-list)
+    AuthFailed,
+    /// Client fell too far behind the feed and was dropped
+    SlowConsumer,
+    /// Client sent something the server can't parse
+    ProtocolViolation,
+    /// Client stopped answering pings
+    IdleTimeout,
+}
+
+impl CloseReason {
+    pub const ALL: [Self; 5] = [
+        Self::ServerShutdown,
+        Self::AuthFailed,
+        Self::SlowConsumer,
+        Self::ProtocolViolation,
+        Self::IdleTimeout,
+    ];
+
+    pub const fn code(self) -> u16 {
+        match self {
+            Self::ServerShutdown => 4000,
+            Self::AuthFailed => 4001,
+            Self::SlowConsumer => 4002,
+            Self::ProtocolViolation => 4003,
+            Self::IdleTimeout => 4004,
+        }
+    }
+
+    pub fn from_code(code: u16) -> Option<Self> {
+        Self::ALL.into_iter().find(|reason| reason.code() == code)
+    }
+
+    /// Short status shown on the connection indicator
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::ServerShutdown => "Server restarting",
+            Self::AuthFailed => "Not authorized",
+            Self::SlowConsumer => "Fell behind",
+            Self::ProtocolViolation => "Protocol error",
+            Self::IdleTimeout => "Timed out",
+        }
+    }
+
+    /// Explanation for the user
+    pub fn message(self) -> &'static str {
+        match self {
+            Self::ServerShutdown => "The server is shutting down; reconnecting when it's back",
+            Self::AuthFailed => "The server refused this connection (origin not allowed)",
+            Self::SlowConsumer => "Kicked for falling behind the feed; reconnecting",
+            Self::ProtocolViolation => "The server closed the connection after an unreadable message",
+            Self::IdleTimeout => "The server dropped the connection after unanswered pings; reconnecting",
+        }
+    }
+
+    /// Worth retrying automatically? Refusals would just repeat.
+    pub fn should_reconnect(self) -> bool {
+        matches!(self, Self::ServerShutdown | Self::SlowConsumer | Self::IdleTimeout)
+    }
+}
+
+/// Whether market data comes from the primary source or a backup
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum FeedSource {
+    Primary,
+    /// Stand-in while the primary is down or stalled; prices are degraded
+    Backup,
+}
+
+/// Which source the server is currently streaming from
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct FeedStatus {
+    pub source: FeedSource,
+    /// Source name (e.g. `mock`, `drift`)
+    pub name: String,
+    /// When this source took over (ms)
+    pub since: i64,
+}
+
+impl FeedStatus {
+    pub fn is_degraded(&self) -> bool {
+        self.source == FeedSource::Backup
+    }
+}
+
+/// What the supervisor sees of an upstream source
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum SourceState {
+    Running,
+    /// Task alive but nothing delivered for a while
+    Stalled,
+    /// Task ended; waiting out the backoff before starting it again
+    Restarting,
+}
+
+impl SourceState {
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Running => "Live",
+            Self::Stalled => "Stalled",
+            Self::Restarting => "Restarting",
+        }
+    }
+}
+
+/// Health of an upstream source, so a feed outage can be told apart from a
+/// problem with the client's own connection
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct SourceStatus {
+    pub name: String,
+    pub state: SourceState,
+    /// Time since the source last delivered a message (`None` before the
+    /// first)
+    pub message_age_ms: Option<i64>,
+    /// Times the source's task crashed or ended
+    pub errors: u32,
+    pub restarts: u32,
+    /// Timestamp in milliseconds
+    pub timestamp: i64,
 }
 
 /// Connection state FSM
@@ -262,6 +778,8 @@ pub enum ConnectionState {
     Connecting,
     Connected,
     Reconnecting,
+    /// Closed by the server for a known reason
+    Closed(CloseReason),
 }
 
 impl ConnectionState {
@@ -275,6 +793,7 @@ impl ConnectionState {
             Self::Connecting => "Connecting...",
             Self::Connected => "Connected",
             Self::Reconnecting => "Reconnecting...",
+            Self::Closed(reason) => reason.label(),
         }
     }
 
@@ -284,6 +803,7 @@ impl ConnectionState {
             Self::Connecting => "conn-connecting",
             Self::Connected => "conn-connected",
             Self::Reconnecting => "conn-reconnecting",
+            Self::Closed(_) => "conn-closed",
         }
     }
 }
@@ -293,10 +813,13 @@ impl ConnectionState {
 // ============================================================================
 
 pub mod colors {
+    use std::fmt::Write;
+
     pub const BULL: &str = "#22c55e";
     pub const BEAR: &str = "#ef4444";
     pub const NEUTRAL: &str = "#888888";
     pub const WARN: &str = "#fbbf24";
+    pub const INFO: &str = "#3b82f6";
     pub const BG_VOID: &str = "#0a0a0a";
     pub const BG_PANEL: &str = "#141414";
     pub const BG_ELEVATED: &str = "#1a1a1a";
@@ -316,12 +839,340 @@ pub mod colors {
     pub fn warn_alpha(alpha: f64) -> String {
         format!("rgba(251, 191, 36, {:.2})", alpha)
     }
+
+    /// CSS custom property references resolved against the active theme.
+    /// Prefer these over the hex constants for inline styles so palette
+    /// switches restyle the DOM without re-rendering.
+    pub mod css {
+        pub const BULL: &str = "var(--accent-bull)";
+        pub const BEAR: &str = "var(--accent-bear)";
+        pub const NEUTRAL: &str = "var(--text-muted)";
+        pub const WARN: &str = "var(--accent-warn)";
+        pub const INFO: &str = "var(--accent-info)";
+        pub const BG_VOID: &str = "var(--bg-void)";
+        pub const BG_PANEL: &str = "var(--bg-panel)";
+        pub const BG_ELEVATED: &str = "var(--bg-elevated)";
+        pub const BORDER: &str = "var(--border-subtle)";
+        pub const TEXT_PRIMARY: &str = "var(--text-primary)";
+        pub const TEXT_MUTED: &str = "var(--text-muted)";
+        pub const GRID: &str = "var(--grid-color)";
+
+        pub fn direction(up: bool) -> &'static str {
+            if up { BULL } else { BEAR }
+        }
+
+        pub fn bull_alpha(alpha: f64) -> String {
+            mix(BULL, alpha)
+        }
+
+        pub fn bear_alpha(alpha: f64) -> String {
+            mix(BEAR, alpha)
+        }
+
+        pub fn warn_alpha(alpha: f64) -> String {
+            mix(WARN, alpha)
+        }
+
+        /// Blend a themed color with transparency (alpha 0.0 - 1.0)
+        pub fn mix(color: &str, alpha: f64) -> String {
+            format!(
+                "color-mix(in srgb, {} {:.0}%, transparent)",
+                color,
+                alpha.clamp(0.0, 1.0) * 100.0
+            )
+        }
+    }
+
+    /// Concrete colors for a theme, one field per CSS custom property
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    pub struct Palette {
+        pub bull: &'static str,
+        pub bear: &'static str,
+        pub warn: &'static str,
+        pub info: &'static str,
+        pub bg_void: &'static str,
+        pub bg_panel: &'static str,
+        pub bg_elevated: &'static str,
+        pub bg_hover: &'static str,
+        pub border: &'static str,
+        pub border_focus: &'static str,
+        pub text_primary: &'static str,
+        pub text_secondary: &'static str,
+        pub text_muted: &'static str,
+        pub text_disabled: &'static str,
+        pub grid: &'static str,
+    }
+
+    impl Palette {
+        /// Default trading terminal palette (matches `:root` in theme.css)
+        pub const DARK: Palette = Palette {
+            bull: "#22c55e",
+            bear: "#ef4444",
+            warn: "#fbbf24",
+            info: "#3b82f6",
+            bg_void: "#0a0a0a",
+            bg_panel: "#141414",
+            bg_elevated: "#1a1a1a",
+            bg_hover: "#222222",
+            border: "#2a2a2a",
+            border_focus: "#3a3a3a",
+            text_primary: "#fafafa",
+            text_secondary: "#b0b0b0",
+            text_muted: "#888888",
+            text_disabled: "#555555",
+            grid: "#1f1f1f",
+        };
+
+        pub const LIGHT: Palette = Palette {
+            bull: "#16a34a",
+            bear: "#dc2626",
+            warn: "#d97706",
+            info: "#2563eb",
+            bg_void: "#f4f4f5",
+            bg_panel: "#ffffff",
+            bg_elevated: "#fafafa",
+            bg_hover: "#ededed",
+            border: "#e4e4e7",
+            border_focus: "#d4d4d8",
+            text_primary: "#18181b",
+            text_secondary: "#3f3f46",
+            text_muted: "#71717a",
+            text_disabled: "#a1a1aa",
+            grid: "#ececec",
+        };
+
+        /// Pure black background with saturated accents (WCAG AAA text contrast)
+        pub const HIGH_CONTRAST: Palette = Palette {
+            bull: "#00ff66",
+            bear: "#ff3b3b",
+            warn: "#ffff00",
+            info: "#00bfff",
+            bg_void: "#000000",
+            bg_panel: "#000000",
+            bg_elevated: "#0d0d0d",
+            bg_hover: "#1f1f1f",
+            border: "#ffffff",
+            border_focus: "#ffff00",
+            text_primary: "#ffffff",
+            text_secondary: "#ffffff",
+            text_muted: "#d0d0d0",
+            text_disabled: "#8a8a8a",
+            grid: "#333333",
+        };
+
+        /// Okabe-Ito blue/orange pairing, distinguishable with red-green deficiency
+        pub const COLORBLIND: Palette = Palette {
+            bull: "#56b4e9",
+            bear: "#e69f00",
+            warn: "#f0e442",
+            info: "#cc79a7",
+            bg_void: "#0a0a0a",
+            bg_panel: "#141414",
+            bg_elevated: "#1a1a1a",
+            bg_hover: "#222222",
+            border: "#2a2a2a",
+            border_focus: "#3a3a3a",
+            text_primary: "#fafafa",
+            text_secondary: "#b0b0b0",
+            text_muted: "#888888",
+            text_disabled: "#555555",
+            grid: "#1f1f1f",
+        };
+
+        /// Bull or bear by direction
+        pub fn direction(&self, up: bool) -> &'static str {
+            if up { self.bull } else { self.bear }
+        }
+
+        pub fn bull_alpha(&self, alpha: f64) -> String {
+            with_alpha(self.bull, alpha)
+        }
+
+        pub fn bear_alpha(&self, alpha: f64) -> String {
+            with_alpha(self.bear, alpha)
+        }
+
+        pub fn warn_alpha(&self, alpha: f64) -> String {
+            with_alpha(self.warn, alpha)
+        }
+
+        /// (custom property, value) pairs for every palette color
+        pub fn variables(&self) -> Vec<(&'static str, String)> {
+            vec![
+                ("--bg-void", self.bg_void.to_string()),
+                ("--bg-panel", self.bg_panel.to_string()),
+                ("--bg-elevated", self.bg_elevated.to_string()),
+                ("--bg-hover", self.bg_hover.to_string()),
+                ("--border-subtle", self.border.to_string()),
+                ("--border-focus", self.border_focus.to_string()),
+                ("--text-primary", self.text_primary.to_string()),
+                ("--text-secondary", self.text_secondary.to_string()),
+                ("--text-muted", self.text_muted.to_string()),
+                ("--text-disabled", self.text_disabled.to_string()),
+                ("--accent-bull", self.bull.to_string()),
+                ("--accent-bull-dim", dim(self.bull)),
+                ("--accent-bear", self.bear.to_string()),
+                ("--accent-bear-dim", dim(self.bear)),
+                ("--accent-warn", self.warn.to_string()),
+                ("--accent-warn-dim", dim(self.warn)),
+                ("--accent-info", self.info.to_string()),
+                ("--accent-info-dim", dim(self.info)),
+                ("--grid-color", self.grid.to_string()),
+                ("--axis-color", self.border.to_string()),
+            ]
+        }
+
+        /// Inline style declarations (e.g. for `document.documentElement`)
+        pub fn css_vars(&self) -> String {
+            let mut css = String::with_capacity(768);
+            for (name, value) in self.variables() {
+                write!(css, "{}: {}; ", name, value).unwrap();
+            }
+            css.trim_end().to_string()
+        }
+    }
+
+    impl Default for Palette {
+        fn default() -> Self {
+            Self::DARK
+        }
+    }
+
+    /// 20% tint used for the `-dim` accent variants
+    fn dim(color: &str) -> String {
+        format!("color-mix(in srgb, {} 20%, transparent)", color)
+    }
+
+    /// `rgba()` of a `#rrggbb` color (alpha 0.0 - 1.0), for canvas and WebGL
+    /// painting where custom properties don't resolve
+    pub fn with_alpha(hex: &str, alpha: f64) -> String {
+        let channel = |i: usize| hex.get(i..i + 2).and_then(|c| u8::from_str_radix(c, 16).ok()).unwrap_or(0);
+        format!("rgba({}, {}, {}, {:.2})", channel(1), channel(3), channel(5), alpha.clamp(0.0, 1.0))
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    /// JSON → value → JSON comes back unchanged, under the expected tag
+    fn round_trip<T: Serialize + serde::de::DeserializeOwned>(value: &T, tag: &str) {
+        let json = serde_json::to_value(value).unwrap();
+        assert_eq!(json["type"], tag);
+        let decoded: T = serde_json::from_value(json.clone()).unwrap();
+        assert_eq!(serde_json::to_value(&decoded).unwrap(), json);
+    }
+
+    #[test]
+    fn test_client_commands_round_trip() {
+        let symbol = Symbol::new("ETH-USD");
+        let commands = [
+            ClientCommand::RequestSnapshot { symbol: symbol.clone() },
+            ClientCommand::RequestCandleHistory { symbol: symbol.clone(), before: Some(60_000), limit: 500 },
+            ClientCommand::ListSymbols,
+            ClientCommand::Pong { nonce: 7 },
+            ClientCommand::SetConflation { interval_ms: Some(250) },
+            ClientCommand::SetConflation { interval_ms: None },
+            ClientCommand::SetBookFeed { feed: BookFeed::Bbo },
+            ClientCommand::TrackSession { anchor: SessionAnchor::Custom { start_minute: 90 } },
+        ];
+        for command in &commands {
+            round_trip(command, command.kind());
+        }
+        let parsed: ClientCommand = serde_json::from_str(r#"{"type":"list_symbols"}"#).unwrap();
+        assert!(matches!(parsed, ClientCommand::ListSymbols));
+    }
+
+    #[cfg(feature = "schema")]
+    #[test]
+    fn test_protocol_schema() {
+        let schema = protocol_schema();
+        assert_eq!(schema["protocol_version"], PROTOCOL_VERSION);
+        let text = schema.to_string();
+        for tag in ["hello", "order_rejected", "candle_history", "request_snapshot", "set_conflation"] {
+            assert!(text.contains(&format!("\"{}\"", tag)), "schema lacks {}", tag);
+        }
+    }
+
+    #[test]
+    fn test_close_reason_codes() {
+        for reason in CloseReason::ALL {
+            assert!((4000..5000).contains(&reason.code()));
+            assert_eq!(CloseReason::from_code(reason.code()), Some(reason));
+        }
+        // Standard codes (normal closure, going away, abnormal) aren't ours
+        assert_eq!(CloseReason::from_code(1000), None);
+        assert_eq!(CloseReason::from_code(1006), None);
+        assert!(CloseReason::SlowConsumer.should_reconnect());
+        assert!(!CloseReason::AuthFailed.should_reconnect());
+        assert_eq!(ConnectionState::Closed(CloseReason::SlowConsumer).label(), "Fell behind");
+    }
+
+    #[test]
+    fn test_responses_round_trip() {
+        let symbol = Symbol::new("BTC-USD");
+        let responses = [
+            WsMessage::Snapshot(MarketSnapshot {
+                symbol: symbol.clone(),
+                ticker: Some(Ticker::new(symbol.clone(), 100.0)),
+                orderbook: Some(OrderBookSnapshot::new(symbol.clone())),
+                candle: None,
+            }),
+            WsMessage::CandleHistory {
+                symbol: symbol.clone(),
+                before: None,
+                limit: 2,
+                candles: vec![Candle::new(symbol.clone(), CandleInterval::M1, 0, 100.0)],
+            },
+            WsMessage::Symbols(vec![SymbolInfo::default()]),
+            WsMessage::Ping { nonce: 7, timestamp: 1_000 },
+            WsMessage::SourceStatus(SourceStatus {
+                name: "mock".to_string(),
+                state: SourceState::Restarting,
+                message_age_ms: Some(12_000),
+                errors: 1,
+                restarts: 1,
+                timestamp: 1_000,
+            }),
+            WsMessage::FeedStatus(FeedStatus {
+                source: FeedSource::Backup,
+                name: "drift".to_string(),
+                since: 1_000,
+            }),
+            WsMessage::Bbo(Bbo {
+                symbol: symbol.clone(),
+                bid: Price::new(99.5),
+                bid_size: Quantity::new(2.0),
+                ask: Price::new(100.5),
+                ask_size: Quantity::new(1.0),
+                timestamp: 1_000,
+            }),
+            WsMessage::Account(AccountSummary {
+                equity: 100_250.0,
+                realized_pnl_today: 300.0,
+                fees_paid: 50.0,
+                day_start: 0,
+                timestamp: 1_000,
+            }),
+            WsMessage::CandleCheck(CandleCheckReport {
+                symbol: symbol.clone(),
+                from: 0,
+                to: 60_000,
+                checked: 1,
+                discrepancies: vec![CandleDiscrepancy {
+                    timestamp: 0,
+                    official: Some(Candle::new(symbol.clone(), CandleInterval::M1, 0, 100.0)),
+                    from_trades: None,
+                    fields: vec!["bar".to_string()],
+                }],
+                timestamp: 1_000,
+            }),
+        ];
+        for response in &responses {
+            round_trip(response, response.kind());
+        }
+    }
+
     #[test]
     fn test_symbol_parsing() {
         let sym = Symbol::new("ETH-BTC");
@@ -329,6 +1180,44 @@ mod tests {
         assert_eq!(sym.quote(), "BTC");
     }
 
+    #[test]
+    fn test_symbol_info_steps() {
+        let info = SymbolInfo::new(Symbol::default(), 0.5, 0.001);
+
+        assert!(info.is_valid_price(100.5));
+        assert!(!info.is_valid_price(100.25));
+        assert_eq!(info.round_price(100.3), 100.5);
+        assert!(info.is_valid_quantity(0.123));
+        assert!(!info.is_valid_quantity(0.1234));
+        assert_eq!(info.quantity_decimals(), 3);
+    }
+
+    #[test]
+    fn test_tick_format_follows_the_symbol() {
+        let format = SymbolInfo::new(Symbol::new("XRP-USD"), 0.0001, 1.0).tick_format();
+        assert_eq!(format.price(0.52), "0.5200");
+        assert_eq!(format.quantity(1500.0), "1500");
+        assert_eq!(TickFormat::default().price(65_000.0), "65000.00");
+        assert_eq!(SymbolInfo::new(Symbol::new("BTC-USD"), 0.5, 0.001).tick_format().price(100.5), "100.5");
+        assert_eq!(format.ticks(0.52), 5_200);
+        assert_eq!(TickFormat::default().ticks(65_000.07), 6_500_007);
+    }
+
+    #[test]
+    fn test_step_decimals_off_powers_of_ten() {
+        let decimals = |tick| SymbolInfo::new(Symbol::default(), tick, 1.0).price_decimals();
+        assert_eq!(decimals(0.25), 2);
+        assert_eq!(decimals(0.025), 3);
+        assert_eq!(decimals(0.5), 1);
+        assert_eq!(decimals(0.0001), 4);
+        assert_eq!(decimals(1.0), 0);
+
+        let format = SymbolInfo::new(Symbol::new("ETH-USD"), 0.25, 0.001).tick_format();
+        assert_eq!(format.price(100.75), "100.75");
+        assert_eq!(format.price(100.75).parse::<f64>().unwrap(), 100.75);
+        assert_eq!(SymbolInfo::new(Symbol::default(), 0.0025, 1.0).tick_format().price(1.0025), "1.0025");
+    }
+
     #[test]
     fn test_price_formatter_strategy() {
         let formatter = DecimalPriceFormatter { decimals: 4 };
@@ -336,6 +1225,21 @@ mod tests {
         assert_eq!(price.format_with(&formatter), "42.5678");
     }
 
+    #[test]
+    fn test_css_color_mix() {
+        assert_eq!(
+            colors::css::bull_alpha(0.2),
+            "color-mix(in srgb, var(--accent-bull) 20%, transparent)"
+        );
+    }
+
+    #[test]
+    fn test_palette_alpha() {
+        assert_eq!(colors::with_alpha("#22c55e", 0.5), "rgba(34, 197, 94, 0.50)");
+        assert_eq!(colors::Palette::DARK.bear_alpha(0.7), colors::with_alpha(colors::Palette::DARK.bear, 0.7));
+        assert_eq!(colors::Palette::LIGHT.direction(false), colors::Palette::LIGHT.bear);
+    }
+
     #[test]
     fn test_compact_formatter() {
         let formatter = CompactNumberFormatter;
@@ -1,6 +1,6 @@
 //! Order book types and market depth visualization
 
-use crate::{colors, Price, Quantity, Symbol};
+use crate::{colors::Palette, Price, Quantity, Symbol, TradeSide};
 use serde::{Deserialize, Serialize};
 
 // ============================================================================
@@ -66,7 +66,8 @@ impl DepthAggregator for FixedBucketAggregator {
 // ============================================================================
 
 /// Single level in the order book (price level aggregation)
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct OrderBookLevel {
     pub price: Price,
     pub quantity: Quantity,
@@ -100,6 +101,7 @@ impl OrderBookLevel {
 
 /// Order book side (bids or asks)
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "lowercase")]
 pub enum OrderSide {
     Bid,
@@ -107,17 +109,15 @@ pub enum OrderSide {
 }
 
 impl OrderSide {
-    pub fn color(&self) -> &'static str {
-        match self {
-            Self::Bid => colors::BULL,
-            Self::Ask => colors::BEAR,
-        }
+    /// Side color in `palette` (for canvas painting)
+    pub fn color(&self, palette: &Palette) -> &'static str {
+        palette.direction(*self == Self::Bid)
     }
 
-    pub fn bg_color(&self, alpha: f64) -> String {
+    pub fn bg_color(&self, palette: &Palette, alpha: f64) -> String {
         match self {
-            Self::Bid => colors::bull_alpha(alpha),
-            Self::Ask => colors::bear_alpha(alpha),
+            Self::Bid => palette.bull_alpha(alpha),
+            Self::Ask => palette.bear_alpha(alpha),
         }
     }
 
@@ -138,6 +138,7 @@ impl OrderSide {
 
 /// Complete order book snapshot
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct OrderBookSnapshot {
     pub symbol: Symbol,
     /// Sorted by price descending (highest bid first)
@@ -239,6 +240,23 @@ impl OrderBookSnapshot {
         bid_max.max(ask_max)
     }
 
+    /// Up to `depth` levels from the touch outward, each paired with the
+    /// running quantity through that level
+    pub fn cumulative_levels(&self, side: OrderSide, depth: usize) -> Vec<(OrderBookLevel, f64)> {
+        let levels = match side {
+            OrderSide::Bid => &self.bids,
+            OrderSide::Ask => &self.asks,
+        };
+        levels
+            .iter()
+            .take(depth)
+            .scan(0.0, |cumulative, level| {
+                *cumulative += level.quantity.as_f64();
+                Some((level.clone(), *cumulative))
+            })
+            .collect()
+    }
+
     /// Get price range (min bid, max ask)
     pub fn price_range(&self) -> Option<(f64, f64)> {
         let bid_min = self.bids.last().map(|l| l.price.as_f64());
@@ -252,18 +270,116 @@ impl OrderBookSnapshot {
         }
     }
 
+    /// Levels merged onto multiples of `step` (bids rounded down, asks up,
+    /// so grouping never makes the book look tighter), at most `depth` per
+    /// side. A step that isn't positive only truncates.
+    pub fn grouped(&self, step: f64, depth: usize) -> Self {
+        let group = |levels: &[OrderBookLevel], bucket: fn(f64) -> f64| {
+            let mut grouped: Vec<OrderBookLevel> = Vec::new();
+            for level in levels {
+                let price = level.price.as_f64();
+                let price = if step > 0.0 { bucket(price / step) * step } else { price };
+                if let Some(last) = grouped.last_mut().filter(|last| last.price.as_f64() == price) {
+                    last.quantity = Quantity::new(last.quantity.as_f64() + level.quantity.as_f64());
+                    last.order_count += level.order_count;
+                } else if grouped.len() == depth {
+                    break;
+                } else {
+                    grouped.push(OrderBookLevel::new(price, level.quantity.as_f64(), level.order_count));
+                }
+            }
+            grouped
+        };
+        // The nudges keep exact multiples from slipping a bucket on float noise
+        Self {
+            symbol: self.symbol.clone(),
+            bids: group(&self.bids, |ticks| (ticks + 1e-9).floor()),
+            asks: group(&self.asks, |ticks| (ticks - 1e-9).ceil()),
+            timestamp: self.timestamp,
+            sequence: self.sequence,
+        }
+    }
+
     /// Aggregate with custom strategy
     pub fn aggregate_with<A: DepthAggregator>(&self, aggregator: &A) -> (Vec<AggregatedLevel>, Vec<AggregatedLevel>) {
         (aggregator.aggregate(&self.bids), aggregator.aggregate(&self.asks))
     }
 }
 
+// ============================================================================
+// PRICE LADDER (fixed price axis)
+// ============================================================================
+
+/// One rung of a fixed-axis price ladder
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LadderRung {
+    pub price: f64,
+    /// Resting bid size bucketed onto this rung
+    pub bid: f64,
+    /// Resting ask size bucketed onto this rung
+    pub ask: f64,
+}
+
+/// Rung a price falls on for a ladder `step` apart
+pub fn ladder_index(price: f64, step: f64) -> i64 {
+    (price / step).round() as i64
+}
+
+impl OrderBookSnapshot {
+    /// `2 * half + 1` rungs `step` apart around `center`, highest price first
+    pub fn ladder(&self, center: f64, step: f64, half: usize) -> Vec<LadderRung> {
+        use std::collections::HashMap;
+
+        let mut sizes: HashMap<i64, (f64, f64)> = HashMap::new();
+        for level in &self.bids {
+            sizes.entry(ladder_index(level.price.as_f64(), step)).or_default().0 += level.quantity.as_f64();
+        }
+        for level in &self.asks {
+            sizes.entry(ladder_index(level.price.as_f64(), step)).or_default().1 += level.quantity.as_f64();
+        }
+
+        let middle = ladder_index(center, step);
+        let half = half as i64;
+        ((middle - half)..=(middle + half))
+            .rev()
+            .map(|index| {
+                let (bid, ask) = sizes.get(&index).copied().unwrap_or_default();
+                LadderRung { price: index as f64 * step, bid, ask }
+            })
+            .collect()
+    }
+
+    /// Smallest 1-2-5 multiple of `tick` that fits the best `depth` levels of
+    /// each side onto `rungs` rungs
+    pub fn ladder_step(&self, tick: f64, rungs: usize, depth: usize) -> f64 {
+        let outer = |levels: &[OrderBookLevel]| levels[..levels.len().min(depth)].last().map(|l| l.price.as_f64());
+        let span = match (outer(&self.bids), outer(&self.asks)) {
+            (Some(low), Some(high)) => high - low,
+            _ => return tick,
+        };
+        let needed = span / rungs.max(1) as f64;
+
+        let mut decade = tick;
+        // Bounded so a degenerate tick can't spin forever
+        for _ in 0..24 {
+            for multiple in [1.0, 2.0, 5.0] {
+                if decade * multiple >= needed {
+                    return decade * multiple;
+                }
+            }
+            decade *= 10.0;
+        }
+        decade
+    }
+}
+
 // ============================================================================
 // MARKET DEPTH (for visualization)
 // ============================================================================
 
 /// Single point on depth chart
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct DepthPoint {
     pub price: f64,
     /// Cumulative quantity up to this price
@@ -272,8 +388,21 @@ pub struct DepthPoint {
     pub cumulative_value: f64,
 }
 
+/// Expected execution of a market order swept through the book
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FillEstimate {
+    /// Quantity the book covers (less than asked when it runs out)
+    pub filled: f64,
+    pub avg_price: f64,
+    /// Deepest level reached
+    pub worst_price: f64,
+    /// Average price's distance from mid against the taker (percent)
+    pub slippage_percent: f64,
+}
+
 /// Aggregated market depth for visualization
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct MarketDepth {
     pub symbol: Symbol,
     /// Cumulative bid depth (sorted highest to lowest price)
@@ -354,6 +483,56 @@ impl MarketDepth {
             _ => None,
         }
     }
+
+    /// Depth resting between the mid and `price`: the bids at or above it
+    /// below the mid, the asks at or below it above the mid
+    pub fn depth_at(&self, price: f64) -> Option<(OrderSide, DepthPoint)> {
+        let mid = self.mid_price()?;
+        if price <= mid {
+            let point = self.bid_depth.iter().take_while(|p| p.price >= price).last()?;
+            Some((OrderSide::Bid, *point))
+        } else {
+            let point = self.ask_depth.iter().take_while(|p| p.price <= price).last()?;
+            Some((OrderSide::Ask, *point))
+        }
+    }
+
+    /// Sweep a market order for `quantity` through the opposite side
+    pub fn estimate_fill(&self, side: TradeSide, quantity: f64) -> Option<FillEstimate> {
+        if !quantity.is_finite() || quantity <= 0.0 {
+            return None;
+        }
+        let mid = self.mid_price()?;
+        let levels = match side {
+            TradeSide::Buy => &self.ask_depth,
+            TradeSide::Sell => &self.bid_depth,
+        };
+
+        let (mut filled, mut value, mut worst) = (0.0, 0.0, None);
+        for point in levels {
+            worst = Some(point.price);
+            if point.cumulative_quantity >= quantity {
+                value += (quantity - filled) * point.price;
+                filled = quantity;
+                break;
+            }
+            filled = point.cumulative_quantity;
+            value = point.cumulative_value;
+        }
+
+        let worst_price = worst.filter(|_| filled > 0.0)?;
+        let avg_price = value / filled;
+        let direction = match side {
+            TradeSide::Buy => 1.0,
+            TradeSide::Sell => -1.0,
+        };
+        Some(FillEstimate {
+            filled,
+            avg_price,
+            worst_price,
+            slippage_percent: (avg_price - mid) / mid * 100.0 * direction,
+        })
+    }
 }
 
 // ============================================================================
@@ -362,6 +541,7 @@ impl MarketDepth {
 
 /// Delta update for order book
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct OrderBookDelta {
     pub symbol: Symbol,
     pub side: OrderSide,
@@ -402,6 +582,21 @@ mod tests {
         assert_eq!(book.spread(), Some(10.0));
     }
 
+    #[test]
+    fn test_grouped() {
+        let book = sample_orderbook();
+        let grouped = book.grouped(25.0, 10);
+        let levels = |side: &[OrderBookLevel]| -> Vec<(f64, f64, u32)> {
+            side.iter().map(|l| (l.price.as_f64(), l.quantity.as_f64(), l.order_count)).collect()
+        };
+        assert_eq!(levels(&grouped.bids), vec![(50000.0, 1.0, 5), (49975.0, 3.5, 11)]);
+        assert_eq!(levels(&grouped.asks), vec![(50025.0, 2.0, 10), (50050.0, 2.0, 10)]);
+
+        let truncated = book.grouped(0.0, 2);
+        assert_eq!(levels(&truncated.bids), levels(&book.bids[..2]));
+        assert_eq!(book.grouped(25.0, 1).asks.len(), 1);
+    }
+
     #[test]
     fn test_mid_price() {
         let book = sample_orderbook();
@@ -429,6 +624,60 @@ mod tests {
         assert_eq!(depth.bid_depth[2].cumulative_quantity, 4.5);
     }
 
+    #[test]
+    fn test_depth_at_and_fill_estimate() {
+        let depth = MarketDepth::from_orderbook(&sample_orderbook());
+
+        let (side, point) = depth.depth_at(49_985.0).unwrap();
+        assert_eq!(side, OrderSide::Bid);
+        assert_eq!(point.cumulative_quantity, 3.0);
+        assert!(depth.depth_at(50_009.0).is_none());
+
+        // 0.8 @ 50010 + 0.2 @ 50020
+        let buy = depth.estimate_fill(TradeSide::Buy, 1.0).unwrap();
+        assert_eq!(buy.filled, 1.0);
+        assert_eq!(buy.worst_price, 50_020.0);
+        assert!((buy.avg_price - 50_012.0).abs() < 1e-9);
+        assert!(buy.slippage_percent > 0.0);
+
+        // More than the bids hold: fills what's there
+        let sell = depth.estimate_fill(TradeSide::Sell, 10.0).unwrap();
+        assert_eq!(sell.filled, 4.5);
+        assert_eq!(sell.worst_price, 49_980.0);
+        assert!(sell.slippage_percent > 0.0);
+    }
+
+    #[test]
+    fn test_cumulative_levels() {
+        let book = sample_orderbook();
+
+        let asks = book.cumulative_levels(OrderSide::Ask, 2);
+        assert_eq!(asks.len(), 2);
+        assert_eq!(asks[0].1, 0.8);
+        assert_eq!(asks[1].1, 2.0);
+
+        let bids = book.cumulative_levels(OrderSide::Bid, 10);
+        assert_eq!(bids.last().map(|(_, cum)| *cum), Some(book.total_bid_depth()));
+    }
+
+    #[test]
+    fn test_price_ladder() {
+        let book = sample_orderbook();
+
+        // Levels are 10 apart and span 50 across the best three per side
+        assert_eq!(book.ladder_step(0.01, 10, 3), 5.0);
+        assert_eq!(book.ladder_step(0.01, 100, 3), 0.5);
+
+        let rungs = book.ladder(50005.0, 10.0, 3);
+        assert_eq!(rungs.len(), 7);
+        assert!(rungs.windows(2).all(|w| w[0].price > w[1].price));
+
+        let at = |price: f64| rungs.iter().find(|r| r.price == price).unwrap();
+        assert_eq!(at(50000.0).bid, 1.0);
+        assert_eq!(at(50010.0).ask, 0.8);
+        assert_eq!(at(50040.0).ask, 0.0);
+    }
+
     #[test]
     fn test_aggregator_strategy() {
         let book = sample_orderbook();
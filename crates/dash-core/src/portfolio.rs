@@ -0,0 +1,531 @@
+//! Paper-trading account types (orders, positions, balances, equity)
+
+use crate::{colors, Price, Quantity, Symbol, SymbolInfo, TradeSide};
+use serde::{Deserialize, Serialize};
+
+// ============================================================================
+// ORDERS
+// ============================================================================
+
+/// Order execution type
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "lowercase")]
+pub enum OrderType {
+    #[default]
+    Market,
+    Limit,
+    /// Market order armed once the last price crosses the stop price
+    Stop,
+}
+
+impl OrderType {
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Market => "Market",
+            Self::Limit => "Limit",
+            Self::Stop => "Stop",
+        }
+    }
+
+    pub fn all() -> &'static [Self] {
+        &[Self::Limit, Self::Market, Self::Stop]
+    }
+}
+
+/// Order lifecycle status
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum OrderStatus {
+    #[default]
+    Open,
+    PartiallyFilled,
+    Filled,
+    Cancelled,
+    Rejected,
+}
+
+impl OrderStatus {
+    /// Still working on the book?
+    pub fn is_open(&self) -> bool {
+        matches!(self, Self::Open | Self::PartiallyFilled)
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Open => "Open",
+            Self::PartiallyFilled => "Partial",
+            Self::Filled => "Filled",
+            Self::Cancelled => "Cancelled",
+            Self::Rejected => "Rejected",
+        }
+    }
+
+    pub fn css_class(&self) -> &'static str {
+        match self {
+            Self::Open => "order-open",
+            Self::PartiallyFilled => "order-partial",
+            Self::Filled => "order-filled",
+            Self::Cancelled => "order-cancelled",
+            Self::Rejected => "order-rejected",
+        }
+    }
+}
+
+/// Paper-trading order
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct PaperOrder {
+    pub id: String,
+    pub symbol: Symbol,
+    pub side: TradeSide,
+    pub order_type: OrderType,
+    /// Limit price (`None` for market orders)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub price: Option<Price>,
+    /// Trigger price (stop orders only)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub stop_price: Option<Price>,
+    pub quantity: Quantity,
+    pub filled: Quantity,
+    pub status: OrderStatus,
+    /// Unix timestamp in milliseconds
+    pub created_at: i64,
+    /// Execution price, once filled
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fill_price: Option<Price>,
+    /// Execution time (ms), once filled
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub filled_at: Option<i64>,
+}
+
+impl PaperOrder {
+    /// Quantity still working
+    pub fn remaining(&self) -> f64 {
+        (self.quantity.as_f64() - self.filled.as_f64()).max(0.0)
+    }
+
+    /// Price the order rests at: the limit, or a stop's trigger
+    pub fn working_price(&self) -> Option<f64> {
+        match self.order_type {
+            OrderType::Limit => self.price.map(|p| p.as_f64()),
+            OrderType::Stop => self.stop_price.map(|p| p.as_f64()),
+            OrderType::Market => None,
+        }
+    }
+
+    /// Fill percentage (0-100)
+    pub fn fill_percent(&self) -> f64 {
+        if self.quantity.as_f64() == 0.0 {
+            0.0
+        } else {
+            self.filled.as_f64() / self.quantity.as_f64() * 100.0
+        }
+    }
+}
+
+/// Order as entered in the UI, before the engine assigns status
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct OrderRequest {
+    /// Client-generated id; the engine reuses it as the order id
+    pub client_id: String,
+    pub symbol: Symbol,
+    pub side: TradeSide,
+    pub order_type: OrderType,
+    /// Limit price (limit orders)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub price: Option<f64>,
+    /// Trigger price (stop orders)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub stop_price: Option<f64>,
+    pub quantity: f64,
+}
+
+impl OrderRequest {
+    /// Price the order is expected to execute at; market orders use `reference`
+    pub fn execution_price(&self, reference: f64) -> f64 {
+        match self.order_type {
+            OrderType::Limit => self.price.unwrap_or(reference),
+            OrderType::Stop => self.stop_price.unwrap_or(reference),
+            OrderType::Market => reference,
+        }
+    }
+
+    /// Expected quote notional
+    pub fn notional(&self, reference: f64) -> f64 {
+        self.quantity * self.execution_price(reference)
+    }
+
+    /// Check prices and quantity against the symbol's tick/lot rules
+    pub fn validate(&self, info: &SymbolInfo) -> Result<(), OrderRejection> {
+        if !self.quantity.is_finite() || self.quantity <= 0.0 {
+            return Err(OrderRejection::InvalidQuantity);
+        }
+        if self.quantity < info.min_quantity {
+            return Err(OrderRejection::BelowMinQuantity { min: info.min_quantity });
+        }
+        if !info.is_valid_quantity(self.quantity) {
+            return Err(OrderRejection::OffLotSize { lot_size: info.lot_size });
+        }
+
+        let required = match self.order_type {
+            OrderType::Market => None,
+            OrderType::Limit => Some(self.price),
+            OrderType::Stop => Some(self.stop_price),
+        };
+        if let Some(price) = required {
+            let price = price.filter(|p| p.is_finite() && *p > 0.0).ok_or(OrderRejection::MissingPrice)?;
+            if !info.is_valid_price(price) {
+                return Err(OrderRejection::OffTickSize { tick_size: info.tick_size });
+            }
+        }
+        Ok(())
+    }
+
+    /// Check the quote balance covers the expected notional
+    pub fn check_balance(&self, reference: f64, available: f64) -> Result<(), OrderRejection> {
+        let required = self.notional(reference);
+        if required > available {
+            Err(OrderRejection::InsufficientBalance { required, available })
+        } else {
+            Ok(())
+        }
+    }
+}
+
+/// Why an order was refused (client-side checks or the engine)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(tag = "code", rename_all = "snake_case")]
+pub enum OrderRejection {
+    InvalidQuantity,
+    BelowMinQuantity { min: f64 },
+    OffLotSize { lot_size: f64 },
+    MissingPrice,
+    OffTickSize { tick_size: f64 },
+    InsufficientBalance { required: f64, available: f64 },
+    UnknownSymbol,
+    /// Free-form engine error
+    Engine { message: String },
+}
+
+impl std::fmt::Display for OrderRejection {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::InvalidQuantity => write!(f, "Quantity must be positive"),
+            Self::BelowMinQuantity { min } => write!(f, "Minimum quantity is {}", min),
+            Self::OffLotSize { lot_size } => write!(f, "Quantity must be a multiple of {}", lot_size),
+            Self::MissingPrice => write!(f, "Price is required"),
+            Self::OffTickSize { tick_size } => write!(f, "Price must be a multiple of {}", tick_size),
+            Self::InsufficientBalance { required, available } => {
+                write!(f, "Insufficient balance: need {:.2}, have {:.2}", required, available)
+            }
+            Self::UnknownSymbol => write!(f, "Unknown symbol"),
+            Self::Engine { message } => write!(f, "{}", message),
+        }
+    }
+}
+
+// ============================================================================
+// POSITIONS
+// ============================================================================
+
+/// Net position in one symbol (positive quantity = long, negative = short)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct Position {
+    pub symbol: Symbol,
+    /// Signed base quantity
+    pub quantity: f64,
+    pub entry_price: Price,
+    pub mark_price: Price,
+    pub realized_pnl: f64,
+}
+
+impl Position {
+    pub fn is_flat(&self) -> bool {
+        self.quantity == 0.0
+    }
+
+    pub fn side(&self) -> Option<TradeSide> {
+        if self.quantity > 0.0 {
+            Some(TradeSide::Buy)
+        } else if self.quantity < 0.0 {
+            Some(TradeSide::Sell)
+        } else {
+            None
+        }
+    }
+
+    /// Position label ("LONG" / "SHORT" / "FLAT")
+    pub fn side_label(&self) -> &'static str {
+        match self.side() {
+            Some(TradeSide::Buy) => "LONG",
+            Some(TradeSide::Sell) => "SHORT",
+            None => "FLAT",
+        }
+    }
+
+    /// Absolute notional at mark price
+    pub fn notional(&self) -> f64 {
+        self.quantity.abs() * self.mark_price.as_f64()
+    }
+
+    /// Unrealized PnL at mark price
+    pub fn unrealized_pnl(&self) -> f64 {
+        self.quantity * (self.mark_price.as_f64() - self.entry_price.as_f64())
+    }
+
+    /// Unrealized PnL relative to entry notional (percent)
+    pub fn unrealized_pnl_percent(&self) -> f64 {
+        let cost = self.quantity.abs() * self.entry_price.as_f64();
+        if cost == 0.0 {
+            0.0
+        } else {
+            self.unrealized_pnl() / cost * 100.0
+        }
+    }
+
+    /// PnL color
+    pub fn pnl_color(&self) -> &'static str {
+        if self.unrealized_pnl() >= 0.0 {
+            colors::css::BULL
+        } else {
+            colors::css::BEAR
+        }
+    }
+}
+
+// ============================================================================
+// BALANCES & EQUITY
+// ============================================================================
+
+/// Asset balance
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct Balance {
+    pub asset: String,
+    pub free: f64,
+    /// Reserved by open orders
+    pub locked: f64,
+}
+
+impl Balance {
+    pub fn total(&self) -> f64 {
+        self.free + self.locked
+    }
+}
+
+/// Account equity sample for the equity curve
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct EquitySample {
+    /// Unix timestamp in milliseconds
+    pub timestamp: i64,
+    pub equity: f64,
+}
+
+/// Fall from an equity peak to the lowest point after it
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Drawdown {
+    pub peak: EquitySample,
+    pub trough: EquitySample,
+}
+
+impl Drawdown {
+    /// Quote amount lost from the peak
+    pub fn amount(&self) -> f64 {
+        self.peak.equity - self.trough.equity
+    }
+
+    /// Loss as a fraction of the peak
+    pub fn fraction(&self) -> f64 {
+        if self.peak.equity > 0.0 {
+            self.amount() / self.peak.equity
+        } else {
+            0.0
+        }
+    }
+}
+
+/// Deepest drawdown (by fraction of the peak) along an equity curve,
+/// oldest first; `None` when equity never falls
+pub fn max_drawdown(samples: &[EquitySample]) -> Option<Drawdown> {
+    let mut peak = *samples.first()?;
+    let mut deepest: Option<Drawdown> = None;
+    for &sample in samples {
+        if sample.equity > peak.equity {
+            peak = sample;
+            continue;
+        }
+        let drawdown = Drawdown { peak, trough: sample };
+        if drawdown.amount() > 0.0 && deepest.is_none_or(|d| drawdown.fraction() > d.fraction()) {
+            deepest = Some(drawdown);
+        }
+    }
+    deepest
+}
+
+/// One paper fill, as kept in the trade journal
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct JournalEntry {
+    pub order_id: String,
+    pub symbol: Symbol,
+    pub side: TradeSide,
+    pub order_type: OrderType,
+    pub quantity: f64,
+    pub price: f64,
+    /// PnL realized by the part of the fill that reduced the position
+    pub realized_pnl: f64,
+    /// Signed position quantity after the fill
+    pub position_after: f64,
+    /// Fee charged in the quote asset
+    #[serde(default)]
+    pub fee: f64,
+    /// Unix timestamp in milliseconds
+    pub timestamp: i64,
+}
+
+impl JournalEntry {
+    pub fn notional(&self) -> f64 {
+        self.quantity * self.price
+    }
+}
+
+/// Paper account totals, sent on connect, after every fill and with every
+/// equity sample
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct AccountSummary {
+    /// Cash plus positions at the last trade price, in the quote asset
+    pub equity: f64,
+    /// PnL realized since `day_start`, before fees
+    pub realized_pnl_today: f64,
+    /// Fees paid since the account opened
+    pub fees_paid: f64,
+    /// UTC midnight starting the current day (ms)
+    pub day_start: i64,
+    /// Unix timestamp in milliseconds
+    pub timestamp: i64,
+}
+
+/// Full paper account state (sent on connect and after resets)
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct PortfolioSnapshot {
+    pub balances: Vec<Balance>,
+    pub positions: Vec<Position>,
+    pub orders: Vec<PaperOrder>,
+    pub equity: Vec<EquitySample>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_position_pnl() {
+        let short = Position {
+            symbol: Symbol::default(),
+            quantity: -2.0,
+            entry_price: Price::new(100.0),
+            mark_price: Price::new(90.0),
+            realized_pnl: 0.0,
+        };
+
+        assert_eq!(short.side(), Some(TradeSide::Sell));
+        assert_eq!(short.notional(), 180.0);
+        assert_eq!(short.unrealized_pnl(), 20.0);
+        assert_eq!(short.unrealized_pnl_percent(), 10.0);
+    }
+
+    #[test]
+    fn test_order_remaining() {
+        let order = PaperOrder {
+            id: "1".into(),
+            symbol: Symbol::default(),
+            side: TradeSide::Buy,
+            order_type: OrderType::Limit,
+            price: Some(Price::new(100.0)),
+            stop_price: None,
+            quantity: Quantity::new(2.0),
+            filled: Quantity::new(0.5),
+            status: OrderStatus::PartiallyFilled,
+            created_at: 0,
+            fill_price: None,
+            filled_at: None,
+        };
+
+        assert_eq!(order.remaining(), 1.5);
+        assert_eq!(order.fill_percent(), 25.0);
+        assert!(order.status.is_open());
+        assert_eq!(order.working_price(), Some(100.0));
+    }
+
+    #[test]
+    fn test_order_request_validation() {
+        let info = SymbolInfo::new(Symbol::default(), 0.5, 0.01);
+        let mut order = OrderRequest {
+            client_id: "c1".into(),
+            symbol: Symbol::default(),
+            side: TradeSide::Buy,
+            order_type: OrderType::Limit,
+            price: Some(100.5),
+            stop_price: None,
+            quantity: 0.25,
+        };
+        assert_eq!(order.validate(&info), Ok(()));
+
+        order.price = Some(100.2);
+        assert!(matches!(order.validate(&info), Err(OrderRejection::OffTickSize { .. })));
+
+        order.order_type = OrderType::Stop;
+        assert_eq!(order.validate(&info), Err(OrderRejection::MissingPrice));
+
+        order.order_type = OrderType::Market;
+        order.quantity = 0.005;
+        assert!(matches!(order.validate(&info), Err(OrderRejection::BelowMinQuantity { .. })));
+    }
+
+    #[test]
+    fn test_max_drawdown() {
+        let curve = |values: &[f64]| -> Vec<EquitySample> {
+            values
+                .iter()
+                .enumerate()
+                .map(|(i, &equity)| EquitySample { timestamp: i as i64, equity })
+                .collect()
+        };
+        assert_eq!(max_drawdown(&curve(&[100.0, 101.0, 102.0])), None);
+        assert_eq!(max_drawdown(&[]), None);
+
+        // 110 → 99 (10%) beats the later 120 → 110 (8.3%)
+        let dd = max_drawdown(&curve(&[100.0, 110.0, 104.0, 99.0, 120.0, 110.0])).unwrap();
+        assert_eq!((dd.peak.timestamp, dd.trough.timestamp), (1, 3));
+        assert_eq!(dd.amount(), 11.0);
+        assert!((dd.fraction() - 0.1).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_order_balance_check() {
+        let order = OrderRequest {
+            client_id: "c1".into(),
+            symbol: Symbol::default(),
+            side: TradeSide::Buy,
+            order_type: OrderType::Market,
+            price: None,
+            stop_price: None,
+            quantity: 2.0,
+        };
+
+        assert_eq!(order.notional(100.0), 200.0);
+        assert!(order.check_balance(100.0, 250.0).is_ok());
+        assert!(matches!(
+            order.check_balance(100.0, 150.0),
+            Err(OrderRejection::InsufficientBalance { .. })
+        ));
+    }
+}
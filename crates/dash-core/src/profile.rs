@@ -0,0 +1,200 @@
+//! Volume-at-price histograms (volume profile) built from trades
+
+use crate::{Trade, TradeSide};
+
+/// Share of volume the value area covers around the point of control
+pub const VALUE_AREA_SHARE: f64 = 0.70;
+
+/// Trades a profile is built from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ProfileRange {
+    /// Since the start of the current UTC day
+    #[default]
+    Session,
+    /// Within the candles currently on the chart
+    Visible,
+}
+
+impl ProfileRange {
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Session => "Session",
+            Self::Visible => "Visible",
+        }
+    }
+
+    pub fn all() -> &'static [Self] {
+        &[Self::Session, Self::Visible]
+    }
+}
+
+/// Start (ms) of the UTC day containing `timestamp_ms`
+pub fn utc_day_start(timestamp_ms: i64) -> i64 {
+    const DAY_MS: i64 = 86_400_000;
+    timestamp_ms - timestamp_ms.rem_euclid(DAY_MS)
+}
+
+/// Volume traded inside one price bucket
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct ProfileBin {
+    pub low: f64,
+    pub high: f64,
+    pub buy_volume: f64,
+    pub sell_volume: f64,
+}
+
+impl ProfileBin {
+    pub fn volume(&self) -> f64 {
+        self.buy_volume + self.sell_volume
+    }
+}
+
+/// Volume-at-price histogram with point of control and value area
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProfileHistogram {
+    /// Bins in ascending price order
+    pub bins: Vec<ProfileBin>,
+    /// Index of the bin with the most volume (point of control)
+    pub poc: usize,
+    /// Inclusive bin range holding [`VALUE_AREA_SHARE`] of the volume
+    pub value_area: (usize, usize),
+}
+
+impl ProfileHistogram {
+    /// Bucket trades into `bin_count` equal price bins (`None` without trades)
+    pub fn from_trades<'a>(trades: impl IntoIterator<Item = &'a Trade>, bin_count: usize) -> Option<Self> {
+        let prints: Vec<(f64, f64, TradeSide)> = trades
+            .into_iter()
+            .map(|t| (t.price.as_f64(), t.quantity.as_f64(), t.side))
+            .collect();
+        if prints.is_empty() {
+            return None;
+        }
+
+        let (low, high) = prints.iter().fold((f64::MAX, f64::MIN), |(low, high), &(price, _, _)| {
+            (low.min(price), high.max(price))
+        });
+
+        // A single traded price gets a single bin
+        let bin_count = if high - low > f64::EPSILON { bin_count.max(1) } else { 1 };
+        let size = (high - low) / bin_count as f64;
+        let mut bins: Vec<ProfileBin> = (0..bin_count)
+            .map(|i| ProfileBin {
+                low: low + i as f64 * size,
+                high: low + (i + 1) as f64 * size,
+                ..ProfileBin::default()
+            })
+            .collect();
+
+        for (price, qty, side) in prints {
+            let index = if size > 0.0 {
+                (((price - low) / size) as usize).min(bin_count - 1)
+            } else {
+                0
+            };
+            match side {
+                TradeSide::Buy => bins[index].buy_volume += qty,
+                TradeSide::Sell => bins[index].sell_volume += qty,
+            }
+        }
+
+        let poc = bins
+            .iter()
+            .enumerate()
+            .max_by(|a, b| a.1.volume().total_cmp(&b.1.volume()))
+            .map_or(0, |(i, _)| i);
+        let value_area = value_area(&bins, poc);
+
+        Some(Self { bins, poc, value_area })
+    }
+
+    pub fn max_volume(&self) -> f64 {
+        self.bins.iter().map(ProfileBin::volume).fold(0.0, f64::max)
+    }
+
+    /// Price at the middle of the point-of-control bin
+    pub fn poc_price(&self) -> Option<f64> {
+        self.bins.get(self.poc).map(|b| (b.low + b.high) / 2.0)
+    }
+
+    /// Value area low/high prices
+    pub fn value_area_prices(&self) -> Option<(f64, f64)> {
+        let (low, high) = self.value_area;
+        Some((self.bins.get(low)?.low, self.bins.get(high)?.high))
+    }
+
+    pub fn in_value_area(&self, index: usize) -> bool {
+        (self.value_area.0..=self.value_area.1).contains(&index)
+    }
+}
+
+/// Grow outward from the POC, taking the heavier neighbour each step, until
+/// the value-area share of volume is covered
+fn value_area(bins: &[ProfileBin], poc: usize) -> (usize, usize) {
+    let total: f64 = bins.iter().map(ProfileBin::volume).sum();
+    let target = total * VALUE_AREA_SHARE;
+    let (mut low, mut high) = (poc, poc);
+    let mut covered = bins.get(poc).map_or(0.0, ProfileBin::volume);
+
+    while covered < target {
+        let below = low.checked_sub(1).map(|i| bins[i].volume());
+        let above = bins.get(high + 1).map(ProfileBin::volume);
+        match (below, above) {
+            (None, None) => break,
+            (Some(b), Some(a)) if b > a => {
+                low -= 1;
+                covered += b;
+            }
+            (_, Some(a)) => {
+                high += 1;
+                covered += a;
+            }
+            (Some(b), None) => {
+                low -= 1;
+                covered += b;
+            }
+        }
+    }
+    (low, high)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Symbol;
+
+    fn trade(price: f64, qty: f64, side: TradeSide) -> Trade {
+        Trade::new(Symbol::new("BTC-USD"), price, qty, side)
+    }
+
+    #[test]
+    fn test_profile_poc_and_value_area() {
+        let trades = vec![
+            trade(100.0, 1.0, TradeSide::Buy),
+            trade(101.0, 1.0, TradeSide::Sell),
+            trade(102.0, 6.0, TradeSide::Buy),
+            trade(102.5, 2.0, TradeSide::Sell),
+            trade(104.0, 1.0, TradeSide::Buy),
+        ];
+        let profile = ProfileHistogram::from_trades(&trades, 4).unwrap();
+
+        assert_eq!(profile.bins.len(), 4);
+        assert_eq!(profile.poc, 2);
+        assert_eq!(profile.bins[2].buy_volume, 6.0);
+        assert_eq!(profile.bins[2].sell_volume, 2.0);
+        // 8 of 11 lots sit in the POC bin, already past 70%
+        assert_eq!(profile.value_area, (2, 2));
+        // Top of the range lands in the last bin
+        assert_eq!(profile.bins[3].volume(), 1.0);
+
+        assert!(ProfileHistogram::from_trades(&[], 4).is_none());
+        let flat = ProfileHistogram::from_trades(&trades[..1], 4).unwrap();
+        assert_eq!(flat.bins.len(), 1);
+    }
+
+    #[test]
+    fn test_utc_day_start() {
+        assert_eq!(utc_day_start(86_400_000 + 5_000), 86_400_000);
+        assert_eq!(utc_day_start(-1), -86_400_000);
+    }
+}
@@ -1,10 +1,11 @@
 //! Real-time ticker data types
 
-use crate::{colors, Price, Quantity, Symbol};
+use crate::{colors::{self, Palette}, OrderBookSnapshot, Price, Quantity, Symbol};
 use serde::{Deserialize, Serialize};
 
 /// Real-time market ticker
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct Ticker {
     pub symbol: Symbol,
     /// Last traded price
@@ -90,12 +91,17 @@ impl Ticker {
         self.change_24h < 0.0
     }
 
-    /// Direction color
-    pub fn color(&self) -> &'static str {
+    /// Direction color in `palette` (for canvas painting)
+    pub fn color(&self, palette: &Palette) -> &'static str {
+        palette.direction(self.is_up())
+    }
+
+    /// Themed direction color (CSS custom property reference)
+    pub fn css_color(&self) -> &'static str {
         if self.is_up() {
-            colors::BULL
+            colors::css::BULL
         } else {
-            colors::BEAR
+            colors::css::BEAR
         }
     }
 
@@ -172,10 +178,19 @@ impl Ticker {
 
         self.timestamp = chrono::Utc::now().timestamp_millis();
     }
+
+    /// Take the touch from a BBO update
+    pub fn apply_bbo(&mut self, bbo: &Bbo) {
+        self.bid_price = bbo.bid;
+        self.bid_qty = bbo.bid_size;
+        self.ask_price = bbo.ask;
+        self.ask_qty = bbo.ask_size;
+    }
 }
 
 /// Mini ticker for compact display
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct MiniTicker {
     pub symbol: Symbol,
     pub last_price: f64,
@@ -187,12 +202,8 @@ impl MiniTicker {
         self.change_percent_24h >= 0.0
     }
 
-    pub fn color(&self) -> &'static str {
-        if self.is_up() {
-            colors::BULL
-        } else {
-            colors::BEAR
-        }
+    pub fn color(&self, palette: &Palette) -> &'static str {
+        palette.direction(self.is_up())
     }
 }
 
@@ -238,9 +249,333 @@ impl From<&Ticker> for TickerStats {
     }
 }
 
+/// Best bid and offer, streamed more often than the full book for clients
+/// that only need the touch (watchlists, mini tickers)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct Bbo {
+    pub symbol: Symbol,
+    pub bid: Price,
+    pub bid_size: Quantity,
+    pub ask: Price,
+    pub ask_size: Quantity,
+    /// Timestamp in milliseconds
+    pub timestamp: i64,
+}
+
+impl Bbo {
+    /// Touch of `book` (`None` while either side is empty)
+    pub fn from_book(book: &OrderBookSnapshot) -> Option<Self> {
+        let (bid, ask) = (book.bids.first()?, book.asks.first()?);
+        Some(Self {
+            symbol: book.symbol.clone(),
+            bid: bid.price,
+            bid_size: bid.quantity,
+            ask: ask.price,
+            ask_size: ask.quantity,
+            timestamp: book.timestamp,
+        })
+    }
+
+    pub fn mid(&self) -> f64 {
+        (self.bid.as_f64() + self.ask.as_f64()) / 2.0
+    }
+
+    pub fn spread(&self) -> f64 {
+        self.ask.as_f64() - self.bid.as_f64()
+    }
+
+    /// Same prices and sizes, whatever the timestamps
+    pub fn same_touch(&self, other: &Self) -> bool {
+        self.bid == other.bid
+            && self.bid_size == other.bid_size
+            && self.ask == other.ask
+            && self.ask_size == other.ask_size
+    }
+}
+
+// ============================================================================
+// SESSION ANALYTICS
+// ============================================================================
+
+/// Where a trading day starts, for the session stats
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum SessionAnchor {
+    #[default]
+    UtcMidnight,
+    /// Futures-style day rolling over at 22:00 UTC (17:00 New York)
+    ExchangeDay,
+    /// Day starting `start_minute` minutes after UTC midnight
+    Custom { start_minute: u16 },
+}
+
+impl SessionAnchor {
+    const DAY_MS: i64 = 86_400_000;
+
+    pub fn presets() -> &'static [Self] {
+        &[Self::UtcMidnight, Self::ExchangeDay]
+    }
+
+    /// Minutes after UTC midnight the session rolls over
+    pub fn start_minute(&self) -> u16 {
+        match self {
+            Self::UtcMidnight => 0,
+            Self::ExchangeDay => 22 * 60,
+            Self::Custom { start_minute } => *start_minute % (24 * 60),
+        }
+    }
+
+    /// Start (ms) of the session containing `timestamp_ms`
+    pub fn session_start(&self, timestamp_ms: i64) -> i64 {
+        let offset = self.start_minute() as i64 * 60_000;
+        timestamp_ms - (timestamp_ms - offset).rem_euclid(Self::DAY_MS)
+    }
+
+    pub fn label(&self) -> String {
+        match self {
+            Self::UtcMidnight => "UTC day".to_string(),
+            Self::ExchangeDay => "Exchange day".to_string(),
+            Self::Custom { .. } => {
+                let minute = self.start_minute();
+                format!("From {:02}:{:02} UTC", minute / 60, minute % 60)
+            }
+        }
+    }
+}
+
+/// Session open, extremes and VWAP from the server analytics channel
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct SessionStats {
+    pub symbol: Symbol,
+    /// Day boundary the session follows
+    #[serde(default)]
+    pub anchor: SessionAnchor,
+    /// Session start in milliseconds
+    pub session_start: i64,
+    pub open: Price,
+    pub high: Price,
+    /// When the session high was set (ms)
+    pub high_time: i64,
+    pub low: Price,
+    /// When the session low was set (ms)
+    pub low_time: i64,
+    /// Volume-weighted average price since the session start
+    pub vwap: f64,
+    /// Volume-weighted variance of price around the VWAP
+    #[serde(default)]
+    pub vwap_variance: f64,
+    /// Base volume since the session start
+    pub volume: f64,
+    /// Timestamp in milliseconds
+    pub timestamp: i64,
+}
+
+impl SessionStats {
+    /// Fresh session opened by a print at `price`
+    pub fn new(symbol: Symbol, anchor: SessionAnchor, price: f64, timestamp: i64) -> Self {
+        Self {
+            symbol,
+            anchor,
+            session_start: anchor.session_start(timestamp),
+            open: Price::new(price),
+            high: Price::new(price),
+            high_time: timestamp,
+            low: Price::new(price),
+            low_time: timestamp,
+            vwap: price,
+            vwap_variance: 0.0,
+            volume: 0.0,
+            timestamp,
+        }
+    }
+
+    /// Fold a trade into the session
+    pub fn update(&mut self, price: f64, quantity: f64, timestamp: i64) {
+        if price > self.high.as_f64() {
+            self.high = Price::new(price);
+            self.high_time = timestamp;
+        }
+        if price < self.low.as_f64() {
+            self.low = Price::new(price);
+            self.low_time = timestamp;
+        }
+        let volume = self.volume + quantity;
+        if volume > 0.0 {
+            let vwap = (self.vwap * self.volume + price * quantity) / volume;
+            // Weighted Welford step, so the bands need no per-trade history
+            self.vwap_variance =
+                (self.vwap_variance * self.volume + quantity * (price - self.vwap) * (price - vwap)) / volume;
+            self.vwap = vwap;
+        }
+        self.volume = volume;
+        self.timestamp = timestamp;
+    }
+
+    /// Position of `price` within the session range (0.0 at low, 1.0 at high)
+    pub fn range_position(&self, price: f64) -> f64 {
+        let range = self.high.as_f64() - self.low.as_f64();
+        if range == 0.0 {
+            0.5
+        } else {
+            ((price - self.low.as_f64()) / range).clamp(0.0, 1.0)
+        }
+    }
+
+    /// Standard deviation of price around the VWAP (width of one band)
+    pub fn vwap_std_dev(&self) -> f64 {
+        self.vwap_variance.max(0.0).sqrt()
+    }
+
+    /// Distance of `price` from the session VWAP in percent
+    pub fn vwap_distance_percent(&self, price: f64) -> f64 {
+        if self.vwap == 0.0 {
+            0.0
+        } else {
+            (price - self.vwap) / self.vwap * 100.0
+        }
+    }
+}
+
+/// VPIN at or above which flow reads as one-sided
+pub const TOXICITY_ELEVATED: f64 = 0.3;
+/// VPIN at or above which flow reads as toxic
+pub const TOXICITY_HIGH: f64 = 0.5;
+
+/// VPIN-style order-flow toxicity from the server analytics channel: the
+/// mean buy/sell imbalance over equal-volume buckets
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct FlowToxicity {
+    pub symbol: Symbol,
+    /// Mean |buy − sell| / bucket volume (0.0 balanced, 1.0 one-sided)
+    pub vpin: f64,
+    /// Buckets averaged so far
+    pub buckets: usize,
+    /// Base volume per bucket
+    pub bucket_volume: f64,
+    /// Timestamp in milliseconds
+    pub timestamp: i64,
+}
+
+impl FlowToxicity {
+    /// Themed color for the gauge (CSS custom property reference)
+    pub fn css_color(&self) -> &'static str {
+        if self.vpin >= TOXICITY_HIGH {
+            colors::css::BEAR
+        } else if self.vpin >= TOXICITY_ELEVATED {
+            colors::css::WARN
+        } else {
+            colors::css::BULL
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        if self.vpin >= TOXICITY_HIGH {
+            "Toxic"
+        } else if self.vpin >= TOXICITY_ELEVATED {
+            "Elevated"
+        } else {
+            "Balanced"
+        }
+    }
+}
+
+/// Format a millisecond timestamp as HH:MM:SS (UTC)
+pub fn format_time_ms(timestamp_ms: i64) -> String {
+    chrono::DateTime::from_timestamp_millis(timestamp_ms)
+        .map(|t| t.format("%H:%M:%S").to_string())
+        .unwrap_or_default()
+}
+
+// ============================================================================
+// DERIVATIVES
+// ============================================================================
+
+/// Funding period of the perpetual contracts (every 8h, aligned to UTC)
+pub const FUNDING_INTERVAL_MS: i64 = 8 * 3_600_000;
+
+/// Next funding time (ms) strictly after `timestamp_ms`
+pub fn next_funding_time(timestamp_ms: i64) -> i64 {
+    timestamp_ms - timestamp_ms.rem_euclid(FUNDING_INTERVAL_MS) + FUNDING_INTERVAL_MS
+}
+
+/// Perpetual-swap stats: funding, open interest and mark/index prices
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct DerivativesTicker {
+    pub symbol: Symbol,
+    /// Price used for margining and liquidations
+    pub mark_price: Price,
+    /// Spot index the contract tracks
+    pub index_price: Price,
+    /// Funding rate for the current period (0.0001 = 0.01%)
+    pub funding_rate: f64,
+    /// Next funding time in milliseconds
+    pub next_funding_time: i64,
+    /// Open interest in base currency
+    pub open_interest: Quantity,
+    /// Open interest change over 24h in base currency
+    pub open_interest_change_24h: f64,
+    /// Timestamp in milliseconds
+    pub timestamp: i64,
+}
+
+impl DerivativesTicker {
+    /// Mark minus index
+    pub fn basis(&self) -> f64 {
+        self.mark_price.as_f64() - self.index_price.as_f64()
+    }
+
+    /// Basis as percentage of the index
+    pub fn basis_percent(&self) -> f64 {
+        let index = self.index_price.as_f64();
+        if index == 0.0 {
+            0.0
+        } else {
+            self.basis() / index * 100.0
+        }
+    }
+
+    /// Funding rate annualized, in percent
+    pub fn funding_annualized(&self) -> f64 {
+        let periods_per_year = 365.0 * 86_400_000.0 / FUNDING_INTERVAL_MS as f64;
+        self.funding_rate * periods_per_year * 100.0
+    }
+
+    /// Longs pay shorts?
+    pub fn is_funding_positive(&self) -> bool {
+        self.funding_rate >= 0.0
+    }
+
+    /// Format funding rate as a signed percentage
+    pub fn funding_str(&self) -> String {
+        let sign = if self.funding_rate >= 0.0 { "+" } else { "" };
+        format!("{}{:.4}%", sign, self.funding_rate * 100.0)
+    }
+
+    /// Open interest change over 24h as percentage of the earlier value
+    pub fn open_interest_change_percent(&self) -> f64 {
+        let previous = self.open_interest.as_f64() - self.open_interest_change_24h;
+        if previous <= 0.0 {
+            0.0
+        } else {
+            self.open_interest_change_24h / previous * 100.0
+        }
+    }
+
+    /// Milliseconds until the next funding at `now_ms`
+    pub fn funding_remaining_ms(&self, now_ms: i64) -> i64 {
+        (self.next_funding_time - now_ms).max(0)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::OrderBookLevel;
 
     #[test]
     fn test_ticker_spread() {
@@ -274,4 +609,97 @@ mod tests {
         assert_eq!(ticker.change_24h, 1000.0);
         assert!((ticker.change_percent_24h - 2.0).abs() < 0.01);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_bbo_from_book() {
+        let mut book = OrderBookSnapshot::new(Symbol::new("BTC-USD"));
+        assert!(Bbo::from_book(&book).is_none());
+
+        book.bids = vec![OrderBookLevel::new(99.0, 2.0, 1), OrderBookLevel::new(98.0, 5.0, 1)];
+        book.asks = vec![OrderBookLevel::new(101.0, 1.0, 1)];
+        let bbo = Bbo::from_book(&book).unwrap();
+        assert_eq!(bbo.mid(), 100.0);
+        assert_eq!(bbo.spread(), 2.0);
+
+        let later = Bbo { timestamp: bbo.timestamp + 25, ..bbo.clone() };
+        assert!(bbo.same_touch(&later));
+        assert!(!bbo.same_touch(&Bbo { ask_size: Quantity::new(3.0), ..later }));
+
+        let mut ticker = Ticker::new(Symbol::new("BTC-USD"), 100.0);
+        ticker.apply_bbo(&bbo);
+        assert_eq!(ticker.spread(), 2.0);
+        assert_eq!(ticker.bid_qty.as_f64(), 2.0);
+    }
+
+    #[test]
+    fn test_derivatives_ticker() {
+        let derivatives = DerivativesTicker {
+            symbol: Symbol::new("BTC-USD"),
+            mark_price: Price::new(50050.0),
+            index_price: Price::new(50000.0),
+            funding_rate: 0.0001,
+            next_funding_time: next_funding_time(1_000),
+            open_interest: Quantity::new(1100.0),
+            open_interest_change_24h: 100.0,
+            timestamp: 1_000,
+        };
+
+        assert_eq!(derivatives.basis(), 50.0);
+        assert!((derivatives.basis_percent() - 0.1).abs() < 1e-9);
+        assert!((derivatives.funding_annualized() - 10.95).abs() < 1e-9);
+        assert_eq!(derivatives.funding_str(), "+0.0100%");
+        assert!((derivatives.open_interest_change_percent() - 10.0).abs() < 1e-9);
+        assert_eq!(derivatives.next_funding_time, FUNDING_INTERVAL_MS);
+        assert_eq!(derivatives.funding_remaining_ms(FUNDING_INTERVAL_MS + 1), 0);
+        // On a boundary the next funding is a full period away
+        assert_eq!(next_funding_time(FUNDING_INTERVAL_MS), 2 * FUNDING_INTERVAL_MS);
+    }
+
+    #[test]
+    fn test_session_stats() {
+        let mut session = SessionStats::new(Symbol::new("BTC-USD"), SessionAnchor::UtcMidnight, 100.0, 1_000);
+        session.update(100.0, 1.0, 1_000);
+        session.update(110.0, 1.0, 2_000);
+        session.update(90.0, 2.0, 3_000);
+
+        assert_eq!(session.open.as_f64(), 100.0);
+        assert_eq!((session.high.as_f64(), session.high_time), (110.0, 2_000));
+        assert_eq!((session.low.as_f64(), session.low_time), (90.0, 3_000));
+        assert!((session.vwap - 97.5).abs() < 1e-9);
+        // (1 × 2.5² + 1 × 12.5² + 2 × 7.5²) / 4
+        assert!((session.vwap_variance - 68.75).abs() < 1e-9);
+        assert_eq!(session.range_position(100.0), 0.5);
+        assert!((session.vwap_distance_percent(97.5)).abs() < 1e-9);
+        assert_eq!(format_time_ms(3_723_000), "01:02:03");
+    }
+
+    #[test]
+    fn test_flow_toxicity_levels() {
+        let mut flow = FlowToxicity {
+            symbol: Symbol::new("BTC-USD"),
+            vpin: 0.1,
+            buckets: 50,
+            bucket_volume: 10.0,
+            timestamp: 0,
+        };
+        assert_eq!((flow.label(), flow.css_color()), ("Balanced", colors::css::BULL));
+        flow.vpin = TOXICITY_ELEVATED;
+        assert_eq!(flow.label(), "Elevated");
+        flow.vpin = 0.8;
+        assert_eq!((flow.label(), flow.css_color()), ("Toxic", colors::css::BEAR));
+    }
+
+    #[test]
+    fn test_session_anchor_start() {
+        let hour = 3_600_000;
+        // 23:00 UTC on day one
+        let late = 86_400_000 + 23 * hour;
+        assert_eq!(SessionAnchor::UtcMidnight.session_start(late), 86_400_000);
+        assert_eq!(SessionAnchor::ExchangeDay.session_start(late), 86_400_000 + 22 * hour);
+        assert_eq!(SessionAnchor::ExchangeDay.session_start(86_400_000 + hour), 22 * hour);
+
+        let tokyo = SessionAnchor::Custom { start_minute: 30 };
+        assert_eq!(tokyo.session_start(86_400_000), 30 * 60_000);
+        assert_eq!(tokyo.label(), "From 00:30 UTC");
+    }
+}
@@ -1,8 +1,9 @@
 //! Trade execution types with Strategy pattern for classification
 
-use crate::{colors, Price, Quantity, Symbol};
+use crate::{colors::{self, Palette}, Price, Quantity, Symbol};
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
+use std::collections::{HashSet, VecDeque};
 use uuid::Uuid;
 
 // ============================================================================
@@ -80,9 +81,11 @@ impl TradeClassifier for ValueThresholdClassifier {
 // ============================================================================
 
 /// Direction of a trade
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "lowercase")]
 pub enum TradeSide {
+    #[default]
     Buy,
     Sell,
 }
@@ -103,10 +106,16 @@ impl TradeSide {
         }
     }
 
-    pub fn color(&self) -> &'static str {
+    /// Side color in `palette` (for canvas painting)
+    pub fn color(&self, palette: &Palette) -> &'static str {
+        palette.direction(*self == Self::Buy)
+    }
+
+    /// Themed color (CSS custom property reference)
+    pub fn css_color(&self) -> &'static str {
         match self {
-            Self::Buy => colors::BULL,
-            Self::Sell => colors::BEAR,
+            Self::Buy => colors::css::BULL,
+            Self::Sell => colors::css::BEAR,
         }
     }
 
@@ -132,14 +141,9 @@ impl TradeSide {
     }
 }
 
-impl Default for TradeSide {
-    fn default() -> Self {
-        Self::Buy
-    }
-}
-
 /// Individual trade execution
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct Trade {
     pub id: String,
     pub symbol: Symbol,
@@ -307,8 +311,213 @@ impl TradeAggregation {
     }
 }
 
+// ============================================================================
+// TAPE GROUPING
+// ============================================================================
+
+/// Grouping windows offered for the trade tape (ms)
+pub const TAPE_GROUP_WINDOWS: [i64; 3] = [250, 1_000, 5_000];
+
+/// Consecutive trades at one price and side, merged into a single tape row
+#[derive(Debug, Clone, PartialEq)]
+pub struct TradeGroup {
+    /// Newest trade of the group, with the summed quantity
+    pub trade: Trade,
+    /// ID of the oldest trade; stays put while the group grows
+    pub first_id: String,
+    pub count: usize,
+}
+
+impl TradeGroup {
+    pub fn single(trade: Trade) -> Self {
+        Self {
+            first_id: trade.id.clone(),
+            trade,
+            count: 1,
+        }
+    }
+
+    /// Row key: changes whenever the group takes another trade
+    pub fn key(&self) -> (String, usize) {
+        (self.first_id.clone(), self.count)
+    }
+}
+
+/// Merge runs of same-price, same-side trades whose span stays within
+/// `window_ms`. Takes and returns newest-first; groups are anchored at their
+/// oldest trade so history doesn't regroup as new trades arrive.
+pub fn group_trades(trades: &[Trade], window_ms: i64) -> Vec<TradeGroup> {
+    let mut groups: Vec<TradeGroup> = Vec::new();
+    let mut anchor: Option<DateTime<Utc>> = None;
+    for trade in trades.iter().rev() {
+        if let (Some(group), Some(start)) = (groups.last_mut(), anchor)
+            && group.trade.price == trade.price
+            && group.trade.side == trade.side
+            && (trade.timestamp - start).num_milliseconds() <= window_ms
+        {
+            let quantity = group.trade.quantity.as_f64() + trade.quantity.as_f64();
+            group.trade = Trade { quantity: Quantity::new(quantity), ..trade.clone() };
+            group.count += 1;
+            continue;
+        }
+        anchor = Some(trade.timestamp);
+        groups.push(TradeGroup::single(trade.clone()));
+    }
+    groups.reverse();
+    groups
+}
+
+/// Trade ids remembered by a default [`TradeDedup`]
+pub const TRADE_DEDUP_WINDOW: usize = 4096;
+
+/// Ids of the most recent trades, for dropping prints a feed delivers twice
+/// (typically the overlap replayed after a reconnect)
+#[derive(Debug, Clone)]
+pub struct TradeDedup {
+    capacity: usize,
+    ids: HashSet<String>,
+    /// Oldest first, for eviction
+    order: VecDeque<String>,
+}
+
+impl Default for TradeDedup {
+    fn default() -> Self {
+        Self::new(TRADE_DEDUP_WINDOW)
+    }
+}
+
+impl TradeDedup {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity: capacity.max(1),
+            ids: HashSet::with_capacity(capacity),
+            order: VecDeque::with_capacity(capacity),
+        }
+    }
+
+    /// Remember `trade`; false if its id was already seen. Trades without
+    /// an id can't be told apart and always pass.
+    pub fn first_sighting(&mut self, trade: &Trade) -> bool {
+        if trade.id.is_empty() {
+            return true;
+        }
+        if !self.ids.insert(trade.id.clone()) {
+            return false;
+        }
+        self.order.push_back(trade.id.clone());
+        if self.order.len() > self.capacity
+            && let Some(oldest) = self.order.pop_front()
+        {
+            self.ids.remove(&oldest);
+        }
+        true
+    }
+
+    pub fn len(&self) -> usize {
+        self.order.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.order.is_empty()
+    }
+}
+
+// ============================================================================
+// LIQUIDATIONS
+// ============================================================================
+
+/// Window of the longs-vs-shorts liquidation summary (1h)
+pub const LIQUIDATION_WINDOW_MS: i64 = 3_600_000;
+
+/// Forced close of a leveraged position
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct Liquidation {
+    pub id: String,
+    pub symbol: Symbol,
+    /// Side of the forced order: `Sell` closes a long, `Buy` closes a short
+    pub side: TradeSide,
+    pub price: Price,
+    pub quantity: Quantity,
+    pub timestamp: DateTime<Utc>,
+}
+
+impl Liquidation {
+    /// Create new liquidation with auto-generated ID
+    pub fn new(symbol: Symbol, price: f64, quantity: f64, side: TradeSide) -> Self {
+        Self {
+            id: Uuid::new_v4().to_string(),
+            symbol,
+            price: Price::new(price),
+            quantity: Quantity::new(quantity),
+            side,
+            timestamp: Utc::now(),
+        }
+    }
+
+    /// Was a long position liquidated?
+    pub fn is_long(&self) -> bool {
+        self.side.is_sell()
+    }
+
+    /// "LONG" / "SHORT" for the liquidated position
+    pub fn position_label(&self) -> &'static str {
+        if self.is_long() { "LONG" } else { "SHORT" }
+    }
+
+    /// Notional value (price × quantity)
+    pub fn value(&self) -> f64 {
+        self.price.as_f64() * self.quantity.as_f64()
+    }
+
+    /// Format timestamp for display (HH:MM:SS)
+    pub fn time_str(&self) -> String {
+        self.timestamp.format("%H:%M:%S").to_string()
+    }
+}
+
+/// Liquidated notional split by position side
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct LiquidationSummary {
+    pub long_value: f64,
+    pub short_value: f64,
+    pub long_count: usize,
+    pub short_count: usize,
+}
+
+impl LiquidationSummary {
+    /// Sum liquidations (newest first) at or after `since_ms`
+    pub fn since<'a>(liquidations: impl IntoIterator<Item = &'a Liquidation>, since_ms: i64) -> Self {
+        let mut summary = Self::default();
+        for liq in liquidations
+            .into_iter()
+            .take_while(|l| l.timestamp.timestamp_millis() >= since_ms)
+        {
+            if liq.is_long() {
+                summary.long_value += liq.value();
+                summary.long_count += 1;
+            } else {
+                summary.short_value += liq.value();
+                summary.short_count += 1;
+            }
+        }
+        summary
+    }
+
+    pub fn total_value(&self) -> f64 {
+        self.long_value + self.short_value
+    }
+
+    /// Share of liquidated notional that was longs (0.5 when empty)
+    pub fn long_share(&self) -> f64 {
+        let total = self.total_value();
+        if total == 0.0 { 0.5 } else { self.long_value / total }
+    }
+}
+
 /// Batch of trades for efficient transmission
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct TradeBatch {
     pub symbol: Symbol,
     pub trades: Vec<Trade>,
@@ -361,6 +570,55 @@ mod tests {
         assert_eq!(trade.value(), 25000.0);
     }
 
+    #[test]
+    fn test_group_trades() {
+        let btc = Symbol::new("BTC-USD");
+        let at = |price: f64, qty: f64, side: TradeSide, ms: i64| Trade {
+            timestamp: DateTime::from_timestamp_millis(ms).unwrap(),
+            ..Trade::new(btc.clone(), price, qty, side)
+        };
+        // Newest first, as the tape stores them
+        let trades = vec![
+            at(101.0, 1.0, TradeSide::Buy, 1_900),
+            at(100.0, 0.5, TradeSide::Buy, 1_500),
+            at(100.0, 0.25, TradeSide::Buy, 1_200),
+            at(100.0, 0.25, TradeSide::Sell, 1_100),
+            at(100.0, 2.0, TradeSide::Buy, 1_000),
+            at(100.0, 1.0, TradeSide::Buy, 0),
+        ];
+
+        let groups = group_trades(&trades, 500);
+        let summary: Vec<_> = groups.iter().map(|g| (g.count, g.trade.quantity.as_f64())).collect();
+        assert_eq!(summary, vec![(1, 1.0), (2, 0.75), (1, 0.25), (1, 2.0), (1, 1.0)]);
+        // The merged row shows the newest trade of the run
+        assert_eq!(groups[1].trade.id, trades[1].id);
+        assert_eq!(groups[1].first_id, trades[2].id);
+
+        assert_eq!(group_trades(&trades, 0).len(), trades.len());
+    }
+
+    #[test]
+    fn test_trade_dedup() {
+        let mut dedup = TradeDedup::new(2);
+        let a = Trade::new(Symbol::default(), 100.0, 1.0, TradeSide::Buy);
+        let b = Trade::new(Symbol::default(), 100.0, 1.0, TradeSide::Buy);
+        let c = Trade::new(Symbol::default(), 100.0, 1.0, TradeSide::Buy);
+        assert_ne!(a.id, b.id);
+
+        assert!(dedup.first_sighting(&a));
+        assert!(!dedup.first_sighting(&a.clone()));
+        assert!(dedup.first_sighting(&b));
+        // `a` is evicted once the window is full
+        assert!(dedup.first_sighting(&c));
+        assert_eq!(dedup.len(), 2);
+        assert!(dedup.first_sighting(&a));
+        assert!(!dedup.first_sighting(&c));
+
+        let anonymous = Trade { id: String::new(), ..a };
+        assert!(dedup.first_sighting(&anonymous));
+        assert!(dedup.first_sighting(&anonymous));
+    }
+
     #[test]
     fn test_trade_classification() {
         let classifier = ValueThresholdClassifier::default();
@@ -385,4 +643,25 @@ mod tests {
         assert_eq!(agg.sell_count, 1);
         assert_eq!(agg.total_volume, 4.0);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_liquidation_summary() {
+        let mut old = Liquidation::new(Symbol::default(), 100.0, 50.0, TradeSide::Sell);
+        old.timestamp -= chrono::Duration::milliseconds(LIQUIDATION_WINDOW_MS + 1);
+        let liqs = vec![
+            Liquidation::new(Symbol::default(), 100.0, 3.0, TradeSide::Sell),
+            Liquidation::new(Symbol::default(), 100.0, 1.0, TradeSide::Buy),
+            old,
+        ];
+        assert!(liqs[0].is_long());
+
+        let since = Utc::now().timestamp_millis() - LIQUIDATION_WINDOW_MS;
+        let summary = LiquidationSummary::since(&liqs, since);
+
+        assert_eq!(summary.long_count, 1);
+        assert_eq!(summary.short_count, 1);
+        assert_eq!(summary.total_value(), 400.0);
+        assert_eq!(summary.long_share(), 0.75);
+        assert_eq!(LiquidationSummary::default().long_share(), 0.5);
+    }
+}
@@ -0,0 +1,83 @@
+//! Price alerts: one-shot levels that fire when the last price crosses them
+
+use dash_core::Symbol;
+
+/// One-shot alert at a price level
+#[derive(Debug, Clone, PartialEq)]
+pub struct PriceAlert {
+    pub id: u64,
+    pub symbol: Symbol,
+    pub price: f64,
+    /// Fire when the price rises to the level (otherwise when it falls to it)
+    pub above: bool,
+}
+
+impl PriceAlert {
+    /// Alert at `price`; the direction comes from where `reference` sits
+    pub fn new(id: u64, symbol: Symbol, price: f64, reference: f64) -> Self {
+        Self {
+            id,
+            symbol,
+            price,
+            above: price >= reference,
+        }
+    }
+
+    pub fn is_triggered(&self, price: f64) -> bool {
+        if self.above {
+            price >= self.price
+        } else {
+            price <= self.price
+        }
+    }
+
+    /// "BTC-USD ≥ 95000.00"
+    pub fn label(&self) -> String {
+        let op = if self.above { "≥" } else { "≤" };
+        format!("{} {} {:.2}", self.symbol, op, self.price)
+    }
+}
+
+/// Fired alerts kept for marking on the chart
+pub const MAX_FIRED_ALERTS: usize = 50;
+
+/// An alert that fired, with when and at what price
+#[derive(Debug, Clone, PartialEq)]
+pub struct FiredAlert {
+    pub alert: PriceAlert,
+    /// Time of the tick that crossed the level (ms)
+    pub time: i64,
+    /// Last price of that tick
+    pub price: f64,
+}
+
+/// Remove and return the alerts for `symbol` that `price` triggers
+pub fn take_triggered(alerts: &mut Vec<PriceAlert>, symbol: &Symbol, price: f64) -> Vec<PriceAlert> {
+    let (fired, kept) = std::mem::take(alerts)
+        .into_iter()
+        .partition(|a| a.symbol == *symbol && a.is_triggered(price));
+    *alerts = kept;
+    fired
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_take_triggered() {
+        let btc = Symbol::new("BTC-USD");
+        let mut alerts = vec![
+            PriceAlert::new(1, btc.clone(), 105.0, 100.0),
+            PriceAlert::new(2, btc.clone(), 95.0, 100.0),
+            PriceAlert::new(3, Symbol::new("ETH-USD"), 105.0, 100.0),
+        ];
+        assert!(alerts[0].above && !alerts[1].above);
+
+        assert!(take_triggered(&mut alerts, &btc, 101.0).is_empty());
+
+        let fired = take_triggered(&mut alerts, &btc, 106.0);
+        assert_eq!(fired.iter().map(|a| a.id).collect::<Vec<_>>(), vec![1]);
+        assert_eq!(alerts.len(), 2);
+    }
+}
@@ -0,0 +1,130 @@
+//! Second symbol overlaid on the chart for comparison
+//!
+//! Keeps its own base (1m) candle series, fed by the same candle stream as
+//! the charted symbol and resampled to the chart's timeframe on demand.
+
+use crate::market::{backfill_request, resample_tail, upsert_candle, BackfillRequest, BackfillStatus, BASE_INTERVAL};
+use crate::{MAX_BASE_CANDLES, MAX_CANDLES};
+use dash_core::{Candle, CandleHistory, CandleInterval, Symbol};
+use leptos::prelude::*;
+
+/// Comparison symbol and its candles
+#[derive(Clone, Copy)]
+pub struct CompareState {
+    /// Symbol overlaid on the chart (`None` = compare mode off)
+    pub symbol: RwSignal<Option<Symbol>>,
+    /// Its base (1m) candles, oldest first
+    pub base_candles: RwSignal<CandleHistory>,
+    /// Loading its history over REST
+    pub backfill: RwSignal<BackfillStatus>,
+}
+
+impl CompareState {
+    pub fn new() -> Self {
+        Self {
+            symbol: RwSignal::new(None),
+            base_candles: RwSignal::new(CandleHistory::new(Symbol::default(), BASE_INTERVAL)),
+            backfill: RwSignal::new(BackfillStatus::Idle),
+        }
+    }
+
+    /// Overlay another symbol (or none); its candles start over
+    pub fn set_symbol(&self, symbol: Option<Symbol>) {
+        if self.symbol.with_untracked(|s| *s == symbol) {
+            return;
+        }
+        self.base_candles
+            .set(CandleHistory::new(symbol.clone().unwrap_or_default(), BASE_INTERVAL));
+        self.backfill.set(BackfillStatus::Idle);
+        self.symbol.set(symbol);
+    }
+
+    /// Is `symbol` the one being compared?
+    pub fn is_compared(&self, symbol: &Symbol) -> bool {
+        self.symbol.with_untracked(|s| s.as_ref() == Some(symbol))
+    }
+
+    /// Upsert a base candle of the compared symbol (others are ignored)
+    pub fn update_candle(&self, candle: Candle) {
+        if candle.interval != BASE_INTERVAL || !self.is_compared(&candle.symbol) {
+            return;
+        }
+        self.base_candles
+            .update(|history| upsert_candle(&mut history.candles, candle, MAX_BASE_CANDLES));
+    }
+
+    /// Older base candles to fetch so `interval` has a chart's worth
+    pub fn backfill_request(&self, interval: CandleInterval) -> Option<BackfillRequest> {
+        if self.backfill.get_untracked() != BackfillStatus::Idle || self.symbol.with_untracked(Option::is_none) {
+            return None;
+        }
+        self.base_candles
+            .with_untracked(|base| backfill_request(base, interval, MAX_CANDLES))
+    }
+
+    /// Put REST candles in front of the streamed ones; a short answer means
+    /// there is nothing older
+    pub fn prepend_candles(&self, symbol: &Symbol, request: BackfillRequest, mut candles: Vec<Candle>) {
+        if !self.is_compared(symbol) {
+            return;
+        }
+        candles.retain(|c| c.interval == BASE_INTERVAL);
+        candles.sort_by_key(|c| c.timestamp);
+        let exhausted = candles.len() < request.limit;
+
+        self.base_candles.update(|history| {
+            if let Some(oldest) = history.candles.first().map(|c| c.timestamp) {
+                candles.retain(|c| c.timestamp < oldest);
+            }
+            candles.append(&mut history.candles);
+            let excess = candles.len().saturating_sub(MAX_BASE_CANDLES);
+            candles.drain(..excess);
+            history.candles = candles;
+        });
+        self.backfill.set(if exhausted { BackfillStatus::Exhausted } else { BackfillStatus::Idle });
+    }
+
+    /// The compared symbol's candles at `interval` (tracked)
+    pub fn candles(&self, interval: CandleInterval) -> CandleHistory {
+        self.base_candles
+            .with(|base| resample_tail(base, interval, MAX_CANDLES))
+    }
+}
+
+impl Default for CompareState {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn candle(symbol: &Symbol, minute: i64, close: f64) -> Candle {
+        Candle::new(symbol.clone(), BASE_INTERVAL, minute * 60_000, close)
+    }
+
+    #[test]
+    fn test_only_the_compared_symbol_is_kept() {
+        let compare = CompareState::new();
+        let (btc, eth) = (Symbol::new("BTC-USD"), Symbol::new("ETH-USD"));
+
+        compare.update_candle(candle(&eth, 0, 10.0));
+        assert!(compare.base_candles.get_untracked().candles.is_empty());
+
+        compare.set_symbol(Some(eth.clone()));
+        compare.update_candle(candle(&eth, 5, 10.0));
+        compare.update_candle(candle(&btc, 6, 100.0));
+        let request = compare.backfill_request(CandleInterval::M1).unwrap();
+        assert_eq!(request.before, Some(5 * 60_000));
+
+        compare.prepend_candles(&eth, request, vec![candle(&eth, 3, 9.0), candle(&eth, 4, 9.5)]);
+        let closes: Vec<f64> = compare.candles(CandleInterval::M1).candles.iter().map(|c| c.close.as_f64()).collect();
+        assert_eq!(closes, vec![9.0, 9.5, 10.0]);
+        assert_eq!(compare.backfill.get_untracked(), BackfillStatus::Exhausted);
+
+        compare.set_symbol(None);
+        assert!(compare.base_candles.get_untracked().candles.is_empty());
+    }
+}
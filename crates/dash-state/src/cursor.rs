@@ -0,0 +1,117 @@
+//! Hover/selection state shared across panels
+
+use dash_core::{Candle, Trade};
+use leptos::prelude::*;
+
+/// Half-open time span `[start, end)` in milliseconds
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TimeSpan {
+    pub start: i64,
+    pub end: i64,
+}
+
+impl TimeSpan {
+    pub fn new(start: i64, end: i64) -> Self {
+        Self { start, end }
+    }
+
+    /// Period covered by a candle
+    pub fn of_candle(candle: &Candle) -> Self {
+        Self::new(candle.timestamp, candle.timestamp + candle.interval.as_millis())
+    }
+
+    pub fn contains(&self, timestamp: i64) -> bool {
+        timestamp >= self.start && timestamp < self.end
+    }
+}
+
+/// What the pointer is over, mirrored to every panel that can highlight it
+#[derive(Clone, Copy)]
+pub struct CursorState {
+    /// Price level under the pointer (order book, depth chart)
+    pub hovered_price: RwSignal<Option<f64>>,
+    /// Candle period under the pointer
+    pub hovered_time: RwSignal<Option<TimeSpan>>,
+    /// Trade clicked in the tape
+    pub selected_trade: RwSignal<Option<String>>,
+}
+
+impl CursorState {
+    pub fn new() -> Self {
+        Self {
+            hovered_price: RwSignal::new(None),
+            hovered_time: RwSignal::new(None),
+            selected_trade: RwSignal::new(None),
+        }
+    }
+
+    /// Set (or clear) the hovered price level
+    pub fn hover_price(&self, price: Option<f64>) {
+        if self.hovered_price.get_untracked() != price {
+            self.hovered_price.set(price);
+        }
+    }
+
+    /// Set (or clear) the hovered candle period
+    pub fn hover_candle(&self, candle: Option<&Candle>) {
+        let span = candle.map(TimeSpan::of_candle);
+        if self.hovered_time.get_untracked() != span {
+            self.hovered_time.set(span);
+        }
+    }
+
+    /// Is this price the hovered level?
+    pub fn is_price_hovered(&self, price: f64) -> bool {
+        self.hovered_price.get() == Some(price)
+    }
+
+    /// Does this trade fall inside the hovered candle?
+    pub fn is_in_hovered_period(&self, trade: &Trade) -> bool {
+        self.hovered_time
+            .get()
+            .is_some_and(|span| span.contains(trade.timestamp.timestamp_millis()))
+    }
+
+    /// Select a trade; selecting it again clears the selection
+    pub fn toggle_trade(&self, id: &str) {
+        self.selected_trade.update(|selected| {
+            *selected = match selected.as_deref() {
+                Some(current) if current == id => None,
+                _ => Some(id.to_string()),
+            };
+        });
+    }
+
+    pub fn is_trade_selected(&self, id: &str) -> bool {
+        self.selected_trade.with(|s| s.as_deref() == Some(id))
+    }
+
+    /// Drop every hover/selection (e.g. on symbol change)
+    pub fn clear(&self) {
+        self.hovered_price.set(None);
+        self.hovered_time.set(None);
+        self.selected_trade.set(None);
+    }
+}
+
+impl Default for CursorState {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use dash_core::{CandleInterval, Symbol};
+
+    #[test]
+    fn test_candle_span() {
+        let candle = Candle::new(Symbol::default(), CandleInterval::M5, 600_000, 1.0);
+        let span = TimeSpan::of_candle(&candle);
+
+        assert!(span.contains(600_000));
+        assert!(span.contains(899_999));
+        assert!(!span.contains(900_000));
+    }
+}
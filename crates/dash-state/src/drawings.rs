@@ -0,0 +1,124 @@
+//! Chart drawings per symbol, persisted under their own storage key so a
+//! long list of lines doesn't bloat every settings write
+
+use crate::storage;
+use dash_core::{Drawing, Symbol};
+use leptos::prelude::*;
+use std::collections::BTreeMap;
+
+/// Drawings on each symbol's chart
+#[derive(Clone, Copy)]
+pub struct DrawingStore {
+    pub by_symbol: RwSignal<BTreeMap<Symbol, Vec<Drawing>>>,
+}
+
+impl DrawingStore {
+    pub const STORAGE_KEY: &'static str = "dash.drawings";
+
+    pub fn new() -> Self {
+        Self {
+            by_symbol: RwSignal::new(BTreeMap::new()),
+        }
+    }
+
+    /// Drawings on `symbol`'s chart (tracked)
+    pub fn for_symbol(&self, symbol: &Symbol) -> Vec<Drawing> {
+        self.by_symbol.with(|all| all.get(symbol).cloned().unwrap_or_default())
+    }
+
+    /// Store a new drawing (any `id` it has is replaced) and return its id
+    pub fn add(&self, symbol: &Symbol, mut drawing: Drawing) -> u64 {
+        self.by_symbol.update(|all| {
+            let drawings = all.entry(symbol.clone()).or_default();
+            drawing.id = drawings.iter().map(|d| d.id).max().map_or(1, |id| id + 1);
+            drawings.push(drawing.clone());
+        });
+        drawing.id
+    }
+
+    /// Replace a stored drawing with an edited copy
+    pub fn replace(&self, symbol: &Symbol, drawing: Drawing) {
+        self.by_symbol.update(|all| {
+            if let Some(slot) = all.get_mut(symbol).and_then(|d| d.iter_mut().find(|d| d.id == drawing.id)) {
+                *slot = drawing;
+            }
+        });
+    }
+
+    pub fn remove(&self, symbol: &Symbol, id: u64) {
+        self.by_symbol.update(|all| {
+            if let Some(drawings) = all.get_mut(symbol) {
+                drawings.retain(|d| d.id != id);
+                if drawings.is_empty() {
+                    all.remove(symbol);
+                }
+            }
+        });
+    }
+
+    /// Remove every drawing on `symbol`'s chart
+    pub fn clear(&self, symbol: &Symbol) {
+        if self.by_symbol.with_untracked(|all| all.contains_key(symbol)) {
+            self.by_symbol.update(|all| {
+                all.remove(symbol);
+            });
+        }
+    }
+
+    /// Restore the stored drawings (unreadable data is dropped)
+    pub fn load(&self) {
+        let Some(json) = storage::load(Self::STORAGE_KEY) else {
+            return;
+        };
+        match serde_json::from_str(&json) {
+            Ok(all) => self.by_symbol.set(all),
+            Err(e) => tracing::warn!("Discarding stored drawings: {}", e),
+        }
+    }
+
+    pub fn save(&self) {
+        let json = self.by_symbol.with_untracked(serde_json::to_string);
+        match json {
+            Ok(json) => storage::save(Self::STORAGE_KEY, &json),
+            Err(e) => tracing::warn!("Failed to serialize drawings: {}", e),
+        }
+    }
+}
+
+impl Default for DrawingStore {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use dash_core::{Anchor, DrawingTool};
+
+    fn ray(price: f64) -> Drawing {
+        let anchor = Anchor::new(0, price);
+        Drawing::new(0, DrawingTool::HorizontalRay, anchor, anchor)
+    }
+
+    #[test]
+    fn test_drawings_are_kept_per_symbol() {
+        let store = DrawingStore::new();
+        let (btc, eth) = (Symbol::new("BTC-USD"), Symbol::new("ETH-USD"));
+
+        assert_eq!(store.add(&btc, ray(100.0)), 1);
+        assert_eq!(store.add(&btc, ray(110.0)), 2);
+        assert_eq!(store.add(&eth, ray(5.0)), 1);
+
+        let mut moved = ray(120.0);
+        moved.id = 2;
+        store.replace(&btc, moved);
+        store.remove(&btc, 1);
+        let prices: Vec<f64> = store.for_symbol(&btc).iter().map(|d| d.start.price).collect();
+        assert_eq!(prices, vec![120.0]);
+
+        store.clear(&eth);
+        assert!(store.for_symbol(&eth).is_empty());
+        assert_eq!(store.for_symbol(&btc).len(), 1);
+    }
+}
@@ -0,0 +1,258 @@
+//! Multi-chart grid: several symbols or timeframes charted side by side
+//!
+//! Each chart in the grid names a symbol and a timeframe. One base (1m)
+//! candle series is kept per symbol on screen, fed by the candle stream and
+//! backfilled over REST, and resampled per chart on demand, so two charts of
+//! one symbol share it.
+
+use crate::market::{backfill_request, resample_tail, upsert_candle, BackfillRequest, BackfillStatus, BASE_INTERVAL};
+use crate::{MAX_BASE_CANDLES, MAX_CANDLES};
+use dash_core::{Candle, CandleHistory, CandleInterval, Symbol};
+use leptos::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Columns × rows of the chart grid
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum GridSize {
+    TwoByOne,
+    #[default]
+    TwoByTwo,
+    ThreeByTwo,
+    ThreeByThree,
+}
+
+impl GridSize {
+    pub fn columns(&self) -> usize {
+        match self {
+            Self::TwoByOne | Self::TwoByTwo => 2,
+            Self::ThreeByTwo | Self::ThreeByThree => 3,
+        }
+    }
+
+    pub fn rows(&self) -> usize {
+        match self {
+            Self::TwoByOne => 1,
+            Self::TwoByTwo | Self::ThreeByTwo => 2,
+            Self::ThreeByThree => 3,
+        }
+    }
+
+    /// Charts on screen
+    pub fn cells(&self) -> usize {
+        self.columns() * self.rows()
+    }
+
+    /// "2×2"
+    pub fn label(&self) -> String {
+        format!("{}×{}", self.columns(), self.rows())
+    }
+
+    pub fn all() -> &'static [Self] {
+        &[Self::TwoByOne, Self::TwoByTwo, Self::ThreeByTwo, Self::ThreeByThree]
+    }
+}
+
+/// What one grid cell charts
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GridChart {
+    pub symbol: Symbol,
+    pub interval: CandleInterval,
+}
+
+/// Charts of the grid and the candles behind them
+#[derive(Clone, Copy)]
+pub struct ChartGridState {
+    /// Charts in grid order; cells past the grid size are kept for when it grows
+    pub charts: RwSignal<Vec<GridChart>>,
+    /// Symbols on screen, each subscribed while shown
+    pub symbols: RwSignal<Vec<Symbol>>,
+    /// Base (1m) candles per symbol on screen, oldest first
+    pub base_candles: RwSignal<BTreeMap<Symbol, CandleHistory>>,
+    /// Loading history over REST, per symbol on screen
+    pub backfill: RwSignal<BTreeMap<Symbol, BackfillStatus>>,
+}
+
+impl ChartGridState {
+    pub fn new() -> Self {
+        Self {
+            charts: RwSignal::new(Vec::new()),
+            symbols: RwSignal::new(Vec::new()),
+            base_candles: RwSignal::new(BTreeMap::new()),
+            backfill: RwSignal::new(BTreeMap::new()),
+        }
+    }
+
+    /// Give the grid `cells` charts, new cells taking `symbol` at the next
+    /// timeframe up from the last chart's, and keep series for the symbols on
+    /// screen only (`cells` = 0 when the grid is off)
+    pub fn show(&self, cells: usize, symbol: &Symbol) {
+        self.charts.update(|charts| {
+            while charts.len() < cells {
+                let interval = charts.last().map_or(CandleInterval::M1, |c: &GridChart| next_interval(c.interval));
+                charts.push(GridChart { symbol: symbol.clone(), interval });
+            }
+        });
+        let mut shown: Vec<Symbol> = self.charts.with_untracked(|charts| {
+            charts.iter().take(cells).map(|c| c.symbol.clone()).collect()
+        });
+        shown.sort();
+        shown.dedup();
+
+        self.base_candles.update(|series| {
+            series.retain(|s, _| shown.contains(s));
+            for symbol in &shown {
+                series
+                    .entry(symbol.clone())
+                    .or_insert_with(|| CandleHistory::new(symbol.clone(), BASE_INTERVAL));
+            }
+        });
+        self.backfill.update(|status| status.retain(|s, _| shown.contains(s)));
+        if self.symbols.with_untracked(|symbols| *symbols != shown) {
+            self.symbols.set(shown);
+        }
+    }
+
+    /// Chart something else in cell `index`; its symbol's history is looked
+    /// at again in case the new timeframe needs more
+    pub fn set_chart(&self, index: usize, chart: GridChart, cells: usize) {
+        let symbol = chart.symbol.clone();
+        self.charts.update(|charts| {
+            if let Some(slot) = charts.get_mut(index) {
+                *slot = chart;
+            }
+        });
+        self.show(cells, &symbol);
+        self.backfill.update(|status| {
+            status.remove(&symbol);
+        });
+    }
+
+    /// Is `symbol` on screen in the grid?
+    pub fn is_shown(&self, symbol: &Symbol) -> bool {
+        self.base_candles.with_untracked(|series| series.contains_key(symbol))
+    }
+
+    /// Upsert a base candle of a symbol on screen (others are ignored)
+    pub fn update_candle(&self, candle: Candle) {
+        if candle.interval != BASE_INTERVAL || !self.is_shown(&candle.symbol) {
+            return;
+        }
+        self.base_candles.update(|series| {
+            if let Some(history) = series.get_mut(&candle.symbol) {
+                upsert_candle(&mut history.candles, candle, MAX_BASE_CANDLES);
+            }
+        });
+    }
+
+    pub fn backfill_status(&self, symbol: &Symbol) -> BackfillStatus {
+        self.backfill
+            .with_untracked(|status| status.get(symbol).copied().unwrap_or(BackfillStatus::Idle))
+    }
+
+    pub fn set_backfill(&self, symbol: &Symbol, status: BackfillStatus) {
+        if self.is_shown(symbol) {
+            self.backfill.update(|all| {
+                all.insert(symbol.clone(), status);
+            });
+        }
+    }
+
+    /// Older base candles to fetch so `symbol` has a chart's worth at `interval`
+    pub fn backfill_request(&self, symbol: &Symbol, interval: CandleInterval) -> Option<BackfillRequest> {
+        if self.backfill_status(symbol) != BackfillStatus::Idle {
+            return None;
+        }
+        self.base_candles
+            .with_untracked(|series| backfill_request(series.get(symbol)?, interval, MAX_CANDLES))
+    }
+
+    /// Put REST candles in front of the streamed ones; a short answer means
+    /// there is nothing older
+    pub fn prepend_candles(&self, symbol: &Symbol, request: BackfillRequest, mut candles: Vec<Candle>) {
+        if !self.is_shown(symbol) {
+            return;
+        }
+        candles.retain(|c| c.interval == BASE_INTERVAL);
+        candles.sort_by_key(|c| c.timestamp);
+        let exhausted = candles.len() < request.limit;
+
+        self.base_candles.update(|series| {
+            let Some(history) = series.get_mut(symbol) else {
+                return;
+            };
+            if let Some(oldest) = history.candles.first().map(|c| c.timestamp) {
+                candles.retain(|c| c.timestamp < oldest);
+            }
+            candles.append(&mut history.candles);
+            let excess = candles.len().saturating_sub(MAX_BASE_CANDLES);
+            candles.drain(..excess);
+            history.candles = candles;
+        });
+        self.set_backfill(symbol, if exhausted { BackfillStatus::Exhausted } else { BackfillStatus::Idle });
+    }
+
+    /// `symbol`'s candles at `interval` (tracked; empty when not on screen)
+    pub fn candles(&self, symbol: &Symbol, interval: CandleInterval) -> CandleHistory {
+        self.base_candles.with(|series| match series.get(symbol) {
+            Some(base) => resample_tail(base, interval, MAX_CANDLES),
+            None => CandleHistory::new(symbol.clone(), interval),
+        })
+    }
+}
+
+impl Default for ChartGridState {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// The timeframe after `interval` (the longest stays put)
+fn next_interval(interval: CandleInterval) -> CandleInterval {
+    let all = CandleInterval::all();
+    all.iter()
+        .position(|&i| i == interval)
+        .and_then(|i| all.get(i + 1))
+        .copied()
+        .unwrap_or(interval)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn candle(symbol: &Symbol, minute: i64, close: f64) -> Candle {
+        Candle::new(symbol.clone(), BASE_INTERVAL, minute * 60_000, close)
+    }
+
+    #[test]
+    fn test_grid_keeps_series_for_symbols_on_screen() {
+        let grid = ChartGridState::new();
+        let (btc, eth) = (Symbol::new("BTC-USD"), Symbol::new("ETH-USD"));
+
+        grid.show(GridSize::TwoByTwo.cells(), &btc);
+        let intervals: Vec<CandleInterval> = grid.charts.get_untracked().iter().map(|c| c.interval).collect();
+        assert_eq!(intervals, vec![CandleInterval::M1, CandleInterval::M5, CandleInterval::M15, CandleInterval::M30]);
+        assert_eq!(grid.symbols.get_untracked(), vec![btc.clone()]);
+
+        grid.update_candle(candle(&eth, 0, 10.0));
+        grid.set_chart(3, GridChart { symbol: eth.clone(), interval: CandleInterval::M1 }, 4);
+        grid.update_candle(candle(&eth, 1, 11.0));
+        grid.update_candle(candle(&btc, 1, 100.0));
+        assert_eq!(grid.symbols.get_untracked(), vec![btc.clone(), eth.clone()]);
+        assert_eq!(grid.candles(&eth, CandleInterval::M1).candles.len(), 1);
+
+        let request = grid.backfill_request(&eth, CandleInterval::M1).unwrap();
+        assert_eq!(request.before, Some(60_000));
+        grid.prepend_candles(&eth, request, vec![candle(&eth, 0, 10.0)]);
+        assert_eq!(grid.candles(&eth, CandleInterval::M1).candles.len(), 2);
+        assert_eq!(grid.backfill_status(&eth), BackfillStatus::Exhausted);
+
+        // Shrinking the grid drops the series it no longer shows
+        grid.show(GridSize::TwoByOne.cells(), &btc);
+        assert!(!grid.is_shown(&eth));
+        assert_eq!(grid.charts.get_untracked().len(), 4);
+        grid.show(0, &btc);
+        assert!(grid.symbols.get_untracked().is_empty());
+    }
+}
@@ -0,0 +1,669 @@
+//! Message catalogs and locale-aware number/date formatting
+//!
+//! Strings are looked up by [`Msg`] key; every catalog is an exhaustive
+//! match, so adding a key without translating it fails to compile.
+
+use dash_core::{TickFormat, TradeSide};
+use leptos::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// Supported UI languages
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Locale {
+    #[default]
+    En,
+    De,
+    Ja,
+    Zh,
+}
+
+impl Locale {
+    pub fn all() -> &'static [Self] {
+        &[Self::En, Self::De, Self::Ja, Self::Zh]
+    }
+
+    /// BCP 47 language code
+    pub fn code(&self) -> &'static str {
+        match self {
+            Self::En => "en",
+            Self::De => "de",
+            Self::Ja => "ja",
+            Self::Zh => "zh",
+        }
+    }
+
+    /// Language name in that language (for the picker)
+    pub fn native_name(&self) -> &'static str {
+        match self {
+            Self::En => "English",
+            Self::De => "Deutsch",
+            Self::Ja => "日本語",
+            Self::Zh => "中文",
+        }
+    }
+
+    /// Match a language tag such as `de-AT` by its primary subtag
+    pub fn from_tag(tag: &str) -> Option<Self> {
+        let primary = tag.split(['-', '_']).next()?.to_ascii_lowercase();
+        Self::all().iter().copied().find(|l| l.code() == primary)
+    }
+
+    /// The browser's preferred language, if supported (English otherwise)
+    pub fn detect() -> Self {
+        #[cfg(target_arch = "wasm32")]
+        {
+            web_sys::window()
+                .and_then(|w| w.navigator().language())
+                .and_then(|tag| Self::from_tag(&tag))
+                .unwrap_or_default()
+        }
+        #[cfg(not(target_arch = "wasm32"))]
+        {
+            Self::default()
+        }
+    }
+
+    /// Thousands and decimal separators
+    fn separators(&self) -> (char, char) {
+        match self {
+            Self::De => ('.', ','),
+            Self::En | Self::Ja | Self::Zh => (',', '.'),
+        }
+    }
+
+    /// Format with `decimals` places and grouped thousands
+    pub fn format_number(&self, value: f64, decimals: usize) -> String {
+        if !value.is_finite() {
+            return "—".to_string();
+        }
+        let (group, decimal) = self.separators();
+        let text = format!("{:.*}", decimals, value.abs());
+        let (int, frac) = text.split_once('.').unwrap_or((&text, ""));
+
+        let mut out = String::with_capacity(text.len() + int.len() / 3 + 1);
+        if value < 0.0 && text.bytes().any(|b| b.is_ascii_digit() && b != b'0') {
+            out.push('-');
+        }
+        for (i, digit) in int.chars().enumerate() {
+            if i > 0 && (int.len() - i) % 3 == 0 {
+                out.push(group);
+            }
+            out.push(digit);
+        }
+        if !frac.is_empty() {
+            out.push(decimal);
+            out.push_str(frac);
+        }
+        out
+    }
+
+    /// Calendar date of a millisecond timestamp (UTC) in the local order
+    pub fn format_date(&self, timestamp_ms: i64) -> String {
+        let pattern = match self {
+            Self::En => "%b %-d, %Y",
+            Self::De => "%d.%m.%Y",
+            Self::Ja => "%Y/%m/%d",
+            Self::Zh => "%Y-%m-%d",
+        };
+        chrono::DateTime::from_timestamp_millis(timestamp_ms)
+            .map(|t| t.format(pattern).to_string())
+            .unwrap_or_default()
+    }
+}
+
+/// Translatable UI strings
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Msg {
+    // Panels
+    OrderBook,
+    Chart,
+    MarketDepth,
+    RecentTrades,
+    OrderEntry,
+    Positions,
+    Account,
+    // Ticker bar
+    Change24h,
+    High24h,
+    Low24h,
+    Volume24h,
+    Spread,
+    SessionOpen,
+    VsVwap,
+    SessionRange,
+    // Table columns
+    Price,
+    Size,
+    Sum,
+    Total,
+    Time,
+    Side,
+    Value,
+    Symbol,
+    Entry,
+    Mark,
+    UnrealizedPnl,
+    BidTotal,
+    AskTotal,
+    Bid,
+    Ask,
+    Orders,
+    PositionSide,
+    // Derivatives
+    Funding,
+    NextFunding,
+    OpenInterest,
+    Index,
+    Basis,
+    // Trading
+    Buy,
+    Sell,
+    Stop,
+    Quantity,
+    Notional,
+    Available,
+    Last,
+    FastTrading,
+    Sending,
+    NoPositions,
+    WorkingOrders,
+    Equity,
+    TotalPnl,
+    RealizedToday,
+    FeesPaid,
+    Exposure,
+    // Status bar and settings
+    Status,
+    Layout,
+    Compact,
+    Settings,
+    Language,
+    AutoLanguage,
+    Accessibility,
+    AccessibleMode,
+    SoundAlerts,
+    ChartRenderer,
+    FitIndicators,
+    CandleTooltip,
+    KeyboardShortcuts,
+    ShortcutTabsHint,
+    PressKey,
+    ResetDefaults,
+    Clear,
+    // Empty states
+    NoLiquidations,
+    NoWhalePrints,
+    WaitingBook,
+    WaitingCandles,
+    WaitingDepth,
+    WaitingTrades,
+    // Onboarding tour
+    TourSymbolTitle,
+    TourSymbolBody,
+    TourOrderTitle,
+    TourOrderBody,
+    TourAlertsTitle,
+    TourAlertsBody,
+    TourNext,
+    TourDone,
+    TourSkip,
+    ShowTour,
+}
+
+impl Msg {
+    pub fn text(self, locale: Locale) -> &'static str {
+        match locale {
+            Locale::En => en(self),
+            Locale::De => de(self),
+            Locale::Ja => ja(self),
+            Locale::Zh => zh(self),
+        }
+    }
+}
+
+fn en(msg: Msg) -> &'static str {
+    match msg {
+        Msg::OrderBook => "Order Book",
+        Msg::Chart => "Chart",
+        Msg::MarketDepth => "Market Depth",
+        Msg::RecentTrades => "Recent Trades",
+        Msg::OrderEntry => "Order Entry",
+        Msg::Positions => "Positions",
+        Msg::Account => "Account",
+        Msg::Change24h => "24h Change",
+        Msg::High24h => "24h High",
+        Msg::Low24h => "24h Low",
+        Msg::Volume24h => "24h Volume",
+        Msg::Spread => "Spread",
+        Msg::SessionOpen => "Session Open",
+        Msg::VsVwap => "vs VWAP",
+        Msg::SessionRange => "Session Range",
+        Msg::Price => "Price",
+        Msg::Size => "Size",
+        Msg::Sum => "Sum",
+        Msg::Total => "Total",
+        Msg::Time => "Time",
+        Msg::Side => "Side",
+        Msg::Value => "Value",
+        Msg::Symbol => "Symbol",
+        Msg::Entry => "Entry",
+        Msg::Mark => "Mark",
+        Msg::UnrealizedPnl => "uPnL",
+        Msg::BidTotal => "Bid Total:",
+        Msg::AskTotal => "Ask Total:",
+        Msg::Bid => "Bid",
+        Msg::Ask => "Ask",
+        Msg::Orders => "Orders",
+        Msg::PositionSide => "Pos",
+        Msg::Funding => "Funding",
+        Msg::NextFunding => "Next Funding",
+        Msg::OpenInterest => "Open Interest",
+        Msg::Index => "Index",
+        Msg::Basis => "Basis",
+        Msg::Buy => "BUY",
+        Msg::Sell => "SELL",
+        Msg::Stop => "Stop",
+        Msg::Quantity => "Quantity",
+        Msg::Notional => "Notional",
+        Msg::Available => "Available",
+        Msg::Last => "Last",
+        Msg::FastTrading => "Fast trading",
+        Msg::Sending => "Sending…",
+        Msg::NoPositions => "No open positions",
+        Msg::WorkingOrders => "Working orders",
+        Msg::Equity => "Equity",
+        Msg::TotalPnl => "Total PnL",
+        Msg::RealizedToday => "Realized today",
+        Msg::FeesPaid => "Fees paid",
+        Msg::Exposure => "Exposure",
+        Msg::Status => "Status:",
+        Msg::Layout => "Layout:",
+        Msg::Compact => "Compact",
+        Msg::Settings => "Settings",
+        Msg::Language => "Language",
+        Msg::AutoLanguage => "Auto (browser)",
+        Msg::Accessibility => "Accessibility",
+        Msg::AccessibleMode => "Reduced motion / screen reader friendly",
+        Msg::SoundAlerts => "Sound alerts",
+        Msg::ChartRenderer => "Chart renderer",
+        Msg::FitIndicators => "Fit price scale to indicators",
+        Msg::CandleTooltip => "Candle tooltip at the crosshair",
+        Msg::KeyboardShortcuts => "Keyboard Shortcuts",
+        Msg::ShortcutTabsHint => "Tabs apply in compact mode",
+        Msg::PressKey => "Press a key…",
+        Msg::ResetDefaults => "Reset defaults",
+        Msg::Clear => "Clear",
+        Msg::NoLiquidations => "No liquidations yet",
+        Msg::NoWhalePrints => "No whale prints yet",
+        Msg::WaitingBook => "Waiting for first order book snapshot…",
+        Msg::WaitingCandles => "Waiting for candle history…",
+        Msg::WaitingDepth => "Waiting for market depth…",
+        Msg::WaitingTrades => "Waiting for the first trade…",
+        Msg::TourSymbolTitle => "Pick a market",
+        Msg::TourSymbolBody => "Search symbols here and star favorites to add them to the ticker strip.",
+        Msg::TourOrderTitle => "Paper trading",
+        Msg::TourOrderBody => "Place simulated orders here. Clicking a price in the order book fills in the ticket.",
+        Msg::TourAlertsTitle => "Price alerts",
+        Msg::TourAlertsBody => "Right-click a price on the chart or order book to set an alert. Fired alerts show up as notifications.",
+        Msg::TourNext => "Next",
+        Msg::TourDone => "Done",
+        Msg::TourSkip => "Skip tour",
+        Msg::ShowTour => "Show the guided tour again",
+    }
+}
+
+fn de(msg: Msg) -> &'static str {
+    match msg {
+        Msg::OrderBook => "Orderbuch",
+        Msg::Chart => "Chart",
+        Msg::MarketDepth => "Markttiefe",
+        Msg::RecentTrades => "Letzte Trades",
+        Msg::OrderEntry => "Ordereingabe",
+        Msg::Positions => "Positionen",
+        Msg::Account => "Konto",
+        Msg::Change24h => "24h-Änderung",
+        Msg::High24h => "24h-Hoch",
+        Msg::Low24h => "24h-Tief",
+        Msg::Volume24h => "24h-Volumen",
+        Msg::Spread => "Spread",
+        Msg::SessionOpen => "Sitzungseröffnung",
+        Msg::VsVwap => "vs. VWAP",
+        Msg::SessionRange => "Sitzungsspanne",
+        Msg::Price => "Preis",
+        Msg::Size => "Größe",
+        Msg::Sum => "Summe",
+        Msg::Total => "Gesamt",
+        Msg::Time => "Zeit",
+        Msg::Side => "Seite",
+        Msg::Value => "Wert",
+        Msg::Symbol => "Symbol",
+        Msg::Entry => "Einstieg",
+        Msg::Mark => "Mark",
+        Msg::UnrealizedPnl => "uGuV",
+        Msg::BidTotal => "Geld gesamt:",
+        Msg::AskTotal => "Brief gesamt:",
+        Msg::Bid => "Geld",
+        Msg::Ask => "Brief",
+        Msg::Orders => "Orders",
+        Msg::PositionSide => "Pos.",
+        Msg::Funding => "Funding",
+        Msg::NextFunding => "Nächstes Funding",
+        Msg::OpenInterest => "Open Interest",
+        Msg::Index => "Index",
+        Msg::Basis => "Basis",
+        Msg::Buy => "KAUFEN",
+        Msg::Sell => "VERKAUFEN",
+        Msg::Stop => "Stop",
+        Msg::Quantity => "Menge",
+        Msg::Notional => "Nominalwert",
+        Msg::Available => "Verfügbar",
+        Msg::Last => "Letzter",
+        Msg::FastTrading => "Schnellhandel",
+        Msg::Sending => "Wird gesendet…",
+        Msg::NoPositions => "Keine offenen Positionen",
+        Msg::WorkingOrders => "Offene Orders",
+        Msg::Equity => "Eigenkapital",
+        Msg::TotalPnl => "Gesamt-GuV",
+        Msg::RealizedToday => "Realisiert heute",
+        Msg::FeesPaid => "Gezahlte Gebühren",
+        Msg::Exposure => "Exposure",
+        Msg::Status => "Status:",
+        Msg::Layout => "Layout:",
+        Msg::Compact => "Kompakt",
+        Msg::Settings => "Einstellungen",
+        Msg::Language => "Sprache",
+        Msg::AutoLanguage => "Automatisch (Browser)",
+        Msg::Accessibility => "Barrierefreiheit",
+        Msg::AccessibleMode => "Weniger Bewegung / Screenreader-freundlich",
+        Msg::SoundAlerts => "Tonsignale",
+        Msg::ChartRenderer => "Chart-Renderer",
+        Msg::FitIndicators => "Preisskala an Indikatoren anpassen",
+        Msg::CandleTooltip => "Kerzen-Tooltip am Fadenkreuz",
+        Msg::KeyboardShortcuts => "Tastenkürzel",
+        Msg::ShortcutTabsHint => "Tabs gelten im Kompaktmodus",
+        Msg::PressKey => "Taste drücken…",
+        Msg::ResetDefaults => "Standard wiederherstellen",
+        Msg::Clear => "Leeren",
+        Msg::NoLiquidations => "Noch keine Liquidationen",
+        Msg::NoWhalePrints => "Noch keine Wal-Trades",
+        Msg::WaitingBook => "Warte auf ersten Orderbuch-Snapshot…",
+        Msg::WaitingCandles => "Warte auf Kerzenhistorie…",
+        Msg::WaitingDepth => "Warte auf Markttiefe…",
+        Msg::WaitingTrades => "Warte auf den ersten Trade…",
+        Msg::TourSymbolTitle => "Markt wählen",
+        Msg::TourSymbolBody => "Hier Symbole suchen und Favoriten markieren, um sie zur Tickerleiste hinzuzufügen.",
+        Msg::TourOrderTitle => "Papierhandel",
+        Msg::TourOrderBody => "Hier simulierte Orders aufgeben. Ein Klick auf einen Preis im Orderbuch füllt das Ticket aus.",
+        Msg::TourAlertsTitle => "Preisalarme",
+        Msg::TourAlertsBody => "Rechtsklick auf einen Preis im Chart oder Orderbuch setzt einen Alarm. Ausgelöste Alarme erscheinen als Benachrichtigung.",
+        Msg::TourNext => "Weiter",
+        Msg::TourDone => "Fertig",
+        Msg::TourSkip => "Tour überspringen",
+        Msg::ShowTour => "Einführung erneut anzeigen",
+    }
+}
+
+fn ja(msg: Msg) -> &'static str {
+    match msg {
+        Msg::OrderBook => "板情報",
+        Msg::Chart => "チャート",
+        Msg::MarketDepth => "市場の厚み",
+        Msg::RecentTrades => "約定履歴",
+        Msg::OrderEntry => "注文入力",
+        Msg::Positions => "ポジション",
+        Msg::Account => "口座",
+        Msg::Change24h => "24時間変動",
+        Msg::High24h => "24時間高値",
+        Msg::Low24h => "24時間安値",
+        Msg::Volume24h => "24時間出来高",
+        Msg::Spread => "スプレッド",
+        Msg::SessionOpen => "セッション始値",
+        Msg::VsVwap => "VWAP比",
+        Msg::SessionRange => "セッションレンジ",
+        Msg::Price => "価格",
+        Msg::Size => "数量",
+        Msg::Sum => "累計",
+        Msg::Total => "合計",
+        Msg::Time => "時刻",
+        Msg::Side => "売買",
+        Msg::Value => "金額",
+        Msg::Symbol => "銘柄",
+        Msg::Entry => "建値",
+        Msg::Mark => "マーク",
+        Msg::UnrealizedPnl => "含み損益",
+        Msg::BidTotal => "買い合計:",
+        Msg::AskTotal => "売り合計:",
+        Msg::Bid => "買い",
+        Msg::Ask => "売り",
+        Msg::Orders => "注文",
+        Msg::PositionSide => "建玉",
+        Msg::Funding => "資金調達率",
+        Msg::NextFunding => "次回資金調達",
+        Msg::OpenInterest => "建玉残高",
+        Msg::Index => "インデックス",
+        Msg::Basis => "ベーシス",
+        Msg::Buy => "買い",
+        Msg::Sell => "売り",
+        Msg::Stop => "ストップ",
+        Msg::Quantity => "数量",
+        Msg::Notional => "想定元本",
+        Msg::Available => "利用可能",
+        Msg::Last => "現在値",
+        Msg::FastTrading => "高速取引",
+        Msg::Sending => "送信中…",
+        Msg::NoPositions => "保有ポジションなし",
+        Msg::WorkingOrders => "未約定注文",
+        Msg::Equity => "純資産",
+        Msg::TotalPnl => "合計損益",
+        Msg::RealizedToday => "本日の実現損益",
+        Msg::FeesPaid => "支払手数料",
+        Msg::Exposure => "エクスポージャー",
+        Msg::Status => "状態:",
+        Msg::Layout => "レイアウト:",
+        Msg::Compact => "コンパクト",
+        Msg::Settings => "設定",
+        Msg::Language => "言語",
+        Msg::AutoLanguage => "自動(ブラウザ)",
+        Msg::Accessibility => "アクセシビリティ",
+        Msg::AccessibleMode => "動きを減らす/スクリーンリーダー対応",
+        Msg::SoundAlerts => "通知音",
+        Msg::ChartRenderer => "チャート描画",
+        Msg::FitIndicators => "価格軸をインジケーターに合わせる",
+        Msg::CandleTooltip => "十字線にローソク足のツールチップ",
+        Msg::KeyboardShortcuts => "キーボードショートカット",
+        Msg::ShortcutTabsHint => "タブはコンパクトモードで有効",
+        Msg::PressKey => "キーを押してください…",
+        Msg::ResetDefaults => "初期設定に戻す",
+        Msg::Clear => "クリア",
+        Msg::NoLiquidations => "清算はまだありません",
+        Msg::NoWhalePrints => "大口約定はまだありません",
+        Msg::WaitingBook => "最初の板情報を待っています…",
+        Msg::WaitingCandles => "ローソク足の履歴を待っています…",
+        Msg::WaitingDepth => "市場の厚みを待っています…",
+        Msg::WaitingTrades => "最初の約定を待っています…",
+        Msg::TourSymbolTitle => "銘柄を選ぶ",
+        Msg::TourSymbolBody => "ここで銘柄を検索し、お気に入りに追加するとティッカーに表示されます。",
+        Msg::TourOrderTitle => "ペーパートレード",
+        Msg::TourOrderBody => "ここで模擬注文を出せます。板の価格をクリックすると注文票に入力されます。",
+        Msg::TourAlertsTitle => "価格アラート",
+        Msg::TourAlertsBody => "チャートや板の価格を右クリックするとアラートを設定できます。発動したアラートは通知で表示されます。",
+        Msg::TourNext => "次へ",
+        Msg::TourDone => "完了",
+        Msg::TourSkip => "ツアーをスキップ",
+        Msg::ShowTour => "ガイドツアーをもう一度表示",
+    }
+}
+
+fn zh(msg: Msg) -> &'static str {
+    match msg {
+        Msg::OrderBook => "订单簿",
+        Msg::Chart => "图表",
+        Msg::MarketDepth => "市场深度",
+        Msg::RecentTrades => "最新成交",
+        Msg::OrderEntry => "下单",
+        Msg::Positions => "持仓",
+        Msg::Account => "账户",
+        Msg::Change24h => "24小时涨跌",
+        Msg::High24h => "24小时最高",
+        Msg::Low24h => "24小时最低",
+        Msg::Volume24h => "24小时成交量",
+        Msg::Spread => "价差",
+        Msg::SessionOpen => "时段开盘",
+        Msg::VsVwap => "相对VWAP",
+        Msg::SessionRange => "时段区间",
+        Msg::Price => "价格",
+        Msg::Size => "数量",
+        Msg::Sum => "累计",
+        Msg::Total => "总额",
+        Msg::Time => "时间",
+        Msg::Side => "方向",
+        Msg::Value => "金额",
+        Msg::Symbol => "交易对",
+        Msg::Entry => "开仓价",
+        Msg::Mark => "标记价",
+        Msg::UnrealizedPnl => "未实现盈亏",
+        Msg::BidTotal => "买盘总量:",
+        Msg::AskTotal => "卖盘总量:",
+        Msg::Bid => "买价",
+        Msg::Ask => "卖价",
+        Msg::Orders => "订单",
+        Msg::PositionSide => "仓位",
+        Msg::Funding => "资金费率",
+        Msg::NextFunding => "下次资金费",
+        Msg::OpenInterest => "持仓量",
+        Msg::Index => "指数价",
+        Msg::Basis => "基差",
+        Msg::Buy => "买入",
+        Msg::Sell => "卖出",
+        Msg::Stop => "止损",
+        Msg::Quantity => "数量",
+        Msg::Notional => "名义价值",
+        Msg::Available => "可用",
+        Msg::Last => "最新",
+        Msg::FastTrading => "快速交易",
+        Msg::Sending => "发送中…",
+        Msg::NoPositions => "暂无持仓",
+        Msg::WorkingOrders => "挂单",
+        Msg::Equity => "权益",
+        Msg::TotalPnl => "总盈亏",
+        Msg::RealizedToday => "今日已实现盈亏",
+        Msg::FeesPaid => "已付手续费",
+        Msg::Exposure => "风险敞口",
+        Msg::Status => "状态:",
+        Msg::Layout => "布局:",
+        Msg::Compact => "紧凑",
+        Msg::Settings => "设置",
+        Msg::Language => "语言",
+        Msg::AutoLanguage => "自动(浏览器)",
+        Msg::Accessibility => "无障碍",
+        Msg::AccessibleMode => "减少动画/屏幕阅读器友好",
+        Msg::SoundAlerts => "声音提醒",
+        Msg::ChartRenderer => "图表渲染",
+        Msg::FitIndicators => "价格轴适配指标",
+        Msg::CandleTooltip => "十字线处显示K线提示",
+        Msg::KeyboardShortcuts => "键盘快捷键",
+        Msg::ShortcutTabsHint => "标签页快捷键仅在紧凑模式下生效",
+        Msg::PressKey => "请按一个键…",
+        Msg::ResetDefaults => "恢复默认",
+        Msg::Clear => "清除",
+        Msg::NoLiquidations => "暂无强平",
+        Msg::NoWhalePrints => "暂无巨鲸成交",
+        Msg::WaitingBook => "正在等待首个订单簿快照…",
+        Msg::WaitingCandles => "正在等待K线历史…",
+        Msg::WaitingDepth => "正在等待市场深度…",
+        Msg::WaitingTrades => "正在等待首笔成交…",
+        Msg::TourSymbolTitle => "选择市场",
+        Msg::TourSymbolBody => "在这里搜索交易对，收藏后会加入行情滚动条。",
+        Msg::TourOrderTitle => "模拟交易",
+        Msg::TourOrderBody => "在这里下模拟订单。点击订单簿中的价格即可填入下单面板。",
+        Msg::TourAlertsTitle => "价格提醒",
+        Msg::TourAlertsBody => "在图表或订单簿的价格上右键即可设置提醒。触发的提醒会以通知显示。",
+        Msg::TourNext => "下一步",
+        Msg::TourDone => "完成",
+        Msg::TourSkip => "跳过引导",
+        Msg::ShowTour => "再次显示引导",
+    }
+}
+
+/// Reactive translation handle; reads re-run when the locale changes
+#[derive(Clone, Copy)]
+pub struct I18n {
+    pub locale: Memo<Locale>,
+}
+
+impl I18n {
+    /// Follow the user's choice, or `fallback` while it's `None` (automatic)
+    pub fn new(choice: Signal<Option<Locale>>, fallback: Locale) -> Self {
+        Self {
+            locale: Memo::new(move |_| choice.get().unwrap_or(fallback)),
+        }
+    }
+
+    pub fn t(&self, msg: Msg) -> &'static str {
+        msg.text(self.locale.get())
+    }
+
+    pub fn side(&self, side: TradeSide) -> &'static str {
+        self.t(match side {
+            TradeSide::Buy => Msg::Buy,
+            TradeSide::Sell => Msg::Sell,
+        })
+    }
+
+    pub fn number(&self, value: f64, decimals: usize) -> String {
+        self.locale.get().format_number(value, decimals)
+    }
+
+    /// Like [`Self::number`] with an explicit `+` on positive values
+    pub fn signed(&self, value: f64, decimals: usize) -> String {
+        let text = self.number(value, decimals);
+        if value > 0.0 { format!("+{}", text) } else { text }
+    }
+
+    /// Price at the symbol's tick precision
+    pub fn price(&self, price: f64, format: TickFormat) -> String {
+        self.number(price, format.price_decimals)
+    }
+
+    /// Quantity at the symbol's lot precision
+    pub fn quantity(&self, qty: f64, format: TickFormat) -> String {
+        self.number(qty, format.quantity_decimals)
+    }
+
+    pub fn date(&self, timestamp_ms: i64) -> String {
+        self.locale.get().format_date(timestamp_ms)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_number_grouping() {
+        assert_eq!(Locale::En.format_number(1234567.891, 2), "1,234,567.89");
+        assert_eq!(Locale::De.format_number(1234567.891, 2), "1.234.567,89");
+        assert_eq!(Locale::Ja.format_number(-950.5, 1), "-950.5");
+        assert_eq!(Locale::En.format_number(999.0, 0), "999");
+        assert_eq!(Locale::En.format_number(-0.0001, 2), "0.00");
+        assert_eq!(Locale::De.format_number(f64::NAN, 2), "—");
+    }
+
+    #[test]
+    fn test_from_tag() {
+        assert_eq!(Locale::from_tag("de-AT"), Some(Locale::De));
+        assert_eq!(Locale::from_tag("zh_CN"), Some(Locale::Zh));
+        assert_eq!(Locale::from_tag("JA"), Some(Locale::Ja));
+        assert_eq!(Locale::from_tag("fr-FR"), None);
+    }
+
+    #[test]
+    fn test_date_order() {
+        // 2024-03-05 12:00 UTC
+        let ts = 1_709_640_000_000;
+        assert_eq!(Locale::En.format_date(ts), "Mar 5, 2024");
+        assert_eq!(Locale::De.format_date(ts), "05.03.2024");
+        assert_eq!(Locale::Ja.format_date(ts), "2024/03/05");
+    }
+}
@@ -0,0 +1,320 @@
+//! Trade-flow indicators maintained incrementally as trades arrive
+
+use crate::{market::BASE_INTERVAL, MAX_BASE_CANDLES};
+use dash_core::{Candle, Trade, TradeSide};
+use leptos::prelude::*;
+use std::collections::VecDeque;
+
+/// Period of the last-price EMA (in trades)
+pub const EMA_PERIOD: usize = 20;
+/// Rolling window for trade count and buy/sell ratio
+pub const FLOW_WINDOW_MS: i64 = 60_000;
+/// Width of one cumulative volume delta bucket
+pub const CVD_BUCKET_MS: i64 = 10_000;
+/// Span of the cumulative volume delta series
+pub const CVD_WINDOW_MS: i64 = 5 * 60_000;
+
+/// Running statistics updated in O(1) (amortized) per trade
+#[derive(Debug, Clone, PartialEq)]
+pub struct TradeFlow {
+    ema: Option<f64>,
+    alpha: f64,
+    /// (timestamp ms, side, quantity) inside the rolling window, oldest first
+    window: VecDeque<(i64, TradeSide, f64)>,
+    buy_volume: f64,
+    sell_volume: f64,
+    /// (bucket start ms, buy − sell volume) over the CVD window, oldest
+    /// first and without gaps
+    delta_buckets: VecDeque<(i64, f64)>,
+    /// (base candle open ms, buy − sell volume) for every minute that
+    /// traded, oldest first, for per-candle delta on the chart
+    minute_deltas: VecDeque<(i64, f64)>,
+}
+
+impl TradeFlow {
+    pub fn new(ema_period: usize) -> Self {
+        Self {
+            ema: None,
+            alpha: 2.0 / (ema_period.max(1) as f64 + 1.0),
+            window: VecDeque::new(),
+            buy_volume: 0.0,
+            sell_volume: 0.0,
+            delta_buckets: VecDeque::new(),
+            minute_deltas: VecDeque::new(),
+        }
+    }
+
+    /// Fold one trade into every indicator
+    pub fn ingest(&mut self, trade: &Trade) {
+        let price = trade.price.as_f64();
+        self.ema = Some(match self.ema {
+            Some(ema) => ema + self.alpha * (price - ema),
+            None => price,
+        });
+
+        let ts = trade.timestamp.timestamp_millis();
+        let qty = trade.quantity.as_f64();
+        match trade.side {
+            TradeSide::Buy => self.buy_volume += qty,
+            TradeSide::Sell => self.sell_volume += qty,
+        }
+        self.window.push_back((ts, trade.side, qty));
+
+        let delta = match trade.side {
+            TradeSide::Buy => qty,
+            TradeSide::Sell => -qty,
+        };
+        self.add_delta(ts, delta);
+        self.add_minute_delta(ts, delta);
+        self.prune(ts);
+    }
+
+    /// Credit `delta` to the bucket holding `ts`, opening empty buckets for
+    /// any quiet stretch since the last one
+    fn add_delta(&mut self, ts: i64, delta: f64) {
+        let bucket = ts.div_euclid(CVD_BUCKET_MS) * CVD_BUCKET_MS;
+        match self.delta_buckets.back_mut() {
+            // Late prints land in the newest bucket rather than reopening history
+            Some((last, value)) if *last >= bucket => *value += delta,
+            Some(&mut (last, _)) => {
+                let first_new = (last + CVD_BUCKET_MS).max(bucket - CVD_WINDOW_MS);
+                let mut start = first_new;
+                while start < bucket {
+                    self.delta_buckets.push_back((start, 0.0));
+                    start += CVD_BUCKET_MS;
+                }
+                self.delta_buckets.push_back((bucket, delta));
+            }
+            None => self.delta_buckets.push_back((bucket, delta)),
+        }
+    }
+
+    /// Credit `delta` to the base candle holding `ts`
+    fn add_minute_delta(&mut self, ts: i64, delta: f64) {
+        let minute = BASE_INTERVAL.bucket_start(ts);
+        match self.minute_deltas.back_mut() {
+            // Late prints land in the newest minute, as with the buckets
+            Some((last, value)) if *last >= minute => *value += delta,
+            _ => {
+                self.minute_deltas.push_back((minute, delta));
+                if self.minute_deltas.len() > MAX_BASE_CANDLES {
+                    self.minute_deltas.pop_front();
+                }
+            }
+        }
+    }
+
+    /// Drop trades that fell out of the window as of `now` (ms)
+    pub fn prune(&mut self, now: i64) {
+        while let Some(&(ts, side, qty)) = self.window.front() {
+            if now - ts < FLOW_WINDOW_MS {
+                break;
+            }
+            match side {
+                TradeSide::Buy => self.buy_volume -= qty,
+                TradeSide::Sell => self.sell_volume -= qty,
+            }
+            self.window.pop_front();
+        }
+        if self.window.is_empty() {
+            // Reset accumulated float drift
+            self.buy_volume = 0.0;
+            self.sell_volume = 0.0;
+        }
+
+        // Keep the delta series scrolling through quiet spells
+        if !self.delta_buckets.is_empty() {
+            self.add_delta(now, 0.0);
+        }
+        while let Some(&(start, _)) = self.delta_buckets.front() {
+            if now - start < CVD_WINDOW_MS {
+                break;
+            }
+            self.delta_buckets.pop_front();
+        }
+    }
+
+    pub fn ema(&self) -> Option<f64> {
+        self.ema
+    }
+
+    /// Buy share of windowed volume (0 to 1, 0.5 when idle)
+    pub fn buy_ratio(&self) -> f64 {
+        let total = self.buy_volume + self.sell_volume;
+        if total <= 0.0 { 0.5 } else { (self.buy_volume / total).clamp(0.0, 1.0) }
+    }
+
+    /// Trades inside the window
+    pub fn trade_count(&self) -> usize {
+        self.window.len()
+    }
+
+    /// Cumulative volume delta at the close of each bucket, starting from
+    /// zero at the beginning of the window
+    pub fn cvd(&self) -> Vec<f64> {
+        self.delta_buckets
+            .iter()
+            .scan(0.0, |sum, &(_, delta)| {
+                *sum += delta;
+                Some(*sum)
+            })
+            .collect()
+    }
+
+    /// Buy − sell volume traded inside each of `candles` (`None` for bars
+    /// that closed before the first recorded trade)
+    pub fn candle_deltas(&self, candles: &[Candle]) -> Vec<Option<f64>> {
+        let Some(&(first, _)) = self.minute_deltas.front() else {
+            return vec![None; candles.len()];
+        };
+        candles
+            .iter()
+            .map(|candle| {
+                let end = candle.timestamp + candle.interval.as_millis();
+                if end <= first {
+                    return None;
+                }
+                let from = self.minute_deltas.partition_point(|&(t, _)| t < candle.timestamp);
+                let to = self.minute_deltas.partition_point(|&(t, _)| t < end);
+                Some(self.minute_deltas.range(from..to).map(|&(_, delta)| delta).sum())
+            })
+            .collect()
+    }
+}
+
+impl Default for TradeFlow {
+    fn default() -> Self {
+        Self::new(EMA_PERIOD)
+    }
+}
+
+/// Memoized views over [`TradeFlow`]; subscribers only rerun when a value
+/// actually changes
+#[derive(Clone, Copy)]
+pub struct Indicators {
+    flow: RwSignal<TradeFlow>,
+    /// EMA of the last traded price
+    pub ema: Memo<Option<f64>>,
+    /// Rolling 1m buy volume share (0 to 1)
+    pub buy_ratio: Memo<f64>,
+    /// Trades in the last minute
+    pub trade_count_1m: Memo<usize>,
+    /// Cumulative volume delta per bucket over the last five minutes
+    pub cvd: Memo<Vec<f64>>,
+}
+
+impl Indicators {
+    pub fn new() -> Self {
+        let flow = RwSignal::new(TradeFlow::default());
+        Self {
+            flow,
+            ema: Memo::new(move |_| flow.with(TradeFlow::ema)),
+            buy_ratio: Memo::new(move |_| flow.with(TradeFlow::buy_ratio)),
+            trade_count_1m: Memo::new(move |_| flow.with(TradeFlow::trade_count)),
+            cvd: Memo::new(move |_| flow.with(TradeFlow::cvd)),
+        }
+    }
+
+    /// Buy − sell volume inside each of `candles` (tracked)
+    pub fn candle_deltas(&self, candles: &[Candle]) -> Vec<Option<f64>> {
+        self.flow.with(|flow| flow.candle_deltas(candles))
+    }
+
+    pub fn ingest(&self, trade: &Trade) {
+        self.flow.update(|flow| flow.ingest(trade));
+    }
+
+    /// Expire old trades when the tape goes quiet (drive from a ~1s interval)
+    pub fn prune(&self, now: i64) {
+        self.flow.update(|flow| flow.prune(now));
+    }
+
+    pub fn reset(&self) {
+        self.flow.set(TradeFlow::default());
+    }
+}
+
+impl Default for Indicators {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use dash_core::Symbol;
+
+    fn trade(ts: i64, side: TradeSide, price: f64, qty: f64) -> Trade {
+        let mut trade = Trade::new(Symbol::default(), price, qty, side);
+        trade.timestamp = chrono::DateTime::from_timestamp_millis(ts).unwrap();
+        trade
+    }
+
+    #[test]
+    fn test_rolling_window() {
+        let mut flow = TradeFlow::default();
+        flow.ingest(&trade(0, TradeSide::Buy, 100.0, 3.0));
+        flow.ingest(&trade(30_000, TradeSide::Sell, 100.0, 1.0));
+
+        assert_eq!(flow.trade_count(), 2);
+        assert_eq!(flow.buy_ratio(), 0.75);
+
+        flow.ingest(&trade(60_000, TradeSide::Sell, 100.0, 1.0));
+        assert_eq!(flow.trade_count(), 2);
+        assert_eq!(flow.buy_ratio(), 0.0);
+
+        flow.prune(200_000);
+        assert_eq!(flow.trade_count(), 0);
+        assert_eq!(flow.buy_ratio(), 0.5);
+    }
+
+    #[test]
+    fn test_cvd_buckets() {
+        let mut flow = TradeFlow::default();
+        flow.ingest(&trade(1_000, TradeSide::Buy, 100.0, 3.0));
+        flow.ingest(&trade(5_000, TradeSide::Sell, 100.0, 1.0));
+        assert_eq!(flow.cvd(), vec![2.0]);
+
+        // A quiet 20s leaves flat buckets in between
+        flow.ingest(&trade(31_000, TradeSide::Sell, 100.0, 4.0));
+        assert_eq!(flow.cvd(), vec![2.0, 2.0, 2.0, -2.0]);
+
+        // Buckets older than the window drop off the front
+        flow.ingest(&trade(CVD_WINDOW_MS + 10_000, TradeSide::Buy, 100.0, 1.0));
+        assert_eq!(flow.cvd().len(), (CVD_WINDOW_MS / CVD_BUCKET_MS) as usize);
+        assert_eq!(flow.cvd().last(), Some(&-3.0));
+
+        // A quiet tape flattens out rather than freezing
+        flow.prune(CVD_WINDOW_MS * 3);
+        assert!(flow.cvd().iter().all(|&v| v == 0.0));
+        assert_eq!(flow.cvd().len(), (CVD_WINDOW_MS / CVD_BUCKET_MS) as usize);
+    }
+
+    #[test]
+    fn test_candle_deltas() {
+        use dash_core::{CandleInterval, Symbol};
+
+        let mut flow = TradeFlow::default();
+        flow.ingest(&trade(61_000, TradeSide::Buy, 100.0, 3.0));
+        flow.ingest(&trade(150_000, TradeSide::Sell, 100.0, 1.0));
+        flow.ingest(&trade(200_000, TradeSide::Sell, 100.0, 0.5));
+
+        let bar = |minute: i64, interval| Candle::new(Symbol::default(), interval, minute * 60_000, 100.0);
+        let m1 = [bar(0, CandleInterval::M1), bar(1, CandleInterval::M1), bar(2, CandleInterval::M1), bar(4, CandleInterval::M1)];
+        assert_eq!(flow.candle_deltas(&m1), vec![None, Some(3.0), Some(-1.0), Some(0.0)]);
+
+        let m5 = [bar(0, CandleInterval::M5)];
+        assert_eq!(flow.candle_deltas(&m5), vec![Some(1.5)]);
+    }
+
+    #[test]
+    fn test_ema() {
+        let mut flow = TradeFlow::new(3);
+        flow.ingest(&trade(0, TradeSide::Buy, 100.0, 1.0));
+        assert_eq!(flow.ema(), Some(100.0));
+
+        flow.ingest(&trade(1, TradeSide::Buy, 110.0, 1.0));
+        assert_eq!(flow.ema(), Some(105.0));
+    }
+}
@@ -0,0 +1,601 @@
+//! Dashboard layout model: panel geometry on a fixed grid plus named presets
+
+use crate::Panel;
+use serde::{Deserialize, Serialize};
+
+/// Grid columns available to panels
+pub const GRID_COLUMNS: u16 = 12;
+/// Grid rows available to panels
+pub const GRID_ROWS: u16 = 12;
+
+/// Named layout presets
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DashboardLayout {
+    #[default]
+    Standard,
+    ChartFocused,
+    OrderBookFocused,
+    /// Preset geometry modified by the user
+    Custom,
+}
+
+impl DashboardLayout {
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Standard => "Standard",
+            Self::ChartFocused => "Chart Focused",
+            Self::OrderBookFocused => "Order Book Focused",
+            Self::Custom => "Custom",
+        }
+    }
+
+    /// URL/storage code (matches the serde representation)
+    pub fn code(&self) -> &'static str {
+        match self {
+            Self::Standard => "standard",
+            Self::ChartFocused => "chart_focused",
+            Self::OrderBookFocused => "order_book_focused",
+            Self::Custom => "custom",
+        }
+    }
+
+    pub fn from_code(code: &str) -> Option<Self> {
+        [Self::Standard, Self::ChartFocused, Self::OrderBookFocused, Self::Custom]
+            .into_iter()
+            .find(|l| l.code() == code)
+    }
+
+    /// Presets selectable from the UI (excludes `Custom`)
+    pub fn presets() -> &'static [Self] {
+        &[Self::Standard, Self::ChartFocused, Self::OrderBookFocused]
+    }
+
+    /// Geometry for this preset (`Custom` falls back to `Standard`)
+    pub fn geometry(&self) -> LayoutState {
+        let [orderbook, candle_chart, depth_chart, trades, positions, account, order_entry] = match self {
+            Self::Standard | Self::Custom => [
+                GridRect::new(0, 0, 3, 12),
+                GridRect::new(3, 0, 6, 7),
+                GridRect::new(3, 7, 3, 5),
+                GridRect::new(9, 0, 3, 5),
+                GridRect::new(6, 7, 3, 5),
+                GridRect::new(9, 9, 3, 3),
+                GridRect::new(9, 5, 3, 4),
+            ],
+            Self::ChartFocused => [
+                GridRect::new(9, 0, 3, 6),
+                GridRect::new(0, 0, 9, 9),
+                GridRect::new(0, 9, 3, 3),
+                GridRect::new(9, 6, 3, 4),
+                GridRect::new(3, 9, 3, 3),
+                GridRect::new(9, 10, 3, 2),
+                GridRect::new(6, 9, 3, 3),
+            ],
+            Self::OrderBookFocused => [
+                GridRect::new(0, 0, 5, 8),
+                GridRect::new(5, 0, 7, 6),
+                GridRect::new(5, 6, 4, 4),
+                GridRect::new(0, 8, 5, 4),
+                GridRect::new(5, 10, 4, 2),
+                GridRect::new(9, 10, 3, 2),
+                GridRect::new(9, 6, 3, 4),
+            ],
+        };
+
+        LayoutState {
+            preset: *self,
+            orderbook: PanelLayout::new(orderbook),
+            trades: PanelLayout::new(trades),
+            depth_chart: PanelLayout::new(depth_chart),
+            candle_chart: PanelLayout::new(candle_chart),
+            positions: PanelLayout::new(positions),
+            account: PanelLayout::new(account),
+            order_entry: PanelLayout::new(order_entry),
+        }
+    }
+}
+
+/// Tabs shown instead of the grid in compact mode
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum CompactTab {
+    #[default]
+    Chart,
+    Book,
+    Trades,
+    Account,
+}
+
+impl CompactTab {
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Chart => "Chart",
+            Self::Book => "Book",
+            Self::Trades => "Trades",
+            Self::Account => "Account",
+        }
+    }
+
+    /// Panels stacked in this tab
+    pub fn panels(&self) -> &'static [Panel] {
+        match self {
+            Self::Chart => &[Panel::CandleChart, Panel::DepthChart],
+            Self::Book => &[Panel::OrderBook],
+            Self::Trades => &[Panel::Trades],
+            Self::Account => &[Panel::OrderEntry, Panel::Positions, Panel::Account],
+        }
+    }
+
+    pub fn all() -> &'static [Self] {
+        &[Self::Chart, Self::Book, Self::Trades, Self::Account]
+    }
+}
+
+/// Viewport size class picked from the window width
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Breakpoint {
+    /// Single column of tabs
+    Mobile,
+    /// Two-column stack of the visible panels
+    Tablet,
+    /// Full 12-column grid
+    #[default]
+    Desktop,
+}
+
+impl Breakpoint {
+    /// Narrowest viewport (CSS px) laid out as a tablet
+    pub const TABLET_MIN_WIDTH: f64 = 768.0;
+    /// Narrowest viewport (CSS px) given the full grid
+    pub const DESKTOP_MIN_WIDTH: f64 = 1200.0;
+
+    pub fn from_width(width: f64) -> Self {
+        if width >= Self::DESKTOP_MIN_WIDTH {
+            Self::Desktop
+        } else if width >= Self::TABLET_MIN_WIDTH {
+            Self::Tablet
+        } else {
+            Self::Mobile
+        }
+    }
+
+    pub fn css_class(&self) -> &'static str {
+        match self {
+            Self::Mobile => "bp-mobile",
+            Self::Tablet => "bp-tablet",
+            Self::Desktop => "bp-desktop",
+        }
+    }
+}
+
+/// Panel rectangle in grid cells (zero-based origin)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GridRect {
+    pub col: u16,
+    pub row: u16,
+    pub width: u16,
+    pub height: u16,
+}
+
+impl GridRect {
+    pub const fn new(col: u16, row: u16, width: u16, height: u16) -> Self {
+        Self { col, row, width, height }
+    }
+
+    /// Clamp into the grid, keeping at least one cell in each direction
+    pub fn clamped(self) -> Self {
+        let col = self.col.min(GRID_COLUMNS - 1);
+        let row = self.row.min(GRID_ROWS - 1);
+        Self {
+            col,
+            row,
+            width: self.width.clamp(1, GRID_COLUMNS - col),
+            height: self.height.clamp(1, GRID_ROWS - row),
+        }
+    }
+
+    /// Same size, origin offset by a cell delta and kept inside the grid
+    pub fn shifted(self, d_col: i32, d_row: i32) -> Self {
+        let shift = |origin: u16, delta: i32, size: u16, limit: u16| {
+            (i32::from(origin) + delta).clamp(0, i32::from(limit.saturating_sub(size))) as u16
+        };
+        Self {
+            col: shift(self.col, d_col, self.width, GRID_COLUMNS),
+            row: shift(self.row, d_row, self.height, GRID_ROWS),
+            ..self
+        }
+    }
+
+    /// Same origin, size changed by a cell delta (at least one cell)
+    pub fn grown(self, d_width: i32, d_height: i32) -> Self {
+        let grow = |size: u16, delta: i32| (i32::from(size) + delta).max(1) as u16;
+        Self {
+            width: grow(self.width, d_width),
+            height: grow(self.height, d_height),
+            ..self
+        }
+        .clamped()
+    }
+
+    pub fn contains(&self, col: u16, row: u16) -> bool {
+        (self.col..self.col + self.width).contains(&col) && (self.row..self.row + self.height).contains(&row)
+    }
+
+    /// Do two rectangles share any cell?
+    pub fn overlaps(&self, other: &GridRect) -> bool {
+        self.col < other.col + other.width
+            && other.col < self.col + self.width
+            && self.row < other.row + other.height
+            && other.row < self.row + self.height
+    }
+
+    /// CSS `grid-area` value (CSS grid lines are one-based)
+    pub fn grid_area(&self) -> String {
+        format!(
+            "{} / {} / span {} / span {}",
+            self.row + 1,
+            self.col + 1,
+            self.height,
+            self.width
+        )
+    }
+}
+
+/// Geometry and visibility of a single panel
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PanelLayout {
+    pub rect: GridRect,
+    pub visible: bool,
+}
+
+impl PanelLayout {
+    pub const fn new(rect: GridRect) -> Self {
+        Self { rect, visible: true }
+    }
+
+    /// Inline style for the panel container
+    pub fn style(&self) -> String {
+        if self.visible {
+            format!("grid-area: {}", self.rect.grid_area())
+        } else {
+            "display: none".to_string()
+        }
+    }
+}
+
+/// Complete dashboard layout (panels missing from stored JSON get the
+/// standard geometry)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct LayoutState {
+    pub preset: DashboardLayout,
+    pub orderbook: PanelLayout,
+    pub trades: PanelLayout,
+    pub depth_chart: PanelLayout,
+    pub candle_chart: PanelLayout,
+    pub positions: PanelLayout,
+    pub account: PanelLayout,
+    pub order_entry: PanelLayout,
+}
+
+impl Default for LayoutState {
+    fn default() -> Self {
+        DashboardLayout::Standard.geometry()
+    }
+}
+
+impl LayoutState {
+    /// Storage key used for persistence
+    pub const STORAGE_KEY: &'static str = "dash.layout";
+
+    pub fn panel(&self, panel: Panel) -> &PanelLayout {
+        match panel {
+            Panel::OrderBook => &self.orderbook,
+            Panel::Trades => &self.trades,
+            Panel::DepthChart => &self.depth_chart,
+            Panel::CandleChart => &self.candle_chart,
+            Panel::Positions => &self.positions,
+            Panel::Account => &self.account,
+            Panel::OrderEntry => &self.order_entry,
+        }
+    }
+
+    pub fn panel_mut(&mut self, panel: Panel) -> &mut PanelLayout {
+        match panel {
+            Panel::OrderBook => &mut self.orderbook,
+            Panel::Trades => &mut self.trades,
+            Panel::DepthChart => &mut self.depth_chart,
+            Panel::CandleChart => &mut self.candle_chart,
+            Panel::Positions => &mut self.positions,
+            Panel::Account => &mut self.account,
+            Panel::OrderEntry => &mut self.order_entry,
+        }
+    }
+
+    /// Resize a panel (in grid cells); marks the layout as custom
+    pub fn resize(&mut self, panel: Panel, width: u16, height: u16) {
+        let rect = &mut self.panel_mut(panel).rect;
+        *rect = GridRect { width, height, ..*rect }.clamped();
+        self.preset = DashboardLayout::Custom;
+    }
+
+    /// Move a panel's origin (in grid cells); marks the layout as custom
+    pub fn move_to(&mut self, panel: Panel, col: u16, row: u16) {
+        let rect = &mut self.panel_mut(panel).rect;
+        *rect = GridRect { col, row, ..*rect }.clamped();
+        self.preset = DashboardLayout::Custom;
+    }
+
+    /// Visible panel covering a cell
+    pub fn panel_at(&self, col: u16, row: u16) -> Option<Panel> {
+        Panel::all()
+            .iter()
+            .copied()
+            .find(|&p| self.panel(p).visible && self.panel(p).rect.contains(col, row))
+    }
+
+    /// Place a panel at `rect`; marks the layout as custom
+    pub fn place(&mut self, panel: Panel, rect: GridRect) {
+        self.panel_mut(panel).rect = rect.clamped();
+        self.preset = DashboardLayout::Custom;
+    }
+
+    /// Dock `panel` into `target`'s slot, moving `target` into the vacated
+    /// one. Within a column of stacked panels `panel` is moved to `target`'s
+    /// place in the stack instead, every panel keeping its height.
+    pub fn dock(&mut self, panel: Panel, target: Panel) {
+        if panel == target {
+            return;
+        }
+        if let Some(mut stack) = self.stack_of(panel).filter(|stack| stack.contains(&target)) {
+            let top = self.panel(stack[0]).rect.row;
+            let to = stack.iter().position(|&p| p == target).unwrap_or_default();
+            stack.retain(|&p| p != panel);
+            stack.insert(to, panel);
+            let mut row = top;
+            for p in stack {
+                let rect = &mut self.panel_mut(p).rect;
+                rect.row = row;
+                row += rect.height;
+            }
+        } else {
+            let a = self.panel(panel).rect;
+            let b = self.panel(target).rect;
+            self.panel_mut(panel).rect = b;
+            self.panel_mut(target).rect = a;
+        }
+        self.preset = DashboardLayout::Custom;
+    }
+
+    /// Visible panels spanning the same columns as `panel` and stacked edge
+    /// to edge with it, top first (`None` when it stands alone)
+    fn stack_of(&self, panel: Panel) -> Option<Vec<Panel>> {
+        let rect = self.panel(panel).rect;
+        let mut column: Vec<Panel> = Panel::all()
+            .iter()
+            .copied()
+            .filter(|&p| {
+                let other = self.panel(p);
+                other.visible && other.rect.col == rect.col && other.rect.width == rect.width
+            })
+            .collect();
+        column.sort_by_key(|&p| self.panel(p).rect.row);
+
+        // Split into runs with no gap between neighbours
+        let mut runs: Vec<Vec<Panel>> = Vec::new();
+        for p in column {
+            let row = self.panel(p).rect.row;
+            match runs.last_mut() {
+                Some(run) if run.last().is_some_and(|&last| {
+                    let above = self.panel(last).rect;
+                    above.row + above.height == row
+                }) =>
+                {
+                    run.push(p)
+                }
+                _ => runs.push(vec![p]),
+            }
+        }
+        runs.into_iter().find(|run| run.len() > 1 && run.contains(&panel))
+    }
+
+    /// Serialize for persistence
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).unwrap_or_default()
+    }
+
+    /// Restore from persisted JSON, re-clamping every rect
+    pub fn from_json(json: &str) -> Option<Self> {
+        serde_json::from_str::<Self>(json).ok().map(Self::clamped)
+    }
+
+    /// Force every rect back inside the grid
+    pub fn clamped(mut self) -> Self {
+        for &panel in Panel::all() {
+            let rect = &mut self.panel_mut(panel).rect;
+            *rect = rect.clamped();
+        }
+        self
+    }
+}
+
+// ============================================================================
+// POINTER INTERACTION
+// ============================================================================
+
+/// Grid cell under a point, given the grid's pixel size (clamped to the grid)
+pub fn grid_cell(x: f64, y: f64, width: f64, height: f64) -> (u16, u16) {
+    let cell = |pos: f64, size: f64, cells: u16| {
+        if size <= 0.0 {
+            return 0;
+        }
+        ((pos / size * f64::from(cells)).floor().max(0.0) as u16).min(cells - 1)
+    };
+    (cell(x, width, GRID_COLUMNS), cell(y, height, GRID_ROWS))
+}
+
+/// What a pointer drag does to a panel
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DragMode {
+    /// Dragged by its header
+    Move,
+    /// Dragged by its bottom-right corner
+    Resize,
+}
+
+/// In-progress panel drag, snapped to whole cells
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PanelDrag {
+    pub panel: Panel,
+    pub mode: DragMode,
+    /// Geometry when the drag started
+    pub origin: GridRect,
+    /// Cell the pointer went down on
+    pub start: (u16, u16),
+    /// Cell currently under the pointer
+    pub current: (u16, u16),
+}
+
+impl PanelDrag {
+    pub fn new(panel: Panel, mode: DragMode, origin: GridRect, start: (u16, u16)) -> Self {
+        Self { panel, mode, origin, start, current: start }
+    }
+
+    /// Rect the panel would take if dropped now
+    pub fn preview(&self) -> GridRect {
+        let d_col = i32::from(self.current.0) - i32::from(self.start.0);
+        let d_row = i32::from(self.current.1) - i32::from(self.start.1);
+        match self.mode {
+            DragMode::Move => self.origin.shifted(d_col, d_row),
+            DragMode::Resize => self.origin.grown(d_col, d_row),
+        }
+    }
+
+    /// Panel a move would dock into (pointer over another visible panel)
+    pub fn dock_target(&self, layout: &LayoutState) -> Option<Panel> {
+        if self.mode != DragMode::Move {
+            return None;
+        }
+        layout
+            .panel_at(self.current.0, self.current.1)
+            .filter(|&p| p != self.panel)
+    }
+
+    /// Commit the drag into the layout
+    pub fn apply(&self, layout: &mut LayoutState) {
+        if self.current == self.start {
+            return;
+        }
+        match self.dock_target(layout) {
+            Some(target) => layout.dock(self.panel, target),
+            None => layout.place(self.panel, self.preview()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_presets_fit_grid_without_overlap() {
+        for preset in DashboardLayout::presets() {
+            let layout = preset.geometry();
+            let rects: Vec<GridRect> = Panel::all().iter().map(|&p| layout.panel(p).rect).collect();
+
+            for (i, a) in rects.iter().enumerate() {
+                assert_eq!(*a, a.clamped(), "{:?} out of bounds", preset);
+                for b in &rects[i + 1..] {
+                    assert!(!a.overlaps(b), "{:?} panels overlap", preset);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_resize_clamps_and_marks_custom() {
+        let mut layout = LayoutState::default();
+        layout.resize(Panel::Trades, 10, 0);
+
+        assert_eq!(layout.trades.rect, GridRect::new(9, 0, 3, 1));
+        assert_eq!(layout.preset, DashboardLayout::Custom);
+    }
+
+    #[test]
+    fn test_layout_json_roundtrip() {
+        let mut layout = DashboardLayout::ChartFocused.geometry();
+        layout.depth_chart.visible = false;
+
+        let restored = LayoutState::from_json(&layout.to_json()).unwrap();
+        assert_eq!(restored, layout);
+        assert!(LayoutState::from_json("not json").is_none());
+    }
+
+    #[test]
+    fn test_drag_move_and_resize() {
+        let mut layout = LayoutState::default();
+        let origin = layout.positions.rect;
+
+        let mut drag = PanelDrag::new(Panel::Positions, DragMode::Resize, origin, (7, 8));
+        drag.current = (8, 20);
+        assert_eq!(drag.preview(), GridRect::new(6, 7, 4, 5));
+
+        // Moves keep the panel's size and stay on the grid
+        let mut drag = PanelDrag::new(Panel::Positions, DragMode::Move, origin, (7, 8));
+        drag.current = (0, 0);
+        assert_eq!(drag.preview(), GridRect::new(0, 0, 3, 5));
+
+        // Dropping over another panel swaps the two slots
+        let orderbook = layout.orderbook.rect;
+        drag.apply(&mut layout);
+        assert_eq!(layout.positions.rect, orderbook);
+        assert_eq!(layout.orderbook.rect, origin);
+        assert_eq!(layout.preset, DashboardLayout::Custom);
+    }
+
+    #[test]
+    fn test_dock_reorders_a_column_stack() {
+        // Standard's right column: trades (5 rows), order entry (4), account (3)
+        let mut layout = LayoutState::default();
+        layout.dock(Panel::Account, Panel::Trades);
+        assert_eq!(layout.account.rect, GridRect::new(9, 0, 3, 3));
+        assert_eq!(layout.trades.rect, GridRect::new(9, 3, 3, 5));
+        assert_eq!(layout.order_entry.rect, GridRect::new(9, 8, 3, 4));
+        assert_eq!(layout.preset, DashboardLayout::Custom);
+
+        // Moving down lands below the target
+        layout.dock(Panel::Account, Panel::OrderEntry);
+        assert_eq!(layout.trades.rect, GridRect::new(9, 0, 3, 5));
+        assert_eq!(layout.order_entry.rect, GridRect::new(9, 5, 3, 4));
+        assert_eq!(layout.account.rect, GridRect::new(9, 9, 3, 3));
+
+        // A hidden panel leaves the stack; a panel from another column swaps
+        layout.order_entry.visible = false;
+        let (account, trades) = (layout.account.rect, layout.trades.rect);
+        layout.dock(Panel::Account, Panel::Trades);
+        assert_eq!((layout.account.rect, layout.trades.rect), (trades, account));
+        let (depth, trades) = (layout.depth_chart.rect, layout.trades.rect);
+        layout.dock(Panel::DepthChart, Panel::Trades);
+        assert_eq!((layout.depth_chart.rect, layout.trades.rect), (trades, depth));
+    }
+
+    #[test]
+    fn test_breakpoints() {
+        assert_eq!(Breakpoint::from_width(375.0), Breakpoint::Mobile);
+        assert_eq!(Breakpoint::from_width(Breakpoint::TABLET_MIN_WIDTH), Breakpoint::Tablet);
+        assert_eq!(Breakpoint::from_width(1199.0), Breakpoint::Tablet);
+        assert_eq!(Breakpoint::from_width(1920.0), Breakpoint::Desktop);
+    }
+
+    #[test]
+    fn test_grid_cell() {
+        assert_eq!(grid_cell(0.0, 0.0, 1200.0, 600.0), (0, 0));
+        assert_eq!(grid_cell(150.0, 290.0, 1200.0, 600.0), (1, 5));
+        assert_eq!(grid_cell(5000.0, -10.0, 1200.0, 600.0), (11, 0));
+    }
+
+    #[test]
+    fn test_grid_area() {
+        assert_eq!(GridRect::new(3, 8, 6, 4).grid_area(), "9 / 4 / span 4 / span 6");
+    }
+}
@@ -3,90 +3,178 @@
 //! Reactive state management for the BTC Exchange Dashboard.
 //! Uses Leptos signals for surgical DOM updates on market data changes.
 
+pub mod alerts;
+pub mod compare;
+pub mod cursor;
+pub mod drawings;
+pub mod grid;
+pub mod i18n;
+pub mod indicators;
+pub mod layout;
 pub mod market;
+pub mod notifications;
+pub mod portfolio;
+#[cfg(feature = "time-travel")]
+pub mod recorder;
+pub mod rpc;
+pub mod settings;
+pub mod shortcuts;
+pub mod sounds;
+pub mod spread;
+pub mod stale;
+pub mod storage;
+pub mod symbols;
+pub mod sync;
+pub mod telemetry;
+pub mod theme;
+pub mod url;
+pub mod whales;
 
+pub use alerts::*;
+pub use compare::CompareState;
+pub use cursor::*;
+pub use drawings::DrawingStore;
+pub use grid::*;
+pub use i18n::{I18n, Locale, Msg};
+pub use indicators::*;
+pub use layout::*;
 pub use market::*;
+pub use notifications::*;
+pub use portfolio::*;
+#[cfg(feature = "time-travel")]
+pub use recorder::*;
+pub use rpc::RpcState;
+pub use settings::{Settings, SETTINGS_VERSION};
+pub use shortcuts::*;
+pub use sounds::*;
+pub use spread::*;
+pub use stale::*;
+pub use symbols::*;
+pub use sync::{TabCoordinator, TabMessage, TabRole};
+pub use telemetry::*;
+pub use theme::*;
+pub use url::UrlState;
+pub use whales::*;
 
-use dash_core::ConnectionState;
+use dash_core::{BrickSize, CandleCheckReport, CandleInterval, ChartOverlay, ChartType, CloseReason, CompactNumberFormatter, Drawing, ConnectionState, FeedStatus, LargeNumberFormatter, OrderRejection, OrderRequest, OrderStatus, PriceScaleMode, RenderBackend, SessionAnchor, SourceStatus, Symbol, TickFormat, TimeZoneMode, TradeSide, WsMessage};
 use leptos::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
 
 /// Configuration constants
-pub const MAX_TRADES: usize = 100;
+pub const MAX_TRADES: usize = 2_000;
 pub const MAX_CANDLES: usize = 200;
+/// Base 1m candles retained for resampling (one week)
+pub const MAX_BASE_CANDLES: usize = 7 * 24 * 60;
+/// Base 1m candles retained once the chart scrolls back further (30 days)
+pub const MAX_HISTORY_BASE_CANDLES: usize = 30 * 24 * 60;
+pub const MAX_EQUITY_SAMPLES: usize = 2_000;
+/// Filled paper orders kept for the chart's entry markers
+pub const MAX_RECENT_FILLS: usize = 200;
+pub const MAX_LIQUIDATIONS: usize = 500;
+/// Price bins in the volume profile
+pub const PROFILE_BINS: usize = 48;
+/// Per-minute server VWAP snapshots kept for the chart (one session)
+pub const VWAP_TRAIL_LEN: usize = 24 * 60;
 
 // ============================================================================
 // UI STATE
 // ============================================================================
 
-/// Application theme
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
-pub enum Theme {
-    #[default]
-    Dark,
-    Light,
-}
-
-impl Theme {
-    pub fn toggle(&self) -> Self {
-        match self {
-            Self::Dark => Self::Light,
-            Self::Light => Self::Dark,
-        }
-    }
-
-    pub fn css_class(&self) -> &'static str {
-        match self {
-            Self::Dark => "theme-dark",
-            Self::Light => "theme-light",
-        }
-    }
-
-    pub fn label(&self) -> &'static str {
-        match self {
-            Self::Dark => "Dark",
-            Self::Light => "Light",
-        }
-    }
-}
-
-/// Panel visibility state
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
-pub struct PanelVisibility {
-    pub orderbook: bool,
-    pub trades: bool,
-    pub depth_chart: bool,
-    pub candle_chart: bool,
-}
-
-impl Default for PanelVisibility {
-    fn default() -> Self {
-        Self {
-            orderbook: true,
-            trades: true,
-            depth_chart: true,
-            candle_chart: true,
-        }
-    }
-}
-
 /// Global UI state
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct UiState {
     pub theme: Theme,
-    pub panels: PanelVisibility,
+    pub layout: LayoutState,
     pub compact_mode: bool,
+    /// Visible tab while in compact mode
+    pub compact_tab: CompactTab,
+    /// Shift-clicking a book price sends the order without review
+    pub fast_trading: bool,
+    /// Reduced motion plus screen reader price announcements
+    pub accessible_mode: bool,
+    /// Chosen UI language (`None` follows the browser)
+    pub locale: Option<Locale>,
+    /// Raise a toast for every whale print
+    pub whale_notifications: bool,
+    /// Current viewport size class (not persisted)
+    pub breakpoint: Breakpoint,
+    /// Finished or skipped the onboarding tour
+    pub has_seen_tour: bool,
+    /// Volume sub-pane under the candles switched off
+    pub hide_volume: bool,
+    /// Session VWAP and its bands drawn over the candles
+    pub show_vwap: bool,
+    /// How the chart draws price
+    pub chart_type: ChartType,
+    /// Renko brick or range bar size
+    pub brick_size: BrickSize,
+    /// SVG or canvas for the chart's candles and volume bars
+    pub chart_backend: RenderBackend,
+    /// Linear, log or percent price axis
+    pub price_scale: PriceScaleMode,
+    /// UTC or local time on the chart's time axis
+    pub time_zone: TimeZoneMode,
+    /// Price auto-fit includes study and VWAP bands
+    pub fit_indicators: bool,
+    /// Best bid and ask lines drawn across the chart
+    pub show_quotes: bool,
+    /// OHLCV tooltip next to the crosshair switched off
+    pub hide_tooltip: bool,
+    /// Recent trades dotted over the candles
+    pub show_prints: bool,
+    /// Cumulative volume delta sub-pane under the chart
+    pub show_cvd: bool,
+    /// Several charts side by side in place of the one (`None` = single chart)
+    pub chart_grid: Option<GridSize>,
+    /// Day start for the session open, range and VWAP
+    pub session_anchor: SessionAnchor,
 }
 
 impl Default for UiState {
     fn default() -> Self {
         Self {
             theme: Theme::Dark,
-            panels: PanelVisibility::default(),
+            layout: LayoutState::default(),
             compact_mode: false,
+            compact_tab: CompactTab::default(),
+            fast_trading: false,
+            accessible_mode: false,
+            locale: None,
+            whale_notifications: false,
+            breakpoint: Breakpoint::default(),
+            has_seen_tour: false,
+            hide_volume: false,
+            show_vwap: false,
+            chart_type: ChartType::default(),
+            brick_size: BrickSize::default(),
+            chart_backend: RenderBackend::default(),
+            price_scale: PriceScaleMode::default(),
+            time_zone: TimeZoneMode::default(),
+            fit_indicators: false,
+            show_quotes: false,
+            hide_tooltip: false,
+            show_prints: false,
+            show_cvd: false,
+            chart_grid: None,
+            session_anchor: SessionAnchor::default(),
         }
     }
 }
 
+impl UiState {
+    /// Showing the tabbed single-column view (chosen, or forced by a narrow
+    /// viewport)
+    pub fn is_compact(&self) -> bool {
+        self.compact_mode || self.breakpoint == Breakpoint::Mobile
+    }
+
+    /// Panels sit on the free-form grid (and can be dragged and resized)
+    pub fn uses_grid(&self) -> bool {
+        !self.is_compact() && self.breakpoint == Breakpoint::Desktop
+    }
+}
+
 // ============================================================================
 // APPLICATION STATE
 // ============================================================================
@@ -96,25 +184,580 @@ impl Default for UiState {
 pub struct AppState {
     /// Market data state
     pub market: MarketState,
+    /// Available symbols, their 24h stats and favorites
+    pub symbols: SymbolDirectory,
+    /// Paper-trading account state
+    pub portfolio: PortfolioState,
+    /// Latest price picked for the order ticket (click-to-trade)
+    pub ticket: RwSignal<Option<TicketFill>>,
+    /// Outgoing commands and order acknowledgements
+    pub rpc: RpcState,
+    /// Hover/selection shared across panels
+    pub cursor: CursorState,
     /// WebSocket connection state
     pub connection: RwSignal<ConnectionState>,
+    /// Server-assigned id of the live connection, for matching server logs
+    pub connection_id: RwSignal<Option<String>>,
+    /// Source the server is streaming from (backup feeds are degraded)
+    pub feed: RwSignal<Option<FeedStatus>>,
+    /// Latest health report per upstream source
+    pub sources: RwSignal<BTreeMap<String, SourceStatus>>,
+    /// Latest check of each symbol's bars against its trades
+    pub candle_checks: RwSignal<BTreeMap<Symbol, CandleCheckReport>>,
+    /// User asked to skip the reconnect backoff
+    pub reconnect_requested: RwSignal<bool>,
     /// UI state (theme, panels, etc.)
     pub ui: RwSignal<UiState>,
-    /// Current error message
-    pub error: RwSignal<Option<String>>,
+    /// Translated strings and number formatting for the active locale
+    pub i18n: I18n,
+    /// Notification center (toasts + history)
+    pub notifications: NotificationsState,
+    /// Key bindings and fired shortcut actions
+    pub shortcuts: ShortcutState,
+    /// Sound alert preferences and cues
+    pub sounds: SoundState,
+    /// Message throughput and frame timing for the status bar
+    pub telemetry: Telemetry,
+    /// Settings dialog visible
+    pub settings_open: RwSignal<bool>,
     /// Loading state
     pub loading: RwSignal<bool>,
+    /// Recent incoming messages for rewind/replay
+    #[cfg(feature = "time-travel")]
+    pub recorder: MessageRecorder,
+    /// IDs of alerts the user has dismissed (shared across tabs)
+    pub alert_acks: RwSignal<BTreeSet<String>>,
+    /// Armed price alerts
+    pub price_alerts: RwSignal<Vec<PriceAlert>>,
+    /// Recently fired alerts, oldest first
+    pub fired_alerts: RwSignal<Vec<FiredAlert>>,
+    /// Minimum trade size shown on the tape (`None` = show all)
+    pub tape_min_size: RwSignal<Option<f64>>,
+    /// Window for merging same-price trades on the tape (`None` = one row per trade)
+    pub tape_group_window: RwSignal<Option<i64>>,
+    /// Chart timeframe last picked for each symbol
+    pub chart_intervals: RwSignal<BTreeMap<Symbol, CandleInterval>>,
+    /// Moving average / band studies drawn over the candles
+    pub chart_overlays: RwSignal<Vec<ChartOverlay>>,
+    /// Trendlines, rays, boxes and retracements drawn on each symbol's chart
+    pub drawings: DrawingStore,
+    /// Second symbol overlaid on the chart as a percent line
+    pub compare: CompareState,
+    /// Charts of the multi-chart grid
+    pub grid: ChartGridState,
+    /// Panel this window shows alone (pop-out windows only)
+    pub popout: RwSignal<Option<Panel>>,
 }
 
 impl AppState {
     /// Create new application state
     pub fn new() -> Self {
+        let ui = RwSignal::new(UiState::default());
         Self {
             market: MarketState::new(),
+            symbols: SymbolDirectory::new(),
+            portfolio: PortfolioState::new(),
+            ticket: RwSignal::new(None),
+            rpc: RpcState::new(),
+            cursor: CursorState::new(),
             connection: RwSignal::new(ConnectionState::Disconnected),
-            ui: RwSignal::new(UiState::default()),
-            error: RwSignal::new(None),
+            connection_id: RwSignal::new(None),
+            feed: RwSignal::new(None),
+            sources: RwSignal::new(BTreeMap::new()),
+            candle_checks: RwSignal::new(BTreeMap::new()),
+            reconnect_requested: RwSignal::new(false),
+            ui,
+            i18n: I18n::new(Signal::derive(move || ui.with(|ui| ui.locale)), Locale::detect()),
+            notifications: NotificationsState::new(),
+            shortcuts: ShortcutState::new(),
+            sounds: SoundState::new(),
+            telemetry: Telemetry::new(),
+            settings_open: RwSignal::new(false),
             loading: RwSignal::new(false),
+            #[cfg(feature = "time-travel")]
+            recorder: MessageRecorder::new(),
+            alert_acks: RwSignal::new(BTreeSet::new()),
+            price_alerts: RwSignal::new(Vec::new()),
+            fired_alerts: RwSignal::new(Vec::new()),
+            tape_min_size: RwSignal::new(None),
+            tape_group_window: RwSignal::new(None),
+            chart_intervals: RwSignal::new(BTreeMap::new()),
+            chart_overlays: RwSignal::new(Vec::new()),
+            drawings: DrawingStore::new(),
+            compare: CompareState::new(),
+            grid: ChartGridState::new(),
+            popout: RwSignal::new(None),
+        }
+    }
+
+    // ========================================================================
+    // Message Dispatch
+    // ========================================================================
+
+    /// Entry point for every incoming market/account message
+    pub fn receive(&self, msg: WsMessage) {
+        self.telemetry.count_message();
+        if matches!(msg, WsMessage::Heartbeat { .. } | WsMessage::Ping { .. }) {
+            self.telemetry.record_heartbeat(chrono::Utc::now().timestamp_millis());
+        }
+        #[cfg(feature = "time-travel")]
+        {
+            self.recorder.record(msg.clone());
+            // Scrubbing: keep recording but leave the signals frozen
+            if !self.recorder.is_live() {
+                return;
+            }
+        }
+        self.dispatch(msg, false);
+    }
+
+    /// Route a message to the owning state; replays skip side effects such
+    /// as notifications
+    fn dispatch(&self, msg: WsMessage, replay: bool) {
+        // The feed carries every symbol; only the active one drives the panels
+        match msg {
+            WsMessage::Trade(trade) => {
+                if self.is_active_symbol(&trade.symbol) && self.market.is_new_trade(&trade) {
+                    let whale = self.market.whales.observe(&trade);
+                    if let Some(alert) = whale.filter(|_| !replay) {
+                        self.on_whale(&alert);
+                    }
+                    self.market.add_trade(trade);
+                }
+            }
+            WsMessage::OrderBook(book) => {
+                if self.is_active_symbol(&book.symbol) {
+                    self.market.update_orderbook(book);
+                }
+            }
+            WsMessage::Ticker(ticker) => {
+                self.symbols.update_ticker(&ticker);
+                self.portfolio.mark(&ticker.symbol, ticker.last_price.as_f64());
+                if !replay {
+                    self.check_price_alerts(&ticker.symbol, ticker.last_price.as_f64(), ticker.timestamp);
+                }
+                if self.is_active_symbol(&ticker.symbol) {
+                    self.market.update_ticker(ticker);
+                }
+            }
+            WsMessage::Bbo(bbo) => {
+                if self.is_active_symbol(&bbo.symbol) {
+                    self.market.update_bbo(&bbo);
+                }
+                self.symbols.update_bbo(bbo);
+            }
+            WsMessage::Candle(candle) => {
+                if self.compare.is_compared(&candle.symbol) {
+                    self.compare.update_candle(candle.clone());
+                }
+                if self.grid.is_shown(&candle.symbol) {
+                    self.grid.update_candle(candle.clone());
+                }
+                if self.is_active_symbol(&candle.symbol) {
+                    self.market.update_candle(candle);
+                }
+            }
+            WsMessage::Depth(depth) => {
+                if self.is_active_symbol(&depth.symbol) {
+                    self.market.depth.set(Some(depth));
+                }
+            }
+            WsMessage::Derivatives(derivatives) => {
+                if self.is_active_symbol(&derivatives.symbol) {
+                    self.market.update_derivatives(derivatives);
+                }
+            }
+            WsMessage::Session(session) => {
+                let anchor = self.ui.with_untracked(|ui| ui.session_anchor);
+                if self.is_active_symbol(&session.symbol) && session.anchor == anchor {
+                    self.market.update_session(session);
+                }
+            }
+            WsMessage::Snapshot(snapshot) => {
+                if self.is_active_symbol(&snapshot.symbol) {
+                    self.market.apply_snapshot(snapshot);
+                }
+            }
+            WsMessage::CandleHistory { symbol, before, limit, candles } => {
+                self.market.prepend_candles(&symbol, BackfillRequest { before, limit }, candles);
+            }
+            WsMessage::Symbols(list) => {
+                self.symbols.set_symbols(list);
+            }
+            WsMessage::Toxicity(flow) => {
+                if self.is_active_symbol(&flow.symbol) {
+                    self.market.toxicity.set(Some(flow));
+                }
+            }
+            WsMessage::Liquidation(liquidation) => {
+                if self.is_active_symbol(&liquidation.symbol) {
+                    self.market.add_liquidation(liquidation);
+                }
+            }
+            WsMessage::Heartbeat { timestamp } => {
+                tracing::trace!("Heartbeat received: {}", timestamp);
+            }
+            // Answered by the transport; only the arrival time matters here
+            WsMessage::Ping { nonce, .. } => {
+                tracing::trace!("Server ping {}", nonce);
+            }
+            WsMessage::Hello { connection_id } => {
+                tracing::info!("Server connection id {}", connection_id);
+                self.connection_id.set(Some(connection_id));
+            }
+            WsMessage::FeedStatus(status) => {
+                let was_degraded = self.feed.with_untracked(|f| f.as_ref().is_some_and(FeedStatus::is_degraded));
+                if status.is_degraded() != was_degraded && !replay {
+                    let (severity, text) = if status.is_degraded() {
+                        (Severity::Warning, format!("Exchange feed down; showing the {} backup", status.name))
+                    } else {
+                        (Severity::Success, "Exchange feed restored".to_string())
+                    };
+                    self.notify(severity, NotificationSource::Connection, text);
+                }
+                self.feed.set(Some(status));
+            }
+            WsMessage::SourceStatus(status) => {
+                self.sources.update(|sources| {
+                    sources.insert(status.name.clone(), status);
+                });
+            }
+            WsMessage::CandleCheck(report) => {
+                if !report.is_consistent() {
+                    tracing::warn!(
+                        "{}: {} of {} bars disagree with the trades",
+                        report.symbol,
+                        report.discrepancies.len(),
+                        report.checked
+                    );
+                }
+                self.candle_checks.update(|checks| {
+                    checks.insert(report.symbol.clone(), report);
+                });
+            }
+            WsMessage::Portfolio(snapshot) => {
+                self.portfolio.apply_snapshot(snapshot);
+            }
+            WsMessage::OrderUpdate(order) => {
+                if order.status == OrderStatus::Filled && !replay {
+                    self.sounds.play(SoundEvent::OrderFilled);
+                    self.notify(
+                        Severity::Success,
+                        NotificationSource::OrderFill,
+                        format!(
+                            "{} {} {} filled",
+                            order.side.label(),
+                            order.quantity.as_f64(),
+                            order.symbol
+                        ),
+                    );
+                }
+                self.rpc.resolve(&order.id);
+                self.portfolio.update_order(order);
+            }
+            WsMessage::OrderRejected { client_id, reason } => {
+                self.rpc.reject(&client_id, reason);
+            }
+            WsMessage::Position(position) => {
+                self.portfolio.update_position(position);
+            }
+            WsMessage::Balance(balance) => {
+                self.portfolio.update_balance(balance);
+            }
+            WsMessage::Equity(sample) => {
+                self.portfolio.push_equity(sample);
+            }
+            WsMessage::Account(summary) => {
+                self.portfolio.account.set(Some(summary));
+            }
+        }
+    }
+
+    fn is_active_symbol(&self, symbol: &Symbol) -> bool {
+        *symbol == self.market.symbol.get_untracked()
+    }
+
+    /// Switch the whole dashboard to another symbol
+    pub fn switch_symbol(&self, symbol: Symbol) {
+        if self.is_active_symbol(&symbol) {
+            return;
+        }
+        self.cursor.clear();
+        // Draw the new symbol's book and ticker without waiting for the feed
+        self.rpc.request_snapshot(&symbol);
+        self.market.set_symbol(symbol);
+        self.restore_interval();
+    }
+
+    /// Switch the chart timeframe and remember it for the current symbol
+    pub fn select_interval(&self, interval: CandleInterval) {
+        let symbol = self.market.symbol.get_untracked();
+        if self.chart_intervals.with_untracked(|saved| saved.get(&symbol) != Some(&interval)) {
+            self.chart_intervals.update(|saved| {
+                saved.insert(symbol, interval);
+            });
+        }
+        if interval != self.market.interval.get_untracked() {
+            self.market.set_interval(interval);
+        }
+    }
+
+    /// Go back to the timeframe remembered for the current symbol (keeps the
+    /// current one for symbols without a saved choice)
+    fn restore_interval(&self) {
+        let symbol = self.market.symbol.get_untracked();
+        if let Some(interval) = self.chart_intervals.with_untracked(|saved| saved.get(&symbol).copied())
+            && interval != self.market.interval.get_untracked()
+        {
+            self.market.set_interval(interval);
+        }
+    }
+
+    pub fn add_overlay(&self, overlay: ChartOverlay) {
+        self.chart_overlays.update(|overlays| overlays.push(overlay));
+    }
+
+    pub fn remove_overlay(&self, index: usize) {
+        self.chart_overlays.update(|overlays| {
+            if index < overlays.len() {
+                overlays.remove(index);
+            }
+        });
+    }
+
+    /// Change one overlay's settings in place
+    pub fn edit_overlay(&self, index: usize, edit: impl FnOnce(&mut ChartOverlay)) {
+        self.chart_overlays.update(|overlays| {
+            if let Some(overlay) = overlays.get_mut(index) {
+                edit(overlay);
+            }
+        });
+    }
+
+    // ========================================================================
+    // Paper Trading
+    // ========================================================================
+
+    /// Validate an order against the symbol's rules and free balance, then
+    /// send it to the paper-trading engine
+    pub fn place_order(&self, order: OrderRequest) -> Result<(), OrderRejection> {
+        let info = self
+            .symbols
+            .symbols
+            .with_untracked(|s| s.iter().find(|i| i.symbol == order.symbol).cloned())
+            .ok_or(OrderRejection::UnknownSymbol)?;
+        order.validate(&info)?;
+
+        let reference = self
+            .market
+            .ticker
+            .with_untracked(|t| t.as_ref().map(|t| t.last_price.as_f64()))
+            .unwrap_or(info.last_price);
+        order.check_balance(reference, self.portfolio.available_quote())?;
+
+        self.rpc.submit_order(order);
+        Ok(())
+    }
+
+    /// Move a working order to `price` (rounded to the symbol's tick)
+    pub fn amend_order(&self, order_id: &str, price: f64) -> Result<(), OrderRejection> {
+        let symbol = self
+            .portfolio
+            .orders
+            .with_untracked(|orders| orders.iter().find(|o| o.id == order_id).map(|o| o.symbol.clone()))
+            .ok_or(OrderRejection::Engine {
+                message: "Order is no longer working".to_string(),
+            })?;
+        let info = self
+            .symbols
+            .symbols
+            .with_untracked(|s| s.iter().find(|i| i.symbol == symbol).cloned())
+            .ok_or(OrderRejection::UnknownSymbol)?;
+        let price = info.round_price(price);
+        if !price.is_finite() || price <= 0.0 {
+            return Err(OrderRejection::MissingPrice);
+        }
+
+        self.rpc.amend_order(order_id, price);
+        Ok(())
+    }
+
+    /// Load a clicked price into the order ticket; `submit` (a modifier
+    /// click) only sends it straight away when fast trading is enabled
+    pub fn fill_ticket(&self, side: TradeSide, price: f64, submit: bool) {
+        let seq = self.ticket.with_untracked(|t| t.map_or(0, |t| t.seq)) + 1;
+        let submit = submit && self.ui.with_untracked(|ui| ui.fast_trading);
+        // The ticket lives on the account tab in compact mode
+        self.set_compact_tab(CompactTab::Account);
+        self.ticket.set(Some(TicketFill { seq, side, price, submit }));
+    }
+
+    pub fn toggle_fast_trading(&self) {
+        self.ui.update(|ui| ui.fast_trading = !ui.fast_trading);
+    }
+
+    /// Pick the UI language (`None` = follow the browser)
+    pub fn set_locale(&self, locale: Option<Locale>) {
+        self.ui.update(|ui| ui.locale = locale);
+    }
+
+    pub fn toggle_accessible_mode(&self) {
+        self.ui.update(|ui| ui.accessible_mode = !ui.accessible_mode);
+    }
+
+    /// Close the onboarding tour for good
+    pub fn finish_tour(&self) {
+        self.ui.update(|ui| ui.has_seen_tour = true);
+    }
+
+    /// Bring the onboarding tour back
+    pub fn restart_tour(&self) {
+        self.ui.update(|ui| ui.has_seen_tour = false);
+    }
+
+    pub fn toggle_whale_notifications(&self) {
+        self.ui.update(|ui| ui.whale_notifications = !ui.whale_notifications);
+    }
+
+    pub fn toggle_volume_pane(&self) {
+        self.ui.update(|ui| ui.hide_volume = !ui.hide_volume);
+    }
+
+    pub fn toggle_vwap(&self) {
+        self.ui.update(|ui| ui.show_vwap = !ui.show_vwap);
+    }
+
+    pub fn toggle_quotes(&self) {
+        self.ui.update(|ui| ui.show_quotes = !ui.show_quotes);
+    }
+
+    pub fn toggle_tooltip(&self) {
+        self.ui.update(|ui| ui.hide_tooltip = !ui.hide_tooltip);
+    }
+
+    pub fn toggle_prints(&self) {
+        self.ui.update(|ui| ui.show_prints = !ui.show_prints);
+    }
+
+    pub fn toggle_cvd(&self) {
+        self.ui.update(|ui| ui.show_cvd = !ui.show_cvd);
+    }
+
+    /// Chart grid of `size`, or back to the single chart
+    pub fn set_chart_grid(&self, size: Option<GridSize>) {
+        if self.ui.with_untracked(|ui| ui.chart_grid) != size {
+            self.ui.update(|ui| ui.chart_grid = size);
+        }
+        self.show_grid();
+    }
+
+    /// Chart something else in grid cell `index`
+    pub fn set_grid_chart(&self, index: usize, chart: GridChart) {
+        self.grid.set_chart(index, chart, self.grid_cells());
+    }
+
+    /// Leave the grid for grid cell `index`'s symbol and timeframe on the
+    /// single chart
+    pub fn focus_grid_chart(&self, index: usize) {
+        let Some(chart) = self.grid.charts.with_untracked(|charts| charts.get(index).cloned()) else {
+            return;
+        };
+        self.switch_symbol(chart.symbol);
+        self.select_interval(chart.interval);
+        self.set_chart_grid(None);
+    }
+
+    fn grid_cells(&self) -> usize {
+        self.ui.with_untracked(|ui| ui.chart_grid.map_or(0, |size| size.cells()))
+    }
+
+    /// Bring the grid's charts and series in line with its size (new cells
+    /// start on the active symbol)
+    fn show_grid(&self) {
+        self.grid.show(self.grid_cells(), &self.market.symbol.get_untracked());
+    }
+
+    pub fn set_chart_type(&self, chart_type: ChartType) {
+        if self.ui.with_untracked(|ui| ui.chart_type) != chart_type {
+            self.ui.update(|ui| ui.chart_type = chart_type);
+        }
+    }
+
+    pub fn set_brick_size(&self, size: BrickSize) {
+        if self.ui.with_untracked(|ui| ui.brick_size) != size {
+            self.ui.update(|ui| ui.brick_size = size);
+        }
+    }
+
+    pub fn set_chart_backend(&self, backend: RenderBackend) {
+        if self.ui.with_untracked(|ui| ui.chart_backend) != backend {
+            self.ui.update(|ui| ui.chart_backend = backend);
+        }
+    }
+
+    pub fn set_price_scale(&self, mode: PriceScaleMode) {
+        if self.ui.with_untracked(|ui| ui.price_scale) != mode {
+            self.ui.update(|ui| ui.price_scale = mode);
+        }
+    }
+
+    pub fn set_time_zone(&self, zone: TimeZoneMode) {
+        if self.ui.with_untracked(|ui| ui.time_zone) != zone {
+            self.ui.update(|ui| ui.time_zone = zone);
+        }
+    }
+
+    /// Follow sessions from `anchor`; the shown session clears until the
+    /// server's first one for it arrives
+    pub fn set_session_anchor(&self, anchor: SessionAnchor) {
+        if self.ui.with_untracked(|ui| ui.session_anchor) != anchor {
+            self.ui.update(|ui| ui.session_anchor = anchor);
+            self.market.clear_session();
+        }
+    }
+
+    pub fn toggle_fit_indicators(&self) {
+        self.ui.update(|ui| ui.fit_indicators = !ui.fit_indicators);
+    }
+
+    // ========================================================================
+    // Time Travel
+    // ========================================================================
+
+    /// Rebuild the signals from the recorded messages `..=index`
+    #[cfg(feature = "time-travel")]
+    pub fn rewind_to(&self, index: usize) {
+        let messages = self.recorder.messages_through(index);
+        self.recorder.position.set(Some(index));
+        self.replay(messages);
+    }
+
+    /// Step the scrubber by `delta` messages
+    #[cfg(feature = "time-travel")]
+    pub fn step_replay(&self, delta: isize) {
+        let len = self.recorder.len();
+        if len == 0 {
+            return;
+        }
+        let current = self.recorder.position.get_untracked().unwrap_or(len - 1);
+        let target = current.saturating_add_signed(delta).min(len - 1);
+        self.rewind_to(target);
+    }
+
+    /// Leave scrubbing: replay the whole buffer and resume live updates
+    #[cfg(feature = "time-travel")]
+    pub fn go_live(&self) {
+        let messages = self.recorder.messages_through(usize::MAX);
+        self.recorder.position.set(None);
+        self.replay(messages);
+    }
+
+    #[cfg(feature = "time-travel")]
+    fn replay(&self, messages: Vec<WsMessage>) {
+        self.market.clear();
+        self.portfolio.clear();
+        for msg in messages {
+            self.dispatch(msg, true);
         }
     }
 
@@ -125,12 +768,26 @@ impl AppState {
     /// Set connected state
     pub fn set_connected(&self) {
         self.connection.set(ConnectionState::Connected);
-        self.error.set(None);
+        self.notifications.dismiss_source(NotificationSource::Connection);
     }
 
-    /// Set disconnected state
+    /// Set disconnected state (sounds the alert if a live link dropped)
     pub fn set_disconnected(&self) {
+        if self.connection.get_untracked().is_connected() {
+            self.sounds.play(SoundEvent::Disconnected);
+        }
         self.connection.set(ConnectionState::Disconnected);
+        self.connection_id.set(None);
+        // Health reports are only current while the server is reachable
+        self.sources.set(BTreeMap::new());
+    }
+
+    /// The server closed the link for `reason`: show it on the indicator and
+    /// explain it in a notification
+    pub fn set_closed(&self, reason: CloseReason) {
+        self.set_disconnected();
+        self.connection.set(ConnectionState::Closed(reason));
+        self.notify_connection_error(reason.message());
     }
 
     /// Set connecting state
@@ -138,9 +795,12 @@ impl AppState {
         self.connection.set(ConnectionState::Connecting);
     }
 
-    /// Set reconnecting state
+    /// Set reconnecting state; a server close reason stays on show through
+    /// the backoff
     pub fn set_reconnecting(&self) {
-        self.connection.set(ConnectionState::Reconnecting);
+        if !matches!(self.connection.get_untracked(), ConnectionState::Closed(_)) {
+            self.connection.set(ConnectionState::Reconnecting);
+        }
     }
 
     /// Check if connected
@@ -148,23 +808,47 @@ impl AppState {
         self.connection.get().is_connected()
     }
 
+    /// Ask the transport to reconnect without waiting out the backoff
+    pub fn request_reconnect(&self) {
+        self.reconnect_requested.set(true);
+    }
+
+    /// Consume a pending reconnect request
+    pub fn take_reconnect_request(&self) -> bool {
+        let requested = self.reconnect_requested.get_untracked();
+        if requested {
+            self.reconnect_requested.set(false);
+        }
+        requested
+    }
+
     // ========================================================================
-    // Error Handling
+    // Notifications
     // ========================================================================
 
-    /// Set error message
-    pub fn set_error(&self, msg: impl Into<String>) {
-        self.error.set(Some(msg.into()));
+    /// Raise a notification
+    pub fn notify(&self, severity: Severity, source: NotificationSource, msg: impl Into<String>) -> u64 {
+        self.notifications.notify(severity, source, msg)
     }
 
-    /// Clear error
-    pub fn clear_error(&self) {
-        self.error.set(None);
+    /// Raise an error notification
+    pub fn notify_error(&self, source: NotificationSource, msg: impl Into<String>) -> u64 {
+        self.notify(Severity::Error, source, msg)
     }
 
-    /// Check if has error
+    /// Raise a connection error offering a "Reconnect now" button
+    pub fn notify_connection_error(&self, msg: impl Into<String>) -> u64 {
+        self.notifications.notify_with_action(
+            Severity::Error,
+            NotificationSource::Connection,
+            msg,
+            NotificationAction::Reconnect,
+        )
+    }
+
+    /// Is any error toast still showing?
     pub fn has_error(&self) -> bool {
-        self.error.get().is_some()
+        self.notifications.latest_active(Severity::Error).is_some()
     }
 
     // ========================================================================
@@ -188,23 +872,138 @@ impl AppState {
     /// Toggle panel visibility
     pub fn toggle_panel(&self, panel: Panel) {
         self.ui.update(|ui| {
-            match panel {
-                Panel::OrderBook => ui.panels.orderbook = !ui.panels.orderbook,
-                Panel::Trades => ui.panels.trades = !ui.panels.trades,
-                Panel::DepthChart => ui.panels.depth_chart = !ui.panels.depth_chart,
-                Panel::CandleChart => ui.panels.candle_chart = !ui.panels.candle_chart,
-            }
+            let layout = ui.layout.panel_mut(panel);
+            layout.visible = !layout.visible;
         });
     }
 
     /// Check if panel is visible
     pub fn is_panel_visible(&self, panel: Panel) -> bool {
-        let ui = self.ui.get();
-        match panel {
-            Panel::OrderBook => ui.panels.orderbook,
-            Panel::Trades => ui.panels.trades,
-            Panel::DepthChart => ui.panels.depth_chart,
-            Panel::CandleChart => ui.panels.candle_chart,
+        self.ui.get().layout.panel(panel).visible
+    }
+
+    /// Switch to a named layout preset
+    pub fn apply_layout(&self, preset: DashboardLayout) {
+        self.ui.update(|ui| {
+            ui.layout = preset.geometry();
+        });
+    }
+
+    /// Resize a panel (grid cells)
+    pub fn resize_panel(&self, panel: Panel, width: u16, height: u16) {
+        self.ui.update(|ui| ui.layout.resize(panel, width, height));
+    }
+
+    /// Move a panel (grid cells)
+    pub fn move_panel(&self, panel: Panel, col: u16, row: u16) {
+        self.ui.update(|ui| ui.layout.move_to(panel, col, row));
+    }
+
+    /// Commit a finished drag (move, resize or dock)
+    pub fn apply_drag(&self, drag: &PanelDrag) {
+        if drag.current != drag.start {
+            self.ui.update(|ui| drag.apply(&mut ui.layout));
+        }
+    }
+
+    /// Persist theme, layout, favorites, chart timeframes/overlays, grid
+    /// charts and display preferences to local storage
+    pub fn save_settings(&self) {
+        Settings::capture(
+            &self.ui.get_untracked(),
+            self.symbols.favorites.get_untracked(),
+            self.shortcuts.bindings.get_untracked(),
+            self.sounds.prefs.get_untracked(),
+            self.chart_intervals.get_untracked(),
+            self.chart_overlays.get_untracked(),
+            self.grid.charts.get_untracked(),
+        )
+        .save();
+    }
+
+    /// Restore persisted preferences, migrating older schemas (keeps the
+    /// current ones if nothing is stored), and the chart drawings
+    pub fn load_settings(&self) {
+        self.drawings.load();
+        if let Some(settings) = Settings::load() {
+            self.ui.update(|ui| settings.apply_to(ui));
+            self.symbols.favorites.set(settings.favorites);
+            self.shortcuts.bindings.set(settings.key_bindings);
+            self.sounds.prefs.set(settings.sounds);
+            self.chart_intervals.set(settings.chart_intervals);
+            self.chart_overlays.set(settings.chart_overlays);
+            self.grid.charts.set(settings.grid_charts);
+            self.show_grid();
+            self.restore_interval();
+        }
+    }
+
+    // ========================================================================
+    // URL Sync
+    // ========================================================================
+
+    /// Snapshot of the state mirrored into the URL
+    pub fn url_state(&self) -> UrlState {
+        let layout = self.ui.get().layout;
+        UrlState {
+            symbol: Some(self.market.symbol.get()),
+            interval: Some(self.market.interval.get()),
+            layout: Some(layout.preset),
+            panels: Some(
+                Panel::all()
+                    .iter()
+                    .copied()
+                    .filter(|&p| layout.panel(p).visible)
+                    .collect(),
+            ),
+            popout: self.popout.get(),
+        }
+    }
+
+    /// Query string for a pop-out window showing `panel` on the current
+    /// symbol and interval
+    pub fn popout_query(&self, panel: Panel) -> String {
+        UrlState {
+            symbol: Some(self.market.symbol.get_untracked()),
+            interval: Some(self.market.interval.get_untracked()),
+            popout: Some(panel),
+            ..UrlState::default()
+        }
+        .to_query()
+    }
+
+    /// Is this window a single-panel pop-out?
+    pub fn is_popout(&self) -> bool {
+        self.popout.with_untracked(Option::is_some)
+    }
+
+    /// Apply state restored from a URL (only fields present in the URL)
+    pub fn apply_url_state(&self, url: &UrlState) {
+        if url.popout.is_some() {
+            self.popout.set(url.popout);
+        }
+        if let Some(symbol) = &url.symbol
+            && *symbol != self.market.symbol.get_untracked()
+        {
+            self.switch_symbol(symbol.clone());
+        }
+        if let Some(interval) = url.interval
+            && interval != self.market.interval.get_untracked()
+        {
+            self.market.set_interval(interval);
+        }
+        // Custom geometry lives in local storage, not the URL
+        if let Some(preset) = url.layout
+            && preset != DashboardLayout::Custom
+        {
+            self.apply_layout(preset);
+        }
+        if let Some(visible) = &url.panels {
+            self.ui.update(|ui| {
+                for &panel in Panel::all() {
+                    ui.layout.panel_mut(panel).visible = visible.contains(&panel);
+                }
+            });
         }
     }
 
@@ -215,6 +1014,174 @@ impl AppState {
         });
     }
 
+    /// Record the viewport size class (drives the responsive layout)
+    pub fn set_breakpoint(&self, breakpoint: Breakpoint) {
+        if self.ui.with_untracked(|ui| ui.breakpoint) != breakpoint {
+            self.ui.update(|ui| ui.breakpoint = breakpoint);
+        }
+    }
+
+    /// Show a tab in compact mode
+    pub fn set_compact_tab(&self, tab: CompactTab) {
+        if self.ui.with_untracked(|ui| ui.compact_tab) != tab {
+            self.ui.update(|ui| ui.compact_tab = tab);
+        }
+    }
+
+    /// Is `panel` on screen (visible in the grid, or on the active compact tab)?
+    pub fn panel_shown(&self, panel: Panel) -> bool {
+        self.ui.with(|ui| {
+            if ui.is_compact() {
+                ui.compact_tab.panels().contains(&panel)
+            } else {
+                ui.layout.panel(panel).visible
+            }
+        })
+    }
+
+    // ========================================================================
+    // Alerts
+    // ========================================================================
+
+    /// Mark an alert as acknowledged
+    pub fn acknowledge_alert(&self, id: impl Into<String>) {
+        let id = id.into();
+        if !self.alert_acks.with_untracked(|acks| acks.contains(&id)) {
+            self.alert_acks.update(|acks| {
+                acks.insert(id);
+            });
+        }
+    }
+
+    /// Merge acknowledgements from another tab; only notifies on new IDs
+    pub fn merge_alert_acks(&self, ids: impl IntoIterator<Item = String>) {
+        let new: Vec<String> = self
+            .alert_acks
+            .with_untracked(|acks| ids.into_iter().filter(|id| !acks.contains(id)).collect());
+        if !new.is_empty() {
+            self.alert_acks.update(|acks| acks.extend(new));
+        }
+    }
+
+    /// Has this alert been acknowledged?
+    pub fn is_alert_acknowledged(&self, id: &str) -> bool {
+        self.alert_acks.with(|acks| acks.contains(id))
+    }
+
+    /// Arm a one-shot alert at `price` on the active symbol; it fires when
+    /// the last price reaches the level from its current side
+    pub fn add_price_alert(&self, price: f64) {
+        let symbol = self.market.symbol.get_untracked();
+        let reference = self
+            .market
+            .ticker
+            .with_untracked(|t| t.as_ref().map(|t| t.last_price.as_f64()))
+            .unwrap_or(price);
+        self.price_alerts.update(|alerts| {
+            let id = alerts.iter().map(|a| a.id).max().map_or(1, |id| id + 1);
+            let alert = PriceAlert::new(id, symbol, price, reference);
+            self.notifications
+                .notify(Severity::Info, NotificationSource::Alert, format!("Alert set: {}", alert.label()));
+            alerts.push(alert);
+        });
+    }
+
+    pub fn remove_price_alert(&self, id: u64) {
+        self.price_alerts.update(|alerts| alerts.retain(|a| a.id != id));
+    }
+
+    /// Keep a drawing made on the active symbol's chart; returns its id
+    pub fn add_drawing(&self, drawing: Drawing) -> u64 {
+        self.drawings.add(&self.market.symbol.get_untracked(), drawing)
+    }
+
+    /// Store an edited drawing on the active symbol's chart
+    pub fn update_drawing(&self, drawing: Drawing) {
+        self.drawings.replace(&self.market.symbol.get_untracked(), drawing);
+    }
+
+    pub fn remove_drawing(&self, id: u64) {
+        self.drawings.remove(&self.market.symbol.get_untracked(), id);
+    }
+
+    /// Remove every drawing on the active symbol's chart
+    pub fn clear_drawings(&self) {
+        self.drawings.clear(&self.market.symbol.get_untracked());
+    }
+
+    fn check_price_alerts(&self, symbol: &Symbol, price: f64, time: i64) {
+        let armed = self.price_alerts.with_untracked(|alerts| alerts.iter().any(|a| a.symbol == *symbol));
+        if !armed {
+            return;
+        }
+        let mut fired = Vec::new();
+        self.price_alerts.update(|alerts| fired = take_triggered(alerts, symbol, price));
+        if fired.is_empty() {
+            return;
+        }
+        self.sounds.play(SoundEvent::AlertTriggered);
+        for alert in &fired {
+            self.notify(Severity::Warning, NotificationSource::Alert, format!("Price alert: {}", alert.label()));
+        }
+        self.fired_alerts.update(|log| {
+            log.extend(fired.into_iter().map(|alert| FiredAlert { alert, time, price }));
+            let excess = log.len().saturating_sub(MAX_FIRED_ALERTS);
+            log.drain(..excess);
+        });
+    }
+
+    /// Side effects of a fresh whale print: the sound cue, plus a toast when
+    /// whale notifications are on
+    fn on_whale(&self, alert: &WhaleAlert) {
+        self.sounds.play(SoundEvent::WhaleTrade);
+        if self.ui.with_untracked(|ui| ui.whale_notifications) {
+            self.notify(
+                Severity::Info,
+                NotificationSource::Whale,
+                format!(
+                    "🐋 {} {} {} @ {:.2} ({})",
+                    alert.side.label(),
+                    alert.quantity,
+                    alert.symbol,
+                    alert.price,
+                    CompactNumberFormatter.format(alert.value)
+                ),
+            );
+        }
+    }
+
+    // ========================================================================
+    // Cross-Tab Sync
+    // ========================================================================
+
+    /// Current preferences as a tab message
+    pub fn preferences_message(&self) -> TabMessage {
+        let ui = self.ui.get();
+        TabMessage::Preferences {
+            theme: ui.theme,
+            layout: ui.layout,
+        }
+    }
+
+    /// Apply preferences/acks received from another tab (market relay is
+    /// handled by the WebSocket layer). Unchanged values are not re-set so
+    /// echoes between tabs die out.
+    pub fn apply_tab_message(&self, msg: &TabMessage) {
+        match msg {
+            TabMessage::Preferences { theme, layout } => {
+                let ui = self.ui.get_untracked();
+                if ui.theme != *theme || ui.layout != *layout {
+                    self.ui.update(|ui| {
+                        ui.theme = *theme;
+                        ui.layout = *layout;
+                    });
+                }
+            }
+            TabMessage::AlertAcks { ids } => self.merge_alert_acks(ids.iter().cloned()),
+            TabMessage::Hello { .. } | TabMessage::PrimaryAlive { .. } | TabMessage::Market { .. } => {}
+        }
+    }
+
     // ========================================================================
     // Loading State
     // ========================================================================
@@ -237,12 +1204,16 @@ impl Default for AppState {
 }
 
 /// Dashboard panel identifiers
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
 pub enum Panel {
     OrderBook,
     Trades,
     DepthChart,
     CandleChart,
+    Positions,
+    Account,
+    OrderEntry,
 }
 
 impl Panel {
@@ -252,11 +1223,49 @@ impl Panel {
             Self::Trades => "Trades",
             Self::DepthChart => "Depth Chart",
             Self::CandleChart => "Chart",
+            Self::Positions => "Positions",
+            Self::Account => "Account",
+            Self::OrderEntry => "Order Entry",
+        }
+    }
+
+    /// URL/storage code (matches the serde representation)
+    pub fn code(&self) -> &'static str {
+        match self {
+            Self::OrderBook => "order_book",
+            Self::Trades => "trades",
+            Self::DepthChart => "depth_chart",
+            Self::CandleChart => "candle_chart",
+            Self::Positions => "positions",
+            Self::Account => "account",
+            Self::OrderEntry => "order_entry",
+        }
+    }
+
+    pub fn from_code(code: &str) -> Option<Self> {
+        Self::all().iter().copied().find(|p| p.code() == code)
+    }
+
+    /// Market stream backing this panel (for stale indicators)
+    pub fn stream(&self) -> Option<StreamChannel> {
+        match self {
+            Self::OrderBook | Self::DepthChart => Some(StreamChannel::OrderBook),
+            Self::Trades => Some(StreamChannel::Trades),
+            Self::CandleChart => Some(StreamChannel::Candles),
+            Self::Positions | Self::Account | Self::OrderEntry => None,
         }
     }
 
     pub fn all() -> &'static [Self] {
-        &[Self::OrderBook, Self::Trades, Self::DepthChart, Self::CandleChart]
+        &[
+            Self::OrderBook,
+            Self::Trades,
+            Self::DepthChart,
+            Self::CandleChart,
+            Self::Positions,
+            Self::Account,
+            Self::OrderEntry,
+        ]
     }
 }
 
@@ -267,6 +1276,8 @@ impl Panel {
 /// Provide app state context to component tree
 pub fn provide_app_state() -> AppState {
     let state = AppState::new();
+    state.load_settings();
+    state.apply_url_state(&UrlState::from_query(&url::read_query()));
     provide_context(state.clone());
     state
 }
@@ -279,4 +1290,21 @@ pub fn use_app_state() -> AppState {
 /// Try to get app state from context (returns None if not provided)
 pub fn try_use_app_state() -> Option<AppState> {
     use_context::<AppState>()
+}
+
+/// Translations for the app's locale (English outside an app context)
+pub fn use_i18n() -> I18n {
+    try_use_app_state().map_or_else(|| I18n::new(Signal::stored(None), Locale::En), |state| state.i18n)
+}
+
+/// Price and quantity precision of the active symbol (defaults outside an
+/// app context); only notifies when the precision itself changes
+pub fn use_tick_format() -> Signal<TickFormat> {
+    match try_use_app_state() {
+        Some(state) => {
+            let (symbol, directory) = (state.market.symbol, state.symbols);
+            Memo::new(move |_| symbol.with(|s| directory.tick_format(s))).into()
+        }
+        None => Signal::stored(TickFormat::default()),
+    }
 }
\ No newline at end of file
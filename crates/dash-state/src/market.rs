@@ -1,9 +1,13 @@
 //! Reactive market data state with fine-grained signal updates
 
-use crate::{MAX_CANDLES, MAX_TRADES};
+use crate::{
+    Indicators, StateFootprint, StreamChannel, StreamFreshness, WhaleAlerts, MAX_BASE_CANDLES, MAX_CANDLES, MAX_HISTORY_BASE_CANDLES,
+    MAX_LIQUIDATIONS, MAX_TRADES,
+    PROFILE_BINS, SpreadHistory, VWAP_TRAIL_LEN,
+};
 use dash_core::{
-    Candle, CandleHistory, CandleInterval, MarketDepth, OrderBookSnapshot,
-    Symbol, Ticker, Trade, TradeSide,
+    candle_path, range_bars, renko, utc_day_start, vwap_series, Bbo, BookHistory, BrickSize, Candle, CandleHistory, CandleInterval, ChartType, DerivativesTicker, FlowToxicity, IcebergDetector, IcebergLevel, Liquidation, MarketDepth, MarketSnapshot,
+    OrderBookSnapshot, PriceSample, ProfileHistogram, ProfileRange, SessionStats, Symbol, Ticker, Trade, TradeDedup, TradeSide, VwapPoint,
 };
 use leptos::prelude::*;
 
@@ -18,14 +22,48 @@ pub struct MarketState {
     pub orderbook: RwSignal<Option<OrderBookSnapshot>>,
     /// Market depth (derived from orderbook)
     pub depth: RwSignal<Option<MarketDepth>>,
+    /// Session open, extremes and VWAP from the analytics channel
+    pub session: RwSignal<Option<SessionStats>>,
+    /// Server VWAP/σ snapshots for the current session, one per minute
+    pub vwap_trail: RwSignal<Vec<(i64, VwapPoint)>>,
+    /// VPIN-style flow toxicity from the analytics channel
+    pub toxicity: RwSignal<Option<FlowToxicity>>,
+    /// Funding / open interest (perpetual markets only)
+    pub derivatives: RwSignal<Option<DerivativesTicker>>,
+    /// Sampled book history for the heatmap
+    pub book_history: RwSignal<BookHistory>,
+    /// Refill tracking behind the iceberg heuristic
+    pub iceberg_detector: StoredValue<IcebergDetector>,
+    /// Book levels flagged as possible icebergs
+    pub icebergs: RwSignal<Vec<IcebergLevel>>,
+    /// Bid-ask spread over the last five minutes
+    pub spread_history: RwSignal<SpreadHistory>,
     /// Recent trades (most recent first)
     pub trades: RwSignal<Vec<Trade>>,
-    /// Candlestick history
-    pub candles: RwSignal<CandleHistory>,
+    /// Ids of recent trades, so prints redelivered after a reconnect aren't
+    /// counted twice
+    pub seen_trades: StoredValue<TradeDedup>,
+    /// Recent liquidations (most recent first)
+    pub liquidations: RwSignal<Vec<Liquidation>>,
+    /// Base 1m candle history (source for every timeframe)
+    pub base_candles: RwSignal<CandleHistory>,
+    /// Candles resampled to the selected interval
+    pub candles: Memo<CandleHistory>,
     /// Current candle interval
     pub interval: RwSignal<CandleInterval>,
+    /// Candles of the current interval the chart keeps loaded; grows a page
+    /// at a time as the chart scrolls back
+    pub chart_span: RwSignal<usize>,
+    /// Loading of older base candles over REST
+    pub backfill: RwSignal<BackfillStatus>,
     /// Last update timestamps
     pub last_update: LastUpdateSignals,
+    /// Local receipt times for staleness indicators
+    pub freshness: StreamFreshness,
+    /// Trade-flow indicators updated on ingest
+    pub indicators: Indicators,
+    /// Whale prints awaiting acknowledgement
+    pub whales: WhaleAlerts,
 }
 
 /// Signals tracking last update times for each data type
@@ -52,15 +90,39 @@ impl MarketState {
     /// Create new market state
     pub fn new() -> Self {
         let symbol = Symbol::default();
+        let base_candles = RwSignal::new(CandleHistory::new(symbol.clone(), BASE_INTERVAL));
+        let interval = RwSignal::new(CandleInterval::M1);
+        let chart_span = RwSignal::new(MAX_CANDLES);
+
         Self {
-            symbol: RwSignal::new(symbol.clone()),
+            symbol: RwSignal::new(symbol),
             ticker: RwSignal::new(None),
             orderbook: RwSignal::new(None),
             depth: RwSignal::new(None),
+            session: RwSignal::new(None),
+            vwap_trail: RwSignal::new(Vec::new()),
+            toxicity: RwSignal::new(None),
+            derivatives: RwSignal::new(None),
+            book_history: RwSignal::new(BookHistory::default()),
+            iceberg_detector: StoredValue::new(IcebergDetector::new()),
+            icebergs: RwSignal::new(Vec::new()),
+            spread_history: RwSignal::new(SpreadHistory::default()),
             trades: RwSignal::new(Vec::with_capacity(MAX_TRADES)),
-            candles: RwSignal::new(CandleHistory::new(symbol, CandleInterval::M1)),
-            interval: RwSignal::new(CandleInterval::M1),
+            seen_trades: StoredValue::new(TradeDedup::default()),
+            liquidations: RwSignal::new(Vec::new()),
+            base_candles,
+            candles: Memo::new(move |_| {
+                let interval = interval.get();
+                let span = chart_span.get();
+                base_candles.with(|base| resample_tail(base, interval, span))
+            }),
+            interval,
+            chart_span,
+            backfill: RwSignal::new(BackfillStatus::Idle),
             last_update: LastUpdateSignals::new(),
+            freshness: StreamFreshness::new(),
+            indicators: Indicators::new(),
+            whales: WhaleAlerts::new(),
         }
     }
 
@@ -71,9 +133,67 @@ impl MarketState {
     /// Update ticker data
     pub fn update_ticker(&self, ticker: Ticker) {
         self.last_update.ticker.set(ticker.timestamp);
+        self.freshness.mark(StreamChannel::Ticker);
         self.ticker.set(Some(ticker));
     }
 
+    /// Move the ticker's touch to a fresher BBO (nothing before the first
+    /// ticker)
+    pub fn update_bbo(&self, bbo: &Bbo) {
+        if self.ticker.with_untracked(Option::is_some) {
+            self.ticker.update(|ticker| {
+                if let Some(ticker) = ticker {
+                    ticker.apply_bbo(bbo);
+                }
+            });
+        }
+    }
+
+    /// Fill in whatever the live feed hasn't delivered yet from a snapshot
+    pub fn apply_snapshot(&self, snapshot: MarketSnapshot) {
+        if let Some(ticker) = snapshot.ticker.filter(|_| self.ticker.with_untracked(Option::is_none)) {
+            self.update_ticker(ticker);
+        }
+        if let Some(book) = snapshot.orderbook.filter(|_| self.orderbook.with_untracked(Option::is_none)) {
+            self.update_orderbook(book);
+        }
+        let newest = self.base_candles.with_untracked(|h| h.candles.last().map(|c| c.timestamp));
+        if let Some(candle) = snapshot.candle.filter(|c| newest.is_none_or(|t| c.timestamp > t)) {
+            self.update_candle(candle);
+        }
+    }
+
+    /// Drop the session and its VWAP trail (day start switched)
+    pub fn clear_session(&self) {
+        self.session.set(None);
+        self.vwap_trail.set(Vec::new());
+    }
+
+    /// Store the session analytics and snapshot its VWAP for the chart
+    pub fn update_session(&self, session: SessionStats) {
+        let minute = session.timestamp - session.timestamp.rem_euclid(60_000);
+        let point = VwapPoint::from(&session);
+        self.vwap_trail.update(|trail| {
+            // A new session starts the trail over
+            if trail.first().is_some_and(|&(ts, _)| ts < session.session_start) {
+                trail.clear();
+            }
+            match trail.last_mut() {
+                Some(last) if last.0 == minute => last.1 = point,
+                _ => trail.push((minute, point)),
+            }
+            if trail.len() > VWAP_TRAIL_LEN {
+                trail.remove(0);
+            }
+        });
+        self.session.set(Some(session));
+    }
+
+    /// Update funding / open interest stats
+    pub fn update_derivatives(&self, derivatives: DerivativesTicker) {
+        self.derivatives.set(Some(derivatives));
+    }
+
     /// Get current price (from ticker)
     pub fn current_price(&self) -> Option<f64> {
         self.ticker.get().map(|t| t.last_price.as_f64())
@@ -88,7 +208,15 @@ impl MarketState {
         // Derive market depth from order book
         let depth = MarketDepth::from_orderbook(&book);
         self.last_update.orderbook.set(book.timestamp);
+        self.freshness.mark(StreamChannel::OrderBook);
         self.depth.set(Some(depth));
+        self.book_history.update(|history| history.push(&book));
+        self.spread_history.update(|history| history.push(&book));
+        self.iceberg_detector.update_value(|detector| detector.on_book(&book));
+        let flagged = self.iceberg_detector.with_value(IcebergDetector::flagged);
+        if self.icebergs.with_untracked(|icebergs| *icebergs != flagged) {
+            self.icebergs.set(flagged);
+        }
         self.orderbook.set(Some(book));
     }
 
@@ -111,9 +239,18 @@ impl MarketState {
     // Trade Updates
     // ========================================================================
 
-    /// Add single trade to history
+    /// Remember `trade`; false if it was already delivered
+    pub fn is_new_trade(&self, trade: &Trade) -> bool {
+        self.seen_trades.try_update_value(|seen| seen.first_sighting(trade)).unwrap_or(true)
+    }
+
+    /// Add a trade already checked with [`Self::is_new_trade`]
     pub fn add_trade(&self, trade: Trade) {
         self.last_update.trade.set(trade.timestamp.timestamp_millis());
+        self.freshness.mark(StreamChannel::Trades);
+        self.apply_trade_to_candles(&trade);
+        self.track_iceberg_trade(&trade);
+        self.indicators.ingest(&trade);
         self.trades.update(|trades| {
             trades.insert(0, trade);
             if trades.len() > MAX_TRADES {
@@ -122,8 +259,17 @@ impl MarketState {
         });
     }
 
-    /// Add batch of trades
-    pub fn add_trades(&self, new_trades: Vec<Trade>) {
+    /// Add a liquidation to the feed
+    pub fn add_liquidation(&self, liquidation: Liquidation) {
+        self.liquidations.update(|liquidations| {
+            liquidations.insert(0, liquidation);
+            liquidations.truncate(MAX_LIQUIDATIONS);
+        });
+    }
+
+    /// Add batch of trades, skipping any already delivered
+    pub fn add_trades(&self, mut new_trades: Vec<Trade>) {
+        new_trades.retain(|trade| self.is_new_trade(trade));
         if new_trades.is_empty() {
             return;
         }
@@ -131,6 +277,12 @@ impl MarketState {
         if let Some(first) = new_trades.first() {
             self.last_update.trade.set(first.timestamp.timestamp_millis());
         }
+        self.freshness.mark(StreamChannel::Trades);
+        for trade in &new_trades {
+            self.apply_trade_to_candles(trade);
+            self.track_iceberg_trade(trade);
+            self.indicators.ingest(trade);
+        }
 
         self.trades.update(|trades| {
             for trade in new_trades {
@@ -140,11 +292,93 @@ impl MarketState {
         });
     }
 
+    /// Volume profile over the retained trades in `range`
+    pub fn volume_profile(&self, range: ProfileRange) -> Option<ProfileHistogram> {
+        let since = match range {
+            ProfileRange::Session => self.session.with(|s| s.as_ref().map(|s| s.session_start)).or_else(|| {
+                self.trades
+                    .with(|t| t.first().map(|t| utc_day_start(t.timestamp.timestamp_millis())))
+            })?,
+            ProfileRange::Visible => self.candles.with(|c| c.candles.first().map(|c| c.timestamp))?,
+        };
+        self.trades.with(|trades| {
+            // Trades are newest first, so stop at the first one before the range
+            let in_range = trades.iter().take_while(|t| t.timestamp.timestamp_millis() >= since);
+            ProfileHistogram::from_trades(in_range, PROFILE_BINS)
+        })
+    }
+
+    /// Session VWAP and σ at each chart candle: the server's figures where
+    /// it has sent them, otherwise computed from the trade buffer
+    pub fn vwap_series(&self) -> Vec<Option<VwapPoint>> {
+        let session_start = self.session.with(|s| s.as_ref().map(|s| s.session_start));
+        self.candles.with(|history| {
+            let Some(session_start) =
+                session_start.or_else(|| history.candles.last().map(|c| utc_day_start(c.timestamp)))
+            else {
+                return Vec::new();
+            };
+            self.trades.with(|trades| {
+                self.vwap_trail
+                    .with(|trail| vwap_series(&history.candles, trades, session_start, trail))
+            })
+        })
+    }
+
+    /// Renko bricks or range bars (per `chart_type`) for the charted symbol,
+    /// walked from the base candles up to the oldest kept trade and from the
+    /// trades after that
+    pub fn brick_series(&self, chart_type: ChartType, size: BrickSize) -> CandleHistory {
+        let symbol = self.symbol.get();
+        let candles = self.base_candles.with(|base| {
+            self.trades.with(|trades| {
+                // Trades are newest first
+                let first_trade = trades.last().map(|t| t.timestamp.timestamp_millis());
+                let split = base
+                    .candles
+                    .partition_point(|c| first_trade.is_none_or(|t| c.timestamp + BASE_INTERVAL.as_millis() <= t));
+                let Some(last_price) = trades
+                    .first()
+                    .map(|t| t.price.as_f64())
+                    .or_else(|| base.candles.last().map(|c| c.close.as_f64()))
+                else {
+                    return Vec::new();
+                };
+                let samples = candle_path(&base.candles[..split]).chain(trades.iter().rev().map(|t| PriceSample {
+                    time: t.timestamp.timestamp_millis(),
+                    price: t.price.as_f64(),
+                    volume: t.quantity.as_f64(),
+                }));
+                let size = size.at(last_price);
+                let mut bars = match chart_type {
+                    ChartType::RangeBars => range_bars(&symbol, samples, size),
+                    _ => renko(&symbol, samples, size),
+                };
+                trim_front(&mut bars, MAX_CANDLES);
+                bars
+            })
+        });
+        CandleHistory { symbol, interval: BASE_INTERVAL, candles }
+    }
+
     /// Get latest trade
     pub fn latest_trade(&self) -> Option<Trade> {
         self.trades.get().first().cloned()
     }
 
+    /// Last trade price and its tick direction (tracks the trade buffer)
+    pub fn last_tick(&self) -> Option<(f64, PriceDirection)> {
+        self.trades.with(|trades| last_tick(trades))
+    }
+
+    /// Best bid and ask prices (tracks the order book)
+    pub fn best_quotes(&self) -> Option<(f64, f64)> {
+        self.orderbook.with(|book| {
+            let book = book.as_ref()?;
+            Some((book.best_bid()?.price.as_f64(), book.best_ask()?.price.as_f64()))
+        })
+    }
+
     /// Get recent N trades
     pub fn recent_trades(&self, n: usize) -> Vec<Trade> {
         self.trades.get().iter().take(n).cloned().collect()
@@ -154,39 +388,72 @@ impl MarketState {
     // Candle Updates
     // ========================================================================
 
-    /// Update or add candle
+    /// Upsert a base (1m) candle by open time; other intervals are ignored
+    /// since every timeframe is resampled from the base series
     pub fn update_candle(&self, candle: Candle) {
+        if candle.interval != BASE_INTERVAL {
+            tracing::debug!("Ignoring {} candle; charts resample from 1m", candle.interval);
+            return;
+        }
+
         self.last_update.candle.set(candle.timestamp);
-        self.candles.update(|history| {
-            // Check if we should update existing candle or add new one
-            if let Some(last) = history.candles.last_mut() {
-                if last.timestamp == candle.timestamp && !last.is_closed {
-                    // Update existing candle
-                    *last = candle;
+        self.freshness.mark(StreamChannel::Candles);
+        let capacity = self.history_capacity();
+        self.base_candles.update(|history| upsert_candle(&mut history.candles, candle, capacity));
+    }
+
+    /// Count a trade of the active symbol against the level it hit
+    fn track_iceberg_trade(&self, trade: &Trade) {
+        if trade.symbol == self.symbol.get_untracked() {
+            self.iceberg_detector.update_value(|detector| detector.on_trade(trade));
+        }
+    }
+
+    /// Fold a trade into the forming base candle, opening a new one when the
+    /// trade falls into a later minute (authoritative candle updates replace it)
+    fn apply_trade_to_candles(&self, trade: &Trade) {
+        if trade.symbol != self.symbol.get_untracked() {
+            return;
+        }
+
+        let ts = trade.timestamp.timestamp_millis();
+        let open_time = BASE_INTERVAL.bucket_start(ts);
+        let price = trade.price.as_f64();
+        let qty = trade.quantity.as_f64();
+        let capacity = self.history_capacity();
+
+        self.base_candles.update(|history| {
+            match history.candles.last_mut() {
+                Some(last) if last.timestamp == open_time => {
+                    last.update(price, qty);
                     return;
                 }
+                Some(last) if last.timestamp > open_time => return,
+                Some(last) => last.close_candle(),
+                None => {}
             }
-            // Add new candle
+
+            let mut candle = Candle::new(history.symbol.clone(), BASE_INTERVAL, open_time, price);
+            candle.update(price, qty);
             history.candles.push(candle);
-            // Maintain max size
-            if history.candles.len() > MAX_CANDLES {
-                history.candles.remove(0);
-            }
+            trim_front(&mut history.candles, capacity);
         });
     }
 
-    /// Set full candle history (bulk load)
-    pub fn set_candles(&self, candles: Vec<Candle>) {
+    /// Set full base (1m) candle history (bulk load)
+    pub fn set_candles(&self, mut candles: Vec<Candle>) {
+        candles.retain(|c| c.interval == BASE_INTERVAL);
+        candles.sort_by_key(|c| c.timestamp);
+        trim_front(&mut candles, self.history_capacity());
+
         if let Some(last) = candles.last() {
             self.last_update.candle.set(last.timestamp);
         }
 
         let symbol = self.symbol.get();
-        let interval = self.interval.get();
 
-        self.candles.update(|history| {
+        self.base_candles.update(|history| {
             history.symbol = symbol;
-            history.interval = interval;
             history.candles = candles;
         });
     }
@@ -195,21 +462,103 @@ impl MarketState {
     // Symbol & Interval Changes
     // ========================================================================
 
+    /// Current buffer sizes (untracked; read it on a timer)
+    pub fn footprint(&self) -> StateFootprint {
+        StateFootprint {
+            trades: self.trades.with_untracked(Vec::len),
+            candles: self.base_candles.with_untracked(CandleHistory::len),
+            book_levels: self
+                .orderbook
+                .with_untracked(|book| book.as_ref().map_or(0, |b| b.bids.len() + b.asks.len())),
+            liquidations: self.liquidations.with_untracked(Vec::len),
+        }
+    }
+
     /// Change trading symbol (clears all data)
     pub fn set_symbol(&self, symbol: Symbol) {
         self.symbol.set(symbol.clone());
+        self.freshness.reset();
+        self.indicators.reset();
+        self.whales.clear();
         // Clear all market data
         self.ticker.set(None);
         self.orderbook.set(None);
         self.depth.set(None);
+        self.session.set(None);
+        self.vwap_trail.set(Vec::new());
+        self.toxicity.set(None);
+        self.derivatives.set(None);
+        self.book_history.update(BookHistory::clear);
+        self.iceberg_detector.update_value(IcebergDetector::clear);
+        self.icebergs.set(Vec::new());
+        self.spread_history.update(SpreadHistory::clear);
         self.trades.set(Vec::new());
-        self.candles.set(CandleHistory::new(symbol, self.interval.get()));
+        self.liquidations.set(Vec::new());
+        self.base_candles.set(CandleHistory::new(symbol, BASE_INTERVAL));
+        self.chart_span.set(MAX_CANDLES);
+        self.backfill.set(BackfillStatus::Idle);
     }
 
-    /// Change candle interval (clears candle history)
+    /// Change candle interval (resampled locally from the base series)
     pub fn set_interval(&self, interval: CandleInterval) {
         self.interval.set(interval);
-        self.candles.set(CandleHistory::new(self.symbol.get(), interval));
+        self.chart_span.set(MAX_CANDLES);
+    }
+
+    /// Load another page of older candles for the chart. Does nothing until
+    /// the last page has arrived, or once the server has nothing older.
+    pub fn extend_history(&self) {
+        if self.backfill.get_untracked() != BackfillStatus::Idle {
+            return;
+        }
+        let span = self.chart_span.get_untracked();
+        if self.candles.with_untracked(CandleHistory::len) < span {
+            return;
+        }
+        let interval = self.interval.get_untracked();
+        // Past the retention cap a bigger span couldn't hold any more
+        if span * base_factor(interval) < history_capacity(interval, span + MAX_CANDLES) {
+            self.chart_span.set(span + MAX_CANDLES);
+        }
+    }
+
+    /// Base candles kept for the current interval and chart span (untracked)
+    fn history_capacity(&self) -> usize {
+        history_capacity(self.interval.get_untracked(), self.chart_span.get_untracked())
+    }
+
+    /// Older base candles to fetch for the selected interval (`None` while a
+    /// request is out, once history ran out, or if the chart is full)
+    pub fn backfill_request(&self) -> Option<BackfillRequest> {
+        if self.backfill.get_untracked() != BackfillStatus::Idle {
+            return None;
+        }
+        let interval = self.interval.get_untracked();
+        let span = self.chart_span.get_untracked();
+        self.base_candles.with_untracked(|base| backfill_request(base, interval, span))
+    }
+
+    /// Put older base candles from REST in front of the live series; a short
+    /// answer means the server has nothing older
+    pub fn prepend_candles(&self, symbol: &Symbol, request: BackfillRequest, mut candles: Vec<Candle>) {
+        // The symbol changed while the request was out
+        if *symbol != self.symbol.get_untracked() {
+            return;
+        }
+        candles.retain(|c| c.interval == BASE_INTERVAL);
+        candles.sort_by_key(|c| c.timestamp);
+        let exhausted = candles.len() < request.limit;
+        let capacity = self.history_capacity();
+
+        self.base_candles.update(|history| {
+            if let Some(oldest) = history.candles.first().map(|c| c.timestamp) {
+                candles.retain(|c| c.timestamp < oldest);
+            }
+            candles.append(&mut history.candles);
+            trim_front(&mut candles, capacity);
+            history.candles = candles;
+        });
+        self.backfill.set(if exhausted { BackfillStatus::Exhausted } else { BackfillStatus::Idle });
     }
 
     // ========================================================================
@@ -219,13 +568,25 @@ impl MarketState {
     /// Clear all market data
     pub fn clear(&self) {
         let symbol = self.symbol.get();
-        let interval = self.interval.get();
 
         self.ticker.set(None);
         self.orderbook.set(None);
         self.depth.set(None);
+        self.session.set(None);
+        self.vwap_trail.set(Vec::new());
+        self.toxicity.set(None);
+        self.derivatives.set(None);
+        self.book_history.update(BookHistory::clear);
+        self.iceberg_detector.update_value(IcebergDetector::clear);
+        self.icebergs.set(Vec::new());
+        self.spread_history.update(SpreadHistory::clear);
         self.trades.set(Vec::new());
-        self.candles.set(CandleHistory::new(symbol, interval));
+        self.liquidations.set(Vec::new());
+        self.indicators.reset();
+        self.whales.clear();
+        self.base_candles.set(CandleHistory::new(symbol, BASE_INTERVAL));
+        self.chart_span.set(MAX_CANDLES);
+        self.backfill.set(BackfillStatus::Idle);
     }
 }
 
@@ -235,6 +596,100 @@ impl Default for MarketState {
     }
 }
 
+// ============================================================================
+// CANDLE HELPERS
+// ============================================================================
+
+/// Interval of the stored candle series
+pub const BASE_INTERVAL: CandleInterval = CandleInterval::M1;
+
+/// Where loading older candles stands for the current symbol
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum BackfillStatus {
+    #[default]
+    Idle,
+    Loading,
+    /// The server had nothing older (or the request failed); retried after
+    /// a symbol change
+    Exhausted,
+}
+
+/// Page of base candles to request over REST
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BackfillRequest {
+    /// Open time bound (exclusive); `None` = up to now
+    pub before: Option<i64>,
+    pub limit: usize,
+}
+
+/// Base candles per candle of `interval`
+fn base_factor(interval: CandleInterval) -> usize {
+    (interval.as_millis() / BASE_INTERVAL.as_millis()).max(1) as usize
+}
+
+/// Base candles retained for `span` candles of `interval`: a week until the
+/// chart scrolls back past its first page, then as much as the span needs
+pub fn history_capacity(interval: CandleInterval, span: usize) -> usize {
+    if span <= MAX_CANDLES {
+        MAX_BASE_CANDLES
+    } else {
+        (span * base_factor(interval)).clamp(MAX_BASE_CANDLES, MAX_HISTORY_BASE_CANDLES)
+    }
+}
+
+/// Base candles still missing for `span` candles of `interval`, capped at
+/// what the base series retains
+pub fn backfill_request(base: &CandleHistory, interval: CandleInterval, span: usize) -> Option<BackfillRequest> {
+    let wanted = (span * base_factor(interval)).min(history_capacity(interval, span));
+    let missing = wanted.checked_sub(base.candles.len()).filter(|&n| n > 0)?;
+    Some(BackfillRequest {
+        before: base.candles.first().map(|c| c.timestamp),
+        limit: missing,
+    })
+}
+
+/// Resample only as much of the base series as the chart keeps (`span` candles)
+pub(crate) fn resample_tail(base: &CandleHistory, interval: CandleInterval, span: usize) -> CandleHistory {
+    let mut start = base.candles.len().saturating_sub(span * base_factor(interval));
+    // Don't split the first bucket
+    while start > 0
+        && interval.bucket_start(base.candles[start - 1].timestamp)
+            == interval.bucket_start(base.candles[start].timestamp)
+    {
+        start -= 1;
+    }
+    let tail = &base.candles[start..];
+
+    let mut candles = dash_core::resample_candles(tail, interval);
+    trim_front(&mut candles, span);
+
+    CandleHistory {
+        symbol: base.symbol.clone(),
+        interval,
+        candles,
+    }
+}
+
+/// Insert or replace a candle keeping the series ordered by open time
+pub(crate) fn upsert_candle(candles: &mut Vec<Candle>, candle: Candle, capacity: usize) {
+    match candles
+        .iter()
+        .rposition(|c| c.timestamp <= candle.timestamp)
+    {
+        Some(i) if candles[i].timestamp == candle.timestamp => candles[i] = candle,
+        Some(i) => candles.insert(i + 1, candle),
+        None => candles.insert(0, candle),
+    }
+    trim_front(candles, capacity);
+}
+
+/// Drop the oldest entries beyond `max`
+fn trim_front<T>(items: &mut Vec<T>, max: usize) {
+    if items.len() > max {
+        items.drain(..items.len() - max);
+    }
+}
+
 // ============================================================================
 // COMPUTED SIGNALS
 // ============================================================================
@@ -257,11 +712,12 @@ impl PriceDirection {
         }
     }
 
+    /// Themed color (CSS custom property reference)
     pub fn color(&self) -> &'static str {
         match self {
-            Self::Up => dash_core::colors::BULL,
-            Self::Down => dash_core::colors::BEAR,
-            Self::Unchanged => dash_core::colors::NEUTRAL,
+            Self::Up => dash_core::colors::css::BULL,
+            Self::Down => dash_core::colors::css::BEAR,
+            Self::Unchanged => dash_core::colors::css::NEUTRAL,
         }
     }
 
@@ -274,6 +730,25 @@ impl PriceDirection {
     }
 }
 
+/// Newest print in `trades` (newest first) and its tick direction: up or
+/// down against the last different price, so repeat prints keep the direction
+pub fn last_tick(trades: &[Trade]) -> Option<(f64, PriceDirection)> {
+    let (last, earlier) = trades.split_first()?;
+    let price = last.price.as_f64();
+    let direction = earlier
+        .iter()
+        .map(|t| t.price.as_f64())
+        .find(|&p| p != price)
+        .map_or(PriceDirection::Unchanged, |previous| {
+            if price > previous {
+                PriceDirection::Up
+            } else {
+                PriceDirection::Down
+            }
+        });
+    Some((price, direction))
+}
+
 /// Computed market signals (derived from raw data)
 #[derive(Clone)]
 pub struct MarketComputed {
@@ -351,9 +826,83 @@ impl MarketComputed {
 mod tests {
     use super::*;
 
+    fn base_candle(minute: i64, price: f64) -> Candle {
+        Candle::new(Symbol::default(), BASE_INTERVAL, minute * 60_000, price)
+    }
+
+    #[test]
+    fn test_upsert_candle_orders_by_time() {
+        let mut candles = vec![base_candle(0, 1.0), base_candle(2, 3.0)];
+
+        upsert_candle(&mut candles, base_candle(1, 2.0), MAX_BASE_CANDLES);
+        upsert_candle(&mut candles, base_candle(2, 4.0), MAX_BASE_CANDLES);
+
+        let opens: Vec<f64> = candles.iter().map(|c| c.open.as_f64()).collect();
+        assert_eq!(opens, vec![1.0, 2.0, 4.0]);
+    }
+
+    #[test]
+    fn test_resample_tail_limits_output() {
+        let mut base = CandleHistory::new(Symbol::default(), BASE_INTERVAL);
+        base.candles = (0..(MAX_CANDLES as i64 * 5 + 10)).map(|m| base_candle(m, 1.0)).collect();
+
+        let resampled = resample_tail(&base, CandleInterval::M5, MAX_CANDLES);
+        assert_eq!(resampled.interval, CandleInterval::M5);
+        assert_eq!(resampled.candles.len(), MAX_CANDLES);
+        assert_eq!(resampled.candles.last().unwrap().timestamp, MAX_CANDLES as i64 * 5 * 60_000 + 5 * 60_000);
+    }
+
+    #[test]
+    fn test_backfill_request() {
+        let mut base = CandleHistory::new(Symbol::default(), BASE_INTERVAL);
+        assert_eq!(
+            backfill_request(&base, CandleInterval::M1, MAX_CANDLES),
+            Some(BackfillRequest { before: None, limit: MAX_CANDLES })
+        );
+
+        base.candles = (100..100 + MAX_CANDLES as i64).map(|m| base_candle(m, 1.0)).collect();
+        assert_eq!(backfill_request(&base, CandleInterval::M1, MAX_CANDLES), None);
+        assert_eq!(
+            backfill_request(&base, CandleInterval::M5, MAX_CANDLES),
+            Some(BackfillRequest { before: Some(100 * 60_000), limit: MAX_CANDLES * 4 })
+        );
+        // Daily bars can only go back as far as the base series is kept
+        let daily = backfill_request(&base, CandleInterval::D1, MAX_CANDLES).unwrap();
+        assert_eq!(daily.limit, MAX_BASE_CANDLES - MAX_CANDLES);
+    }
+
+    #[test]
+    fn test_scroll_back_pages() {
+        let mut base = CandleHistory::new(Symbol::default(), BASE_INTERVAL);
+        base.candles = (100..100 + MAX_CANDLES as i64).map(|m| base_candle(m, 1.0)).collect();
+
+        // A second page asks for the span's worth in front of the oldest candle
+        assert_eq!(
+            backfill_request(&base, CandleInterval::M1, MAX_CANDLES * 2),
+            Some(BackfillRequest { before: Some(100 * 60_000), limit: MAX_CANDLES })
+        );
+        // Past the first page the retention follows the span, up to the hard cap
+        assert_eq!(history_capacity(CandleInterval::M1, MAX_CANDLES * 2), MAX_BASE_CANDLES);
+        assert_eq!(history_capacity(CandleInterval::H1, MAX_CANDLES * 2), MAX_CANDLES * 2 * 60);
+        assert_eq!(history_capacity(CandleInterval::D1, MAX_CANDLES * 2), MAX_HISTORY_BASE_CANDLES);
+
+        let resampled = resample_tail(&base, CandleInterval::M1, MAX_CANDLES / 2);
+        assert_eq!(resampled.candles.len(), MAX_CANDLES / 2);
+    }
+
     #[test]
     fn test_price_direction() {
         assert_eq!(PriceDirection::Up.arrow(), "▲");
         assert_eq!(PriceDirection::Down.arrow(), "▼");
     }
+
+    #[test]
+    fn test_last_tick_carries_direction_through_repeats() {
+        let print = |price: f64| Trade::new(Symbol::default(), price, 1.0, TradeSide::Buy);
+        assert_eq!(last_tick(&[]), None);
+        assert_eq!(last_tick(&[print(100.0)]), Some((100.0, PriceDirection::Unchanged)));
+        // Newest first: 99 → 101 → 101
+        assert_eq!(last_tick(&[print(101.0), print(101.0), print(99.0)]), Some((101.0, PriceDirection::Up)));
+        assert_eq!(last_tick(&[print(98.0), print(101.0)]), Some((98.0, PriceDirection::Down)));
+    }
 }
\ No newline at end of file
@@ -0,0 +1,448 @@
+//! Notification center: severity-tagged messages shown as toasts and kept in a
+//! capped history
+
+use leptos::prelude::*;
+use std::collections::VecDeque;
+
+/// Notifications retained in history
+pub const MAX_NOTIFICATIONS: usize = 100;
+
+/// How serious a notification is
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Severity {
+    Info,
+    Success,
+    Warning,
+    Error,
+}
+
+impl Severity {
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Info => "Info",
+            Self::Success => "Success",
+            Self::Warning => "Warning",
+            Self::Error => "Error",
+        }
+    }
+
+    pub fn css_class(&self) -> &'static str {
+        match self {
+            Self::Info => "severity-info",
+            Self::Success => "severity-success",
+            Self::Warning => "severity-warning",
+            Self::Error => "severity-error",
+        }
+    }
+
+    pub fn icon(&self) -> &'static str {
+        match self {
+            Self::Info => "ℹ",
+            Self::Success => "✓",
+            Self::Warning => "⚠",
+            Self::Error => "✕",
+        }
+    }
+
+    /// How long a toast stays up (errors stick until dismissed or resolved)
+    pub fn auto_dismiss_ms(&self) -> Option<i64> {
+        match self {
+            Self::Info | Self::Success => Some(4_000),
+            Self::Warning => Some(8_000),
+            Self::Error => None,
+        }
+    }
+}
+
+/// Subsystem that raised a notification
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum NotificationSource {
+    Connection,
+    Alert,
+    OrderFill,
+    Whale,
+    System,
+}
+
+impl NotificationSource {
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Connection => "Connection",
+            Self::Alert => "Alert",
+            Self::OrderFill => "Order Fill",
+            Self::Whale => "Whale Trade",
+            Self::System => "System",
+        }
+    }
+}
+
+/// Follow-up a toast can offer
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum NotificationAction {
+    /// Skip the backoff and reconnect immediately
+    Reconnect,
+}
+
+impl NotificationAction {
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Reconnect => "Reconnect now",
+        }
+    }
+}
+
+/// Single notification
+#[derive(Debug, Clone, PartialEq)]
+pub struct Notification {
+    pub id: u64,
+    pub severity: Severity,
+    pub source: NotificationSource,
+    pub message: String,
+    /// Unix timestamp in milliseconds (latest occurrence)
+    pub timestamp: i64,
+    /// Occurrences folded into this entry while it was still active
+    pub count: u32,
+    pub read: bool,
+    /// Still showing as a toast
+    pub active: bool,
+    pub action: Option<NotificationAction>,
+    /// When the toast hides itself (`None` = sticky)
+    pub expires_at: Option<i64>,
+    /// Time left on the auto-dismiss clock while hovered
+    pub paused_remaining: Option<i64>,
+}
+
+/// Non-reactive notification queue + history
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct NotificationLog {
+    items: VecDeque<Notification>,
+    next_id: u64,
+}
+
+impl NotificationLog {
+    /// Record a notification; repeats of an active one are folded into it
+    pub fn push(
+        &mut self,
+        severity: Severity,
+        source: NotificationSource,
+        message: impl Into<String>,
+        timestamp: i64,
+    ) -> u64 {
+        self.push_with_action(severity, source, message, None, timestamp)
+    }
+
+    /// Record a notification offering a follow-up action
+    pub fn push_with_action(
+        &mut self,
+        severity: Severity,
+        source: NotificationSource,
+        message: impl Into<String>,
+        action: Option<NotificationAction>,
+        timestamp: i64,
+    ) -> u64 {
+        let message = message.into();
+        let expires_at = severity.auto_dismiss_ms().map(|ms| timestamp + ms);
+
+        if let Some(existing) = self.items.iter_mut().find(|n| {
+            n.active && n.severity == severity && n.source == source && n.message == message
+        }) {
+            existing.count += 1;
+            existing.timestamp = timestamp;
+            existing.read = false;
+            existing.expires_at = expires_at;
+            existing.action = action.or(existing.action);
+            return existing.id;
+        }
+
+        self.next_id += 1;
+        self.items.push_back(Notification {
+            id: self.next_id,
+            severity,
+            source,
+            message,
+            timestamp,
+            count: 1,
+            read: false,
+            active: true,
+            action,
+            expires_at,
+            paused_remaining: None,
+        });
+        if self.items.len() > MAX_NOTIFICATIONS {
+            self.items.pop_front();
+        }
+
+        self.next_id
+    }
+
+    /// Hide a toast (it stays in history)
+    pub fn dismiss(&mut self, id: u64) {
+        if let Some(n) = self.items.iter_mut().find(|n| n.id == id) {
+            n.active = false;
+        }
+    }
+
+    /// Hide every toast from a source (e.g. connection restored)
+    pub fn dismiss_source(&mut self, source: NotificationSource) {
+        for n in self.items.iter_mut().filter(|n| n.source == source) {
+            n.active = false;
+        }
+    }
+
+    pub fn mark_read(&mut self, id: u64) {
+        if let Some(n) = self.items.iter_mut().find(|n| n.id == id) {
+            n.read = true;
+        }
+    }
+
+    /// Stop a toast's auto-dismiss clock (pointer hovering it)
+    pub fn pause(&mut self, id: u64, now: i64) {
+        if let Some(n) = self.items.iter_mut().find(|n| n.id == id && n.paused_remaining.is_none())
+            && let Some(expires_at) = n.expires_at
+        {
+            n.paused_remaining = Some((expires_at - now).max(0));
+        }
+    }
+
+    /// Restart a paused toast's clock with the time it had left
+    pub fn resume(&mut self, id: u64, now: i64) {
+        if let Some(n) = self.items.iter_mut().find(|n| n.id == id)
+            && let Some(remaining) = n.paused_remaining.take()
+        {
+            n.expires_at = Some(now + remaining);
+        }
+    }
+
+    /// Toasts whose clock has run out (paused ones are kept)
+    fn expired(&self, now: i64) -> impl Iterator<Item = &Notification> {
+        self.items.iter().filter(move |n| {
+            n.active && n.paused_remaining.is_none() && n.expires_at.is_some_and(|t| t <= now)
+        })
+    }
+
+    /// Hide expired toasts
+    pub fn expire(&mut self, now: i64) {
+        let ids: Vec<u64> = self.expired(now).map(|n| n.id).collect();
+        for id in ids {
+            self.dismiss(id);
+        }
+    }
+
+    /// Anything due to expire at `now`?
+    pub fn has_expired(&self, now: i64) -> bool {
+        self.expired(now).next().is_some()
+    }
+
+    pub fn mark_all_read(&mut self) {
+        for n in self.items.iter_mut() {
+            n.read = true;
+        }
+    }
+
+    /// Drop history entirely
+    pub fn clear(&mut self) {
+        self.items.clear();
+    }
+
+    /// Active toasts, oldest first
+    pub fn toasts(&self) -> impl Iterator<Item = &Notification> {
+        self.items.iter().filter(|n| n.active)
+    }
+
+    /// Full history, newest first
+    pub fn history(&self) -> impl Iterator<Item = &Notification> {
+        self.items.iter().rev()
+    }
+
+    pub fn unread_count(&self) -> usize {
+        self.items.iter().filter(|n| !n.read).count()
+    }
+
+    /// Most recent active toast at or above `min` severity
+    pub fn latest_active(&self, min: Severity) -> Option<&Notification> {
+        self.items
+            .iter()
+            .rev()
+            .find(|n| n.active && n.severity >= min)
+    }
+}
+
+/// Reactive notification center
+#[derive(Clone, Copy)]
+pub struct NotificationsState {
+    pub log: RwSignal<NotificationLog>,
+    /// Unread notifications (for badges)
+    pub unread: Memo<usize>,
+}
+
+impl NotificationsState {
+    pub fn new() -> Self {
+        let log = RwSignal::new(NotificationLog::default());
+        Self {
+            log,
+            unread: Memo::new(move |_| log.with(|l| l.unread_count())),
+        }
+    }
+
+    /// Raise a notification; returns its ID
+    pub fn notify(
+        &self,
+        severity: Severity,
+        source: NotificationSource,
+        message: impl Into<String>,
+    ) -> u64 {
+        let now = chrono::Utc::now().timestamp_millis();
+        let mut id = 0;
+        self.log.update(|log| id = log.push(severity, source, message, now));
+        id
+    }
+
+    /// Raise a notification with a follow-up action button
+    pub fn notify_with_action(
+        &self,
+        severity: Severity,
+        source: NotificationSource,
+        message: impl Into<String>,
+        action: NotificationAction,
+    ) -> u64 {
+        let now = chrono::Utc::now().timestamp_millis();
+        let mut id = 0;
+        self.log
+            .update(|log| id = log.push_with_action(severity, source, message, Some(action), now));
+        id
+    }
+
+    pub fn dismiss(&self, id: u64) {
+        self.log.update(|log| log.dismiss(id));
+    }
+
+    pub fn pause(&self, id: u64, now: i64) {
+        self.log.update(|log| log.pause(id, now));
+    }
+
+    pub fn resume(&self, id: u64, now: i64) {
+        self.log.update(|log| log.resume(id, now));
+    }
+
+    /// Hide toasts whose auto-dismiss clock has run out (call periodically)
+    pub fn expire(&self, now: i64) {
+        if self.log.with_untracked(|log| log.has_expired(now)) {
+            self.log.update(|log| log.expire(now));
+        }
+    }
+
+    pub fn dismiss_source(&self, source: NotificationSource) {
+        let any_active = self
+            .log
+            .with_untracked(|log| log.toasts().any(|n| n.source == source));
+        if any_active {
+            self.log.update(|log| log.dismiss_source(source));
+        }
+    }
+
+    pub fn mark_read(&self, id: u64) {
+        self.log.update(|log| log.mark_read(id));
+    }
+
+    pub fn mark_all_read(&self) {
+        self.log.update(|log| log.mark_all_read());
+    }
+
+    pub fn clear(&self) {
+        self.log.update(|log| log.clear());
+    }
+
+    /// Active toasts, oldest first
+    pub fn toasts(&self) -> Vec<Notification> {
+        self.log.with(|log| log.toasts().cloned().collect())
+    }
+
+    /// Full history, newest first
+    pub fn history(&self) -> Vec<Notification> {
+        self.log.with(|log| log.history().cloned().collect())
+    }
+
+    /// Most recent active toast at or above `min` severity
+    pub fn latest_active(&self, min: Severity) -> Option<Notification> {
+        self.log.with(|log| log.latest_active(min).cloned())
+    }
+}
+
+impl Default for NotificationsState {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_repeats_fold_into_active_toast() {
+        let mut log = NotificationLog::default();
+        let a = log.push(Severity::Error, NotificationSource::Connection, "down", 1);
+        let b = log.push(Severity::Error, NotificationSource::Connection, "down", 2);
+        assert_eq!(a, b);
+        assert_eq!(log.toasts().next().unwrap().count, 2);
+
+        log.dismiss(a);
+        let c = log.push(Severity::Error, NotificationSource::Connection, "down", 3);
+        assert_ne!(a, c);
+        assert_eq!(log.history().count(), 2);
+    }
+
+    #[test]
+    fn test_simultaneous_issues_are_kept() {
+        let mut log = NotificationLog::default();
+        log.push(Severity::Error, NotificationSource::Connection, "socket closed", 1);
+        log.push(Severity::Warning, NotificationSource::Alert, "BTC > 100k", 2);
+        log.push(Severity::Info, NotificationSource::OrderFill, "filled 0.1", 3);
+
+        assert_eq!(log.toasts().count(), 3);
+        assert_eq!(log.latest_active(Severity::Warning).unwrap().message, "BTC > 100k");
+
+        log.dismiss_source(NotificationSource::Connection);
+        assert_eq!(log.toasts().count(), 2);
+        assert_eq!(log.unread_count(), 3);
+
+        log.mark_all_read();
+        assert_eq!(log.unread_count(), 0);
+    }
+
+    #[test]
+    fn test_auto_dismiss_pauses_on_hover() {
+        let mut log = NotificationLog::default();
+        let info = log.push(Severity::Info, NotificationSource::System, "saved", 0);
+        let error = log.push_with_action(
+            Severity::Error,
+            NotificationSource::Connection,
+            "down",
+            Some(NotificationAction::Reconnect),
+            0,
+        );
+
+        log.pause(info, 1_000);
+        log.expire(10_000);
+        assert_eq!(log.toasts().count(), 2);
+
+        // 3s were left when hovered
+        log.resume(info, 10_000);
+        log.expire(12_999);
+        assert_eq!(log.toasts().count(), 2);
+        log.expire(13_000);
+
+        let remaining: Vec<u64> = log.toasts().map(|n| n.id).collect();
+        assert_eq!(remaining, vec![error]);
+    }
+
+    #[test]
+    fn test_history_is_capped() {
+        let mut log = NotificationLog::default();
+        for i in 0..(MAX_NOTIFICATIONS + 5) {
+            log.push(Severity::Info, NotificationSource::System, format!("n{}", i), i as i64);
+        }
+        assert_eq!(log.history().count(), MAX_NOTIFICATIONS);
+        assert_eq!(log.history().last().unwrap().message, "n5");
+    }
+}
@@ -0,0 +1,266 @@
+//! Reactive paper-trading account state fed by the server's portfolio messages
+
+use crate::{MAX_EQUITY_SAMPLES, MAX_RECENT_FILLS};
+use dash_core::{AccountSummary, Balance, EquitySample, OrderStatus, PaperOrder, PortfolioSnapshot, Position, Price, Symbol, TradeSide};
+use leptos::prelude::*;
+
+/// Quote asset used for equity and exposure
+pub const QUOTE_ASSET: &str = "USD";
+
+/// Price and side picked elsewhere (e.g. an order book click) for the ticket
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TicketFill {
+    /// Bumped per click so picking the same level twice still notifies
+    pub seq: u64,
+    pub side: TradeSide,
+    pub price: f64,
+    /// Send immediately instead of waiting for review
+    pub submit: bool,
+}
+
+/// Reactive paper-trading portfolio
+#[derive(Clone)]
+pub struct PortfolioState {
+    /// Open positions (flat positions are dropped)
+    pub positions: RwSignal<Vec<Position>>,
+    /// Working orders (terminal orders are dropped)
+    pub orders: RwSignal<Vec<PaperOrder>>,
+    /// Recently filled orders (oldest first), for the chart's entry markers
+    pub fills: RwSignal<Vec<PaperOrder>>,
+    /// Asset balances
+    pub balances: RwSignal<Vec<Balance>>,
+    /// Equity curve samples (oldest first)
+    pub equity: RwSignal<Vec<EquitySample>>,
+    /// Server totals: today's realized PnL and fees paid
+    pub account: RwSignal<Option<AccountSummary>>,
+    /// Realized + unrealized PnL across positions
+    pub total_pnl: Memo<f64>,
+    /// Unrealized PnL across positions
+    pub unrealized_pnl: Memo<f64>,
+    /// Gross notional exposure at mark
+    pub exposure: Memo<f64>,
+    /// Quote balance plus positions at mark
+    pub account_equity: Memo<f64>,
+}
+
+impl PortfolioState {
+    pub fn new() -> Self {
+        let positions = RwSignal::new(Vec::<Position>::new());
+        let balances = RwSignal::new(Vec::<Balance>::new());
+
+        let unrealized_pnl =
+            Memo::new(move |_| positions.with(|p| p.iter().map(Position::unrealized_pnl).sum()));
+
+        Self {
+            positions,
+            orders: RwSignal::new(Vec::new()),
+            fills: RwSignal::new(Vec::new()),
+            balances,
+            equity: RwSignal::new(Vec::new()),
+            account: RwSignal::new(None),
+            total_pnl: Memo::new(move |_| {
+                let realized: f64 = positions.with(|p| p.iter().map(|pos| pos.realized_pnl).sum());
+                realized + unrealized_pnl.get()
+            }),
+            unrealized_pnl,
+            exposure: Memo::new(move |_| positions.with(|p| p.iter().map(Position::notional).sum())),
+            // Fills move the cost of a position out of the quote balance, so
+            // equity adds the positions back at mark
+            account_equity: Memo::new(move |_| {
+                let cash = balances.with(|b| quote_balance(b));
+                let holdings: f64 = positions.with(|p| p.iter().map(|pos| pos.quantity * pos.mark_price.as_f64()).sum());
+                cash + holdings
+            }),
+        }
+    }
+
+    /// Replace everything with a server snapshot
+    pub fn apply_snapshot(&self, snapshot: PortfolioSnapshot) {
+        let PortfolioSnapshot {
+            balances,
+            mut positions,
+            mut orders,
+            mut equity,
+        } = snapshot;
+
+        positions.retain(|p| !p.is_flat());
+        orders.retain(|o| o.status.is_open());
+        if equity.len() > MAX_EQUITY_SAMPLES {
+            equity.drain(..equity.len() - MAX_EQUITY_SAMPLES);
+        }
+
+        self.balances.set(balances);
+        self.positions.set(positions);
+        self.orders.set(orders);
+        self.equity.set(equity);
+    }
+
+    /// Insert/replace an order; filled, cancelled and rejected orders are
+    /// removed, fills moving to the recent fills
+    pub fn update_order(&self, order: PaperOrder) {
+        if order.status == OrderStatus::Filled {
+            self.fills.update(|fills| {
+                fills.retain(|f| f.id != order.id);
+                fills.push(order.clone());
+                if fills.len() > MAX_RECENT_FILLS {
+                    fills.remove(0);
+                }
+            });
+        }
+        self.orders.update(|orders| {
+            let existing = orders.iter().position(|o| o.id == order.id);
+            match (existing, order.status.is_open()) {
+                (Some(i), true) => orders[i] = order,
+                (Some(i), false) => {
+                    orders.remove(i);
+                }
+                (None, true) => orders.push(order),
+                (None, false) => {}
+            }
+        });
+    }
+
+    /// Insert/replace a position; flat positions are removed
+    pub fn update_position(&self, position: Position) {
+        self.positions.update(|positions| {
+            let existing = positions.iter().position(|p| p.symbol == position.symbol);
+            match (existing, position.is_flat()) {
+                (Some(i), false) => positions[i] = position,
+                (Some(i), true) => {
+                    positions.remove(i);
+                }
+                (None, false) => positions.push(position),
+                (None, true) => {}
+            }
+        });
+    }
+
+    /// Insert/replace a balance by asset
+    pub fn update_balance(&self, balance: Balance) {
+        self.balances.update(|balances| {
+            match balances.iter_mut().find(|b| b.asset == balance.asset) {
+                Some(existing) => *existing = balance,
+                None => balances.push(balance),
+            }
+        });
+    }
+
+    /// Re-mark the position in `symbol` at the latest price
+    pub fn mark(&self, symbol: &Symbol, price: f64) {
+        let held = self
+            .positions
+            .with_untracked(|p| p.iter().any(|pos| pos.symbol == *symbol));
+        if held {
+            self.positions.update(|positions| {
+                for pos in positions.iter_mut().filter(|pos| pos.symbol == *symbol) {
+                    pos.mark_price = Price::new(price);
+                }
+            });
+        }
+    }
+
+    /// Append an equity curve sample
+    pub fn push_equity(&self, sample: EquitySample) {
+        self.equity.update(|equity| {
+            equity.push(sample);
+            if equity.len() > MAX_EQUITY_SAMPLES {
+                equity.remove(0);
+            }
+        });
+    }
+
+    /// Free (unreserved) quote balance available for new orders
+    pub fn available_quote(&self) -> f64 {
+        self.balances.with(|balances| {
+            balances
+                .iter()
+                .filter(|b| b.asset == QUOTE_ASSET)
+                .map(|b| b.free)
+                .sum()
+        })
+    }
+
+    /// Clear the account (e.g. on disconnect from a different server)
+    pub fn clear(&self) {
+        self.apply_snapshot(PortfolioSnapshot::default());
+        self.account.set(None);
+    }
+}
+
+impl Default for PortfolioState {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Total balance held in the quote asset
+pub fn quote_balance(balances: &[Balance]) -> f64 {
+    balances
+        .iter()
+        .filter(|b| b.asset == QUOTE_ASSET)
+        .map(Balance::total)
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_quote_balance() {
+        let balances = vec![
+            Balance { asset: "USD".into(), free: 900.0, locked: 100.0 },
+            Balance { asset: "BTC".into(), free: 1.0, locked: 0.0 },
+        ];
+        assert_eq!(quote_balance(&balances), 1000.0);
+    }
+
+    #[test]
+    fn test_account_equity_marks_positions() {
+        let portfolio = PortfolioState::new();
+        // 1 BTC bought at 100 out of 150
+        portfolio.update_balance(Balance { asset: "USD".into(), free: 40.0, locked: 10.0 });
+        portfolio.update_position(Position {
+            symbol: Symbol::new("BTC-USD"),
+            quantity: 1.0,
+            entry_price: Price::new(100.0),
+            mark_price: Price::new(100.0),
+            realized_pnl: 0.0,
+        });
+        assert_eq!(portfolio.account_equity.get_untracked(), 150.0);
+
+        portfolio.mark(&Symbol::new("BTC-USD"), 110.0);
+        assert_eq!(portfolio.account_equity.get_untracked(), 160.0);
+        assert_eq!(portfolio.unrealized_pnl.get_untracked(), 10.0);
+    }
+
+    #[test]
+    fn test_filled_orders_move_to_fills() {
+        let portfolio = PortfolioState::new();
+        let mut order = PaperOrder {
+            id: "o1".into(),
+            symbol: Symbol::default(),
+            side: TradeSide::Buy,
+            order_type: dash_core::OrderType::Limit,
+            price: Some(Price::new(100.0)),
+            stop_price: None,
+            quantity: dash_core::Quantity::new(1.0),
+            filled: dash_core::Quantity::ZERO,
+            status: OrderStatus::Open,
+            created_at: 0,
+            fill_price: None,
+            filled_at: None,
+        };
+
+        portfolio.update_order(order.clone());
+        assert_eq!(portfolio.orders.get_untracked().len(), 1);
+        assert!(portfolio.fills.get_untracked().is_empty());
+
+        order.status = OrderStatus::Filled;
+        order.fill_price = Some(Price::new(99.5));
+        portfolio.update_order(order.clone());
+        portfolio.update_order(order);
+        assert!(portfolio.orders.get_untracked().is_empty());
+        assert_eq!(portfolio.fills.get_untracked().len(), 1);
+    }
+}
@@ -0,0 +1,122 @@
+//! Time-travel debugging: ring buffer of incoming messages that can be
+//! rewound and replayed into the signals (`time-travel` feature)
+
+use dash_core::WsMessage;
+use leptos::prelude::*;
+use std::collections::VecDeque;
+
+/// Messages kept for replay
+pub const MAX_RECORDED: usize = 2_000;
+
+/// Incoming message with its local receipt time
+#[derive(Debug, Clone)]
+pub struct RecordedMessage {
+    /// Unix timestamp in milliseconds
+    pub received_at: i64,
+    pub message: WsMessage,
+}
+
+/// Recorded message buffer plus scrubber position
+#[derive(Clone, Copy)]
+pub struct MessageRecorder {
+    pub entries: RwSignal<VecDeque<RecordedMessage>>,
+    /// `None` = live; `Some(i)` = signals reflect messages `..=i`
+    pub position: RwSignal<Option<usize>>,
+}
+
+impl MessageRecorder {
+    pub fn new() -> Self {
+        Self {
+            entries: RwSignal::new(VecDeque::with_capacity(MAX_RECORDED)),
+            position: RwSignal::new(None),
+        }
+    }
+
+    pub fn is_live(&self) -> bool {
+        self.position.get_untracked().is_none()
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.with(|e| e.len())
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Append a message, evicting the oldest when full
+    pub fn record(&self, message: WsMessage) {
+        let entry = RecordedMessage {
+            received_at: chrono::Utc::now().timestamp_millis(),
+            message,
+        };
+
+        let mut evicted = false;
+        self.entries.update(|entries| {
+            entries.push_back(entry);
+            if entries.len() > MAX_RECORDED {
+                entries.pop_front();
+                evicted = true;
+            }
+        });
+
+        // Keep the scrubber pointing at the same message
+        if evicted && let Some(pos) = self.position.get_untracked() {
+            self.position.set(Some(pos.saturating_sub(1)));
+        }
+    }
+
+    /// Messages `..=index` (clamped), oldest first
+    pub fn messages_through(&self, index: usize) -> Vec<WsMessage> {
+        self.entries.with_untracked(|entries| {
+            entries
+                .iter()
+                .take(index.saturating_add(1))
+                .map(|e| e.message.clone())
+                .collect()
+        })
+    }
+
+    /// Entry at the scrubber position (latest when live)
+    pub fn current(&self) -> Option<RecordedMessage> {
+        let position = self.position.get();
+        self.entries.with(|entries| match position {
+            Some(i) => entries.get(i).cloned(),
+            None => entries.back().cloned(),
+        })
+    }
+
+    pub fn clear(&self) {
+        self.entries.update(|e| e.clear());
+        self.position.set(None);
+    }
+}
+
+impl Default for MessageRecorder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_eviction_keeps_scrubber_on_message() {
+        let recorder = MessageRecorder::new();
+        for ts in 0..MAX_RECORDED as i64 {
+            recorder.record(WsMessage::Heartbeat { timestamp: ts });
+        }
+        recorder.position.set(Some(10));
+
+        recorder.record(WsMessage::Heartbeat { timestamp: -1 });
+
+        assert_eq!(recorder.len(), MAX_RECORDED);
+        assert_eq!(recorder.position.get_untracked(), Some(9));
+        assert!(matches!(
+            recorder.messages_through(9).last(),
+            Some(WsMessage::Heartbeat { timestamp: 10 })
+        ));
+    }
+}
@@ -0,0 +1,173 @@
+//! Client → server commands (the WS RPC layer) and paper order tracking
+
+use dash_core::{BookFeed, ClientCommand, OrderRejection, OrderRequest, SessionAnchor, Symbol};
+use futures::channel::mpsc::{unbounded, UnboundedReceiver, UnboundedSender};
+use leptos::prelude::*;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+/// Outgoing command queue plus request/response bookkeeping. Commands sent
+/// while disconnected wait in the queue until the transport reconnects.
+#[derive(Clone)]
+pub struct RpcState {
+    sender: UnboundedSender<ClientCommand>,
+    receiver: Arc<Mutex<Option<UnboundedReceiver<ClientCommand>>>>,
+    /// Orders sent but not yet acknowledged, by client id
+    pub pending: RwSignal<HashMap<String, OrderRequest>>,
+    /// Engine rejections by client id
+    pub rejections: RwSignal<HashMap<String, OrderRejection>>,
+}
+
+impl RpcState {
+    pub fn new() -> Self {
+        let (sender, receiver) = unbounded();
+        Self {
+            sender,
+            receiver: Arc::new(Mutex::new(Some(receiver))),
+            pending: RwSignal::new(HashMap::new()),
+            rejections: RwSignal::new(HashMap::new()),
+        }
+    }
+
+    /// Queue a command for the server
+    pub fn send(&self, command: ClientCommand) {
+        if self.sender.unbounded_send(command).is_err() {
+            tracing::warn!("Command queue closed; dropping command");
+        }
+    }
+
+    /// Hand the queue's receiving end to the transport (only the first caller gets it)
+    pub fn take_receiver(&self) -> Option<UnboundedReceiver<ClientCommand>> {
+        self.receiver.lock().ok()?.take()
+    }
+
+    pub fn subscribe(&self, symbol: &Symbol) {
+        self.send(ClientCommand::Subscribe {
+            symbol: symbol.to_string(),
+        });
+    }
+
+    pub fn unsubscribe(&self, symbol: &Symbol) {
+        self.send(ClientCommand::Unsubscribe {
+            symbol: symbol.to_string(),
+        });
+    }
+
+    /// Ask the analytics channel for sessions from `anchor`
+    pub fn track_session(&self, anchor: SessionAnchor) {
+        self.send(ClientCommand::TrackSession { anchor });
+    }
+
+    /// Latest ticker, book and candle for `symbol`, answered by a snapshot
+    pub fn request_snapshot(&self, symbol: &Symbol) {
+        self.send(ClientCommand::RequestSnapshot { symbol: symbol.clone() });
+    }
+
+    /// Page of 1m candles over the socket rather than REST
+    pub fn request_candle_history(&self, symbol: &Symbol, before: Option<i64>, limit: usize) {
+        self.send(ClientCommand::RequestCandleHistory {
+            symbol: symbol.clone(),
+            before,
+            limit,
+        });
+    }
+
+    pub fn list_symbols(&self) {
+        self.send(ClientCommand::ListSymbols);
+    }
+
+    /// Cap book, depth and BBO updates at one per `interval_ms` (`None` = all)
+    pub fn set_conflation(&self, interval_ms: Option<u32>) {
+        self.send(ClientCommand::SetConflation { interval_ms });
+    }
+
+    /// Full book updates, or just the BBO for views that only show the touch
+    pub fn set_book_feed(&self, feed: BookFeed) {
+        self.send(ClientCommand::SetBookFeed { feed });
+    }
+
+    /// Track and send a new order
+    pub fn submit_order(&self, order: OrderRequest) {
+        let client_id = order.client_id.clone();
+        self.rejections.update(|r| {
+            r.remove(&client_id);
+        });
+        self.pending.update(|p| {
+            p.insert(client_id, order.clone());
+        });
+        self.send(ClientCommand::PlaceOrder { order });
+    }
+
+    pub fn cancel_order(&self, order_id: impl Into<String>) {
+        self.send(ClientCommand::CancelOrder {
+            order_id: order_id.into(),
+        });
+    }
+
+    pub fn amend_order(&self, order_id: impl Into<String>, price: f64) {
+        self.send(ClientCommand::AmendOrder {
+            order_id: order_id.into(),
+            price,
+        });
+    }
+
+    /// The engine acknowledged an order (any status)
+    pub fn resolve(&self, client_id: &str) {
+        if self.pending.with_untracked(|p| p.contains_key(client_id)) {
+            self.pending.update(|p| {
+                p.remove(client_id);
+            });
+        }
+    }
+
+    /// The engine refused an order
+    pub fn reject(&self, client_id: &str, reason: OrderRejection) {
+        self.resolve(client_id);
+        self.rejections.update(|r| {
+            r.insert(client_id.to_string(), reason);
+        });
+    }
+
+    pub fn is_pending(&self, client_id: &str) -> bool {
+        self.pending.with(|p| p.contains_key(client_id))
+    }
+
+    pub fn rejection(&self, client_id: &str) -> Option<OrderRejection> {
+        self.rejections.with(|r| r.get(client_id).cloned())
+    }
+}
+
+impl Default for RpcState {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use dash_core::{OrderType, Symbol, TradeSide};
+
+    #[test]
+    fn test_order_round_trip() {
+        let rpc = RpcState::new();
+        let mut rx = rpc.take_receiver().unwrap();
+        assert!(rpc.take_receiver().is_none());
+
+        rpc.submit_order(OrderRequest {
+            client_id: "c1".into(),
+            symbol: Symbol::default(),
+            side: TradeSide::Buy,
+            order_type: OrderType::Market,
+            price: None,
+            stop_price: None,
+            quantity: 1.0,
+        });
+        assert!(rpc.is_pending("c1"));
+        assert!(matches!(rx.try_next(), Ok(Some(ClientCommand::PlaceOrder { .. }))));
+
+        rpc.reject("c1", OrderRejection::UnknownSymbol);
+        assert!(!rpc.is_pending("c1"));
+        assert_eq!(rpc.rejection("c1"), Some(OrderRejection::UnknownSymbol));
+    }
+}
@@ -0,0 +1,309 @@
+//! Versioned persisted preferences with step-by-step schema migrations
+//!
+//! Stored as `{"version": N, "settings": {...}}`. Older payloads are walked
+//! forward through [`MIGRATIONS`] one version at a time; each field is then
+//! decoded on its own so one bad or renamed value falls back to its default
+//! instead of discarding everything.
+
+use crate::{storage, GridChart, GridSize, KeyBindings, LayoutState, Locale, SoundPrefs, Theme, UiState};
+use dash_core::{BrickSize, CandleInterval, ChartOverlay, ChartType, PriceScaleMode, RenderBackend, SessionAnchor, Symbol, TimeZoneMode};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use serde_json::{json, Value};
+use std::collections::BTreeMap;
+
+/// Current schema version
+pub const SETTINGS_VERSION: u32 = 2;
+
+/// Upgrade steps; `MIGRATIONS[n]` turns version `n + 1` data into `n + 2`
+const MIGRATIONS: &[fn(Value) -> Value] = &[v1_to_v2];
+
+/// v1 persisted only the bare layout under [`LayoutState::STORAGE_KEY`]
+fn v1_to_v2(layout: Value) -> Value {
+    json!({ "layout": layout })
+}
+
+/// User preferences that survive reloads
+#[derive(Debug, Clone, PartialEq, Default, Serialize)]
+pub struct Settings {
+    pub theme: Theme,
+    pub layout: LayoutState,
+    pub compact_mode: bool,
+    /// Starred symbols, pinned in the symbol selector
+    pub favorites: Vec<Symbol>,
+    /// Remapped keyboard shortcuts
+    pub key_bindings: KeyBindings,
+    /// One-click order sending from the book
+    pub fast_trading: bool,
+    /// Reduced motion / screen reader friendly mode
+    pub accessible_mode: bool,
+    /// Sound alert volumes and mutes
+    pub sounds: SoundPrefs,
+    /// UI language (`None` follows the browser)
+    pub locale: Option<Locale>,
+    /// Toast on whale prints
+    pub whale_notifications: bool,
+    /// Onboarding tour finished or skipped
+    pub has_seen_tour: bool,
+    /// Volume bars hidden under the candles
+    pub hide_volume: bool,
+    /// Session VWAP bands on the chart
+    pub show_vwap: bool,
+    /// Candles, bars, line, area, Heikin-Ashi, renko or range bars
+    pub chart_type: ChartType,
+    /// Renko brick or range bar size
+    pub brick_size: BrickSize,
+    /// SVG or canvas chart rendering
+    pub chart_backend: RenderBackend,
+    /// Linear, log or percent price axis
+    pub price_scale: PriceScaleMode,
+    /// Chart time axis in UTC or local time
+    pub time_zone: TimeZoneMode,
+    /// Price auto-fit includes indicator bands
+    pub fit_indicators: bool,
+    /// Best bid and ask lines on the chart
+    pub show_quotes: bool,
+    /// Crosshair OHLCV tooltip turned off
+    pub hide_tooltip: bool,
+    /// Trade prints over the candles
+    pub show_prints: bool,
+    /// Cumulative volume delta pane under the chart
+    pub show_cvd: bool,
+    /// Chart grid in place of the single chart
+    pub chart_grid: Option<GridSize>,
+    /// Day start for the session stats
+    pub session_anchor: SessionAnchor,
+    /// Chart timeframe per symbol
+    pub chart_intervals: BTreeMap<Symbol, CandleInterval>,
+    /// Studies drawn over the candles
+    pub chart_overlays: Vec<ChartOverlay>,
+    /// Symbol and timeframe of each grid chart
+    pub grid_charts: Vec<GridChart>,
+}
+
+impl Settings {
+    /// Storage key for the versioned envelope
+    pub const STORAGE_KEY: &'static str = "dash.settings";
+
+    pub fn capture(
+        ui: &UiState,
+        favorites: Vec<Symbol>,
+        key_bindings: KeyBindings,
+        sounds: SoundPrefs,
+        chart_intervals: BTreeMap<Symbol, CandleInterval>,
+        chart_overlays: Vec<ChartOverlay>,
+        grid_charts: Vec<GridChart>,
+    ) -> Self {
+        Self {
+            theme: ui.theme,
+            layout: ui.layout,
+            compact_mode: ui.compact_mode,
+            favorites,
+            key_bindings,
+            fast_trading: ui.fast_trading,
+            accessible_mode: ui.accessible_mode,
+            sounds,
+            locale: ui.locale,
+            whale_notifications: ui.whale_notifications,
+            has_seen_tour: ui.has_seen_tour,
+            hide_volume: ui.hide_volume,
+            show_vwap: ui.show_vwap,
+            chart_type: ui.chart_type,
+            brick_size: ui.brick_size,
+            chart_backend: ui.chart_backend,
+            price_scale: ui.price_scale,
+            time_zone: ui.time_zone,
+            fit_indicators: ui.fit_indicators,
+            show_quotes: ui.show_quotes,
+            hide_tooltip: ui.hide_tooltip,
+            show_prints: ui.show_prints,
+            show_cvd: ui.show_cvd,
+            chart_grid: ui.chart_grid,
+            session_anchor: ui.session_anchor,
+            chart_intervals,
+            chart_overlays,
+            grid_charts,
+        }
+    }
+
+    /// Copy the persisted preferences onto the UI state
+    pub fn apply_to(&self, ui: &mut UiState) {
+        ui.theme = self.theme;
+        ui.layout = self.layout;
+        ui.compact_mode = self.compact_mode;
+        ui.fast_trading = self.fast_trading;
+        ui.accessible_mode = self.accessible_mode;
+        ui.locale = self.locale;
+        ui.whale_notifications = self.whale_notifications;
+        ui.has_seen_tour = self.has_seen_tour;
+        ui.hide_volume = self.hide_volume;
+        ui.show_vwap = self.show_vwap;
+        ui.chart_type = self.chart_type;
+        ui.brick_size = self.brick_size;
+        ui.chart_backend = self.chart_backend;
+        ui.price_scale = self.price_scale;
+        ui.time_zone = self.time_zone;
+        ui.fit_indicators = self.fit_indicators;
+        ui.show_quotes = self.show_quotes;
+        ui.hide_tooltip = self.hide_tooltip;
+        ui.show_prints = self.show_prints;
+        ui.show_cvd = self.show_cvd;
+        ui.chart_grid = self.chart_grid;
+        ui.session_anchor = self.session_anchor;
+    }
+
+    /// Serialize inside a versioned envelope
+    pub fn to_json(&self) -> String {
+        json!({ "version": SETTINGS_VERSION, "settings": self }).to_string()
+    }
+
+    /// Decode an envelope of any known version (`None` if not JSON at all)
+    pub fn from_json(json: &str) -> Option<Self> {
+        let envelope: Value = serde_json::from_str(json).ok()?;
+        let version = envelope
+            .get("version")
+            .and_then(Value::as_u64)
+            .map_or(1, |v| v as u32);
+        let data = envelope.get("settings").cloned().unwrap_or(Value::Null);
+        Some(Self::from_versioned(version, data))
+    }
+
+    /// Migrate `data` from `version` to the current schema and decode it
+    pub fn from_versioned(version: u32, data: Value) -> Self {
+        if version > SETTINGS_VERSION {
+            tracing::warn!(
+                "Settings v{} are newer than v{}; reading known fields only",
+                version,
+                SETTINGS_VERSION
+            );
+        }
+
+        let data = MIGRATIONS
+            .iter()
+            .skip(version.saturating_sub(1) as usize)
+            .fold(data, |data, migrate| migrate(data));
+
+        let layout: LayoutState = field(&data, "layout");
+        Self {
+            theme: field(&data, "theme"),
+            layout: layout.clamped(),
+            compact_mode: field(&data, "compact_mode"),
+            favorites: field(&data, "favorites"),
+            key_bindings: field(&data, "key_bindings"),
+            fast_trading: field(&data, "fast_trading"),
+            accessible_mode: field(&data, "accessible_mode"),
+            sounds: field(&data, "sounds"),
+            locale: field(&data, "locale"),
+            whale_notifications: field(&data, "whale_notifications"),
+            has_seen_tour: field(&data, "has_seen_tour"),
+            hide_volume: field(&data, "hide_volume"),
+            show_vwap: field(&data, "show_vwap"),
+            chart_type: field(&data, "chart_type"),
+            brick_size: field(&data, "brick_size"),
+            chart_backend: field(&data, "chart_backend"),
+            price_scale: field(&data, "price_scale"),
+            time_zone: field(&data, "time_zone"),
+            fit_indicators: field(&data, "fit_indicators"),
+            show_quotes: field(&data, "show_quotes"),
+            hide_tooltip: field(&data, "hide_tooltip"),
+            show_prints: field(&data, "show_prints"),
+            show_cvd: field(&data, "show_cvd"),
+            chart_grid: field(&data, "chart_grid"),
+            session_anchor: field(&data, "session_anchor"),
+            chart_intervals: field(&data, "chart_intervals"),
+            chart_overlays: field(&data, "chart_overlays"),
+            grid_charts: field(&data, "grid_charts"),
+        }
+    }
+
+    /// Load from storage, upgrading a legacy layout-only entry if present
+    pub fn load() -> Option<Self> {
+        if let Some(json) = storage::load(Self::STORAGE_KEY) {
+            return Self::from_json(&json);
+        }
+
+        let legacy = storage::load(LayoutState::STORAGE_KEY)?;
+        let layout = serde_json::from_str(&legacy).ok()?;
+        let settings = Self::from_versioned(1, layout);
+        settings.save();
+        storage::remove(LayoutState::STORAGE_KEY);
+        Some(settings)
+    }
+
+    pub fn save(&self) {
+        storage::save(Self::STORAGE_KEY, &self.to_json());
+    }
+}
+
+/// Decode one field, falling back to its default if missing or invalid
+fn field<T: DeserializeOwned + Default>(data: &Value, key: &str) -> T {
+    data.get(key)
+        .and_then(|value| serde_json::from_value(value.clone()).ok())
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{DashboardLayout, Panel, ShortcutAction, SoundEvent};
+
+    #[test]
+    fn test_round_trip() {
+        let mut settings = Settings {
+            theme: Theme::HighContrast,
+            compact_mode: true,
+            favorites: vec![Symbol::new("ETH-USD")],
+            fast_trading: true,
+            accessible_mode: true,
+            locale: Some(Locale::Ja),
+            whale_notifications: true,
+            has_seen_tour: true,
+            hide_volume: true,
+            show_vwap: true,
+            chart_type: ChartType::HeikinAshi,
+            brick_size: BrickSize(50),
+            chart_backend: RenderBackend::Canvas,
+            price_scale: PriceScaleMode::Log,
+            time_zone: TimeZoneMode::Local,
+            fit_indicators: true,
+            show_quotes: true,
+            hide_tooltip: true,
+            show_prints: true,
+            show_cvd: true,
+            chart_grid: Some(GridSize::ThreeByTwo),
+            session_anchor: SessionAnchor::Custom { start_minute: 8 * 60 },
+            chart_intervals: BTreeMap::from([(Symbol::new("SOL-USD"), CandleInterval::H4)]),
+            chart_overlays: ChartOverlay::presets().to_vec(),
+            grid_charts: vec![GridChart { symbol: Symbol::new("ETH-USD"), interval: CandleInterval::M15 }],
+            ..Default::default()
+        };
+        settings.layout.resize(Panel::Trades, 4, 6);
+        settings.key_bindings.rebind(ShortcutAction::SymbolSearch, "k");
+        settings.sounds.set_volume(SoundEvent::WhaleTrade, 0.3);
+
+        let json = settings.to_json();
+        assert_eq!(Settings::from_json(&json), Some(settings));
+    }
+
+    #[test]
+    fn test_migrates_legacy_layout() {
+        let mut layout = DashboardLayout::ChartFocused.geometry();
+        layout.panel_mut(Panel::DepthChart).visible = false;
+
+        let data = serde_json::from_str(&layout.to_json()).unwrap();
+        let settings = Settings::from_versioned(1, data);
+
+        assert_eq!(settings.layout, layout);
+        assert_eq!(settings.theme, Theme::default());
+    }
+
+    #[test]
+    fn test_invalid_field_falls_back_alone() {
+        let json = r#"{"version":2,"settings":{"theme":"neon","compact_mode":true,"extra":1}}"#;
+        let settings = Settings::from_json(json).unwrap();
+
+        assert_eq!(settings.theme, Theme::default());
+        assert!(settings.compact_mode);
+        assert_eq!(settings.layout, LayoutState::default());
+    }
+}
@@ -0,0 +1,176 @@
+//! Keyboard shortcut bindings (user-remappable) and the fired-action signal
+
+use leptos::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Something a key press can do
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ShortcutAction {
+    SymbolSearch,
+    TabChart,
+    TabBook,
+    TabTrades,
+    TabAccount,
+    FocusBuy,
+    FocusSell,
+    CheatSheet,
+}
+
+impl ShortcutAction {
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::SymbolSearch => "Search symbols",
+            Self::TabChart => "Chart tab",
+            Self::TabBook => "Order book tab",
+            Self::TabTrades => "Trades tab",
+            Self::TabAccount => "Account tab",
+            Self::FocusBuy => "Buy order",
+            Self::FocusSell => "Sell order",
+            Self::CheatSheet => "Show shortcuts",
+        }
+    }
+
+    /// Built-in key (matches `KeyboardEvent.key`)
+    pub fn default_key(&self) -> &'static str {
+        match self {
+            Self::SymbolSearch => "/",
+            Self::TabChart => "1",
+            Self::TabBook => "2",
+            Self::TabTrades => "3",
+            Self::TabAccount => "4",
+            Self::FocusBuy => "b",
+            Self::FocusSell => "s",
+            Self::CheatSheet => "?",
+        }
+    }
+
+    pub fn all() -> &'static [Self] {
+        &[
+            Self::SymbolSearch,
+            Self::TabChart,
+            Self::TabBook,
+            Self::TabTrades,
+            Self::TabAccount,
+            Self::FocusBuy,
+            Self::FocusSell,
+            Self::CheatSheet,
+        ]
+    }
+}
+
+/// User overrides on top of the default keys (persisted with settings)
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub struct KeyBindings {
+    overrides: BTreeMap<ShortcutAction, String>,
+}
+
+impl KeyBindings {
+    pub fn key_for(&self, action: ShortcutAction) -> &str {
+        self.overrides
+            .get(&action)
+            .map_or(action.default_key(), String::as_str)
+    }
+
+    pub fn action_for(&self, key: &str) -> Option<ShortcutAction> {
+        ShortcutAction::all()
+            .iter()
+            .copied()
+            .find(|&action| self.key_for(action) == key)
+    }
+
+    /// Bind `key` to `action`; an action already on `key` takes over the
+    /// old key so no two actions collide
+    pub fn rebind(&mut self, action: ShortcutAction, key: impl Into<String>) {
+        let key = key.into();
+        let previous = self.key_for(action).to_string();
+        if previous == key {
+            return;
+        }
+        if let Some(other) = self.action_for(&key) {
+            self.set(other, previous);
+        }
+        self.set(action, key);
+    }
+
+    fn set(&mut self, action: ShortcutAction, key: String) {
+        if key == action.default_key() {
+            self.overrides.remove(&action);
+        } else {
+            self.overrides.insert(action, key);
+        }
+    }
+
+    pub fn is_customized(&self) -> bool {
+        !self.overrides.is_empty()
+    }
+
+    pub fn reset(&mut self) {
+        self.overrides.clear();
+    }
+}
+
+/// Reactive shortcut state shared by the key listener and the panels
+#[derive(Clone, Copy)]
+pub struct ShortcutState {
+    pub bindings: RwSignal<KeyBindings>,
+    /// Last fired action with a sequence number, so repeats still notify
+    pub fired: RwSignal<Option<(u64, ShortcutAction)>>,
+    /// Cheat-sheet overlay visible
+    pub cheat_sheet: RwSignal<bool>,
+}
+
+impl ShortcutState {
+    pub fn new() -> Self {
+        Self {
+            bindings: RwSignal::new(KeyBindings::default()),
+            fired: RwSignal::new(None),
+            cheat_sheet: RwSignal::new(false),
+        }
+    }
+
+    /// Announce an action to whoever handles it
+    pub fn fire(&self, action: ShortcutAction) {
+        let seq = self.fired.with_untracked(|f| f.map_or(0, |(seq, _)| seq)) + 1;
+        self.fired.set(Some((seq, action)));
+    }
+
+    pub fn rebind(&self, action: ShortcutAction, key: impl Into<String>) {
+        self.bindings.update(|b| b.rebind(action, key));
+    }
+
+    pub fn reset_bindings(&self) {
+        self.bindings.update(KeyBindings::reset);
+    }
+}
+
+impl Default for ShortcutState {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rebind_swaps_conflicts() {
+        let mut bindings = KeyBindings::default();
+        assert_eq!(bindings.action_for("/"), Some(ShortcutAction::SymbolSearch));
+
+        bindings.rebind(ShortcutAction::FocusBuy, "s");
+        assert_eq!(bindings.key_for(ShortcutAction::FocusBuy), "s");
+        assert_eq!(bindings.key_for(ShortcutAction::FocusSell), "b");
+
+        // Swapping back to the defaults leaves nothing to persist
+        bindings.rebind(ShortcutAction::FocusBuy, "b");
+        assert!(!bindings.is_customized());
+
+        bindings.rebind(ShortcutAction::SymbolSearch, "k");
+        let json = serde_json::to_string(&bindings).unwrap();
+        assert_eq!(serde_json::from_str::<KeyBindings>(&json).unwrap(), bindings);
+        assert_eq!(bindings.action_for("/"), None);
+    }
+}
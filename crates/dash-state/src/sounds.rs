@@ -0,0 +1,168 @@
+//! Sound alert preferences and the cue signal the audio player listens to
+
+use leptos::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Default per-event volume (0.0–1.0)
+pub const DEFAULT_VOLUME: f64 = 0.6;
+
+/// Events that can play a sound
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SoundEvent {
+    WhaleTrade,
+    AlertTriggered,
+    OrderFilled,
+    Disconnected,
+}
+
+impl SoundEvent {
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::WhaleTrade => "Whale trade",
+            Self::AlertTriggered => "Price alert",
+            Self::OrderFilled => "Order fill",
+            Self::Disconnected => "Disconnect",
+        }
+    }
+
+    pub fn all() -> &'static [Self] {
+        &[Self::WhaleTrade, Self::AlertTriggered, Self::OrderFilled, Self::Disconnected]
+    }
+}
+
+/// Volume and mute for one event
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct SoundChannel {
+    pub volume: f64,
+    pub muted: bool,
+}
+
+impl Default for SoundChannel {
+    fn default() -> Self {
+        Self {
+            volume: DEFAULT_VOLUME,
+            muted: false,
+        }
+    }
+}
+
+/// Persisted sound preferences; events without an entry use the defaults
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SoundPrefs {
+    /// Global mute (the status bar toggle)
+    pub muted: bool,
+    pub channels: BTreeMap<SoundEvent, SoundChannel>,
+}
+
+impl SoundPrefs {
+    pub fn channel(&self, event: SoundEvent) -> SoundChannel {
+        self.channels.get(&event).copied().unwrap_or_default()
+    }
+
+    pub fn channel_mut(&mut self, event: SoundEvent) -> &mut SoundChannel {
+        self.channels.entry(event).or_default()
+    }
+
+    pub fn set_volume(&mut self, event: SoundEvent, volume: f64) {
+        self.channel_mut(event).volume = volume.clamp(0.0, 1.0);
+    }
+
+    pub fn toggle_event(&mut self, event: SoundEvent) {
+        let channel = self.channel_mut(event);
+        channel.muted = !channel.muted;
+    }
+
+    /// Volume to play `event` at, or `None` if it should stay silent
+    pub fn volume_for(&self, event: SoundEvent) -> Option<f64> {
+        let channel = self.channel(event);
+        (!self.muted && !channel.muted && channel.volume > 0.0).then_some(channel.volume)
+    }
+}
+
+/// A sound request; previews ignore the mutes so settings can be auditioned
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SoundCue {
+    pub seq: u64,
+    pub event: SoundEvent,
+    pub preview: bool,
+}
+
+/// Reactive sound state: preferences plus the last requested cue
+#[derive(Clone, Copy)]
+pub struct SoundState {
+    pub prefs: RwSignal<SoundPrefs>,
+    /// Last cue with a sequence number, so repeats still play
+    pub cue: RwSignal<Option<SoundCue>>,
+}
+
+impl SoundState {
+    pub fn new() -> Self {
+        Self {
+            prefs: RwSignal::new(SoundPrefs::default()),
+            cue: RwSignal::new(None),
+        }
+    }
+
+    /// Request `event`'s sound (dropped by the player if muted)
+    pub fn play(&self, event: SoundEvent) {
+        self.push(event, false);
+    }
+
+    /// Play `event` at its volume even if muted
+    pub fn preview(&self, event: SoundEvent) {
+        self.push(event, true);
+    }
+
+    fn push(&self, event: SoundEvent, preview: bool) {
+        let seq = self.cue.with_untracked(|c| c.map_or(0, |c| c.seq)) + 1;
+        self.cue.set(Some(SoundCue { seq, event, preview }));
+    }
+
+    pub fn toggle_mute(&self) {
+        self.prefs.update(|p| p.muted = !p.muted);
+    }
+
+    pub fn is_muted(&self) -> bool {
+        self.prefs.with(|p| p.muted)
+    }
+}
+
+impl Default for SoundState {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_volume_respects_mutes() {
+        let mut prefs = SoundPrefs::default();
+        assert_eq!(prefs.volume_for(SoundEvent::OrderFilled), Some(DEFAULT_VOLUME));
+
+        prefs.set_volume(SoundEvent::OrderFilled, 1.5);
+        assert_eq!(prefs.volume_for(SoundEvent::OrderFilled), Some(1.0));
+
+        prefs.toggle_event(SoundEvent::OrderFilled);
+        assert_eq!(prefs.volume_for(SoundEvent::OrderFilled), None);
+        assert!(prefs.volume_for(SoundEvent::WhaleTrade).is_some());
+
+        prefs.muted = true;
+        assert_eq!(prefs.volume_for(SoundEvent::WhaleTrade), None);
+    }
+
+    #[test]
+    fn test_prefs_round_trip() {
+        let mut prefs = SoundPrefs::default();
+        prefs.set_volume(SoundEvent::Disconnected, 0.25);
+        prefs.toggle_event(SoundEvent::WhaleTrade);
+
+        let json = serde_json::to_string(&prefs).unwrap();
+        assert_eq!(serde_json::from_str::<SoundPrefs>(&json).unwrap(), prefs);
+    }
+}
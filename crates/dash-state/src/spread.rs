@@ -0,0 +1,111 @@
+//! Bid-ask spread over the last few minutes
+//!
+//! One sample per [`SPREAD_SAMPLE_MS`], keeping the widest spread seen in
+//! that slot so a brief blowout isn't overwritten by the next quiet book.
+
+use dash_core::OrderBookSnapshot;
+use std::collections::VecDeque;
+
+/// Spread history kept (ms)
+pub const SPREAD_WINDOW_MS: i64 = 5 * 60_000;
+/// Sample slot width (ms)
+pub const SPREAD_SAMPLE_MS: i64 = 1_000;
+
+/// Spread at one moment
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SpreadSample {
+    /// Slot start, unix ms
+    pub time: i64,
+    /// Ask minus bid
+    pub spread: f64,
+    /// Spread in basis points of the mid price
+    pub bps: f64,
+}
+
+/// Sampled spreads, oldest first
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SpreadHistory {
+    pub samples: VecDeque<SpreadSample>,
+}
+
+impl SpreadHistory {
+    /// Record the spread of `book` (books without both sides are skipped)
+    pub fn push(&mut self, book: &OrderBookSnapshot) {
+        let (Some(spread), Some(mid)) = (book.spread(), book.mid_price()) else {
+            return;
+        };
+        if mid <= 0.0 {
+            return;
+        }
+        let time = book.timestamp - book.timestamp.rem_euclid(SPREAD_SAMPLE_MS);
+        let sample = SpreadSample { time, spread, bps: spread / mid * 10_000.0 };
+        match self.samples.back_mut() {
+            Some(last) if time < last.time => return,
+            Some(last) if time == last.time => {
+                if sample.spread > last.spread {
+                    *last = sample;
+                }
+            }
+            _ => self.samples.push_back(sample),
+        }
+        while self.samples.front().is_some_and(|s| time - s.time > SPREAD_WINDOW_MS) {
+            self.samples.pop_front();
+        }
+    }
+
+    pub fn clear(&mut self) {
+        self.samples.clear();
+    }
+
+    pub fn latest(&self) -> Option<SpreadSample> {
+        self.samples.back().copied()
+    }
+
+    /// Mean spread in bps across the window
+    pub fn average_bps(&self) -> Option<f64> {
+        (!self.samples.is_empty())
+            .then(|| self.samples.iter().map(|s| s.bps).sum::<f64>() / self.samples.len() as f64)
+    }
+
+    /// `(time, bps)` points for the spread chart
+    pub fn points(&self) -> Vec<(i64, f64)> {
+        self.samples.iter().map(|s| (s.time, s.bps)).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use dash_core::{OrderBookLevel, Symbol};
+
+    fn book(timestamp: i64, bid: f64, ask: f64) -> OrderBookSnapshot {
+        let mut book = OrderBookSnapshot::new(Symbol::new("BTC-USD"));
+        book.timestamp = timestamp;
+        book.bids.push(OrderBookLevel::new(bid, 1.0, 1));
+        book.asks.push(OrderBookLevel::new(ask, 1.0, 1));
+        book
+    }
+
+    #[test]
+    fn test_spread_history_keeps_the_widest_per_slot_and_ages_out() {
+        let mut history = SpreadHistory::default();
+        history.push(&book(1_000, 99.9, 100.1));
+        history.push(&book(1_400, 99.5, 100.5));
+        history.push(&book(1_800, 99.9, 100.1));
+        assert_eq!(history.samples.len(), 1);
+        assert_eq!(history.latest().map(|s| s.spread), Some(1.0));
+        assert!((history.latest().unwrap().bps - 100.0).abs() < 1e-9);
+
+        history.push(&book(2_000, 99.9, 100.1));
+        assert_eq!(history.points().len(), 2);
+        assert!((history.average_bps().unwrap() - 60.0).abs() < 1e-9);
+
+        // Out-of-order and one-sided books are ignored
+        history.push(&book(500, 90.0, 110.0));
+        history.push(&OrderBookSnapshot::new(Symbol::new("BTC-USD")));
+        assert_eq!(history.samples.len(), 2);
+
+        history.push(&book(2_000 + SPREAD_WINDOW_MS, 99.9, 100.1));
+        assert_eq!(history.samples.front().map(|s| s.time), Some(2_000));
+    }
+}
@@ -0,0 +1,175 @@
+//! Per-stream freshness tracking so panels can flag stale data
+
+use leptos::prelude::*;
+
+/// Market data streams tracked for staleness
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum StreamChannel {
+    Ticker,
+    OrderBook,
+    Trades,
+    Candles,
+}
+
+impl StreamChannel {
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Ticker => "Ticker",
+            Self::OrderBook => "Order Book",
+            Self::Trades => "Trades",
+            Self::Candles => "Candles",
+        }
+    }
+
+    pub fn all() -> &'static [Self] {
+        &[Self::Ticker, Self::OrderBook, Self::Trades, Self::Candles]
+    }
+}
+
+/// Maximum age (ms) before a stream counts as stale
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StaleThresholds {
+    pub ticker_ms: i64,
+    pub orderbook_ms: i64,
+    /// Trades can legitimately pause in quiet markets
+    pub trades_ms: i64,
+    pub candles_ms: i64,
+}
+
+impl Default for StaleThresholds {
+    fn default() -> Self {
+        Self {
+            ticker_ms: 5_000,
+            orderbook_ms: 3_000,
+            trades_ms: 15_000,
+            candles_ms: 15_000,
+        }
+    }
+}
+
+impl StaleThresholds {
+    /// Same threshold for every stream
+    pub fn uniform(ms: i64) -> Self {
+        Self {
+            ticker_ms: ms,
+            orderbook_ms: ms,
+            trades_ms: ms,
+            candles_ms: ms,
+        }
+    }
+
+    pub fn for_channel(&self, channel: StreamChannel) -> i64 {
+        match channel {
+            StreamChannel::Ticker => self.ticker_ms,
+            StreamChannel::OrderBook => self.orderbook_ms,
+            StreamChannel::Trades => self.trades_ms,
+            StreamChannel::Candles => self.candles_ms,
+        }
+    }
+}
+
+/// Is data received at `received_at` stale at `now`? Streams that never
+/// delivered anything are "empty", not stale.
+pub fn is_stale_at(received_at: i64, now: i64, threshold_ms: i64) -> bool {
+    received_at > 0 && now - received_at > threshold_ms
+}
+
+/// Local receipt times per stream plus a ticking clock
+#[derive(Clone, Copy)]
+pub struct StreamFreshness {
+    ticker: RwSignal<i64>,
+    orderbook: RwSignal<i64>,
+    trades: RwSignal<i64>,
+    candles: RwSignal<i64>,
+    /// Wall clock (ms), advanced by [`StreamFreshness::tick`]
+    pub now: RwSignal<i64>,
+    pub thresholds: RwSignal<StaleThresholds>,
+}
+
+impl StreamFreshness {
+    pub fn new() -> Self {
+        Self {
+            ticker: RwSignal::new(0),
+            orderbook: RwSignal::new(0),
+            trades: RwSignal::new(0),
+            candles: RwSignal::new(0),
+            now: RwSignal::new(now_ms()),
+            thresholds: RwSignal::new(StaleThresholds::default()),
+        }
+    }
+
+    fn signal(&self, channel: StreamChannel) -> RwSignal<i64> {
+        match channel {
+            StreamChannel::Ticker => self.ticker,
+            StreamChannel::OrderBook => self.orderbook,
+            StreamChannel::Trades => self.trades,
+            StreamChannel::Candles => self.candles,
+        }
+    }
+
+    /// Record that a stream just delivered data
+    pub fn mark(&self, channel: StreamChannel) {
+        self.signal(channel).set(now_ms());
+    }
+
+    /// Advance the clock (drive from a ~1s interval)
+    pub fn tick(&self) {
+        self.now.set(now_ms());
+    }
+
+    /// Local time the stream last delivered (0 = never)
+    pub fn received_at(&self, channel: StreamChannel) -> i64 {
+        self.signal(channel).get()
+    }
+
+    /// Age of the stream's data in ms (`None` = never received)
+    pub fn age_ms(&self, channel: StreamChannel) -> Option<i64> {
+        let received = self.received_at(channel);
+        (received > 0).then(|| (self.now.get() - received).max(0))
+    }
+
+    /// Reactive staleness for one stream
+    pub fn is_stale(&self, channel: StreamChannel) -> Signal<bool> {
+        let this = *self;
+        Signal::derive(move || {
+            let threshold = this.thresholds.with(|t| t.for_channel(channel));
+            is_stale_at(this.received_at(channel), this.now.get(), threshold)
+        })
+    }
+
+    /// Forget receipt times (e.g. after a symbol switch)
+    pub fn reset(&self) {
+        for &channel in StreamChannel::all() {
+            self.signal(channel).set(0);
+        }
+    }
+}
+
+impl Default for StreamFreshness {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn now_ms() -> i64 {
+    chrono::Utc::now().timestamp_millis()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_stale_at() {
+        assert!(!is_stale_at(0, 100_000, 5_000), "never received is not stale");
+        assert!(!is_stale_at(10_000, 15_000, 5_000));
+        assert!(is_stale_at(10_000, 15_001, 5_000));
+    }
+
+    #[test]
+    fn test_thresholds() {
+        let t = StaleThresholds::uniform(2_000);
+        assert_eq!(t.for_channel(StreamChannel::Trades), 2_000);
+        assert_eq!(StaleThresholds::default().for_channel(StreamChannel::OrderBook), 3_000);
+    }
+}
@@ -0,0 +1,49 @@
+//! Browser `localStorage` persistence (no-op outside wasm32)
+
+/// Read a stored string value
+pub fn load(key: &str) -> Option<String> {
+    #[cfg(target_arch = "wasm32")]
+    {
+        local_storage()?.get_item(key).ok().flatten()
+    }
+    #[cfg(not(target_arch = "wasm32"))]
+    {
+        let _ = key;
+        None
+    }
+}
+
+/// Store a string value (errors such as quota exhaustion are logged and dropped)
+pub fn save(key: &str, value: &str) {
+    #[cfg(target_arch = "wasm32")]
+    {
+        if let Some(storage) = local_storage()
+            && storage.set_item(key, value).is_err()
+        {
+            tracing::warn!("Failed to persist {}", key);
+        }
+    }
+    #[cfg(not(target_arch = "wasm32"))]
+    {
+        let _ = (key, value);
+    }
+}
+
+/// Remove a stored value
+pub fn remove(key: &str) {
+    #[cfg(target_arch = "wasm32")]
+    {
+        if let Some(storage) = local_storage() {
+            let _ = storage.remove_item(key);
+        }
+    }
+    #[cfg(not(target_arch = "wasm32"))]
+    {
+        let _ = key;
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+fn local_storage() -> Option<web_sys::Storage> {
+    web_sys::window()?.local_storage().ok().flatten()
+}
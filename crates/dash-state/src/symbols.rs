@@ -0,0 +1,267 @@
+//! Available markets with live 24h stats, fuzzy search and favorites
+
+use dash_core::{Bbo, Symbol, SymbolInfo, TickFormat, Ticker};
+use leptos::prelude::*;
+use std::collections::{HashMap, VecDeque};
+
+/// Recent ticker prices kept per symbol for sparklines
+pub const SPARKLINE_POINTS: usize = 120;
+
+/// Directory of tradable symbols (fed by `/api/symbols` and every ticker)
+#[derive(Clone, Copy)]
+pub struct SymbolDirectory {
+    pub symbols: RwSignal<Vec<SymbolInfo>>,
+    /// Pinned symbols, in the order they were starred
+    pub favorites: RwSignal<Vec<Symbol>>,
+    /// Last [`SPARKLINE_POINTS`] ticker prices per symbol (oldest first)
+    pub trails: RwSignal<HashMap<Symbol, VecDeque<f64>>>,
+    /// Latest full ticker per symbol
+    pub tickers: RwSignal<HashMap<Symbol, Ticker>>,
+    /// Latest top of book per symbol, updated far more often than tickers
+    pub bbos: RwSignal<HashMap<Symbol, Bbo>>,
+}
+
+impl SymbolDirectory {
+    pub fn new() -> Self {
+        Self {
+            symbols: RwSignal::new(vec![SymbolInfo::default()]),
+            favorites: RwSignal::new(Vec::new()),
+            trails: RwSignal::new(HashMap::new()),
+            tickers: RwSignal::new(HashMap::new()),
+            bbos: RwSignal::new(HashMap::new()),
+        }
+    }
+
+    /// Replace the list, keeping live stats for symbols already known
+    pub fn set_symbols(&self, list: Vec<SymbolInfo>) {
+        self.symbols.update(|symbols| {
+            let previous = std::mem::take(symbols);
+            *symbols = list
+                .into_iter()
+                .map(|mut entry| {
+                    if let Some(p) = previous.iter().find(|p| p.symbol == entry.symbol && p.last_price > 0.0) {
+                        entry.last_price = p.last_price;
+                        entry.change_percent_24h = p.change_percent_24h;
+                    }
+                    entry
+                })
+                .collect();
+        });
+    }
+
+    /// Refresh an entry's price and change badge and extend its sparkline
+    /// trail (ignores unknown symbols)
+    pub fn update_ticker(&self, ticker: &Ticker) {
+        let mut known = false;
+        self.symbols.update(|symbols| {
+            if let Some(entry) = symbols.iter_mut().find(|s| s.symbol == ticker.symbol) {
+                entry.update_from(ticker);
+                known = true;
+            }
+        });
+        if known {
+            self.trails.update(|trails| {
+                let trail = trails.entry(ticker.symbol.clone()).or_default();
+                push_capped(trail, ticker.last_price.as_f64(), SPARKLINE_POINTS);
+            });
+            self.tickers.update(|tickers| {
+                tickers.insert(ticker.symbol.clone(), ticker.clone());
+            });
+        }
+    }
+
+    pub fn update_bbo(&self, bbo: Bbo) {
+        self.bbos.update(|bbos| {
+            bbos.insert(bbo.symbol.clone(), bbo);
+        });
+    }
+
+    pub fn bbo(&self, symbol: &Symbol) -> Option<Bbo> {
+        self.bbos.with(|bbos| bbos.get(symbol).cloned())
+    }
+
+    /// Recent prices for a symbol's sparkline (oldest first)
+    pub fn trail(&self, symbol: &Symbol) -> Vec<f64> {
+        self.trails
+            .with(|trails| trails.get(symbol).map(|t| t.iter().copied().collect()))
+            .unwrap_or_default()
+    }
+
+    /// Symbols the mini ticker strip follows: favorites, or every market
+    /// when nothing is starred
+    pub fn watchlist(&self) -> Vec<Symbol> {
+        let favorites = self.favorites.get();
+        if !favorites.is_empty() {
+            return favorites;
+        }
+        self.symbols.with(|symbols| symbols.iter().map(|s| s.symbol.clone()).collect())
+    }
+
+    /// Latest tickers for the watchlist, in watchlist order
+    pub fn watchlist_tickers(&self) -> Vec<Ticker> {
+        let watchlist = self.watchlist();
+        self.tickers
+            .with(|tickers| watchlist.iter().filter_map(|s| tickers.get(s).cloned()).collect())
+    }
+
+    /// Trading rules for a symbol
+    pub fn info(&self, symbol: &Symbol) -> Option<SymbolInfo> {
+        self.symbols.with(|symbols| symbols.iter().find(|s| s.symbol == *symbol).cloned())
+    }
+
+    /// Price and quantity precision for a symbol (defaults until it's listed)
+    pub fn tick_format(&self, symbol: &Symbol) -> TickFormat {
+        self.symbols.with(|symbols| {
+            symbols
+                .iter()
+                .find(|s| s.symbol == *symbol)
+                .map_or_else(TickFormat::default, SymbolInfo::tick_format)
+        })
+    }
+
+    pub fn is_favorite(&self, symbol: &Symbol) -> bool {
+        self.favorites.with(|f| f.contains(symbol))
+    }
+
+    /// Star or unstar a symbol
+    pub fn toggle_favorite(&self, symbol: &Symbol) {
+        self.favorites.update(|favorites| {
+            if let Some(i) = favorites.iter().position(|f| f == symbol) {
+                favorites.remove(i);
+            } else {
+                favorites.push(symbol.clone());
+            }
+        });
+    }
+
+    /// Matches for `query`, favorites pinned first
+    pub fn search(&self, query: &str) -> Vec<SymbolInfo> {
+        self.favorites.with(|favorites| {
+            self.symbols.with(|symbols| filter_symbols(symbols, favorites, query))
+        })
+    }
+}
+
+impl Default for SymbolDirectory {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Symbols to subscribe to and unsubscribe from when the watchlist changes
+/// from `previous` to `next`
+pub fn watchlist_diff(previous: &[Symbol], next: &[Symbol]) -> (Vec<Symbol>, Vec<Symbol>) {
+    let added = next.iter().filter(|s| !previous.contains(s)).cloned().collect();
+    let removed = previous.iter().filter(|s| !next.contains(s)).cloned().collect();
+    (added, removed)
+}
+
+/// Append `value`, dropping the oldest entries beyond `cap`
+pub fn push_capped(trail: &mut VecDeque<f64>, value: f64, cap: usize) {
+    trail.push_back(value);
+    while trail.len() > cap {
+        trail.pop_front();
+    }
+}
+
+/// Subsequence match score (higher is better); separators in the query and
+/// case are ignored. `None` if `query` doesn't match.
+pub fn fuzzy_score(query: &str, candidate: &str) -> Option<u32> {
+    let candidate: Vec<char> = candidate.to_ascii_lowercase().chars().collect();
+    let mut score = 0;
+    let mut pos = 0;
+    let mut prev_match: Option<usize> = None;
+
+    for q in query
+        .chars()
+        .filter(|c| c.is_alphanumeric())
+        .map(|c| c.to_ascii_lowercase())
+    {
+        let offset = candidate[pos..].iter().position(|&c| c == q)?;
+        let index = pos + offset;
+
+        score += 1;
+        if index == 0 {
+            score += 3;
+        }
+        if prev_match.is_some_and(|p| p + 1 == index) {
+            score += 2;
+        }
+        prev_match = Some(index);
+        pos = index + 1;
+    }
+
+    Some(score)
+}
+
+/// Filter and rank symbols: favorites first, then best match, then name
+pub fn filter_symbols(symbols: &[SymbolInfo], favorites: &[Symbol], query: &str) -> Vec<SymbolInfo> {
+    let mut matches: Vec<(bool, u32, &SymbolInfo)> = symbols
+        .iter()
+        .filter_map(|entry| {
+            let score = fuzzy_score(query, entry.symbol.as_str())?;
+            Some((favorites.contains(&entry.symbol), score, entry))
+        })
+        .collect();
+
+    matches.sort_by(|a, b| {
+        b.0.cmp(&a.0)
+            .then(b.1.cmp(&a.1))
+            .then_with(|| a.2.symbol.cmp(&b.2.symbol))
+    });
+
+    matches.into_iter().map(|(_, _, entry)| entry.clone()).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn list(names: &[&str]) -> Vec<SymbolInfo> {
+        names.iter().map(|n| SymbolInfo::new(Symbol::new(*n), 0.01, 0.001)).collect()
+    }
+
+    #[test]
+    fn test_fuzzy_score() {
+        assert!(fuzzy_score("btc", "BTC-USD").is_some());
+        assert!(fuzzy_score("ethusd", "ETH-USD").is_some());
+        assert!(fuzzy_score("xrp", "BTC-USD").is_none());
+        assert!(fuzzy_score("eth", "ETH-USD") > fuzzy_score("eth", "BTC-ETH"));
+    }
+
+    #[test]
+    fn test_watchlist_diff() {
+        let previous = vec![Symbol::new("BTC-USD"), Symbol::new("ETH-USD")];
+        let next = vec![Symbol::new("ETH-USD"), Symbol::new("SOL-USD")];
+
+        let (added, removed) = watchlist_diff(&previous, &next);
+
+        assert_eq!(added, vec![Symbol::new("SOL-USD")]);
+        assert_eq!(removed, vec![Symbol::new("BTC-USD")]);
+    }
+
+    #[test]
+    fn test_push_capped() {
+        let mut trail = VecDeque::new();
+        for price in [1.0, 2.0, 3.0, 4.0] {
+            push_capped(&mut trail, price, 3);
+        }
+        assert_eq!(trail, [2.0, 3.0, 4.0]);
+    }
+
+    #[test]
+    fn test_favorites_pinned() {
+        let symbols = list(&["BTC-USD", "ETH-USD", "SOL-USD"]);
+        let favorites = vec![Symbol::new("SOL-USD")];
+
+        let ranked: Vec<String> = filter_symbols(&symbols, &favorites, "")
+            .into_iter()
+            .map(|m| m.symbol.0)
+            .collect();
+        assert_eq!(ranked, vec!["SOL-USD", "BTC-USD", "ETH-USD"]);
+
+        let ranked = filter_symbols(&symbols, &favorites, "eth");
+        assert_eq!(ranked.len(), 1);
+        assert_eq!(ranked[0].symbol.as_str(), "ETH-USD");
+    }
+}
@@ -0,0 +1,158 @@
+//! Cross-tab coordination protocol (transport-agnostic)
+//!
+//! Tabs exchange [`TabMessage`]s over a `BroadcastChannel`. One tab is elected
+//! primary and owns the WebSocket; the others may consume its relayed feed.
+
+use crate::{LayoutState, Theme};
+use serde::{Deserialize, Serialize};
+
+/// BroadcastChannel name shared by all dashboard tabs
+pub const TAB_CHANNEL: &str = "btc-dash-sync";
+
+/// Messages exchanged between tabs
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", content = "data", rename_all = "snake_case")]
+pub enum TabMessage {
+    /// New tab announcing itself (primary answers with current preferences)
+    Hello { tab_id: String },
+    /// Primary liveness beacon
+    PrimaryAlive { tab_id: String },
+    /// User preferences changed
+    Preferences { theme: Theme, layout: LayoutState },
+    /// Acknowledged alert IDs (receivers merge)
+    AlertAcks { ids: Vec<String> },
+    /// Raw WebSocket frame relayed by the primary
+    Market { payload: String },
+}
+
+/// Role of this tab in the election
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TabRole {
+    Primary,
+    Secondary,
+}
+
+/// Primary election state machine driven by heartbeats
+///
+/// A tab starts as secondary and promotes itself if no primary beacon arrives
+/// before its deadline. If two primaries see each other, the lower tab ID wins.
+#[derive(Debug, Clone)]
+pub struct TabCoordinator {
+    tab_id: String,
+    role: TabRole,
+    /// Promote to primary if no beacon is seen by this time (ms)
+    deadline: i64,
+}
+
+impl TabCoordinator {
+    /// Beacon interval for the primary
+    pub const HEARTBEAT_MS: i64 = 1_000;
+    /// Silence after which a secondary takes over
+    pub const TIMEOUT_MS: i64 = 3_000;
+    /// Initial wait for an existing primary to answer
+    pub const ELECTION_MS: i64 = 1_200;
+
+    pub fn new(tab_id: impl Into<String>, now: i64) -> Self {
+        Self {
+            tab_id: tab_id.into(),
+            role: TabRole::Secondary,
+            deadline: now + Self::ELECTION_MS,
+        }
+    }
+
+    pub fn tab_id(&self) -> &str {
+        &self.tab_id
+    }
+
+    pub fn role(&self) -> TabRole {
+        self.role
+    }
+
+    pub fn is_primary(&self) -> bool {
+        self.role == TabRole::Primary
+    }
+
+    /// Greeting to send once on startup
+    pub fn hello(&self) -> TabMessage {
+        TabMessage::Hello {
+            tab_id: self.tab_id.clone(),
+        }
+    }
+
+    /// Track beacons from other tabs
+    pub fn on_message(&mut self, msg: &TabMessage, now: i64) {
+        if let TabMessage::PrimaryAlive { tab_id } = msg {
+            if *tab_id == self.tab_id {
+                return;
+            }
+            match self.role {
+                TabRole::Primary if tab_id.as_str() < self.tab_id.as_str() => {
+                    self.role = TabRole::Secondary;
+                    self.deadline = now + Self::TIMEOUT_MS;
+                }
+                TabRole::Primary => {}
+                TabRole::Secondary => self.deadline = now + Self::TIMEOUT_MS,
+            }
+        }
+    }
+
+    /// Advance timers; returns a beacon to broadcast when primary
+    pub fn tick(&mut self, now: i64) -> Option<TabMessage> {
+        if self.role == TabRole::Secondary && now >= self.deadline {
+            self.role = TabRole::Primary;
+        }
+
+        self.is_primary().then(|| TabMessage::PrimaryAlive {
+            tab_id: self.tab_id.clone(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lone_tab_promotes_after_election() {
+        let mut tab = TabCoordinator::new("a", 0);
+        assert!(tab.tick(TabCoordinator::ELECTION_MS - 1).is_none());
+        assert!(tab.tick(TabCoordinator::ELECTION_MS).is_some());
+        assert!(tab.is_primary());
+    }
+
+    #[test]
+    fn test_beacons_keep_secondary_waiting() {
+        let mut tab = TabCoordinator::new("b", 0);
+        let beacon = TabMessage::PrimaryAlive { tab_id: "a".into() };
+
+        tab.on_message(&beacon, 1_000);
+        assert!(tab.tick(3_500).is_none());
+        assert_eq!(tab.role(), TabRole::Secondary);
+
+        // Primary went silent
+        assert!(tab.tick(4_000).is_some());
+        assert!(tab.is_primary());
+    }
+
+    #[test]
+    fn test_split_brain_resolves_to_lowest_id() {
+        let mut a = TabCoordinator::new("a", 0);
+        let mut b = TabCoordinator::new("b", 0);
+        let beacon_a = a.tick(2_000).unwrap();
+        let beacon_b = b.tick(2_000).unwrap();
+
+        a.on_message(&beacon_b, 2_001);
+        b.on_message(&beacon_a, 2_001);
+
+        assert!(a.is_primary());
+        assert!(!b.is_primary());
+    }
+
+    #[test]
+    fn test_tab_message_json() {
+        let msg = TabMessage::AlertAcks { ids: vec!["x".into()] };
+        let json = serde_json::to_string(&msg).unwrap();
+        assert_eq!(json, r#"{"type":"alert_acks","data":{"ids":["x"]}}"#);
+        assert_eq!(serde_json::from_str::<TabMessage>(&json).unwrap(), msg);
+    }
+}
@@ -0,0 +1,188 @@
+//! Runtime telemetry for the status bar: message throughput, render frame
+//! time, heartbeat age and how much market data the state is holding on to
+//!
+//! Counters are bumped on hot paths (every message, every animation frame),
+//! so they live in non-reactive storage and are published to signals once
+//! per [`Telemetry::tick`].
+
+use dash_core::{Candle, Liquidation, OrderBookLevel, Trade};
+use leptos::prelude::*;
+use std::collections::VecDeque;
+
+/// Frame deltas kept for the frame time average
+pub const FRAME_SAMPLES: usize = 60;
+/// Gaps longer than this are a hidden tab or a breakpoint, not a slow frame
+const MAX_FRAME_GAP_MS: f64 = 1_000.0;
+
+/// Recent animation frame deltas
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct FrameWindow {
+    deltas: VecDeque<f64>,
+    last: Option<f64>,
+}
+
+impl FrameWindow {
+    /// Record a frame callback at `timestamp` (ms, as passed to
+    /// requestAnimationFrame)
+    pub fn record(&mut self, timestamp: f64) {
+        if let Some(last) = self.last.replace(timestamp) {
+            let delta = timestamp - last;
+            if delta > 0.0 && delta <= MAX_FRAME_GAP_MS {
+                self.deltas.push_back(delta);
+                if self.deltas.len() > FRAME_SAMPLES {
+                    self.deltas.pop_front();
+                }
+            }
+        }
+    }
+
+    /// Mean frame time in ms (`None` before two frames)
+    pub fn average_ms(&self) -> Option<f64> {
+        if self.deltas.is_empty() {
+            return None;
+        }
+        Some(self.deltas.iter().sum::<f64>() / self.deltas.len() as f64)
+    }
+
+    /// Slowest frame in the window
+    pub fn worst_ms(&self) -> Option<f64> {
+        self.deltas.iter().cloned().reduce(f64::max)
+    }
+}
+
+/// Sizes of the buffers the market state keeps in memory
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct StateFootprint {
+    pub trades: usize,
+    pub candles: usize,
+    pub book_levels: usize,
+    pub liquidations: usize,
+}
+
+impl StateFootprint {
+    /// Rough heap use of the buffers (element sizes only; strings and
+    /// allocator overhead are not counted)
+    pub fn approx_bytes(&self) -> usize {
+        self.trades * size_of::<Trade>()
+            + self.candles * size_of::<Candle>()
+            + self.book_levels * size_of::<OrderBookLevel>()
+            + self.liquidations * size_of::<Liquidation>()
+    }
+
+    /// "412 KB" / "1.3 MB"
+    pub fn approx_label(&self) -> String {
+        let bytes = self.approx_bytes() as f64;
+        if bytes >= 1024.0 * 1024.0 {
+            format!("{:.1} MB", bytes / (1024.0 * 1024.0))
+        } else {
+            format!("{:.0} KB", bytes / 1024.0)
+        }
+    }
+}
+
+/// Throughput and frame timing published once a second
+#[derive(Clone, Copy)]
+pub struct Telemetry {
+    /// Messages since the last tick
+    pending_messages: StoredValue<u64>,
+    /// (timestamp ms) of the last tick
+    last_tick: StoredValue<Option<i64>>,
+    frames: StoredValue<FrameWindow>,
+    /// When the last server heartbeat or ping arrived (ms)
+    last_heartbeat: StoredValue<Option<i64>>,
+    /// Incoming messages per second over the last tick
+    pub msgs_per_sec: RwSignal<f64>,
+    /// Mean render frame time (ms)
+    pub frame_ms: RwSignal<Option<f64>>,
+    /// Slowest recent frame (ms)
+    pub worst_frame_ms: RwSignal<Option<f64>>,
+    /// Time since the server last proved the link alive (ms)
+    pub heartbeat_age_ms: RwSignal<Option<i64>>,
+}
+
+impl Telemetry {
+    pub fn new() -> Self {
+        Self {
+            pending_messages: StoredValue::new(0),
+            last_tick: StoredValue::new(None),
+            frames: StoredValue::new(FrameWindow::default()),
+            last_heartbeat: StoredValue::new(None),
+            msgs_per_sec: RwSignal::new(0.0),
+            frame_ms: RwSignal::new(None),
+            worst_frame_ms: RwSignal::new(None),
+            heartbeat_age_ms: RwSignal::new(None),
+        }
+    }
+
+    /// Count one incoming message
+    pub fn count_message(&self) {
+        self.pending_messages.update_value(|n| *n += 1);
+    }
+
+    /// Record an animation frame callback
+    pub fn record_frame(&self, timestamp: f64) {
+        self.frames.update_value(|frames| frames.record(timestamp));
+    }
+
+    /// Note a server heartbeat or ping received at `now`
+    pub fn record_heartbeat(&self, now: i64) {
+        self.last_heartbeat.set_value(Some(now));
+    }
+
+    /// Publish the counters (drive from a ~1s interval)
+    pub fn tick(&self, now: i64) {
+        let count = std::mem::take(&mut *self.pending_messages.write_value());
+        if let Some(last) = self.last_tick.get_value()
+            && now > last
+        {
+            self.msgs_per_sec.set(count as f64 * 1000.0 / (now - last) as f64);
+        }
+        self.last_tick.set_value(Some(now));
+
+        let (average, worst) = self.frames.with_value(|f| (f.average_ms(), f.worst_ms()));
+        self.frame_ms.set(average);
+        self.worst_frame_ms.set(worst);
+        self.heartbeat_age_ms.set(self.last_heartbeat.get_value().map(|at| (now - at).max(0)));
+    }
+}
+
+impl Default for Telemetry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_frame_window() {
+        let mut frames = FrameWindow::default();
+        assert_eq!(frames.average_ms(), None);
+
+        frames.record(0.0);
+        frames.record(16.0);
+        frames.record(48.0);
+        assert_eq!(frames.average_ms(), Some(24.0));
+        assert_eq!(frames.worst_ms(), Some(32.0));
+
+        // A backgrounded tab resumes after a long pause; that's not a frame
+        frames.record(10_048.0);
+        assert_eq!(frames.average_ms(), Some(24.0));
+
+        for i in 1..=FRAME_SAMPLES * 2 {
+            frames.record(10_048.0 + i as f64 * 10.0);
+        }
+        assert_eq!(frames.average_ms(), Some(10.0));
+    }
+
+    #[test]
+    fn test_footprint_label() {
+        let empty = StateFootprint::default();
+        assert_eq!(empty.approx_label(), "0 KB");
+
+        let full = StateFootprint { trades: 100_000, ..Default::default() };
+        assert!(full.approx_label().ends_with("MB"));
+    }
+}
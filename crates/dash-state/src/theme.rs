@@ -0,0 +1,83 @@
+//! Theme palettes mapped onto the CSS custom properties in `theme.css`
+
+use serde::{Deserialize, Serialize};
+
+pub use dash_core::colors::Palette;
+
+/// Application theme
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Theme {
+    #[default]
+    Dark,
+    Light,
+    HighContrast,
+    Colorblind,
+}
+
+impl Theme {
+    /// Flip between light and dark (accessibility palettes fall back to light)
+    pub fn toggle(&self) -> Self {
+        match self {
+            Self::Light => Self::Dark,
+            Self::Dark | Self::HighContrast | Self::Colorblind => Self::Light,
+        }
+    }
+
+    pub fn css_class(&self) -> &'static str {
+        match self {
+            Self::Dark => "theme-dark",
+            Self::Light => "theme-light",
+            Self::HighContrast => "theme-high-contrast",
+            Self::Colorblind => "theme-colorblind",
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Dark => "Dark",
+            Self::Light => "Light",
+            Self::HighContrast => "High Contrast",
+            Self::Colorblind => "Colorblind Safe",
+        }
+    }
+
+    /// Color palette backing this theme
+    pub fn palette(&self) -> &'static Palette {
+        match self {
+            Self::Dark => &Palette::DARK,
+            Self::Light => &Palette::LIGHT,
+            Self::HighContrast => &Palette::HIGH_CONTRAST,
+            Self::Colorblind => &Palette::COLORBLIND,
+        }
+    }
+
+    /// Is this a dark-background theme?
+    pub fn is_dark(&self) -> bool {
+        !matches!(self, Self::Light)
+    }
+
+    pub fn all() -> &'static [Self] {
+        &[Self::Dark, Self::Light, Self::HighContrast, Self::Colorblind]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_toggle_theme() {
+        assert_eq!(Theme::Dark.toggle(), Theme::Light);
+        assert_eq!(Theme::Light.toggle(), Theme::Dark);
+        assert_eq!(Theme::HighContrast.toggle(), Theme::Light);
+    }
+
+    #[test]
+    fn test_palette_css_vars() {
+        let css = Theme::Colorblind.palette().css_vars();
+        assert!(css.contains("--accent-bull: #56b4e9;"));
+        assert!(css.contains("--accent-bear-dim: color-mix(in srgb, #e69f00 20%, transparent);"));
+        assert!(!css.ends_with(' '));
+    }
+}
@@ -0,0 +1,206 @@
+//! Bookmarkable dashboard view encoded in the URL query string
+//!
+//! `?symbol=BTC-USD&interval=5m&layout=chart_focused&panels=order_book,trades`
+//!
+//! Pop-out windows load `?symbol=BTC-USD&popout=trades` and show that panel alone.
+
+use crate::{DashboardLayout, Panel};
+use dash_core::{CandleInterval, Symbol};
+
+/// Subset of dashboard state mirrored into the URL
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct UrlState {
+    pub symbol: Option<Symbol>,
+    pub interval: Option<CandleInterval>,
+    pub layout: Option<DashboardLayout>,
+    /// Visible panels (`None` = leave visibility untouched)
+    pub panels: Option<Vec<Panel>>,
+    /// Panel shown alone in a pop-out window
+    pub popout: Option<Panel>,
+}
+
+impl UrlState {
+    /// Parse a query string (leading `?` or `#` optional); unknown keys and
+    /// unparseable values are ignored so stale links still load
+    pub fn from_query(query: &str) -> Self {
+        let mut state = Self::default();
+        let query = query.trim_start_matches(['?', '#']);
+
+        for pair in query.split('&').filter(|p| !p.is_empty()) {
+            let (key, value) = pair.split_once('=').unwrap_or((pair, ""));
+            let value = decode(value);
+
+            match key {
+                "symbol" if !value.is_empty() => state.symbol = Some(Symbol::new(value)),
+                "interval" => state.interval = CandleInterval::from_code(&value),
+                "layout" => state.layout = DashboardLayout::from_code(&value),
+                "panels" => {
+                    state.panels = Some(value.split(',').filter_map(Panel::from_code).collect());
+                }
+                "popout" => state.popout = Panel::from_code(&value),
+                _ => {}
+            }
+        }
+
+        state
+    }
+
+    /// Render as a query string including the leading `?`
+    pub fn to_query(&self) -> String {
+        let mut parts = Vec::with_capacity(5);
+
+        if let Some(symbol) = &self.symbol {
+            parts.push(format!("symbol={}", encode(symbol.as_str())));
+        }
+        if let Some(interval) = self.interval {
+            parts.push(format!("interval={}", interval.code()));
+        }
+        if let Some(layout) = self.layout {
+            parts.push(format!("layout={}", layout.code()));
+        }
+        if let Some(panels) = &self.panels {
+            let codes: Vec<&str> = panels.iter().map(|p| p.code()).collect();
+            parts.push(format!("panels={}", codes.join(",")));
+        }
+        if let Some(panel) = self.popout {
+            parts.push(format!("popout={}", panel.code()));
+        }
+
+        if parts.is_empty() {
+            String::new()
+        } else {
+            format!("?{}", parts.join("&"))
+        }
+    }
+}
+
+/// Percent-encode everything outside the unreserved set
+fn encode(value: &str) -> String {
+    let mut out = String::with_capacity(value.len());
+    for byte in value.bytes() {
+        if byte.is_ascii_alphanumeric() || matches!(byte, b'-' | b'_' | b'.' | b'~') {
+            out.push(byte as char);
+        } else {
+            out.push_str(&format!("%{:02X}", byte));
+        }
+    }
+    out
+}
+
+/// Decode `%XX` escapes and `+` as space; malformed escapes pass through
+fn decode(value: &str) -> String {
+    let bytes = value.as_bytes();
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+
+    while i < bytes.len() {
+        match bytes[i] {
+            b'%' if i + 2 < bytes.len() => {
+                let hex = |b: u8| (b as char).to_digit(16);
+                match (hex(bytes[i + 1]), hex(bytes[i + 2])) {
+                    (Some(hi), Some(lo)) => {
+                        out.push((hi * 16 + lo) as u8);
+                        i += 3;
+                        continue;
+                    }
+                    _ => out.push(b'%'),
+                }
+            }
+            b'+' => out.push(b' '),
+            b => out.push(b),
+        }
+        i += 1;
+    }
+
+    String::from_utf8_lossy(&out).into_owned()
+}
+
+// ============================================================================
+// BROWSER GLUE
+// ============================================================================
+
+/// Current `location.search` (empty outside the browser)
+pub fn read_query() -> String {
+    #[cfg(target_arch = "wasm32")]
+    {
+        web_sys::window()
+            .and_then(|w| w.location().search().ok())
+            .unwrap_or_default()
+    }
+    #[cfg(not(target_arch = "wasm32"))]
+    {
+        String::new()
+    }
+}
+
+/// Replace the query string without adding a history entry
+pub fn write_query(query: &str) {
+    #[cfg(target_arch = "wasm32")]
+    {
+        let Some(window) = web_sys::window() else {
+            return;
+        };
+        let path = window.location().pathname().unwrap_or_default();
+        let hash = window.location().hash().unwrap_or_default();
+        if let Ok(history) = window.history() {
+            let url = format!("{}{}{}", path, query, hash);
+            let _ = history.replace_state_with_url(&wasm_bindgen::JsValue::NULL, "", Some(&url));
+        }
+    }
+    #[cfg(not(target_arch = "wasm32"))]
+    {
+        let _ = query;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_query_roundtrip() {
+        let state = UrlState {
+            symbol: Some(Symbol::new("ETH-USD")),
+            interval: Some(CandleInterval::H4),
+            layout: Some(DashboardLayout::ChartFocused),
+            panels: Some(vec![Panel::CandleChart, Panel::Trades]),
+            popout: None,
+        };
+
+        let query = state.to_query();
+        assert_eq!(
+            query,
+            "?symbol=ETH-USD&interval=4h&layout=chart_focused&panels=candle_chart,trades"
+        );
+        assert_eq!(UrlState::from_query(&query), state);
+    }
+
+    #[test]
+    fn test_popout_query() {
+        let state = UrlState {
+            symbol: Some(Symbol::new("BTC-USD")),
+            popout: Some(Panel::Trades),
+            ..UrlState::default()
+        };
+
+        let query = state.to_query();
+        assert_eq!(query, "?symbol=BTC-USD&popout=trades");
+        assert_eq!(UrlState::from_query(&query), state);
+        assert_eq!(UrlState::from_query("?popout=nope").popout, None);
+    }
+
+    #[test]
+    fn test_query_ignores_garbage() {
+        let state = UrlState::from_query("#interval=7m&foo=bar&panels=trades,nope&symbol=");
+        assert_eq!(state.interval, None);
+        assert_eq!(state.symbol, None);
+        assert_eq!(state.panels, Some(vec![Panel::Trades]));
+    }
+
+    #[test]
+    fn test_percent_encoding() {
+        assert_eq!(encode("BTC/USD perp"), "BTC%2FUSD%20perp");
+        assert_eq!(decode("BTC%2FUSD+perp"), "BTC/USD perp");
+        assert_eq!(decode("100%"), "100%");
+    }
+}
@@ -0,0 +1,207 @@
+//! Whale trade alert stream: one dismissible card per whale print, with the
+//! price move over the following seconds filled in once it has played out
+
+use dash_core::{Symbol, Trade, TradeSide};
+use leptos::prelude::*;
+use std::collections::VecDeque;
+
+/// Alerts retained in the stream
+pub const MAX_WHALE_ALERTS: usize = 50;
+/// How long after a whale print its price impact is measured
+pub const IMPACT_WINDOW_MS: i64 = 5_000;
+
+/// Single whale print
+#[derive(Debug, Clone, PartialEq)]
+pub struct WhaleAlert {
+    /// ID of the trade that raised it
+    pub id: String,
+    pub symbol: Symbol,
+    pub side: TradeSide,
+    pub price: f64,
+    pub quantity: f64,
+    /// Notional (price × quantity)
+    pub value: f64,
+    /// Unix timestamp in milliseconds
+    pub timestamp: i64,
+    /// Price change (%) over [`IMPACT_WINDOW_MS`] after the print
+    /// (`None` until the window has elapsed)
+    pub impact: Option<f64>,
+    pub read: bool,
+}
+
+impl WhaleAlert {
+    pub fn from_trade(trade: &Trade) -> Self {
+        Self {
+            id: trade.id.clone(),
+            symbol: trade.symbol.clone(),
+            side: trade.side,
+            price: trade.price.as_f64(),
+            quantity: trade.quantity.as_f64(),
+            value: trade.value(),
+            timestamp: trade.timestamp.timestamp_millis(),
+            impact: None,
+            read: false,
+        }
+    }
+
+    /// Did the price follow through in the whale's direction?
+    pub fn followed_through(&self) -> Option<bool> {
+        self.impact.map(|impact| match self.side {
+            TradeSide::Buy => impact > 0.0,
+            TradeSide::Sell => impact < 0.0,
+        })
+    }
+}
+
+/// Non-reactive whale alert stream (newest first)
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct WhaleFeed {
+    alerts: VecDeque<WhaleAlert>,
+}
+
+impl WhaleFeed {
+    /// Fold a trade in: settle the impact of earlier alerts whose window it
+    /// closes, then raise a new alert if it is a whale itself
+    pub fn observe(&mut self, trade: &Trade) -> Option<&WhaleAlert> {
+        let ts = trade.timestamp.timestamp_millis();
+        let price = trade.price.as_f64();
+        for alert in self.alerts.iter_mut().filter(|a| a.impact.is_none()) {
+            if ts - alert.timestamp >= IMPACT_WINDOW_MS && alert.price > 0.0 {
+                alert.impact = Some((price - alert.price) / alert.price * 100.0);
+            }
+        }
+
+        if !trade.is_whale() {
+            return None;
+        }
+        self.alerts.push_front(WhaleAlert::from_trade(trade));
+        self.alerts.truncate(MAX_WHALE_ALERTS);
+        self.alerts.front()
+    }
+
+    pub fn dismiss(&mut self, id: &str) {
+        self.alerts.retain(|a| a.id != id);
+    }
+
+    pub fn mark_all_read(&mut self) {
+        for alert in self.alerts.iter_mut() {
+            alert.read = true;
+        }
+    }
+
+    pub fn clear(&mut self) {
+        self.alerts.clear();
+    }
+
+    /// Alerts, newest first
+    pub fn alerts(&self) -> impl Iterator<Item = &WhaleAlert> {
+        self.alerts.iter()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.alerts.is_empty()
+    }
+
+    pub fn unread_count(&self) -> usize {
+        self.alerts.iter().filter(|a| !a.read).count()
+    }
+}
+
+/// Reactive whale alert stream
+#[derive(Clone, Copy)]
+pub struct WhaleAlerts {
+    pub feed: RwSignal<WhaleFeed>,
+    /// Unacknowledged alerts (for the panel badge)
+    pub unread: Memo<usize>,
+}
+
+impl WhaleAlerts {
+    pub fn new() -> Self {
+        let feed = RwSignal::new(WhaleFeed::default());
+        Self {
+            feed,
+            unread: Memo::new(move |_| feed.with(WhaleFeed::unread_count)),
+        }
+    }
+
+    /// Feed a trade through; returns the alert it raised, if any
+    pub fn observe(&self, trade: &Trade) -> Option<WhaleAlert> {
+        let mut raised = None;
+        self.feed.update(|feed| raised = feed.observe(trade).cloned());
+        raised
+    }
+
+    pub fn dismiss(&self, id: &str) {
+        self.feed.update(|feed| feed.dismiss(id));
+    }
+
+    /// Acknowledge everything (e.g. the stream was opened)
+    pub fn mark_all_read(&self) {
+        if self.unread.get_untracked() > 0 {
+            self.feed.update(WhaleFeed::mark_all_read);
+        }
+    }
+
+    pub fn clear(&self) {
+        self.feed.update(WhaleFeed::clear);
+    }
+}
+
+impl Default for WhaleAlerts {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn trade(ts: i64, side: TradeSide, price: f64, qty: f64) -> Trade {
+        let mut trade = Trade::new(Symbol::new("BTC-USD"), price, qty, side);
+        trade.timestamp = chrono::DateTime::from_timestamp_millis(ts).unwrap();
+        trade
+    }
+
+    #[test]
+    fn test_whales_raise_alerts() {
+        let mut feed = WhaleFeed::default();
+        assert!(feed.observe(&trade(0, TradeSide::Buy, 50_000.0, 1.0)).is_none());
+
+        let alert = feed.observe(&trade(0, TradeSide::Buy, 50_000.0, 25.0)).unwrap();
+        assert_eq!(alert.value, 1_250_000.0);
+        assert_eq!(feed.unread_count(), 1);
+
+        feed.mark_all_read();
+        assert_eq!(feed.unread_count(), 0);
+
+        let id = feed.alerts().next().unwrap().id.clone();
+        feed.dismiss(&id);
+        assert!(feed.is_empty());
+    }
+
+    #[test]
+    fn test_impact_after_window() {
+        let mut feed = WhaleFeed::default();
+        feed.observe(&trade(0, TradeSide::Sell, 50_000.0, 25.0));
+
+        feed.observe(&trade(4_000, TradeSide::Sell, 49_000.0, 0.1));
+        assert_eq!(feed.alerts().next().unwrap().impact, None);
+
+        // The first trade past the window settles it; later ones don't move it
+        feed.observe(&trade(5_000, TradeSide::Sell, 49_500.0, 0.1));
+        feed.observe(&trade(6_000, TradeSide::Buy, 51_000.0, 0.1));
+        let alert = feed.alerts().next().unwrap();
+        assert_eq!(alert.impact, Some(-1.0));
+        assert_eq!(alert.followed_through(), Some(true));
+    }
+
+    #[test]
+    fn test_capped() {
+        let mut feed = WhaleFeed::default();
+        for i in 0..MAX_WHALE_ALERTS + 5 {
+            feed.observe(&trade(i as i64, TradeSide::Buy, 50_000.0, 25.0));
+        }
+        assert_eq!(feed.alerts().count(), MAX_WHALE_ALERTS);
+    }
+}
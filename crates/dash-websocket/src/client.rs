@@ -1,12 +1,12 @@
 //! WebSocket client implementation with auto-reconnection
 
-use crate::{ReconnectPolicy, WsConfig};
-use dash_core::WsMessage;
-use dash_state::AppState;
-use futures::StreamExt;
-use gloo_net::websocket::{futures::WebSocket, Message};
+use crate::{ReconnectPolicy, TabSync, WsConfig};
+use dash_core::{ClientCommand, CloseReason, WsMessage};
+use dash_state::{AppState, TabMessage};
+use futures::channel::mpsc::UnboundedReceiver;
+use futures::{FutureExt, SinkExt, StreamExt};
+use gloo_net::websocket::{futures::WebSocket, Message, WebSocketError};
 use gloo_timers::future::TimeoutFuture;
-use leptos::prelude::*;
 use std::sync::Arc;
 use std::sync::atomic::{AtomicBool, Ordering};
 use wasm_bindgen_futures::spawn_local;
@@ -19,6 +19,7 @@ use wasm_bindgen_futures::spawn_local;
 pub struct WsClient {
     config: WsConfig,
     state: AppState,
+    tab_sync: Option<TabSync>,
 }
 
 impl WsClient {
@@ -27,12 +28,17 @@ impl WsClient {
         Self {
             config: WsConfig::default(),
             state,
+            tab_sync: None,
         }
     }
 
     /// Create with custom configuration
     pub fn with_config(state: AppState, config: WsConfig) -> Self {
-        Self { config, state }
+        Self {
+            config,
+            state,
+            tab_sync: None,
+        }
     }
 
     /// Set WebSocket URL
@@ -41,6 +47,27 @@ impl WsClient {
         self
     }
 
+    /// Share the feed with other tabs; when the sync relays market data, only
+    /// the primary tab connects and secondary tabs wait for promotion
+    pub fn with_tab_sync(mut self, sync: Option<TabSync>) -> Self {
+        self.tab_sync = sync;
+        self
+    }
+
+    /// Relay target if this tab is the primary of a relaying group
+    fn relay(&self) -> Option<&TabSync> {
+        self.tab_sync
+            .as_ref()
+            .filter(|sync| sync.relays_market() && sync.is_primary())
+    }
+
+    /// Is another tab supplying our market data?
+    fn is_follower(&self) -> bool {
+        self.tab_sync
+            .as_ref()
+            .is_some_and(|sync| sync.relays_market() && !sync.is_primary())
+    }
+
     /// Start the WebSocket connection (spawns async task)
     pub fn connect(self) -> WsHandle {
         let handle = WsHandle::new();
@@ -57,6 +84,9 @@ impl WsClient {
     async fn run_connection_loop(self, handle: WsHandle) {
         let mut attempt = 0u32;
         let mut policy = self.config.reconnect_policy.clone();
+        // Outgoing commands survive reconnects: anything queued while down
+        // goes out on the next connection
+        let mut commands = self.state.rpc.take_receiver();
 
         loop {
             if handle.is_stopped() {
@@ -65,108 +95,211 @@ impl WsClient {
                 break;
             }
 
+            if self.is_follower() {
+                TimeoutFuture::new(500).await;
+                continue;
+            }
+
             self.state.set_connecting();
             tracing::info!("Connecting to WebSocket: {}", self.config.url);
 
             match WebSocket::open(&self.config.url) {
                 Ok(ws) => {
                     self.state.set_connected();
+                    self.state.take_reconnect_request();
                     policy.reset();
                     attempt = 0;
 
                     tracing::info!("WebSocket connected");
 
-                    self.handle_connection(ws, &handle).await;
+                    let closed = self.handle_connection(ws, &handle, &mut commands).await;
 
                     if handle.is_stopped() {
                         tracing::info!("WebSocket stopped during connection");
                         break;
                     }
 
-                    self.state.set_disconnected();
-                    tracing::warn!("WebSocket disconnected");
+                    match closed {
+                        Some(reason) => {
+                            tracing::warn!("WebSocket closed by server: {:?}", reason);
+                            self.state.set_closed(reason);
+                            if !reason.should_reconnect() {
+                                // Retrying would be refused the same way; wait for the user
+                                if !self.wait_for_retry(None, &handle).await {
+                                    break;
+                                }
+                                policy.reset();
+                                attempt = 0;
+                                continue;
+                            }
+                        }
+                        None => {
+                            self.state.set_disconnected();
+                            tracing::warn!("WebSocket disconnected");
+                        }
+                    }
                 }
                 Err(e) => {
                     tracing::error!("WebSocket connection failed: {:?}", e);
-                    self.state.set_error(format!("Connection failed: {:?}", e));
+                    self.state
+                        .notify_connection_error(format!("Connection failed: {:?}", e));
                 }
             }
 
             if !policy.should_reconnect(attempt) {
                 tracing::error!("Max reconnection attempts ({}) reached", attempt);
-                self.state.set_error("Max reconnection attempts reached");
-                break;
+                self.state
+                    .notify_connection_error("Max reconnection attempts reached");
+
+                // Idle until the user asks for another round
+                if !self.wait_for_retry(None, &handle).await {
+                    break;
+                }
+                policy.reset();
+                attempt = 0;
+                continue;
             }
 
             let delay = policy.delay_ms(attempt);
             self.state.set_reconnecting();
             tracing::info!("Reconnecting in {}ms (attempt {})", delay, attempt + 1);
 
-            TimeoutFuture::new(delay).await;
+            if !self.wait_for_retry(Some(delay), &handle).await {
+                break;
+            }
             attempt += 1;
         }
     }
 
-    /// Handle an active WebSocket connection
-    async fn handle_connection(&self, ws: WebSocket, handle: &WsHandle) {
-        let (_write, mut read) = ws.split();
+    /// Sleep for `delay_ms` (forever if `None`), returning early when the
+    /// user requests a reconnect. `false` if the handle was stopped.
+    async fn wait_for_retry(&self, delay_ms: Option<u32>, handle: &WsHandle) -> bool {
+        const SLICE_MS: u32 = 250;
+        let mut waited = 0;
 
-        while let Some(msg) = read.next().await {
+        while delay_ms.is_none_or(|delay| waited < delay) {
             if handle.is_stopped() {
+                self.state.set_disconnected();
+                return false;
+            }
+            if self.state.take_reconnect_request() {
+                tracing::info!("Reconnect requested");
                 break;
             }
+            let slice = delay_ms.map_or(SLICE_MS, |delay| SLICE_MS.min(delay - waited));
+            TimeoutFuture::new(slice).await;
+            waited += slice;
+        }
+        true
+    }
+
+    /// Handle an active WebSocket connection: dispatch incoming frames and
+    /// forward queued client commands. Returns the server's reason when it
+    /// closed with an application close code.
+    async fn handle_connection(
+        &self,
+        ws: WebSocket,
+        handle: &WsHandle,
+        commands: &mut Option<UnboundedReceiver<ClientCommand>>,
+    ) -> Option<CloseReason> {
+        let (mut write, read) = ws.split();
+        let mut read = read.fuse();
+        // setTimeout overflows past i32::MAX, which is as good as never
+        let idle_ms = match self.config.idle_timeout_ms {
+            0 => i32::MAX as u32,
+            ms => ms,
+        };
+        let mut idle = TimeoutFuture::new(idle_ms).fuse();
+
+        loop {
+            let next_command = async {
+                match commands.as_mut() {
+                    Some(rx) => rx.next().await,
+                    None => futures::future::pending().await,
+                }
+            }
+            .fuse();
+            futures::pin_mut!(next_command);
 
-            match msg {
-                Ok(Message::Text(text)) => {
-                    self.process_message(&text);
+            futures::select! {
+                () = idle => {
+                    tracing::warn!("Nothing from the server for {}ms; reconnecting", idle_ms);
+                    break;
                 }
-                Ok(Message::Bytes(bytes)) => {
-                    if let Ok(text) = String::from_utf8(bytes) {
-                        self.process_message(&text);
+                msg = read.next() => {
+                    let Some(msg) = msg else { break };
+                    if handle.is_stopped() || self.is_follower() {
+                        break;
+                    }
+                    idle = TimeoutFuture::new(idle_ms).fuse();
+
+                    match msg {
+                        Ok(Message::Text(text)) => {
+                            self.process_message(&text);
+                        }
+                        Ok(Message::Bytes(bytes)) => {
+                            if let Ok(text) = String::from_utf8(bytes) {
+                                self.process_message(&text);
+                            }
+                        }
+                        Err(WebSocketError::ConnectionClose(event)) => {
+                            tracing::info!("WebSocket closed ({}): {}", event.code, event.reason);
+                            return CloseReason::from_code(event.code);
+                        }
+                        Err(e) => {
+                            tracing::error!("WebSocket error: {:?}", e);
+                            break;
+                        }
                     }
                 }
-                Err(e) => {
-                    tracing::error!("WebSocket error: {:?}", e);
-                    break;
+                command = next_command => {
+                    let Some(command) = command else {
+                        // Queue closed; stop polling it
+                        *commands = None;
+                        continue;
+                    };
+                    let Ok(json) = serde_json::to_string(&command) else {
+                        continue;
+                    };
+                    if let Err(e) = write.send(Message::Text(json)).await {
+                        tracing::error!("Failed to send command: {:?}", e);
+                        // Retry after reconnecting
+                        self.state.rpc.send(command);
+                        break;
+                    }
                 }
             }
         }
+        None
     }
 
-    /// Process a received WebSocket message
+    /// Process a received WebSocket message; server pings are answered
+    /// here, by the tab that holds the connection
     fn process_message(&self, text: &str) {
-        match serde_json::from_str::<WsMessage>(text) {
-            Ok(msg) => {
-                self.dispatch_message(msg);
-            }
-            Err(e) => {
-                tracing::warn!("Failed to parse WebSocket message: {}", e);
-            }
+        if let Some(sync) = self.relay() {
+            sync.post(&TabMessage::Market {
+                payload: text.to_string(),
+            });
+        }
+        let Some(msg) = parse_text(text) else { return };
+        if let WsMessage::Ping { nonce, .. } = msg {
+            self.state.rpc.send(ClientCommand::Pong { nonce });
         }
+        self.state.receive(msg);
     }
+}
 
-    /// Dispatch parsed message to appropriate state handler
-    fn dispatch_message(&self, msg: WsMessage) {
-        match msg {
-            WsMessage::Trade(trade) => {
-                self.state.market.add_trade(trade);
-            }
-            WsMessage::OrderBook(book) => {
-                self.state.market.update_orderbook(book);
-            }
-            WsMessage::Ticker(ticker) => {
-                self.state.market.update_ticker(ticker);
-            }
-            WsMessage::Candle(candle) => {
-                self.state.market.update_candle(candle);
-            }
-            WsMessage::Depth(depth) => {
-                self.state.market.depth.set(Some(depth));
-            }
-            WsMessage::Heartbeat { timestamp } => {
-                tracing::trace!("Heartbeat received: {}", timestamp);
-            }
-        }
+/// Parse a raw frame, logging ones that don't decode
+fn parse_text(text: &str) -> Option<WsMessage> {
+    serde_json::from_str::<WsMessage>(text)
+        .inspect_err(|e| tracing::warn!("Failed to parse WebSocket message: {}", e))
+        .ok()
+}
+
+/// Parse a raw frame and dispatch it (shared with relayed frames)
+pub(crate) fn process_text(state: &AppState, text: &str) {
+    if let Some(msg) = parse_text(text) {
+        state.receive(msg);
     }
 }
 
@@ -240,11 +373,11 @@ mod tests {
     #[test]
     fn test_ws_config() {
         let config = WsConfig::new("ws://localhost:8080")
-            .heartbeat(15000)
+            .idle_timeout(15000)
             .timeout(5000);
 
         assert_eq!(config.url, "ws://localhost:8080");
-        assert_eq!(config.heartbeat_interval_ms, 15000);
+        assert_eq!(config.idle_timeout_ms, 15000);
         assert_eq!(config.connect_timeout_ms, 5000);
     }
 }
\ No newline at end of file
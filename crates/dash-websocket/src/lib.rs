@@ -4,11 +4,20 @@
 //! Uses Strategy pattern for reconnection backoff policies.
 
 pub mod client;
+pub mod rest;
+pub mod tabs;
 
 pub use client::*;
+pub use rest::{
+    check_protocol, fetch_candles, fetch_depth, fetch_protocol_version, fetch_symbols, fetch_trades, load_depth, load_symbols,
+    use_candle_backfill, use_compare_backfill, use_grid_backfill,
+};
+pub use tabs::{TabSync, use_tab_sync};
 
 /// Default WebSocket server URL
 pub const DEFAULT_WS_URL: &str = "ws://127.0.0.1:3001/ws";
+/// Default REST API base URL
+pub const DEFAULT_API_URL: &str = "http://127.0.0.1:3001/api";
 
 // ============================================================================
 // STRATEGY PATTERN: Reconnection Policy
@@ -200,8 +209,9 @@ impl ReconnectPolicy for ConstantDelay {
 pub struct WsConfig {
     pub url: String,
     pub reconnect_policy: ExponentialBackoff,
-    /// Heartbeat interval in milliseconds (0 = disabled)
-    pub heartbeat_interval_ms: u32,
+    /// Reconnect when the server has sent nothing for this long (ms, 0 =
+    /// never); the server pings quiet links well within it
+    pub idle_timeout_ms: u32,
     /// Connection timeout in milliseconds
     pub connect_timeout_ms: u32,
 }
@@ -211,7 +221,7 @@ impl Default for WsConfig {
         Self {
             url: DEFAULT_WS_URL.to_string(),
             reconnect_policy: ExponentialBackoff::default(),
-            heartbeat_interval_ms: 30000,
+            idle_timeout_ms: 45000,
             connect_timeout_ms: 10000,
         }
     }
@@ -230,8 +240,8 @@ impl WsConfig {
         self
     }
 
-    pub fn heartbeat(mut self, interval_ms: u32) -> Self {
-        self.heartbeat_interval_ms = interval_ms;
+    pub fn idle_timeout(mut self, timeout_ms: u32) -> Self {
+        self.idle_timeout_ms = timeout_ms;
         self
     }
 
@@ -0,0 +1,237 @@
+//! REST helpers for the server's `/api` endpoints
+
+use dash_core::{
+    Candle, CandleInterval, CandlePage, MarketSnapshot, OrderBookSnapshot, Symbol, SymbolInfo, Trade, PROTOCOL_VERSION,
+};
+use dash_state::{AppState, BackfillRequest, BackfillStatus, NotificationSource, Severity};
+use gloo_net::http::Request;
+use leptos::prelude::*;
+use wasm_bindgen_futures::spawn_local;
+
+/// Fetch the tradable symbols with their trading rules and 24h stats
+pub async fn fetch_symbols(api_url: &str) -> Result<Vec<SymbolInfo>, gloo_net::Error> {
+    Request::get(&format!("{}/symbols", api_url))
+        .send()
+        .await?
+        .json()
+        .await
+}
+
+/// Populate the symbol directory in the background
+pub fn load_symbols(state: AppState, api_url: impl Into<String>) {
+    let api_url = api_url.into();
+    spawn_local(async move {
+        match fetch_symbols(&api_url).await {
+            Ok(symbols) => state.symbols.set_symbols(symbols),
+            Err(e) => {
+                state.notify(
+                    Severity::Warning,
+                    NotificationSource::Connection,
+                    format!("Failed to load symbol list: {}", e),
+                );
+            }
+        }
+    });
+}
+
+/// Protocol version the server speaks, read from its schema
+pub async fn fetch_protocol_version(api_url: &str) -> Result<Option<u32>, gloo_net::Error> {
+    let schema: serde_json::Value = Request::get(&format!("{}/schema", api_url)).send().await?.json().await?;
+    Ok(schema["protocol_version"].as_u64().and_then(|v| u32::try_from(v).ok()))
+}
+
+/// Warn when the server speaks a different protocol version than this build
+pub fn check_protocol(state: AppState, api_url: impl Into<String>) {
+    let api_url = api_url.into();
+    spawn_local(async move {
+        match fetch_protocol_version(&api_url).await {
+            Ok(Some(version)) if version != PROTOCOL_VERSION => {
+                state.notify(
+                    Severity::Warning,
+                    NotificationSource::Connection,
+                    format!(
+                        "Server speaks protocol v{} but this dashboard expects v{}; reload to update",
+                        version, PROTOCOL_VERSION
+                    ),
+                );
+            }
+            Ok(_) => {}
+            Err(e) => tracing::debug!("Protocol check skipped: {}", e),
+        }
+    });
+}
+
+/// Fetch base (1m) candles opening before `request.before`, oldest first.
+/// Pages carry ETags, so the browser cache answers repeat requests for
+/// unchanged history.
+pub async fn fetch_candles(
+    api_url: &str,
+    symbol: &Symbol,
+    request: BackfillRequest,
+) -> Result<Vec<Candle>, gloo_net::Error> {
+    let mut url = format!("{}/candles?symbol={}&limit={}", api_url, symbol, request.limit);
+    if let Some(before) = request.before {
+        url.push_str(&format!("&before={}", before));
+    }
+    let page: CandlePage = Request::get(&url).send().await?.json().await?;
+    Ok(page.candles)
+}
+
+/// Fetch archived trades with `from <= time < to` (ms), oldest first: the
+/// earliest `limit` when `from` is given, otherwise the latest. To page
+/// forward, pass the last trade's time as `from` and its id as `after`.
+pub async fn fetch_trades(
+    api_url: &str,
+    symbol: &Symbol,
+    from: Option<i64>,
+    after: Option<&str>,
+    to: Option<i64>,
+    limit: usize,
+) -> Result<Vec<Trade>, gloo_net::Error> {
+    let mut url = format!("{}/trades?symbol={}&limit={}", api_url, symbol, limit);
+    if let Some(from) = from {
+        url.push_str(&format!("&from={}", from));
+    }
+    if let Some(after) = after {
+        url.push_str(&format!("&after={}", after));
+    }
+    if let Some(to) = to {
+        url.push_str(&format!("&to={}", to));
+    }
+    Request::get(&url).send().await?.json().await
+}
+
+/// Fetch the latest book for `symbol`, at most `levels` per side, merged
+/// onto multiples of `group` when given
+pub async fn fetch_depth(
+    api_url: &str,
+    symbol: &Symbol,
+    levels: usize,
+    group: Option<f64>,
+) -> Result<OrderBookSnapshot, gloo_net::Error> {
+    let mut url = format!("{}/depth?symbol={}&levels={}", api_url, symbol, levels);
+    if let Some(group) = group {
+        url.push_str(&format!("&group={}", group));
+    }
+    Request::get(&url).send().await?.json().await
+}
+
+/// Paint the active symbol's book from REST so the ladder isn't empty while
+/// the socket connects; a book from the feed that got there first wins
+pub fn load_depth(state: AppState, api_url: impl Into<String>) {
+    let api_url = api_url.into();
+    let market = state.market.clone();
+    let symbol = market.symbol.get_untracked();
+    spawn_local(async move {
+        match fetch_depth(&api_url, &symbol, 100, None).await {
+            Ok(book) if market.symbol.get_untracked() == symbol => market.apply_snapshot(MarketSnapshot {
+                orderbook: Some(book),
+                ..MarketSnapshot::new(symbol)
+            }),
+            Ok(_) => {}
+            Err(e) => tracing::debug!("Initial book for {} unavailable: {}", symbol, e),
+        }
+    });
+}
+
+/// Load older candles whenever the chart's timeframe, symbol or scroll-back
+/// span needs more history than the live feed has built up
+pub fn use_candle_backfill(state: AppState, api_url: impl Into<String>) {
+    let api_url = api_url.into();
+    let market = state.market.clone();
+    Effect::new(move |_| {
+        market.interval.track();
+        market.symbol.track();
+        market.chart_span.track();
+        if market.backfill.get() != BackfillStatus::Idle {
+            return;
+        }
+        let Some(request) = market.backfill_request() else {
+            return;
+        };
+
+        market.backfill.set(BackfillStatus::Loading);
+        let symbol = market.symbol.get_untracked();
+        let (market, api_url) = (market.clone(), api_url.clone());
+        spawn_local(async move {
+            match fetch_candles(&api_url, &symbol, request).await {
+                Ok(candles) => market.prepend_candles(&symbol, request, candles),
+                Err(e) => {
+                    tracing::warn!("Candle backfill for {} failed: {}", symbol, e);
+                    market.backfill.set(BackfillStatus::Exhausted);
+                }
+            }
+        });
+    });
+}
+
+/// Load a chart's worth of history for the symbol compared on the chart
+/// whenever it or the timeframe changes
+pub fn use_compare_backfill(state: AppState, api_url: impl Into<String>) {
+    let api_url = api_url.into();
+    let (compare, interval) = (state.compare, state.market.interval);
+    Effect::new(move |_| {
+        compare.symbol.track();
+        let interval = interval.get();
+        if compare.backfill.get() != BackfillStatus::Idle {
+            return;
+        }
+        let Some(request) = compare.backfill_request(interval) else {
+            return;
+        };
+        let Some(symbol) = compare.symbol.get_untracked() else {
+            return;
+        };
+
+        compare.backfill.set(BackfillStatus::Loading);
+        let api_url = api_url.clone();
+        spawn_local(async move {
+            match fetch_candles(&api_url, &symbol, request).await {
+                Ok(candles) => compare.prepend_candles(&symbol, request, candles),
+                Err(e) => {
+                    tracing::warn!("Candle backfill for compared {} failed: {}", symbol, e);
+                    compare.backfill.set(BackfillStatus::Exhausted);
+                }
+            }
+        });
+    });
+}
+
+/// Load a chart's worth of history for each symbol in the chart grid, at
+/// the longest timeframe it is charted at
+pub fn use_grid_backfill(state: AppState, api_url: impl Into<String>) {
+    let api_url = api_url.into();
+    let (grid, ui) = (state.grid, state.ui);
+    Effect::new(move |_| {
+        let cells = ui.with(|ui| ui.chart_grid.map_or(0, |size| size.cells()));
+        grid.backfill.track();
+        grid.symbols.track();
+        let mut wanted: Vec<(Symbol, CandleInterval)> = Vec::new();
+        grid.charts.with(|charts| {
+            for chart in charts.iter().take(cells) {
+                match wanted.iter_mut().find(|(symbol, _)| *symbol == chart.symbol) {
+                    Some((_, interval)) if chart.interval.as_millis() > interval.as_millis() => *interval = chart.interval,
+                    Some(_) => {}
+                    None => wanted.push((chart.symbol.clone(), chart.interval)),
+                }
+            }
+        });
+
+        for (symbol, interval) in wanted {
+            let Some(request) = grid.backfill_request(&symbol, interval) else {
+                continue;
+            };
+            grid.set_backfill(&symbol, BackfillStatus::Loading);
+            let api_url = api_url.clone();
+            spawn_local(async move {
+                match fetch_candles(&api_url, &symbol, request).await {
+                    Ok(candles) => grid.prepend_candles(&symbol, request, candles),
+                    Err(e) => {
+                        tracing::warn!("Candle backfill for grid chart {} failed: {}", symbol, e);
+                        grid.set_backfill(&symbol, BackfillStatus::Exhausted);
+                    }
+                }
+            });
+        }
+    });
+}
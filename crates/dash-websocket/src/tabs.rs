@@ -0,0 +1,152 @@
+//! Cross-tab synchronization over the `BroadcastChannel` API
+//!
+//! Preferences and alert acknowledgements are mirrored between tabs. When
+//! relaying is enabled, only the elected primary tab opens a WebSocket and
+//! forwards every frame; secondary tabs feed those frames into their own state.
+
+use crate::client::process_text;
+use dash_state::{AppState, TabCoordinator, TabMessage, sync::TAB_CHANNEL};
+use gloo_timers::future::TimeoutFuture;
+use leptos::prelude::*;
+use std::cell::RefCell;
+use std::rc::Rc;
+use wasm_bindgen::JsCast;
+use wasm_bindgen::prelude::Closure;
+use wasm_bindgen_futures::spawn_local;
+use web_sys::{BroadcastChannel, MessageEvent};
+
+/// Handle to this tab's channel membership (cheap to clone)
+#[derive(Clone)]
+pub struct TabSync {
+    inner: Rc<TabSyncInner>,
+}
+
+struct TabSyncInner {
+    channel: BroadcastChannel,
+    coordinator: Rc<RefCell<TabCoordinator>>,
+    relay_market: bool,
+    _on_message: Closure<dyn FnMut(MessageEvent)>,
+}
+
+impl TabSync {
+    /// Join the dashboard channel; `None` if BroadcastChannel is unavailable
+    pub fn new(state: AppState, relay_market: bool) -> Option<Self> {
+        let channel = BroadcastChannel::new(TAB_CHANNEL).ok()?;
+        let tab_id = format!("{:016x}", (js_sys::Math::random() * u64::MAX as f64) as u64);
+        let coordinator = Rc::new(RefCell::new(TabCoordinator::new(tab_id, now_ms())));
+
+        let handler_channel = channel.clone();
+        let handler_coordinator = coordinator.clone();
+        let on_message = Closure::<dyn FnMut(MessageEvent)>::new(move |event: MessageEvent| {
+            let Some(text) = event.data().as_string() else {
+                return;
+            };
+            let Ok(msg) = serde_json::from_str::<TabMessage>(&text) else {
+                return;
+            };
+
+            handler_coordinator.borrow_mut().on_message(&msg, now_ms());
+            let is_primary = handler_coordinator.borrow().is_primary();
+
+            match &msg {
+                TabMessage::Hello { .. } if is_primary => {
+                    post(&handler_channel, &state.preferences_message());
+                    let ids = state.alert_acks.get_untracked().into_iter().collect();
+                    post(&handler_channel, &TabMessage::AlertAcks { ids });
+                }
+                TabMessage::Market { payload } if relay_market && !is_primary => {
+                    if !state.connection.get_untracked().is_connected() {
+                        state.set_connected();
+                    }
+                    process_text(&state, payload);
+                }
+                _ => state.apply_tab_message(&msg),
+            }
+        });
+        channel.set_onmessage(Some(on_message.as_ref().unchecked_ref()));
+
+        let hello = coordinator.borrow().hello();
+        post(&channel, &hello);
+
+        Some(Self {
+            inner: Rc::new(TabSyncInner {
+                channel,
+                coordinator,
+                relay_market,
+                _on_message: on_message,
+            }),
+        })
+    }
+
+    /// Broadcast a message to every other tab
+    pub fn post(&self, msg: &TabMessage) {
+        post(&self.inner.channel, msg);
+    }
+
+    /// Is this tab the elected primary?
+    pub fn is_primary(&self) -> bool {
+        self.inner.coordinator.borrow().is_primary()
+    }
+
+    /// Do secondary tabs consume the primary's feed?
+    pub fn relays_market(&self) -> bool {
+        self.inner.relay_market
+    }
+
+    /// Advance the election and emit a beacon when primary
+    fn tick(&self) {
+        let beacon = self.inner.coordinator.borrow_mut().tick(now_ms());
+        if let Some(beacon) = beacon {
+            self.post(&beacon);
+        }
+    }
+}
+
+fn post(channel: &BroadcastChannel, msg: &TabMessage) {
+    if let Ok(json) = serde_json::to_string(msg) {
+        let _ = channel.post_message(&json.into());
+    }
+}
+
+fn now_ms() -> i64 {
+    js_sys::Date::now() as i64
+}
+
+// ============================================================================
+// LEPTOS INTEGRATION
+// ============================================================================
+
+/// Join cross-tab sync: runs the election heartbeat and broadcasts local
+/// preference and acknowledgement changes
+pub fn use_tab_sync(state: AppState, relay_market: bool) -> Option<TabSync> {
+    let sync = TabSync::new(state.clone(), relay_market)?;
+
+    let heartbeat = sync.clone();
+    spawn_local(async move {
+        loop {
+            heartbeat.tick();
+            TimeoutFuture::new(TabCoordinator::HEARTBEAT_MS as u32).await;
+        }
+    });
+
+    // Skip the initial run so a fresh tab doesn't clobber the others
+    let prefs_state = state.clone();
+    let prefs_sync = sync.clone();
+    Effect::new(move |prev: Option<()>| {
+        let msg = prefs_state.preferences_message();
+        if prev.is_some() {
+            prefs_sync.post(&msg);
+        }
+    });
+
+    let acks = state.alert_acks;
+    let acks_sync = sync.clone();
+    Effect::new(move |prev: Option<()>| {
+        let ids: Vec<String> = acks.get().into_iter().collect();
+        if prev.is_some() {
+            acks_sync.post(&TabMessage::AlertAcks { ids });
+        }
+    });
+
+    Some(sync)
+}
@@ -0,0 +1,268 @@
+//! Session analytics: open, range and VWAP per symbol and session anchor,
+//! plus a VPIN-style flow toxicity estimate
+//!
+//! Sessions for the preset anchors are always kept; clients can ask for a
+//! custom day start, which is backfilled from the 1m archive so its stats
+//! cover the whole session from the first broadcast.
+
+use std::collections::{BTreeMap, HashMap, VecDeque};
+use std::sync::Arc;
+use std::time::Duration;
+
+use chrono::Utc;
+use tokio::sync::broadcast;
+use tokio::time::interval;
+
+use dash_core::{
+    Candle, CandleInterval, FlowToxicity, SessionAnchor, SessionStats, Symbol, Trade, TradeSide, WsMessage,
+};
+
+use crate::api::{CandleArchive, SymbolDirectory};
+use crate::AppState;
+
+/// Anchors tracked at most, presets included
+pub const MAX_SESSION_ANCHORS: usize = 8;
+
+/// Live sessions for every tracked anchor
+pub struct SessionTracker {
+    anchors: Vec<SessionAnchor>,
+    sessions: HashMap<(Symbol, SessionAnchor), SessionStats>,
+}
+
+impl SessionTracker {
+    /// Preset anchors, backfilled from `history` (1m candles per symbol)
+    pub fn new(history: &BTreeMap<Symbol, Vec<Candle>>, now: i64) -> Self {
+        let mut tracker = Self {
+            anchors: Vec::new(),
+            sessions: HashMap::new(),
+        };
+        for &anchor in SessionAnchor::presets() {
+            tracker.track(anchor, history, now);
+        }
+        tracker
+    }
+
+    pub fn is_tracked(&self, anchor: SessionAnchor) -> bool {
+        self.anchors.contains(&anchor)
+    }
+
+    /// Start keeping sessions from `anchor` (false once the limit is hit)
+    pub fn track(&mut self, anchor: SessionAnchor, history: &BTreeMap<Symbol, Vec<Candle>>, now: i64) -> bool {
+        if self.is_tracked(anchor) {
+            return true;
+        }
+        if self.anchors.len() >= MAX_SESSION_ANCHORS {
+            return false;
+        }
+        self.anchors.push(anchor);
+        for (symbol, candles) in history {
+            if let Some(session) = backfill(symbol, anchor, candles, now) {
+                self.sessions.insert((symbol.clone(), anchor), session);
+            }
+        }
+        true
+    }
+
+    /// Fold a trade into every anchor's session, rolling over at its start
+    pub fn record_trade(&mut self, trade: &Trade) {
+        let timestamp = trade.timestamp.timestamp_millis();
+        let (price, qty) = (trade.price.as_f64(), trade.quantity.as_f64());
+        for &anchor in &self.anchors {
+            let start = anchor.session_start(timestamp);
+            let session = self
+                .sessions
+                .entry((trade.symbol.clone(), anchor))
+                .or_insert_with(|| SessionStats::new(trade.symbol.clone(), anchor, price, timestamp));
+            if session.session_start != start {
+                *session = SessionStats::new(trade.symbol.clone(), anchor, price, timestamp);
+            }
+            session.update(price, qty, timestamp);
+        }
+    }
+
+    pub fn sessions(&self) -> impl Iterator<Item = &SessionStats> {
+        self.sessions.values()
+    }
+}
+
+/// Session so far from 1m candles: extremes at candle times, VWAP from each
+/// candle's average price
+fn backfill(symbol: &Symbol, anchor: SessionAnchor, candles: &[Candle], now: i64) -> Option<SessionStats> {
+    let start = anchor.session_start(now);
+    let from = candles.partition_point(|c| c.timestamp < start);
+    let (first, rest) = candles[from..].split_first()?;
+    let mut session = SessionStats::new(symbol.clone(), anchor, first.open.as_f64(), first.timestamp);
+    for candle in std::iter::once(first).chain(rest) {
+        let volume = candle.volume.as_f64();
+        let average = if volume > 0.0 { candle.quote_volume / volume } else { candle.close.as_f64() };
+        session.update(candle.high.as_f64(), 0.0, candle.timestamp);
+        session.update(candle.low.as_f64(), 0.0, candle.timestamp);
+        session.update(average, volume, candle.timestamp);
+    }
+    Some(session)
+}
+
+/// Volume buckets per day, and buckets averaged into the VPIN
+pub const VPIN_BUCKETS: usize = 50;
+
+/// Trades poured into equal-volume buckets; VPIN is the mean buy/sell
+/// imbalance of the last [`VPIN_BUCKETS`] full ones
+pub struct ToxicityEstimator {
+    bucket_volume: f64,
+    buy: f64,
+    sell: f64,
+    /// |buy − sell| / bucket volume per full bucket, oldest first
+    imbalances: VecDeque<f64>,
+}
+
+impl ToxicityEstimator {
+    /// Buckets sized to a fiftieth of the last day's volume in `candles`
+    pub fn new(candles: &[Candle]) -> Self {
+        let daily: f64 = candles.iter().map(|c| c.volume.as_f64()).sum();
+        Self {
+            bucket_volume: daily / VPIN_BUCKETS as f64,
+            buy: 0.0,
+            sell: 0.0,
+            imbalances: VecDeque::with_capacity(VPIN_BUCKETS),
+        }
+    }
+
+    pub fn record_trade(&mut self, trade: &Trade) {
+        let mut quantity = trade.quantity.as_f64();
+        // No history to size buckets by: take it from the first print
+        if self.bucket_volume <= 0.0 {
+            self.bucket_volume = quantity * VPIN_BUCKETS as f64;
+        }
+        // A print larger than the room left spills into the next buckets
+        while quantity > 0.0 && self.bucket_volume > 0.0 {
+            let room = self.bucket_volume - self.buy - self.sell;
+            let fill = quantity.min(room);
+            match trade.side {
+                TradeSide::Buy => self.buy += fill,
+                TradeSide::Sell => self.sell += fill,
+            }
+            quantity -= fill;
+            if fill >= room {
+                self.imbalances.push_back((self.buy - self.sell).abs() / self.bucket_volume);
+                if self.imbalances.len() > VPIN_BUCKETS {
+                    self.imbalances.pop_front();
+                }
+                self.buy = 0.0;
+                self.sell = 0.0;
+            }
+        }
+    }
+
+    /// Current estimate (`None` before the first full bucket)
+    pub fn toxicity(&self, symbol: &Symbol, now: i64) -> Option<FlowToxicity> {
+        let buckets = self.imbalances.len();
+        (buckets > 0).then(|| FlowToxicity {
+            symbol: symbol.clone(),
+            vpin: self.imbalances.iter().sum::<f64>() / buckets as f64,
+            buckets,
+            bucket_volume: self.bucket_volume,
+            timestamp: now,
+        })
+    }
+}
+
+/// Archived 1m candles of the last day per listed symbol
+pub fn session_history(symbols: &SymbolDirectory, archive: &CandleArchive) -> BTreeMap<Symbol, Vec<Candle>> {
+    let day = (86_400_000 / CandleInterval::M1.as_millis()) as usize;
+    symbols
+        .list()
+        .into_iter()
+        .map(|info| {
+            let candles = archive.before(&info.symbol, i64::MAX, day);
+            (info.symbol, candles)
+        })
+        .collect()
+}
+
+/// Fold trades into the sessions and toxicity estimates and broadcast them
+/// each second
+pub async fn run_analytics(state: Arc<AppState>) {
+    let mut toxicity: BTreeMap<Symbol, ToxicityEstimator> = session_history(&state.symbols, &state.candles)
+        .into_iter()
+        .map(|(symbol, candles)| (symbol, ToxicityEstimator::new(&candles)))
+        .collect();
+
+    let mut rx = state.tx.subscribe();
+    let mut publish = interval(Duration::from_secs(1));
+    loop {
+        tokio::select! {
+            msg = rx.recv() => match msg {
+                Ok(WsMessage::Trade(trade)) => {
+                    if let Some(estimator) = toxicity.get_mut(&trade.symbol) {
+                        estimator.record_trade(&trade);
+                    }
+                    match state.sessions.lock() {
+                        Ok(mut sessions) => sessions.record_trade(&trade),
+                        Err(_) => break,
+                    }
+                }
+                Ok(_) => {}
+                Err(broadcast::error::RecvError::Lagged(missed)) => state.metrics.record_lag("analytics", missed),
+                Err(broadcast::error::RecvError::Closed) => break,
+            },
+            _ = publish.tick() => {
+                let sessions: Vec<SessionStats> = match state.sessions.lock() {
+                    Ok(sessions) => sessions.sessions().cloned().collect(),
+                    Err(_) => break,
+                };
+                for session in sessions {
+                    let _ = state.tx.send(WsMessage::Session(session));
+                }
+                let now = Utc::now().timestamp_millis();
+                for (symbol, estimator) in &toxicity {
+                    if let Some(flow) = estimator.toxicity(symbol, now) {
+                        let _ = state.tx.send(WsMessage::Toxicity(flow));
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// A client asked for sessions from `anchor`
+pub fn track_session(state: &AppState, anchor: SessionAnchor) {
+    let tracked = state.sessions.lock().is_ok_and(|s| s.is_tracked(anchor));
+    if tracked {
+        return;
+    }
+    let history = session_history(&state.symbols, &state.candles);
+    let now = Utc::now().timestamp_millis();
+    if let Ok(mut sessions) = state.sessions.lock()
+        && !sessions.track(anchor, &history, now)
+    {
+        tracing::debug!("Session anchor limit reached; ignoring {:?}", anchor);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_large_print_spills_across_buckets() {
+        let symbol = Symbol::new("BTC-USD");
+        // A day of 50 units: one unit per bucket
+        let mut day = Candle::new(symbol.clone(), CandleInterval::M1, 0, 100.0);
+        day.update(100.0, VPIN_BUCKETS as f64);
+        let mut estimator = ToxicityEstimator::new(&[day]);
+        assert!(estimator.toxicity(&symbol, 0).is_none());
+
+        // 2.5 units bought fill two one-sided buckets and half the third
+        estimator.record_trade(&Trade::new(symbol.clone(), 100.0, 2.5, TradeSide::Buy));
+        let flow = estimator.toxicity(&symbol, 0).unwrap();
+        assert_eq!(flow.buckets, 2);
+        assert_eq!(flow.bucket_volume, 1.0);
+        assert!((flow.vpin - 1.0).abs() < 1e-9);
+
+        // Selling the other half closes the third bucket balanced
+        estimator.record_trade(&Trade::new(symbol.clone(), 100.0, 0.5, TradeSide::Sell));
+        let flow = estimator.toxicity(&symbol, 0).unwrap();
+        assert_eq!(flow.buckets, 3);
+        assert!((flow.vpin - 2.0 / 3.0).abs() < 1e-9);
+    }
+}
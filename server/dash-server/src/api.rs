@@ -0,0 +1,543 @@
+//! REST endpoints under `/api`
+
+use std::collections::{BTreeMap, VecDeque};
+use std::hash::{DefaultHasher, Hash, Hasher};
+use std::sync::{Arc, RwLock};
+
+use axum::{
+    extract::{Query, State},
+    http::{header, HeaderMap, StatusCode},
+    response::{IntoResponse, Response},
+    Json,
+};
+use serde::{Deserialize, Serialize};
+use tokio::sync::broadcast;
+
+use dash_core::{Candle, CandleInterval, CandlePage, MarketSnapshot, Symbol, SymbolInfo, Ticker, Trade, WsMessage};
+
+use crate::{paper, AppState};
+
+/// Trading rules and latest 24h stats per symbol, kept current from the
+/// ticker feed
+pub struct SymbolDirectory {
+    entries: RwLock<BTreeMap<Symbol, SymbolInfo>>,
+}
+
+impl SymbolDirectory {
+    pub fn new(symbols: impl IntoIterator<Item = SymbolInfo>) -> Self {
+        let entries = symbols.into_iter().map(|s| (s.symbol.clone(), s)).collect();
+        Self {
+            entries: RwLock::new(entries),
+        }
+    }
+
+    pub fn list(&self) -> Vec<SymbolInfo> {
+        self.entries
+            .read()
+            .map(|entries| entries.values().cloned().collect())
+            .unwrap_or_default()
+    }
+
+    pub fn get(&self, symbol: &Symbol) -> Option<SymbolInfo> {
+        self.entries.read().ok()?.get(symbol).cloned()
+    }
+
+    fn update(&self, ticker: &Ticker) {
+        if let Ok(mut entries) = self.entries.write()
+            && let Some(entry) = entries.get_mut(&ticker.symbol)
+        {
+            entry.update_from(ticker);
+        }
+    }
+}
+
+/// Follow the broadcast feed and refresh the directory on every ticker
+pub async fn track_symbols(state: Arc<AppState>) {
+    let mut rx = state.tx.subscribe();
+    loop {
+        match rx.recv().await {
+            Ok(WsMessage::Ticker(ticker)) => state.symbols.update(&ticker),
+            Ok(_) => {}
+            Err(broadcast::error::RecvError::Lagged(missed)) => state.metrics.record_lag("symbols", missed),
+            Err(broadcast::error::RecvError::Closed) => break,
+        }
+    }
+}
+
+/// `GET /api/schema`: JSON Schema of the WebSocket messages and client
+/// commands, with the protocol version
+pub async fn schema_handler(headers: HeaderMap) -> Response {
+    cached_json(&headers, &dash_core::protocol_schema())
+}
+
+/// `GET /api/symbols`
+pub async fn symbols_handler(State(state): State<Arc<AppState>>) -> Json<Vec<SymbolInfo>> {
+    Json(state.symbols.list())
+}
+
+/// 1m candles kept per symbol for chart backfill (one week)
+pub const ARCHIVE_CANDLES: usize = 7 * 24 * 60;
+
+/// Base (1m) candle history per symbol: seeded at startup, then extended
+/// from the live feed
+pub struct CandleArchive {
+    entries: RwLock<BTreeMap<Symbol, VecDeque<Candle>>>,
+}
+
+impl CandleArchive {
+    pub fn new(seed: impl IntoIterator<Item = (Symbol, Vec<Candle>)>) -> Self {
+        let entries = seed.into_iter().map(|(symbol, candles)| (symbol, candles.into())).collect();
+        Self {
+            entries: RwLock::new(entries),
+        }
+    }
+
+    /// Upsert a candle from the feed by open time
+    fn record(&self, candle: &Candle) {
+        if candle.interval != CandleInterval::M1 {
+            return;
+        }
+        let Ok(mut entries) = self.entries.write() else {
+            return;
+        };
+        let candles = entries.entry(candle.symbol.clone()).or_default();
+        match candles.back_mut() {
+            Some(last) if last.timestamp == candle.timestamp => *last = candle.clone(),
+            Some(last) if last.timestamp > candle.timestamp => {}
+            _ => {
+                candles.push_back(candle.clone());
+                if candles.len() > ARCHIVE_CANDLES {
+                    candles.pop_front();
+                }
+            }
+        }
+    }
+
+    /// The `limit` most recent candles opening before `before`, oldest first
+    pub fn before(&self, symbol: &Symbol, before: i64, limit: usize) -> Vec<Candle> {
+        let Ok(entries) = self.entries.read() else {
+            return Vec::new();
+        };
+        let Some(candles) = entries.get(symbol) else {
+            return Vec::new();
+        };
+        let end = candles.partition_point(|c| c.timestamp < before);
+        candles.range(end.saturating_sub(limit)..end).cloned().collect()
+    }
+
+    /// Up to `limit` candles opening strictly between `after` and `before`:
+    /// the oldest ones when paging forward from `after`, otherwise the
+    /// newest
+    pub fn page(&self, symbol: &Symbol, before: Option<i64>, after: Option<i64>, limit: usize) -> CandlePage {
+        let Ok(entries) = self.entries.read() else {
+            return CandlePage::default();
+        };
+        let Some(candles) = entries.get(symbol) else {
+            return CandlePage::default();
+        };
+        let start = after.map_or(0, |after| candles.partition_point(|c| c.timestamp <= after));
+        let end = before
+            .map_or(candles.len(), |before| candles.partition_point(|c| c.timestamp < before))
+            .max(start);
+        let (from, to) = match after {
+            Some(_) => (start, (start + limit).min(end)),
+            None => (end.saturating_sub(limit).max(start), end),
+        };
+        let page: Vec<Candle> = candles.range(from..to).cloned().collect();
+        CandlePage {
+            older: page.first().filter(|_| from > 0).map(|c| c.timestamp),
+            newer: page.last().filter(|_| to < candles.len()).map(|c| c.timestamp),
+            candles: page,
+        }
+    }
+}
+
+/// Follow the broadcast feed and archive every candle update
+pub async fn track_candles(state: Arc<AppState>) {
+    let mut rx = state.tx.subscribe();
+    loop {
+        match rx.recv().await {
+            Ok(WsMessage::Candle(candle)) => state.candles.record(&candle),
+            Ok(_) => {}
+            Err(broadcast::error::RecvError::Lagged(missed)) => state.metrics.record_lag("candles", missed),
+            Err(broadcast::error::RecvError::Closed) => break,
+        }
+    }
+}
+
+/// Trades kept per symbol for the history endpoint
+pub const ARCHIVE_TRADES: usize = 100_000;
+/// Most trades returned by one `GET /api/trades`
+pub const MAX_TRADES_PAGE: usize = 5_000;
+
+/// Recent prints per symbol from the live feed, in time order
+#[derive(Default)]
+pub struct TradeArchive {
+    entries: RwLock<BTreeMap<Symbol, VecDeque<Trade>>>,
+}
+
+impl TradeArchive {
+    fn record(&self, trade: &Trade) {
+        let Ok(mut entries) = self.entries.write() else {
+            return;
+        };
+        let trades = entries.entry(trade.symbol.clone()).or_default();
+        // Feeds are mostly in order; a late print slots in by time
+        let at = trades.partition_point(|t| t.timestamp <= trade.timestamp);
+        trades.insert(at, trade.clone());
+        if trades.len() > ARCHIVE_TRADES {
+            trades.pop_front();
+        }
+    }
+
+    /// Up to `limit` trades with `from <= time < to` (ms), oldest first: the
+    /// earliest ones when `from` is given, otherwise the latest. `after`
+    /// names the last trade of the previous page: the trades at `from` up to
+    /// and including it are skipped, so a millisecond split across pages
+    /// isn't lost.
+    pub fn range(
+        &self,
+        symbol: &Symbol,
+        from: Option<i64>,
+        after: Option<&str>,
+        to: Option<i64>,
+        limit: usize,
+    ) -> Vec<Trade> {
+        let Ok(entries) = self.entries.read() else {
+            return Vec::new();
+        };
+        let Some(trades) = entries.get(symbol) else {
+            return Vec::new();
+        };
+        let time = |t: &Trade| t.timestamp.timestamp_millis();
+        let mut start = from.map_or(0, |from| trades.partition_point(|t| time(t) < from));
+        if let (Some(from), Some(after)) = (from, after)
+            && let Some(seen) = trades.range(start..).take_while(|t| time(t) == from).position(|t| t.id == after)
+        {
+            start += seen + 1;
+        }
+        let end = to.map_or(trades.len(), |to| trades.partition_point(|t| time(t) < to)).max(start);
+        let (first, last) = match from {
+            Some(_) => (start, (start + limit).min(end)),
+            None => (end.saturating_sub(limit).max(start), end),
+        };
+        trades.range(first..last).cloned().collect()
+    }
+
+    /// Time (ms) of the oldest trade still held for `symbol`
+    pub fn oldest(&self, symbol: &Symbol) -> Option<i64> {
+        let entries = self.entries.read().ok()?;
+        entries.get(symbol)?.front().map(|t| t.timestamp.timestamp_millis())
+    }
+}
+
+/// Follow the broadcast feed and archive every trade
+pub async fn track_trades(state: Arc<AppState>) {
+    let mut rx = state.tx.subscribe();
+    loop {
+        match rx.recv().await {
+            Ok(WsMessage::Trade(trade)) => state.trades.record(&trade),
+            Ok(_) => {}
+            Err(broadcast::error::RecvError::Lagged(missed)) => state.metrics.record_lag("trades", missed),
+            Err(broadcast::error::RecvError::Closed) => break,
+        }
+    }
+}
+
+/// Latest ticker, book and forming candle per symbol, for snapshot requests
+#[derive(Default)]
+pub struct SnapshotCache {
+    entries: RwLock<BTreeMap<Symbol, MarketSnapshot>>,
+}
+
+impl SnapshotCache {
+    fn record(&self, msg: WsMessage) {
+        let Ok(mut entries) = self.entries.write() else {
+            return;
+        };
+        let snapshot = |symbol: &Symbol| MarketSnapshot::new(symbol.clone());
+        match msg {
+            WsMessage::Ticker(ticker) => {
+                let symbol = ticker.symbol.clone();
+                entries.entry(symbol).or_insert_with_key(snapshot).ticker = Some(ticker);
+            }
+            WsMessage::OrderBook(book) => {
+                let symbol = book.symbol.clone();
+                entries.entry(symbol).or_insert_with_key(snapshot).orderbook = Some(book);
+            }
+            WsMessage::Candle(candle) if candle.interval == CandleInterval::M1 && !candle.is_closed => {
+                let symbol = candle.symbol.clone();
+                entries.entry(symbol).or_insert_with_key(snapshot).candle = Some(candle);
+            }
+            _ => {}
+        }
+    }
+
+    /// Latest state of `symbol` (empty before any update)
+    pub fn get(&self, symbol: &Symbol) -> MarketSnapshot {
+        self.entries
+            .read()
+            .ok()
+            .and_then(|entries| entries.get(symbol).cloned())
+            .unwrap_or_else(|| MarketSnapshot::new(symbol.clone()))
+    }
+}
+
+/// Follow the broadcast feed and keep the snapshot cache current
+pub async fn track_snapshots(state: Arc<AppState>) {
+    let mut rx = state.tx.subscribe();
+    loop {
+        match rx.recv().await {
+            Ok(msg) => state.snapshots.record(msg),
+            Err(broadcast::error::RecvError::Lagged(missed)) => state.metrics.record_lag("snapshots", missed),
+            Err(broadcast::error::RecvError::Closed) => break,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CandlesQuery {
+    symbol: Symbol,
+    /// Open time bound in ms (exclusive); latest candles when omitted
+    before: Option<i64>,
+    /// Open time bound in ms (exclusive); pages forward from here when set
+    after: Option<i64>,
+    limit: Option<usize>,
+}
+
+/// `GET /api/candles?symbol=BTC-USD&before=<ms>&after=<ms>&limit=<n>`: a
+/// [`CandlePage`] of 1m candles, oldest first
+pub async fn candles_handler(
+    State(state): State<Arc<AppState>>,
+    headers: HeaderMap,
+    Query(query): Query<CandlesQuery>,
+) -> Response {
+    let limit = query.limit.unwrap_or(ARCHIVE_CANDLES).min(ARCHIVE_CANDLES);
+    let page = state.candles.page(&query.symbol, query.before, query.after, limit);
+    cached_json(&headers, &page)
+}
+
+#[derive(Debug, Deserialize)]
+pub struct TradesQuery {
+    symbol: Symbol,
+    /// Trade time bounds in ms, `from` inclusive and `to` exclusive
+    from: Option<i64>,
+    /// Id of the last trade already received, at time `from`
+    after: Option<String>,
+    to: Option<i64>,
+    limit: Option<usize>,
+}
+
+/// `GET /api/trades?symbol=BTC-USD&from=<ms>&after=<id>&to=<ms>&limit=<n>`:
+/// archived trades, oldest first; page forward by passing the last one's
+/// time as `from` and its id as `after`
+pub async fn trades_handler(
+    State(state): State<Arc<AppState>>,
+    headers: HeaderMap,
+    Query(query): Query<TradesQuery>,
+) -> Response {
+    let limit = query.limit.unwrap_or(1_000).min(MAX_TRADES_PAGE);
+    let trades = state.trades.range(&query.symbol, query.from, query.after.as_deref(), query.to, limit);
+    cached_json(&headers, &trades)
+}
+
+#[derive(Debug, Deserialize)]
+pub struct JournalQuery {
+    symbol: Option<Symbol>,
+    /// Fill time bounds in ms, `from` inclusive and `to` exclusive
+    from: Option<i64>,
+    to: Option<i64>,
+    limit: Option<usize>,
+}
+
+/// `GET /api/journal?symbol=BTC-USD&from=<ms>&to=<ms>&limit=<n>`: paper
+/// fills, oldest first, for every symbol when `symbol` is left out
+pub async fn journal_handler(
+    State(state): State<Arc<AppState>>,
+    headers: HeaderMap,
+    Query(query): Query<JournalQuery>,
+) -> Response {
+    let limit = query.limit.unwrap_or(1_000).min(paper::JOURNAL_LIMIT);
+    let entries = match state.paper.lock() {
+        Ok(engine) => engine.journal(query.symbol.as_ref(), query.from, query.to, limit),
+        Err(_) => return StatusCode::INTERNAL_SERVER_ERROR.into_response(),
+    };
+    cached_json(&headers, &entries)
+}
+
+#[derive(Debug, Deserialize)]
+pub struct EquityQuery {
+    /// Sample time bound in ms (inclusive); latest samples when omitted
+    from: Option<i64>,
+    limit: Option<usize>,
+}
+
+/// `GET /api/equity?from=<ms>&limit=<n>`: paper account equity sampled
+/// every 10 seconds, oldest first
+pub async fn equity_handler(
+    State(state): State<Arc<AppState>>,
+    headers: HeaderMap,
+    Query(query): Query<EquityQuery>,
+) -> Response {
+    let limit = query.limit.unwrap_or(paper::EQUITY_HISTORY).min(paper::EQUITY_HISTORY);
+    let samples = match state.paper.lock() {
+        Ok(engine) => engine.equity_history(query.from, limit),
+        Err(_) => return StatusCode::INTERNAL_SERVER_ERROR.into_response(),
+    };
+    cached_json(&headers, &samples)
+}
+
+/// Levels per side from `GET /api/depth`, by default and at most
+pub const DEFAULT_DEPTH_LEVELS: usize = 100;
+pub const MAX_DEPTH_LEVELS: usize = 1_000;
+
+#[derive(Debug, Deserialize)]
+pub struct DepthQuery {
+    symbol: Symbol,
+    levels: Option<usize>,
+    /// Price step to merge levels onto, snapped to a multiple of the tick
+    group: Option<f64>,
+}
+
+/// `GET /api/depth?symbol=BTC-USD&levels=100&group=<price step>`: the latest
+/// book, optionally grouped; 404 until the feed has sent one
+pub async fn depth_handler(
+    State(state): State<Arc<AppState>>,
+    headers: HeaderMap,
+    Query(query): Query<DepthQuery>,
+) -> Response {
+    let Some(book) = state.snapshots.get(&query.symbol).orderbook else {
+        return (StatusCode::NOT_FOUND, format!("No book for {}", query.symbol)).into_response();
+    };
+    let levels = query.levels.unwrap_or(DEFAULT_DEPTH_LEVELS).clamp(1, MAX_DEPTH_LEVELS);
+    let step = match (query.group.filter(|g| *g > 0.0), state.symbols.get(&query.symbol)) {
+        (Some(group), Some(info)) if info.tick_size > 0.0 => {
+            (group / info.tick_size).round().max(1.0) * info.tick_size
+        }
+        (group, _) => group.unwrap_or(0.0),
+    };
+    cached_json(&headers, &book.grouped(step, levels))
+}
+
+/// JSON body tagged with an ETag of its bytes, or `304 Not Modified` when
+/// the client already holds that version. `no-cache` lets browsers keep the
+/// body but makes them revalidate, since the newest page keeps changing.
+fn cached_json<T: Serialize>(request: &HeaderMap, value: &T) -> Response {
+    let body = match serde_json::to_vec(value) {
+        Ok(body) => body,
+        Err(e) => {
+            tracing::error!("Failed to serialize response: {}", e);
+            return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+        }
+    };
+    let mut hasher = DefaultHasher::new();
+    body.hash(&mut hasher);
+    let etag = format!("\"{:016x}\"", hasher.finish());
+
+    let cache_headers = [(header::ETAG, etag.clone()), (header::CACHE_CONTROL, "no-cache".to_string())];
+    // If-None-Match compares weakly: a `W/` prefix doesn't matter
+    let fresh = request
+        .get(header::IF_NONE_MATCH)
+        .and_then(|v| v.to_str().ok())
+        .is_some_and(|tags| tags.split(',').map(|t| t.trim().trim_start_matches("W/")).any(|t| t == etag || t == "*"));
+    if fresh {
+        return (StatusCode::NOT_MODIFIED, cache_headers).into_response();
+    }
+    (cache_headers, [(header::CONTENT_TYPE, "application/json")], body).into_response()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{TimeZone, Utc};
+    use dash_core::TradeSide;
+
+    #[test]
+    fn test_trade_pages_split_a_millisecond() {
+        let archive = TradeArchive::default();
+        let symbol = Symbol::new("BTC-USD");
+        let at = |ms: i64, id: &str| Trade {
+            id: id.to_string(),
+            timestamp: Utc.timestamp_millis_opt(ms).unwrap(),
+            ..Trade::new(symbol.clone(), 50_000.0, 0.01, TradeSide::Buy)
+        };
+        for trade in [at(1_000, "a"), at(1_001, "b"), at(1_001, "c"), at(1_001, "d"), at(1_002, "e")] {
+            archive.record(&trade);
+        }
+        let ids = |trades: Vec<Trade>| trades.into_iter().map(|t| t.id).collect::<Vec<_>>();
+
+        let first = archive.range(&symbol, Some(0), None, None, 3);
+        assert_eq!(ids(first), ["a", "b", "c"]);
+        let second = archive.range(&symbol, Some(1_001), Some("c"), None, 3);
+        assert_eq!(ids(second), ["d", "e"]);
+
+        // Without a cursor the page starts at the first trade of `from`
+        assert_eq!(ids(archive.range(&symbol, Some(1_001), None, None, 1)), ["b"]);
+        assert_eq!(ids(archive.range(&symbol, None, None, Some(1_002), 2)), ["c", "d"]);
+    }
+
+    #[test]
+    fn test_candle_pages_walk_both_ways() {
+        let symbol = Symbol::new("BTC-USD");
+        let minute = |i: i64| i * 60_000;
+        let seed = (0..10).map(|i| Candle::new(symbol.clone(), CandleInterval::M1, minute(i), 100.0)).collect();
+        let archive = CandleArchive::new([(symbol.clone(), seed)]);
+        let opens = |page: &CandlePage| page.candles.iter().map(|c| c.timestamp).collect::<Vec<_>>();
+
+        // Newest page first, then back to the oldest
+        let newest = archive.page(&symbol, None, None, 4);
+        assert_eq!(opens(&newest), [minute(6), minute(7), minute(8), minute(9)]);
+        assert_eq!((newest.older, newest.newer), (Some(minute(6)), None));
+        let middle = archive.page(&symbol, newest.older, None, 4);
+        assert_eq!(opens(&middle), [minute(2), minute(3), minute(4), minute(5)]);
+        assert_eq!((middle.older, middle.newer), (Some(minute(2)), Some(minute(5))));
+        let oldest = archive.page(&symbol, middle.older, None, 4);
+        assert_eq!(opens(&oldest), [minute(0), minute(1)]);
+        assert_eq!((oldest.older, oldest.newer), (None, Some(minute(1))));
+
+        // And forward again from the oldest
+        let forward = archive.page(&symbol, None, oldest.newer, 4);
+        assert_eq!(opens(&forward), opens(&middle));
+        let last = archive.page(&symbol, None, forward.newer, 4);
+        assert_eq!(opens(&last), opens(&newest));
+        assert_eq!(last.newer, None);
+
+        // Both bounds are exclusive
+        let between = archive.page(&symbol, Some(minute(5)), Some(minute(2)), 10);
+        assert_eq!(opens(&between), [minute(3), minute(4)]);
+
+        // Past either end
+        for page in [archive.page(&symbol, Some(minute(0)), None, 4), archive.page(&symbol, None, Some(minute(9)), 4)] {
+            assert!(page.candles.is_empty());
+            assert_eq!((page.older, page.newer), (None, None));
+        }
+        assert!(archive.page(&Symbol::new("ETH-USD"), None, None, 4).candles.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_etag_revalidation() {
+        let first = cached_json(&HeaderMap::new(), &[1, 2, 3]);
+        assert_eq!(first.status(), StatusCode::OK);
+        let etag = first.headers()[header::ETAG].clone();
+
+        let mut revalidate = HeaderMap::new();
+        revalidate.insert(header::IF_NONE_MATCH, etag.clone());
+        let unchanged = cached_json(&revalidate, &[1, 2, 3]);
+        assert_eq!(unchanged.status(), StatusCode::NOT_MODIFIED);
+        assert_eq!(unchanged.headers()[header::ETAG], etag);
+        let body = axum::body::to_bytes(unchanged.into_body(), usize::MAX).await.unwrap();
+        assert!(body.is_empty());
+
+        // Weak validators match too
+        let weak = format!("W/{}", etag.to_str().unwrap());
+        revalidate.insert(header::IF_NONE_MATCH, weak.parse().unwrap());
+        assert_eq!(cached_json(&revalidate, &[1, 2, 3]).status(), StatusCode::NOT_MODIFIED);
+
+        // A changed payload gets a new tag and a full body
+        let changed = cached_json(&revalidate, &[1, 2, 4]);
+        assert_eq!(changed.status(), StatusCode::OK);
+        assert_ne!(changed.headers()[header::ETAG], etag);
+        let body = axum::body::to_bytes(changed.into_body(), usize::MAX).await.unwrap();
+        assert_eq!(&body[..], b"[1,2,4]");
+    }
+}
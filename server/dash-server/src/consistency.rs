@@ -0,0 +1,142 @@
+//! Cross-check of archived candles against the trade archive
+//!
+//! Every minute the closed 1m bars of the last quarter hour are rebuilt
+//! from the archived trades and compared with the bars the source sent.
+//! Each symbol's report goes out as a `candle_check` message and is served
+//! at `/api/diagnostics/candles`. A divergent bar is repaired by sending the
+//! source's version again: clients replace bars by open time.
+
+use std::collections::BTreeMap;
+use std::sync::{Arc, RwLock};
+use std::time::Duration;
+
+use axum::{
+    extract::{Query, State},
+    http::{header, HeaderMap, StatusCode},
+    response::{IntoResponse, Response},
+    Json,
+};
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use tokio::time::interval;
+
+use dash_core::{CandleCheckReport, CandleInterval, Symbol, WsMessage};
+
+use crate::api::ARCHIVE_TRADES;
+use crate::AppState;
+
+/// How often the check runs
+const CHECK_EVERY: Duration = Duration::from_secs(60);
+/// Closed buckets covered by each check
+const CHECK_BUCKETS: i64 = 15;
+
+/// Latest report per symbol
+#[derive(Default)]
+pub struct CandleChecks {
+    reports: RwLock<BTreeMap<Symbol, CandleCheckReport>>,
+}
+
+impl CandleChecks {
+    fn record(&self, report: CandleCheckReport) {
+        if let Ok(mut reports) = self.reports.write() {
+            reports.insert(report.symbol.clone(), report);
+        }
+    }
+
+    pub fn get(&self, symbol: &Symbol) -> Option<CandleCheckReport> {
+        self.reports.read().ok()?.get(symbol).cloned()
+    }
+
+    pub fn list(&self) -> Vec<CandleCheckReport> {
+        self.reports.read().map(|r| r.values().cloned().collect()).unwrap_or_default()
+    }
+}
+
+/// Check `symbol`'s closed buckets before `now`, leaving out any the trade
+/// archive only partly covers; `None` until it fully covers one
+fn check_symbol(state: &AppState, symbol: &Symbol, now: i64) -> Option<CandleCheckReport> {
+    let minute = CandleInterval::M1.as_millis();
+    let to = CandleInterval::M1.bucket_start(now);
+    // Trades before the oldest one held may have been dropped (or predate
+    // the server), so its bucket can't be rebuilt
+    let covered = CandleInterval::M1.bucket_start(state.trades.oldest(symbol)?) + minute;
+    let from = (to - CHECK_BUCKETS * minute).max(covered);
+    if from >= to {
+        return None;
+    }
+    let official = state.candles.page(symbol, Some(to), Some(from - 1), CHECK_BUCKETS as usize).candles;
+    let trades = state.trades.range(symbol, Some(from), None, Some(to), ARCHIVE_TRADES);
+    Some(CandleCheckReport::check(symbol.clone(), &official, &trades, from, to, now))
+}
+
+/// Check every symbol once a minute and publish the reports
+pub async fn run_candle_checks(state: Arc<AppState>) {
+    let mut tick = interval(CHECK_EVERY);
+    loop {
+        tick.tick().await;
+        let now = Utc::now().timestamp_millis();
+        for info in state.symbols.list() {
+            let Some(report) = check_symbol(&state, &info.symbol, now) else {
+                continue;
+            };
+            if !report.is_consistent() {
+                tracing::warn!(
+                    "{}: {} of {} bars disagree with the trades",
+                    report.symbol,
+                    report.discrepancies.len(),
+                    report.checked
+                );
+            }
+            state.candle_checks.record(report.clone());
+            let _ = state.tx.send(WsMessage::CandleCheck(report));
+        }
+    }
+}
+
+/// `GET /api/diagnostics/candles`: the latest report per symbol
+pub async fn reports_handler(State(state): State<Arc<AppState>>) -> Json<Vec<CandleCheckReport>> {
+    Json(state.candle_checks.list())
+}
+
+#[derive(Debug, Deserialize)]
+pub struct RepairQuery {
+    symbol: Symbol,
+}
+
+/// Body of a repair
+#[derive(Debug, Serialize)]
+pub struct RepairOutcome {
+    pub symbol: Symbol,
+    /// Bars sent again
+    pub repaired: usize,
+}
+
+/// `POST /api/diagnostics/candles/repair?symbol=BTC-USD`: send the source's
+/// version of every divergent bar in the latest report again, so clients
+/// replace theirs; 403 from an origin outside the allow-list, 404 when the
+/// symbol hasn't been checked yet
+pub async fn repair_handler(
+    headers: HeaderMap,
+    State(state): State<Arc<AppState>>,
+    Query(query): Query<RepairQuery>,
+) -> Response {
+    let origin = headers.get(header::ORIGIN).and_then(|o| o.to_str().ok());
+    if !state.origins.allows(origin) {
+        tracing::warn!("Refused candle repair from origin {:?}", origin);
+        return (StatusCode::FORBIDDEN, "Origin not allowed").into_response();
+    }
+    let Some(report) = state.candle_checks.get(&query.symbol) else {
+        return (StatusCode::NOT_FOUND, format!("No candle check for {}", query.symbol)).into_response();
+    };
+    let mut repaired = 0;
+    for candle in report.repairs() {
+        let _ = state.tx.send(WsMessage::Candle(candle.clone()));
+        repaired += 1;
+    }
+    tracing::info!("{}: re-sent {} divergent bars", query.symbol, repaired);
+    Json(RepairOutcome {
+        symbol: query.symbol,
+        repaired,
+    })
+    .into_response()
+}
@@ -0,0 +1,280 @@
+//! Failover between the primary market data source and a backup
+//!
+//! The primary source feeds a relay rather than the broadcast channel. When
+//! it ends or goes quiet for longer than the stall threshold, the relay
+//! switches to the configured backup and announces it with a `feed_status`
+//! message, so clients can flag the data as degraded; the first message
+//! from the primary switches back. Trades whose id was already published
+//! are dropped here, as a venue feed would redeliver them after a
+//! reconnect.
+//!
+//! The only backup so far is `drift`: each symbol's last known price takes
+//! a small random walk, sent as BBO updates only. Drift never prints
+//! trades, so nothing made up fills paper orders, lands in the trade
+//! archive or counts in the analytics.
+
+use std::collections::BTreeMap;
+use std::sync::Arc;
+use std::time::Duration;
+
+use chrono::Utc;
+use rand::Rng;
+use tokio::sync::mpsc;
+use tokio::time::{interval, Instant};
+
+use dash_core::{Bbo, FeedSource, FeedStatus, Price, Quantity, Symbol, SymbolInfo, TradeDedup, WsMessage};
+
+use crate::supervisor::SourceMonitor;
+use crate::AppState;
+
+/// Environment variables for the failover
+pub const STALL_ENV: &str = "DASH_FAILOVER_STALL_MS";
+pub const BACKUP_ENV: &str = "DASH_BACKUP_SOURCE";
+
+/// Primary silence after which the backup takes over
+pub const DEFAULT_STALL: Duration = Duration::from_secs(5);
+
+/// How often the drift backup moves each price
+const DRIFT_STEP: Duration = Duration::from_millis(500);
+/// Largest relative move per drift step
+const DRIFT_MAX_MOVE: f64 = 0.0002;
+
+/// Sources that can stand in for the primary
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BackupSource {
+    /// Random walk from the last known prices
+    Drift,
+}
+
+impl BackupSource {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Drift => "drift",
+        }
+    }
+}
+
+/// When to fail over, and to what
+#[derive(Debug, Clone, Copy)]
+pub struct FailoverConfig {
+    pub stall: Duration,
+    /// `None` leaves clients on the stalled primary
+    pub backup: Option<BackupSource>,
+}
+
+impl FailoverConfig {
+    /// `DASH_FAILOVER_STALL_MS` (default 5000) and `DASH_BACKUP_SOURCE`
+    /// (`drift`, the default, or `none`)
+    pub fn from_env() -> Self {
+        let stall = match std::env::var(STALL_ENV) {
+            Ok(value) => value.trim().parse().map(Duration::from_millis).unwrap_or_else(|_| {
+                tracing::warn!("Ignoring {}={:?}: not a whole number of ms", STALL_ENV, value);
+                DEFAULT_STALL
+            }),
+            Err(_) => DEFAULT_STALL,
+        };
+        let backup = match std::env::var(BACKUP_ENV).as_deref().map(str::trim) {
+            Ok("none") | Ok("off") => None,
+            Ok("drift") | Ok("") | Err(_) => Some(BackupSource::Drift),
+            Ok(other) => {
+                tracing::warn!("Unknown {}={:?}; using drift", BACKUP_ENV, other);
+                Some(BackupSource::Drift)
+            }
+        };
+        Self { stall, backup }
+    }
+
+    pub fn describe(&self) -> String {
+        match self.backup {
+            Some(backup) => format!("{} after {}ms of silence", backup.name(), self.stall.as_millis()),
+            None => "none".to_string(),
+        }
+    }
+}
+
+/// Status announcing `name` as the source from now on
+pub fn feed_status(source: FeedSource, name: &str) -> FeedStatus {
+    FeedStatus {
+        source,
+        name: name.to_string(),
+        since: Utc::now().timestamp_millis(),
+    }
+}
+
+/// Last known price and touch width per symbol
+struct DriftMarket {
+    info: SymbolInfo,
+    price: f64,
+    half_spread: f64,
+}
+
+/// Backup feed walking the last prices seen from the primary
+pub struct DriftFeed {
+    markets: BTreeMap<Symbol, DriftMarket>,
+}
+
+impl DriftFeed {
+    pub fn new(symbols: Vec<SymbolInfo>) -> Self {
+        let markets = symbols
+            .into_iter()
+            .map(|info| {
+                let market = DriftMarket {
+                    half_spread: info.tick_size,
+                    price: 0.0,
+                    info,
+                };
+                (market.info.symbol.clone(), market)
+            })
+            .collect();
+        Self { markets }
+    }
+
+    /// Follow the primary so the walk starts where it stopped
+    pub fn observe(&mut self, msg: &WsMessage) {
+        match msg {
+            WsMessage::Trade(trade) => {
+                if let Some(market) = self.markets.get_mut(&trade.symbol) {
+                    market.price = trade.price.as_f64();
+                }
+            }
+            WsMessage::Bbo(bbo) => {
+                if let Some(market) = self.markets.get_mut(&bbo.symbol) {
+                    market.half_spread = (bbo.spread() / 2.0).max(market.info.tick_size);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// One step of the walk: the touch around the moved price for every
+    /// symbol with a known price
+    pub fn step(&mut self) -> Vec<WsMessage> {
+        let mut rng = rand::thread_rng();
+        let now = Utc::now().timestamp_millis();
+        let mut messages = Vec::new();
+        for (symbol, market) in self.markets.iter_mut().filter(|(_, m)| m.price > 0.0) {
+            market.price =
+                market.info.round_price(market.price * (1.0 + rng.gen_range(-DRIFT_MAX_MOVE..DRIFT_MAX_MOVE)));
+            let quantity = market.info.min_quantity;
+            messages.push(WsMessage::Bbo(Bbo {
+                symbol: symbol.clone(),
+                bid: Price::new(market.info.round_price(market.price - market.half_spread)),
+                bid_size: Quantity::new(quantity),
+                ask: Price::new(market.info.round_price(market.price + market.half_spread)),
+                ask_size: Quantity::new(quantity),
+                timestamp: now,
+            }));
+        }
+        messages
+    }
+}
+
+/// False for a trade already published
+fn first_sighting(seen_trades: &mut TradeDedup, msg: &WsMessage) -> bool {
+    match msg {
+        WsMessage::Trade(trade) if !seen_trades.first_sighting(trade) => {
+            tracing::debug!("Dropping redelivered trade {}", trade.id);
+            false
+        }
+        _ => true,
+    }
+}
+
+/// Forward the primary feed onto the broadcast channel, standing in the
+/// backup while it is silent
+pub async fn run_failover(
+    state: Arc<AppState>,
+    mut primary: mpsc::UnboundedReceiver<WsMessage>,
+    monitor: Arc<SourceMonitor>,
+    config: FailoverConfig,
+) {
+    let primary_name = monitor.name();
+    let mut drift = DriftFeed::new(state.symbols.list());
+    let mut seen_trades = TradeDedup::default();
+    let mut last_seen = Instant::now();
+    let mut primary_open = true;
+    let mut check = interval(Duration::from_millis(250));
+    let mut drift_step = interval(DRIFT_STEP);
+    let mut on_backup = false;
+
+    loop {
+        tokio::select! {
+            msg = primary.recv(), if primary_open => match msg {
+                Some(msg) => {
+                    last_seen = Instant::now();
+                    monitor.record_message();
+                    if on_backup {
+                        tracing::info!("Primary feed {} is back", primary_name);
+                        on_backup = false;
+                        announce(&state, feed_status(FeedSource::Primary, primary_name));
+                    }
+                    if first_sighting(&mut seen_trades, &msg) {
+                        drift.observe(&msg);
+                        let _ = state.tx.send(msg);
+                    }
+                }
+                None => {
+                    tracing::error!("Primary feed {} ended", primary_name);
+                    primary_open = false;
+                }
+            },
+            _ = check.tick(), if !on_backup => {
+                let stalled = !primary_open || last_seen.elapsed() >= config.stall;
+                if let Some(backup) = config.backup.filter(|_| stalled) {
+                    tracing::warn!(
+                        "Primary feed {} silent for {:?}; failing over to {}",
+                        primary_name,
+                        last_seen.elapsed(),
+                        backup.name()
+                    );
+                    on_backup = true;
+                    announce(&state, feed_status(FeedSource::Backup, backup.name()));
+                }
+            }
+            _ = drift_step.tick(), if on_backup => {
+                for msg in drift.step() {
+                    let _ = state.tx.send(msg);
+                }
+            }
+        }
+    }
+}
+
+/// Record the new source for clients that connect later and tell the
+/// connected ones
+fn announce(state: &AppState, status: FeedStatus) {
+    state.feed.send_replace(status.clone());
+    let _ = state.tx.send(WsMessage::FeedStatus(status));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use dash_core::{Trade, TradeSide};
+
+    #[test]
+    fn test_redelivered_trade_dropped() {
+        let mut seen_trades = TradeDedup::default();
+        let trade = WsMessage::Trade(Trade::new(Symbol::new("BTC-USD"), 50_000.0, 0.01, TradeSide::Buy));
+        assert!(first_sighting(&mut seen_trades, &trade));
+        assert!(!first_sighting(&mut seen_trades, &trade.clone()));
+        let other = WsMessage::Trade(Trade::new(Symbol::new("BTC-USD"), 50_000.0, 0.01, TradeSide::Buy));
+        assert!(first_sighting(&mut seen_trades, &other));
+    }
+
+    #[test]
+    fn test_drift_sends_no_trades() {
+        let info = SymbolInfo::new(Symbol::new("BTC-USD"), 0.01, 0.001);
+        let mut drift = DriftFeed::new(vec![info.clone()]);
+        assert!(drift.step().is_empty());
+
+        drift.observe(&WsMessage::Trade(Trade::new(info.symbol.clone(), 50_000.0, 0.01, TradeSide::Buy)));
+        let messages = drift.step();
+        assert_eq!(messages.len(), 1);
+        let WsMessage::Bbo(bbo) = &messages[0] else {
+            panic!("expected a BBO, got {}", messages[0].kind());
+        };
+        assert!(bbo.bid.as_f64() < bbo.ask.as_f64());
+        assert!((bbo.mid() - 50_000.0).abs() <= 50_000.0 * DRIFT_MAX_MOVE + 0.01);
+    }
+}
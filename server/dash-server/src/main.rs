@@ -2,38 +2,100 @@
 //!
 //! Axum-based server providing:
 //! - WebSocket endpoint for real-time market data
+//! - REST endpoints under `/api`
 //! - Static file serving for the WASM frontend
-//! - Mock data engine for demo mode
+//! - Mock data engine for demo mode, with optional clock skew, latency and outages
+//! - Failover to a backup feed while the primary is silent
+//! - Source supervision: restarts with backoff and health reports
+//! - 24h ticker stats rolled up from the trade stream
+//! - Consistency check of archived candles against the trade archive
+//! - Session analytics per configurable day start, plus flow toxicity
+//! - Origin allow-list for CORS and WebSocket upgrades
+//! - Broadcast channel capacity and lag counters (`/api/stats`, `/metrics`)
+//! - Paper-trading engine driven by client commands, with a trade journal
+//!   and equity history
 
+mod analytics;
+mod api;
+mod consistency;
+mod failover;
+mod metrics;
 mod mock;
+mod origins;
+mod paper;
+mod supervisor;
+mod ticker;
 mod ws;
 
 use axum::{
-    routing::get,
+    routing::{get, post},
     Router,
 };
 use std::net::SocketAddr;
-use std::sync::Arc;
-use tokio::sync::broadcast;
-use tower_http::{
-    cors::{Any, CorsLayer},
-    services::ServeDir,
-    trace::TraceLayer,
-};
+use std::sync::{Arc, Mutex};
+use tokio::sync::{broadcast, mpsc, watch};
+use tower_http::{services::ServeDir, trace::TraceLayer};
 use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
 
-use dash_core::WsMessage;
+use dash_core::{FeedSource, FeedStatus, WsMessage};
 
 /// Shared application state
 pub struct AppState {
     /// Broadcast channel for market data
     pub tx: broadcast::Sender<WsMessage>,
+    /// Tradable symbols with their latest 24h stats
+    pub symbols: api::SymbolDirectory,
+    /// 1m candle history served for chart backfill
+    pub candles: api::CandleArchive,
+    /// Recent trades served by the history endpoint
+    pub trades: api::TradeArchive,
+    /// Latest ticker, book and candle per symbol for snapshot requests
+    pub snapshots: api::SnapshotCache,
+    /// Shared demo paper-trading account
+    pub paper: Mutex<paper::PaperEngine>,
+    /// Session open, range and VWAP per symbol and day start
+    pub sessions: Mutex<analytics::SessionTracker>,
+    /// Browser origins allowed to call the API and open the socket
+    pub origins: origins::OriginPolicy,
+    /// Flips to true when the server starts shutting down
+    pub shutdown: watch::Sender<bool>,
+    /// Times each subscriber fell behind the broadcast channel
+    pub metrics: metrics::BroadcastMetrics,
+    /// Source the market data currently comes from
+    pub feed: watch::Sender<FeedStatus>,
+    /// Latest candle-versus-trades check per symbol
+    pub candle_checks: consistency::CandleChecks,
 }
 
 impl AppState {
     pub fn new() -> Self {
-        let (tx, _) = broadcast::channel(1024);
-        Self { tx }
+        let capacity = metrics::broadcast_capacity();
+        let (tx, _) = broadcast::channel(capacity);
+        let symbols = api::SymbolDirectory::new(mock::symbol_infos());
+        let now = chrono::Utc::now().timestamp_millis();
+        let candles = api::CandleArchive::new(mock::seed_history(now, api::ARCHIVE_CANDLES));
+        let history = analytics::session_history(&symbols, &candles);
+        let sessions = analytics::SessionTracker::new(&history, now);
+        Self {
+            tx,
+            symbols,
+            candles,
+            trades: api::TradeArchive::default(),
+            snapshots: api::SnapshotCache::default(),
+            paper: Mutex::new(paper::PaperEngine::new()),
+            sessions: Mutex::new(sessions),
+            origins: origins::OriginPolicy::from_env(),
+            shutdown: watch::Sender::new(false),
+            metrics: metrics::BroadcastMetrics::new(capacity),
+            feed: watch::Sender::new(failover::feed_status(FeedSource::Primary, mock::SOURCE_NAME)),
+            candle_checks: consistency::CandleChecks::default(),
+        }
+    }
+}
+
+impl Default for AppState {
+    fn default() -> Self {
+        Self::new()
     }
 }
 
@@ -49,36 +111,75 @@ async fn main() {
     // Create shared state
     let state = Arc::new(AppState::new());
 
-    // Start mock data engine
-    let mock_tx = state.tx.clone();
-    tokio::spawn(async move {
-        mock::run_mock_engine(mock_tx).await;
-    });
+    // Start mock data engine as the primary source, supervised and relayed
+    // through failover
+    let (mock_tx, mock_rx) = mpsc::unbounded_channel();
+    let impairments = mock::MockImpairments::from_env();
+    let failover = failover::FailoverConfig::from_env();
+    let monitor = Arc::new(supervisor::SourceMonitor::new(mock::SOURCE_NAME));
+    let mock_state = state.clone();
+    tokio::spawn(supervisor::supervise(state.clone(), monitor.clone(), failover.stall, move || {
+        mock::run_mock_engine(mock_tx.clone(), impairments, mock_state.symbols.list())
+    }));
+    tokio::spawn(failover::run_failover(state.clone(), mock_rx, monitor, failover));
+    tokio::spawn(api::track_symbols(state.clone()));
+    tokio::spawn(api::track_candles(state.clone()));
+    tokio::spawn(api::track_trades(state.clone()));
+    tokio::spawn(api::track_snapshots(state.clone()));
+    tokio::spawn(paper::run_fills(state.clone()));
+    tokio::spawn(paper::run_equity(state.clone()));
+    tokio::spawn(ticker::run_tickers(state.clone()));
+    tokio::spawn(analytics::run_analytics(state.clone()));
+    tokio::spawn(consistency::run_candle_checks(state.clone()));
 
     // Build router
+    let cors = state.origins.cors_layer();
     let app = Router::new()
         // WebSocket endpoint
         .route("/ws", get(ws::ws_handler))
+        // REST API
+        .route("/api/symbols", get(api::symbols_handler))
+        .route("/api/candles", get(api::candles_handler))
+        .route("/api/trades", get(api::trades_handler))
+        .route("/api/depth", get(api::depth_handler))
+        .route("/api/journal", get(api::journal_handler))
+        .route("/api/equity", get(api::equity_handler))
+        .route("/api/schema", get(api::schema_handler))
+        .route("/api/stats", get(metrics::stats_handler))
+        .route("/api/diagnostics/candles", get(consistency::reports_handler))
+        .route("/api/diagnostics/candles/repair", post(consistency::repair_handler))
+        .route("/metrics", get(metrics::metrics_handler))
         // Health check
         .route("/health", get(|| async { "OK" }))
         // Static files (WASM frontend)
         .fallback_service(ServeDir::new("dist").append_index_html_on_directories(true))
         // Middleware
         .layer(TraceLayer::new_for_http())
-        .layer(
-            CorsLayer::new()
-                .allow_origin(Any)
-                .allow_methods(Any)
-                .allow_headers(Any),
-        )
-        .with_state(state);
+        .layer(cors)
+        .with_state(state.clone());
 
     // Bind and serve
     let addr = SocketAddr::from(([0, 0, 0, 0], 3001));
     tracing::info!("🚀 Server starting on http://{}", addr);
     tracing::info!("   WebSocket: ws://{}/ws", addr);
     tracing::info!("   Frontend:  http://{}", addr);
+    tracing::info!("   Origins:   {}", state.origins.describe());
+    tracing::info!("   Broadcast: {} messages", state.metrics.capacity());
+    tracing::info!("   Failover:  {}", failover.describe());
 
     let listener = tokio::net::TcpListener::bind(addr).await.unwrap();
-    axum::serve(listener, app).await.unwrap();
+    axum::serve(listener, app)
+        .with_graceful_shutdown(shutdown_signal(state))
+        .await
+        .unwrap();
+}
+
+/// Wait for Ctrl-C, then tell open sockets to close with a shutdown code
+async fn shutdown_signal(state: Arc<AppState>) {
+    if tokio::signal::ctrl_c().await.is_err() {
+        // No signal handler: run until killed
+        std::future::pending::<()>().await;
+    }
+    tracing::info!("Shutting down; closing client connections");
+    state.shutdown.send_replace(true);
 }
@@ -0,0 +1,254 @@
+//! Broadcast channel sizing and overflow counters
+//!
+//! A subscriber that falls more than the channel capacity behind misses
+//! messages (`Lagged`). Counting that per subscriber shows whether to grow
+//! the channel or conflate harder; the counts are served as JSON at
+//! `/api/stats` and in Prometheus text format at `/metrics`. Internal
+//! consumers are counted by name, WebSocket clients by connection.
+
+use std::collections::{BTreeMap, VecDeque};
+use std::fmt::Write;
+use std::sync::{Arc, Mutex};
+
+use axum::{
+    extract::State,
+    http::header,
+    response::{IntoResponse, Response},
+    Json,
+};
+use serde::Serialize;
+use tokio::sync::broadcast;
+
+use dash_core::WsMessage;
+
+use crate::AppState;
+
+/// Environment variable holding the broadcast channel capacity
+pub const BROADCAST_CAPACITY_ENV: &str = "DASH_BROADCAST_CAPACITY";
+
+/// Messages the broadcast channel holds when not configured
+pub const DEFAULT_BROADCAST_CAPACITY: usize = 1024;
+
+/// Lagged WebSocket connections listed in the stats, newest kept
+const RECENT_LAGGED_CLIENTS: usize = 64;
+
+/// Capacity from `DASH_BROADCAST_CAPACITY`, or the default when unset or
+/// not a positive number
+pub fn broadcast_capacity() -> usize {
+    capacity_from(std::env::var(BROADCAST_CAPACITY_ENV).ok().as_deref())
+}
+
+fn capacity_from(spec: Option<&str>) -> usize {
+    let Some(spec) = spec else {
+        return DEFAULT_BROADCAST_CAPACITY;
+    };
+    match spec.trim().parse::<usize>() {
+        Ok(capacity) if capacity > 0 => capacity,
+        _ => {
+            tracing::warn!("Ignoring {}={:?}; using {}", BROADCAST_CAPACITY_ENV, spec, DEFAULT_BROADCAST_CAPACITY);
+            DEFAULT_BROADCAST_CAPACITY
+        }
+    }
+}
+
+/// Times a subscriber fell behind, and the messages it missed doing so
+#[derive(Debug, Clone, Copy, Default, Serialize)]
+pub struct LagCounts {
+    pub events: u64,
+    pub missed: u64,
+}
+
+/// A WebSocket connection that fell behind (and was disconnected for it)
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct LaggedClient {
+    pub connection_id: String,
+    pub missed: u64,
+}
+
+/// Lag across all WebSocket connections, and the latest ones to lag
+#[derive(Debug, Default)]
+struct ClientLag {
+    total: LagCounts,
+    recent: VecDeque<LaggedClient>,
+}
+
+/// Lag counters for the broadcast channel
+pub struct BroadcastMetrics {
+    capacity: usize,
+    lagged: Mutex<BTreeMap<&'static str, LagCounts>>,
+    clients: Mutex<ClientLag>,
+}
+
+impl BroadcastMetrics {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            lagged: Mutex::new(BTreeMap::new()),
+            clients: Mutex::new(ClientLag::default()),
+        }
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Internal consumer `subscriber` fell `missed` messages behind
+    pub fn record_lag(&self, subscriber: &'static str, missed: u64) {
+        if let Ok(mut lagged) = self.lagged.lock() {
+            let counts = lagged.entry(subscriber).or_default();
+            counts.events += 1;
+            counts.missed += missed;
+        }
+    }
+
+    /// WebSocket connection `connection_id` fell `missed` messages behind
+    pub fn record_client_lag(&self, connection_id: &str, missed: u64) {
+        if let Ok(mut clients) = self.clients.lock() {
+            clients.total.events += 1;
+            clients.total.missed += missed;
+            if clients.recent.len() == RECENT_LAGGED_CLIENTS {
+                clients.recent.pop_front();
+            }
+            clients.recent.push_back(LaggedClient {
+                connection_id: connection_id.to_string(),
+                missed,
+            });
+        }
+    }
+
+    pub fn stats(&self, tx: &broadcast::Sender<WsMessage>) -> BroadcastStats {
+        let subscribers = self.lagged.lock().map(|l| l.clone()).unwrap_or_default();
+        let (clients, lagged_clients) = self
+            .clients
+            .lock()
+            .map(|c| (c.total, c.recent.iter().cloned().collect()))
+            .unwrap_or_default();
+        BroadcastStats {
+            capacity: self.capacity,
+            queued: tx.len(),
+            receivers: tx.receiver_count(),
+            lagged_events: subscribers.values().map(|c| c.events).sum::<u64>() + clients.events,
+            missed_messages: subscribers.values().map(|c| c.missed).sum::<u64>() + clients.missed,
+            subscribers,
+            clients,
+            lagged_clients,
+        }
+    }
+}
+
+/// Body of `GET /api/stats`
+#[derive(Debug, Clone, Serialize)]
+pub struct BroadcastStats {
+    pub capacity: usize,
+    /// Messages still waiting for the slowest receiver
+    pub queued: usize,
+    pub receivers: usize,
+    pub lagged_events: u64,
+    pub missed_messages: u64,
+    /// Counts per internal consumer (only those that ever lagged)
+    pub subscribers: BTreeMap<&'static str, LagCounts>,
+    /// Counts over all WebSocket clients; a client is dropped on its first
+    /// lag, so `events` is the number of clients that fell behind
+    pub clients: LagCounts,
+    /// The latest clients to lag, oldest first
+    pub lagged_clients: Vec<LaggedClient>,
+}
+
+impl BroadcastStats {
+    /// Prometheus text exposition
+    pub fn prometheus(&self) -> String {
+        let mut out = String::new();
+        let mut gauge = |name: &str, help: &str, value: usize| {
+            let _ = writeln!(out, "# HELP {name} {help}\n# TYPE {name} gauge\n{name} {value}");
+        };
+        gauge("dash_broadcast_capacity", "Broadcast channel capacity in messages", self.capacity);
+        gauge("dash_broadcast_queued", "Messages waiting for the slowest receiver", self.queued);
+        gauge("dash_broadcast_receivers", "Open broadcast receivers", self.receivers);
+
+        let mut counter = |name: &str, help: &str, value: fn(&LagCounts) -> u64| {
+            let _ = writeln!(out, "# HELP {name} {help}\n# TYPE {name} counter");
+            for (subscriber, counts) in &self.subscribers {
+                let _ = writeln!(out, "{name}{{subscriber=\"{subscriber}\"}} {}", value(counts));
+            }
+        };
+        counter("dash_broadcast_lagged_total", "Times a subscriber fell behind the channel", |c| c.events);
+        counter("dash_broadcast_missed_total", "Messages subscribers missed by falling behind", |c| c.missed);
+
+        // Per-connection labels would grow without bound, so clients are one series
+        let mut client_counter = |name: &str, help: &str, value: u64| {
+            let _ = writeln!(out, "# HELP {name} {help}\n# TYPE {name} counter\n{name} {value}");
+        };
+        client_counter("dash_broadcast_client_lagged_total", "WebSocket clients dropped for falling behind", self.clients.events);
+        client_counter("dash_broadcast_client_missed_total", "Messages WebSocket clients missed by falling behind", self.clients.missed);
+        out
+    }
+}
+
+/// `GET /api/stats`
+pub async fn stats_handler(State(state): State<Arc<AppState>>) -> Json<BroadcastStats> {
+    Json(state.metrics.stats(&state.tx))
+}
+
+/// `GET /metrics`
+pub async fn metrics_handler(State(state): State<Arc<AppState>>) -> Response {
+    let body = state.metrics.stats(&state.tx).prometheus();
+    ([(header::CONTENT_TYPE, "text/plain; version=0.0.4")], body).into_response()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_capacity_falls_back_to_the_default() {
+        assert_eq!(capacity_from(None), DEFAULT_BROADCAST_CAPACITY);
+        assert_eq!(capacity_from(Some(" 4096 ")), 4096);
+        for invalid in ["0", "", "-5", "lots", "1.5"] {
+            assert_eq!(capacity_from(Some(invalid)), DEFAULT_BROADCAST_CAPACITY, "{:?}", invalid);
+        }
+    }
+
+    #[test]
+    fn test_stats_and_prometheus() {
+        let (tx, _rx) = broadcast::channel::<WsMessage>(8);
+        let metrics = BroadcastMetrics::new(8);
+        metrics.record_lag("candles", 3);
+        metrics.record_lag("candles", 2);
+        metrics.record_client_lag("conn-a", 10);
+        metrics.record_client_lag("conn-b", 1);
+
+        let stats = metrics.stats(&tx);
+        assert_eq!((stats.capacity, stats.queued, stats.receivers), (8, 0, 1));
+        assert_eq!((stats.subscribers["candles"].events, stats.subscribers["candles"].missed), (2, 5));
+        assert_eq!((stats.clients.events, stats.clients.missed), (2, 11));
+        assert_eq!((stats.lagged_events, stats.missed_messages), (4, 16));
+        let ids: Vec<_> = stats.lagged_clients.iter().map(|c| (c.connection_id.as_str(), c.missed)).collect();
+        assert_eq!(ids, [("conn-a", 10), ("conn-b", 1)]);
+
+        let text = stats.prometheus();
+        for line in [
+            "dash_broadcast_capacity 8",
+            "dash_broadcast_receivers 1",
+            "dash_broadcast_lagged_total{subscriber=\"candles\"} 2",
+            "dash_broadcast_missed_total{subscriber=\"candles\"} 5",
+            "dash_broadcast_client_lagged_total 2",
+            "dash_broadcast_client_missed_total 11",
+            "# TYPE dash_broadcast_client_missed_total counter",
+        ] {
+            assert!(text.lines().any(|l| l == line), "missing {:?} in\n{}", line, text);
+        }
+    }
+
+    #[test]
+    fn test_lagged_clients_list_is_bounded() {
+        let (tx, _rx) = broadcast::channel::<WsMessage>(8);
+        let metrics = BroadcastMetrics::new(8);
+        for i in 0..RECENT_LAGGED_CLIENTS + 5 {
+            metrics.record_client_lag(&format!("conn-{}", i), 1);
+        }
+        let stats = metrics.stats(&tx);
+        assert_eq!(stats.clients.events, RECENT_LAGGED_CLIENTS as u64 + 5);
+        assert_eq!(stats.lagged_clients.len(), RECENT_LAGGED_CLIENTS);
+        assert_eq!(stats.lagged_clients[0].connection_id, "conn-5");
+    }
+}
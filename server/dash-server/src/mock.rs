@@ -1,38 +1,293 @@
 //! Mock data engine for demo/development
+//!
+//! Trades arrive in clusters (a Hawkes-style self-exciting intensity) and
+//! fill against a persistent book: levels deplete when hit and refill over
+//! time, the spread widens while prices jump, and the odd sweep clears
+//! several levels at once.
+//!
+//! For testing against imperfect feeds the engine can also skew its clock,
+//! hold messages back before delivery, go silent for part of every minute
+//! and stop altogether (see [`MockImpairments`]).
 
 use std::time::Duration;
 
-use chrono::Utc;
+use chrono::{DateTime, Utc};
 use rand::Rng;
-use tokio::sync::broadcast;
-use tokio::time::interval;
+use tokio::sync::mpsc;
+use tokio::time::{interval, sleep_until, Instant};
 
 use dash_core::{
-    Candle, CandleInterval, MarketDepth, OrderBookLevel, OrderBookSnapshot,
-    Price, Quantity, Symbol, Ticker, Trade, TradeSide, WsMessage,
+    next_funding_time, Bbo, Candle, CandleInterval, DerivativesTicker, Liquidation, MarketDepth,
+    OrderBookLevel, OrderBookSnapshot, Price, Quantity, Symbol, SymbolInfo, Trade, TradeSide, WsMessage,
 };
 
+/// Name the mock engine goes by as a feed source
+pub const SOURCE_NAME: &str = "mock";
+
+/// Markets simulated by the mock engine (symbol, starting price, tick size, lot size)
+pub const MOCK_MARKETS: &[(&str, f64, f64, f64)] = &[
+    ("BTC-USD", 95000.0, 0.01, 0.0001),
+    ("ETH-USD", 3400.0, 0.01, 0.001),
+    ("SOL-USD", 180.0, 0.001, 0.01),
+];
+
+/// Trading rules for every mock market
+pub fn symbol_infos() -> Vec<SymbolInfo> {
+    MOCK_MARKETS
+        .iter()
+        .map(|&(symbol, _, tick, lot)| SymbolInfo::new(Symbol::new(symbol), tick, lot))
+        .collect()
+}
+
+/// Synthetic closed 1m candles for the `count` minutes before `now`, ending
+/// at each market's starting price so the live feed picks up where they stop
+pub fn seed_history(now: i64, count: usize) -> Vec<(Symbol, Vec<Candle>)> {
+    let mut rng = rand::thread_rng();
+    let minute = CandleInterval::M1.as_millis();
+    let last_open = CandleInterval::M1.bucket_start(now) - minute;
+
+    MOCK_MARKETS
+        .iter()
+        .map(|&(symbol, start_price, _, _)| {
+            let symbol = Symbol::new(symbol);
+            // Walk backwards from the starting price, then flip to oldest-first
+            let mut close = start_price;
+            let mut candles: Vec<Candle> = (0..count as i64)
+                .map(|i| {
+                    let open = (close * (1.0 + (rng.r#gen::<f64>() - 0.5) * 0.004)).max(start_price * 0.01);
+                    let mut candle = Candle::new(symbol.clone(), CandleInterval::M1, last_open - i * minute, open);
+                    for _ in 0..rng.gen_range(3..12) {
+                        let price = open + (close - open) * rng.r#gen::<f64>();
+                        let wick = price * (rng.r#gen::<f64>() - 0.5) * 0.001;
+                        candle.update(price + wick, rng.r#gen::<f64>().exp() * 0.1);
+                    }
+                    candle.update(close, rng.r#gen::<f64>() * 0.1);
+                    candle.close_candle();
+                    close = open;
+                    candle
+                })
+                .collect();
+            candles.reverse();
+            (symbol, candles)
+        })
+        .collect()
+}
+
+/// Environment variables for the feed impairments
+pub const CLOCK_SKEW_ENV: &str = "DASH_MOCK_CLOCK_SKEW_MS";
+pub const LATENCY_ENV: &str = "DASH_MOCK_LATENCY_MS";
+pub const JITTER_ENV: &str = "DASH_MOCK_JITTER_MS";
+pub const OUTAGE_ENV: &str = "DASH_MOCK_OUTAGE_S";
+pub const CRASH_ENV: &str = "DASH_MOCK_CRASH_AFTER_S";
+
+/// Imperfections injected into the mock feed; all off by default
+#[derive(Debug, Clone, Copy, Default)]
+pub struct MockImpairments {
+    /// Added to every timestamp the engine stamps (negative: clock behind)
+    pub clock_skew_ms: i64,
+    /// Fixed hold before each message is broadcast
+    pub latency_ms: u64,
+    /// Extra random hold of up to this much on top of the latency
+    pub jitter_ms: u64,
+    /// Seconds at the end of every minute the feed sends nothing, to
+    /// exercise failover
+    pub outage_s: u64,
+    /// Seconds after which the engine stops as if it had crashed (0 =
+    /// never), to exercise the supervisor
+    pub crash_after_s: u64,
+}
+
+impl MockImpairments {
+    pub fn from_env() -> Self {
+        fn var<T: std::str::FromStr>(name: &str) -> Option<T> {
+            let value = std::env::var(name).ok()?;
+            let parsed = value.trim().parse().ok();
+            if parsed.is_none() {
+                tracing::warn!("Ignoring {}={:?}: not a whole number", name, value);
+            }
+            parsed
+        }
+        Self {
+            clock_skew_ms: var(CLOCK_SKEW_ENV).unwrap_or(0),
+            latency_ms: var(LATENCY_ENV).unwrap_or(0),
+            jitter_ms: var(JITTER_ENV).unwrap_or(0),
+            outage_s: var::<u64>(OUTAGE_ENV).unwrap_or(0).min(59),
+            crash_after_s: var(CRASH_ENV).unwrap_or(0),
+        }
+    }
+
+    fn delays(&self) -> bool {
+        self.latency_ms > 0 || self.jitter_ms > 0
+    }
+
+    /// Inside the silent part of the minute
+    fn in_outage(&self) -> bool {
+        self.outage_s > 0 && Utc::now().timestamp().rem_euclid(60) as u64 >= 60 - self.outage_s
+    }
+
+    pub fn describe(&self) -> Option<String> {
+        (self.clock_skew_ms != 0 || self.delays() || self.outage_s > 0 || self.crash_after_s > 0).then(|| {
+            format!(
+                "clock skew {:+}ms, latency {}ms ± {}ms jitter, silent {}s a minute, crash after {}s",
+                self.clock_skew_ms, self.latency_ms, self.jitter_ms, self.outage_s, self.crash_after_s
+            )
+        })
+    }
+
+    /// Wall-clock time as the skewed engine sees it
+    fn now(&self) -> DateTime<Utc> {
+        Utc::now() + chrono::Duration::milliseconds(self.clock_skew_ms)
+    }
+
+    fn now_ms(&self) -> i64 {
+        self.now().timestamp_millis()
+    }
+}
+
+/// Outlet for the engine's messages: straight to the failover relay, or
+/// through a delay line that keeps them in order.
+struct Feed {
+    tx: mpsc::UnboundedSender<WsMessage>,
+    delayed: Option<mpsc::UnboundedSender<(Instant, WsMessage)>>,
+    impairments: MockImpairments,
+    /// Release time of the last delayed message, so jitter can't reorder
+    last_due: Instant,
+}
+
+impl Feed {
+    fn new(tx: mpsc::UnboundedSender<WsMessage>, impairments: MockImpairments) -> Self {
+        let delayed = impairments.delays().then(|| {
+            let (delay_tx, mut delay_rx) = mpsc::unbounded_channel::<(Instant, WsMessage)>();
+            let tx = tx.clone();
+            tokio::spawn(async move {
+                while let Some((due, msg)) = delay_rx.recv().await {
+                    sleep_until(due).await;
+                    let _ = tx.send(msg);
+                }
+            });
+            delay_tx
+        });
+        Self {
+            tx,
+            delayed,
+            impairments,
+            last_due: Instant::now(),
+        }
+    }
+
+    fn send(&mut self, msg: WsMessage) {
+        if self.impairments.in_outage() {
+            return;
+        }
+        let Some(delayed) = &self.delayed else {
+            let _ = self.tx.send(msg);
+            return;
+        };
+        let jitter = match self.impairments.jitter_ms {
+            0 => 0,
+            max => rand::thread_rng().gen_range(0..=max),
+        };
+        let due = Instant::now() + Duration::from_millis(self.impairments.latency_ms + jitter);
+        self.last_due = due.max(self.last_due);
+        let _ = delayed.send((self.last_due, msg));
+    }
+}
+
+/// Trade loop period; arrivals are drawn per step from the current intensity
+const STEP_MS: u64 = 25;
+/// Levels kept per side of the book
+const BOOK_DEPTH: usize = 20;
+/// Baseline trade arrivals per second
+const BASE_INTENSITY: f64 = 6.0;
+/// Intensity added by each print; with [`INTENSITY_DECAY`] this sets the
+/// branching ratio (0.7), so bursts die out instead of running away
+const INTENSITY_JUMP: f64 = 1.4;
+/// Rate (per second) at which excitation decays back to the baseline
+const INTENSITY_DECAY: f64 = 2.0;
+/// Intensity ceiling, keeping the busiest burst within the broadcast buffer
+const MAX_INTENSITY: f64 = 200.0;
+/// Chance per step of an aggressive order sweeping several levels
+const SWEEP_ODDS: f64 = 0.0005;
+
+/// One resting price level of the simulated book
+struct MockLevel {
+    price: f64,
+    quantity: f64,
+    orders: u32,
+    /// Size the level refills toward after being traded into
+    target: f64,
+}
+
+impl MockLevel {
+    fn new(price: f64) -> Self {
+        let mut rng = rand::thread_rng();
+        let target = rng.r#gen::<f64>() * 2.0 + 0.1;
+        Self {
+            price,
+            quantity: target * rng.gen_range(0.3..1.0),
+            orders: rng.gen_range(1..10),
+            target,
+        }
+    }
+}
+
+/// Simulated market: a random-walk fair price with self-exciting (Hawkes)
+/// trade arrivals filled against a persistent book that depletes and refills
 struct MockMarket {
     symbol: Symbol,
+    /// Fair value the book is quoted around
     price: f64,
+    /// Price floor so the random walk can't collapse
+    floor: f64,
+    /// Typical gap between book levels
+    level_step: f64,
     volatility: f64,
     trend: f64,
+    /// Trade arrivals per second, excited by each print
+    intensity: f64,
+    /// Smoothed absolute return per print, widening the spread in bursts
+    realized_vol: f64,
+    /// Best first on both sides
+    bids: Vec<MockLevel>,
+    asks: Vec<MockLevel>,
+    /// Touch last sent on the BBO channel
+    last_bbo: Option<Bbo>,
+    /// Source of the (possibly skewed) timestamps
+    clock: MockImpairments,
     sequence: u64,
     candle_open_time: i64,
     current_candle: Option<Candle>,
+    /// Perpetual premium over the index as a fraction
+    basis: f64,
+    open_interest: f64,
+    /// Open interest at startup, standing in for the value 24h ago
+    open_interest_base: f64,
 }
 
 impl MockMarket {
-    fn new(symbol: Symbol, initial_price: f64) -> Self {
-        Self {
+    fn new(symbol: Symbol, initial_price: f64, clock: MockImpairments) -> Self {
+        let mut market = Self {
             symbol,
             price: initial_price,
+            floor: initial_price * 0.01,
+            level_step: initial_price * 0.00001,
             volatility: 0.0005,
             trend: 0.0,
+            intensity: BASE_INTENSITY,
+            realized_vol: 0.0,
+            bids: Vec::with_capacity(BOOK_DEPTH),
+            asks: Vec::with_capacity(BOOK_DEPTH),
+            last_bbo: None,
+            clock,
             sequence: 0,
             candle_open_time: 0,
             current_candle: None,
-        }
+            basis: 0.0002,
+            open_interest: 2_000_000.0 / initial_price.sqrt(),
+            open_interest_base: 2_000_000.0 / initial_price.sqrt(),
+        };
+        market.requote();
+        market
     }
 
     fn tick(&mut self) -> f64 {
@@ -44,86 +299,207 @@ impl MockMarket {
             self.trend = (rng.r#gen::<f64>() - 0.5) * 2.0;
         }
 
-        self.price *= 1.0 + drift + random;
-        self.price = self.price.max(1000.0);
+        self.move_price(self.price * (1.0 + drift + random));
         self.price
     }
 
-    fn generate_trade(&mut self) -> Trade {
-        let mut rng = rand::thread_rng();
-        let price = self.tick();
-        let side = if rng.r#gen::<bool>() { TradeSide::Buy } else { TradeSide::Sell };
-        let base_qty = rng.r#gen::<f64>().exp() * 0.1;
-        let quantity = base_qty.min(10.0);
-        Trade::new(self.symbol.clone(), price, quantity, side)
+    fn move_price(&mut self, price: f64) {
+        let price = price.max(self.floor);
+        let change = (price / self.price - 1.0).abs();
+        self.realized_vol = self.realized_vol * 0.95 + change * 0.05;
+        self.price = price;
     }
 
-    fn generate_orderbook(&mut self) -> OrderBookSnapshot {
+    /// Half the quoted spread: 1bp at rest, wider while prices are jumping
+    fn half_spread(&self) -> f64 {
+        let stress = self.realized_vol / (self.volatility * 0.5);
+        self.price * 0.0001 * (0.5 + stress).clamp(1.0, 8.0)
+    }
+
+    /// Re-centre the book on the fair price: quotes it crossed are pulled,
+    /// the touch follows it in and the back is topped up to full depth
+    fn requote(&mut self) {
         let mut rng = rand::thread_rng();
-        self.sequence += 1;
+        let half = self.half_spread();
+        let step = self.level_step;
+        let (bid_cap, ask_floor) = (self.price - half, self.price + half);
+
+        self.bids.retain(|l| l.price <= bid_cap);
+        self.asks.retain(|l| l.price >= ask_floor);
+        if self.bids.first().is_none_or(|l| bid_cap - l.price > step * 3.0) {
+            self.bids.insert(0, MockLevel::new(bid_cap));
+        }
+        if self.asks.first().is_none_or(|l| l.price - ask_floor > step * 3.0) {
+            self.asks.insert(0, MockLevel::new(ask_floor));
+        }
+        while self.bids.len() < BOOK_DEPTH {
+            let last = self.bids.last().map_or(bid_cap, |l| l.price);
+            self.bids.push(MockLevel::new(last - (rng.r#gen::<f64>() * 5.0 + 1.0) * step));
+        }
+        while self.asks.len() < BOOK_DEPTH {
+            let last = self.asks.last().map_or(ask_floor, |l| l.price);
+            self.asks.push(MockLevel::new(last + (rng.r#gen::<f64>() * 5.0 + 1.0) * step));
+        }
+        self.bids.truncate(BOOK_DEPTH);
+        self.asks.truncate(BOOK_DEPTH);
+    }
 
-        let mid = self.price;
-        let spread = mid * 0.0002;
+    /// Fill an aggressive order against the book, one print per level taken
+    fn execute(&mut self, side: TradeSide, mut quantity: f64) -> Vec<Trade> {
+        let book = match side {
+            TradeSide::Buy => &mut self.asks,
+            TradeSide::Sell => &mut self.bids,
+        };
+        let mut prints = Vec::new();
+        while quantity > 1e-9 {
+            let Some(level) = book.first_mut() else { break };
+            let fill = quantity.min(level.quantity);
+            let mut trade = Trade::new(self.symbol.clone(), level.price, fill, side);
+            trade.timestamp = self.clock.now();
+            prints.push(trade);
+            level.quantity -= fill;
+            quantity -= fill;
+            if level.quantity <= 1e-9 {
+                book.remove(0);
+            }
+        }
+        // Taking out levels drags the fair price to where the order stopped
+        if let Some(last) = prints.last().map(|t| t.price.as_f64()) {
+            let half = self.half_spread();
+            let impact = match side {
+                TradeSide::Buy => (last + half).max(self.price),
+                TradeSide::Sell => (last - half).min(self.price),
+            };
+            self.move_price(impact);
+        }
+        self.intensity = (self.intensity + INTENSITY_JUMP * prints.len() as f64).min(MAX_INTENSITY);
+        prints
+    }
 
-        let mut bids = Vec::with_capacity(20);
-        let mut asks = Vec::with_capacity(20);
+    /// Size that clears the first few levels on one side and then some
+    fn sweep_size(&self, side: TradeSide) -> f64 {
+        let mut rng = rand::thread_rng();
+        let book = match side {
+            TradeSide::Buy => &self.asks,
+            TradeSide::Sell => &self.bids,
+        };
+        let levels = rng.gen_range(3..9);
+        book.iter().take(levels).map(|l| l.quantity).sum::<f64>() * 1.05
+    }
 
-        let mut bid_price = mid - spread / 2.0;
-        for _ in 0..20 {
-            let qty = rng.r#gen::<f64>() * 2.0 + 0.1;
-            let orders = rng.gen_range(1..10);
-            bids.push(OrderBookLevel::new(bid_price, qty, orders));
-            bid_price -= rng.r#gen::<f64>() * 5.0 + 1.0;
+    /// Prints for one step: a Poisson count drawn from the decaying
+    /// intensity, and now and then a sweep
+    fn step(&mut self, dt: f64) -> Vec<Trade> {
+        let mut rng = rand::thread_rng();
+        self.intensity = BASE_INTENSITY + (self.intensity - BASE_INTENSITY) * (-INTENSITY_DECAY * dt).exp();
+
+        let mut prints = Vec::new();
+        if rng.r#gen::<f64>() < SWEEP_ODDS {
+            let side = if rng.r#gen::<f64>() < 0.5 + self.trend * 0.2 { TradeSide::Buy } else { TradeSide::Sell };
+            let size = self.sweep_size(side);
+            prints.extend(self.execute(side, size));
+            self.requote();
+        }
+        for _ in 0..poisson(self.intensity * dt) {
+            self.tick();
+            self.requote();
+            // Flow leans with the trend so runs of one side cluster too
+            let side = if rng.r#gen::<f64>() < 0.5 + self.trend * 0.1 { TradeSide::Buy } else { TradeSide::Sell };
+            let quantity = (rng.r#gen::<f64>().exp() * 0.1).min(10.0);
+            prints.extend(self.execute(side, quantity));
         }
+        self.requote();
+        prints
+    }
 
-        let mut ask_price = mid + spread / 2.0;
-        for _ in 0..20 {
-            let qty = rng.r#gen::<f64>() * 2.0 + 0.1;
-            let orders = rng.gen_range(1..10);
-            asks.push(OrderBookLevel::new(ask_price, qty, orders));
-            ask_price += rng.r#gen::<f64>() * 5.0 + 1.0;
+    /// Levels refill toward their resting size, and some quotes get pulled
+    /// or replaced
+    fn refill(&mut self) {
+        let mut rng = rand::thread_rng();
+        for level in self.bids.iter_mut().chain(self.asks.iter_mut()) {
+            level.quantity += (level.target - level.quantity) * 0.2;
+            if rng.r#gen::<f64>() < 0.05 {
+                *level = MockLevel::new(level.price);
+            }
         }
+    }
 
+    fn generate_orderbook(&mut self) -> OrderBookSnapshot {
+        self.sequence += 1;
+        self.refill();
+        self.requote();
+
+        let level = |l: &MockLevel| OrderBookLevel::new(l.price, l.quantity, l.orders);
         OrderBookSnapshot {
             symbol: self.symbol.clone(),
-            bids,
-            asks,
-            timestamp: Utc::now().timestamp_millis(),
+            bids: self.bids.iter().map(level).collect(),
+            asks: self.asks.iter().map(level).collect(),
+            timestamp: self.clock.now_ms(),
             sequence: self.sequence,
         }
     }
 
-    fn generate_ticker(&self) -> Ticker {
+    /// Current touch, if it moved since the last one sent
+    fn bbo_update(&mut self) -> Option<Bbo> {
+        let (bid, ask) = (self.bids.first()?, self.asks.first()?);
+        let bbo = Bbo {
+            symbol: self.symbol.clone(),
+            bid: Price::new(bid.price),
+            bid_size: Quantity::new(bid.quantity),
+            ask: Price::new(ask.price),
+            ask_size: Quantity::new(ask.quantity),
+            timestamp: self.clock.now_ms(),
+        };
+        if self.last_bbo.as_ref().is_some_and(|last| last.same_touch(&bbo)) {
+            return None;
+        }
+        self.last_bbo = Some(bbo.clone());
+        Some(bbo)
+    }
+
+    /// Occasional forced close, skewed against the prevailing trend
+    fn maybe_liquidation(&self) -> Option<Liquidation> {
         let mut rng = rand::thread_rng();
+        // Odds per step, keeping the old rate of one in fifty per 100ms
+        if rng.r#gen::<f64>() >= 0.005 {
+            return None;
+        }
+        // A falling market mostly liquidates longs (forced sells)
+        let long_odds = 0.5 - self.trend * 0.3;
+        let side = if rng.r#gen::<f64>() < long_odds { TradeSide::Sell } else { TradeSide::Buy };
+        let notional = (rng.r#gen::<f64>() * 4.0).exp() * 2_000.0;
+        let mut liquidation = Liquidation::new(self.symbol.clone(), self.price, notional / self.price, side);
+        liquidation.timestamp = self.clock.now();
+        Some(liquidation)
+    }
 
-        let open = self.price * (1.0 - rng.r#gen::<f64>() * 0.02);
-        let high = self.price * (1.0 + rng.r#gen::<f64>() * 0.03);
-        let low = self.price * (1.0 - rng.r#gen::<f64>() * 0.03);
+    fn generate_derivatives(&mut self) -> DerivativesTicker {
+        let mut rng = rand::thread_rng();
 
-        let change = self.price - open;
-        let change_pct = change / open * 100.0;
+        // Premium mean-reverts toward zero and leans with the trend
+        self.basis = self.basis * 0.95 + self.trend * 0.00005 + (rng.r#gen::<f64>() - 0.5) * 0.0001;
+        self.open_interest *= 1.0 + (rng.r#gen::<f64>() - 0.48) * 0.002;
 
-        Ticker {
+        let now = self.clock.now_ms();
+        let index = self.price;
+        let mark = index * (1.0 + self.basis);
+
+        DerivativesTicker {
             symbol: self.symbol.clone(),
-            last_price: Price::new(self.price),
-            bid_price: Price::new(self.price * 0.9999),
-            bid_qty: Quantity::new(rng.r#gen::<f64>() * 5.0),
-            ask_price: Price::new(self.price * 1.0001),
-            ask_qty: Quantity::new(rng.r#gen::<f64>() * 5.0),
-            high_24h: Price::new(high),
-            low_24h: Price::new(low),
-            volume_24h: Quantity::new(rng.r#gen::<f64>() * 10000.0 + 1000.0),
-            quote_volume_24h: rng.r#gen::<f64>() * 500_000_000.0,
-            change_24h: change,
-            change_percent_24h: change_pct,
-            open_24h: Price::new(open),
-            trade_count_24h: rng.gen_range(10000..100000),
-            timestamp: Utc::now().timestamp_millis(),
+            mark_price: Price::new(mark),
+            index_price: Price::new(index),
+            // Funding follows the premium, clamped like most venues
+            funding_rate: (self.basis / 3.0).clamp(-0.0075, 0.0075),
+            next_funding_time: next_funding_time(now),
+            open_interest: Quantity::new(self.open_interest),
+            open_interest_change_24h: self.open_interest - self.open_interest_base,
+            timestamp: now,
         }
     }
 
     fn update_candle(&mut self, trade: &Trade) -> Option<Candle> {
-        let now = Utc::now().timestamp_millis();
+        // Bucket by the print's own time so bars rebuilt from trades agree
+        let now = trade.timestamp.timestamp_millis();
         let interval_ms = CandleInterval::M1.as_millis();
         let candle_time = (now / interval_ms) * interval_ms;
 
@@ -137,12 +513,9 @@ impl MockMarket {
             });
 
             self.candle_open_time = candle_time;
-            self.current_candle = Some(Candle::new(
-                self.symbol.clone(),
-                CandleInterval::M1,
-                candle_time,
-                price,
-            ));
+            let mut candle = Candle::new(self.symbol.clone(), CandleInterval::M1, candle_time, price);
+            candle.update(price, qty);
+            self.current_candle = Some(candle);
 
             prev
         } else {
@@ -154,50 +527,106 @@ impl MockMarket {
     }
 }
 
-pub async fn run_mock_engine(tx: broadcast::Sender<WsMessage>) {
-    tracing::info!("Starting mock data engine");
-
-    let mut market = MockMarket::new(Symbol::new("BTC-USD"), 95000.0);
+/// Knuth's method; fine for the small means drawn per step
+fn poisson(mean: f64) -> usize {
+    let mut rng = rand::thread_rng();
+    let limit = (-mean).exp();
+    let mut product = rng.r#gen::<f64>();
+    let mut count = 0;
+    while product > limit {
+        count += 1;
+        product *= rng.r#gen::<f64>();
+    }
+    count
+}
 
-    let mut trade_interval = interval(Duration::from_millis(100));
+/// Run the engine, picking prices up from `resume` (the last known
+/// `last_price` per symbol) when restarted
+pub async fn run_mock_engine(tx: mpsc::UnboundedSender<WsMessage>, impairments: MockImpairments, resume: Vec<SymbolInfo>) {
+    tracing::info!("Starting mock data engine ({} markets)", MOCK_MARKETS.len());
+    if let Some(description) = impairments.describe() {
+        tracing::warn!("Mock feed impaired: {}", description);
+    }
+    let mut feed = Feed::new(tx, impairments);
+
+    let mut markets: Vec<MockMarket> = MOCK_MARKETS
+        .iter()
+        .map(|&(symbol, price, _, _)| {
+            let symbol = Symbol::new(symbol);
+            let price = resume
+                .iter()
+                .find(|info| info.symbol == symbol && info.last_price > 0.0)
+                .map_or(price, |info| info.last_price);
+            MockMarket::new(symbol, price, impairments)
+        })
+        .collect();
+
+    let crash = (impairments.crash_after_s > 0).then(|| Instant::now() + Duration::from_secs(impairments.crash_after_s));
+
+    let mut trade_interval = interval(Duration::from_millis(STEP_MS));
     let mut book_interval = interval(Duration::from_millis(250));
-    let mut ticker_interval = interval(Duration::from_secs(1));
+    let mut stats_interval = interval(Duration::from_secs(1));
     let mut heartbeat_interval = interval(Duration::from_secs(30));
+    let dt = STEP_MS as f64 / 1_000.0;
 
     loop {
         tokio::select! {
+            _ = sleep_until(crash.unwrap_or_else(Instant::now)), if crash.is_some() => {
+                tracing::warn!("Mock engine stopping (simulated crash)");
+                return;
+            }
             _ = trade_interval.tick() => {
-                let trade = market.generate_trade();
-
-                if let Some(closed_candle) = market.update_candle(&trade) {
-                    let _ = tx.send(WsMessage::Candle(closed_candle));
+                for market in &mut markets {
+                    let prints = market.step(dt);
+                    if !prints.is_empty() {
+                        for trade in prints {
+                            // Trade first: clients fold it into their forming bar, then the
+                            // candle snapshot (which already includes it) replaces that bar
+                            let closed_candle = market.update_candle(&trade);
+                            feed.send(WsMessage::Trade(trade));
+                            if let Some(closed_candle) = closed_candle {
+                                feed.send(WsMessage::Candle(closed_candle));
+                            }
+                        }
+
+                        if let Some(liquidation) = market.maybe_liquidation() {
+                            feed.send(WsMessage::Liquidation(liquidation));
+                        }
+
+                        if let Some(ref candle) = market.current_candle {
+                            feed.send(WsMessage::Candle(candle.clone()));
+                        }
+                    }
+
+                    // The touch goes out every step it moves; the full book
+                    // only at the book interval
+                    if let Some(bbo) = market.bbo_update() {
+                        feed.send(WsMessage::Bbo(bbo));
+                    }
                 }
-
-                if let Some(ref candle) = market.current_candle {
-                    let _ = tx.send(WsMessage::Candle(candle.clone()));
-                }
-
-                let _ = tx.send(WsMessage::Trade(trade));
             }
 
             _ = book_interval.tick() => {
-                let book = market.generate_orderbook();
-                let depth = MarketDepth::from_orderbook(&book);
+                for market in &mut markets {
+                    let book = market.generate_orderbook();
+                    let depth = MarketDepth::from_orderbook(&book);
 
-                let _ = tx.send(WsMessage::OrderBook(book));
-                let _ = tx.send(WsMessage::Depth(depth));
+                    feed.send(WsMessage::OrderBook(book));
+                    feed.send(WsMessage::Depth(depth));
+                }
             }
 
-            _ = ticker_interval.tick() => {
-                let ticker = market.generate_ticker();
-                let _ = tx.send(WsMessage::Ticker(ticker));
+            _ = stats_interval.tick() => {
+                for market in &mut markets {
+                    feed.send(WsMessage::Derivatives(market.generate_derivatives()));
+                }
             }
 
             _ = heartbeat_interval.tick() => {
-                let _ = tx.send(WsMessage::Heartbeat {
-                    timestamp: Utc::now().timestamp_millis(),
+                feed.send(WsMessage::Heartbeat {
+                    timestamp: impairments.now_ms(),
                 });
             }
         }
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,98 @@
+//! Browser origins allowed to call the REST API and open the WebSocket
+//!
+//! Read from `DASH_ALLOWED_ORIGINS` (comma-separated, `*` for any); unset,
+//! only the dev frontend and the bundled one served by this server are let in.
+
+use axum::http::HeaderValue;
+use tower_http::cors::{AllowOrigin, Any, CorsLayer};
+
+/// Environment variable holding the allow-list
+pub const ALLOWED_ORIGINS_ENV: &str = "DASH_ALLOWED_ORIGINS";
+
+/// Trunk dev server and the frontend served from `dist`
+pub const DEFAULT_ORIGINS: &[&str] = &[
+    "http://127.0.0.1:8080",
+    "http://localhost:8080",
+    "http://127.0.0.1:3001",
+    "http://localhost:3001",
+];
+
+/// Which `Origin` headers are accepted
+#[derive(Debug, Clone)]
+pub enum OriginPolicy {
+    Any,
+    List(Vec<String>),
+}
+
+impl OriginPolicy {
+    pub fn from_env() -> Self {
+        match std::env::var(ALLOWED_ORIGINS_ENV) {
+            Ok(spec) if !spec.trim().is_empty() => Self::parse(&spec),
+            _ => Self::List(DEFAULT_ORIGINS.iter().map(|o| o.to_string()).collect()),
+        }
+    }
+
+    /// `*` or comma-separated origins (trailing slashes ignored)
+    pub fn parse(spec: &str) -> Self {
+        let origins: Vec<String> = spec
+            .split(',')
+            .map(|o| o.trim().trim_end_matches('/').to_string())
+            .filter(|o| !o.is_empty())
+            .collect();
+        if origins.iter().any(|o| o == "*") {
+            Self::Any
+        } else {
+            Self::List(origins)
+        }
+    }
+
+    /// Requests without an `Origin` come from non-browser clients, which
+    /// CORS doesn't govern, so they pass
+    pub fn allows(&self, origin: Option<&str>) -> bool {
+        match (self, origin) {
+            (Self::Any, _) | (_, None) => true,
+            (Self::List(origins), Some(origin)) => origins.iter().any(|o| o == origin),
+        }
+    }
+
+    pub fn cors_layer(&self) -> CorsLayer {
+        let origin = match self {
+            Self::Any => AllowOrigin::any(),
+            Self::List(origins) => {
+                AllowOrigin::list(origins.iter().filter_map(|o| HeaderValue::from_str(o).ok()))
+            }
+        };
+        CorsLayer::new().allow_origin(origin).allow_methods(Any).allow_headers(Any)
+    }
+
+    pub fn describe(&self) -> String {
+        match self {
+            Self::Any => "any origin".to_string(),
+            Self::List(origins) => origins.join(", "),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_and_allow() {
+        let any = OriginPolicy::parse("https://a.example, *");
+        assert!(matches!(any, OriginPolicy::Any));
+        assert!(any.allows(Some("https://anywhere.example")));
+
+        let list = OriginPolicy::parse(" https://a.example/ ,,http://localhost:8080");
+        assert!(list.allows(Some("https://a.example")));
+        assert!(list.allows(Some("http://localhost:8080")));
+        assert!(!list.allows(Some("https://a.example.evil")));
+        assert!(!list.allows(Some("http://localhost:3001")));
+        assert_eq!(list.describe(), "https://a.example, http://localhost:8080");
+
+        // Non-browser clients send no Origin
+        assert!(list.allows(None));
+        assert!(OriginPolicy::parse("").allows(None));
+        assert!(!OriginPolicy::parse("").allows(Some("https://a.example")));
+    }
+}
@@ -0,0 +1,565 @@
+//! Paper-trading engine: fills demo orders against the mock market feed
+//!
+//! Every fill pays a taker fee and goes into a trade journal, and the
+//! account's equity is sampled every few seconds; both are kept in memory
+//! while the server runs and served at `/api/journal` and `/api/equity`.
+
+use std::collections::{HashMap, VecDeque};
+use std::sync::Arc;
+use std::time::Duration;
+
+use chrono::Utc;
+use tokio::sync::broadcast;
+use tokio::time::interval;
+
+use dash_core::{
+    AccountSummary, Balance, EquitySample, JournalEntry, OrderRejection, OrderRequest, OrderStatus, OrderType, PaperOrder,
+    PortfolioSnapshot, Position, Price, Quantity, Symbol, SymbolInfo, Trade, TradeSide, WsMessage,
+};
+
+use crate::AppState;
+
+/// Quote asset of the paper account
+pub const QUOTE_ASSET: &str = "USD";
+/// Cash every new paper account starts with
+pub const STARTING_BALANCE: f64 = 100_000.0;
+/// Fee on every fill as a fraction of its notional (10 bps)
+pub const FEE_RATE: f64 = 0.001;
+const DAY_MS: i64 = 24 * 60 * 60 * 1_000;
+
+/// How often the account's equity is sampled
+pub const EQUITY_EVERY: Duration = Duration::from_secs(10);
+/// Equity samples kept (a day at the sampling interval)
+pub const EQUITY_HISTORY: usize = 24 * 60 * 6;
+/// Samples sent with the portfolio snapshot on connect
+const SNAPSHOT_EQUITY: usize = 2_000;
+/// Fills kept in the journal
+pub const JOURNAL_LIMIT: usize = 10_000;
+
+/// Single shared demo account
+pub struct PaperEngine {
+    /// Free quote balance
+    cash: f64,
+    /// Quote reserved by resting orders, by order id
+    reserved: HashMap<String, f64>,
+    positions: HashMap<Symbol, Position>,
+    /// Resting limit/stop orders
+    open_orders: Vec<PaperOrder>,
+    last_prices: HashMap<Symbol, f64>,
+    /// Fills, oldest first
+    journal: VecDeque<JournalEntry>,
+    /// Equity samples, oldest first
+    equity: VecDeque<EquitySample>,
+    /// UTC day the realized PnL counter covers (ms at midnight)
+    day_start: i64,
+    realized_today: f64,
+    fees_paid: f64,
+}
+
+impl PaperEngine {
+    pub fn new() -> Self {
+        Self {
+            cash: STARTING_BALANCE,
+            reserved: HashMap::new(),
+            positions: HashMap::new(),
+            open_orders: Vec::new(),
+            last_prices: HashMap::new(),
+            journal: VecDeque::new(),
+            equity: VecDeque::new(),
+            day_start: 0,
+            realized_today: 0.0,
+            fees_paid: 0.0,
+        }
+    }
+
+    /// Full account state (sent to clients on connect)
+    pub fn snapshot(&self) -> PortfolioSnapshot {
+        let mut balances = vec![self.balance()];
+        balances.extend(self.base_assets().into_iter().filter_map(|asset| self.asset_balance(&asset)));
+        PortfolioSnapshot {
+            balances,
+            positions: self.positions.values().cloned().collect(),
+            orders: self.open_orders.clone(),
+            equity: self.equity.range(self.equity.len().saturating_sub(SNAPSHOT_EQUITY)..).copied().collect(),
+        }
+    }
+
+    /// Cash (free and reserved) plus positions at the last trade price
+    pub fn equity(&self) -> f64 {
+        let cash = self.cash + self.reserved.values().sum::<f64>();
+        let holdings: f64 = self
+            .positions
+            .values()
+            .map(|p| p.quantity * self.last_prices.get(&p.symbol).copied().unwrap_or(p.mark_price.as_f64()))
+            .sum();
+        cash + holdings
+    }
+
+    /// Record the current equity in the history
+    pub fn sample_equity(&mut self, timestamp: i64) -> EquitySample {
+        let sample = EquitySample {
+            timestamp,
+            equity: self.equity(),
+        };
+        self.equity.push_back(sample);
+        if self.equity.len() > EQUITY_HISTORY {
+            self.equity.pop_front();
+        }
+        sample
+    }
+
+    /// Up to `limit` equity samples at or after `from` (ms), oldest first:
+    /// the earliest ones when `from` is given, otherwise the latest
+    pub fn equity_history(&self, from: Option<i64>, limit: usize) -> Vec<EquitySample> {
+        let start = from.map_or(self.equity.len().saturating_sub(limit), |from| {
+            self.equity.partition_point(|s| s.timestamp < from)
+        });
+        self.equity.range(start..).take(limit).copied().collect()
+    }
+
+    /// Up to `limit` fills with `from <= time < to` (ms), optionally for one
+    /// symbol, oldest first: the earliest ones when `from` is given,
+    /// otherwise the latest
+    pub fn journal(&self, symbol: Option<&Symbol>, from: Option<i64>, to: Option<i64>, limit: usize) -> Vec<JournalEntry> {
+        let matching = self.journal.iter().filter(|e| {
+            symbol.is_none_or(|s| e.symbol == *s)
+                && from.is_none_or(|from| e.timestamp >= from)
+                && to.is_none_or(|to| e.timestamp < to)
+        });
+        match from {
+            Some(_) => matching.take(limit).cloned().collect(),
+            None => {
+                let mut latest: Vec<JournalEntry> = matching.rev().take(limit).cloned().collect();
+                latest.reverse();
+                latest
+            }
+        }
+    }
+
+    fn balance(&self) -> Balance {
+        Balance {
+            asset: QUOTE_ASSET.to_string(),
+            free: self.cash,
+            locked: self.reserved.values().sum(),
+        }
+    }
+
+    fn base_assets(&self) -> Vec<String> {
+        let mut assets: Vec<String> = self.positions.keys().map(|s| s.base().to_string()).collect();
+        assets.sort();
+        assets.dedup();
+        assets
+    }
+
+    /// Net holding of a base asset across its positions (negative when short)
+    fn asset_balance(&self, asset: &str) -> Option<Balance> {
+        let held: Vec<&Position> = self.positions.values().filter(|p| p.symbol.base() == asset).collect();
+        (!held.is_empty()).then(|| Balance {
+            asset: asset.to_string(),
+            free: held.iter().map(|p| p.quantity).sum(),
+            locked: 0.0,
+        })
+    }
+
+    /// Start a new day for the realized PnL counter once UTC midnight passes
+    fn roll_day(&mut self, now: i64) {
+        let day_start = now - now.rem_euclid(DAY_MS);
+        if day_start != self.day_start {
+            self.day_start = day_start;
+            self.realized_today = 0.0;
+        }
+    }
+
+    /// Equity, today's realized PnL and fees paid as of `now`
+    pub fn account_summary(&mut self, now: i64) -> AccountSummary {
+        self.roll_day(now);
+        AccountSummary {
+            equity: self.equity(),
+            realized_pnl_today: self.realized_today,
+            fees_paid: self.fees_paid,
+            day_start: self.day_start,
+            timestamp: now,
+        }
+    }
+
+    /// Validate and accept an order. `Err` carries the rejection for the
+    /// submitting client; `Ok` carries updates for every client.
+    pub fn place(
+        &mut self,
+        request: OrderRequest,
+        info: Option<SymbolInfo>,
+    ) -> Result<Vec<WsMessage>, OrderRejection> {
+        let info = info.ok_or(OrderRejection::UnknownSymbol)?;
+        request.validate(&info)?;
+
+        let last = self
+            .last_prices
+            .get(&request.symbol)
+            .copied()
+            .ok_or_else(|| OrderRejection::Engine {
+                message: "No market price yet".to_string(),
+            })?;
+        // The fee comes out of the same cash, whatever the order type
+        let reserve = with_fee(request.notional(last));
+        if reserve > self.cash {
+            return Err(OrderRejection::InsufficientBalance {
+                required: reserve,
+                available: self.cash,
+            });
+        }
+
+        let mut order = PaperOrder {
+            id: request.client_id.clone(),
+            symbol: request.symbol.clone(),
+            side: request.side,
+            order_type: request.order_type,
+            price: request.price.map(Price::new),
+            stop_price: request.stop_price.map(Price::new),
+            quantity: Quantity::new(request.quantity),
+            filled: Quantity::ZERO,
+            status: OrderStatus::Open,
+            created_at: Utc::now().timestamp_millis(),
+            fill_price: None,
+            filled_at: None,
+        };
+
+        if let Some(price) = trigger_price(&order, last) {
+            return Ok(self.fill(&mut order, price));
+        }
+
+        // Rest on the book with its notional and fee reserved
+        self.cash -= reserve;
+        self.reserved.insert(order.id.clone(), reserve);
+        self.open_orders.push(order.clone());
+
+        Ok(vec![
+            WsMessage::OrderUpdate(order),
+            WsMessage::Balance(self.balance()),
+        ])
+    }
+
+    /// Cancel a resting order (unknown ids are ignored)
+    pub fn cancel(&mut self, order_id: &str) -> Vec<WsMessage> {
+        let Some(index) = self.open_orders.iter().position(|o| o.id == order_id) else {
+            return Vec::new();
+        };
+        let mut order = self.open_orders.remove(index);
+        self.release(&order.id);
+        order.status = OrderStatus::Cancelled;
+
+        vec![
+            WsMessage::OrderUpdate(order),
+            WsMessage::Balance(self.balance()),
+        ]
+    }
+
+    /// Symbol of a resting order
+    pub fn symbol_of(&self, order_id: &str) -> Option<Symbol> {
+        self.open_orders.iter().find(|o| o.id == order_id).map(|o| o.symbol.clone())
+    }
+
+    /// Move a resting order to `price`, re-reserving its notional; an order
+    /// moved through the market fills straight away (unknown ids are ignored)
+    pub fn amend(
+        &mut self,
+        order_id: &str,
+        price: f64,
+        info: Option<SymbolInfo>,
+    ) -> Result<Vec<WsMessage>, OrderRejection> {
+        let Some(index) = self.open_orders.iter().position(|o| o.id == order_id) else {
+            return Ok(Vec::new());
+        };
+        let info = info.ok_or(OrderRejection::UnknownSymbol)?;
+        if !price.is_finite() || price <= 0.0 {
+            return Err(OrderRejection::MissingPrice);
+        }
+        if !info.is_valid_price(price) {
+            return Err(OrderRejection::OffTickSize { tick_size: info.tick_size });
+        }
+
+        let held = self.reserved.get(order_id).copied().unwrap_or(0.0);
+        let reserve = with_fee(self.open_orders[index].remaining() * price);
+        if reserve > self.cash + held {
+            return Err(OrderRejection::InsufficientBalance {
+                required: reserve,
+                available: self.cash + held,
+            });
+        }
+
+        let mut order = self.open_orders.remove(index);
+        match order.order_type {
+            OrderType::Stop => order.stop_price = Some(Price::new(price)),
+            _ => order.price = Some(Price::new(price)),
+        }
+        self.release(&order.id);
+
+        let last = self.last_prices.get(&order.symbol).copied();
+        if let Some(fill_price) = last.and_then(|last| trigger_price(&order, last)) {
+            return Ok(self.fill(&mut order, fill_price));
+        }
+
+        self.cash -= reserve;
+        self.reserved.insert(order.id.clone(), reserve);
+        self.open_orders.push(order.clone());
+
+        Ok(vec![
+            WsMessage::OrderUpdate(order),
+            WsMessage::Balance(self.balance()),
+        ])
+    }
+
+    /// Track the last price and fill any resting orders it triggers
+    pub fn on_trade(&mut self, trade: &Trade) -> Vec<WsMessage> {
+        let price = trade.price.as_f64();
+        self.last_prices.insert(trade.symbol.clone(), price);
+
+        let (mut triggered, resting): (Vec<_>, Vec<_>) = std::mem::take(&mut self.open_orders)
+            .into_iter()
+            .partition(|o| o.symbol == trade.symbol && trigger_price(o, price).is_some());
+        self.open_orders = resting;
+
+        let mut messages = Vec::new();
+        for order in &mut triggered {
+            let fill_price = trigger_price(order, price).unwrap_or(price);
+            self.release(&order.id);
+            messages.extend(self.fill(order, fill_price));
+        }
+        messages
+    }
+
+    fn release(&mut self, order_id: &str) {
+        if let Some(amount) = self.reserved.remove(order_id) {
+            self.cash += amount;
+        }
+    }
+
+    /// Execute the whole order at `price` and update position and cash
+    fn fill(&mut self, order: &mut PaperOrder, price: f64) -> Vec<WsMessage> {
+        let qty = order.quantity.as_f64();
+        let signed = match order.side {
+            TradeSide::Buy => qty,
+            TradeSide::Sell => -qty,
+        };
+
+        let now = Utc::now().timestamp_millis();
+        let fee = qty * price * FEE_RATE;
+        self.cash -= signed * price + fee;
+        self.fees_paid += fee;
+        order.filled = order.quantity;
+        order.status = OrderStatus::Filled;
+        order.fill_price = Some(Price::new(price));
+        order.filled_at = Some(now);
+
+        let position = self
+            .positions
+            .entry(order.symbol.clone())
+            .or_insert_with(|| Position {
+                symbol: order.symbol.clone(),
+                quantity: 0.0,
+                entry_price: Price::ZERO,
+                mark_price: Price::ZERO,
+                realized_pnl: 0.0,
+            });
+        let realized_before = position.realized_pnl;
+        apply_fill(position, signed, price);
+        let realized = position.realized_pnl - realized_before;
+        let position = position.clone();
+
+        self.journal.push_back(JournalEntry {
+            order_id: order.id.clone(),
+            symbol: order.symbol.clone(),
+            side: order.side,
+            order_type: order.order_type,
+            quantity: qty,
+            price,
+            realized_pnl: realized,
+            position_after: position.quantity,
+            fee,
+            timestamp: now,
+        });
+        if self.journal.len() > JOURNAL_LIMIT {
+            self.journal.pop_front();
+        }
+        self.roll_day(now);
+        self.realized_today += realized;
+
+        let mut messages = vec![
+            WsMessage::OrderUpdate(order.clone()),
+            WsMessage::Position(position.clone()),
+            WsMessage::Balance(self.balance()),
+        ];
+        messages.extend(self.asset_balance(position.symbol.base()).map(WsMessage::Balance));
+        messages.push(WsMessage::Account(self.account_summary(now)));
+        messages
+    }
+}
+
+impl Default for PaperEngine {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Follow the trade feed and broadcast fills of resting orders
+pub async fn run_fills(state: Arc<AppState>) {
+    let mut rx = state.tx.subscribe();
+    loop {
+        match rx.recv().await {
+            Ok(WsMessage::Trade(trade)) => {
+                let messages = match state.paper.lock() {
+                    Ok(mut engine) => engine.on_trade(&trade),
+                    Err(_) => break,
+                };
+                for msg in messages {
+                    let _ = state.tx.send(msg);
+                }
+            }
+            Ok(_) => {}
+            Err(broadcast::error::RecvError::Lagged(missed)) => state.metrics.record_lag("paper", missed),
+            Err(broadcast::error::RecvError::Closed) => break,
+        }
+    }
+}
+
+/// Sample the account's equity every [`EQUITY_EVERY`] and broadcast it
+/// along with the account totals
+pub async fn run_equity(state: Arc<AppState>) {
+    let mut tick = interval(EQUITY_EVERY);
+    loop {
+        tick.tick().await;
+        let now = Utc::now().timestamp_millis();
+        let (sample, summary) = match state.paper.lock() {
+            Ok(mut engine) => (engine.sample_equity(now), engine.account_summary(now)),
+            Err(_) => break,
+        };
+        let _ = state.tx.send(WsMessage::Equity(sample));
+        let _ = state.tx.send(WsMessage::Account(summary));
+    }
+}
+
+/// Quote cash an order ties up: its notional plus the fee on it
+fn with_fee(notional: f64) -> f64 {
+    notional * (1.0 + FEE_RATE)
+}
+
+/// Execution price if `order` should fill with the market at `last`
+fn trigger_price(order: &PaperOrder, last: f64) -> Option<f64> {
+    match (order.order_type, order.side) {
+        (OrderType::Market, _) => Some(last),
+        (OrderType::Limit, side) => {
+            let limit = order.price?.as_f64();
+            let crossed = match side {
+                TradeSide::Buy => last <= limit,
+                TradeSide::Sell => last >= limit,
+            };
+            crossed.then_some(last)
+        }
+        (OrderType::Stop, side) => {
+            let stop = order.stop_price?.as_f64();
+            let triggered = match side {
+                TradeSide::Buy => last >= stop,
+                TradeSide::Sell => last <= stop,
+            };
+            triggered.then_some(last)
+        }
+    }
+}
+
+/// Fold a signed fill into a position (average entry, realized PnL on reductions)
+fn apply_fill(position: &mut Position, signed_qty: f64, price: f64) {
+    let current = position.quantity;
+    let entry = position.entry_price.as_f64();
+
+    if current == 0.0 || current.signum() == signed_qty.signum() {
+        let total = current.abs() + signed_qty.abs();
+        position.entry_price = Price::new((current.abs() * entry + signed_qty.abs() * price) / total);
+    } else {
+        let closed = current.abs().min(signed_qty.abs());
+        position.realized_pnl += closed * (price - entry) * current.signum();
+        if signed_qty.abs() > current.abs() {
+            // Flipped sides: the remainder opens at the fill price
+            position.entry_price = Price::new(price);
+        }
+    }
+
+    position.quantity = current + signed_qty;
+    position.mark_price = Price::new(price);
+    if position.quantity.abs() < 1e-12 {
+        position.quantity = 0.0;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn market(symbol: &Symbol, side: TradeSide, quantity: f64) -> OrderRequest {
+        OrderRequest {
+            client_id: format!("{:?}-{}", side, quantity),
+            symbol: symbol.clone(),
+            side,
+            order_type: OrderType::Market,
+            price: None,
+            stop_price: None,
+            quantity,
+        }
+    }
+
+    #[test]
+    fn test_fee_counts_against_every_order_type() {
+        let info = SymbolInfo::new(Symbol::new("BTC-USD"), 0.01, 0.001);
+        let symbol = info.symbol.clone();
+        let mut engine = PaperEngine::new();
+        engine.on_trade(&Trade::new(symbol.clone(), 100.0, 1.0, TradeSide::Buy));
+
+        // A limit for exactly the free cash leaves nothing for the fee
+        let limit = |quantity| OrderRequest {
+            order_type: OrderType::Limit,
+            price: Some(80.0),
+            ..market(&symbol, TradeSide::Buy, quantity)
+        };
+        let OrderRejection::InsufficientBalance { required, available } =
+            engine.place(limit(1_250.0), Some(info.clone())).unwrap_err()
+        else {
+            panic!("expected an insufficient balance rejection");
+        };
+        assert!((required - 100_100.0).abs() < 1e-6);
+        assert_eq!(available, STARTING_BALANCE);
+
+        // Sized down to cover the fee it rests, then fills without overdrawing
+        engine.place(limit(1_248.0), Some(info)).unwrap();
+        engine.on_trade(&Trade::new(symbol.clone(), 80.0, 1.0, TradeSide::Sell));
+        assert!(engine.open_orders.is_empty());
+        assert!(engine.cash >= 0.0);
+        assert!(engine.open_orders.is_empty());
+        assert!(engine.cash >= 0.0);
+    }
+
+    #[test]
+    fn test_fees_and_realized_pnl_over_a_day() {
+        let info = SymbolInfo::new(Symbol::new("BTC-USD"), 0.01, 0.001);
+        let symbol = info.symbol.clone();
+        let mut engine = PaperEngine::new();
+
+        engine.on_trade(&Trade::new(symbol.clone(), 100.0, 1.0, TradeSide::Buy));
+        engine.place(market(&symbol, TradeSide::Buy, 2.0), Some(info.clone())).unwrap();
+        engine.on_trade(&Trade::new(symbol.clone(), 110.0, 1.0, TradeSide::Buy));
+        engine.place(market(&symbol, TradeSide::Sell, 2.0), Some(info)).unwrap();
+
+        // 10 bps on 200 bought and 220 sold; 2 × 10 realized before fees
+        let now = Utc::now().timestamp_millis();
+        let today = engine.account_summary(now);
+        assert!((today.fees_paid - 0.42).abs() < 1e-9);
+        assert!((today.realized_pnl_today - 20.0).abs() < 1e-9);
+        assert!((today.equity - (STARTING_BALANCE + 20.0 - 0.42)).abs() < 1e-9);
+        let journal = engine.journal(None, None, None, 10);
+        assert!((journal.iter().map(|e| e.fee).sum::<f64>() - today.fees_paid).abs() < 1e-9);
+        assert!((journal.iter().map(|e| e.realized_pnl).sum::<f64>() - today.realized_pnl_today).abs() < 1e-9);
+
+        // Past midnight the realized counter starts over; fees are lifetime
+        let tomorrow = engine.account_summary(today.day_start + DAY_MS);
+        assert_eq!(tomorrow.day_start, today.day_start + DAY_MS);
+        assert_eq!(tomorrow.realized_pnl_today, 0.0);
+        assert_eq!(tomorrow.fees_paid, today.fees_paid);
+    }
+}
@@ -0,0 +1,136 @@
+//! Supervision of upstream source tasks
+//!
+//! Each source runs as its own task. The supervisor restarts it with
+//! exponential backoff whenever it crashes or ends, and publishes a
+//! `source_status` every second (state, message age, error and restart
+//! counts) so clients can tell a feed outage from trouble on their side.
+
+use std::future::Future;
+use std::sync::atomic::{AtomicI64, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use chrono::Utc;
+use tokio::time::{interval, sleep_until, Instant};
+
+use dash_core::{SourceState, SourceStatus, WsMessage};
+
+use crate::AppState;
+
+/// First restart delay, doubled after every crash up to [`BACKOFF_MAX`]
+const BACKOFF_START: Duration = Duration::from_secs(1);
+const BACKOFF_MAX: Duration = Duration::from_secs(30);
+/// Run time after which a source counts as healthy again and the backoff
+/// starts over
+const STABLE_AFTER: Duration = Duration::from_secs(60);
+
+/// Arrival time of a source's latest message, stamped by whoever consumes
+/// its output
+pub struct SourceMonitor {
+    name: &'static str,
+    /// Wall-clock ms (0 before the first message)
+    last_message_ms: AtomicI64,
+}
+
+impl SourceMonitor {
+    pub fn new(name: &'static str) -> Self {
+        Self {
+            name,
+            last_message_ms: AtomicI64::new(0),
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+
+    pub fn record_message(&self) {
+        self.last_message_ms.store(Utc::now().timestamp_millis(), Ordering::Relaxed);
+    }
+
+    pub fn message_age_ms(&self, now: i64) -> Option<i64> {
+        match self.last_message_ms.load(Ordering::Relaxed) {
+            0 => None,
+            last => Some((now - last).max(0)),
+        }
+    }
+}
+
+/// Health counters for one supervised source
+struct Health {
+    monitor: Arc<SourceMonitor>,
+    /// Silence after which a running source counts as stalled
+    stall: Duration,
+    errors: u32,
+    restarts: u32,
+}
+
+impl Health {
+    fn publish(&self, state: &AppState, running_for: Option<Duration>) {
+        let now = Utc::now().timestamp_millis();
+        let age = self.monitor.message_age_ms(now);
+        let stall_ms = self.stall.as_millis() as i64;
+        let source_state = match running_for {
+            None => SourceState::Restarting,
+            // A fresh start gets the stall period to deliver its first message
+            Some(running) if running >= self.stall && age.is_none_or(|a| a >= stall_ms) => SourceState::Stalled,
+            Some(_) => SourceState::Running,
+        };
+        let _ = state.tx.send(WsMessage::SourceStatus(SourceStatus {
+            name: self.monitor.name().to_string(),
+            state: source_state,
+            message_age_ms: age,
+            errors: self.errors,
+            restarts: self.restarts,
+            timestamp: now,
+        }));
+    }
+}
+
+/// Keep the source built by `start` running, restarting it with backoff,
+/// and report its health every second
+pub async fn supervise<F, Fut>(state: Arc<AppState>, monitor: Arc<SourceMonitor>, stall: Duration, mut start: F)
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = ()> + Send + 'static,
+{
+    let mut health = Health {
+        monitor,
+        stall,
+        errors: 0,
+        restarts: 0,
+    };
+    let name = health.monitor.name();
+    let mut backoff = BACKOFF_START;
+    let mut publish = interval(Duration::from_secs(1));
+
+    loop {
+        let started = Instant::now();
+        let mut task = tokio::spawn(start());
+        let outcome = loop {
+            tokio::select! {
+                outcome = &mut task => break outcome,
+                _ = publish.tick() => health.publish(&state, Some(started.elapsed())),
+            }
+        };
+        health.errors += 1;
+        match outcome {
+            Err(e) if e.is_panic() => tracing::error!("Source {} crashed", name),
+            _ => tracing::error!("Source {} ended", name),
+        }
+
+        if started.elapsed() >= STABLE_AFTER {
+            backoff = BACKOFF_START;
+        }
+        tracing::info!("Restarting source {} in {:?}", name, backoff);
+        let resume = Instant::now() + backoff;
+        loop {
+            tokio::select! {
+                _ = sleep_until(resume) => break,
+                _ = publish.tick() => health.publish(&state, None),
+            }
+        }
+        backoff = (backoff * 2).min(BACKOFF_MAX);
+        health.restarts += 1;
+    }
+}
@@ -0,0 +1,201 @@
+//! 24h ticker statistics rolled up from the trade stream
+//!
+//! Trades fold into 1m buckets kept for a day; high, low, volume and change
+//! are read off the buckets and the touch off the latest book, so every
+//! symbol gets a consistent ticker whether or not its feed sends one.
+
+use std::collections::{BTreeMap, VecDeque};
+use std::sync::Arc;
+use std::time::Duration;
+
+use chrono::Utc;
+use tokio::sync::broadcast;
+use tokio::time::interval;
+
+use dash_core::{Bbo, Candle, CandleInterval, OrderBookSnapshot, Price, Quantity, Symbol, Ticker, WsMessage};
+
+use crate::AppState;
+
+/// Rolling window covered by the ticker stats (ms)
+pub const TICKER_WINDOW_MS: i64 = 24 * 60 * 60_000;
+
+/// 24h rolling stats for one symbol
+pub struct RollingTicker {
+    symbol: Symbol,
+    /// 1m buckets, oldest first
+    buckets: VecDeque<Candle>,
+    /// Best bid and ask (price, quantity) from the latest book or BBO
+    touch: Option<((f64, f64), (f64, f64))>,
+}
+
+impl RollingTicker {
+    /// Start from archived 1m candles so the stats cover a full day at once
+    pub fn new(symbol: Symbol, seed: impl IntoIterator<Item = Candle>) -> Self {
+        Self {
+            symbol,
+            buckets: seed.into_iter().collect(),
+            touch: None,
+        }
+    }
+
+    pub fn record_trade(&mut self, price: f64, quantity: f64, timestamp: i64) {
+        let start = CandleInterval::M1.bucket_start(timestamp);
+        match self.buckets.back_mut() {
+            Some(last) if last.timestamp == start => last.update(price, quantity),
+            Some(last) if last.timestamp > start => {}
+            _ => {
+                let mut bucket = Candle::new(self.symbol.clone(), CandleInterval::M1, start, price);
+                bucket.update(price, quantity);
+                self.buckets.push_back(bucket);
+            }
+        }
+        self.expire(timestamp);
+    }
+
+    pub fn record_book(&mut self, book: &OrderBookSnapshot) {
+        if let (Some(bid), Some(ask)) = (book.bids.first(), book.asks.first()) {
+            self.touch = Some((
+                (bid.price.as_f64(), bid.quantity.as_f64()),
+                (ask.price.as_f64(), ask.quantity.as_f64()),
+            ));
+        }
+    }
+
+    pub fn record_bbo(&mut self, bbo: &Bbo) {
+        self.touch = Some((
+            (bbo.bid.as_f64(), bbo.bid_size.as_f64()),
+            (bbo.ask.as_f64(), bbo.ask_size.as_f64()),
+        ));
+    }
+
+    /// Drop buckets that closed before the window
+    fn expire(&mut self, now: i64) {
+        let minute = CandleInterval::M1.as_millis();
+        while self.buckets.front().is_some_and(|b| b.timestamp + minute <= now - TICKER_WINDOW_MS) {
+            self.buckets.pop_front();
+        }
+    }
+
+    /// Stats over the day ending at `now` (`None` until the first trade)
+    pub fn ticker(&mut self, now: i64) -> Option<Ticker> {
+        self.expire(now);
+        let first = self.buckets.front()?;
+        let last = self.buckets.back()?.close.as_f64();
+        let open = first.open.as_f64();
+        let (mut high, mut low) = (first.high.as_f64(), first.low.as_f64());
+        let (mut volume, mut quote_volume, mut trades) = (0.0, 0.0, 0u64);
+        for bucket in &self.buckets {
+            high = high.max(bucket.high.as_f64());
+            low = low.min(bucket.low.as_f64());
+            volume += bucket.volume.as_f64();
+            quote_volume += bucket.quote_volume;
+            trades += u64::from(bucket.trade_count);
+        }
+        // Without a book yet the touch collapses onto the last print
+        let ((bid, bid_qty), (ask, ask_qty)) = self.touch.unwrap_or(((last, 0.0), (last, 0.0)));
+        let change = last - open;
+
+        Some(Ticker {
+            symbol: self.symbol.clone(),
+            last_price: Price::new(last),
+            bid_price: Price::new(bid),
+            bid_qty: Quantity::new(bid_qty),
+            ask_price: Price::new(ask),
+            ask_qty: Quantity::new(ask_qty),
+            high_24h: Price::new(high),
+            low_24h: Price::new(low),
+            volume_24h: Quantity::new(volume),
+            quote_volume_24h: quote_volume,
+            change_24h: change,
+            change_percent_24h: if open == 0.0 { 0.0 } else { change / open * 100.0 },
+            open_24h: Price::new(open),
+            trade_count_24h: trades,
+            timestamp: now,
+        })
+    }
+}
+
+/// Follow trades and books for every listed symbol and broadcast a ticker
+/// per symbol each second
+pub async fn run_tickers(state: Arc<AppState>) {
+    let day = (TICKER_WINDOW_MS / CandleInterval::M1.as_millis()) as usize;
+    let mut tickers: BTreeMap<Symbol, RollingTicker> = state
+        .symbols
+        .list()
+        .into_iter()
+        .map(|info| {
+            let seed = state.candles.before(&info.symbol, i64::MAX, day);
+            (info.symbol.clone(), RollingTicker::new(info.symbol, seed))
+        })
+        .collect();
+
+    let mut rx = state.tx.subscribe();
+    let mut publish = interval(Duration::from_secs(1));
+    loop {
+        tokio::select! {
+            msg = rx.recv() => match msg {
+                Ok(WsMessage::Trade(trade)) => {
+                    if let Some(ticker) = tickers.get_mut(&trade.symbol) {
+                        ticker.record_trade(
+                            trade.price.as_f64(),
+                            trade.quantity.as_f64(),
+                            trade.timestamp.timestamp_millis(),
+                        );
+                    }
+                }
+                Ok(WsMessage::OrderBook(book)) => {
+                    if let Some(ticker) = tickers.get_mut(&book.symbol) {
+                        ticker.record_book(&book);
+                    }
+                }
+                Ok(WsMessage::Bbo(bbo)) => {
+                    if let Some(ticker) = tickers.get_mut(&bbo.symbol) {
+                        ticker.record_bbo(&bbo);
+                    }
+                }
+                Ok(_) => {}
+                Err(broadcast::error::RecvError::Lagged(missed)) => state.metrics.record_lag("tickers", missed),
+                Err(broadcast::error::RecvError::Closed) => break,
+            },
+            _ = publish.tick() => {
+                let now = Utc::now().timestamp_millis();
+                for ticker in tickers.values_mut() {
+                    if let Some(ticker) = ticker.ticker(now) {
+                        let _ = state.tx.send(WsMessage::Ticker(ticker));
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MINUTE: i64 = 60_000;
+
+    #[test]
+    fn test_old_buckets_leave_the_window() {
+        let mut ticker = RollingTicker::new(Symbol::new("BTC-USD"), []);
+        assert!(ticker.ticker(0).is_none());
+
+        ticker.record_trade(90.0, 2.0, 0);
+        ticker.record_trade(110.0, 1.0, 10 * MINUTE);
+        ticker.record_trade(100.0, 1.0, 10 * MINUTE + 1);
+        let day = ticker.ticker(20 * MINUTE).unwrap();
+        assert_eq!(day.open_24h.as_f64(), 90.0);
+        assert_eq!(day.low_24h.as_f64(), 90.0);
+        assert_eq!(day.volume_24h.as_f64(), 4.0);
+        assert_eq!(day.trade_count_24h, 3);
+
+        // Once its minute closed more than a day ago, the first bucket is gone
+        let later = ticker.ticker(TICKER_WINDOW_MS + MINUTE).unwrap();
+        assert_eq!(later.open_24h.as_f64(), 110.0);
+        assert_eq!(later.low_24h.as_f64(), 100.0);
+        assert_eq!(later.high_24h.as_f64(), 110.0);
+        assert_eq!(later.volume_24h.as_f64(), 2.0);
+        assert_eq!(later.last_price.as_f64(), 100.0);
+        assert!(ticker.ticker(TICKER_WINDOW_MS + 11 * MINUTE).is_none());
+    }
+}
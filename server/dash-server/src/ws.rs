@@ -1,120 +1,373 @@
 //! WebSocket handler for client connections
 
+use std::collections::HashMap;
 use std::sync::Arc;
+use std::time::Duration;
 
 use axum::{
     extract::{
-        ws::{Message, WebSocket},
+        ws::{CloseFrame, Message, WebSocket},
         State, WebSocketUpgrade,
     },
-    response::IntoResponse,
+    http::{header, HeaderMap},
+    response::Response,
 };
+use futures::stream::SplitSink;
 use futures::{SinkExt, StreamExt};
-use tokio::sync::broadcast;
+use tokio::sync::{broadcast, mpsc, watch};
+use tokio::time::{interval, Instant};
+use tracing::Instrument;
+use uuid::Uuid;
 
-use crate::AppState;
-use dash_core::WsMessage;
+use crate::api::ARCHIVE_CANDLES;
+use crate::{analytics, AppState};
+use dash_core::{BookFeed, ClientCommand, CloseReason, Symbol, WsMessage};
 
-/// WebSocket upgrade handler
+/// Shortest and longest book conflation a client may ask for (ms)
+const MIN_CONFLATION_MS: u32 = 50;
+const MAX_CONFLATION_MS: u32 = 5_000;
+
+/// Client silence after which the server pings, and how long the client
+/// then has to answer before it is dropped
+const PING_AFTER_IDLE: Duration = Duration::from_secs(15);
+const PONG_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Updates a conflating client only needs the newest of, keyed by kind and
+/// symbol
+fn conflation_key(msg: &WsMessage) -> Option<(&'static str, Symbol)> {
+    match msg {
+        WsMessage::OrderBook(book) => Some((msg.kind(), book.symbol.clone())),
+        WsMessage::Depth(depth) => Some((msg.kind(), depth.symbol.clone())),
+        WsMessage::Bbo(bbo) => Some((msg.kind(), bbo.symbol.clone())),
+        _ => None,
+    }
+}
+
+/// Full book updates a BBO-only client goes without
+fn is_full_book(msg: &WsMessage) -> bool {
+    matches!(msg, WsMessage::OrderBook(_) | WsMessage::Depth(_))
+}
+
+/// WebSocket upgrade handler; browsers on origins outside the allow-list
+/// are refused (CORS doesn't cover WebSocket upgrades). The refusal is a
+/// close code rather than a 403, since browsers hide the status of a failed
+/// upgrade from the page.
 pub async fn ws_handler(
     ws: WebSocketUpgrade,
+    headers: HeaderMap,
     State(state): State<Arc<AppState>>,
-) -> impl IntoResponse {
+) -> Response {
+    let origin = headers.get(header::ORIGIN).and_then(|o| o.to_str().ok());
+    if !state.origins.allows(origin) {
+        tracing::warn!("Refused WebSocket connection from origin {:?}", origin);
+        return ws.on_upgrade(|socket| async move {
+            let (mut sender, _) = socket.split();
+            close(&mut sender, CloseReason::AuthFailed).await;
+        });
+    }
     ws.on_upgrade(|socket| handle_socket(socket, state))
 }
 
-/// Handle individual WebSocket connection
+/// Send a close frame carrying `reason`'s application code
+async fn close(sender: &mut SplitSink<WebSocket, Message>, reason: CloseReason) {
+    tracing::info!("Closing connection: {}", reason.label());
+    let frame = CloseFrame {
+        code: reason.code(),
+        reason: reason.label().into(),
+    };
+    let _ = sender.send(Message::Close(Some(frame))).await;
+}
+
+/// Handle individual WebSocket connection; everything logged for it carries
+/// its connection id, which the client is told in `hello`
 async fn handle_socket(socket: WebSocket, state: Arc<AppState>) {
+    let connection_id = Uuid::new_v4();
+    let span = tracing::info_span!("ws", connection = %connection_id);
+    serve_socket(socket, state, connection_id).instrument(span).await;
+}
+
+async fn serve_socket(socket: WebSocket, state: Arc<AppState>, connection_id: Uuid) {
     let (mut sender, mut receiver) = socket.split();
 
     // Subscribe to broadcast channel
     let mut rx = state.tx.subscribe();
+    // Replies meant only for this client (snapshot, rejections)
+    let (direct_tx, mut direct_rx) = mpsc::unbounded_channel::<WsMessage>();
 
     tracing::info!("New WebSocket client connected");
+    let _ = direct_tx.send(WsMessage::Hello {
+        connection_id: connection_id.to_string(),
+    });
 
-    // Spawn task to forward broadcast messages to client
-    let send_task = tokio::spawn(async move {
-        while let Ok(msg) = rx.recv().await {
-            match serde_json::to_string(&msg) {
-                Ok(json) => {
-                    if sender.send(Message::Text(json)).await.is_err() {
-                        break;
+    let _ = direct_tx.send(WsMessage::FeedStatus(state.feed.borrow().clone()));
+
+    if let Ok(mut engine) = state.paper.lock() {
+        let _ = direct_tx.send(WsMessage::Portfolio(engine.snapshot()));
+        let _ = direct_tx.send(WsMessage::Account(engine.account_summary(chrono::Utc::now().timestamp_millis())));
+    }
+
+    // Book and depth throttle requested by this client
+    let (conflation_tx, mut conflation_rx) = watch::channel(None::<Duration>);
+    // Book detail requested by this client
+    let (book_feed_tx, mut book_feed_rx) = watch::channel(BookFeed::Full);
+    // Reason the receive side wants the connection closed
+    let (close_tx, mut close_rx) = mpsc::unbounded_channel::<CloseReason>();
+    let mut shutdown = state.shutdown.subscribe();
+    // When the client last sent anything; any frame proves it alive
+    let (activity_tx, activity_rx) = watch::channel(Instant::now());
+
+    // Spawn task to forward broadcast and direct messages to client
+    let send_state = state.clone();
+    let send_task = tokio::spawn(
+        async move {
+            let mut conflation = None;
+            let mut book_feed = BookFeed::Full;
+            let mut flush = interval(Duration::from_secs(1));
+            // Newest held-back update per (kind, symbol)
+            let mut held: HashMap<(&'static str, Symbol), WsMessage> = HashMap::new();
+            let mut idle_check = interval(Duration::from_secs(1));
+            // Nonce and send time of the last ping
+            let mut pinged: Option<(u64, Instant)> = None;
+            let mut nonce = 0u64;
+            loop {
+                let messages: Vec<WsMessage> = tokio::select! {
+                    msg = rx.recv() => match msg {
+                        Ok(msg) if book_feed == BookFeed::Bbo && is_full_book(&msg) => continue,
+                        Ok(msg) => match conflation_key(&msg).filter(|_| conflation.is_some()) {
+                            Some(key) => {
+                                held.insert(key, msg);
+                                continue;
+                            }
+                            None => vec![msg],
+                        },
+                        // Missed messages can't be recovered; make the client resync
+                        Err(broadcast::error::RecvError::Lagged(missed)) => {
+                            tracing::warn!("Client lagged {} messages behind", missed);
+                            send_state.metrics.record_client_lag(&connection_id.to_string(), missed);
+                            return close(&mut sender, CloseReason::SlowConsumer).await;
+                        }
+                        Err(broadcast::error::RecvError::Closed) => {
+                            return close(&mut sender, CloseReason::ServerShutdown).await;
+                        }
+                    },
+                    Some(reason) = close_rx.recv() => return close(&mut sender, reason).await,
+                    _ = idle_check.tick() => {
+                        let last_seen = *activity_rx.borrow();
+                        let idle = last_seen.elapsed();
+                        if idle >= PING_AFTER_IDLE + PONG_TIMEOUT {
+                            tracing::info!("No answer to ping {:?} in {:?}", pinged.map(|(n, _)| n), PONG_TIMEOUT);
+                            return close(&mut sender, CloseReason::IdleTimeout).await;
+                        }
+                        // One ping per quiet spell
+                        if idle < PING_AFTER_IDLE || pinged.is_some_and(|(_, at)| at > last_seen) {
+                            continue;
+                        }
+                        nonce += 1;
+                        pinged = Some((nonce, Instant::now()));
+                        vec![WsMessage::Ping { nonce, timestamp: chrono::Utc::now().timestamp_millis() }]
+                    }
+                    Ok(()) = shutdown.changed() => {
+                        if *shutdown.borrow_and_update() {
+                            return close(&mut sender, CloseReason::ServerShutdown).await;
+                        }
+                        continue;
+                    }
+                    Some(msg) = direct_rx.recv() => vec![msg],
+                    Ok(()) = conflation_rx.changed() => {
+                        conflation = *conflation_rx.borrow_and_update();
+                        if let Some(period) = conflation {
+                            flush = interval(period);
+                        }
+                        held.drain().map(|(_, msg)| msg).collect()
+                    }
+                    _ = flush.tick(), if conflation.is_some() => held.drain().map(|(_, msg)| msg).collect(),
+                    Ok(()) = book_feed_rx.changed() => {
+                        book_feed = *book_feed_rx.borrow_and_update();
+                        if book_feed == BookFeed::Bbo {
+                            held.retain(|_, msg| !is_full_book(msg));
+                        }
+                        continue;
+                    }
+                };
+
+                for msg in messages {
+                    match serde_json::to_string(&msg) {
+                        Ok(json) => {
+                            if sender.send(Message::Text(json)).await.is_err() {
+                                return;
+                            }
+                        }
+                        Err(e) => {
+                            tracing::error!("Failed to serialize message: {}", e);
+                        }
                     }
-                }
-                Err(e) => {
-                    tracing::error!("Failed to serialize message: {}", e);
                 }
             }
         }
-    });
+        .in_current_span(),
+    );
 
     // Spawn task to handle incoming messages from client
-    let recv_task = tokio::spawn(async move {
-        while let Some(Ok(msg)) = receiver.next().await {
-            match msg {
-                Message::Text(text) => {
-                    // Handle client messages (e.g., subscription requests)
-                    handle_client_message(&text).await;
-                }
-                Message::Ping(data) => {
-                    tracing::trace!("Received ping");
-                    // Pong is sent automatically by axum
-                }
-                Message::Close(_) => {
-                    tracing::info!("Client initiated close");
-                    break;
+    let recv_task = tokio::spawn(
+        async move {
+            while let Some(Ok(msg)) = receiver.next().await {
+                activity_tx.send_replace(Instant::now());
+                match msg {
+                    Message::Text(text) => {
+                        // Handle client commands (subscriptions, paper orders)
+                        if let Err(reason) =
+                            handle_client_message(&state, &direct_tx, &conflation_tx, &book_feed_tx, &text).await
+                        {
+                            let _ = close_tx.send(reason);
+                            break;
+                        }
+                    }
+                    Message::Binary(_) => {
+                        tracing::debug!("Unexpected binary frame");
+                        let _ = close_tx.send(CloseReason::ProtocolViolation);
+                        break;
+                    }
+                    Message::Ping(_) => {
+                        tracing::trace!("Received ping");
+                        // Pong is sent automatically by axum
+                    }
+                    Message::Close(_) => {
+                        tracing::info!("Client initiated close");
+                        break;
+                    }
+                    _ => {}
                 }
-                _ => {}
             }
         }
-    });
+        .in_current_span(),
+    );
 
-    // Wait for either task to complete
+    // Wait for the send side to finish: it ends on a dropped link, or after
+    // writing the close frame when either side asked to close. A client that
+    // just goes away ends the receive side first, which takes the send side
+    // with it.
+    tokio::pin!(send_task);
     tokio::select! {
-        _ = send_task => {
+        _ = &mut send_task => {
             tracing::info!("Send task completed");
         }
         _ = recv_task => {
             tracing::info!("Receive task completed");
+            // Give a pending close frame the chance to go out
+            let _ = tokio::time::timeout(Duration::from_secs(1), &mut send_task).await;
         }
     }
 
     tracing::info!("WebSocket client disconnected");
 }
 
-/// Handle messages from client
-async fn handle_client_message(text: &str) {
-    // Parse client commands (e.g., subscribe to specific symbols)
-    #[derive(serde::Deserialize)]
-    #[serde(tag = "type")]
-    enum ClientMessage {
-        #[serde(rename = "subscribe")]
-        Subscribe { symbol: String },
-        #[serde(rename = "unsubscribe")]
-        Unsubscribe { symbol: String },
-        #[serde(rename = "ping")]
-        Ping,
-    }
-
-    match serde_json::from_str::<ClientMessage>(text) {
-        Ok(ClientMessage::Subscribe { symbol }) => {
+/// Handle commands from a client; text that isn't JSON at all is a protocol
+/// violation, while well-formed commands this server doesn't know are
+/// ignored so newer clients keep working
+async fn handle_client_message(
+    state: &AppState,
+    direct_tx: &mpsc::UnboundedSender<WsMessage>,
+    conflation_tx: &watch::Sender<Option<Duration>>,
+    book_feed_tx: &watch::Sender<BookFeed>,
+    text: &str,
+) -> Result<(), CloseReason> {
+    match serde_json::from_str::<ClientCommand>(text) {
+        Ok(ClientCommand::Subscribe { symbol }) => {
             tracing::info!("Client subscribed to {}", symbol);
             // TODO: Implement subscription filtering
         }
-        Ok(ClientMessage::Unsubscribe { symbol }) => {
+        Ok(ClientCommand::Unsubscribe { symbol }) => {
             tracing::info!("Client unsubscribed from {}", symbol);
         }
-        Ok(ClientMessage::Ping) => {
+        Ok(ClientCommand::Ping) => {
             tracing::trace!("Client ping");
         }
+        Ok(ClientCommand::Pong { nonce }) => {
+            tracing::trace!("Client answered ping {}", nonce);
+        }
+        Ok(ClientCommand::PlaceOrder { order }) => {
+            let client_id = order.client_id.clone();
+            let info = state.symbols.get(&order.symbol);
+            let result = match state.paper.lock() {
+                Ok(mut engine) => engine.place(order, info),
+                Err(_) => return Ok(()),
+            };
+
+            match result {
+                Ok(updates) => {
+                    for msg in updates {
+                        let _ = state.tx.send(msg);
+                    }
+                }
+                Err(reason) => {
+                    tracing::debug!("Rejected order {}: {}", client_id, reason);
+                    let _ = direct_tx.send(WsMessage::OrderRejected { client_id, reason });
+                }
+            }
+        }
+        Ok(ClientCommand::CancelOrder { order_id }) => {
+            let updates = match state.paper.lock() {
+                Ok(mut engine) => engine.cancel(&order_id),
+                Err(_) => return Ok(()),
+            };
+            for msg in updates {
+                let _ = state.tx.send(msg);
+            }
+        }
+        Ok(ClientCommand::AmendOrder { order_id, price }) => {
+            let result = match state.paper.lock() {
+                Ok(mut engine) => {
+                    let info = engine.symbol_of(&order_id).and_then(|symbol| state.symbols.get(&symbol));
+                    engine.amend(&order_id, price, info)
+                }
+                Err(_) => return Ok(()),
+            };
+
+            match result {
+                Ok(updates) => {
+                    for msg in updates {
+                        let _ = state.tx.send(msg);
+                    }
+                }
+                Err(reason) => {
+                    tracing::debug!("Rejected amend of {}: {}", order_id, reason);
+                    let _ = direct_tx.send(WsMessage::OrderRejected {
+                        client_id: order_id,
+                        reason,
+                    });
+                }
+            }
+        }
+        Ok(ClientCommand::TrackSession { anchor }) => {
+            analytics::track_session(state, anchor);
+        }
+        Ok(ClientCommand::RequestSnapshot { symbol }) => {
+            let _ = direct_tx.send(WsMessage::Snapshot(state.snapshots.get(&symbol)));
+        }
+        Ok(ClientCommand::RequestCandleHistory { symbol, before, limit }) => {
+            let limit = limit.min(ARCHIVE_CANDLES);
+            let candles = state.candles.before(&symbol, before.unwrap_or(i64::MAX), limit);
+            let _ = direct_tx.send(WsMessage::CandleHistory { symbol, before, limit, candles });
+        }
+        Ok(ClientCommand::ListSymbols) => {
+            let _ = direct_tx.send(WsMessage::Symbols(state.symbols.list()));
+        }
+        Ok(ClientCommand::SetConflation { interval_ms }) => {
+            let period = interval_ms.map(|ms| Duration::from_millis(ms.clamp(MIN_CONFLATION_MS, MAX_CONFLATION_MS).into()));
+            tracing::debug!("Client conflation set to {:?}", period);
+            let _ = conflation_tx.send(period);
+        }
+        Ok(ClientCommand::SetBookFeed { feed }) => {
+            tracing::debug!("Client book feed set to {:?}", feed);
+            let _ = book_feed_tx.send(feed);
+        }
+        Err(_) if serde_json::from_str::<serde_json::Value>(text).is_err() => {
+            tracing::debug!("Unparseable client message: {}", text);
+            return Err(CloseReason::ProtocolViolation);
+        }
         Err(_) => {
             tracing::trace!("Unknown client message: {}", text);
         }
     }
-}
-
-/// Broadcast a message to all connected clients
-pub async fn broadcast(tx: &broadcast::Sender<WsMessage>, msg: WsMessage) {
-    // Ignore send errors (no receivers)
-    let _ = tx.send(msg);
+    Ok(())
 }